@@ -1,21 +1,38 @@
+use chrono::{Local, NaiveDate, TimeZone};
 use sevenz_rust::SevenZReader;
 use std::fs::{self, File};
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use tauri::{AppHandle, Emitter};
 use unrar::Archive;
 use walkdir::WalkDir;
 use zip::ZipArchive;
 
+use crate::detection;
+use crate::fs_ops::{self, FileOperationKind};
+use crate::i18n::{t, tn};
+use crate::mod_service::{find_companions, COMPANION_EXTENSIONS};
+use crate::notifications::{self, NotificationKind};
+use crate::progress::ProgressEmitter;
+use crate::sorting::natural_cmp;
+use crate::thumbnail_service::{find_preview_image, pick_preview_image};
+use crate::types::MetadataSuggestion;
+
 const SUPPORTED_MOD_EXTENSIONS: &[&str] = &[".pak"];
 const MAX_ARCHIVE_SIZE: u64 = 5 * 1024 * 1024 * 1024; // 5GB limit
+const NESTED_ARCHIVE_EXTENSIONS: &[&str] = &["zip", "rar", "7z"];
+const ENTRY_BUFFER_SIZE: usize = 256 * 1024;
 
 #[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ExtractionProgress {
     pub current_file: String,
     pub current: usize,
     pub total: usize,
     pub bytes_extracted: u64,
+    /// Uncompressed size of the whole archive, from its headers
+    pub bytes_total: u64,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -24,23 +41,114 @@ pub struct DetectedMod {
     pub pak_file: String,
     pub associated_files: Vec<String>,
     pub size: u64,
+    /// Preview image shipped next to the pak, adopted as the thumbnail on install
+    pub preview_image: Option<String>,
+    /// Category and character auto-detection would give it, with confidence
+    pub suggested_metadata: MetadataSuggestion,
+    /// Why it can't be installed: the pak or a companion came out of the
+    /// archive damaged
+    pub invalid_reason: Option<String>,
+}
+
+/// Why one archive entry failed. The rest of the archive is still extracted.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "code", rename_all = "camelCase")]
+pub enum ArchiveEntryError {
+    /// The extracted bytes don't match the CRC32 the archive declares for the
+    /// entry, or the entry couldn't be decompressed at all
+    CorruptArchiveEntry { name: String },
+}
+
+impl std::fmt::Display for ArchiveEntryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CorruptArchiveEntry { name } => {
+                write!(f, "{}", t("error.corruptArchiveEntry", &[("name", name)]))
+            }
+        }
+    }
+}
+
+/// What extracting an archive wrote
+#[derive(Debug, Default)]
+pub struct Extraction {
+    /// Paks written, including any from failed entries
+    pub mod_files: Vec<PathBuf>,
+    /// Entries that were written but failed, by where they were written
+    pub failed_entries: Vec<(PathBuf, ArchiveEntryError)>,
+}
+
+/// One file or folder inside an archive, listed without extracting it
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveTreeNode {
+    pub name: String,
+    /// Path inside the archive, '/'-separated
+    pub path: String,
+    /// Uncompressed size; for folders, the total of everything below
+    pub size: u64,
+    pub is_directory: bool,
+    pub children: Vec<ArchiveTreeNode>,
+}
+
+/// What an archive holds, read from its central directory / header / listing
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivePreview {
+    pub entries: Vec<ArchiveTreeNode>,
+    pub file_count: usize,
+    pub total_size: u64,
+    /// Paks grouped with their companions and preview image. Paths are
+    /// inside the archive, so they can't be installed from directly.
+    pub detected_mods: Vec<DetectedMod>,
+    /// True when the listing may not show everything: some entries couldn't
+    /// be read, or the archive contains nested archives whose contents are
+    /// only known after extraction
+    pub preview_partial: bool,
 }
 
-pub struct ArchiveExtractor {
-    app_handle: AppHandle,
+/// Extracts archives, reporting progress to `on_progress` (throttled, plus
+/// one final update per archive). Commands pass a closure that emits
+/// `extraction-progress`; anything else (tests, background jobs) can pass its
+/// own or a no-op.
+pub struct ArchiveExtractor<F: Fn(ExtractionProgress)> {
+    progress: ProgressEmitter<F>,
 }
 
-impl ArchiveExtractor {
-    pub fn new(app_handle: AppHandle) -> Self {
-        Self { app_handle }
+/// Progress callback that forwards to the frontend's `extraction-progress` listener
+pub fn progress_emitter(app: AppHandle) -> impl Fn(ExtractionProgress) {
+    move |progress| {
+        if let Err(e) = app.emit("extraction-progress", progress) {
+            log::error!("Failed to emit progress: {}", e);
+        }
+    }
+}
+
+impl<F: Fn(ExtractionProgress)> ArchiveExtractor<F> {
+    pub fn new(on_progress: F) -> Self {
+        Self {
+            progress: ProgressEmitter::new(on_progress),
+        }
+    }
+
+    /// Extract any supported archive, picking the format from its extension
+    pub fn extract(&self, archive_path: &Path, dest_dir: &Path) -> Result<Extraction, String> {
+        let extension = archive_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .ok_or("Invalid archive file")?;
+
+        match extension.as_str() {
+            "zip" => self.extract_zip(archive_path, dest_dir),
+            "rar" => self.extract_rar(archive_path, dest_dir),
+            "7z" => self.extract_7z(archive_path, dest_dir),
+            _ => Err(format!("Unsupported archive format: {}", extension)),
+        }
     }
 
     /// Extract a ZIP archive to the destination directory
-    pub fn extract_zip(
-        &self,
-        archive_path: &Path,
-        dest_dir: &Path,
-    ) -> Result<Vec<PathBuf>, String> {
+    pub fn extract_zip(&self, archive_path: &Path, dest_dir: &Path) -> Result<Extraction, String> {
         // Validate archive size
         let metadata = fs::metadata(archive_path)
             .map_err(|e| format!("Failed to read archive metadata: {}", e))?;
@@ -60,8 +168,12 @@ impl ArchiveExtractor {
             ZipArchive::new(file).map_err(|e| format!("Failed to read ZIP archive: {}", e))?;
 
         let total_files = archive.len();
-        let mut extracted_mods = Vec::new();
+        let bytes_total: u64 = (0..total_files)
+            .filter_map(|i| archive.by_index_raw(i).ok().map(|f| f.size()))
+            .sum();
+        let mut extraction = Extraction::default();
         let mut bytes_extracted = 0u64;
+        let mut last_file = String::new();
 
         // Ensure destination directory exists
         fs::create_dir_all(dest_dir)
@@ -87,14 +199,6 @@ impl ArchiveExtractor {
                 continue;
             }
 
-            // Send progress update
-            self.emit_progress(ExtractionProgress {
-                current_file: file.name().to_string(),
-                current: i + 1,
-                total: total_files,
-                bytes_extracted,
-            })?;
-
             if file.is_dir() {
                 // Create directory
                 fs::create_dir_all(&outpath)
@@ -110,27 +214,63 @@ impl ArchiveExtractor {
                 let mut outfile =
                     File::create(&outpath).map_err(|e| format!("Failed to create file: {}", e))?;
 
-                let bytes = io::copy(&mut file, &mut outfile)
+                let (bytes, crc) = copy_entry(&mut file, &mut outfile)
                     .map_err(|e| format!("Failed to extract file: {}", e))?;
+                drop(outfile);
+                keep_modified_time(
+                    &outpath,
+                    file.last_modified().and_then(|at| {
+                        let (date, time): (u16, u16) = at.into();
+                        dos_time(date, time)
+                    }),
+                );
+                if !entry_intact(crc, Some(file.crc32())) {
+                    log::warn!("Corrupt archive entry: {}", file.name());
+                    extraction.failed_entries.push((
+                        outpath.clone(),
+                        ArchiveEntryError::CorruptArchiveEntry {
+                            name: file.name().to_string(),
+                        },
+                    ));
+                }
+                fs_ops::record(
+                    FileOperationKind::Extract,
+                    archive_path,
+                    Some(&outpath),
+                    Some(bytes),
+                );
 
                 bytes_extracted += bytes;
 
                 // Track mod files
                 if self.is_mod_file(&outpath) {
-                    extracted_mods.push(outpath);
+                    extraction.mod_files.push(outpath);
                 }
             }
+
+            last_file = file.name().to_string();
+            self.emit_progress(ExtractionProgress {
+                current_file: last_file.clone(),
+                current: i + 1,
+                total: total_files,
+                bytes_extracted,
+                bytes_total,
+            });
         }
 
-        Ok(extracted_mods)
+        self.finish_progress(ExtractionProgress {
+            current_file: last_file,
+            current: total_files,
+            total: total_files,
+            bytes_extracted,
+            bytes_total,
+        });
+        Ok(extraction)
     }
 
-    /// Extract a RAR archive using unrar
-    pub fn extract_rar(
-        &self,
-        archive_path: &Path,
-        dest_dir: &Path,
-    ) -> Result<Vec<PathBuf>, String> {
+    /// Extract a RAR archive using unrar. unrar checks each entry's CRC
+    /// itself and fails the whole archive on a damaged one.
+    pub fn extract_rar(&self, archive_path: &Path, dest_dir: &Path) -> Result<Extraction, String> {
         // Validate archive size
         let metadata = fs::metadata(archive_path)
             .map_err(|e| format!("Failed to read archive metadata: {}", e))?;
@@ -146,14 +286,25 @@ impl ArchiveExtractor {
         fs::create_dir_all(dest_dir)
             .map_err(|e| format!("Failed to create destination directory: {}", e))?;
 
+        // unrar only reports entries as it reaches them; list the headers
+        // first for the totals
+        let (total_files, bytes_total) = match list_rar(archive_path) {
+            Ok(listing) => (
+                listing.entries.len(),
+                listing.entries.iter().map(|e| e.size).sum::<u64>(),
+            ),
+            Err(_) => (0, 0),
+        };
+
         // Open RAR archive
         let mut archive = Archive::new(archive_path)
             .open_for_processing()
             .map_err(|e| format!("Failed to open RAR archive: {}", e))?;
 
-        let mut extracted_mods = Vec::new();
+        let mut extraction = Extraction::default();
         let mut bytes_extracted = 0u64;
         let mut file_count = 0usize;
+        let mut last_file = String::new();
 
         // Process all entries
         while let Some(header) = archive
@@ -184,16 +335,6 @@ impl ArchiveExtractor {
                 continue;
             }
 
-            // Send progress update
-            if let Err(e) = self.emit_progress(ExtractionProgress {
-                current_file: entry_name.clone(),
-                current: file_count,
-                total: file_count, // unrar doesn't provide total count upfront
-                bytes_extracted,
-            }) {
-                log::error!("Failed to emit progress: {}", e);
-            }
-
             // Ensure parent directory exists
             if let Some(parent) = outpath.parent() {
                 fs::create_dir_all(parent)
@@ -201,26 +342,53 @@ impl ArchiveExtractor {
             }
 
             // Extract the file
+            let file_time = header.entry().file_time;
             archive = header
                 .extract_to(&outpath)
                 .map_err(|e| format!("Failed to extract file: {}", e))?;
+            keep_modified_time(
+                &outpath,
+                dos_time((file_time >> 16) as u16, file_time as u16),
+            );
 
             let file_size = outpath.metadata().map(|m| m.len()).unwrap_or(0);
+            fs_ops::record(
+                FileOperationKind::Extract,
+                archive_path,
+                Some(&outpath),
+                Some(file_size),
+            );
             bytes_extracted += file_size;
 
             // Check if this is a .pak file
             if let Some(ext) = outpath.extension() {
                 if ext.eq_ignore_ascii_case("pak") {
-                    extracted_mods.push(outpath);
+                    extraction.mod_files.push(outpath);
                 }
             }
+
+            last_file = entry_name;
+            self.emit_progress(ExtractionProgress {
+                current_file: last_file.clone(),
+                current: file_count,
+                total: total_files.max(file_count),
+                bytes_extracted,
+                bytes_total,
+            });
         }
 
-        Ok(extracted_mods)
+        self.finish_progress(ExtractionProgress {
+            current_file: last_file,
+            current: file_count,
+            total: total_files.max(file_count),
+            bytes_extracted,
+            bytes_total,
+        });
+        Ok(extraction)
     }
 
     /// Extract a 7z archive
-    pub fn extract_7z(&self, archive_path: &Path, dest_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    pub fn extract_7z(&self, archive_path: &Path, dest_dir: &Path) -> Result<Extraction, String> {
         // Validate archive size
         let metadata = fs::metadata(archive_path)
             .map_err(|e| format!("Failed to read archive metadata: {}", e))?;
@@ -250,15 +418,17 @@ impl ArchiveExtractor {
         fs::create_dir_all(dest_dir)
             .map_err(|e| format!("Failed to create destination directory: {}", e))?;
 
-        let mut extracted_mods = Vec::new();
+        let mut extraction = Extraction::default();
         let mut bytes_extracted = 0u64;
 
         // Get archive information
         let archive = reader.archive();
         let total_files = archive.files.len();
+        let bytes_total: u64 = archive.files.iter().map(|f| f.size()).sum();
 
         // Extract all files
         let mut current_index = 0;
+        let mut last_file = String::new();
         reader
             .for_each_entries(|entry, reader| {
                 current_index += 1;
@@ -280,16 +450,6 @@ impl ArchiveExtractor {
                     return Ok(true);
                 }
 
-                // Send progress update
-                if let Err(e) = self.emit_progress(ExtractionProgress {
-                    current_file: file_name.to_string(),
-                    current: current_index,
-                    total: total_files,
-                    bytes_extracted,
-                }) {
-                    log::error!("Failed to emit progress: {}", e);
-                }
-
                 // Ensure parent directory exists
                 if let Some(parent) = outpath.parent() {
                     if let Err(e) = fs::create_dir_all(parent) {
@@ -313,13 +473,35 @@ impl ArchiveExtractor {
                     }
                 };
 
-                match io::copy(reader, &mut outfile) {
-                    Ok(bytes) => {
+                match copy_entry(reader, &mut outfile) {
+                    Ok((bytes, crc)) => {
+                        drop(outfile);
+                        keep_modified_time(
+                            &outpath,
+                            entry
+                                .has_last_modified_date
+                                .then(|| entry.last_modified_date().into()),
+                        );
+                        if !entry_intact(crc, entry.has_crc.then_some(entry.crc as u32)) {
+                            log::warn!("Corrupt archive entry: {}", file_name);
+                            extraction.failed_entries.push((
+                                outpath.clone(),
+                                ArchiveEntryError::CorruptArchiveEntry {
+                                    name: file_name.to_string(),
+                                },
+                            ));
+                        }
+                        fs_ops::record(
+                            FileOperationKind::Extract,
+                            archive_path,
+                            Some(&outpath),
+                            Some(bytes),
+                        );
                         bytes_extracted += bytes;
 
                         // Track mod files
                         if self.is_mod_file(&outpath) {
-                            extracted_mods.push(outpath);
+                            extraction.mod_files.push(outpath);
                         }
                     }
                     Err(e) => {
@@ -331,11 +513,27 @@ impl ArchiveExtractor {
                     }
                 }
 
+                last_file = file_name.to_string();
+                self.emit_progress(ExtractionProgress {
+                    current_file: last_file.clone(),
+                    current: current_index,
+                    total: total_files,
+                    bytes_extracted,
+                    bytes_total,
+                });
+
                 Ok(true)
             })
             .map_err(|e| format!("Extraction failed: {}", e))?;
 
-        Ok(extracted_mods)
+        self.finish_progress(ExtractionProgress {
+            current_file: last_file,
+            current: total_files,
+            total: total_files,
+            bytes_extracted,
+            bytes_total,
+        });
+        Ok(extraction)
     }
 
     /// Check if a file is a valid mod file
@@ -348,11 +546,79 @@ impl ArchiveExtractor {
             .unwrap_or(false)
     }
 
-    /// Emit extraction progress to frontend
-    fn emit_progress(&self, progress: ExtractionProgress) -> Result<(), String> {
-        self.app_handle
-            .emit("extraction-progress", progress)
-            .map_err(|e| format!("Failed to emit progress: {}", e))
+    /// Report extraction progress to the caller's callback, at most every
+    /// 100 ms or 1% of the archive's bytes
+    fn emit_progress(&self, progress: ExtractionProgress) {
+        self.progress
+            .emit(progress.bytes_extracted, progress.bytes_total, progress);
+    }
+
+    /// Report the end of an archive; never throttled
+    fn finish_progress(&self, progress: ExtractionProgress) {
+        self.progress.finish(progress);
+    }
+}
+
+/// Copy an entry's bytes out, computing their CRC32 on the way. Returns the
+/// bytes written and the CRC, or no CRC if the entry stopped reading partway
+/// (a damaged entry). Only a failed write is an error.
+fn copy_entry<R: io::Read + ?Sized>(
+    reader: &mut R,
+    writer: &mut impl io::Write,
+) -> io::Result<(u64, Option<u32>)> {
+    let mut hasher = crc32fast::Hasher::new();
+    let mut buffer = vec![0u8; ENTRY_BUFFER_SIZE];
+    let mut bytes = 0u64;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                log::warn!("Archive entry stopped reading after {} bytes: {}", bytes, e);
+                return Ok((bytes, None));
+            }
+        };
+        writer.write_all(&buffer[..read])?;
+        hasher.update(&buffer[..read]);
+        bytes += read as u64;
+    }
+    Ok((bytes, Some(hasher.finalize())))
+}
+
+/// Whether an entry came out whole: it read to the end and, where the
+/// archive declares a CRC, matches it
+fn entry_intact(crc: Option<u32>, declared: Option<u32>) -> bool {
+    match (crc, declared) {
+        (None, _) => false,
+        (Some(crc), Some(declared)) => crc == declared,
+        (Some(_), None) => true,
+    }
+}
+
+/// An MS-DOS date and time, as zip and rar headers store them: local time,
+/// two-second resolution. None for the zeroed fields of entries without one.
+fn dos_time(date: u16, time: u16) -> Option<SystemTime> {
+    let at = NaiveDate::from_ymd_opt(
+        1980 + i32::from(date >> 9),
+        u32::from((date >> 5) & 0x0f),
+        u32::from(date & 0x1f),
+    )?
+    .and_hms_opt(
+        u32::from(time >> 11),
+        u32::from((time >> 5) & 0x3f),
+        u32::from(time & 0x1f) * 2,
+    )?;
+    Some(Local.from_local_datetime(&at).earliest()?.into())
+}
+
+/// Give an extracted file the modification time its entry carries, so it
+/// keeps the mod's build date. Entries without one keep the extraction time.
+fn keep_modified_time(path: &Path, modified: Option<SystemTime>) {
+    if let Some(modified) = modified {
+        if let Err(e) = fs_ops::set_modified(path, modified) {
+            log::debug!("Failed to set modification time of {:?}: {}", path, e);
+        }
     }
 }
 
@@ -369,25 +635,19 @@ pub async fn extract_archive(
     let archive_path = PathBuf::from(archive_path);
     let dest_dir = PathBuf::from(dest_dir);
 
-    let extractor = ArchiveExtractor::new(app);
-
-    // Determine archive type by extension
-    let extension = archive_path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| ext.to_lowercase())
-        .ok_or("Invalid archive file")?;
+    let extractor = ArchiveExtractor::new(progress_emitter(app));
+    let extraction = extractor.extract(&archive_path, &dest_dir)?;
 
-    let extracted_files = match extension.as_str() {
-        "zip" => extractor.extract_zip(&archive_path, &dest_dir)?,
-        "rar" => extractor.extract_rar(&archive_path, &dest_dir)?,
-        "7z" => extractor.extract_7z(&archive_path, &dest_dir)?,
-        _ => return Err(format!("Unsupported archive format: {}", extension)),
-    };
-
-    // Convert PathBuf to String for serialization
-    let file_paths = extracted_files
+    // Damaged paks are left out; they would only crash the game
+    let file_paths = extraction
+        .mod_files
         .into_iter()
+        .filter(|p| {
+            !extraction
+                .failed_entries
+                .iter()
+                .any(|(failed, _)| failed == p)
+        })
         .map(|p| p.to_string_lossy().to_string())
         .collect();
 
@@ -519,6 +779,282 @@ fn detect_mods_in_7z(archive_path: &Path) -> Result<Vec<String>, String> {
     Ok(mod_files)
 }
 
+/// An archive entry as listed, before it's arranged into a tree
+struct ListedEntry {
+    path: String,
+    size: u64,
+    is_directory: bool,
+}
+
+/// Entries listed from an archive; `complete` is false if reading stopped
+/// early or skipped entries
+struct Listing {
+    entries: Vec<ListedEntry>,
+    complete: bool,
+}
+
+fn list_zip(archive_path: &Path) -> Result<Listing, String> {
+    let file = File::open(archive_path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive =
+        ZipArchive::new(file).map_err(|e| format!("Failed to read ZIP archive: {}", e))?;
+
+    let mut listing = Listing {
+        entries: Vec::new(),
+        complete: true,
+    };
+    for i in 0..archive.len() {
+        // Raw access reads the central directory record only, no decompression
+        let Ok(file) = archive.by_index_raw(i) else {
+            listing.complete = false;
+            continue;
+        };
+        // Entries that would escape the extraction folder are never extracted
+        let Some(path) = file.enclosed_name() else {
+            listing.complete = false;
+            continue;
+        };
+        listing.entries.push(ListedEntry {
+            path: path.to_string_lossy().to_string(),
+            size: file.size(),
+            is_directory: file.is_dir(),
+        });
+    }
+    Ok(listing)
+}
+
+fn list_rar(archive_path: &Path) -> Result<Listing, String> {
+    let mut archive = Archive::new(archive_path)
+        .open_for_listing()
+        .map_err(|e| format!("Failed to open RAR archive: {}", e))?;
+
+    let mut listing = Listing {
+        entries: Vec::new(),
+        complete: true,
+    };
+    // A damaged or truncated RAR still lists everything before the damage
+    loop {
+        let header = match archive.read_header() {
+            Ok(Some(header)) => header,
+            Ok(None) => break,
+            Err(e) => {
+                log::warn!("Stopped listing RAR archive early: {}", e);
+                listing.complete = false;
+                break;
+            }
+        };
+        let entry = header.entry();
+        listing.entries.push(ListedEntry {
+            path: entry.filename.to_string_lossy().to_string(),
+            size: entry.unpacked_size as u64,
+            is_directory: entry.is_directory(),
+        });
+        archive = match header.skip() {
+            Ok(next) => next,
+            Err(e) => {
+                log::warn!("Stopped listing RAR archive early: {}", e);
+                listing.complete = false;
+                break;
+            }
+        };
+    }
+    Ok(listing)
+}
+
+fn list_7z(archive_path: &Path) -> Result<Listing, String> {
+    let file = File::open(archive_path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let file_size = file
+        .metadata()
+        .map_err(|e| format!("Failed to get file metadata: {}", e))?
+        .len();
+
+    // Opening the reader only decodes the header, not the packed streams
+    let reader = SevenZReader::new(file, file_size, sevenz_rust::Password::empty())
+        .map_err(|e| format!("Failed to read archive: {}", e))?;
+
+    let entries = reader
+        .archive()
+        .files
+        .iter()
+        .filter(|entry| !entry.name().is_empty())
+        .map(|entry| ListedEntry {
+            path: entry.name().to_string(),
+            size: entry.size(),
+            is_directory: entry.is_directory(),
+        })
+        .collect();
+    Ok(Listing {
+        entries,
+        complete: true,
+    })
+}
+
+/// Folder tree under construction; children keyed by name
+#[derive(Default)]
+struct TreeBuilder {
+    size: u64,
+    is_directory: bool,
+    children: std::collections::BTreeMap<String, TreeBuilder>,
+}
+
+impl TreeBuilder {
+    fn insert(&mut self, parts: &[&str], size: u64, is_directory: bool) {
+        let Some((first, rest)) = parts.split_first() else {
+            return;
+        };
+        let child = self.children.entry(first.to_string()).or_default();
+        if rest.is_empty() {
+            child.is_directory |= is_directory;
+            child.size = size;
+        } else {
+            // Zips often omit folder entries, so folders are implied by paths
+            child.is_directory = true;
+            child.insert(rest, size, is_directory);
+        }
+    }
+
+    /// Folders first, then natural name order
+    fn into_nodes(self, prefix: &str) -> Vec<ArchiveTreeNode> {
+        let mut nodes: Vec<ArchiveTreeNode> = self
+            .children
+            .into_iter()
+            .map(|(name, child)| {
+                let path = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}/{}", prefix, name)
+                };
+                let is_directory = child.is_directory;
+                let own_size = child.size;
+                let children = child.into_nodes(&path);
+                let size = if is_directory {
+                    children.iter().map(|c| c.size).sum()
+                } else {
+                    own_size
+                };
+                ArchiveTreeNode {
+                    name,
+                    path,
+                    size,
+                    is_directory,
+                    children,
+                }
+            })
+            .collect();
+        nodes.sort_by(|a, b| {
+            b.is_directory
+                .cmp(&a.is_directory)
+                .then_with(|| natural_cmp(&a.name, &b.name))
+        });
+        nodes
+    }
+}
+
+fn has_extension(path: &str, extensions: &[&str]) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Group listed paks with their .ucas/.utoc companions and a preview image
+/// from the same archive folder
+fn detect_listed_mods(files: &[&ListedEntry]) -> Vec<DetectedMod> {
+    let parent = |path: &str| path.rsplit_once('/').map(|(dir, _)| dir.to_string());
+
+    let mut detected_mods = Vec::new();
+    for pak in files.iter().filter(|e| has_extension(&e.path, &["pak"])) {
+        let pak_dir = parent(&pak.path);
+        let siblings: Vec<&&ListedEntry> = files
+            .iter()
+            .filter(|e| parent(&e.path) == pak_dir)
+            .collect();
+        let stem = pak
+            .path
+            .rsplit_once('.')
+            .map_or(pak.path.as_str(), |(stem, _)| stem);
+
+        let associated_files = COMPANION_EXTENSIONS
+            .iter()
+            .filter_map(|ext| {
+                let companion = format!("{}.{}", stem, ext);
+                siblings
+                    .iter()
+                    .find(|e| e.path.eq_ignore_ascii_case(&companion))
+                    .map(|e| e.path.clone())
+            })
+            .collect();
+
+        let sibling_paths: Vec<PathBuf> = siblings.iter().map(|e| PathBuf::from(&e.path)).collect();
+        let preview_image = pick_preview_image(Path::new(&pak.path), &sibling_paths)
+            .map(|p| p.to_string_lossy().to_string());
+
+        let pak_path = Path::new(&pak.path);
+        let file_name = pak_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        detected_mods.push(DetectedMod {
+            pak_file: pak.path.clone(),
+            associated_files,
+            size: pak.size,
+            preview_image,
+            suggested_metadata: detection::suggest_metadata(pak_path, &file_name, &[]),
+            invalid_reason: None,
+        });
+    }
+    detected_mods
+}
+
+/// Build an [`ArchivePreview`] from the archive's own index, without
+/// extracting anything
+pub fn build_archive_preview(archive_path: &Path) -> Result<ArchivePreview, String> {
+    let extension = archive_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase())
+        .ok_or("Invalid archive file")?;
+
+    let mut listing = match extension.as_str() {
+        "zip" => list_zip(archive_path)?,
+        "rar" => list_rar(archive_path)?,
+        "7z" => list_7z(archive_path)?,
+        _ => return Err(format!("Unsupported archive format: {}", extension)),
+    };
+
+    // Windows-made archives may use backslashes
+    for entry in &mut listing.entries {
+        entry.path = entry.path.replace('\\', "/").trim_matches('/').to_string();
+    }
+
+    let files: Vec<&ListedEntry> = listing.entries.iter().filter(|e| !e.is_directory).collect();
+    let nested_archives = files
+        .iter()
+        .any(|e| has_extension(&e.path, NESTED_ARCHIVE_EXTENSIONS));
+
+    let mut tree = TreeBuilder::default();
+    for entry in &listing.entries {
+        let parts: Vec<&str> = entry.path.split('/').filter(|p| !p.is_empty()).collect();
+        tree.insert(&parts, entry.size, entry.is_directory);
+    }
+
+    Ok(ArchivePreview {
+        file_count: files.len(),
+        total_size: files.iter().map(|e| e.size).sum(),
+        detected_mods: detect_listed_mods(&files),
+        preview_partial: !listing.complete || nested_archives,
+        entries: tree.into_nodes(""),
+    })
+}
+
+/// List an archive's contents (tree with sizes, detected mods, preview
+/// images) without writing anything to disk, so the install dialog can open
+/// instantly and extraction waits until the user confirms
+#[tauri::command]
+pub async fn preview_archive(archive_path: String) -> Result<ArchivePreview, String> {
+    log::info!("Previewing archive: {}", archive_path);
+    build_archive_preview(Path::new(&archive_path))
+}
+
 /// Extract archive and detect all mods with their associated files
 /// This provides more detailed information than detect_mods_in_archive
 #[tauri::command]
@@ -542,26 +1078,45 @@ pub async fn extract_and_detect_mods(
     log::info!("Extracting to temporary directory: {:?}", temp_dir);
 
     // Extract the archive
-    let extractor = ArchiveExtractor::new(app);
+    let extractor = ArchiveExtractor::new(progress_emitter(app.clone()));
+    let extraction = extractor.extract(&archive_path, &temp_dir)?;
+
+    let detected_mods = detect_extracted_mods(&temp_dir, &extraction.failed_entries);
+    log::info!(
+        "Detected {} mods in archive, {} damaged entries",
+        detected_mods.len(),
+        extraction.failed_entries.len()
+    );
+
+    let archive_name = archive_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    notifications::notify(
+        &app,
+        NotificationKind::ExtractionComplete,
+        &t("notify.extractionComplete.title", &[]),
+        &tn(
+            "notify.extractionComplete.body",
+            detected_mods.len(),
+            &[("archive", &archive_name)],
+        ),
+    );
 
-    let extension = archive_path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| ext.to_lowercase())
-        .ok_or("Invalid archive file")?;
-
-    match extension.as_str() {
-        "zip" => extractor.extract_zip(&archive_path, &temp_dir)?,
-        "rar" => extractor.extract_rar(&archive_path, &temp_dir)?,
-        "7z" => extractor.extract_7z(&archive_path, &temp_dir)?,
-        _ => return Err(format!("Unsupported archive format: {}", extension)),
-    };
+    Ok(detected_mods)
+}
 
-    // Scan extracted directory for .pak files
+/// Scan an extracted archive for .pak files and gather their companions and
+/// preview image. Paks whose own entry or a companion's is in
+/// `failed_entries` are marked invalid.
+pub fn detect_extracted_mods(
+    dir: &Path,
+    failed_entries: &[(PathBuf, ArchiveEntryError)],
+) -> Vec<DetectedMod> {
     let mut detected_mods = Vec::new();
     let mut processed_paks = std::collections::HashSet::new();
 
-    for entry in WalkDir::new(&temp_dir)
+    for entry in WalkDir::new(dir)
         .follow_links(false)
         .into_iter()
         .filter_map(|e| e.ok())
@@ -584,31 +1139,134 @@ pub async fn extract_and_detect_mods(
 
                 processed_paks.insert(pak_path_str.clone());
 
-                // Find associated files (.ucas, .utoc)
-                let mut associated_files = Vec::new();
-                let base_name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-                let parent_dir = path.parent().unwrap_or(Path::new(""));
-
-                for ext in &["ucas", "utoc"] {
-                    let companion_path = parent_dir.join(format!("{}.{}", base_name, ext));
-                    if companion_path.exists() {
-                        associated_files.push(companion_path.to_string_lossy().to_string());
-                    }
-                }
+                // Find associated files (.ucas, .utoc, .sig)
+                let associated_files: Vec<String> = find_companions(path, "")
+                    .iter()
+                    .map(|companion| companion.to_string_lossy().to_string())
+                    .collect();
 
                 // Get file size
                 let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
 
+                let invalid_reason = failed_entries
+                    .iter()
+                    .find(|(failed, _)| {
+                        failed == path
+                            || associated_files
+                                .iter()
+                                .any(|companion| Path::new(companion) == failed)
+                    })
+                    .map(|(_, error)| error.to_string());
+
+                let file_name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
                 detected_mods.push(DetectedMod {
                     pak_file: pak_path_str,
                     associated_files,
                     size,
+                    preview_image: find_preview_image(path)
+                        .map(|p| p.to_string_lossy().to_string()),
+                    suggested_metadata: detection::suggest_metadata(path, &file_name, &[dir]),
+                    invalid_reason,
                 });
             }
         }
     }
 
-    log::info!("Detected {} mods in archive", detected_mods.len());
+    detected_mods
+}
 
-    Ok(detected_mods)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn test_corrupt_zip_entry_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("pack.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+        let stored =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        for (name, content) in [
+            ("Good/good_P.pak", b"GOOD-PAK-CONTENT".as_slice()),
+            ("Bad/bad_P.pak", b"BAD-PAK-CONTENT!".as_slice()),
+            ("Bad/bad_P.ucas", b"companion".as_slice()),
+        ] {
+            writer.start_file(name, stored).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap();
+
+        // Flip bytes inside the stored data so its CRC no longer matches
+        let mut bytes = fs::read(&archive_path).unwrap();
+        let at = bytes
+            .windows(15)
+            .position(|w| w == b"BAD-PAK-CONTENT")
+            .unwrap();
+        bytes[at..at + 3].copy_from_slice(b"XXX");
+        fs::write(&archive_path, bytes).unwrap();
+
+        let dest = dir.path().join("out");
+        let extraction = ArchiveExtractor::new(|_| {})
+            .extract(&archive_path, &dest)
+            .unwrap();
+        // The damaged entry doesn't stop the rest of the archive
+        assert_eq!(extraction.mod_files.len(), 2);
+        assert_eq!(
+            extraction.failed_entries,
+            vec![(
+                dest.join("Bad/bad_P.pak"),
+                ArchiveEntryError::CorruptArchiveEntry {
+                    name: "Bad/bad_P.pak".to_string()
+                }
+            )]
+        );
+
+        let detected = detect_extracted_mods(&dest, &extraction.failed_entries);
+        let invalid: Vec<&DetectedMod> = detected
+            .iter()
+            .filter(|m| m.invalid_reason.is_some())
+            .collect();
+        assert_eq!(invalid.len(), 1);
+        assert!(invalid[0].pak_file.ends_with("bad_P.pak"));
+        assert!(invalid[0]
+            .invalid_reason
+            .as_deref()
+            .unwrap()
+            .contains("download the archive again"));
+    }
+
+    #[test]
+    fn test_extracted_files_keep_their_modification_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("pack.zip");
+        let built = zip::DateTime::from_date_and_time(2023, 6, 15, 18, 30, 42).unwrap();
+        let mut writer = zip::ZipWriter::new(File::create(&archive_path).unwrap());
+        writer
+            .start_file(
+                "Mod/mod_P.pak",
+                SimpleFileOptions::default().last_modified_time(built),
+            )
+            .unwrap();
+        writer.write_all(b"PAK").unwrap();
+        writer.finish().unwrap();
+
+        let dest = dir.path().join("out");
+        ArchiveExtractor::new(|_| {})
+            .extract(&archive_path, &dest)
+            .unwrap();
+        let modified = fs::metadata(dest.join("Mod/mod_P.pak"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        let (date, time): (u16, u16) = built.into();
+        assert_eq!(Some(modified), dos_time(date, time));
+
+        // Zeroed DOS fields mean no time was stored
+        assert_eq!(dos_time(0, 0), None);
+    }
 }
@@ -0,0 +1,54 @@
+//! Version handshake the frontend runs before anything else. The updater can
+//! leave a cached webview bundle and the backend binary on different
+//! releases; with this the frontend can check what the backend offers and
+//! degrade or ask for a restart instead of failing on individual invokes.
+
+use serde::{Deserialize, Serialize};
+
+/// Bump whenever a command is removed or renamed, or a payload changes shape
+/// in a way older frontends can't read. Adding commands doesn't need a bump,
+/// the command list covers that.
+pub const IPC_SCHEMA_VERSION: u32 = 1;
+
+/// Optional subsystems the frontend may have to hide when missing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendFeatures {
+    /// Nexus Mods downloads
+    pub nexus: bool,
+    /// Reading asset lists out of .utoc files (conflict detection)
+    pub pak_reader: bool,
+    /// System tray icon
+    pub tray: bool,
+    /// Running against the generated demo library (`--demo`); Nexus
+    /// requests go through `demo_nexus_request` instead of online
+    pub demo: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendInfo {
+    pub version: String,
+    pub ipc_schema_version: u32,
+    pub commands: Vec<String>,
+    pub features: BackendFeatures,
+}
+
+pub fn backend_info() -> BackendInfo {
+    BackendInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        ipc_schema_version: IPC_SCHEMA_VERSION,
+        commands: crate::COMMANDS.iter().map(|c| c.to_string()).collect(),
+        features: BackendFeatures {
+            nexus: true,
+            pak_reader: true,
+            tray: false,
+            demo: crate::demo::is_enabled(),
+        },
+    }
+}
+
+#[tauri::command]
+pub fn get_backend_info() -> BackendInfo {
+    backend_info()
+}
@@ -0,0 +1,397 @@
+//! Headless mode for scripting: `marvel-rivals-mod-manager --cli <command>`
+//! runs one command against the library and exits without opening a window.
+//! It reads the same settings and metadata as the app, prints its result as
+//! JSON on stdout and reports failure through the exit code, so it can sit in
+//! a Steam launch option or a modpack build script.
+//!
+//! The release build is a Windows GUI program, so its output only shows up
+//! when stdout is redirected (`... --cli list > mods.json`). Run it while the
+//! app is closed; both moving the same files at once is not guarded against.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::mod_service::{validate_pak_file, ModService};
+use crate::read_only::ReadOnlyError;
+use crate::types::{AppSettings, ModInfo};
+
+/// Must match `identifier` in tauri.conf.json, which names the app data
+/// folder
+const APP_IDENTIFIER: &str = "com.marvelrivalsmodmanager.app";
+
+pub const EXIT_OK: i32 = 0;
+/// The command ran and failed
+pub const EXIT_FAILED: i32 = 1;
+/// Unknown command or missing arguments
+pub const EXIT_USAGE: i32 = 2;
+/// `verify` found problems with installed mods
+pub const EXIT_ISSUES_FOUND: i32 = 3;
+
+const USAGE: &str = "usage: --cli [--data-dir <path> | --demo] <command>
+commands:
+  list                     every installed mod
+  enable <id|title>        enable a mod
+  disable <id|title>       disable a mod
+  apply-profile <id>       enable exactly the mods in a profile
+  install <path>           install a .pak, or every .pak in a folder
+  verify                   check installed mods for problems";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CliCommand {
+    List,
+    SetEnabled { query: String, enabled: bool },
+    ApplyProfile { profile_id: String },
+    Install { path: PathBuf },
+    Verify,
+}
+
+impl CliCommand {
+    /// How a command that changes mods is named when read-only mode
+    /// refuses it; `None` for the ones that only read
+    fn mutating_action(&self) -> Option<&'static str> {
+        match self {
+            Self::SetEnabled { enabled: true, .. } => Some("enable"),
+            Self::SetEnabled { enabled: false, .. } => Some("disable"),
+            Self::ApplyProfile { .. } => Some("apply-profile"),
+            Self::Install { .. } => Some("install"),
+            Self::List | Self::Verify => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct CliArgs {
+    data_dir: Option<PathBuf>,
+    /// Run against the demo library, generating it if needed
+    demo: bool,
+    command: CliCommand,
+}
+
+#[derive(Serialize)]
+struct CliError<'a> {
+    error: &'a str,
+}
+
+/// If the arguments ask for CLI mode, run the command and return the exit
+/// code; `None` means start the app as usual
+pub fn run_from_args(args: impl IntoIterator<Item = String>) -> Option<i32> {
+    let mut args = args.into_iter().skip_while(|arg| arg != "--cli");
+    args.next()?;
+
+    let parsed = match parse_args(args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{e}\n{USAGE}");
+            return Some(EXIT_USAGE);
+        }
+    };
+    Some(match execute(parsed) {
+        Ok(code) => code,
+        Err(e) => {
+            print_json(&CliError { error: &e });
+            EXIT_FAILED
+        }
+    })
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<CliArgs, String> {
+    let mut args = args.into_iter();
+    let mut data_dir = None;
+    let mut demo = false;
+    let mut positional = Vec::new();
+    while let Some(arg) = args.next() {
+        if arg == "--data-dir" {
+            data_dir = Some(PathBuf::from(args.next().ok_or("--data-dir needs a path")?));
+        } else if arg == crate::demo::LAUNCH_ARG {
+            demo = true;
+        } else {
+            positional.push(arg);
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let name = positional.next().ok_or("No command given")?;
+    // Titles can have spaces; accept them unquoted
+    let rest = positional.collect::<Vec<_>>().join(" ");
+    let argument = |what: &str| {
+        if rest.is_empty() {
+            Err(format!("{name} needs {what}"))
+        } else {
+            Ok(rest.clone())
+        }
+    };
+
+    let command = match name.as_str() {
+        "list" => CliCommand::List,
+        "enable" | "disable" => CliCommand::SetEnabled {
+            query: argument("a mod ID or title")?,
+            enabled: name == "enable",
+        },
+        "apply-profile" => CliCommand::ApplyProfile {
+            profile_id: argument("a profile ID")?,
+        },
+        "install" => CliCommand::Install {
+            path: PathBuf::from(argument("a path")?),
+        },
+        "verify" => CliCommand::Verify,
+        _ => return Err(format!("Unknown command: {name}")),
+    };
+    Ok(CliArgs {
+        data_dir,
+        demo,
+        command,
+    })
+}
+
+/// Where Tauri keeps the app data on this platform, worked out without an
+/// `AppHandle`
+fn default_data_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        PathBuf::from(std::env::var_os("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(std::env::var_os("HOME")?).join("Library/Application Support")
+    } else {
+        match std::env::var_os("XDG_DATA_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(std::env::var_os("HOME")?).join(".local/share"),
+        }
+    };
+    Some(base.join(APP_IDENTIFIER))
+}
+
+fn load_settings(data_dir: &Path) -> Result<AppSettings, String> {
+    let settings_path = data_dir.join("settings.json");
+    if !settings_path.exists() {
+        return Err(format!(
+            "No settings in {}; open the app once to set the game folder",
+            data_dir.display()
+        ));
+    }
+    crate::parse_settings_file(&settings_path)
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{json}"),
+        Err(e) => eprintln!("Failed to serialize output: {e}"),
+    }
+}
+
+fn execute(args: CliArgs) -> Result<i32, String> {
+    let data_dir = match args.data_dir {
+        Some(dir) => dir,
+        None if args.demo => crate::demo::ensure_sandbox(&crate::demo::sandbox_root())?,
+        None => default_data_dir().ok_or("Could not find the app data folder")?,
+    };
+    let settings = load_settings(&data_dir)?;
+    // The app's command guard doesn't cover the CLI, so check here
+    if settings.read_only_mode {
+        if let Some(action) = args.command.mutating_action() {
+            return Err(ReadOnlyError::ReadOnlyMode {
+                action: action.to_string(),
+            }
+            .into());
+        }
+    }
+    let game_directory = settings
+        .game_directory
+        .as_deref()
+        .ok_or("Game directory not configured")?;
+    if !game_directory.is_dir() {
+        return Err(crate::types::ModServiceError::GameDirectoryUnavailable {
+            path: game_directory.to_path_buf(),
+        }
+        .into());
+    }
+    let service = crate::mod_service_for_data_dir(&data_dir, &settings)?;
+
+    let code = match args.command {
+        CliCommand::List => {
+            print_json(&service.get_all_mods()?);
+            EXIT_OK
+        }
+        CliCommand::SetEnabled { query, enabled } => {
+            let mods = service.get_all_mods()?;
+            let mod_info = find_mod(&mods, &query)?;
+            print_json(&service.enable_mod(&mod_info.id, enabled)?);
+            EXIT_OK
+        }
+        CliCommand::ApplyProfile { profile_id } => {
+            let toggled =
+                crate::profile_service::apply_profile_in(&service, &data_dir, &profile_id)?;
+            print_json(&toggled);
+            EXIT_OK
+        }
+        CliCommand::Install { path } => {
+            print_json(&install(&service, &path)?);
+            EXIT_OK
+        }
+        CliCommand::Verify => {
+            let issues = service.verify_mods()?;
+            print_json(&issues);
+            if issues.is_empty() {
+                EXIT_OK
+            } else {
+                EXIT_ISSUES_FOUND
+            }
+        }
+    };
+    service.flush_pending_metadata()?;
+    Ok(code)
+}
+
+/// The mod with this ID, or failing that the one mod with this title
+fn find_mod<'a>(mods: &'a [ModInfo], query: &str) -> Result<&'a ModInfo, String> {
+    if let Some(found) = mods.iter().find(|m| m.id == query) {
+        return Ok(found);
+    }
+    let by_title: Vec<&ModInfo> = mods
+        .iter()
+        .filter(|m| m.metadata.title.eq_ignore_ascii_case(query))
+        .collect();
+    match by_title.as_slice() {
+        [only] => Ok(*only),
+        [] => Err(format!("No mod with ID or title \"{query}\"")),
+        several => Err(format!(
+            "{} mods are titled \"{query}\"; use an ID: {}",
+            several.len(),
+            several
+                .iter()
+                .map(|m| m.id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Install a pak, or every pak under a folder, then sort them into category
+/// folders the way the app does at startup. Returns the installed mods as
+/// they are after sorting.
+fn install(service: &ModService, path: &Path) -> Result<Vec<ModInfo>, String> {
+    let paks: Vec<PathBuf> = if path.is_dir() {
+        let mut paks: Vec<PathBuf> = WalkDir::new(path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .map(|e| e.into_path())
+            .filter(|p| {
+                p.extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("pak"))
+            })
+            .collect();
+        paks.sort();
+        paks
+    } else {
+        vec![path.to_path_buf()]
+    };
+    if paks.is_empty() {
+        return Err(format!("No .pak files in {}", path.display()));
+    }
+
+    // Refuse the lot if any pak is broken, before anything is copied
+    for pak in &paks {
+        validate_pak_file(pak).map_err(|e| format!("{}: {}", pak.display(), e))?;
+    }
+    let mut file_names = Vec::new();
+    for pak in &paks {
+        let mod_info = service.install_mod_to_folder(pak, "")?;
+        file_names.push(mod_info.original_file_name);
+    }
+    service.organize_loose_mods(None)?;
+
+    Ok(service
+        .get_all_mods()?
+        .into_iter()
+        .filter(|m| file_names.contains(&m.original_file_name))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::Fixture;
+
+    fn args(line: &str) -> Result<CliArgs, String> {
+        parse_args(line.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args("--data-dir /tmp/data enable Classic Venom").unwrap(),
+            CliArgs {
+                data_dir: Some(PathBuf::from("/tmp/data")),
+                demo: false,
+                command: CliCommand::SetEnabled {
+                    query: "Classic Venom".to_string(),
+                    enabled: true,
+                },
+            }
+        );
+        assert_eq!(args("verify").unwrap().command, CliCommand::Verify);
+        assert!(args("--demo list").unwrap().demo);
+        assert!(args("disable").is_err());
+        assert!(args("launch").is_err());
+        assert!(args("").is_err());
+
+        // Without --cli the app starts normally
+        assert_eq!(run_from_args(["--minimized".to_string()]), None);
+    }
+
+    #[test]
+    fn test_find_mod_by_id_or_title() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        fx.installed_mod("Skins/Storm/Weather", "Storm_P");
+        fx.installed_mod("Skins/Venom/A", "Venom_P");
+        fx.installed_mod("Skins/Venom/B", "Venom_P");
+        let mods = service.get_all_mods().unwrap();
+
+        let storm = mods
+            .iter()
+            .find(|m| m.original_file_name == "Storm_P.pak")
+            .unwrap();
+        assert_eq!(find_mod(&mods, &storm.id).unwrap().id, storm.id);
+        assert_eq!(find_mod(&mods, "storm").unwrap().id, storm.id);
+        // The same pak twice: only its IDs tell the copies apart
+        assert!(find_mod(&mods, "Venom").unwrap_err().contains("use an ID"));
+        assert!(find_mod(&mods, "nothing like it").is_err());
+    }
+
+    #[test]
+    fn test_read_only_refuses_changes() {
+        let fx = Fixture::new();
+        let data_dir = fx.metadata_dir.parent().unwrap().to_path_buf();
+        let settings = AppSettings {
+            game_directory: Some(fx.game_dir.clone()),
+            read_only_mode: true,
+            ..AppSettings::default()
+        };
+        std::fs::write(
+            data_dir.join("settings.json"),
+            serde_json::to_string(&settings).unwrap(),
+        )
+        .unwrap();
+        fx.installed_mod("Skins/Storm/Weather", "Storm_P");
+        let source = fx.source_mod("Venom_P", 64, None);
+        let run = |command| {
+            execute(CliArgs {
+                data_dir: Some(data_dir.clone()),
+                demo: false,
+                command,
+            })
+        };
+
+        assert!(run(CliCommand::Install { path: source }).is_err());
+        assert!(run(CliCommand::SetEnabled {
+            query: "Storm_P".to_string(),
+            enabled: false,
+        })
+        .is_err());
+        let mods = fx.service().get_all_mods().unwrap();
+        assert_eq!(mods.len(), 1);
+        assert!(mods[0].enabled);
+        // Reading still works
+        assert_eq!(run(CliCommand::List), Ok(EXIT_OK));
+    }
+}
@@ -0,0 +1,552 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::mod_service::{Clock, ModService};
+use crate::types::{DisabledReason, ModCollection, ModInfo, ModMetadata, ToggledMod};
+
+/// Name of the manifest written at the root of an exported collection
+pub const MANIFEST_FILE: &str = "collection.json";
+
+/// Serializes read-modify-write cycles on collections.json across commands
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectionsFile {
+    #[serde(default)]
+    collections: Vec<ModCollection>,
+}
+
+/// `collections.json`, next to the metadata folder. Mod IDs are path-based,
+/// so `ModService` calls [`CollectionStore::remap_mod_ids`] whenever a mod
+/// moves and [`CollectionStore::forget_mods`] when one is deleted.
+pub struct CollectionStore {
+    path: PathBuf,
+    clock: Arc<dyn Clock>,
+}
+
+impl CollectionStore {
+    pub fn new(path: PathBuf, clock: Arc<dyn Clock>) -> Self {
+        Self { path, clock }
+    }
+
+    fn load(&self) -> Result<Vec<ModCollection>, String> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let content = fs::read_to_string(&self.path)
+            .map_err(|e| format!("Failed to read collections: {}", e))?;
+        let file: CollectionsFile = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse collections: {}", e))?;
+        Ok(file.collections)
+    }
+
+    fn save(&self, collections: Vec<ModCollection>) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create collections folder: {}", e))?;
+        }
+        let json = serde_json::to_string_pretty(&CollectionsFile { collections })
+            .map_err(|e| format!("Failed to serialize collections: {}", e))?;
+
+        // Write to a temporary file first, then rename (atomic operation)
+        let temp_path = self.path.with_extension("tmp");
+        fs::write(&temp_path, json).map_err(|e| format!("Failed to write collections: {}", e))?;
+        fs::rename(&temp_path, &self.path)
+            .map_err(|e| format!("Failed to finalize collections: {}", e))
+    }
+
+    /// Apply `change` to one collection and save, returning the result
+    fn update(
+        &self,
+        collection_id: &str,
+        change: impl FnOnce(&mut ModCollection) -> Result<(), String>,
+    ) -> Result<ModCollection, String> {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut collections = self.load()?;
+        let collection = collections
+            .iter_mut()
+            .find(|c| c.id == collection_id)
+            .ok_or_else(|| format!("Collection not found: {}", collection_id))?;
+        change(collection)?;
+        collection.updated_at = self.clock.now();
+        let updated = collection.clone();
+        self.save(collections)?;
+        Ok(updated)
+    }
+
+    pub fn list(&self) -> Result<Vec<ModCollection>, String> {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        self.load()
+    }
+
+    pub fn get(&self, collection_id: &str) -> Result<ModCollection, String> {
+        self.list()?
+            .into_iter()
+            .find(|c| c.id == collection_id)
+            .ok_or_else(|| format!("Collection not found: {}", collection_id))
+    }
+
+    pub fn create(&self, name: &str, description: Option<String>) -> Result<ModCollection, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Collection name cannot be empty".to_string());
+        }
+
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut collections = self.load()?;
+        let now = self.clock.now();
+        let collection = ModCollection {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            description: description.filter(|d| !d.trim().is_empty()),
+            mod_ids: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        };
+        collections.push(collection.clone());
+        self.save(collections)?;
+        Ok(collection)
+    }
+
+    pub fn delete(&self, collection_id: &str) -> Result<(), String> {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut collections = self.load()?;
+        let before = collections.len();
+        collections.retain(|c| c.id != collection_id);
+        if collections.len() == before {
+            return Err(format!("Collection not found: {}", collection_id));
+        }
+        self.save(collections)
+    }
+
+    /// Append mods to the end of the collection, skipping ones already in it
+    pub fn add(&self, collection_id: &str, mod_ids: &[String]) -> Result<ModCollection, String> {
+        self.update(collection_id, |collection| {
+            for mod_id in mod_ids {
+                if !collection.mod_ids.contains(mod_id) {
+                    collection.mod_ids.push(mod_id.clone());
+                }
+            }
+            Ok(())
+        })
+    }
+
+    pub fn remove(&self, collection_id: &str, mod_ids: &[String]) -> Result<ModCollection, String> {
+        self.update(collection_id, |collection| {
+            collection.mod_ids.retain(|id| !mod_ids.contains(id));
+            Ok(())
+        })
+    }
+
+    /// Replace the member order. `mod_ids` must list exactly the current
+    /// members, so a stale UI can't silently add or drop mods.
+    pub fn reorder(
+        &self,
+        collection_id: &str,
+        mod_ids: &[String],
+    ) -> Result<ModCollection, String> {
+        self.update(collection_id, |collection| {
+            let current: HashSet<&String> = collection.mod_ids.iter().collect();
+            let requested: HashSet<&String> = mod_ids.iter().collect();
+            if current != requested || requested.len() != mod_ids.len() {
+                return Err(
+                    "New order must contain each collection member exactly once".to_string()
+                );
+            }
+            collection.mod_ids = mod_ids.to_vec();
+            Ok(())
+        })
+    }
+
+    /// Follow mods whose path-based ID changed (old ID -> new ID). Returns how
+    /// many memberships were updated.
+    pub fn remap_mod_ids(&self, remap: &HashMap<String, String>) -> Result<usize, String> {
+        self.rewrite_members(|mod_ids| {
+            let mut changed = 0;
+            for mod_id in mod_ids.iter_mut() {
+                if let Some(new_id) = remap.get(mod_id) {
+                    *mod_id = new_id.clone();
+                    changed += 1;
+                }
+            }
+            changed
+        })
+    }
+
+    /// Drop deleted mods from every collection. Returns how many memberships
+    /// were removed.
+    pub fn forget_mods(&self, mod_ids: &[String]) -> Result<usize, String> {
+        self.rewrite_members(|members| {
+            let before = members.len();
+            members.retain(|id| !mod_ids.contains(id));
+            before - members.len()
+        })
+    }
+
+    /// Run `change` over every collection's member list, saving only if it
+    /// reports a change. No file is created when there are no collections.
+    fn rewrite_members(&self, change: impl Fn(&mut Vec<String>) -> usize) -> Result<usize, String> {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        if !self.path.exists() {
+            return Ok(0);
+        }
+        let mut collections = self.load()?;
+        let now = self.clock.now();
+        let mut total = 0;
+        for collection in &mut collections {
+            let changed = change(&mut collection.mod_ids);
+            if changed > 0 {
+                collection.updated_at = now;
+                total += changed;
+            }
+        }
+        if total > 0 {
+            self.save(collections)?;
+        }
+        Ok(total)
+    }
+}
+
+/// One member as recorded in an exported collection's manifest
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestMod {
+    pub id: String,
+    pub enabled: bool,
+    /// Archive paths of the mod's files ('/'-separated)
+    pub files: Vec<String>,
+    pub metadata: ModMetadata,
+}
+
+/// `collection.json` inside an exported collection archive
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollectionManifest {
+    pub name: String,
+    pub description: Option<String>,
+    pub exported_at: DateTime<Utc>,
+    /// Members in collection order
+    pub mods: Vec<ManifestMod>,
+}
+
+/// Archive path for a mod file: its path under ~mods (or disabled-mods),
+/// so the export unpacks into the same folder layout
+fn archive_path(mod_info: &ModInfo, file: &Path, service: &ModService) -> String {
+    let root = if mod_info.enabled {
+        service.mods_directory()
+    } else {
+        service.disabled_mods_directory()
+    };
+    let relative = file
+        .strip_prefix(root)
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|_| file.file_name().map(PathBuf::from).unwrap_or_default());
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn write_archive(
+    service: &ModService,
+    collection: &ModCollection,
+    now: DateTime<Utc>,
+    file: File,
+) -> Result<usize, String> {
+    let mods = service.get_all_mods()?;
+    let by_id: HashMap<&str, &ModInfo> = mods.iter().map(|m| (m.id.as_str(), m)).collect();
+
+    let mut zip = ZipWriter::new(file);
+    let mut manifest_mods = Vec::new();
+    for mod_id in &collection.mod_ids {
+        let Some(mod_info) = by_id.get(mod_id.as_str()) else {
+            log::warn!("[collections] Skipping missing member {} in export", mod_id);
+            continue;
+        };
+
+        let mut files = Vec::new();
+        for path in &mod_info.associated_files {
+            let name = archive_path(mod_info, path, service);
+            let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            // Paks are already compressed; storing them keeps big exports fast
+            let options = SimpleFileOptions::default()
+                .compression_method(CompressionMethod::Stored)
+                .large_file(size >= u32::MAX as u64);
+            zip.start_file(name.as_str(), options)
+                .map_err(|e| format!("Failed to add {} to archive: {}", name, e))?;
+            let mut source =
+                File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+            io::copy(&mut source, &mut zip)
+                .map_err(|e| format!("Failed to write {} to archive: {}", name, e))?;
+            files.push(name);
+        }
+
+        manifest_mods.push(ManifestMod {
+            id: mod_info.id.clone(),
+            enabled: mod_info.enabled,
+            files,
+            metadata: mod_info.metadata.clone(),
+        });
+    }
+
+    let exported = manifest_mods.len();
+    let manifest = CollectionManifest {
+        name: collection.name.clone(),
+        description: collection.description.clone(),
+        exported_at: now,
+        mods: manifest_mods,
+    };
+    let json = serde_json::to_string_pretty(&manifest)
+        .map_err(|e| format!("Failed to serialize collection manifest: {}", e))?;
+    zip.start_file(MANIFEST_FILE, SimpleFileOptions::default())
+        .map_err(|e| format!("Failed to add manifest to archive: {}", e))?;
+    zip.write_all(json.as_bytes())
+        .map_err(|e| format!("Failed to write manifest: {}", e))?;
+    zip.finish()
+        .map_err(|e| format!("Failed to finish archive: {}", e))?;
+
+    Ok(exported)
+}
+
+/// Write every member of `collection` plus a `collection.json` manifest into
+/// one zip at `dest`. Members that no longer exist are skipped. Returns the
+/// number of mods exported.
+pub fn export_collection_archive(
+    service: &ModService,
+    collection: &ModCollection,
+    now: DateTime<Utc>,
+    dest: &Path,
+) -> Result<usize, String> {
+    // Build next to the destination and rename, so a failed export never
+    // leaves a truncated zip behind under the requested name
+    let temp_path = dest.with_extension("zip.tmp");
+    let file = File::create(&temp_path).map_err(|e| format!("Failed to create archive: {}", e))?;
+    match write_archive(service, collection, now, file) {
+        Ok(exported) => {
+            fs::rename(&temp_path, dest)
+                .map_err(|e| format!("Failed to finalize archive: {}", e))?;
+            Ok(exported)
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(e)
+        }
+    }
+}
+
+// ===== TAURI COMMANDS =====
+
+#[tauri::command]
+pub async fn list_collections(app: AppHandle) -> Result<Vec<ModCollection>, String> {
+    crate::get_mod_service(&app)?.collections().list()
+}
+
+#[tauri::command]
+pub async fn create_collection(
+    app: AppHandle,
+    name: String,
+    description: Option<String>,
+) -> Result<ModCollection, String> {
+    log::info!("[collections] Creating collection \"{}\"", name);
+    crate::get_mod_service(&app)?
+        .collections()
+        .create(&name, description)
+}
+
+#[tauri::command]
+pub async fn delete_collection(app: AppHandle, collection_id: String) -> Result<(), String> {
+    log::info!("[collections] Deleting collection {}", collection_id);
+    crate::get_mod_service(&app)?
+        .collections()
+        .delete(&collection_id)
+}
+
+#[tauri::command]
+pub async fn add_to_collection(
+    app: AppHandle,
+    collection_id: String,
+    mod_ids: Vec<String>,
+) -> Result<ModCollection, String> {
+    crate::get_mod_service(&app)?
+        .collections()
+        .add(&collection_id, &mod_ids)
+}
+
+#[tauri::command]
+pub async fn remove_from_collection(
+    app: AppHandle,
+    collection_id: String,
+    mod_ids: Vec<String>,
+) -> Result<ModCollection, String> {
+    crate::get_mod_service(&app)?
+        .collections()
+        .remove(&collection_id, &mod_ids)
+}
+
+#[tauri::command]
+pub async fn reorder_collection(
+    app: AppHandle,
+    collection_id: String,
+    mod_ids: Vec<String>,
+) -> Result<ModCollection, String> {
+    crate::get_mod_service(&app)?
+        .collections()
+        .reorder(&collection_id, &mod_ids)
+}
+
+/// Enable or disable every member through the bulk toggle path. Members
+/// follow their new IDs, so the collection stays intact afterwards.
+#[tauri::command]
+pub async fn set_collection_enabled(
+    app: AppHandle,
+    collection_id: String,
+    enabled: bool,
+) -> Result<Vec<ToggledMod>, String> {
+    let collection = crate::get_mod_service(&app)?
+        .collections()
+        .get(&collection_id)?;
+    log::info!(
+        "[collections] Setting \"{}\" ({} mod(s)) enabled: {}",
+        collection.name,
+        collection.mod_ids.len(),
+        enabled
+    );
+    crate::toggle_mods_with_progress(
+        &app,
+        &collection.mod_ids,
+        enabled,
+        &DisabledReason::UserAction,
+    )
+}
+
+/// Export a collection as one zip: every member's files in their folder
+/// layout plus a `collection.json` manifest. Returns the number of mods
+/// written.
+#[tauri::command]
+pub async fn export_collection(
+    app: AppHandle,
+    collection_id: String,
+    dest_path: String,
+) -> Result<usize, String> {
+    let service = crate::get_mod_service(&app)?;
+    let collection = service.collections().get(&collection_id)?;
+    log::info!(
+        "[collections] Exporting \"{}\" to {}",
+        collection.name,
+        dest_path
+    );
+    export_collection_archive(&service, &collection, Utc::now(), Path::new(&dest_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::{fixed_now, metadata, FixedClock, Fixture};
+    use crate::types::{DeleteModError, ModCategory};
+    use zip::ZipArchive;
+
+    #[test]
+    fn test_collection_membership_and_order() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let store = CollectionStore::new(
+            dir.path().join("collections.json"),
+            Arc::new(FixedClock(fixed_now())),
+        );
+        let ids = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert!(store.create("  ", None).is_err());
+        let theme = store.create("Symbiote theme", None).unwrap();
+        let added = store.add(&theme.id, &ids(&["a", "b", "c", "a"])).unwrap();
+        assert_eq!(added.mod_ids, ids(&["a", "b", "c"]));
+
+        assert!(store.reorder(&theme.id, &ids(&["c", "a"])).is_err());
+        assert!(store.reorder(&theme.id, &ids(&["c", "a", "a"])).is_err());
+        let reordered = store.reorder(&theme.id, &ids(&["c", "a", "b"])).unwrap();
+        assert_eq!(reordered.mod_ids, ids(&["c", "a", "b"]));
+
+        // A moved mod keeps its place under its new ID; a deleted one is dropped
+        let remap = HashMap::from([("a".to_string(), "a2".to_string())]);
+        assert_eq!(store.remap_mod_ids(&remap).unwrap(), 1);
+        assert_eq!(store.forget_mods(&ids(&["b"])).unwrap(), 1);
+        assert_eq!(store.get(&theme.id).unwrap().mod_ids, ids(&["c", "a2"]));
+
+        store.delete(&theme.id).unwrap();
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_collection_follows_toggles_and_exports() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut members = Vec::new();
+        for (stem, folder) in [("Venom_P", "Skins/Venom"), ("Carnage_P", "Skins/Carnage")] {
+            let source = fx.source_mod(stem, 10, Some(100));
+            let title = stem.trim_end_matches("_P");
+            let installed = service
+                .install_mod_to_folder_with_metadata(
+                    &source,
+                    folder,
+                    metadata(title, ModCategory::Skins),
+                )
+                .unwrap();
+            members.push(installed.id);
+        }
+        let collection = service.collections().create("Symbiotes", None).unwrap();
+        service.collections().add(&collection.id, &members).unwrap();
+
+        let toggled = service
+            .set_mods_enabled(&members, false, |_, _| {})
+            .unwrap();
+        let disabled_ids: Vec<String> = toggled.iter().map(|t| t.mod_info.id.clone()).collect();
+        assert_eq!(
+            service.collections().get(&collection.id).unwrap().mod_ids,
+            disabled_ids
+        );
+
+        let dest = fx.source_dir.join("symbiotes.zip");
+        let collection = service.collections().get(&collection.id).unwrap();
+        assert_eq!(
+            export_collection_archive(&service, &collection, fixed_now(), &dest).unwrap(),
+            2
+        );
+        let mut archive = ZipArchive::new(File::open(&dest).unwrap()).unwrap();
+        let manifest: CollectionManifest =
+            serde_json::from_reader(archive.by_name(MANIFEST_FILE).unwrap()).unwrap();
+        assert_eq!(manifest.name, "Symbiotes");
+        assert_eq!(manifest.mods[0].metadata.title, "Venom");
+        assert_eq!(
+            manifest.mods[0].files,
+            vec![
+                "Skins/Venom/Venom_P.pak",
+                "Skins/Venom/Venom_P.ucas",
+                "Skins/Venom/Venom_P.utoc"
+            ]
+        );
+        assert!(archive.by_name("Skins/Carnage/Carnage_P.ucas").is_ok());
+
+        // Members are only deleted when forced, and leave no trace
+        let refused = service
+            .delete_mod(&disabled_ids[0], false, true)
+            .unwrap_err();
+        let DeleteModError::ModInUse { mods } = refused else {
+            panic!("expected ModInUse, got {refused}");
+        };
+        assert_eq!(mods[0].collections, vec![collection.id.clone()]);
+        assert!(mods[0].profiles.is_empty());
+        service.delete_mod(&disabled_ids[0], true, true).unwrap();
+        assert_eq!(
+            service.collections().get(&collection.id).unwrap().mod_ids,
+            vec![disabled_ids[1].clone()]
+        );
+    }
+}
@@ -1,18 +1,20 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
-use serde::Serialize;
-use tauri::{AppHandle, Manager};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
 
+use crate::app_data_dir;
 use crate::types::Costume;
 
 // Costume database structure - matches costume-data.json
 type CostumeDatabase = HashMap<String, Vec<Costume>>;
 
-// Global static costume data (loaded once at startup)
-static COSTUME_DATA: Mutex<Option<CostumeDatabase>> = Mutex::new(None);
+// Global static costume data (loaded at startup, reloaded by a sync or a
+// retry). Holds the error when loading failed, so commands can say why.
+static COSTUME_DATA: Mutex<Option<Result<CostumeDatabase, String>>> = Mutex::new(None);
 
 // Costume data shipped with this build (icons for these are bundled in the frontend)
 const EMBEDDED_COSTUME_JSON: &str = include_str!("../resources/costume-data.json");
@@ -31,12 +33,66 @@ pub struct CostumeSyncResult {
     pub total_costumes: usize,
 }
 
+/// Whether the costume data loaded, returned by get_costume_service_status
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum CostumeServiceStatus {
+    NotLoaded,
+    #[serde(rename_all = "camelCase")]
+    Loaded {
+        characters: usize,
+        costumes: usize,
+    },
+    Failed {
+        error: String,
+    },
+}
+
 /// Initialize the costume service by loading embedded costume data,
-/// overlaid with any previously synced data from the app data directory
+/// overlaid with any previously synced data from the app data directory.
+/// A failure is kept, for the costume commands to report.
 pub fn initialize_costume_service(app: &AppHandle) -> Result<(), String> {
-    let costume_data = build_database(app)?;
-    store_database(costume_data);
-    Ok(())
+    match build_database(app) {
+        Ok(costume_data) => {
+            store_database(costume_data);
+            Ok(())
+        }
+        Err(e) => {
+            log::error!("[CostumeService] Failed to load costume data: {e}");
+            let mut data = COSTUME_DATA.lock().unwrap_or_else(|e| e.into_inner());
+            // A failed reload keeps data that did load
+            if !matches!(*data, Some(Ok(_))) {
+                *data = Some(Err(e.clone()));
+            }
+            Err(e)
+        }
+    }
+}
+
+fn status() -> CostumeServiceStatus {
+    match COSTUME_DATA
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+    {
+        None => CostumeServiceStatus::NotLoaded,
+        Some(Ok(database)) => CostumeServiceStatus::Loaded {
+            characters: database.len(),
+            costumes: database.values().map(Vec::len).sum(),
+        },
+        Some(Err(error)) => CostumeServiceStatus::Failed {
+            error: error.clone(),
+        },
+    }
+}
+
+/// The loaded database, or why there isn't one
+fn loaded(data: &Option<Result<CostumeDatabase, String>>) -> Result<&CostumeDatabase, String> {
+    match data {
+        Some(Ok(database)) => Ok(database),
+        Some(Err(error)) => Err(format!("Costume data failed to load: {error}")),
+        None => Err("Costume data not loaded yet".to_string()),
+    }
 }
 
 /// Parse the costume data embedded in the binary at compile time
@@ -68,30 +124,51 @@ fn synced_icons_dir(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(app_data_dir(app)?.join("costume-icons"))
 }
 
-fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
-    app.path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to resolve app data directory: {e}"))
+/// The synced costume data, if there is a readable copy
+fn load_synced(synced_path: &Path) -> Option<CostumeDatabase> {
+    if !synced_path.exists() {
+        return None;
+    }
+    match fs::read_to_string(synced_path) {
+        Ok(json) => match serde_json::from_str::<CostumeDatabase>(&json) {
+            Ok(synced) => Some(synced),
+            Err(e) => {
+                log::warn!("[CostumeService] Ignoring corrupt synced costume data: {e}");
+                None
+            }
+        },
+        Err(e) => {
+            log::warn!("[CostumeService] Failed to read synced costume data: {e}");
+            None
+        }
+    }
 }
 
 /// Build the full database: embedded data overlaid with synced data (if any)
 fn build_database(app: &AppHandle) -> Result<CostumeDatabase, String> {
-    let mut database = embedded_database()?;
-
-    let synced_path = synced_data_path(app)?;
-    if synced_path.exists() {
-        match fs::read_to_string(&synced_path) {
-            Ok(json) => match serde_json::from_str::<CostumeDatabase>(&json) {
-                Ok(synced) => {
-                    let icons_dir = synced_icons_dir(app)?;
-                    overlay_synced(&mut database, synced, &icons_dir);
-                }
-                Err(e) => log::warn!("[CostumeService] Ignoring corrupt synced costume data: {e}"),
-            },
-            Err(e) => log::warn!("[CostumeService] Failed to read synced costume data: {e}"),
+    let synced = load_synced(&synced_data_path(app)?);
+    combine(embedded_database(), synced, &synced_icons_dir(app)?)
+}
+
+/// Overlay `synced` on the embedded data. When the embedded data is broken,
+/// a synced copy stands in for it, so a bad build isn't stuck without
+/// costumes once a sync has succeeded.
+fn combine(
+    embedded: Result<CostumeDatabase, String>,
+    synced: Option<CostumeDatabase>,
+    icons_dir: &Path,
+) -> Result<CostumeDatabase, String> {
+    let mut database = match embedded {
+        Ok(database) => database,
+        Err(e) if synced.is_some() => {
+            log::error!("[CostumeService] {e}; using the synced costume data alone");
+            CostumeDatabase::new()
         }
+        Err(e) => return Err(e),
+    };
+    if let Some(synced) = synced {
+        overlay_synced(&mut database, synced, icons_dir);
     }
-
     Ok(database)
 }
 
@@ -120,9 +197,27 @@ fn store_database(costume_data: CostumeDatabase) {
     let total_costumes: usize = costume_data.values().map(|v| v.len()).sum();
     let character_count = costume_data.len();
 
-    *COSTUME_DATA.lock().unwrap() = Some(costume_data);
+    *COSTUME_DATA.lock().unwrap_or_else(|e| e.into_inner()) = Some(Ok(costume_data));
+
+    log::info!(
+        "[CostumeService] Loaded {total_costumes} costumes for {character_count} characters"
+    );
+}
+
+/// Whether the costume data loaded, with counts or the error
+#[tauri::command]
+pub fn get_costume_service_status() -> Result<CostumeServiceStatus, String> {
+    Ok(status())
+}
 
-    log::info!("👗 Loaded {total_costumes} costumes for {character_count} characters");
+/// Load the costume data again, e.g. after a sync brought a usable copy of
+/// data that failed to load at startup
+#[tauri::command]
+pub async fn retry_costume_initialization(app: AppHandle) -> Result<CostumeServiceStatus, String> {
+    log::info!("[CostumeService] Retrying costume data initialization");
+    // The failure is kept in the status returned
+    let _ = initialize_costume_service(&app);
+    Ok(status())
 }
 
 /// Sync the costume database from the app's GitHub repo: fetch the latest
@@ -130,6 +225,19 @@ fn store_database(costume_data: CostumeDatabase) {
 /// bundle, storing both in the app data directory.
 #[tauri::command]
 pub async fn sync_costumes(app: AppHandle) -> Result<CostumeSyncResult, String> {
+    if crate::demo::is_enabled() {
+        log::info!("[CostumeService] Demo mode, keeping the bundled costume database");
+        let total_costumes = match status() {
+            CostumeServiceStatus::Loaded { costumes, .. } => costumes,
+            _ => 0,
+        };
+        return Ok(CostumeSyncResult {
+            new_costumes: Vec::new(),
+            icons_downloaded: 0,
+            total_costumes,
+        });
+    }
+
     log::info!("[CostumeService] Syncing costume database from GitHub…");
 
     let client = reqwest::Client::builder()
@@ -158,7 +266,7 @@ pub async fn sync_costumes(app: AppHandle) -> Result<CostumeSyncResult, String>
     // Snapshot what we knew before this sync, to report what's new
     let previous_keys: HashSet<(String, String)> = {
         let data = COSTUME_DATA.lock().unwrap();
-        data.as_ref()
+        loaded(&data)
             .map(|db| {
                 db.iter()
                     .flat_map(|(character, costumes)| {
@@ -171,7 +279,9 @@ pub async fn sync_costumes(app: AppHandle) -> Result<CostumeSyncResult, String>
             .unwrap_or_default()
     };
 
-    let embedded = embedded_keys()?;
+    // With broken embedded data every icon counts as not bundled, so the
+    // sync still brings a usable database
+    let embedded = embedded_keys().unwrap_or_default();
     let icons_dir = synced_icons_dir(&app)?;
 
     let mut new_costumes = Vec::new();
@@ -270,8 +380,8 @@ pub fn get_costumes_for_character(character: String) -> Result<Vec<Costume>, Str
 
     let data = COSTUME_DATA.lock().unwrap();
 
-    match data.as_ref() {
-        Some(costume_db) => {
+    match loaded(&data) {
+        Ok(costume_db) => {
             log::info!(
                 "[CostumeService] Database has {} characters",
                 costume_db.len()
@@ -297,9 +407,9 @@ pub fn get_costumes_for_character(character: String) -> Result<Vec<Costume>, Str
                 Ok(Vec::new()) // Return empty vec if character not found
             }
         }
-        None => {
-            log::error!("[CostumeService] Costume data not initialized!");
-            Err("Costume data not initialized".to_string())
+        Err(e) => {
+            log::error!("[CostumeService] {e}");
+            Err(e)
         }
     }
 }
@@ -308,11 +418,16 @@ pub fn get_costumes_for_character(character: String) -> Result<Vec<Costume>, Str
 #[tauri::command]
 pub fn get_all_costumes() -> Result<HashMap<String, Vec<Costume>>, String> {
     let data = COSTUME_DATA.lock().unwrap();
+    loaded(&data).cloned()
+}
 
-    match data.as_ref() {
-        Some(costume_db) => Ok(costume_db.clone()),
-        None => Err("Costume data not initialized".to_string()),
-    }
+/// Whether `character` (display name) has a costume with this ID. `None`
+/// when the costume data can't tell: not loaded yet, or a character it
+/// doesn't cover.
+pub fn costume_exists(character: &str, costume_id: &str) -> Option<bool> {
+    let data = COSTUME_DATA.lock().unwrap_or_else(|e| e.into_inner());
+    let costumes = loaded(&data).ok()?.get(character)?;
+    Some(costumes.iter().any(|c| c.id == costume_id))
 }
 
 /// Get a specific costume by character and costume ID
@@ -326,8 +441,8 @@ pub fn get_costume(character: String, costume_id: String) -> Result<Option<Costu
 
     let data = COSTUME_DATA.lock().unwrap();
 
-    match data.as_ref() {
-        Some(costume_db) => {
+    match loaded(&data) {
+        Ok(costume_db) => {
             if let Some(costumes) = costume_db.get(&character) {
                 let costume = costumes.iter().find(|c| c.id == costume_id).cloned();
                 if costume.is_some() {
@@ -348,10 +463,247 @@ pub fn get_costume(character: String, costume_id: String) -> Result<Option<Costu
                 Ok(None)
             }
         }
-        None => Err("Costume data not initialized".to_string()),
+        Err(e) => Err(e),
     }
 }
 
+// ===== Community contributions =====
+
+/// Version of the costume patch file format
+const COSTUME_PATCH_VERSION: u32 = 1;
+
+/// Costume additions and fixes relative to the data shipped with the app,
+/// in the form community members send upstream
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CostumePatch {
+    pub version: u32,
+    pub characters: BTreeMap<String, CharacterCostumePatch>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CharacterCostumePatch {
+    /// Costumes the shipped data doesn't have
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<CostumePatchEntry>,
+    /// Shipped costumes with a different name or default flag
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed: Vec<CostumePatchEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CostumePatchEntry {
+    pub id: String,
+    pub name: String,
+    /// Icon path relative to the costume icon folder, e.g.
+    /// `spider-man/img_icon_classic.png`
+    pub image_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_default: Option<bool>,
+}
+
+impl From<&Costume> for CostumePatchEntry {
+    fn from(costume: &Costume) -> Self {
+        Self {
+            id: costume.id.clone(),
+            name: costume.name.clone(),
+            image_path: costume.image_path.clone(),
+            is_default: costume.is_default,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostumePatchSummary {
+    pub characters: usize,
+    pub added: usize,
+    pub changed: usize,
+}
+
+impl CostumePatch {
+    fn summary(&self) -> CostumePatchSummary {
+        CostumePatchSummary {
+            characters: self.characters.len(),
+            added: self.characters.values().map(|c| c.added.len()).sum(),
+            changed: self.characters.values().map(|c| c.changed.len()).sum(),
+        }
+    }
+}
+
+/// Result of checking a received costume patch before importing it
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostumePatchValidation {
+    pub valid: bool,
+    /// Problems that make the patch unusable
+    pub errors: Vec<String>,
+    /// Worth a look, but fine to import (e.g. a character new to this build)
+    pub warnings: Vec<String>,
+    /// Counts, when the file parsed
+    pub summary: Option<CostumePatchSummary>,
+}
+
+/// What `merged` adds to or changes in `baseline`, characters and costumes
+/// sorted so the same data always exports the same file
+fn diff_costumes(baseline: &CostumeDatabase, merged: &CostumeDatabase) -> CostumePatch {
+    let mut characters = BTreeMap::new();
+    for (character, costumes) in merged {
+        let shipped = baseline
+            .get(character)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let mut patch = CharacterCostumePatch::default();
+        for costume in costumes {
+            match shipped.iter().find(|c| c.id == costume.id) {
+                None => patch.added.push(costume.into()),
+                Some(known)
+                    if known.name != costume.name || known.is_default != costume.is_default =>
+                {
+                    patch.changed.push(costume.into())
+                }
+                Some(_) => {}
+            }
+        }
+        if patch.added.is_empty() && patch.changed.is_empty() {
+            continue;
+        }
+        patch.added.sort_by(|a, b| a.id.cmp(&b.id));
+        patch.changed.sort_by(|a, b| a.id.cmp(&b.id));
+        characters.insert(character.clone(), patch);
+    }
+    CostumePatch {
+        version: COSTUME_PATCH_VERSION,
+        characters,
+    }
+}
+
+/// Costume ids are lowercase slugs, like the shipped ones
+fn is_costume_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Check a costume patch against the schema and the costumes `database`
+/// already has
+fn validate_costume_patch(json: &str, database: &CostumeDatabase) -> CostumePatchValidation {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let patch: CostumePatch = match serde_json::from_str(json) {
+        Ok(patch) => patch,
+        Err(e) => {
+            return CostumePatchValidation {
+                valid: false,
+                errors: vec![format!("Not a costume patch: {e}")],
+                warnings,
+                summary: None,
+            }
+        }
+    };
+    if patch.version != COSTUME_PATCH_VERSION {
+        errors.push(format!(
+            "Unsupported patch version {} (expected {COSTUME_PATCH_VERSION})",
+            patch.version
+        ));
+    }
+
+    for (character, changes) in &patch.characters {
+        let known = database.get(character);
+        if known.is_none() {
+            warnings.push(format!("{character} is not a known character"));
+        }
+        let mut seen = HashSet::new();
+        for (entry, is_added) in changes
+            .added
+            .iter()
+            .map(|e| (e, true))
+            .chain(changes.changed.iter().map(|e| (e, false)))
+        {
+            let label = format!("{character}: {}", entry.id);
+            if !is_costume_id(&entry.id) {
+                errors.push(format!(
+                    "{label}: id must be lowercase letters, digits and dashes"
+                ));
+            }
+            if !seen.insert(entry.id.as_str()) {
+                errors.push(format!("{label}: listed more than once"));
+            }
+            if entry.name.trim().is_empty() {
+                errors.push(format!("{label}: name is empty"));
+            }
+            let image = Path::new(&entry.image_path);
+            if !entry.image_path.ends_with(".png")
+                || entry.image_path.contains('\\')
+                || image.is_absolute()
+                || image
+                    .components()
+                    .any(|c| c == std::path::Component::ParentDir)
+            {
+                errors.push(format!(
+                    "{label}: image path must be a relative .png path, got {:?}",
+                    entry.image_path
+                ));
+            }
+            let existing = known.and_then(|costumes| costumes.iter().find(|c| c.id == entry.id));
+            match (is_added, existing) {
+                (true, Some(costume)) => {
+                    errors.push(format!("{label}: already exists as \"{}\"", costume.name))
+                }
+                (false, None) => {
+                    errors.push(format!("{label}: changes a costume that doesn't exist"))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    CostumePatchValidation {
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+        summary: Some(patch.summary()),
+    }
+}
+
+/// Write the user's costume additions and fixes (synced or otherwise not
+/// shipped with this build) to `dest_path` as a patch file for submitting
+/// upstream
+#[tauri::command]
+pub fn export_costume_overrides(dest_path: String) -> Result<CostumePatchSummary, String> {
+    let patch = {
+        let data = COSTUME_DATA.lock().unwrap();
+        let merged = loaded(&data)?;
+        diff_costumes(&embedded_database()?, merged)
+    };
+    let json = serde_json::to_string_pretty(&patch)
+        .map_err(|e| format!("Failed to serialize costume patch: {e}"))?;
+    fs::write(&dest_path, json).map_err(|e| format!("Failed to write costume patch: {e}"))?;
+
+    let summary = patch.summary();
+    log::info!(
+        "[CostumeService] Exported {} added and {} changed costume(s) to {}",
+        summary.added,
+        summary.changed,
+        dest_path
+    );
+    Ok(summary)
+}
+
+/// Check a received costume patch for schema errors and id collisions with
+/// the current costume data, before it is imported
+#[tauri::command]
+pub fn validate_costume_override_file(path: String) -> Result<CostumePatchValidation, String> {
+    let json =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read costume patch: {e}"))?;
+    let data = COSTUME_DATA.lock().unwrap();
+    let database = loaded(&data)?;
+    Ok(validate_costume_patch(&json, database))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -361,6 +713,39 @@ mod tests {
         store_database(embedded_database().unwrap());
     }
 
+    #[test]
+    fn test_status_reports_counts_once_loaded() {
+        initialize_embedded();
+        let database = embedded_database().unwrap();
+        assert_eq!(
+            get_costume_service_status().unwrap(),
+            CostumeServiceStatus::Loaded {
+                characters: database.len(),
+                costumes: database.values().map(Vec::len).sum(),
+            }
+        );
+        assert_eq!(
+            loaded(&Some(Err("bad json".to_string()))).unwrap_err(),
+            "Costume data failed to load: bad json"
+        );
+        assert!(loaded(&None).is_err());
+    }
+
+    #[test]
+    fn test_synced_data_stands_in_for_broken_embedded_data() {
+        let icons_dir = Path::new("icons");
+        let broken = || Err("Failed to parse costume data".to_string());
+        assert_eq!(
+            combine(broken(), None, icons_dir).unwrap_err(),
+            "Failed to parse costume data"
+        );
+
+        let mut synced: CostumeDatabase = HashMap::new();
+        synced.insert("Hulk".to_string(), vec![costume("classic", "Classic")]);
+        let database = combine(broken(), Some(synced), icons_dir).unwrap();
+        assert_eq!(database["Hulk"][0].id, "classic");
+    }
+
     #[test]
     fn test_costume_data_loads() {
         let costume_db = embedded_database().expect("Failed to load costume data");
@@ -441,4 +826,89 @@ mod tests {
         // Existing costume untouched
         assert!(costumes.iter().any(|c| c.id == "default"));
     }
+
+    fn costume(id: &str, name: &str) -> Costume {
+        Costume {
+            id: id.to_string(),
+            name: name.to_string(),
+            image_path: format!("storm/img_icon_{id}.png"),
+            is_default: None,
+            local_icon_path: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_costumes_exports_only_additions_and_changes() {
+        let mut baseline: CostumeDatabase = HashMap::new();
+        baseline.insert(
+            "Storm".to_string(),
+            vec![costume("default", "Default"), costume("typo", "Wether")],
+        );
+        let mut merged = baseline.clone();
+        let storm = merged.get_mut("Storm").unwrap();
+        storm[1].name = "Weather".to_string();
+        storm.push(Costume {
+            local_icon_path: Some("/appdata/costume-icons/storm/img_icon_new.png".to_string()),
+            ..costume("new", "New")
+        });
+        merged.insert("Blade".to_string(), vec![costume("default", "Default")]);
+
+        let patch = diff_costumes(&baseline, &merged);
+        assert_eq!(
+            patch.characters.keys().collect::<Vec<_>>(),
+            vec!["Blade", "Storm"]
+        );
+        let storm = &patch.characters["Storm"];
+        assert_eq!(
+            storm.added,
+            vec![CostumePatchEntry::from(&costume("new", "New"))]
+        );
+        assert_eq!(storm.changed[0].name, "Weather");
+
+        // The export is what validation expects, and applies cleanly to the
+        // baseline but not twice
+        let json = serde_json::to_string(&patch).unwrap();
+        assert!(!json.contains("localIconPath"));
+        assert!(validate_costume_patch(&json, &baseline).valid);
+        let validation = validate_costume_patch(&json, &merged);
+        assert!(!validation.valid);
+        assert!(validation
+            .errors
+            .iter()
+            .any(|e| e.contains("already exists")));
+    }
+
+    #[test]
+    fn test_validate_costume_patch_reports_bad_entries() {
+        let mut database: CostumeDatabase = HashMap::new();
+        database.insert("Storm".to_string(), vec![costume("default", "Default")]);
+
+        let json = r#"{
+            "version": 1,
+            "characters": {
+                "Storm": {
+                    "added": [
+                        { "id": "Bad Id", "name": "", "imagePath": "../evil.exe" },
+                        { "id": "twice", "name": "Twice", "imagePath": "storm/a.png" },
+                        { "id": "twice", "name": "Twice", "imagePath": "storm/a.png" }
+                    ],
+                    "changed": [{ "id": "ghost", "name": "Ghost", "imagePath": "storm/g.png" }]
+                },
+                "Newcomer": { "added": [{ "id": "default", "name": "Default", "imagePath": "newcomer/d.png" }] }
+            }
+        }"#;
+        let validation = validate_costume_patch(json, &database);
+        assert!(!validation.valid);
+        assert_eq!(validation.errors.len(), 5, "{:?}", validation.errors);
+        assert_eq!(
+            validation.warnings,
+            vec!["Newcomer is not a known character"]
+        );
+        assert_eq!(validation.summary.unwrap().added, 4);
+
+        let validation =
+            validate_costume_patch(r#"{"version":1,"characters":{},"extra":1}"#, &database);
+        assert!(!validation.valid);
+        assert!(validation.summary.is_none());
+    }
 }
@@ -0,0 +1,615 @@
+//! Demo mode, for trying the app, taking screenshots or reproducing a bug
+//! without a game install. Launched with `--demo`, every service runs
+//! against a sandbox in the temp folder instead of the real app data: a
+//! game folder skeleton holding a few dozen tiny mods across characters and
+//! categories, with metadata and placeholder thumbnails. The sandbox is
+//! generated on first use and kept between demo runs; delete the folder to
+//! start over.
+//!
+//! Nothing in demo mode goes online. Nexus requests, mod update checks,
+//! costume syncs and thumbnail downloads answer with canned data instead.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use chrono::{Duration, Utc};
+use image::{ImageFormat, RgbImage};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+
+use crate::mod_service::PAK_MAGIC;
+use crate::thumbnail_service::ThumbnailService;
+use crate::types::{AppSettings, Character, ModCategory, ModMetadata};
+
+/// Launch argument switching to the demo library for that session
+pub const LAUNCH_ARG: &str = "--demo";
+
+/// The sandbox's folder in the temp directory
+const SANDBOX_FOLDER: &str = "marvel-rivals-mod-manager-demo";
+
+/// Written once the sandbox is complete. A sandbox without it was cut short
+/// and is generated again.
+const MARKER_FILE: &str = ".demo-library";
+
+/// Mount point of the fake paks, the one real mods use
+const MOUNT_POINT: &str = "../../../Marvel/Content/";
+
+/// The game's own paks in the skeleton, so the build fingerprint has
+/// something to hash
+const GAME_PAKS: &[&str] = &[
+    "pakchunk0-Windows.pak",
+    "pakchunk1-Windows.pak",
+    "pakchunkCharacter-Windows.pak",
+];
+
+/// The demo library. Its position decides the rest: every fifth mod is
+/// disabled, every seventh a favorite, and the first few come from the
+/// canned Nexus catalogue, with an update waiting.
+const DEMO_MODS: &[(&str, ModCategory, Option<Character>, &str)] = &[
+    (
+        "Midnight Suit",
+        ModCategory::Skins,
+        Some(Character::SpiderMan),
+        "webhead",
+    ),
+    (
+        "Classic Comic Venom",
+        ModCategory::Skins,
+        Some(Character::Venom),
+        "symbiote_lab",
+    ),
+    (
+        "Golden Age Cap",
+        ModCategory::Skins,
+        Some(Character::CaptainAmerica),
+        "starspangled",
+    ),
+    (
+        "Worthy Armor",
+        ModCategory::Skins,
+        Some(Character::Thor),
+        "asgard_forge",
+    ),
+    (
+        "Gamma Glow",
+        ModCategory::Skins,
+        Some(Character::Hulk),
+        "greenroom",
+    ),
+    (
+        "Weather Witch",
+        ModCategory::Skins,
+        Some(Character::Storm),
+        "windrider",
+    ),
+    (
+        "Arcade Sneaking Suit",
+        ModCategory::Skins,
+        Some(Character::BlackWidow),
+        "redroom",
+    ),
+    (
+        "Limbo Queen",
+        ModCategory::Skins,
+        Some(Character::Magik),
+        "darkchild",
+    ),
+    (
+        "Trash Can Groot",
+        ModCategory::Skins,
+        Some(Character::Groot),
+        "barkbark",
+    ),
+    (
+        "Ice Palace",
+        ModCategory::Skins,
+        Some(Character::LunaSnow),
+        "snowfall",
+    ),
+    (
+        "Disco Loki",
+        ModCategory::Skins,
+        Some(Character::Loki),
+        "trickster",
+    ),
+    (
+        "Brown Jacket Logan",
+        ModCategory::Skins,
+        Some(Character::Wolverine),
+        "berserker",
+    ),
+    (
+        "Sorcerer Supreme Robes",
+        ModCategory::Skins,
+        Some(Character::DoctorStrange),
+        "kamartaj",
+    ),
+    (
+        "Neon Rocket",
+        ModCategory::Skins,
+        Some(Character::RocketRaccoon),
+        "trashpanda",
+    ),
+    (
+        "Blue Flame",
+        ModCategory::Skins,
+        Some(Character::HumanTorch),
+        "flameon",
+    ),
+    (
+        "Pink Shark",
+        ModCategory::Skins,
+        Some(Character::JeffTheLandShark),
+        "jeffsworld",
+    ),
+    ("Quieter Footsteps", ModCategory::Audio, None, "soundcheck"),
+    ("Retro Announcer", ModCategory::Audio, None, "soundcheck"),
+    (
+        "Thunderclap Ult Voice",
+        ModCategory::Audio,
+        Some(Character::Thor),
+        "asgard_forge",
+    ),
+    ("Lo-fi Menu Music", ModCategory::Audio, None, "chillbeats"),
+    ("Minimal HUD", ModCategory::UI, None, "cleanhud"),
+    ("Bigger Kill Feed", ModCategory::UI, None, "cleanhud"),
+    ("Dark Scoreboard", ModCategory::UI, None, "nightshift"),
+    (
+        "Colorblind Team Outlines",
+        ModCategory::UI,
+        None,
+        "seeclear",
+    ),
+    ("No Camera Shake", ModCategory::Gameplay, None, "steadycam"),
+    (
+        "Clearer Ability Effects",
+        ModCategory::Gameplay,
+        None,
+        "steadycam",
+    ),
+    (
+        "Brighter Night Maps",
+        ModCategory::Gameplay,
+        None,
+        "nightshift",
+    ),
+    ("Dot Crosshair", ModCategory::Other, None, "aimlab"),
+    (
+        "Comic Loading Screens",
+        ModCategory::Other,
+        None,
+        "panelpages",
+    ),
+    ("Skip Intro Videos", ModCategory::Other, None, "fastboot"),
+];
+
+/// How many demo mods are linked to the canned Nexus catalogue
+const NEXUS_LINKED: usize = 4;
+
+/// Nexus page IDs of the canned catalogue start here, in library order
+const NEXUS_ID_BASE: i32 = 1000;
+
+/// Version of every mod in the canned catalogue; linked mods are installed
+/// at an older one
+const NEXUS_VERSION: &str = "1.1.0";
+const INSTALLED_VERSION: &str = "1.0.0";
+
+/// Thumbnail colours, picked by position
+const PALETTE: &[[u8; 3]] = &[
+    [0xE6, 0x39, 0x46],
+    [0x45, 0x7B, 0x9D],
+    [0x2A, 0x9D, 0x8F],
+    [0xE9, 0xC4, 0x6A],
+    [0xF4, 0xA2, 0x61],
+    [0x6D, 0x59, 0x7A],
+    [0x26, 0x46, 0x53],
+    [0xB5, 0x83, 0x8D],
+];
+
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+pub fn is_enabled() -> bool {
+    DATA_DIR.get().is_some()
+}
+
+/// The sandbox's app data folder, while in demo mode
+pub fn data_dir() -> Option<&'static Path> {
+    DATA_DIR.get().map(PathBuf::as_path)
+}
+
+/// Where the sandbox lives
+pub fn sandbox_root() -> PathBuf {
+    std::env::temp_dir().join(SANDBOX_FOLDER)
+}
+
+/// Switch to the sandbox if the launch arguments ask for demo mode,
+/// generating it the first time. Runs before anything reads app data; if
+/// the sandbox can't be made, the app starts on the real library.
+pub fn init(app: &AppHandle, args: impl IntoIterator<Item = String>) {
+    if !args.into_iter().any(|arg| arg == LAUNCH_ARG) {
+        return;
+    }
+    match ensure_sandbox(&sandbox_root()) {
+        Ok(data_dir) => {
+            log::info!("[demo] Demo mode on, using {}", data_dir.display());
+            // Thumbnails load through the asset protocol, which is only
+            // scoped to the real app data
+            if let Err(e) = app.asset_protocol_scope().allow_directory(&data_dir, true) {
+                log::warn!("[demo] Failed to allow thumbnails to load: {e}");
+            }
+            let _ = DATA_DIR.set(data_dir);
+        }
+        Err(e) => log::error!("[demo] Failed to create the demo library: {e}"),
+    }
+}
+
+/// The sandbox's app data folder under `root`, generating the sandbox if it
+/// isn't there or was left incomplete
+pub fn ensure_sandbox(root: &Path) -> Result<PathBuf, String> {
+    let data_dir = root.join("data");
+    if root.join(MARKER_FILE).exists() {
+        return Ok(data_dir);
+    }
+    if root.exists() {
+        fs::remove_dir_all(root)
+            .map_err(|e| format!("Failed to clear incomplete demo library: {e}"))?;
+    }
+
+    log::info!("[demo] Generating demo library in {}", root.display());
+    generate(root, &data_dir)?;
+    fs::write(root.join(MARKER_FILE), "")
+        .map_err(|e| format!("Failed to finish demo library: {e}"))?;
+    Ok(data_dir)
+}
+
+fn generate(root: &Path, data_dir: &Path) -> Result<(), String> {
+    let game_dir = root.join("MarvelRivals");
+    let paks_dir = game_dir
+        .join("MarvelGame")
+        .join("Marvel")
+        .join("Content")
+        .join("Paks");
+    for name in GAME_PAKS {
+        write_file(&paks_dir.join(name), &fake_pak(name))?;
+    }
+
+    let settings = AppSettings {
+        game_directory: Some(game_dir),
+        auto_detect_game_dir: false,
+        auto_check_updates: false,
+        ..AppSettings::default()
+    };
+    let json = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize demo settings: {e}"))?;
+    write_file(&data_dir.join("settings.json"), json.as_bytes())?;
+
+    let service = crate::mod_service_for_data_dir(data_dir, &settings)?;
+    let thumbnails = ThumbnailService::new(data_dir.join("metadata"));
+    let staging = root.join("staging");
+    for (index, (title, category, character, author)) in DEMO_MODS.iter().enumerate() {
+        let metadata = demo_metadata(index, title, category, character, author);
+        let pak = write_fake_mod(&staging, title)?;
+        let folder = service.expected_folder_for(&metadata);
+        let folder = folder
+            .strip_prefix(service.mods_directory())
+            .map_err(|_| format!("Demo mod folder outside ~mods: {}", folder.display()))?;
+        let mut installed = service.install_mod_to_folder_with_metadata(
+            &pak,
+            &folder.to_string_lossy(),
+            metadata,
+        )?;
+        if index % 5 == 4 {
+            installed = service.enable_mod(&installed.id, false)?;
+        }
+        write_thumbnail(&thumbnails.get_thumbnail_path(&installed.id), index)?;
+    }
+    service.flush_pending_metadata()?;
+    fs::remove_dir_all(&staging).map_err(|e| format!("Failed to clean up demo staging: {e}"))?;
+
+    log::info!("[demo] Generated {} demo mods", DEMO_MODS.len());
+    Ok(())
+}
+
+fn demo_metadata(
+    index: usize,
+    title: &str,
+    category: &ModCategory,
+    character: &Option<Character>,
+    author: &str,
+) -> ModMetadata {
+    let installed = Utc::now() - Duration::days(index as i64 * 3);
+    let nexus_id = (index < NEXUS_LINKED).then_some(NEXUS_ID_BASE + index as i32);
+    ModMetadata {
+        title: title.to_string(),
+        subtitle: None,
+        description: describe(title, category, character),
+        author: Some(author.to_string()),
+        version: Some(INSTALLED_VERSION.to_string()),
+        tags: vec!["demo".to_string()],
+        category: category.clone(),
+        character: character.clone(),
+        costume: None,
+        is_favorite: index % 7 == 0,
+        is_nsfw: false,
+        created_at: installed,
+        updated_at: installed,
+        install_date: installed,
+        profile_ids: None,
+        nexus_mod_id: nexus_id,
+        nexus_file_id: nexus_id.map(|id| id * 10 + 1),
+        nexus_version: nexus_id.map(|_| INSTALLED_VERSION.to_string()),
+        original_folder_path: None,
+        parent_mod_id: None,
+        disabled_reason: None,
+        disabled_at: None,
+        last_verified_game_build: None,
+        manage_layout: true,
+        file_fingerprint: None,
+        externally_modified_at: None,
+        collision_folder: None,
+        priority: None,
+        notes: None,
+        history: Vec::new(),
+        variant_group: None,
+    }
+}
+
+fn describe(title: &str, category: &ModCategory, character: &Option<Character>) -> String {
+    match character {
+        Some(character) => format!("{title}: a demo {category} mod for {character}."),
+        None => format!("{title}: a demo {category} mod."),
+    }
+}
+
+/// `Title_P`, the way pak names are usually written
+fn pak_stem(title: &str) -> String {
+    let name: String = title
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+    format!("{name}_P")
+}
+
+fn write_file(path: &Path, contents: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("Failed to write {path:?}: {e}"))
+}
+
+/// A length-prefixed, NUL-terminated string as paks store them
+fn fstring(text: &str) -> Vec<u8> {
+    let mut out = ((text.len() + 1) as i32).to_le_bytes().to_vec();
+    out.extend_from_slice(text.as_bytes());
+    out.push(0);
+    out
+}
+
+/// A pak with `seed` as its data (so no two are identical), an index
+/// holding the mount point, and a version 11 footer pointing at it
+fn fake_pak(seed: &str) -> Vec<u8> {
+    let mut pak = seed.as_bytes().to_vec();
+    let index_offset = pak.len() as u64;
+    let index = fstring(MOUNT_POINT);
+    pak.extend_from_slice(&index);
+    // Encryption key GUID and flag: not encrypted
+    pak.extend_from_slice(&[0u8; 17]);
+    pak.extend_from_slice(&PAK_MAGIC.to_le_bytes());
+    pak.extend_from_slice(&11u32.to_le_bytes());
+    pak.extend_from_slice(&index_offset.to_le_bytes());
+    pak.extend_from_slice(&(index.len() as u64).to_le_bytes());
+    // Index hash
+    pak.extend_from_slice(&[0u8; 20]);
+    pak
+}
+
+/// A .utoc header followed by a directory index holding the mount point
+fn fake_utoc() -> Vec<u8> {
+    let mut utoc = b"-==--==--==--==-".to_vec();
+    utoc.push(3);
+    utoc.resize(0x90, 0);
+    utoc.extend_from_slice(&fstring(MOUNT_POINT));
+    utoc
+}
+
+/// Write a pak with its .ucas and .utoc into `dir`. Returns the pak.
+fn write_fake_mod(dir: &Path, title: &str) -> Result<PathBuf, String> {
+    let stem = pak_stem(title);
+    let pak = dir.join(format!("{stem}.pak"));
+    write_file(&pak, &fake_pak(title))?;
+    write_file(&dir.join(format!("{stem}.ucas")), title.as_bytes())?;
+    write_file(&dir.join(format!("{stem}.utoc")), &fake_utoc())?;
+    Ok(pak)
+}
+
+/// A fake mod standing in for a Nexus download, in the sandbox's downloads
+/// folder
+pub fn fake_download(mod_name: &str) -> Result<PathBuf, String> {
+    write_fake_mod(&sandbox_root().join("downloads"), mod_name)
+}
+
+/// A 16:9 placeholder, shaded from a palette colour picked by `index`
+pub fn placeholder_thumbnail(index: usize) -> RgbImage {
+    let [r, g, b] = PALETTE[index % PALETTE.len()];
+    let (width, height) = (320, 180);
+    RgbImage::from_fn(width, height, |x, y| {
+        let shade = 1.0 - 0.5 * (x + y) as f32 / (width + height) as f32;
+        let scale = |channel: u8| (channel as f32 * shade) as u8;
+        image::Rgb([scale(r), scale(g), scale(b)])
+    })
+}
+
+fn write_thumbnail(path: &Path, index: usize) -> Result<(), String> {
+    placeholder_thumbnail(index)
+        .save_with_format(path, ImageFormat::Png)
+        .map_err(|e| format!("Failed to write demo thumbnail: {e}"))
+}
+
+/// A mod in the canned Nexus catalogue, shaped like the v1 API's mod
+/// endpoint
+fn nexus_mod(position: usize) -> Option<Value> {
+    let (title, category, character, author) = DEMO_MODS.get(position)?;
+    let updated = 1_760_000_000 - position as i64 * 86_400;
+    Some(json!({
+        "mod_id": NEXUS_ID_BASE + position as i32,
+        "name": title,
+        "summary": describe(title, category, character),
+        "picture_url": null,
+        "version": NEXUS_VERSION,
+        "author": author,
+        "uploaded_by": author,
+        "endorsement_count": 50 * (DEMO_MODS.len() - position),
+        "mod_downloads": 900 * (DEMO_MODS.len() - position),
+        "updated_timestamp": updated,
+        "created_timestamp": updated - 30 * 86_400,
+        "contains_adult_content": false,
+        "status": "published",
+        "available": true,
+    }))
+}
+
+/// Position in the catalogue of a Nexus page ID
+fn catalogue_position(segment: &str) -> Option<usize> {
+    let id: i32 = segment.trim_end_matches(".json").parse().ok()?;
+    usize::try_from(id - NEXUS_ID_BASE).ok()
+}
+
+/// The canned answer to a Nexus v1 API request, `endpoint` being the path
+/// after `/v1` as the frontend builds it
+pub fn nexus_response(endpoint: &str) -> Result<Value, String> {
+    let path = endpoint.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    let not_found = || format!("No demo data for {endpoint}");
+
+    let response = match segments.as_slice() {
+        ["users", "validate.json"] => json!({ "name": "Demo User", "is_premium": true }),
+        ["games", _, "mods", "latest_added.json" | "latest_updated.json" | "trending.json"] => {
+            Value::Array((0..10).filter_map(nexus_mod).collect())
+        }
+        ["games", _, "mods", "updated.json"] => Value::Array(
+            (0..DEMO_MODS.len())
+                .filter_map(nexus_mod)
+                .map(|listing| {
+                    json!({
+                        "mod_id": listing["mod_id"],
+                        "latest_file_update": listing["updated_timestamp"],
+                        "latest_mod_activity": listing["updated_timestamp"],
+                    })
+                })
+                .collect(),
+        ),
+        ["games", _, "mods", id] => catalogue_position(id)
+            .and_then(nexus_mod)
+            .ok_or_else(not_found)?,
+        ["games", _, "mods", id, "files.json"] => {
+            let listing = catalogue_position(id)
+                .and_then(nexus_mod)
+                .ok_or_else(not_found)?;
+            let mod_id = listing["mod_id"].as_i64().unwrap_or_default();
+            json!({
+                "files": [{
+                    "file_id": mod_id * 10 + 1,
+                    "name": listing["name"],
+                    "version": NEXUS_VERSION,
+                    "category_name": "MAIN",
+                    "size_kb": 1,
+                    "uploaded_timestamp": listing["updated_timestamp"],
+                    "description": "Demo file",
+                }]
+            })
+        }
+        ["games", _, "mods", id, "files", file_id, "download_link.json"] => json!([{
+            "name": "Demo",
+            "short_name": "Demo",
+            "URI": format!("demo://mods/{id}/files/{file_id}"),
+        }]),
+        _ => return Err(not_found()),
+    };
+    Ok(response)
+}
+
+/// The canned answer to a Nexus API request, which the frontend asks for
+/// in place of going online while in demo mode
+#[tauri::command]
+pub async fn demo_nexus_request(endpoint: String) -> Result<Value, String> {
+    if !is_enabled() {
+        return Err("Demo mode is off".to_string());
+    }
+    nexus_response(&endpoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_library() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path().join("demo");
+        let data_dir = ensure_sandbox(&root).unwrap();
+
+        let settings = crate::parse_settings_file(&data_dir.join("settings.json")).unwrap();
+        let service = crate::mod_service_for_data_dir(&data_dir, &settings).unwrap();
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), DEMO_MODS.len());
+        assert_eq!(
+            mods.iter().filter(|m| !m.enabled).count(),
+            DEMO_MODS.len() / 5
+        );
+        assert!(mods.iter().all(|m| m.thumbnail_path.is_some()));
+        assert!(mods.iter().all(|m| m.associated_files.len() == 3));
+        assert_eq!(
+            mods.iter()
+                .filter(|m| m.metadata.nexus_mod_id.is_some())
+                .count(),
+            NEXUS_LINKED
+        );
+
+        // The fake paks read like real ones
+        let info = service.get_pak_technical_info(&mods[0].id).unwrap();
+        assert!(info.errors.is_empty(), "{:?}", info.errors);
+        assert_eq!(info.pak_version, Some(11));
+        assert_eq!(info.pak_mount_point.as_deref(), Some(MOUNT_POINT));
+        assert!(service.verify_mods().unwrap().is_empty());
+
+        // Generated once: later runs keep what the user did to it
+        service
+            .delete_mods(&[mods[0].id.clone()], true, true)
+            .unwrap();
+        assert_eq!(ensure_sandbox(&root).unwrap(), data_dir);
+        assert_eq!(service.get_all_mods().unwrap().len(), DEMO_MODS.len() - 1);
+
+        // Unless the first run didn't finish
+        fs::remove_file(root.join(MARKER_FILE)).unwrap();
+        ensure_sandbox(&root).unwrap();
+        let service = crate::mod_service_for_data_dir(&data_dir, &settings).unwrap();
+        assert_eq!(service.get_all_mods().unwrap().len(), DEMO_MODS.len());
+    }
+
+    #[test]
+    fn test_canned_nexus_responses() {
+        assert_eq!(
+            nexus_response("/users/validate.json").unwrap()["is_premium"],
+            true
+        );
+        let latest = nexus_response("/games/marvelrivals/mods/latest_added.json").unwrap();
+        assert_eq!(latest.as_array().unwrap().len(), 10);
+
+        let listing = nexus_response("/games/marvelrivals/mods/1001.json").unwrap();
+        assert_eq!(listing["name"], DEMO_MODS[1].0);
+        assert_eq!(listing["version"], NEXUS_VERSION);
+        let files = nexus_response("/games/marvelrivals/mods/1001/files.json").unwrap();
+        assert_eq!(files["files"][0]["file_id"], 10011);
+        let links = nexus_response(
+            "/games/marvelrivals/mods/1001/files/10011/download_link.json?key=k&expires=1",
+        )
+        .unwrap();
+        assert!(links[0]["URI"].as_str().unwrap().starts_with("demo://"));
+
+        let updated = nexus_response("/games/marvelrivals/mods/updated.json?period=1w").unwrap();
+        assert_eq!(updated.as_array().unwrap().len(), DEMO_MODS.len());
+        assert!(nexus_response("/games/marvelrivals/mods/5.json").is_err());
+        assert!(nexus_response("/colleges/mods.json").is_err());
+    }
+}
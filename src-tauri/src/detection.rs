@@ -0,0 +1,247 @@
+//! Guessing a mod's category and character from where its pak sits and what
+//! it is called, along with how far the guess can be trusted. A folder named
+//! after a character is near certain; "storm" somewhere in a file name could
+//! be Storm, Human Torch or Invisible Woman.
+
+use std::path::{Component, Path};
+
+use crate::types::{Character, DetectionConfidence, MetadataSuggestion, ModCategory};
+
+/// Characters in the order they are tried; the first match wins
+const DETECTABLE_CHARACTERS: &[Character] = &[
+    // Vanguards
+    Character::CaptainAmerica,
+    Character::DoctorStrange,
+    Character::Groot,
+    Character::Hulk,
+    Character::Magneto,
+    Character::PeniParker,
+    Character::TheThing,
+    Character::Thor,
+    Character::Venom,
+    // Duelists
+    Character::Angela,
+    Character::Blade,
+    Character::BlackPanther,
+    Character::BlackWidow,
+    Character::Daredevil,
+    Character::EmmaFrost,
+    Character::Hawkeye,
+    Character::Hela,
+    Character::HumanTorch,
+    Character::IronFist,
+    Character::Magik,
+    Character::MisterFantastic,
+    Character::MoonKnight,
+    Character::Namor,
+    Character::Phoenix,
+    Character::Psylocke,
+    Character::ScarletWitch,
+    Character::SpiderMan,
+    Character::SquirrelGirl,
+    Character::StarLord,
+    Character::Storm,
+    Character::ThePunisher,
+    Character::Ultron,
+    Character::WinterSoldier,
+    Character::Wolverine,
+    // Strategists
+    Character::AdamWarlock,
+    Character::CloakAndDagger,
+    Character::InvisibleWoman,
+    Character::IronMan,
+    Character::JeffTheLandShark,
+    Character::Loki,
+    Character::LunaSnow,
+    Character::Mantis,
+    Character::RocketRaccoon,
+];
+
+/// Category and character for a pak at `file_path`. Only folders below one
+/// of `roots` count as evidence (a user folder named "Logan" says nothing
+/// about the mod); paths outside every root, such as paths inside an
+/// archive, use all their folders.
+pub fn suggest_metadata(file_path: &Path, file_name: &str, roots: &[&Path]) -> MetadataSuggestion {
+    let folders = folder_names(file_path, roots);
+    let (category, category_confidence) = detect_category(&folders, file_name);
+    let character = detect_character(&folders, file_name);
+    MetadataSuggestion {
+        category,
+        category_confidence,
+        character_confidence: character.as_ref().map(|(_, confidence)| *confidence),
+        character: character.map(|(character, _)| character),
+    }
+}
+
+/// Lowercased names of the folders holding the file, below the first root
+/// it is in, with separators like '-' and ' ' dropped
+fn folder_names(file_path: &Path, roots: &[&Path]) -> Vec<String> {
+    let relative = roots
+        .iter()
+        .find_map(|root| file_path.strip_prefix(root).ok())
+        .unwrap_or(file_path);
+    let parent = relative.parent().unwrap_or(Path::new(""));
+    parent
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .map(|name| {
+            name.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect()
+        })
+        .collect()
+}
+
+fn detect_category(folders: &[String], file_name: &str) -> (ModCategory, DetectionConfidence) {
+    for category in [
+        ModCategory::Skins,
+        ModCategory::UI,
+        ModCategory::Audio,
+        ModCategory::Gameplay,
+    ] {
+        let name = category.to_string().to_lowercase();
+        if folders.contains(&name) {
+            return (category, DetectionConfidence::High);
+        }
+    }
+    // "Other" is a common folder name, so only trust it at the top level
+    if folders.first().is_some_and(|folder| folder == "other") {
+        return (ModCategory::Other, DetectionConfidence::High);
+    }
+
+    let lower_name = file_name.to_lowercase();
+    for category in [
+        ModCategory::UI,
+        ModCategory::Audio,
+        ModCategory::Skins,
+        ModCategory::Gameplay,
+    ] {
+        let matched = category
+            .keywords()
+            .iter()
+            .filter(|keyword| lower_name.contains(*keyword))
+            .count();
+        if matched > 0 {
+            return (category, keyword_confidence(matched, false, false));
+        }
+    }
+
+    (ModCategory::Skins, DetectionConfidence::Low) // Default
+}
+
+fn detect_character(
+    folders: &[String],
+    file_name: &str,
+) -> Option<(Character, DetectionConfidence)> {
+    for character in DETECTABLE_CHARACTERS {
+        if character
+            .keywords()
+            .iter()
+            .any(|keyword| folders.iter().any(|folder| folder == keyword))
+        {
+            return Some((character.clone(), DetectionConfidence::High));
+        }
+    }
+
+    // How many of a character's keywords the name contains, and whether one
+    // is the first: the full name ("blackwidow"). The rest are nicknames and
+    // first names that several characters share.
+    let lower_name = file_name.to_lowercase();
+    let score = |character: &Character| -> (usize, bool) {
+        let keywords = character.keywords();
+        let matched = keywords
+            .iter()
+            .filter(|keyword| lower_name.contains(*keyword))
+            .count();
+        (matched, lower_name.contains(keywords[0]))
+    };
+    let (index, character, (matched, full_name)) = DETECTABLE_CHARACTERS
+        .iter()
+        .enumerate()
+        .map(|(index, character)| (index, character, score(character)))
+        .find(|(_, _, (matched, _))| *matched > 0)?;
+    let contested = DETECTABLE_CHARACTERS[index + 1..].iter().any(|other| {
+        let (other_matched, other_full_name) = score(other);
+        other_full_name || (!full_name && other_matched >= matched)
+    });
+    Some((
+        character.clone(),
+        keyword_confidence(matched, full_name, contested),
+    ))
+}
+
+/// Several keywords, or an unmistakable one, make a filename guess
+/// reasonable; a single generic word, or one that fits another character
+/// just as well, is a wild guess
+fn keyword_confidence(matched: usize, full_name: bool, contested: bool) -> DetectionConfidence {
+    if !contested && (matched >= 2 || full_name) {
+        DetectionConfidence::Medium
+    } else {
+        DetectionConfidence::Low
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggest(path: &str) -> MetadataSuggestion {
+        let path = Path::new(path);
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+        suggest_metadata(path, file_name, &[Path::new("/game/~mods")])
+    }
+
+    #[test]
+    fn test_folder_matches_are_confident() {
+        let suggestion = suggest("/game/~mods/Skins/Black-Widow/red_P.pak");
+        assert_eq!(suggestion.category, ModCategory::Skins);
+        assert_eq!(suggestion.category_confidence, DetectionConfidence::High);
+        assert_eq!(suggestion.character, Some(Character::BlackWidow));
+        assert_eq!(
+            suggestion.character_confidence,
+            Some(DetectionConfidence::High)
+        );
+
+        // Folders above the mods folder don't count
+        let suggestion = suggest_metadata(
+            Path::new("/home/logan/~mods/thing_P.pak"),
+            "thing_P.pak",
+            &[Path::new("/home/logan/~mods")],
+        );
+        assert_eq!(suggestion.character, Some(Character::TheThing));
+        assert_eq!(
+            suggestion.character_confidence,
+            Some(DetectionConfidence::Medium)
+        );
+    }
+
+    #[test]
+    fn test_filename_matches_are_graded() {
+        let suggestion = suggest("/game/~mods/spiderman_peter_P.pak");
+        assert_eq!(suggestion.character, Some(Character::SpiderMan));
+        assert_eq!(
+            suggestion.character_confidence,
+            Some(DetectionConfidence::Medium)
+        );
+        assert_eq!(suggestion.category, ModCategory::Skins);
+        assert_eq!(suggestion.category_confidence, DetectionConfidence::Low);
+
+        // "storm" fits Human Torch, Storm and Invisible Woman
+        let suggestion = suggest("/game/~mods/storm_hud_menu_P.pak");
+        assert_eq!(suggestion.character, Some(Character::HumanTorch));
+        assert_eq!(
+            suggestion.character_confidence,
+            Some(DetectionConfidence::Low)
+        );
+        assert_eq!(suggestion.category, ModCategory::UI);
+        assert_eq!(suggestion.category_confidence, DetectionConfidence::Medium);
+
+        let suggestion = suggest("/game/~mods/cool_P.pak");
+        assert_eq!(suggestion.character, None);
+        assert_eq!(suggestion.character_confidence, None);
+    }
+}
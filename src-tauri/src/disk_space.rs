@@ -0,0 +1,92 @@
+//! Free space on the drives the app writes to. Installs check it up front so
+//! a mod too big for the game drive fails with a plain message instead of a
+//! copy error halfway through, and settings show it for both volumes.
+
+use std::path::Path;
+
+use tauri::AppHandle;
+
+use crate::types::{DiskUsage, VolumeUsage};
+
+/// Free and total bytes of the volume `path` is on. `path` doesn't have to
+/// exist yet; its closest existing ancestor is asked instead.
+pub fn volume_usage(path: &Path) -> Result<VolumeUsage, String> {
+    let existing = path
+        .ancestors()
+        .find(|dir| dir.exists())
+        .ok_or_else(|| format!("No existing folder above {}", path.display()))?;
+    let free_bytes = fs2::available_space(existing)
+        .map_err(|e| format!("Failed to read free space of {}: {}", existing.display(), e))?;
+    let total_bytes = fs2::total_space(existing)
+        .map_err(|e| format!("Failed to read size of {}: {}", existing.display(), e))?;
+    Ok(VolumeUsage {
+        path: path.to_path_buf(),
+        free_bytes,
+        total_bytes,
+    })
+}
+
+/// Err with how much is missing unless `needed` bytes fit on `path`'s volume
+pub fn ensure_free_space(path: &Path, needed: u64) -> Result<(), String> {
+    let free = volume_usage(path)?.free_bytes;
+    if needed > free {
+        return Err(format!(
+            "Not enough disk space: need {}, have {}",
+            format_size(needed),
+            format_size(free)
+        ));
+    }
+    Ok(())
+}
+
+/// `bytes` for people: "512 B", "1.5 MB"
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+/// Free and total space of the drive mods are installed to and of the one
+/// holding app data (metadata, thumbnails, disabled-mods)
+#[tauri::command]
+pub async fn get_disk_usage(app: AppHandle) -> Result<DiskUsage, String> {
+    let app_data_dir = crate::app_data_dir(&app)?;
+    let mods_volume = match crate::get_mod_service(&app) {
+        Ok(service) => Some(volume_usage(service.mods_directory())?),
+        Err(_) => None,
+    };
+    Ok(DiskUsage {
+        mods_volume,
+        app_data_volume: volume_usage(&app_data_dir)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_free_space_check_names_what_is_needed() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let usage = volume_usage(&dir.path().join("not").join("made")).unwrap();
+        assert!(usage.total_bytes >= usage.free_bytes);
+        ensure_free_space(dir.path(), 0).unwrap();
+
+        let error = ensure_free_space(dir.path(), u64::MAX).unwrap_err();
+        assert!(error.starts_with("Not enough disk space: need "), "{error}");
+
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(
+            format_size(3 * 1024 * 1024 * 1024 + 200 * 1024 * 1024),
+            "3.2 GB"
+        );
+    }
+}
@@ -0,0 +1,646 @@
+//! Downloads that survive flaky connections. Data streams into `<name>.part`
+//! next to a `<name>.part.json` record of where it comes from and how far it
+//! got. A dropped connection is retried from where it stopped with an HTTP
+//! range request, and downloads cut short by closing the app pick up again at
+//! the next start. Pausing keeps the partial file, cancelling deletes it.
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::io::AsyncWriteExt;
+
+use crate::progress::ProgressThrottle;
+
+/// Connection drops tolerated per download before it is marked failed
+const MAX_ATTEMPTS: u32 = 5;
+/// Wait before a retry, multiplied by the attempt number
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+/// How often the record on disk catches up with the bytes written
+const RECORD_INTERVAL: Duration = Duration::from_secs(2);
+
+const PAUSED: &str = "Download paused";
+const CANCELLED: &str = "Download cancelled";
+
+// What a running download has been asked to do, checked between chunks
+const RUN: u8 = 0;
+const PAUSE: u8 = 1;
+const CANCEL: u8 = 2;
+
+/// Downloads running in this session, by ID
+static ACTIVE: Mutex<BTreeMap<String, Arc<AtomicU8>>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DownloadState {
+    Downloading,
+    Paused,
+    Failed,
+}
+
+/// A download that hasn't finished, as listed by `list_downloads`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadRecord {
+    /// The file name, which is unique within the downloads folder
+    pub id: String,
+    pub url: String,
+    pub bytes: u64,
+    pub total: Option<u64>,
+    /// Expected SHA-256 (hex) of the finished file, when the source gives one
+    #[serde(default)]
+    pub sha256: Option<String>,
+    pub state: DownloadState,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadProgress {
+    pub id: String,
+    pub bytes: u64,
+    pub total: Option<u64>,
+    /// Bytes per second since the download (re)started
+    pub speed: u64,
+}
+
+/// Where mod archives are downloaded to before being installed
+pub fn downloads_dir() -> PathBuf {
+    std::env::temp_dir().join("marvel_rivals_nexus")
+}
+
+impl DownloadRecord {
+    fn new(id: String, url: &str, total: Option<u64>, sha256: Option<String>) -> Self {
+        Self {
+            id,
+            url: url.to_string(),
+            bytes: 0,
+            total,
+            sha256,
+            state: DownloadState::Downloading,
+            error: None,
+        }
+    }
+
+    fn final_path(&self) -> PathBuf {
+        downloads_dir().join(&self.id)
+    }
+
+    fn part_path(&self) -> PathBuf {
+        downloads_dir().join(format!("{}.part", self.id))
+    }
+
+    fn record_path(&self) -> PathBuf {
+        downloads_dir().join(format!("{}.part.json", self.id))
+    }
+
+    /// Bytes actually on disk, which a crash can leave ahead of `bytes`
+    fn bytes_on_disk(&self) -> u64 {
+        std::fs::metadata(self.part_path())
+            .map(|m| m.len())
+            .unwrap_or(0)
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize download record: {e}"))?;
+        let path = self.record_path();
+        let temp_path = path.with_extension("tmp");
+        std::fs::write(&temp_path, json)
+            .map_err(|e| format!("Failed to write download record: {e}"))?;
+        std::fs::rename(&temp_path, &path)
+            .map_err(|e| format!("Failed to finalize download record: {e}"))
+    }
+
+    fn remove_files(&self) {
+        let _ = std::fs::remove_file(self.part_path());
+        let _ = std::fs::remove_file(self.record_path());
+    }
+}
+
+fn load_record(id: &str) -> Option<DownloadRecord> {
+    let path = downloads_dir().join(format!("{id}.part.json"));
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn load_records() -> Vec<DownloadRecord> {
+    let Ok(entries) = std::fs::read_dir(downloads_dir()) else {
+        return Vec::new();
+    };
+    let mut records: Vec<DownloadRecord> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            load_record(name.strip_suffix(".part.json")?)
+        })
+        .collect();
+    records.sort_by(|a, b| a.id.cmp(&b.id));
+    records
+}
+
+/// Mark `id` as running. `None` if it already is.
+fn register(id: &str) -> Option<Arc<AtomicU8>> {
+    let mut active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    if active.contains_key(id) {
+        return None;
+    }
+    let control = Arc::new(AtomicU8::new(RUN));
+    active.insert(id.to_string(), control.clone());
+    Some(control)
+}
+
+fn unregister(id: &str) {
+    ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).remove(id);
+}
+
+/// Ask a running download to stop. False if it isn't running.
+fn signal(id: &str, action: u8) -> bool {
+    match ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).get(id) {
+        Some(control) => {
+            control.store(action, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+/// The server's name for the file (Content-Disposition), else the last
+/// segment of the URL, else `fallback`. Never a path.
+fn file_name_for(content_disposition: Option<&str>, url: &str, fallback: &str) -> String {
+    let from_header = content_disposition
+        .and_then(|v| v.split("filename=").nth(1))
+        .map(|f| f.split(';').next().unwrap_or(f).trim().trim_matches('"'));
+    let from_url = url
+        .split('?')
+        .next()
+        .and_then(|path| path.rsplit('/').next());
+    [from_header, from_url]
+        .into_iter()
+        .flatten()
+        .filter_map(|name| Path::new(name).file_name()?.to_str().map(str::to_string))
+        .find(|name| !name.is_empty() && !name.ends_with(".part"))
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+/// Why one pass over the connection stopped short
+enum Interrupted {
+    /// Worth another range request
+    Retry(String),
+    /// Expired link, paused, cancelled, disk error
+    Stop(String),
+}
+
+/// What a transfer needs besides the record. Detached transfers (no app)
+/// keep no record on disk and send no events.
+struct Transfer<'a> {
+    client: &'a reqwest::Client,
+    app: Option<&'a AppHandle>,
+    control: &'a AtomicU8,
+}
+
+impl Transfer<'_> {
+    /// Download into the record's `.part` file until it is complete,
+    /// retrying dropped connections, then verify it
+    async fn run(
+        &self,
+        record: &mut DownloadRecord,
+        mut response: Option<reqwest::Response>,
+    ) -> Result<(), String> {
+        let mut attempt = 0;
+        loop {
+            match self.run_once(record, response.take()).await {
+                Ok(()) => break,
+                Err(Interrupted::Stop(e)) => return Err(e),
+                Err(Interrupted::Retry(e)) => {
+                    attempt += 1;
+                    if attempt >= MAX_ATTEMPTS {
+                        return Err(format!(
+                            "Download failed after {MAX_ATTEMPTS} attempts: {e}"
+                        ));
+                    }
+                    log::warn!(
+                        "[download] {} interrupted at {} bytes ({}), retrying",
+                        record.id,
+                        record.bytes_on_disk(),
+                        e
+                    );
+                    tokio::time::sleep(RETRY_DELAY * attempt).await;
+                }
+            }
+        }
+        verify(record)
+    }
+
+    async fn run_once(
+        &self,
+        record: &mut DownloadRecord,
+        response: Option<reqwest::Response>,
+    ) -> Result<(), Interrupted> {
+        let part_path = record.part_path();
+        let offset = record.bytes_on_disk();
+        if offset > 0 && record.total == Some(offset) {
+            return Ok(());
+        }
+
+        let response = match response {
+            Some(response) => response,
+            None => {
+                let mut request = self.client.get(&record.url);
+                if offset > 0 {
+                    request = request.header(reqwest::header::RANGE, format!("bytes={offset}-"));
+                }
+                request
+                    .send()
+                    .await
+                    .map_err(|e| Interrupted::Retry(format!("Download request failed: {e}")))?
+            }
+        };
+
+        let status = response.status();
+        if status.is_client_error() {
+            // Nexus links are signed and expire after a while
+            return Err(Interrupted::Stop(format!(
+                "The download link is no longer valid ({status}); start the download again"
+            )));
+        }
+        if !status.is_success() {
+            return Err(Interrupted::Retry(format!("Server answered {status}")));
+        }
+
+        // A server that ignores the range sends the whole file again
+        let append = offset > 0 && status == reqwest::StatusCode::PARTIAL_CONTENT;
+        let start = if append { offset } else { 0 };
+        if let Some(length) = response.content_length() {
+            record.total = Some(start + length);
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(&part_path)
+            .await
+            .map_err(|e| Interrupted::Stop(format!("Failed to create file: {e}")))?;
+
+        record.bytes = start;
+        let started = Instant::now();
+        let mut throttle = ProgressThrottle::default();
+        let mut last_saved = Instant::now();
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            match self.control.load(Ordering::Relaxed) {
+                PAUSE => return Err(Interrupted::Stop(PAUSED.to_string())),
+                CANCEL => return Err(Interrupted::Stop(CANCELLED.to_string())),
+                _ => {}
+            }
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    // Make sure the retry's offset counts everything received
+                    let _ = file.flush().await;
+                    return Err(Interrupted::Retry(format!("Download stream error: {e}")));
+                }
+            };
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| Interrupted::Stop(format!("Failed to write chunk: {e}")))?;
+            record.bytes += chunk.len() as u64;
+
+            let now = Instant::now();
+            if throttle.should_emit(now, record.bytes, record.total.unwrap_or(0)) {
+                self.emit_progress(record, start, started);
+            }
+            if self.app.is_some() && now.duration_since(last_saved) >= RECORD_INTERVAL {
+                let _ = file.flush().await;
+                let _ = record.save();
+                last_saved = now;
+            }
+        }
+        file.flush()
+            .await
+            .map_err(|e| Interrupted::Stop(format!("Failed to flush file: {e}")))?;
+        self.emit_progress(record, start, started);
+
+        match record.total {
+            Some(total) if record.bytes < total => Err(Interrupted::Retry(format!(
+                "Connection closed at {} of {} bytes",
+                record.bytes, total
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn emit_progress(&self, record: &DownloadRecord, start: u64, started: Instant) {
+        let Some(app) = self.app else {
+            return;
+        };
+        let seconds = started.elapsed().as_secs_f64().max(0.001);
+        let _ = app.emit(
+            "download-progress",
+            DownloadProgress {
+                id: record.id.clone(),
+                bytes: record.bytes,
+                total: record.total,
+                speed: ((record.bytes - start) as f64 / seconds) as u64,
+            },
+        );
+    }
+}
+
+/// Check the finished `.part` against the expected size and hash. A file
+/// with the wrong hash is deleted, since continuing it can't fix it.
+fn verify(record: &DownloadRecord) -> Result<(), String> {
+    let part_path = record.part_path();
+    let size = record.bytes_on_disk();
+    if let Some(total) = record.total.filter(|&total| total != size) {
+        return Err(format!("Downloaded {size} of {total} bytes"));
+    }
+    let Some(expected) = &record.sha256 else {
+        return Ok(());
+    };
+
+    let mut file = std::fs::File::open(&part_path)
+        .map_err(|e| format!("Failed to open download to verify it: {e}"))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read download to verify it: {e}"))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    let actual = format!("{:x}", hasher.finalize());
+    if !actual.eq_ignore_ascii_case(expected) {
+        let _ = std::fs::remove_file(&part_path);
+        return Err(format!(
+            "{} is corrupted (checksum mismatch); download it again",
+            record.id
+        ));
+    }
+    Ok(())
+}
+
+/// Run a registered download to the end and settle its record: removed once
+/// the file is in place, kept as paused or failed otherwise, deleted along
+/// with the partial file when cancelled
+async fn finish(
+    app: &AppHandle,
+    mut record: DownloadRecord,
+    response: Option<reqwest::Response>,
+    control: Arc<AtomicU8>,
+) -> Result<PathBuf, String> {
+    let client = reqwest::Client::new();
+    let transfer = Transfer {
+        client: &client,
+        app: Some(app),
+        control: &control,
+    };
+    let result = transfer.run(&mut record, response).await;
+    unregister(&record.id);
+
+    let error = match result {
+        Ok(()) => {
+            let final_path = record.final_path();
+            std::fs::rename(record.part_path(), &final_path)
+                .map_err(|e| format!("Failed to finalize download: {e}"))?;
+            let _ = std::fs::remove_file(record.record_path());
+            log::info!("[download] Finished {} ({} bytes)", record.id, record.bytes);
+            return Ok(final_path);
+        }
+        Err(e) => e,
+    };
+
+    match control.load(Ordering::Relaxed) {
+        CANCEL => {
+            record.remove_files();
+            log::info!("[download] Cancelled {}", record.id);
+        }
+        PAUSE => {
+            record.state = DownloadState::Paused;
+            record.bytes = record.bytes_on_disk();
+            record.save()?;
+            log::info!("[download] Paused {} at {} bytes", record.id, record.bytes);
+        }
+        _ => {
+            record.state = DownloadState::Failed;
+            record.bytes = record.bytes_on_disk();
+            record.error = Some(error.clone());
+            record.save()?;
+            log::warn!("[download] {} failed: {}", record.id, error);
+        }
+    }
+    Err(error)
+}
+
+/// Download `url` into the downloads folder and return the finished file.
+/// `fallback_name` names the file when neither the server nor the URL do.
+/// A partial file of the same name and size, left by an earlier attempt
+/// through a link that has since expired, is continued rather than restarted.
+pub async fn download(
+    app: &AppHandle,
+    url: &str,
+    fallback_name: &str,
+    sha256: Option<String>,
+) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(downloads_dir())
+        .map_err(|e| format!("Failed to create temp dir: {e}"))?;
+
+    let response = reqwest::Client::new()
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Download request failed: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Download failed with status: {}",
+            response.status()
+        ));
+    }
+
+    let content_disposition = response
+        .headers()
+        .get("content-disposition")
+        .and_then(|v| v.to_str().ok());
+    let id = file_name_for(content_disposition, url, fallback_name);
+    let total = response.content_length();
+    let control = register(&id).ok_or_else(|| format!("{id} is already downloading"))?;
+
+    let mut record = match load_record(&id) {
+        Some(earlier) if total.is_some() && earlier.total == total => {
+            log::info!(
+                "[download] Continuing {} from {} bytes",
+                id,
+                earlier.bytes_on_disk()
+            );
+            DownloadRecord {
+                sha256: sha256.or(earlier.sha256),
+                ..DownloadRecord::new(id, url, total, None)
+            }
+        }
+        earlier => {
+            if let Some(earlier) = earlier {
+                earlier.remove_files();
+            }
+            DownloadRecord::new(id, url, total, sha256)
+        }
+    };
+    record.bytes = record.bytes_on_disk();
+    // Continuing needs a range request; this response starts at zero
+    let response = (record.bytes == 0).then_some(response);
+    if let Err(e) = record.save() {
+        unregister(&record.id);
+        return Err(e);
+    }
+
+    finish(app, record, response, control).await
+}
+
+/// Fetch `url` into memory through the same retrying transfer, for large
+/// images. Nothing is listed or kept if it fails.
+pub async fn download_detached(
+    client: &reqwest::Client,
+    url: &str,
+    response: reqwest::Response,
+) -> Result<Vec<u8>, String> {
+    std::fs::create_dir_all(downloads_dir())
+        .map_err(|e| format!("Failed to create temp dir: {e}"))?;
+    let mut record = DownloadRecord::new(
+        format!("{}.download", uuid::Uuid::new_v4()),
+        url,
+        response.content_length(),
+        None,
+    );
+    let control = AtomicU8::new(RUN);
+    let transfer = Transfer {
+        client,
+        app: None,
+        control: &control,
+    };
+    let result = transfer.run(&mut record, Some(response)).await;
+    let bytes = result.and_then(|()| {
+        std::fs::read(record.part_path()).map_err(|e| format!("Failed to read download: {e}"))
+    });
+    record.remove_files();
+    bytes
+}
+
+/// Continue a paused, failed or interrupted download in the background.
+/// Once done the file goes through the same install flow as a fresh Nexus
+/// download.
+fn spawn_resume(app: AppHandle, mut record: DownloadRecord) -> Result<(), String> {
+    let Some(control) = register(&record.id) else {
+        return Ok(());
+    };
+    record.state = DownloadState::Downloading;
+    record.error = None;
+    tauri::async_runtime::spawn(async move {
+        if let Ok(path) = finish(&app, record, None, control).await {
+            let _ = app.emit("nexus-mod-downloaded", path.to_string_lossy().to_string());
+        }
+    });
+    Ok(())
+}
+
+/// Pick up downloads that were running when the app last closed. Ones whose
+/// link has expired end up failed, with the reason in their record.
+pub fn resume_interrupted(app: &AppHandle) {
+    for record in load_records() {
+        if record.state != DownloadState::Downloading {
+            continue;
+        }
+        log::info!("[download] Resuming interrupted download {}", record.id);
+        if let Err(e) = spawn_resume(app.clone(), record) {
+            log::warn!("[download] {e}");
+        }
+    }
+}
+
+/// Downloads that haven't finished: running, paused or failed
+#[tauri::command]
+pub async fn list_downloads() -> Result<Vec<DownloadRecord>, String> {
+    let active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    Ok(load_records()
+        .into_iter()
+        .map(|mut record| {
+            record.bytes = record.bytes_on_disk();
+            if active.contains_key(&record.id) {
+                record.state = DownloadState::Downloading;
+            }
+            record
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub async fn pause_download(id: String) -> Result<(), String> {
+    if signal(&id, PAUSE) {
+        return Ok(());
+    }
+    let mut record = load_record(&id).ok_or_else(|| format!("No download {id}"))?;
+    if record.state == DownloadState::Downloading {
+        record.state = DownloadState::Paused;
+        record.save()?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn resume_download(app: AppHandle, id: String) -> Result<(), String> {
+    let record = load_record(&id).ok_or_else(|| format!("No download {id}"))?;
+    spawn_resume(app, record)
+}
+
+/// Stop a download and delete what it had downloaded
+#[tauri::command]
+pub async fn cancel_download(id: String) -> Result<(), String> {
+    if signal(&id, CANCEL) {
+        return Ok(());
+    }
+    let record = load_record(&id).ok_or_else(|| format!("No download {id}"))?;
+    record.remove_files();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name_for() {
+        let url = "https://cdn.example.com/files/Storm%20Skin.zip?md5=abc&expires=1";
+        assert_eq!(
+            file_name_for(
+                Some("attachment; filename=\"Storm.7z\"; size=12"),
+                url,
+                "x.zip"
+            ),
+            "Storm.7z"
+        );
+        assert_eq!(file_name_for(None, url, "x.zip"), "Storm%20Skin.zip");
+        // Never a path outside the downloads folder
+        assert_eq!(
+            file_name_for(
+                Some("attachment; filename=\"../../evil.zip\""),
+                url,
+                "x.zip"
+            ),
+            "evil.zip"
+        );
+        assert_eq!(
+            file_name_for(None, "https://example.com/", "x.zip"),
+            "x.zip"
+        );
+    }
+}
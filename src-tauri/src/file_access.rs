@@ -0,0 +1,228 @@
+//! Reads of files outside app data that the frontend needs: mods dropped on
+//! the window and images picked for thumbnails. Done here rather than in the
+//! webview, so it needs no fs scope and no asset scope beyond app data.
+//! Paths are checked before anything is read: they must be absolute,
+//! existing files under a size cap.
+
+use std::fs::File;
+use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use base64::{engine::general_purpose, Engine as _};
+use image::{imageops::FilterType, ImageFormat, ImageReader};
+use serde::Serialize;
+
+use crate::mod_service::PAK_MAGIC;
+
+/// Largest file worth inspecting as a dropped mod, the archive size limit
+pub const MAX_DROPPED_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// Largest image that's decoded for a preview
+const MAX_IMAGE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Bounds for the longest edge of a preview
+const PREVIEW_EDGE_RANGE: std::ops::RangeInclusive<u32> = 64..=4096;
+
+/// What a file is by its content, whatever its extension says
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DroppedFileKind {
+    Zip,
+    SevenZip,
+    Rar,
+    Pak,
+    Image,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DroppedFileInfo {
+    pub path: PathBuf,
+    pub size: u64,
+    pub kind: DroppedFileKind,
+    pub is_archive: bool,
+    pub is_pak: bool,
+}
+
+/// A downscaled image for showing in the webview. `width` and `height` are
+/// the original's, so crops picked on the preview can be scaled back.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImagePreview {
+    pub data_url: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// `path` if it's an absolute path to an existing file of at most
+/// `max_size` bytes, with its size
+fn checked_file(path: &str, max_size: u64) -> Result<(PathBuf, u64), String> {
+    let path = PathBuf::from(path);
+    if !path.is_absolute() {
+        return Err(format!("Not an absolute path: {}", path.display()));
+    }
+    let metadata =
+        std::fs::metadata(&path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("Not a file: {}", path.display()));
+    }
+    if metadata.len() > max_size {
+        return Err(format!(
+            "{} is too large ({} MB, at most {} MB)",
+            path.display(),
+            metadata.len() / (1024 * 1024),
+            max_size / (1024 * 1024)
+        ));
+    }
+    Ok((path, metadata.len()))
+}
+
+/// Tell the kind of a file from its leading bytes, or for paks the footer
+/// magic near its end
+fn sniff(path: &Path) -> std::io::Result<DroppedFileKind> {
+    let mut file = File::open(path)?;
+    let mut head = [0u8; 12];
+    let read = file.read(&mut head)?;
+    let head = &head[..read];
+
+    let kind = if head.starts_with(b"PK\x03\x04") || head.starts_with(b"PK\x05\x06") {
+        DroppedFileKind::Zip
+    } else if head.starts_with(b"7z\xBC\xAF\x27\x1C") {
+        DroppedFileKind::SevenZip
+    } else if head.starts_with(b"Rar!\x1A\x07") {
+        DroppedFileKind::Rar
+    } else if head.starts_with(b"\x89PNG")
+        || head.starts_with(b"\xFF\xD8\xFF")
+        || (head.starts_with(b"RIFF") && head.get(8..12) == Some(b"WEBP".as_slice()))
+    {
+        DroppedFileKind::Image
+    } else {
+        let len = file.metadata()?.len();
+        let mut tail = Vec::new();
+        file.seek(SeekFrom::Start(len.saturating_sub(1024)))?;
+        file.read_to_end(&mut tail)?;
+        let magic = PAK_MAGIC.to_le_bytes();
+        if tail.windows(4).any(|w| w == magic) {
+            DroppedFileKind::Pak
+        } else {
+            DroppedFileKind::Unknown
+        }
+    };
+    Ok(kind)
+}
+
+fn dropped_file_info(path: &str) -> Result<DroppedFileInfo, String> {
+    let (path, size) = checked_file(path, MAX_DROPPED_SIZE)?;
+    let kind = sniff(&path).map_err(|e| format!("Can't read {}: {}", path.display(), e))?;
+    Ok(DroppedFileInfo {
+        path,
+        size,
+        is_archive: matches!(
+            kind,
+            DroppedFileKind::Zip | DroppedFileKind::SevenZip | DroppedFileKind::Rar
+        ),
+        is_pak: kind == DroppedFileKind::Pak,
+        kind,
+    })
+}
+
+fn image_preview(path: &str, max_edge: u32) -> Result<ImagePreview, String> {
+    let (path, _) = checked_file(path, MAX_IMAGE_SIZE)?;
+    let image = ImageReader::open(&path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| format!("Can't read {}: {}", path.display(), e))?
+        .decode()
+        .map_err(|e| format!("Not a readable image: {}", e))?;
+
+    let (width, height) = (image.width(), image.height());
+    let max_edge = max_edge.clamp(*PREVIEW_EDGE_RANGE.start(), *PREVIEW_EDGE_RANGE.end());
+    let preview = if width.max(height) > max_edge {
+        image.resize(max_edge, max_edge, FilterType::Triangle)
+    } else {
+        image
+    };
+    let mut png = Cursor::new(Vec::new());
+    preview
+        .write_to(&mut png, ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode preview: {}", e))?;
+
+    Ok(ImagePreview {
+        data_url: format!(
+            "data:image/png;base64,{}",
+            general_purpose::STANDARD.encode(png.into_inner())
+        ),
+        width,
+        height,
+    })
+}
+
+/// Size and content kind of a file dropped on the window
+#[tauri::command]
+pub async fn read_dropped_file_info(path: String) -> Result<DroppedFileInfo, String> {
+    dropped_file_info(&path)
+}
+
+/// An image from anywhere on disk as a data URL at most `max_edge` pixels
+/// on its longest side, for the thumbnail picker
+#[tauri::command]
+pub async fn read_image_for_preview(path: String, max_edge: u32) -> Result<ImagePreview, String> {
+    image_preview(&path, max_edge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dropped_files_are_sniffed_by_content() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let write = |name: &str, bytes: &[u8]| {
+            let path = dir.path().join(name);
+            std::fs::write(&path, bytes).unwrap();
+            path.to_string_lossy().to_string()
+        };
+
+        let zip = dropped_file_info(&write("renamed.pak", b"PK\x03\x04rest")).unwrap();
+        assert_eq!(zip.kind, DroppedFileKind::Zip);
+        assert!(zip.is_archive && !zip.is_pak);
+
+        let mut pak = vec![0u8; 2048];
+        pak.extend_from_slice(&PAK_MAGIC.to_le_bytes());
+        pak.extend_from_slice(&[0u8; 40]);
+        let pak = dropped_file_info(&write("Mod_P.pak", &pak)).unwrap();
+        assert_eq!(pak.kind, DroppedFileKind::Pak);
+        assert!(pak.is_pak && !pak.is_archive);
+        assert_eq!(pak.size, 2092);
+
+        let text = dropped_file_info(&write("notes.zip", b"not an archive")).unwrap();
+        assert_eq!(text.kind, DroppedFileKind::Unknown);
+
+        // Relative paths, folders and missing files are refused
+        assert!(dropped_file_info("Mod_P.pak").is_err());
+        assert!(dropped_file_info(&dir.path().to_string_lossy()).is_err());
+        assert!(dropped_file_info(&dir.path().join("gone.zip").to_string_lossy()).is_err());
+    }
+
+    #[test]
+    fn test_image_preview_is_downscaled() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("cover.jpg");
+        image::RgbImage::new(800, 400)
+            .save_with_format(&path, ImageFormat::Png)
+            .unwrap();
+
+        let preview = image_preview(&path.to_string_lossy(), 200).unwrap();
+        assert_eq!((preview.width, preview.height), (800, 400));
+        let base64 = preview
+            .data_url
+            .trim_start_matches("data:image/png;base64,");
+        let png = general_purpose::STANDARD.decode(base64).unwrap();
+        let decoded = image::load_from_memory(&png).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (200, 100));
+
+        let text = dir.path().join("notes.png");
+        std::fs::write(&text, "text").unwrap();
+        assert!(image_preview(&text.to_string_lossy(), 200).is_err());
+    }
+}
@@ -0,0 +1,382 @@
+//! Files handed over from the webview in chunks, for drops that come with
+//! content but no usable path: files dragged out of some archive tools and
+//! browsers. Each import is spooled to its own folder under the temp dir,
+//! hashed as it arrives, and on finishing checked against the size (and
+//! hash, if given) announced up front. The finished file's path then goes
+//! to the normal install and extract commands. Imports idle for longer than
+//! [`IDLE_TIMEOUT`] are dropped along with their data, and finished files
+//! are removed once [`FINISHED_TTL`] has passed.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use base64::{engine::general_purpose, Engine as _};
+use sha2::{Digest, Sha256};
+
+use crate::file_access::MAX_DROPPED_SIZE;
+
+/// Largest chunk accepted by `append_file_chunk`, decoded
+pub const MAX_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// How long an import may go without a chunk before it's abandoned
+const IDLE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// How long a finished file is kept for the install that asked for it
+const FINISHED_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// How often abandoned imports are looked for
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Imports in progress, by ID
+static IMPORTS: Mutex<Option<HashMap<String, Import>>> = Mutex::new(None);
+
+struct Import {
+    /// The import's own folder, removed with everything in it when the
+    /// import is abandoned
+    dir: PathBuf,
+    name: String,
+    total_size: u64,
+    expected_hash: Option<String>,
+    spool: File,
+    received: u64,
+    hasher: Sha256,
+    last_chunk_at: Instant,
+}
+
+/// Where imports are spooled
+pub fn imports_dir() -> PathBuf {
+    std::env::temp_dir().join("marvel_rivals_imports")
+}
+
+fn with_imports<T>(f: impl FnOnce(&mut HashMap<String, Import>) -> T) -> T {
+    let mut imports = IMPORTS.lock().unwrap_or_else(|e| e.into_inner());
+    f(imports.get_or_insert_with(HashMap::new))
+}
+
+fn begin(
+    root: &Path,
+    name: &str,
+    total_size: u64,
+    expected_hash: Option<String>,
+    now: Instant,
+) -> Result<String, String> {
+    // Only the file name; a path from the webview must not pick the folder
+    let name = Path::new(name)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .filter(|n| !n.is_empty())
+        .ok_or_else(|| format!("Not a file name: {}", name))?;
+    if total_size > MAX_DROPPED_SIZE {
+        return Err(format!(
+            "{} is too large ({} MB, at most {} MB)",
+            name,
+            total_size / (1024 * 1024),
+            MAX_DROPPED_SIZE / (1024 * 1024)
+        ));
+    }
+
+    let import_id = uuid::Uuid::new_v4().to_string();
+    let dir = root.join(&import_id);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    let spool = File::create(dir.join(format!("{}.part", name)))
+        .map_err(|e| format!("Failed to create spool file: {}", e))?;
+    log::info!(
+        "[import] {} started for {} ({} bytes)",
+        import_id,
+        name,
+        total_size
+    );
+    with_imports(|imports| {
+        imports.insert(
+            import_id.clone(),
+            Import {
+                dir,
+                name,
+                total_size,
+                expected_hash: expected_hash.map(|hash| hash.trim().to_lowercase()),
+                spool,
+                received: 0,
+                hasher: Sha256::new(),
+                last_chunk_at: now,
+            },
+        )
+    });
+    Ok(import_id)
+}
+
+/// Write `chunk`, which must start at `offset`: exactly where the previous
+/// chunk ended. Returns the bytes received so far.
+fn append(import_id: &str, offset: u64, chunk: &[u8], now: Instant) -> Result<u64, String> {
+    if chunk.len() > MAX_CHUNK_SIZE {
+        return Err(format!(
+            "Chunk of {} bytes is larger than the {} byte limit",
+            chunk.len(),
+            MAX_CHUNK_SIZE
+        ));
+    }
+    with_imports(|imports| {
+        let import = imports
+            .get_mut(import_id)
+            .ok_or_else(|| format!("No import {}; it may have timed out", import_id))?;
+        if offset < import.received {
+            return Err(format!(
+                "Chunk at offset {} was already received (have {} bytes)",
+                offset, import.received
+            ));
+        }
+        if offset > import.received {
+            return Err(format!(
+                "Chunk at offset {} is out of order (expected offset {})",
+                offset, import.received
+            ));
+        }
+        if import.received + chunk.len() as u64 > import.total_size {
+            return Err(format!(
+                "Chunk runs past the announced size of {} bytes",
+                import.total_size
+            ));
+        }
+        import
+            .spool
+            .write_all(chunk)
+            .map_err(|e| format!("Failed to write chunk: {}", e))?;
+        import.hasher.update(chunk);
+        import.received += chunk.len() as u64;
+        import.last_chunk_at = now;
+        Ok(import.received)
+    })
+}
+
+/// Check the size and hash and move the file to its name. A file that
+/// doesn't check out is deleted.
+fn finish(import_id: &str) -> Result<PathBuf, String> {
+    let import = with_imports(|imports| imports.remove(import_id))
+        .ok_or_else(|| format!("No import {}; it may have timed out", import_id))?;
+    let fail = |message: String| {
+        let _ = fs::remove_dir_all(&import.dir);
+        log::warn!("[import] {} failed: {}", import_id, message);
+        Err(message)
+    };
+
+    if import.received != import.total_size {
+        return fail(format!(
+            "Received {} of {} bytes",
+            import.received, import.total_size
+        ));
+    }
+    let hash = format!("{:x}", import.hasher.finalize());
+    if let Some(expected) = &import.expected_hash {
+        if &hash != expected {
+            return fail(format!(
+                "SHA-256 mismatch: expected {}, got {}",
+                expected, hash
+            ));
+        }
+    }
+    if let Err(e) = import.spool.sync_all() {
+        return fail(format!("Failed to flush spool file: {}", e));
+    }
+    drop(import.spool);
+
+    let path = import.dir.join(&import.name);
+    if let Err(e) = fs::rename(import.dir.join(format!("{}.part", import.name)), &path) {
+        return fail(format!("Failed to finish spool file: {}", e));
+    }
+    log::info!(
+        "[import] {} finished: {} ({} bytes)",
+        import_id,
+        path.display(),
+        import.total_size
+    );
+    Ok(path)
+}
+
+fn cancel(import_id: &str) {
+    if let Some(import) = with_imports(|imports| imports.remove(import_id)) {
+        let _ = fs::remove_dir_all(&import.dir);
+        log::info!("[import] {} cancelled", import_id);
+    }
+}
+
+/// Drop imports under `root` idle since before `now - IDLE_TIMEOUT`, and
+/// folders under it that no import owns and that haven't changed for
+/// `FINISHED_TTL`: finished files and leftovers from earlier sessions.
+/// Returns how many imports were abandoned.
+fn sweep(root: &Path, now: Instant) -> usize {
+    let (abandoned, active): (Vec<PathBuf>, Vec<PathBuf>) = with_imports(|imports| {
+        let stale: Vec<String> = imports
+            .iter()
+            .filter(|(_, import)| {
+                import.dir.starts_with(root)
+                    && now.saturating_duration_since(import.last_chunk_at) > IDLE_TIMEOUT
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+        let abandoned = stale
+            .iter()
+            .filter_map(|id| imports.remove(id))
+            .map(|import| {
+                log::info!(
+                    "[import] Abandoned {} after {} of {} bytes",
+                    import.name,
+                    import.received,
+                    import.total_size
+                );
+                import.dir
+            })
+            .collect();
+        (abandoned, imports.values().map(|i| i.dir.clone()).collect())
+    });
+    for dir in &abandoned {
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    if let Ok(entries) = fs::read_dir(root) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let dir = entry.path();
+            let expired = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .is_some_and(|age| age > FINISHED_TTL);
+            if expired && !active.contains(&dir) {
+                let _ = fs::remove_dir_all(&dir);
+            }
+        }
+    }
+    abandoned.len()
+}
+
+/// Look for abandoned imports every minute
+pub fn start_sweeper() {
+    std::thread::spawn(|| loop {
+        std::thread::sleep(SWEEP_INTERVAL);
+        sweep(&imports_dir(), Instant::now());
+    });
+}
+
+/// Start importing a file of `total_size` bytes, optionally with the
+/// SHA-256 it must have. Returns the import ID chunks are sent to.
+#[tauri::command]
+pub async fn begin_file_import(
+    name: String,
+    total_size: u64,
+    expected_hash: Option<String>,
+) -> Result<String, String> {
+    begin(
+        &imports_dir(),
+        &name,
+        total_size,
+        expected_hash,
+        Instant::now(),
+    )
+}
+
+/// Add the next chunk of an import, base64 encoded. `offset` is where it
+/// starts in the file; chunks must arrive in order, each exactly once.
+/// Returns the bytes received so far.
+#[tauri::command]
+pub async fn append_file_chunk(
+    import_id: String,
+    offset: u64,
+    base64_chunk: String,
+) -> Result<u64, String> {
+    // Checked before decoding, so an oversized chunk isn't decoded at all
+    if base64_chunk.len() > MAX_CHUNK_SIZE.div_ceil(3) * 4 {
+        return Err(format!(
+            "Chunk is larger than the {} byte limit",
+            MAX_CHUNK_SIZE
+        ));
+    }
+    let chunk = general_purpose::STANDARD
+        .decode(base64_chunk)
+        .map_err(|e| format!("Chunk isn't valid base64: {}", e))?;
+    append(&import_id, offset, &chunk, Instant::now())
+}
+
+/// Verify an import's size and hash. Returns the path of the finished file
+/// for the install and extract commands.
+#[tauri::command]
+pub async fn finish_file_import(import_id: String) -> Result<PathBuf, String> {
+    finish(&import_id)
+}
+
+/// Give up on an import and delete what was received
+#[tauri::command]
+pub async fn cancel_file_import(import_id: String) -> Result<(), String> {
+    cancel(&import_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_are_checked_and_assembled() {
+        let root = tempfile::TempDir::new().unwrap();
+        let now = Instant::now();
+        let data = b"0123456789abcdef";
+        let hash = format!("{:x}", Sha256::digest(data));
+        let id = begin(
+            root.path(),
+            "../Mod_P.zip",
+            16,
+            Some(hash.to_uppercase()),
+            now,
+        )
+        .unwrap();
+
+        assert_eq!(append(&id, 0, &data[..6], now).unwrap(), 6);
+        // A repeated chunk and one skipping ahead are both refused
+        assert!(append(&id, 0, &data[..6], now)
+            .unwrap_err()
+            .contains("already received"));
+        assert!(append(&id, 10, &data[10..], now)
+            .unwrap_err()
+            .contains("out of order"));
+        assert!(append(&id, 6, &[0; 20], now)
+            .unwrap_err()
+            .contains("past the announced size"));
+        assert_eq!(append(&id, 6, &data[6..], now).unwrap(), 16);
+
+        let path = finish(&id).unwrap();
+        assert_eq!(path, root.path().join(&id).join("Mod_P.zip"));
+        assert_eq!(fs::read(&path).unwrap(), data);
+        assert!(finish(&id).is_err());
+
+        // A hash that doesn't match deletes what arrived
+        let id = begin(root.path(), "Other.zip", 4, Some("00".into()), now).unwrap();
+        append(&id, 0, b"data", now).unwrap();
+        assert!(finish(&id).unwrap_err().contains("mismatch"));
+        assert!(!root.path().join(&id).exists());
+    }
+
+    #[test]
+    fn test_idle_imports_are_abandoned() {
+        let root = tempfile::TempDir::new().unwrap();
+        let now = Instant::now();
+        let idle = begin(root.path(), "Idle.zip", 8, None, now).unwrap();
+        append(&idle, 0, b"1234", now).unwrap();
+        let later = now + IDLE_TIMEOUT / 2;
+        let busy = begin(root.path(), "Busy.zip", 8, None, later).unwrap();
+
+        assert_eq!(sweep(root.path(), later), 0);
+        assert_eq!(
+            sweep(root.path(), now + IDLE_TIMEOUT + Duration::from_secs(1)),
+            1
+        );
+        assert!(!root.path().join(&idle).exists());
+        assert!(append(&idle, 4, b"5678", later)
+            .unwrap_err()
+            .contains("timed out"));
+        assert!(root.path().join(&busy).exists());
+        cancel(&busy);
+        assert!(!root.path().join(&busy).exists());
+    }
+}
@@ -1,11 +1,54 @@
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Emitter, Manager};
+use tauri::{AppHandle, Emitter};
+
+use crate::ignored_paths::IgnoredPaths;
 
 const DEBOUNCE_DURATION: Duration = Duration::from_secs(2);
 
+/// Minimum time between logged watcher errors. An unplugged drive makes the
+/// backend report the same failure over and over.
+const ERROR_BACKOFF: Duration = Duration::from_secs(30);
+
+/// The running watcher and the folder it watches, kept so it can be
+/// replaced when the game folder comes back after going offline
+static ACTIVE: Mutex<Option<(FileWatcher, PathBuf)>> = Mutex::new(None);
+
+/// Set once the watcher is running, cleared on the first backend error. The
+/// background rescan uses this to decide whether it needs to poll at all.
+static WATCHER_HEALTHY: AtomicBool = AtomicBool::new(false);
+
+pub fn is_watcher_healthy() -> bool {
+    WATCHER_HEALTHY.load(Ordering::Relaxed)
+}
+
+/// Number of live [`WatcherPause`] guards
+static PAUSE_DEPTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Suppresses change events while alive. Bulk operations hold one so the
+/// frontend refreshes once at the end instead of after every folder move.
+pub struct WatcherPause(());
+
+impl Drop for WatcherPause {
+    fn drop(&mut self) {
+        PAUSE_DEPTH.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Pause change events until the returned guard is dropped. Nests.
+pub fn pause() -> WatcherPause {
+    PAUSE_DEPTH.fetch_add(1, Ordering::Relaxed);
+    WatcherPause(())
+}
+
+pub fn is_paused() -> bool {
+    PAUSE_DEPTH.load(Ordering::Relaxed) > 0
+}
+
 pub struct FileWatcher {
     watcher: RecommendedWatcher,
     debounce_timer: Option<Instant>,
@@ -13,7 +56,7 @@ pub struct FileWatcher {
 
 impl FileWatcher {
     /// Create a new file watcher for the mods directory
-    pub fn new(_watch_path: PathBuf, app_handle: AppHandle) -> Result<Self, String> {
+    pub fn new(watch_path: PathBuf, app_handle: AppHandle) -> Result<Self, String> {
         let (tx, rx): (
             Sender<Result<Event, notify::Error>>,
             Receiver<Result<Event, notify::Error>>,
@@ -30,7 +73,7 @@ impl FileWatcher {
 
         // Start monitoring thread
         std::thread::spawn(move || {
-            Self::monitor_events(rx, app_handle);
+            Self::monitor_events(rx, app_handle, watch_path);
         });
 
         Ok(Self {
@@ -54,8 +97,15 @@ impl FileWatcher {
     }
 
     /// Monitor file system events and emit to frontend
-    fn monitor_events(rx: Receiver<Result<Event, notify::Error>>, app_handle: AppHandle) {
+    fn monitor_events(
+        rx: Receiver<Result<Event, notify::Error>>,
+        app_handle: AppHandle,
+        watch_path: PathBuf,
+    ) {
         let mut last_emit = Instant::now();
+        let mut last_error: Option<Instant> = None;
+        // Compiled from settings, and again whenever the list changes
+        let mut ignored: (Vec<String>, IgnoredPaths) = Default::default();
 
         loop {
             match rx.recv() {
@@ -68,7 +118,26 @@ impl FileWatcher {
                             | notify::EventKind::Modify(_)
                     );
 
+                    // Hashes of changed files and the cached scan are stale
+                    // whether or not the frontend hears about the change
                     if is_relevant {
+                        crate::hash_cache::invalidate(&event.paths);
+                        crate::mod_cache::invalidate();
+                    }
+
+                    if let Ok(settings) = crate::load_app_settings(&app_handle) {
+                        if settings.ignored_paths != ignored.0 {
+                            ignored.1 = IgnoredPaths::new(&settings.ignored_paths);
+                            ignored.0 = settings.ignored_paths;
+                        }
+                    }
+                    let in_ignored_folder = !event.paths.is_empty()
+                        && event
+                            .paths
+                            .iter()
+                            .all(|path| is_ignored(&ignored.1, &watch_path, path));
+
+                    if is_relevant && !in_ignored_folder && !is_paused() {
                         // Debounce events to prevent excessive updates
                         let now = Instant::now();
                         if now.duration_since(last_emit) >= DEBOUNCE_DURATION {
@@ -81,17 +150,34 @@ impl FileWatcher {
                     }
                 }
                 Ok(Err(e)) => {
+                    WATCHER_HEALTHY.store(false, Ordering::Relaxed);
+                    if last_error.is_some_and(|at| at.elapsed() < ERROR_BACKOFF) {
+                        continue;
+                    }
+                    last_error = Some(Instant::now());
                     eprintln!("File watcher error: {}", e);
+                    // Most often the drive holding the game went away; the
+                    // availability check restarts the watcher once it's back
+                    if let Ok(settings) = crate::load_app_settings(&app_handle) {
+                        if let Some(game_directory) = settings.game_directory {
+                            crate::game_directory::check(&app_handle, &game_directory);
+                        }
+                    }
                 }
-                Err(e) => {
-                    eprintln!("File watcher channel error: {}", e);
-                    break;
-                }
+                // The watcher was dropped, either replaced by a new one or
+                // stopped; neither makes the current watcher unhealthy
+                Err(_) => break,
             }
         }
     }
 }
 
+/// Whether a changed path is inside an ignored folder under `watch_path`
+fn is_ignored(ignored: &IgnoredPaths, watch_path: &Path, path: &Path) -> bool {
+    path.strip_prefix(watch_path)
+        .is_ok_and(|relative| ignored.matches(relative))
+}
+
 /// Start watching the mods directory
 #[tauri::command]
 pub async fn start_file_watcher(app: AppHandle, mods_directory: String) -> Result<(), String> {
@@ -101,19 +187,41 @@ pub async fn start_file_watcher(app: AppHandle, mods_directory: String) -> Resul
         return Err("Mods directory does not exist".to_string());
     }
 
+    watch_directory(&app, watch_path)
+}
+
+/// Replace the running watcher with one on `watch_path`
+fn watch_directory(app: &AppHandle, watch_path: PathBuf) -> Result<(), String> {
     let mut watcher = FileWatcher::new(watch_path.clone(), app.clone())?;
-    watcher.watch(watch_path)?;
+    watcher.watch(watch_path.clone())?;
 
-    // Store watcher in app state
-    app.manage(watcher);
+    *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = Some((watcher, watch_path));
+    WATCHER_HEALTHY.store(true, Ordering::Relaxed);
 
     Ok(())
 }
 
+/// Watch the last watched folder again. Its old watch died with the drive
+/// it was on. Does nothing if the watcher was never started.
+pub fn restart(app: &AppHandle) {
+    let watch_path = match &*ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) {
+        Some((_, watch_path)) => watch_path.clone(),
+        None => return,
+    };
+    // On failure the dead watcher stays, so the next restart tries again
+    if let Err(e) = watch_directory(app, watch_path.clone()) {
+        log::warn!(
+            "[watcher] Failed to restart watcher on {:?}: {}",
+            watch_path,
+            e
+        );
+    }
+}
+
 /// Stop watching the mods directory
 #[tauri::command]
-pub async fn stop_file_watcher(app: AppHandle) -> Result<(), String> {
-    // Watcher will be dropped when app state is cleared
-    // For manual control, we would need to store the watcher handle
+pub async fn stop_file_watcher(_app: AppHandle) -> Result<(), String> {
+    *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    WATCHER_HEALTHY.store(false, Ordering::Relaxed);
     Ok(())
 }
@@ -0,0 +1,227 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tauri::AppHandle;
+
+use crate::types::ModServiceError;
+
+/// Why a folder could not be opened. Serialized as `{ code, message, path }`
+/// so the UI can tell a stale path apart from a real failure.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OpenFolderErrorCode {
+    PathNotFound,
+    ModNotFound,
+    GameDirectoryUnavailable,
+    OpenFailed,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenFolderError {
+    pub code: OpenFolderErrorCode,
+    pub message: String,
+    pub path: Option<PathBuf>,
+}
+
+impl OpenFolderError {
+    fn path_not_found(path: &Path) -> Self {
+        Self {
+            code: OpenFolderErrorCode::PathNotFound,
+            message: format!("Path no longer exists: {}", path.display()),
+            path: Some(path.to_path_buf()),
+        }
+    }
+
+    fn mod_not_found(mod_id: &str) -> Self {
+        Self {
+            code: OpenFolderErrorCode::ModNotFound,
+            message: format!("Mod not found: {mod_id}"),
+            path: None,
+        }
+    }
+}
+
+impl From<String> for OpenFolderError {
+    fn from(message: String) -> Self {
+        Self {
+            code: OpenFolderErrorCode::OpenFailed,
+            message,
+            path: None,
+        }
+    }
+}
+
+impl From<ModServiceError> for OpenFolderError {
+    fn from(error: ModServiceError) -> Self {
+        match error {
+            ModServiceError::GameDirectoryUnavailable { ref path } => Self {
+                code: OpenFolderErrorCode::GameDirectoryUnavailable,
+                message: error.to_string(),
+                path: Some(path.clone()),
+            },
+            ModServiceError::Failed { message } => message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for OpenFolderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Resolve `path` to an existing, absolute location. Windows verbatim
+/// prefixes (`\\?\`) are stripped because Explorer doesn't understand them.
+fn resolve_existing(path: &Path) -> Result<PathBuf, OpenFolderError> {
+    let canonical = path
+        .canonicalize()
+        .map_err(|_| OpenFolderError::path_not_found(path))?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let text = canonical.to_string_lossy();
+        if let Some(unc) = text.strip_prefix(r"\\?\UNC\") {
+            return Ok(PathBuf::from(format!(r"\\{unc}")));
+        }
+        if let Some(local) = text.strip_prefix(r"\\?\") {
+            return Ok(PathBuf::from(local));
+        }
+    }
+
+    Ok(canonical)
+}
+
+fn spawn(command: &mut Command) -> Result<(), OpenFolderError> {
+    command
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open folder: {e}").into())
+}
+
+/// Open the file manager with `file` selected
+fn reveal(file: &Path) -> Result<(), OpenFolderError> {
+    let file = resolve_existing(file)?;
+    log::info!("[folders] Revealing {:?}", file);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+
+        // Explorer parses its own command line, so the path goes in as one
+        // quoted token. Windows paths can't contain '"', so it can't break out.
+        spawn(Command::new("explorer").raw_arg(format!("/select,\"{}\"", file.display())))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        spawn(Command::new("open").arg("-R").arg(&file))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        // xdg-open can't select a file, open the containing folder instead
+        let parent = file.parent().unwrap_or(&file);
+        spawn(Command::new("xdg-open").arg(parent))?;
+    }
+
+    Ok(())
+}
+
+/// Open `dir` itself in the file manager
+fn open_dir(dir: &Path) -> Result<(), OpenFolderError> {
+    let dir = resolve_existing(dir)?;
+    log::info!("[folders] Opening {:?}", dir);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+
+        spawn(Command::new("explorer").raw_arg(format!("\"{}\"", dir.display())))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        spawn(Command::new("open").arg(&dir))?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        spawn(Command::new("xdg-open").arg(&dir))?;
+    }
+
+    Ok(())
+}
+
+/// Current `.pak` path of a mod, wherever it lives now (~mods or disabled-mods)
+fn mod_file_path(app: &AppHandle, mod_id: &str) -> Result<PathBuf, OpenFolderError> {
+    crate::get_mod_service(app)?
+        .find_mod_by_id(mod_id)?
+        .map(|m| m.file_path)
+        .ok_or_else(|| OpenFolderError::mod_not_found(mod_id))
+}
+
+pub fn open_mods_root_folder(app: &AppHandle) -> Result<(), OpenFolderError> {
+    let service = crate::get_mod_service(app)?;
+    open_dir(service.mods_directory())
+}
+
+pub fn open_metadata_dir(app: &AppHandle) -> Result<(), OpenFolderError> {
+    let metadata_dir = crate::app_data_dir(app)?.join("metadata");
+    open_dir(&metadata_dir)
+}
+
+/// Reveal a file in the system file manager. Pass `mod_id` instead of
+/// `file_path` to resolve the mod's current location here, which stays
+/// correct even if the frontend's copy is stale after a toggle or rename.
+#[tauri::command]
+pub async fn show_in_folder(
+    app: AppHandle,
+    file_path: Option<String>,
+    mod_id: Option<String>,
+) -> Result<(), OpenFolderError> {
+    let file_path = match (mod_id, file_path) {
+        (Some(mod_id), _) => mod_file_path(&app, &mod_id)?,
+        (None, Some(file_path)) => PathBuf::from(file_path),
+        (None, None) => return Err("No file path or mod ID given".to_string().into()),
+    };
+    reveal(&file_path)
+}
+
+/// Open the folder a mod currently lives in
+#[tauri::command]
+pub async fn open_mod_folder(app: AppHandle, mod_id: String) -> Result<(), OpenFolderError> {
+    let file_path = mod_file_path(&app, &mod_id)?;
+    let folder = file_path
+        .parent()
+        .ok_or_else(|| OpenFolderError::path_not_found(&file_path))?;
+    open_dir(folder)
+}
+
+#[tauri::command]
+pub async fn open_mods_root(app: AppHandle) -> Result<(), OpenFolderError> {
+    open_mods_root_folder(&app)
+}
+
+#[tauri::command]
+pub async fn open_metadata_folder(app: AppHandle) -> Result<(), OpenFolderError> {
+    open_metadata_dir(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_path_is_reported_as_not_found() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let missing = dir.path().join("gone").join("Mod_P.pak");
+
+        let err = resolve_existing(&missing).unwrap_err();
+        assert!(matches!(err.code, OpenFolderErrorCode::PathNotFound));
+        assert_eq!(err.path.as_deref(), Some(missing.as_path()));
+
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["code"], "pathNotFound");
+    }
+}
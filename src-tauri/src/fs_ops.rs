@@ -0,0 +1,540 @@
+//! Every change the app makes to mod files, their folders and thumbnails
+//! goes through here, so there is one copy/move/retry implementation and one
+//! place that sees all of it. With the `fileActivityEvents` setting on, each
+//! operation is also queued for the frontend's activity feed and sent in
+//! `file-operation` batches. App bookkeeping (metadata JSON, checkpoints,
+//! temp files) is written directly and doesn't show up.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter};
+use walkdir::WalkDir;
+
+use crate::progress::{CancellationToken, ProgressEmitter};
+use crate::types::{DirectoryCopyProgress, DirectoryCopySummary};
+
+/// How often queued operations go out to the frontend
+const FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Operations sent per batch; the rest of a bigger burst is only counted
+const MAX_BATCH: usize = 500;
+
+/// Buffer reused for every file a directory copy writes; big enough that
+/// multi-GB .ucas files don't crawl
+const COPY_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Chunk size for copying a single mod file during an install; skin packs
+/// run to gigabytes
+const INSTALL_COPY_BUFFER_SIZE: usize = 4 * 1024 * 1024;
+
+/// Error message of a copy stopped by its [`CancellationToken`]
+pub const COPY_CANCELLED: &str = "Copy cancelled";
+
+static FEED_ENABLED: AtomicBool = AtomicBool::new(false);
+
+static PENDING: Mutex<FileOperationBatch> = Mutex::new(FileOperationBatch {
+    operations: Vec::new(),
+    dropped: 0,
+});
+
+thread_local! {
+    /// Mod the current thread is working on, see [`mod_scope`]
+    static CURRENT_MOD: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FileOperationKind {
+    Move,
+    Copy,
+    Delete,
+    /// Written out of an archive; the source is the archive
+    Extract,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileOperation {
+    pub kind: FileOperationKind,
+    pub source: PathBuf,
+    /// Unset for deletes
+    pub destination: Option<PathBuf>,
+    pub bytes: Option<u64>,
+    pub mod_id: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+/// Payload of one `file-operation` event
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileOperationBatch {
+    pub operations: Vec<FileOperation>,
+    /// Operations left out because the burst didn't fit in one batch
+    pub dropped: usize,
+}
+
+/// Turn the activity feed on or off. Anything still queued is dropped when
+/// it goes off.
+pub fn set_feed_enabled(enabled: bool) {
+    FEED_ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        take_pending();
+    }
+}
+
+/// Send queued operations to the frontend every 250ms, so a burst (a bulk
+/// toggle, a big archive) arrives as a few batches instead of one event per
+/// file
+pub fn start_feed(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(FLUSH_INTERVAL);
+        let batch = take_pending();
+        if !batch.operations.is_empty() || batch.dropped > 0 {
+            let _ = app.emit("file-operation", &batch);
+        }
+    });
+}
+
+fn take_pending() -> FileOperationBatch {
+    std::mem::take(&mut *PENDING.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Restores the previous mod when dropped
+pub struct ModScope {
+    previous: Option<String>,
+}
+
+impl Drop for ModScope {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT_MOD.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Tag the operations this thread records with `mod_id` until the returned
+/// guard is dropped
+pub fn mod_scope(mod_id: &str) -> ModScope {
+    let previous = CURRENT_MOD.with(|current| current.replace(Some(mod_id.to_string())));
+    ModScope { previous }
+}
+
+/// Queue an operation for the activity feed. The wrappers below call this;
+/// code that writes files some other way (archive extraction) calls it
+/// directly.
+pub fn record(
+    kind: FileOperationKind,
+    source: &Path,
+    destination: Option<&Path>,
+    bytes: Option<u64>,
+) {
+    crate::mod_cache::invalidate();
+    if !FEED_ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let operation = FileOperation {
+        kind,
+        source: source.to_path_buf(),
+        destination: destination.map(Path::to_path_buf),
+        bytes,
+        mod_id: CURRENT_MOD.with(|current| current.borrow().clone()),
+        at: Utc::now(),
+    };
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    if pending.operations.len() < MAX_BATCH {
+        pending.operations.push(operation);
+    } else {
+        pending.dropped += 1;
+    }
+}
+
+/// Clear the read-only attribute if it is set. Paks restored from NTFS
+/// backups often carry it, and Windows then refuses to delete or overwrite
+/// them. A missing path has nothing to clear.
+pub fn clear_readonly(path: &Path) -> std::io::Result<()> {
+    let mut permissions = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata.permissions(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    if !permissions.readonly() {
+        return Ok(());
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        permissions.set_mode(permissions.mode() | 0o200);
+    }
+    // Only reached off Unix, where this clears the attribute and nothing else
+    #[cfg(not(unix))]
+    #[allow(clippy::permissions_set_readonly_false)]
+    permissions.set_readonly(false);
+    fs::set_permissions(path, permissions)
+}
+
+/// [`clear_readonly`] for a directory and everything under it, ahead of
+/// removing it. Failures are logged; the removal reports the real error.
+fn clear_readonly_recursive(dir: &Path) {
+    for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
+        if let Err(e) = clear_readonly(entry.path()) {
+            log::warn!("[read-only] Failed to clear {:?}: {}", entry.path(), e);
+        }
+    }
+}
+
+/// Rename a file or folder. Unlike [`move_file`] there is no copy fallback,
+/// for folders and for callers that handle the failure themselves.
+pub fn rename(source: &Path, dest: &Path) -> std::io::Result<()> {
+    fs::rename(source, dest)?;
+    record(FileOperationKind::Move, source, Some(dest), None);
+    Ok(())
+}
+
+/// `fs::copy` over a possibly read-only destination, then check the copy has
+/// the source's full size
+fn copy_unlocked(source: &Path, dest: &Path) -> std::io::Result<u64> {
+    let expected = fs::metadata(source)?.len();
+    clear_readonly(dest)?;
+    fs::copy(source, dest)?;
+    // The copy carries the source's attributes, so unlock it as well
+    clear_readonly(dest)?;
+    let copied = fs::metadata(dest)?.len();
+    if copied != expected {
+        let _ = fs::remove_file(dest);
+        return Err(std::io::Error::other(format!(
+            "copy is incomplete ({} of {} bytes)",
+            copied, expected
+        )));
+    }
+    Ok(copied)
+}
+
+/// Copy a file over a possibly read-only destination. A copy that comes out
+/// short is removed and reported as an error.
+pub fn copy_verified(source: &Path, dest: &Path) -> std::io::Result<u64> {
+    let copied = copy_unlocked(source, dest)?;
+    record(FileOperationKind::Copy, source, Some(dest), Some(copied));
+    Ok(copied)
+}
+
+/// [`copy_verified`] in chunks, passing each chunk's size to `on_chunk` and
+/// stopping with [`COPY_CANCELLED`] once `cancel` is set. `dest` must be a
+/// fresh path; a cancelled, failed or short copy is removed.
+pub fn copy_verified_chunked(
+    source: &Path,
+    dest: &Path,
+    cancel: &CancellationToken,
+    on_chunk: impl FnMut(u64),
+) -> std::io::Result<u64> {
+    let expected = fs::metadata(source)?.len();
+    let mut buffer = vec![0u8; INSTALL_COPY_BUFFER_SIZE];
+    let result =
+        copy_file_buffered(source, dest, &mut buffer, cancel, on_chunk).and_then(|copied| {
+            if copied == expected {
+                Ok(copied)
+            } else {
+                Err(std::io::Error::other(format!(
+                    "copy is incomplete ({} of {} bytes)",
+                    copied, expected
+                )))
+            }
+        });
+    match result {
+        Ok(copied) => {
+            record(FileOperationKind::Copy, source, Some(dest), Some(copied));
+            Ok(copied)
+        }
+        Err(e) => {
+            let _ = fs::remove_file(dest);
+            Err(e)
+        }
+    }
+}
+
+/// Give `path` the modification time `modified`, e.g. the one an archive
+/// entry or an installed file's source carries
+pub fn set_modified(path: &Path, modified: SystemTime) -> std::io::Result<()> {
+    filetime::set_file_mtime(path, filetime::FileTime::from_system_time(modified))
+}
+
+/// Move a file, falling back to copy + verify + delete when a rename fails
+/// (disabled-mods lives under app data, often on another drive than the
+/// game). The source is only removed once the copy has its full size.
+pub fn move_file(source: &Path, dest: &Path) -> Result<(), String> {
+    if fs::rename(source, dest).is_ok() {
+        record(FileOperationKind::Move, source, Some(dest), None);
+        return Ok(());
+    }
+
+    let copied =
+        copy_unlocked(source, dest).map_err(|e| format!("Failed to copy {:?}: {}", source, e))?;
+    clear_readonly(source)
+        .and_then(|_| fs::remove_file(source))
+        .map_err(|e| format!("Failed to remove {:?} after copying: {}", source, e))?;
+    record(FileOperationKind::Move, source, Some(dest), Some(copied));
+    Ok(())
+}
+
+/// Delete a file, read-only or not
+pub fn remove_file(path: &Path) -> std::io::Result<()> {
+    let bytes = fs::metadata(path).ok().map(|m| m.len());
+    clear_readonly(path)?;
+    fs::remove_file(path)?;
+    record(FileOperationKind::Delete, path, None, bytes);
+    Ok(())
+}
+
+/// Delete a directory with retry logic to handle file locks
+/// This helps prevent folder duplication when files are temporarily locked
+pub fn remove_dir_all_retry(path: &Path, max_retries: u32) -> Result<(), String> {
+    let mut last_error = None;
+    // Access denied on a read-only file won't go away by retrying
+    clear_readonly_recursive(path);
+
+    for attempt in 0..max_retries {
+        match fs::remove_dir_all(path) {
+            Ok(_) => {
+                log::info!("Successfully deleted directory: {:?}", path);
+                record(FileOperationKind::Delete, path, None, None);
+                return Ok(());
+            }
+            Err(e) => {
+                last_error = Some(e);
+                if attempt < max_retries - 1 {
+                    // Exponential backoff: 100ms, 200ms, 400ms
+                    let delay_ms = 100 * (2_u64.pow(attempt));
+                    log::warn!(
+                        "Failed to delete directory (attempt {}/{}): {}. Retrying in {}ms...",
+                        attempt + 1,
+                        max_retries,
+                        last_error.as_ref().unwrap(),
+                        delay_ms
+                    );
+                    std::thread::sleep(Duration::from_millis(delay_ms));
+                }
+            }
+        }
+    }
+
+    // All retries failed
+    Err(format!(
+        "Failed to delete directory {:?} after {} attempts: {}",
+        path,
+        max_retries,
+        last_error.unwrap()
+    ))
+}
+
+/// Copy one file through `buffer`, passing each chunk's size to `on_chunk`.
+/// Stops with [`COPY_CANCELLED`] between chunks once `cancel` is set.
+fn copy_file_buffered(
+    source: &Path,
+    dest: &Path,
+    buffer: &mut [u8],
+    cancel: &CancellationToken,
+    mut on_chunk: impl FnMut(u64),
+) -> std::io::Result<u64> {
+    clear_readonly(dest)?;
+    let mut reader = fs::File::open(source)?;
+    let mut writer = fs::File::create(dest)?;
+    let mut copied = 0;
+    loop {
+        if cancel.is_cancelled() {
+            return Err(std::io::Error::other(COPY_CANCELLED));
+        }
+        let read = match reader.read(buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        writer.write_all(&buffer[..read])?;
+        copied += read as u64;
+        on_chunk(read as u64);
+    }
+    writer.flush()?;
+    Ok(copied)
+}
+
+/// Recursively copy a directory and all its contents. Everything is listed
+/// first so progress has real totals, and `cancel` is checked between files.
+/// A cancelled or failed copy takes back what it wrote (the destination
+/// itself if it created it); the source is never touched.
+pub fn copy_directory_recursive<F>(
+    source: &Path,
+    destination: &Path,
+    progress: &ProgressEmitter<F>,
+    cancel: &CancellationToken,
+) -> Result<DirectoryCopySummary, String>
+where
+    F: Fn(DirectoryCopyProgress),
+{
+    log::info!("Copying directory from {:?} to {:?}", source, destination);
+    let started = Instant::now();
+    let mut summary = DirectoryCopySummary::default();
+
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+    for entry in WalkDir::new(source).min_depth(1) {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                log::warn!("Skipping unreadable entry: {}", e);
+                summary.skipped.extend(e.path().map(Path::to_path_buf));
+                continue;
+            }
+        };
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .map_err(|e| format!("Failed to compute relative path: {}", e))?
+            .to_path_buf();
+        if entry.file_type().is_dir() {
+            dirs.push(relative);
+        } else if entry.file_type().is_file() {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            files.push((relative, size));
+        } else {
+            summary.skipped.push(entry.path().to_path_buf());
+        }
+    }
+
+    let mut state = DirectoryCopyProgress {
+        files_done: 0,
+        files_total: files.len(),
+        bytes_done: 0,
+        bytes_total: files.iter().map(|(_, size)| size).sum(),
+    };
+    let created = !destination.exists();
+    let mut written: Vec<PathBuf> = Vec::new();
+    let mut copy_all = || -> Result<(), String> {
+        // Parents come before their children in the walk
+        fs::create_dir_all(destination)
+            .map_err(|e| format!("Failed to create destination directory: {}", e))?;
+        for dir in &dirs {
+            let dest_dir = destination.join(dir);
+            fs::create_dir_all(&dest_dir)
+                .map_err(|e| format!("Failed to create directory {:?}: {}", dest_dir, e))?;
+        }
+
+        let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+        for (relative, _) in &files {
+            if cancel.is_cancelled() {
+                return Err("Copy cancelled".to_string());
+            }
+            let source_path = source.join(relative);
+            let dest_path = destination.join(relative);
+            written.push(dest_path.clone());
+            let copied =
+                copy_file_buffered(&source_path, &dest_path, &mut buffer, cancel, |chunk| {
+                    state.bytes_done += chunk;
+                    progress.emit(state.bytes_done, state.bytes_total, state.clone());
+                })
+                .map_err(|e| format!("Failed to copy file {:?}: {}", source_path, e))?;
+            record(
+                FileOperationKind::Copy,
+                &source_path,
+                Some(&dest_path),
+                Some(copied),
+            );
+            state.files_done += 1;
+        }
+        Ok(())
+    };
+
+    if let Err(e) = copy_all() {
+        log::warn!("Directory copy stopped, removing what was copied: {}", e);
+        if created {
+            let _ = fs::remove_dir_all(destination);
+        } else {
+            for path in &written {
+                let _ = clear_readonly(path).and_then(|_| fs::remove_file(path));
+            }
+        }
+        return Err(e);
+    }
+    progress.finish(state.clone());
+
+    summary.files_copied = state.files_done;
+    summary.bytes_copied = state.bytes_done;
+    summary.duration = started.elapsed().as_millis() as u64;
+    log::info!(
+        "Successfully copied directory: {} file(s), {} bytes, {} skipped",
+        summary.files_copied,
+        summary.bytes_copied,
+        summary.skipped.len()
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::{write_file, Fixture};
+
+    #[test]
+    fn test_copy_directory_recursive() {
+        let fx = Fixture::new();
+        let source = fx.installed_mod("Skins/Hulk/Gamma", "Gamma_P");
+        let source = source.parent().unwrap().parent().unwrap().to_path_buf();
+        write_file(&source.join("readme.txt"), 16);
+
+        let dest = fx.source_dir.join("Hulk");
+        let progress = ProgressEmitter::new(|_: DirectoryCopyProgress| {});
+        let summary =
+            copy_directory_recursive(&source, &dest, &progress, &CancellationToken::new()).unwrap();
+        assert_eq!(summary.files_copied, 4);
+        assert_eq!(summary.bytes_copied, 8 + 64 + 4 + 16);
+        assert!(summary.skipped.is_empty());
+        assert_eq!(
+            fs::read(dest.join("Gamma").join("Gamma_P.ucas")).unwrap(),
+            fs::read(source.join("Gamma").join("Gamma_P.ucas")).unwrap()
+        );
+
+        let dest = fx.source_dir.join("Hulk-cancelled");
+        let cancel = CancellationToken::new();
+        let sink = cancel.clone();
+        // Cancelled as soon as the first file starts
+        let progress = ProgressEmitter::new(move |_: DirectoryCopyProgress| sink.cancel());
+        assert!(copy_directory_recursive(&source, &dest, &progress, &cancel).is_err());
+        assert!(!dest.exists());
+        assert_eq!(WalkDir::new(&source).into_iter().count(), 6);
+        assert_eq!(fs::read(source.join("readme.txt")).unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_feed_tags_operations_with_their_mod() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let source = dir.path().join("a.pak");
+        let moved = dir.path().join("b.pak");
+        write_file(&source, 8);
+
+        set_feed_enabled(true);
+        {
+            let _scope = mod_scope("mod-a");
+            move_file(&source, &moved).unwrap();
+        }
+        remove_file(&moved).unwrap();
+        // Other tests may be moving files at the same time
+        let ours: Vec<FileOperation> = take_pending()
+            .operations
+            .into_iter()
+            .filter(|op| op.source.starts_with(dir.path()))
+            .collect();
+        set_feed_enabled(false);
+
+        assert_eq!(ours.len(), 2);
+        assert_eq!(ours[0].kind, FileOperationKind::Move);
+        assert_eq!(ours[0].destination.as_deref(), Some(moved.as_path()));
+        assert_eq!(ours[0].mod_id.as_deref(), Some("mod-a"));
+        assert_eq!(ours[1].kind, FileOperationKind::Delete);
+        assert_eq!(ours[1].bytes, Some(8));
+        assert_eq!(ours[1].mod_id, None);
+    }
+}
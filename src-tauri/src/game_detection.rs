@@ -0,0 +1,344 @@
+//! Finding the game folder on first run and when the user asks. Regional
+//! Steam installs and the NetEase launcher don't use the English
+//! `MarvelRivals` folder under Program Files, so besides that default this
+//! looks at the Windows uninstall entries, paths in the NetEase launcher's
+//! config files, and a list of folder names (extendable in settings) under
+//! the usual install roots of every drive. Every hit is reported with where
+//! it came from; the source of the one in use is kept in settings.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// Folder names the game is installed under, tried on top of the ones in
+/// `AppSettings::game_folder_names`
+pub const DEFAULT_FOLDER_NAMES: &[&str] = &["MarvelRivals", "Marvel Rivals", "漫威争锋"];
+
+/// Folders below a drive root that installs usually go in
+const INSTALL_ROOTS: &[&str] = &[
+    r"Program Files (x86)\Steam\steamapps\common",
+    r"Program Files\Steam\steamapps\common",
+    r"SteamLibrary\steamapps\common",
+    r"Steam\steamapps\common",
+    r"Program Files",
+    r"Program Files (x86)",
+    r"NetEase",
+    r"Games",
+    "",
+];
+
+/// Config files larger than this aren't searched for paths
+const MAX_CONFIG_SIZE: u64 = 1024 * 1024;
+
+/// Where a detected game folder came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DetectionSource {
+    /// The English folder in the default Steam library
+    SteamDefault,
+    /// An entry under the Windows uninstall registry keys
+    UninstallRegistry,
+    /// A path in the NetEase launcher's config
+    NetEaseLauncher,
+    /// One of the candidate folder names under a usual install root
+    CandidateFolder,
+    /// Picked by the user
+    Manual,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectedGameDirectory {
+    pub path: PathBuf,
+    pub source: DetectionSource,
+}
+
+/// Whether `path` holds the game, going by its Paks folder
+fn is_game_directory(path: &Path) -> bool {
+    path.join("MarvelGame")
+        .join("Marvel")
+        .join("Content")
+        .join("Paks")
+        .is_dir()
+}
+
+/// The game folder `path` is in or points into (e.g. the exe), if any
+fn game_directory_of(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| is_game_directory(dir))
+        .map(Path::to_path_buf)
+}
+
+/// Every `folder_names` entry under every install root of every drive
+fn candidate_paths(drives: &[PathBuf], folder_names: &[&str]) -> Vec<PathBuf> {
+    drives
+        .iter()
+        .flat_map(|drive| INSTALL_ROOTS.iter().map(move |root| drive.join(root)))
+        .flat_map(|root| folder_names.iter().map(move |name| root.join(name)))
+        .collect()
+}
+
+/// Values from `reg query` output as (key, name, data)
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn parse_reg_query(output: &str) -> Vec<(String, String, String)> {
+    let mut values = Vec::new();
+    let mut key = None;
+    for line in output.lines() {
+        if line.starts_with("HKEY_") {
+            key = Some(line.trim().to_string());
+            continue;
+        }
+        let Some(key) = &key else {
+            continue;
+        };
+        // "    Name    REG_SZ    Data", where name and data may hold spaces
+        let mut parts = line.trim().splitn(3, "    ");
+        if let (Some(name), Some(kind), Some(data)) = (parts.next(), parts.next(), parts.next()) {
+            if kind.starts_with("REG_") {
+                values.push((key.clone(), name.to_string(), data.trim().to_string()));
+            }
+        }
+    }
+    values
+}
+
+/// Absolute Windows paths in a config file, whatever its format. JSON's
+/// escaped backslashes are undone first.
+fn paths_in_config(text: &str) -> Vec<PathBuf> {
+    let text = text.replace(r"\\", r"\");
+    let bytes = text.as_bytes();
+    let mut paths = Vec::new();
+    for (start, _) in text.match_indices(":\\").chain(text.match_indices(":/")) {
+        let Some(drive) = start.checked_sub(1) else {
+            continue;
+        };
+        if !bytes[drive].is_ascii_alphabetic()
+            || (drive > 0 && bytes[drive - 1].is_ascii_alphanumeric())
+        {
+            continue;
+        }
+        let path: String = text[drive..]
+            .chars()
+            .take_while(|c| !matches!(c, '"' | '\'' | '\r' | '\n' | '\0' | '<' | '>' | '|' | ','))
+            .collect();
+        paths.push(PathBuf::from(path.trim_end()));
+    }
+    paths
+}
+
+#[cfg(target_os = "windows")]
+fn reg_query(args: &[&str]) -> String {
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+
+    // CREATE_NO_WINDOW flag to prevent console window from flashing
+    const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+    match Command::new("reg")
+        .arg("query")
+        .args(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+    {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).to_string(),
+        Err(e) => {
+            log::warn!("[detect] Failed to query the registry: {}", e);
+            String::new()
+        }
+    }
+}
+
+/// Install locations of uninstall entries named after the game
+#[cfg(target_os = "windows")]
+fn registry_install_locations() -> Vec<PathBuf> {
+    const UNINSTALL_KEYS: &[&str] = &[
+        r"HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+        r"HKLM\SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+        r"HKCU\SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    ];
+
+    let mut keys = Vec::new();
+    for root in UNINSTALL_KEYS {
+        let found = reg_query(&[*root, "/s", "/f", "Marvel Rivals", "/d"]);
+        keys.extend(parse_reg_query(&found).into_iter().map(|(key, _, _)| key));
+    }
+    keys.dedup();
+    keys.iter()
+        .flat_map(|key| parse_reg_query(&reg_query(&[key.as_str(), "/v", "InstallLocation"])))
+        .filter(|(_, name, data)| name == "InstallLocation" && !data.is_empty())
+        .map(|(_, _, data)| PathBuf::from(data))
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn registry_install_locations() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+/// Paths from the config files under the launcher's folders in AppData
+fn launcher_config_paths() -> Vec<PathBuf> {
+    ["APPDATA", "LOCALAPPDATA"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(|dir| PathBuf::from(dir).join("NetEase"))
+        .filter(|dir| dir.is_dir())
+        .flat_map(|dir| walkdir::WalkDir::new(dir).max_depth(4))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry.file_type().is_file()
+                && entry
+                    .metadata()
+                    .is_ok_and(|metadata| metadata.len() <= MAX_CONFIG_SIZE)
+                && entry.path().extension().is_some_and(|ext| {
+                    ["json", "ini", "cfg", "txt", "xml"]
+                        .iter()
+                        .any(|known| ext.eq_ignore_ascii_case(known))
+                })
+        })
+        .filter_map(|entry| std::fs::read(entry.path()).ok())
+        .flat_map(|bytes| paths_in_config(&String::from_utf8_lossy(&bytes)))
+        .collect()
+}
+
+fn drives() -> Vec<PathBuf> {
+    if cfg!(target_os = "windows") {
+        (b'C'..=b'Z')
+            .map(|letter| PathBuf::from(format!("{}:\\", letter as char)))
+            .filter(|drive| drive.is_dir())
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Game folders from `sources` in order, each once (compared ignoring
+/// case, as on Windows) and with the first source that found it
+fn collect_hits(sources: Vec<(DetectionSource, Vec<PathBuf>)>) -> Vec<DetectedGameDirectory> {
+    let mut seen = HashSet::new();
+    let mut hits = Vec::new();
+    for (source, paths) in sources {
+        for path in paths.iter().filter_map(|path| game_directory_of(path)) {
+            if seen.insert(path.to_string_lossy().to_lowercase()) {
+                hits.push(DetectedGameDirectory { path, source });
+            }
+        }
+    }
+    hits
+}
+
+/// Every game install found, best guess first. `extra_folder_names` are
+/// tried on top of [`DEFAULT_FOLDER_NAMES`].
+pub fn find_game_directories(extra_folder_names: &[String]) -> Vec<DetectedGameDirectory> {
+    let folder_names: Vec<&str> = DEFAULT_FOLDER_NAMES
+        .iter()
+        .copied()
+        .chain(extra_folder_names.iter().map(String::as_str))
+        .collect();
+    let hits = collect_hits(vec![
+        (
+            DetectionSource::SteamDefault,
+            vec![PathBuf::from(
+                r"C:\Program Files (x86)\Steam\steamapps\common\MarvelRivals",
+            )],
+        ),
+        (
+            DetectionSource::UninstallRegistry,
+            registry_install_locations(),
+        ),
+        (DetectionSource::NetEaseLauncher, launcher_config_paths()),
+        (
+            DetectionSource::CandidateFolder,
+            candidate_paths(&drives(), &folder_names),
+        ),
+    ]);
+
+    if hits.is_empty() {
+        log::warn!("Could not auto-detect Marvel Rivals installation");
+    }
+    for hit in &hits {
+        log::info!(
+            "Auto-detected Marvel Rivals at: {:?} ({:?})",
+            hit.path,
+            hit.source
+        );
+    }
+    hits
+}
+
+/// Every game install that can be found, tagged with where it was found,
+/// for the user to pick from
+#[tauri::command]
+pub async fn detect_game_directories(app: AppHandle) -> Result<Vec<DetectedGameDirectory>, String> {
+    let settings = crate::load_app_settings(&app)?;
+    Ok(find_game_directories(&settings.game_folder_names))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_game_dir(path: &Path) {
+        std::fs::create_dir_all(path.join("MarvelGame/Marvel/Content/Paks")).unwrap();
+    }
+
+    #[test]
+    fn test_game_folders_are_found_from_every_source() {
+        let drive = tempfile::TempDir::new().unwrap();
+        let steam = drive
+            .path()
+            .join(r"SteamLibrary\steamapps\common")
+            .join("漫威争锋");
+        let launcher = drive.path().join("Games").join("Marvel Rivals Global");
+        make_game_dir(&steam);
+        make_game_dir(&launcher);
+
+        let reg = "\r\nHKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\Steam App 2767030\r\n    DisplayName    REG_SZ    Marvel Rivals\r\n    InstallLocation    REG_SZ    D:\\Games\\Marvel Rivals\r\n\r\nEnd of search: 2 match(es) found.\r\n";
+        let values = parse_reg_query(reg);
+        assert_eq!(values.len(), 2);
+        assert!(values[0].0.ends_with("Steam App 2767030"));
+        assert_eq!(
+            (values[1].1.as_str(), values[1].2.as_str()),
+            ("InstallLocation", r"D:\Games\Marvel Rivals")
+        );
+
+        let config = r#"{"gamePath": "D:\\Games\\Marvel Rivals\\MarvelGame\\Binaries\\Win64\\MarvelGame-Win64-Shipping.exe", "cache":"E:/cache"}"#;
+        assert_eq!(
+            paths_in_config(config),
+            [
+                PathBuf::from(
+                    r"D:\Games\Marvel Rivals\MarvelGame\Binaries\Win64\MarvelGame-Win64-Shipping.exe"
+                ),
+                PathBuf::from("E:/cache"),
+            ]
+        );
+
+        // The launcher's path points at the exe inside the game folder; the
+        // same folder found again as a candidate keeps the first source
+        let drives = [drive.path().to_path_buf()];
+        let hits = collect_hits(vec![
+            (
+                DetectionSource::NetEaseLauncher,
+                vec![launcher.join("MarvelGame/Binaries/Win64/MarvelGame-Win64-Shipping.exe")],
+            ),
+            (
+                DetectionSource::CandidateFolder,
+                candidate_paths(&drives, &["漫威争锋", "Marvel Rivals Global"]),
+            ),
+        ]);
+        assert_eq!(
+            hits,
+            [
+                DetectedGameDirectory {
+                    path: launcher,
+                    source: DetectionSource::NetEaseLauncher
+                },
+                DetectedGameDirectory {
+                    path: steam,
+                    source: DetectionSource::CandidateFolder
+                },
+            ]
+        );
+    }
+}
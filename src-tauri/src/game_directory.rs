@@ -0,0 +1,204 @@
+//! The game folder going away while the app runs, usually an external drive
+//! that got unplugged or hasn't mounted yet. Commands then fail with
+//! [`GameDirectoryUnavailable`](crate::types::ModServiceError) instead of a
+//! generic I/O message, the library list falls back to the last listing saved here, and a background
+//! check notices when the drive comes back.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::types::ModInfo;
+
+/// How often the background check looks for the game folder
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+const SNAPSHOT_FILE: &str = "library-snapshot.json";
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Hash of the last snapshot written, to skip rewriting an unchanged library
+static LAST_SNAPSHOT: Mutex<Option<u64>> = Mutex::new(None);
+
+/// The last library listing taken while the game folder was reachable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibrarySnapshot {
+    pub saved_at: DateTime<Utc>,
+    pub mods: Vec<ModInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameDirectoryStatus {
+    pub configured: bool,
+    /// False when a game folder is configured but can't be found
+    pub available: bool,
+    pub path: Option<PathBuf>,
+    /// When the listing shown while offline was taken
+    pub snapshot_saved_at: Option<DateTime<Utc>>,
+}
+
+/// Whether `game_directory` is there right now. Going offline or coming back
+/// emits `game-directory-unavailable` / `game-directory-available`, and
+/// coming back restarts the file watcher.
+pub fn check(app: &AppHandle, game_directory: &Path) -> bool {
+    let available = game_directory.is_dir();
+    let was_offline = OFFLINE.swap(!available, Ordering::Relaxed);
+    if was_offline == !available {
+        return available;
+    }
+
+    if available {
+        log::info!(
+            "[game-dir] Game folder is back: {}",
+            game_directory.display()
+        );
+        crate::file_watcher::restart(app);
+        let _ = app.emit("game-directory-available", game_directory);
+    } else {
+        log::warn!(
+            "[game-dir] Game folder is missing, showing the last saved library: {}",
+            game_directory.display()
+        );
+        let _ = app.emit("game-directory-unavailable", game_directory);
+    }
+    available
+}
+
+/// Check on the configured game folder every 30 seconds, so a drive that
+/// reappears is picked up without the user doing anything
+pub fn start_monitor(app: AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(CHECK_INTERVAL);
+        if let Ok(settings) = crate::load_app_settings(&app) {
+            if let Some(game_directory) = settings.game_directory {
+                check(&app, &game_directory);
+            }
+        }
+    });
+}
+
+fn snapshot_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::app_data_dir(app)?.join(SNAPSHOT_FILE))
+}
+
+/// Remember `mods` as the listing to show while the game folder is offline
+pub fn save_snapshot(app: &AppHandle, mods: &[ModInfo]) {
+    if let Err(e) = snapshot_path(app).and_then(|path| write_snapshot(&path, mods, Utc::now())) {
+        log::warn!("[game-dir] Failed to save library snapshot: {}", e);
+    }
+}
+
+pub fn load_snapshot(app: &AppHandle) -> Option<LibrarySnapshot> {
+    match snapshot_path(app).and_then(|path| read_snapshot(&path)) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            log::warn!("[game-dir] Failed to read library snapshot: {}", e);
+            None
+        }
+    }
+}
+
+fn write_snapshot(path: &Path, mods: &[ModInfo], now: DateTime<Utc>) -> Result<(), String> {
+    let json = serde_json::to_vec(mods).map_err(|e| format!("Failed to serialize mods: {}", e))?;
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    json.hash(&mut hasher);
+    let hash = hasher.finish();
+
+    let mut last = LAST_SNAPSHOT.lock().unwrap_or_else(|e| e.into_inner());
+    if *last == Some(hash) && path.exists() {
+        return Ok(());
+    }
+    let snapshot = LibrarySnapshot {
+        saved_at: now,
+        mods: mods.to_vec(),
+    };
+    let json = serde_json::to_string(&snapshot)
+        .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write snapshot: {}", e))?;
+    *last = Some(hash);
+    Ok(())
+}
+
+fn read_snapshot(path: &Path) -> Result<Option<LibrarySnapshot>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let json =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read snapshot: {}", e))?;
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse snapshot: {}", e))
+}
+
+/// Whether the game folder is configured and reachable, for the status bar
+/// and settings page
+#[tauri::command]
+pub async fn get_game_directory_status(app: AppHandle) -> Result<GameDirectoryStatus, String> {
+    let settings = crate::load_app_settings(&app)?;
+    let available = match &settings.game_directory {
+        Some(game_directory) => check(&app, game_directory),
+        None => false,
+    };
+    let snapshot_saved_at = if available {
+        None
+    } else {
+        load_snapshot(&app).map(|snapshot| snapshot.saved_at)
+    };
+    Ok(GameDirectoryStatus {
+        configured: settings.game_directory.is_some(),
+        available,
+        path: settings.game_directory,
+        snapshot_saved_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::{date, Fixture};
+    use crate::types::{DeleteModError, ModServiceError};
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        service
+            .install_mod_to_folder(&fx.source_mod("Hulk_P", 8, None), "Hulk")
+            .unwrap();
+        let mods = service.get_all_mods().unwrap();
+
+        let path = fx.source_dir.join(SNAPSHOT_FILE);
+        assert!(read_snapshot(&path).unwrap().is_none());
+        write_snapshot(&path, &mods, date("2025-05-01T00:00:00Z")).unwrap();
+        // Unchanged mods keep the original timestamp
+        write_snapshot(&path, &mods, date("2025-06-01T00:00:00Z")).unwrap();
+
+        let snapshot = read_snapshot(&path).unwrap().unwrap();
+        assert_eq!(snapshot.saved_at, date("2025-05-01T00:00:00Z"));
+        assert_eq!(snapshot.mods.len(), 1);
+        assert_eq!(snapshot.mods[0].id, mods[0].id);
+    }
+
+    #[test]
+    fn test_unavailable_error_keeps_its_code() {
+        let error = ModServiceError::GameDirectoryUnavailable {
+            path: PathBuf::from("/mnt/games/MarvelRivals"),
+        };
+        let json = serde_json::to_value(DeleteModError::from(error.clone())).unwrap();
+        assert_eq!(json["code"], "gameDirectoryUnavailable");
+        assert_eq!(json["path"], "/mnt/games/MarvelRivals");
+        assert!(String::from(error).contains("/mnt/games/MarvelRivals"));
+
+        let json = serde_json::to_value(ModServiceError::from("Game directory not configured"));
+        assert_eq!(json.unwrap()["code"], "failed");
+    }
+}
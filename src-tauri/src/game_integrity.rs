@@ -0,0 +1,271 @@
+//! Evidence that the manager leaves the game's own files alone, for users
+//! worried that installing mods alters their install. The names and sizes
+//! of the base `pakchunk*` files in the Paks folder are recorded the first
+//! time a check runs and compared on later ones; a size change almost
+//! always means a game update, and can be accepted as the new baseline.
+//! Separately, every file of every mod has to live under `~mods` or
+//! `disabled-mods`, the only places the manager writes in the game folder.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use crate::mod_service::ModService;
+use crate::types::ModInfo;
+
+pub const FILE_NAME: &str = "game-integrity-baseline.json";
+
+/// Base file sizes as first seen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Baseline {
+    recorded_at: DateTime<Utc>,
+    /// File name -> size in bytes
+    files: BTreeMap<String, u64>,
+}
+
+/// A base file that differs from the baseline. A size of `None` means the
+/// file wasn't there at that point.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseFileChange {
+    pub name: String,
+    pub baseline_size: Option<u64>,
+    pub current_size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseGameIntegrity {
+    pub baseline_recorded_at: DateTime<Utc>,
+    /// Base files found in the Paks folder now
+    pub base_files: usize,
+    /// Base files changed since the baseline, usually by a game update
+    pub changed_files: Vec<BaseFileChange>,
+    /// Mod files outside `~mods` and `disabled-mods`
+    pub files_outside_mod_folders: Vec<PathBuf>,
+    /// Every mod file is where the manager is allowed to write
+    pub untouched: bool,
+}
+
+/// The game's own paks (and their .utoc/.ucas/.sig) directly in `paks_dir`,
+/// by name
+fn base_files(paks_dir: &Path) -> Result<BTreeMap<String, u64>, String> {
+    let entries =
+        fs::read_dir(paks_dir).map_err(|e| format!("Failed to read {:?}: {}", paks_dir, e))?;
+    Ok(entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let metadata = entry.metadata().ok()?;
+            (metadata.is_file() && name.to_lowercase().starts_with("pakchunk"))
+                .then(|| (name, metadata.len()))
+        })
+        .collect())
+}
+
+fn compare(
+    baseline: &BTreeMap<String, u64>,
+    current: &BTreeMap<String, u64>,
+) -> Vec<BaseFileChange> {
+    let mut names: Vec<&String> = baseline.keys().chain(current.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let baseline_size = baseline.get(name).copied();
+            let current_size = current.get(name).copied();
+            (baseline_size != current_size).then(|| BaseFileChange {
+                name: name.clone(),
+                baseline_size,
+                current_size,
+            })
+        })
+        .collect()
+}
+
+/// Files of `mods` that aren't under any of `allowed`
+fn files_outside(mods: &[ModInfo], allowed: &[&Path]) -> Vec<PathBuf> {
+    mods.iter()
+        .flat_map(|mod_info| std::iter::once(&mod_info.file_path).chain(&mod_info.associated_files))
+        .filter(|path| !allowed.iter().any(|folder| path.starts_with(folder)))
+        .cloned()
+        .collect()
+}
+
+fn load(baseline_file: &Path) -> Option<Baseline> {
+    let json = fs::read_to_string(baseline_file).ok()?;
+    serde_json::from_str(&json)
+        .map_err(|e| log::warn!("[integrity] Ignoring unreadable {:?}: {}", baseline_file, e))
+        .ok()
+}
+
+fn save(baseline_file: &Path, baseline: &Baseline) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(baseline)
+        .map_err(|e| format!("Failed to serialize baseline: {}", e))?;
+    fs::write(baseline_file, json).map_err(|e| format!("Failed to write baseline: {}", e))
+}
+
+/// Compare the base files with the baseline in `baseline_file`, recording
+/// it first if there is none or `rebaseline` is set, and check where every
+/// mod's files are
+fn check(
+    service: &ModService,
+    baseline_file: &Path,
+    rebaseline: bool,
+    now: DateTime<Utc>,
+) -> Result<BaseGameIntegrity, String> {
+    let mods_directory = service.mods_directory();
+    let paks_dir = mods_directory
+        .parent()
+        .ok_or("The ~mods folder has no parent Paks folder")?;
+    let current = base_files(paks_dir)?;
+
+    let baseline = match load(baseline_file).filter(|_| !rebaseline) {
+        Some(baseline) => baseline,
+        None => {
+            let baseline = Baseline {
+                recorded_at: now,
+                files: current.clone(),
+            };
+            save(baseline_file, &baseline)?;
+            log::info!(
+                "[integrity] Recorded a baseline of {} base file(s)",
+                current.len()
+            );
+            baseline
+        }
+    };
+
+    let changed_files = compare(&baseline.files, &current);
+    let files_outside_mod_folders = files_outside(
+        &service.get_all_mods()?,
+        &[mods_directory, service.disabled_mods_directory()],
+    );
+    if !changed_files.is_empty() {
+        log::info!(
+            "[integrity] {} base file(s) changed since {}, likely a game update",
+            changed_files.len(),
+            baseline.recorded_at
+        );
+    }
+    if !files_outside_mod_folders.is_empty() {
+        log::warn!(
+            "[integrity] {} mod file(s) outside ~mods and disabled-mods",
+            files_outside_mod_folders.len()
+        );
+    }
+    Ok(BaseGameIntegrity {
+        baseline_recorded_at: baseline.recorded_at,
+        base_files: current.len(),
+        untouched: files_outside_mod_folders.is_empty(),
+        changed_files,
+        files_outside_mod_folders,
+    })
+}
+
+fn baseline_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::app_data_dir(app)?.join(FILE_NAME))
+}
+
+/// Whether the game's own files are as first recorded, and every mod file
+/// is inside the mod folders. Records the baseline on the first run.
+#[tauri::command]
+pub async fn verify_base_game_untouched(app: AppHandle) -> Result<BaseGameIntegrity, String> {
+    let service = crate::get_mod_service(&app)?;
+    check(&service, &baseline_path(&app)?, false, Utc::now())
+}
+
+/// Accept the base files as they are now, e.g. after a game update
+#[tauri::command]
+pub async fn rebaseline_game_files(app: AppHandle) -> Result<BaseGameIntegrity, String> {
+    let service = crate::get_mod_service(&app)?;
+    check(&service, &baseline_path(&app)?, true, Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::{date, Fixture};
+
+    #[test]
+    fn test_base_files_are_compared_with_the_baseline() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        service
+            .install_mod_to_folder(&fx.source_mod("Hulk_P", 8, None), "Hulk")
+            .unwrap();
+        let paks_dir = fx.mods_dir().parent().unwrap().to_path_buf();
+        fs::write(paks_dir.join("pakchunk0-Windows.pak"), [0u8; 100]).unwrap();
+        fs::write(paks_dir.join("pakchunk0-Windows.utoc"), [0u8; 10]).unwrap();
+        fs::write(paks_dir.join("readme.txt"), "not a base file").unwrap();
+        let baseline_file = fx.source_dir.join(FILE_NAME);
+
+        let first = check(
+            &service,
+            &baseline_file,
+            false,
+            date("2025-05-01T00:00:00Z"),
+        )
+        .unwrap();
+        assert_eq!(first.base_files, 2);
+        assert!(first.changed_files.is_empty());
+        assert!(first.untouched);
+
+        // A game update grows one file and adds another
+        fs::write(paks_dir.join("pakchunk0-Windows.pak"), [0u8; 150]).unwrap();
+        fs::write(paks_dir.join("pakchunk1-Windows.pak"), [0u8; 5]).unwrap();
+        let updated = check(
+            &service,
+            &baseline_file,
+            false,
+            date("2025-06-01T00:00:00Z"),
+        )
+        .unwrap();
+        assert_eq!(updated.baseline_recorded_at, date("2025-05-01T00:00:00Z"));
+        assert_eq!(
+            updated.changed_files,
+            [
+                BaseFileChange {
+                    name: "pakchunk0-Windows.pak".to_string(),
+                    baseline_size: Some(100),
+                    current_size: Some(150),
+                },
+                BaseFileChange {
+                    name: "pakchunk1-Windows.pak".to_string(),
+                    baseline_size: None,
+                    current_size: Some(5),
+                },
+            ]
+        );
+        // Base files changing isn't the manager touching them
+        assert!(updated.untouched);
+
+        let rebaselined =
+            check(&service, &baseline_file, true, date("2025-06-01T00:00:00Z")).unwrap();
+        assert!(rebaselined.changed_files.is_empty());
+        assert_eq!(rebaselined.base_files, 3);
+    }
+
+    #[test]
+    fn test_mod_files_outside_the_mod_folders_are_reported() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        service
+            .install_mod_to_folder(&fx.source_mod("Hulk_P", 8, Some(16)), "Hulk")
+            .unwrap();
+        let mut mods = service.get_all_mods().unwrap();
+        let allowed = [fx.mods_dir(), fx.disabled_dir()];
+        let allowed: Vec<&Path> = allowed.iter().map(PathBuf::as_path).collect();
+        assert!(files_outside(&mods, &allowed).is_empty());
+
+        let stray = fx.source_dir.join("Hulk_P.utoc");
+        mods[0].associated_files.push(stray.clone());
+        assert_eq!(files_outside(&mods, &allowed), [stray]);
+    }
+}
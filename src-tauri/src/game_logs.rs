@@ -0,0 +1,185 @@
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// A mod the game complained about while mounting paks last session
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameLogIssue {
+    pub mod_id: String,
+    pub mod_title: String,
+    pub pak_file: String,
+    pub log_line: String,
+    /// Unreal log timestamp ("2025.01.31-18.04.12"), when the line has one
+    pub timestamp: Option<String>,
+}
+
+// Bare pak/utoc/ucas file name at the end of a path in a log line
+const FILE_PATTERN: &str = r#"(?i)[^\\/"'\s:]+\.(?:pak|utoc|ucas)\b"#;
+// Leading Unreal log timestamp, e.g. "[2025.01.31-18.04.12:345]"
+const TIME_PATTERN: &str = r"^\[(\d{4}\.\d{2}\.\d{2}-\d{2}\.\d{2}\.\d{2}):\d{3}\]";
+
+// Words that mark a line about a pak as a load failure rather than the
+// normal "Mounting pak file" chatter.
+const FAILURE_MARKERS: &[&str] = &[
+    "failed",
+    "error",
+    "corrupt",
+    "mismatch",
+    "couldn't",
+    "could not",
+    "unable",
+    "invalid",
+    "signature",
+];
+
+/// Directories the game writes `Marvel.log` to. The user profile location is
+/// the one the shipping build uses; the game-relative one covers portable
+/// and older installs.
+fn log_directories(game_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
+        dirs.push(
+            PathBuf::from(local_app_data)
+                .join("Marvel")
+                .join("Saved")
+                .join("Logs"),
+        );
+    }
+    dirs.push(
+        game_dir
+            .join("MarvelGame")
+            .join("Marvel")
+            .join("Saved")
+            .join("Logs"),
+    );
+    dirs
+}
+
+/// Newest `*.log` across all candidate directories
+fn newest_log(dirs: &[PathBuf]) -> Option<PathBuf> {
+    dirs.iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .filter(|e| {
+            e.path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("log"))
+        })
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Pull `(pak file name, timestamp)` out of a log line if it reports a
+/// failure involving a pak/utoc/ucas file.
+fn parse_failure_line(
+    line: &str,
+    file_re: &Regex,
+    time_re: &Regex,
+) -> Option<(String, Option<String>)> {
+    let lower = line.to_lowercase();
+    if !FAILURE_MARKERS.iter().any(|m| lower.contains(m)) {
+        return None;
+    }
+
+    let file_name = file_re.find(line)?.as_str().to_string();
+    let timestamp = time_re
+        .captures(line)
+        .and_then(|c| c.get(1))
+        .map(|m| m.as_str().to_string());
+    Some((file_name, timestamp))
+}
+
+/// Scan the newest game log for pak mount failures and map them back to
+/// installed mods. Lines about files we didn't install (base game paks) are
+/// ignored. Only the last complaint per mod is kept.
+#[tauri::command]
+pub async fn analyze_game_logs(app: AppHandle) -> Result<Vec<GameLogIssue>, String> {
+    find_game_log_issues(&app)
+}
+
+pub fn find_game_log_issues(app: &AppHandle) -> Result<Vec<GameLogIssue>, String> {
+    let settings = crate::load_app_settings(app)?;
+    let game_dir = settings
+        .game_directory
+        .ok_or("Game directory not configured")?;
+
+    let Some(log_path) = newest_log(&log_directories(&game_dir)) else {
+        log::info!("[game-logs] No game log found");
+        return Ok(Vec::new());
+    };
+    log::info!("[game-logs] Analyzing {:?}", log_path);
+
+    // Logs are usually UTF-8 but can contain stray bytes from asset names
+    let bytes = std::fs::read(&log_path).map_err(|e| format!("Failed to read game log: {e}"))?;
+    let content = String::from_utf8_lossy(&bytes);
+
+    let service = crate::get_mod_service(app)?;
+    let mods = service.get_all_mods()?;
+    let mut by_file_name: HashMap<String, (String, String)> = HashMap::new();
+    for m in &mods {
+        for file in &m.associated_files {
+            if let Some(name) = file.file_name().and_then(|n| n.to_str()) {
+                by_file_name.insert(
+                    name.to_lowercase(),
+                    (m.id.clone(), m.metadata.title.clone()),
+                );
+            }
+        }
+    }
+
+    let file_re = Regex::new(FILE_PATTERN).map_err(|e| format!("Regex compilation error: {e}"))?;
+    let time_re = Regex::new(TIME_PATTERN).map_err(|e| format!("Regex compilation error: {e}"))?;
+
+    let mut issues: HashMap<String, GameLogIssue> = HashMap::new();
+    for line in content.lines() {
+        let Some((file_name, timestamp)) = parse_failure_line(line, &file_re, &time_re) else {
+            continue;
+        };
+        let Some((mod_id, mod_title)) = by_file_name.get(&file_name.to_lowercase()) else {
+            continue;
+        };
+        issues.insert(
+            mod_id.clone(),
+            GameLogIssue {
+                mod_id: mod_id.clone(),
+                mod_title: mod_title.clone(),
+                pak_file: file_name,
+                log_line: line.trim().to_string(),
+                timestamp,
+            },
+        );
+    }
+
+    let mut issues: Vec<GameLogIssue> = issues.into_values().collect();
+    issues.sort_by(|a, b| a.mod_title.cmp(&b.mod_title));
+    log::info!(
+        "[game-logs] {} mod(s) failed to load last session",
+        issues.len()
+    );
+    Ok(issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_failure_line() {
+        let file_re = Regex::new(FILE_PATTERN).unwrap();
+        let time_re = Regex::new(TIME_PATTERN).unwrap();
+
+        let line = r"[2025.01.31-18.04.12:345][  0]LogPakFile: Error: Corrupt pak index in ../../../MarvelGame/Marvel/Content/Paks/~mods/Skins/Cool_P.pak";
+        let (file, timestamp) = parse_failure_line(line, &file_re, &time_re).unwrap();
+        assert_eq!(file, "Cool_P.pak");
+        assert_eq!(timestamp.as_deref(), Some("2025.01.31-18.04.12"));
+
+        // Normal mount chatter is not a failure
+        let ok = r"[2025.01.31-18.04.12:345][  0]LogPakFile: Display: Mounting pak file: ~mods/Cool_P.pak";
+        assert!(parse_failure_line(ok, &file_re, &time_re).is_none());
+    }
+}
@@ -0,0 +1,264 @@
+//! SHA-256 hashes of mod files, so a pak is read at most once per change
+//! however many features need its hash. Kept in `hash-cache.json` beside
+//! the metadata folder, keyed by path; an entry only counts while the file
+//! still has the size and modification time it was hashed at. The file
+//! watcher drops entries for paths that change, and files a scan finds
+//! without a hash are hashed on a background thread afterwards.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter};
+
+use crate::progress::ProgressEmitter;
+use crate::types::HashingProgress;
+
+pub const FILE_NAME: &str = "hash-cache.json";
+
+/// Background hashing saves after this many new hashes, so a long run
+/// that's interrupted keeps most of its work
+const SAVE_EVERY: usize = 20;
+
+/// Loaded caches, by cache file
+static CACHES: Mutex<BTreeMap<PathBuf, HashMap<PathBuf, HashEntry>>> = Mutex::new(BTreeMap::new());
+
+/// Set while a background run is going; a scan finishing meanwhile doesn't
+/// start a second one
+static HASHING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HashEntry {
+    size: u64,
+    /// Nanoseconds since the Unix epoch
+    modified: u64,
+    sha256: String,
+}
+
+/// Size and modification time, what an entry has to match to be reused
+fn stamp(path: &Path) -> std::io::Result<(u64, u64)> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    Ok((metadata.len(), modified))
+}
+
+fn compute(path: &Path) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn with_cache<T>(cache_file: &Path, f: impl FnOnce(&mut HashMap<PathBuf, HashEntry>) -> T) -> T {
+    let mut caches = CACHES.lock().unwrap_or_else(|e| e.into_inner());
+    let entries = caches
+        .entry(cache_file.to_path_buf())
+        .or_insert_with(|| load(cache_file));
+    f(entries)
+}
+
+fn load(cache_file: &Path) -> HashMap<PathBuf, HashEntry> {
+    let Ok(json) = fs::read_to_string(cache_file) else {
+        return HashMap::new();
+    };
+    serde_json::from_str(&json).unwrap_or_else(|e| {
+        log::warn!("[hash-cache] Ignoring unreadable {:?}: {}", cache_file, e);
+        HashMap::new()
+    })
+}
+
+fn save(cache_file: &Path) -> Result<(), String> {
+    let json = with_cache(cache_file, |entries| {
+        // Sorted, so the file diffs sensibly between runs
+        let sorted: BTreeMap<_, _> = entries.iter().collect();
+        serde_json::to_string(&sorted)
+    })
+    .map_err(|e| format!("Failed to serialize hash cache: {}", e))?;
+    if let Some(parent) = cache_file.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    fs::write(cache_file, json).map_err(|e| format!("Failed to write hash cache: {}", e))
+}
+
+/// The cached hash of `path`, if it hasn't changed since it was hashed
+pub fn cached(cache_file: &Path, path: &Path) -> Option<String> {
+    let (size, modified) = stamp(path).ok()?;
+    with_cache(cache_file, |entries| {
+        entries
+            .get(path)
+            .filter(|entry| entry.size == size && entry.modified == modified)
+            .map(|entry| entry.sha256.clone())
+    })
+}
+
+/// Hash `path` and remember it. Not recorded if the file changed while it
+/// was being read.
+fn hash_and_record(cache_file: &Path, path: &Path) -> std::io::Result<String> {
+    let (size, modified) = stamp(path)?;
+    let sha256 = compute(path)?;
+    if stamp(path)? == (size, modified) {
+        with_cache(cache_file, |entries| {
+            entries.insert(
+                path.to_path_buf(),
+                HashEntry {
+                    size,
+                    modified,
+                    sha256: sha256.clone(),
+                },
+            )
+        });
+    }
+    Ok(sha256)
+}
+
+/// The SHA-256 of `path`, from the cache when the file hasn't changed,
+/// otherwise read and recorded
+pub fn get_or_compute(cache_file: &Path, path: &Path) -> Result<String, String> {
+    if let Some(sha256) = cached(cache_file, path) {
+        return Ok(sha256);
+    }
+    let sha256 = hash_and_record(cache_file, path)
+        .map_err(|e| format!("Failed to hash {:?}: {}", path, e))?;
+    save(cache_file)?;
+    Ok(sha256)
+}
+
+/// Forget the hashes of `paths` and anything under them, in every loaded
+/// cache. Called by the file watcher for each change it sees.
+pub fn invalidate(paths: &[PathBuf]) {
+    let mut caches = CACHES.lock().unwrap_or_else(|e| e.into_inner());
+    for entries in caches.values_mut() {
+        entries.retain(|path, _| !paths.iter().any(|changed| path.starts_with(changed)));
+    }
+}
+
+/// Carry hashes over to where files were moved, given as (from, to). A
+/// move keeps size and modification time, so the entries stay valid.
+pub fn rename_paths(cache_file: &Path, moves: &[(PathBuf, PathBuf)]) -> Result<(), String> {
+    let moved = with_cache(cache_file, |entries| {
+        moves
+            .iter()
+            .filter(|(from, to)| {
+                let Some(entry) = entries.remove(from) else {
+                    return false;
+                };
+                entries.insert(to.clone(), entry);
+                true
+            })
+            .count()
+    });
+    if moved > 0 {
+        save(cache_file)?;
+    }
+    Ok(())
+}
+
+/// Drop every hash. Returns how many there were.
+pub fn clear(cache_file: &Path) -> Result<usize, String> {
+    let cleared = with_cache(cache_file, |entries| {
+        let count = entries.len();
+        entries.clear();
+        count
+    });
+    if cache_file.exists() {
+        fs::remove_file(cache_file).map_err(|e| format!("Failed to remove hash cache: {}", e))?;
+    }
+    log::info!("[hash-cache] Cleared {} hash(es)", cleared);
+    Ok(cleared)
+}
+
+/// Hash `files` that have no current hash, one at a time on a background
+/// thread, sending `hashing-progress` as it goes. Does nothing if a run is
+/// already going or everything is hashed.
+pub fn hash_in_background(app: &AppHandle, cache_file: PathBuf, files: Vec<PathBuf>) {
+    let files: Vec<PathBuf> = files
+        .into_iter()
+        .filter(|path| cached(&cache_file, path).is_none())
+        .collect();
+    if files.is_empty() || HASHING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let total = files.len();
+        let emitter = ProgressEmitter::new(|progress: HashingProgress| {
+            let _ = app.emit("hashing-progress", progress);
+        });
+        log::info!("[hash-cache] Hashing {} file(s) in the background", total);
+        for (done, path) in files.iter().enumerate() {
+            emitter.emit(
+                done as u64,
+                total as u64,
+                HashingProgress {
+                    done,
+                    total,
+                    current: Some(path.clone()),
+                },
+            );
+            if let Err(e) = hash_and_record(&cache_file, path) {
+                log::debug!("[hash-cache] Skipped {:?}: {}", path, e);
+            }
+            if (done + 1) % SAVE_EVERY == 0 {
+                if let Err(e) = save(&cache_file) {
+                    log::warn!("[hash-cache] {}", e);
+                }
+            }
+        }
+        if let Err(e) = save(&cache_file) {
+            log::warn!("[hash-cache] {}", e);
+        }
+        emitter.finish(HashingProgress {
+            done: total,
+            total,
+            current: None,
+        });
+        HASHING.store(false, Ordering::SeqCst);
+        log::info!("[hash-cache] Finished hashing {} file(s)", total);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashes_are_cached_until_the_file_changes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cache_file = dir.path().join(FILE_NAME);
+        let pak = dir.path().join("Mod_P.pak");
+        fs::write(&pak, b"first").unwrap();
+
+        assert_eq!(cached(&cache_file, &pak), None);
+        let first = get_or_compute(&cache_file, &pak).unwrap();
+        assert_eq!(first, compute(&pak).unwrap());
+        assert_eq!(cached(&cache_file, &pak), Some(first.clone()));
+        // Saved, and read back from disk
+        CACHES.lock().unwrap().remove(&cache_file);
+        assert_eq!(cached(&cache_file, &pak), Some(first.clone()));
+
+        // A different size no longer matches
+        fs::write(&pak, b"second!").unwrap();
+        assert_eq!(cached(&cache_file, &pak), None);
+        let second = get_or_compute(&cache_file, &pak).unwrap();
+        assert_ne!(first, second);
+
+        // The watcher invalidating a folder drops what's under it
+        invalidate(&[dir.path().to_path_buf()]);
+        assert_eq!(cached(&cache_file, &pak), None);
+
+        get_or_compute(&cache_file, &pak).unwrap();
+        assert_eq!(clear(&cache_file).unwrap(), 1);
+        assert!(!cache_file.exists());
+        assert_eq!(cached(&cache_file, &pak), None);
+    }
+}
@@ -0,0 +1,103 @@
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::i18n::{t, tn};
+use crate::types::GlobalHotkeySettings;
+use crate::{notifications, safe_mode};
+
+/// Build the global-shortcut plugin. The handler only reacts to key-down so a
+/// single press toggles once.
+pub fn plugin() -> tauri::plugin::TauriPlugin<tauri::Wry> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(|app, _shortcut, event| {
+            if event.state() != ShortcutState::Pressed {
+                return;
+            }
+            // Toggling moves every mod on disk, keep it off the main thread
+            let app = app.clone();
+            std::thread::spawn(move || toggle_safe_mode_from_hotkey(&app));
+        })
+        .build()
+}
+
+/// (Re)register the safe-mode shortcut from settings. Called at startup and
+/// whenever settings are saved; an invalid accelerator is logged and skipped.
+pub fn apply(app: &AppHandle, settings: &GlobalHotkeySettings) {
+    let shortcuts = app.global_shortcut();
+    if let Err(e) = shortcuts.unregister_all() {
+        log::warn!("[hotkeys] Failed to clear global shortcuts: {e}");
+    }
+
+    if !settings.enabled {
+        log::info!("[hotkeys] Global hotkeys disabled");
+        return;
+    }
+
+    let shortcut: Shortcut = match settings.toggle_safe_mode.parse() {
+        Ok(shortcut) => shortcut,
+        Err(e) => {
+            log::warn!(
+                "[hotkeys] Invalid safe mode shortcut \"{}\": {e}",
+                settings.toggle_safe_mode
+            );
+            return;
+        }
+    };
+
+    match shortcuts.register(shortcut) {
+        Ok(_) => log::info!(
+            "[hotkeys] Registered {} for safe mode toggle",
+            settings.toggle_safe_mode
+        ),
+        Err(e) => log::warn!(
+            "[hotkeys] Failed to register {}: {e}",
+            settings.toggle_safe_mode
+        ),
+    }
+}
+
+fn toggle_safe_mode_from_hotkey(app: &AppHandle) {
+    // Paks are mounted at launch, so toggling mid-session changes nothing
+    // until a restart. Say so instead of silently moving files.
+    if crate::game_process_running().unwrap_or(false) {
+        notifications::show(
+            app,
+            &t("notify.safeMode.unchangedTitle", &[]),
+            &t("notify.safeMode.gameRunning", &[]),
+        );
+        return;
+    }
+
+    if let Err(e) = crate::read_only::check("safe mode hotkey") {
+        notifications::show(
+            app,
+            &t("notify.safeMode.unchangedTitle", &[]),
+            &e.to_string(),
+        );
+        return;
+    }
+
+    let active = safe_mode::load_state(app)
+        .map(|state| state.active)
+        .unwrap_or(false);
+
+    let (title, body) = if active {
+        match safe_mode::exit(app) {
+            Ok(restored) => (
+                t("notify.safeMode.offTitle", &[]),
+                tn("notify.safeMode.offBody", restored, &[]),
+            ),
+            Err(e) => (t("notify.safeMode.failedTitle", &[]), e),
+        }
+    } else {
+        match safe_mode::enter(app) {
+            Ok(state) => (
+                t("notify.safeMode.onTitle", &[]),
+                tn("notify.safeMode.onBody", state.disabled_mod_ids.len(), &[]),
+            ),
+            Err(e) => (t("notify.safeMode.failedTitle", &[]), e),
+        }
+    };
+
+    notifications::show(app, &title, &body);
+}
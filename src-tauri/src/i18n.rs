@@ -0,0 +1,206 @@
+//! Translations for text the backend shows itself: menu labels, native
+//! notifications and the messages of typed errors (their `code` stays as
+//! it is, for the frontend to match on). Messages are flat JSON maps in
+//! `resources/locales/`, bundled into the binary, with `{name}`
+//! placeholders. A key missing from a locale falls back to English, logged
+//! once per key.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Display;
+use std::sync::{Mutex, RwLock};
+
+use tauri::{AppHandle, Emitter};
+
+/// Bundled locales; English first, as the fallback for the others
+const LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("../resources/locales/en.json")),
+    ("es", include_str!("../resources/locales/es.json")),
+    ("ja", include_str!("../resources/locales/ja.json")),
+    ("zh-CN", include_str!("../resources/locales/zh-CN.json")),
+];
+
+const FALLBACK: &str = "en";
+
+static CURRENT: RwLock<&str> = RwLock::new(FALLBACK);
+
+/// Parsed message files, by locale, filled on first use
+static MESSAGES: Mutex<BTreeMap<&str, HashMap<String, String>>> = Mutex::new(BTreeMap::new());
+
+/// `locale/key` pairs already logged as missing
+static WARNED: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// The bundled locale for a requested one: an exact match ignoring case and
+/// `_`/`-`, else one for the same language ("es-MX" gets "es", "zh" gets
+/// "zh-CN")
+pub fn resolve(requested: &str) -> Option<&'static str> {
+    let requested = requested.trim().replace('_', "-");
+    let language = |code: &str| code.split('-').next().unwrap_or_default().to_lowercase();
+    LOCALES
+        .iter()
+        .map(|(code, _)| *code)
+        .find(|code| code.eq_ignore_ascii_case(&requested))
+        .or_else(|| {
+            LOCALES
+                .iter()
+                .map(|(code, _)| *code)
+                .find(|code| language(code) == language(&requested))
+        })
+}
+
+/// The locale backend text is currently shown in
+pub fn current() -> &'static str {
+    *CURRENT.read().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Switch to `locale`, or the closest bundled one. Unknown locales switch to
+/// English. Returns the locale now in use.
+pub fn set_current(locale: &str) -> &'static str {
+    let resolved = resolve(locale).unwrap_or_else(|| {
+        log::warn!("[i18n] No messages for locale {locale:?}, using {FALLBACK}");
+        FALLBACK
+    });
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = resolved;
+    resolved
+}
+
+/// Use the locale saved in preferences, if any. Runs at startup, before the
+/// menu is built.
+pub fn init(app: &AppHandle) {
+    match crate::read_preferences(app) {
+        Ok(preferences) => {
+            if let Some(locale) = preferences.locale {
+                log::info!("[i18n] Using locale {}", set_current(&locale));
+            }
+        }
+        Err(e) => log::warn!("[i18n] Failed to read preferences, using {FALLBACK}: {e}"),
+    }
+}
+
+/// The message for `key` in the current locale, with `args` filled in
+pub fn t(key: &str, args: &[(&str, &dyn Display)]) -> String {
+    translate(current(), key, args)
+}
+
+/// `t` for a message about `count` things, passed as `{count}`. Uses the
+/// `<key>.one` message for a count of 1 where the locale has one.
+pub fn tn(key: &str, count: usize, args: &[(&str, &dyn Display)]) -> String {
+    let mut args = args.to_vec();
+    args.push(("count", &count));
+    let locale = current();
+    if count == 1 {
+        if let Some(message) = lookup(locale, &format!("{key}.one")) {
+            return fill(&message, &args);
+        }
+    }
+    translate(locale, key, &args)
+}
+
+fn translate(locale: &str, key: &str, args: &[(&str, &dyn Display)]) -> String {
+    if let Some(message) = lookup(locale, key) {
+        return fill(&message, args);
+    }
+    warn_once(locale, key);
+    match lookup(FALLBACK, key) {
+        Some(message) => fill(&message, args),
+        // A typo in the code rather than a missing translation
+        None => key.to_string(),
+    }
+}
+
+fn lookup(locale: &str, key: &str) -> Option<String> {
+    let mut messages = MESSAGES.lock().unwrap_or_else(|e| e.into_inner());
+    let (code, json) = LOCALES.iter().find(|(code, _)| *code == locale)?;
+    messages
+        .entry(*code)
+        .or_insert_with(|| {
+            serde_json::from_str(json).unwrap_or_else(|e| {
+                log::error!("[i18n] Failed to parse messages for {code}: {e}");
+                HashMap::new()
+            })
+        })
+        .get(key)
+        .cloned()
+}
+
+fn warn_once(locale: &str, key: &str) {
+    let mut warned = WARNED.lock().unwrap_or_else(|e| e.into_inner());
+    if warned.insert(format!("{locale}/{key}")) {
+        log::warn!("[i18n] No {locale} message for {key:?}, falling back to {FALLBACK}");
+    }
+}
+
+/// Replace each `{name}` in `message` with its argument. Placeholders
+/// without one are left as they are.
+fn fill(message: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut filled = message.to_string();
+    for (name, value) in args {
+        filled = filled.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    filled
+}
+
+/// Show backend text in `locale` from now on: saved in preferences, the
+/// menu rebuilt with its labels and `locale-changed` sent to every window.
+/// Returns the bundled locale picked, which may be a close match.
+#[tauri::command]
+pub async fn set_locale(app: AppHandle, locale: String) -> Result<String, String> {
+    let resolved = resolve(&locale).ok_or_else(|| format!("Unsupported locale: {locale}"))?;
+    set_current(resolved);
+
+    let mut preferences = crate::read_preferences(&app)?;
+    preferences.locale = Some(resolved.to_string());
+    crate::write_preferences(&app, &preferences)?;
+
+    let menu = crate::build_app_menu(&app).map_err(|e| format!("Failed to rebuild menu: {e}"))?;
+    app.set_menu(menu)
+        .map_err(|e| format!("Failed to rebuild menu: {e}"))?;
+
+    app.emit("locale-changed", resolved)
+        .map_err(|e| format!("Failed to emit locale-changed: {e}"))?;
+    log::info!("[i18n] Locale changed to {resolved}");
+    Ok(resolved.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(locale: &str) -> BTreeSet<String> {
+        let (_, json) = LOCALES.iter().find(|(code, _)| *code == locale).unwrap();
+        serde_json::from_str::<HashMap<String, String>>(json)
+            .unwrap()
+            .into_keys()
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_locale() {
+        assert_eq!(resolve("en"), Some("en"));
+        assert_eq!(resolve("zh_cn"), Some("zh-CN"));
+        assert_eq!(resolve("zh"), Some("zh-CN"));
+        assert_eq!(resolve("es-MX"), Some("es"));
+        assert_eq!(resolve("JA"), Some("ja"));
+        assert_eq!(resolve("fr"), None);
+    }
+
+    #[test]
+    fn test_translate_fills_args_and_falls_back() {
+        let count = 3;
+        assert_eq!(
+            translate("en", "notify.gameUpdated.check", &[("count", &count)]),
+            "3 enabled mod(s) may need checking after the game update"
+        );
+        // Not a key any locale has: shown as is rather than blank
+        assert_eq!(translate("ja", "no.such.key", &[]), "no.such.key");
+        assert_eq!(fill("{a} and {b}", &[("a", &1)]), "1 and {b}");
+    }
+
+    #[test]
+    fn test_locales_only_use_english_keys() {
+        let english = keys(FALLBACK);
+        for (locale, _) in LOCALES {
+            let extra: Vec<String> = keys(locale).difference(&english).cloned().collect();
+            assert!(extra.is_empty(), "{locale} has unknown keys {extra:?}");
+        }
+    }
+}
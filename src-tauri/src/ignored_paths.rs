@@ -0,0 +1,113 @@
+//! Folders under ~mods the user keeps for themselves (tool folders,
+//! `_backup/`) and wants the manager to leave alone: not scanned, organized,
+//! merged or cleaned up. Patterns are relative to the mods root and may use
+//! `*` (within one folder name), `**` (across folders) and `?`.
+
+use regex::Regex;
+use std::path::{Component, Path};
+
+#[derive(Debug, Clone, Default)]
+pub struct IgnoredPaths {
+    patterns: Vec<Regex>,
+}
+
+impl IgnoredPaths {
+    /// Compile the patterns from settings. Ones that can't be compiled are
+    /// logged and skipped.
+    pub fn new(patterns: &[String]) -> Self {
+        let patterns = patterns
+            .iter()
+            .filter_map(|pattern| match glob_to_regex(pattern) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    log::warn!("[ignored-paths] Skipping pattern {:?}: {}", pattern, e);
+                    None
+                }
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Whether `relative`, a path below the mods root, is ignored: it or a
+    /// folder above it matches a pattern
+    pub fn matches(&self, relative: &Path) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let mut prefix = String::new();
+        for component in relative.components() {
+            let Component::Normal(name) = component else {
+                continue;
+            };
+            if !prefix.is_empty() {
+                prefix.push('/');
+            }
+            prefix.push_str(&name.to_string_lossy());
+            if self.patterns.iter().any(|regex| regex.is_match(&prefix)) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Normalize a pattern as typed: '/' separators, no leading or trailing ones
+pub fn normalize_pattern(pattern: &str) -> String {
+    pattern
+        .trim()
+        .replace('\\', "/")
+        .trim_matches('/')
+        .to_string()
+}
+
+/// Case-insensitive, whole-path regex for a glob pattern
+fn glob_to_regex(pattern: &str) -> Result<Regex, String> {
+    let pattern = normalize_pattern(pattern);
+    if pattern.is_empty() {
+        return Err("empty pattern".to_string());
+    }
+
+    let mut regex = String::from("(?i)^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    Regex::new(&regex).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_folders_and_their_contents() {
+        let ignored = IgnoredPaths::new(&[
+            "_backup/".to_string(),
+            "Tools\\*-old".to_string(),
+            "**/scratch".to_string(),
+        ]);
+        assert!(ignored.matches(Path::new("_backup")));
+        assert!(ignored.matches(Path::new("_BACKUP/Skins/a_P.pak")));
+        assert!(ignored.matches(Path::new("Tools/fmodel-old/x.pak")));
+        assert!(!ignored.matches(Path::new("Tools/fmodel/x.pak")));
+        assert!(ignored.matches(Path::new("Skins/Hulk/scratch/a_P.pak")));
+        // A pattern names a whole folder, not the start of one
+        assert!(!ignored.matches(Path::new("_backup2/a_P.pak")));
+        assert!(!ignored.matches(Path::new("Skins/_backup/a_P.pak")));
+
+        assert!(IgnoredPaths::new(&["  ".to_string()]).is_empty());
+    }
+}
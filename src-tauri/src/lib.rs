@@ -1,28 +1,113 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::menu::{MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder};
+use tauri::menu::{
+    CheckMenuItemBuilder, Menu, MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder,
+};
 use tauri::{AppHandle, Emitter, Manager};
 
 // Marvel Rivals Mod Manager modules
-mod archive_extractor;
+pub mod archive_extractor;
+mod backend_info;
+mod cli;
+mod collections;
 mod costume_service;
+mod demo;
+mod detection;
+mod disk_space;
+mod download_service;
+mod file_access;
+mod file_import;
 mod file_watcher;
-mod mod_service;
+mod folders;
+mod fs_ops;
+mod game_detection;
+mod game_directory;
+mod game_integrity;
+mod game_logs;
+mod hash_cache;
+mod hotkeys;
+mod i18n;
+mod ignored_paths;
+mod library_cleanup;
+mod library_repair;
+mod menu_state;
+mod metadata_validation;
+mod mod_cache;
+mod mod_cards;
+pub mod mod_service;
+mod mod_updates;
+mod mods_integrity;
+mod notifications;
+mod perf_metrics;
+mod profile_service;
+mod progress;
+mod quarantine;
+mod read_only;
+mod rescan;
+mod safe_mode;
+mod settings_cache;
+mod sorting;
+mod thumbnail_import;
 mod thumbnail_service;
-mod types;
+pub mod types;
+mod windows;
+mod write_behind;
 
-use archive_extractor::{detect_mods_in_archive, extract_and_detect_mods, extract_archive};
+use archive_extractor::{
+    detect_mods_in_archive, extract_and_detect_mods, extract_archive, preview_archive,
+};
+use backend_info::get_backend_info;
+use collections::{
+    add_to_collection, create_collection, delete_collection, export_collection, list_collections,
+    remove_from_collection, reorder_collection, set_collection_enabled,
+};
 use costume_service::{
-    get_all_costumes, get_costume, get_costumes_for_character, initialize_costume_service,
-    sync_costumes,
+    export_costume_overrides, get_all_costumes, get_costume, get_costume_service_status,
+    get_costumes_for_character, initialize_costume_service, retry_costume_initialization,
+    sync_costumes, validate_costume_override_file,
 };
+use demo::demo_nexus_request;
+use disk_space::get_disk_usage;
+use download_service::{cancel_download, list_downloads, pause_download, resume_download};
+use file_access::{read_dropped_file_info, read_image_for_preview};
+use file_import::{append_file_chunk, begin_file_import, cancel_file_import, finish_file_import};
 use file_watcher::{start_file_watcher, stop_file_watcher};
-use mod_service::ModService;
+use folders::{open_metadata_folder, open_mod_folder, open_mods_root, show_in_folder};
+use game_detection::detect_game_directories;
+use game_directory::get_game_directory_status;
+use game_integrity::{rebaseline_game_files, verify_base_game_untouched};
+use game_logs::analyze_game_logs;
+use i18n::{set_locale, t};
+use library_cleanup::{analyze_library_cleanup, apply_cleanup_suggestions};
+use library_repair::{get_library_repair_checkpoint, run_library_repair};
+use menu_state::{set_sidebar_menu_state, MenuState};
+use mod_cache::{refresh_mods, ModCache};
+use mod_cards::{get_mod, get_mod_cards};
+use mod_service::{InstallCopy, ModService};
+use mod_updates::{dismiss_mod_update, get_pending_mod_updates};
+use mods_integrity::{restore_mods_directory, verify_mods_integrity};
+use notifications::{
+    notify_install_complete, test_notification, NotificationKind, NotificationOutcome,
+};
+use profile_service::{
+    apply_profile, create_profile, create_profile_from_favorites, delete_profile,
+    get_active_profile, list_profiles, preview_profile_apply, rename_profile,
+};
+use progress::{CancellationToken, ProgressEmitter};
+use quarantine::{get_quarantine_status, release_quarantine};
+use read_only::{enter_read_only_mode, exit_read_only_mode, get_read_only_mode};
+use rescan::force_rescan;
+use safe_mode::{enter_safe_mode, exit_safe_mode, get_safe_mode_status};
+use settings_cache::SettingsCache;
+use thumbnail_import::import_thumbnails_from_folder;
 use thumbnail_service::{CropData, ThumbnailService};
 use types::*;
+use windows::{open_mod_detail_window, WindowState};
 
 // Validation functions
 fn validate_filename(filename: &str) -> Result<(), String> {
@@ -94,6 +179,17 @@ pub struct AppPreferences {
     pub card_glow: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nexus_api_key: Option<String>,
+    /// Last library sort the user picked, restored on next launch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mod_sort: Option<SortSpec>,
+    /// Size and position of each window by label, saved by the backend as
+    /// windows close
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub window_states: BTreeMap<String, WindowState>,
+    /// Locale for menus, notifications and backend errors, set through
+    /// `set_locale`. None is English.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
 }
 
 impl Default for AppPreferences {
@@ -105,15 +201,26 @@ impl Default for AppPreferences {
             card_tilt: Some(true),
             card_glow: Some(true),
             nexus_api_key: None,
+            mod_sort: None,
+            window_states: BTreeMap::new(),
+            locale: None,
         }
     }
 }
 
-fn get_preferences_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
+/// The app data folder, or the demo sandbox's when launched with `--demo`.
+/// Everything that reads or writes app data finds it through here.
+fn app_data_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    if let Some(data_dir) = demo::data_dir() {
+        return Ok(data_dir.to_path_buf());
+    }
+    app.path()
         .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+        .map_err(|e| format!("Failed to get app data directory: {e}"))
+}
+
+fn get_preferences_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app_data_dir(app)?;
 
     // Ensure the directory exists
     std::fs::create_dir_all(&app_data_dir)
@@ -124,7 +231,11 @@ fn get_preferences_path(app: &AppHandle) -> Result<PathBuf, String> {
 
 #[tauri::command]
 async fn load_preferences(app: AppHandle) -> Result<AppPreferences, String> {
-    let prefs_path = get_preferences_path(&app)?;
+    read_preferences(&app)
+}
+
+fn read_preferences(app: &AppHandle) -> Result<AppPreferences, String> {
+    let prefs_path = get_preferences_path(app)?;
 
     if !prefs_path.exists() {
         log::info!("Preferences file not found, using defaults");
@@ -145,14 +256,24 @@ async fn load_preferences(app: AppHandle) -> Result<AppPreferences, String> {
 }
 
 #[tauri::command]
-async fn save_preferences(app: AppHandle, preferences: AppPreferences) -> Result<(), String> {
+async fn save_preferences(app: AppHandle, mut preferences: AppPreferences) -> Result<(), String> {
     // Validate theme value
     validate_theme(&preferences.theme)?;
 
+    // Window positions and the locale belong to the backend; the frontend's
+    // copy of the preferences doesn't carry them
+    if let Ok(current) = read_preferences(&app) {
+        preferences.window_states = current.window_states;
+        preferences.locale = current.locale;
+    }
+    write_preferences(&app, &preferences)
+}
+
+fn write_preferences(app: &AppHandle, preferences: &AppPreferences) -> Result<(), String> {
     log::debug!("Saving preferences to disk: {preferences:?}");
-    let prefs_path = get_preferences_path(&app)?;
+    let prefs_path = get_preferences_path(app)?;
 
-    let json_content = serde_json::to_string_pretty(&preferences).map_err(|e| {
+    let json_content = serde_json::to_string_pretty(preferences).map_err(|e| {
         log::error!("Failed to serialize preferences: {e}");
         format!("Failed to serialize preferences: {e}")
     })?;
@@ -179,44 +300,14 @@ async fn send_native_notification(
     app: AppHandle,
     title: String,
     body: Option<String>,
-) -> Result<(), String> {
+) -> Result<NotificationOutcome, String> {
     log::info!("Sending native notification: {title}");
-
-    #[cfg(not(mobile))]
-    {
-        use tauri_plugin_notification::NotificationExt;
-
-        let mut notification = app.notification().builder().title(title);
-
-        if let Some(body_text) = body {
-            notification = notification.body(body_text);
-        }
-
-        match notification.show() {
-            Ok(_) => {
-                log::info!("Native notification sent successfully");
-                Ok(())
-            }
-            Err(e) => {
-                log::error!("Failed to send native notification: {e}");
-                Err(format!("Failed to send notification: {e}"))
-            }
-        }
-    }
-
-    #[cfg(mobile)]
-    {
-        log::warn!("Native notifications not supported on mobile");
-        Err("Native notifications not supported on mobile".to_string())
-    }
+    Ok(notifications::try_show(&app, &title, body.as_deref()))
 }
 
 // Recovery functions - simple pattern for saving JSON data to disk
 fn get_recovery_dir(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {e}"))?;
+    let app_data_dir = app_data_dir(app)?;
 
     let recovery_dir = app_data_dir.join("recovery");
 
@@ -395,10 +486,7 @@ async fn migrate_electron_data(app: AppHandle) -> Result<(usize, usize), String>
     let old_metadata_dir = old_app_data.join("metadata");
     let old_thumbnails_dir = old_app_data.join("thumbnails");
 
-    let new_app_data = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let new_app_data = app_data_dir(&app)?;
 
     let new_metadata_dir = new_app_data.join("metadata");
     let new_thumbnails_dir = new_app_data.join("thumbnails");
@@ -519,19 +607,30 @@ async fn migrate_electron_data(app: AppHandle) -> Result<(usize, usize), String>
                 if !obj.contains_key("isNsfw") {
                     obj.insert("isNsfw".to_string(), JsonValue::Bool(false));
                 }
+                // Resolve the install date before defaulting createdAt, otherwise
+                // it would inherit "now" and every migrated mod would sort as
+                // freshly installed. Older Electron builds used other key names;
+                // failing those, the metadata file's own mtime is the best guess.
+                if !obj.contains_key("installDate") {
+                    let install_date = ["installedAt", "dateAdded", "createdAt"]
+                        .iter()
+                        .find_map(|key| obj.get(*key).filter(|v| v.is_string()).cloned())
+                        .or_else(|| {
+                            let modified = entry.metadata().ok()?.modified().ok()?;
+                            Some(JsonValue::String(
+                                chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339(),
+                            ))
+                        })
+                        .unwrap_or_else(|| JsonValue::String(now.clone()));
+                    obj.insert("installDate".to_string(), install_date);
+                }
                 if !obj.contains_key("createdAt") {
-                    obj.insert("createdAt".to_string(), JsonValue::String(now.clone()));
+                    let install_date = obj["installDate"].clone();
+                    obj.insert("createdAt".to_string(), install_date);
                 }
                 if !obj.contains_key("updatedAt") {
                     obj.insert("updatedAt".to_string(), JsonValue::String(now.clone()));
                 }
-                if !obj.contains_key("installDate") {
-                    if let Some(created) = obj.get("createdAt") {
-                        obj.insert("installDate".to_string(), created.clone());
-                    } else {
-                        obj.insert("installDate".to_string(), JsonValue::String(now.clone()));
-                    }
-                }
                 if !obj.contains_key("profileIds") {
                     obj.insert("profileIds".to_string(), JsonValue::Array(vec![]));
                 }
@@ -558,6 +657,22 @@ async fn migrate_electron_data(app: AppHandle) -> Result<(usize, usize), String>
         }
     }
 
+    // The copied thumbnails are still named the old way; give them to their
+    // mods now that the metadata is in place
+    if migrated_thumbnails > 0 {
+        match get_mod_service(&app)
+            .map_err(String::from)
+            .and_then(|service| service.consolidate_thumbnail_storage())
+        {
+            Ok(report) => log::info!(
+                "Consolidated {} thumbnails, {} unclaimed",
+                report.migrated,
+                report.unclaimed.len()
+            ),
+            Err(e) => log::warn!("Failed to consolidate migrated thumbnails: {}", e),
+        }
+    }
+
     log::info!(
         "Migration complete: {} metadata files, {} thumbnails",
         migrated_metadata,
@@ -569,40 +684,327 @@ async fn migrate_electron_data(app: AppHandle) -> Result<(usize, usize), String>
 
 // ===== MOD MANAGEMENT COMMANDS =====
 
-fn get_mod_service(app: &AppHandle) -> Result<ModService, String> {
+fn get_mod_service(app: &AppHandle) -> Result<ModService, ModServiceError> {
+    build_mod_service(app, true)
+}
+
+/// `require_game_directory` off skips checking that the game folder is
+/// there, for reading app data while its drive is offline
+fn build_mod_service(
+    app: &AppHandle,
+    require_game_directory: bool,
+) -> Result<ModService, ModServiceError> {
     // Get settings to find game directory
     let app_settings = load_app_settings(app)?;
 
     let game_directory = app_settings
         .game_directory
+        .as_deref()
         .ok_or("Game directory not configured")?;
+    if require_game_directory && !game_directory::check(app, game_directory) {
+        return Err(ModServiceError::GameDirectoryUnavailable {
+            path: game_directory.to_path_buf(),
+        });
+    }
 
-    let metadata_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?
-        .join("metadata");
+    let app_data_dir = app_data_dir(app)?;
+    mod_service_for_data_dir(&app_data_dir, &app_settings).map_err(ModServiceError::from)
+}
+
+/// A `ModService` over the app data in `app_data_dir` with `settings`, for
+/// callers without an `AppHandle` such as the CLI. Does not check that the
+/// game folder is there.
+fn mod_service_for_data_dir(
+    app_data_dir: &std::path::Path,
+    settings: &AppSettings,
+) -> Result<ModService, String> {
+    let game_directory = settings
+        .game_directory
+        .clone()
+        .ok_or("Game directory not configured")?;
+
+    let active_profile = if settings.auto_assign_to_active_profile {
+        profile_service::active_profile_id_in(app_data_dir)?
+    } else {
+        None
+    };
+
+    let metadata_dir = app_data_dir.join("metadata");
+    Ok(ModService::new(game_directory, metadata_dir)
+        .with_mods_directory(settings.mod_directory.clone())
+        .with_active_profile(active_profile)
+        .with_disable_strategy(settings.disable_strategy)
+        .with_ignored_paths(&settings.ignored_paths)
+        .with_max_scan_depth(settings.max_scan_depth))
+}
+
+/// The library from the scan cache, saving the listing for when the game
+/// folder is offline and hashing new files in the background whenever it
+/// was re-read
+pub(crate) async fn list_mods(app: &AppHandle) -> Result<Vec<ModInfo>, String> {
+    match get_mod_service(app) {
+        Ok(service) => {
+            let (mods, changed) = app.state::<ModCache>().mods(&service).await?;
+            if !changed {
+                return Ok(mods);
+            }
+            game_directory::save_snapshot(app, &mods);
+            hash_cache::hash_in_background(
+                app,
+                service.hash_cache_path(),
+                service.hashable_files(&mods),
+            );
+            Ok(mods)
+        }
+        // Drive unplugged: show the library as it was rather than nothing
+        Err(e @ ModServiceError::GameDirectoryUnavailable { .. }) => {
+            Ok(game_directory::load_snapshot(app).ok_or(e)?.mods)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
 
-    Ok(ModService::new(game_directory, metadata_dir))
+/// `get_mod_service` looking single mods up in the scan cache's listing, for
+/// commands acting on one mod: a cached library is only re-read for that mod
+async fn listed_mod_service(app: &AppHandle) -> Result<ModService, ModServiceError> {
+    let service = get_mod_service(app)?;
+    let (mods, _) = app.state::<ModCache>().mods(&service).await?;
+    Ok(service.with_listing(mods))
+}
+
+#[tauri::command]
+async fn get_all_mods(app: AppHandle, sort: Option<SortSpec>) -> Result<Vec<ModInfo>, String> {
+    let mut mods = list_mods(&app).await?;
+    if let Some(sort) = sort {
+        sorting::sort_mods(&mut mods, &sort);
+    }
+    Ok(mods)
 }
 
 #[tauri::command]
-async fn get_all_mods(app: AppHandle) -> Result<Vec<ModInfo>, String> {
+async fn detect_mod_conflicts(
+    app: AppHandle,
+    include_disabled: Option<bool>,
+) -> Result<Vec<ModConflict>, String> {
     let service = get_mod_service(&app)?;
-    service.get_all_mods()
+    let timer = service.timer("detect_mod_conflicts");
+    let conflicts = service.detect_mod_conflicts(include_disabled.unwrap_or(false))?;
+    timer.finish(conflicts.len());
+    Ok(conflicts)
 }
 
 #[tauri::command]
-async fn detect_mod_conflicts(app: AppHandle) -> Result<Vec<ModConflict>, String> {
+async fn get_pak_technical_info(
+    app: AppHandle,
+    mod_id: String,
+) -> Result<PakTechnicalInfo, String> {
     let service = get_mod_service(&app)?;
-    service.detect_mod_conflicts()
+    service.get_pak_technical_info(&mod_id)
 }
 
 #[tauri::command]
-async fn install_mod(app: AppHandle, file_path: String) -> Result<ModInfo, String> {
-    log::info!("Installing mod from: {}", file_path);
+async fn verify_mods(app: AppHandle) -> Result<Vec<ModVerificationIssue>, String> {
+    let service = get_mod_service(&app)?;
+    service.verify_mods()
+}
+
+/// Whether a mod's pak is intact and its companions pair up
+#[tauri::command]
+async fn validate_mod(app: AppHandle, mod_id: String) -> Result<PakValidation, String> {
+    let service = get_mod_service(&app)?;
+    service.validate_mod(&mod_id)
+}
+
+/// Every installed mod that fails `validate_mod`
+#[tauri::command]
+async fn validate_mods(app: AppHandle) -> Result<Vec<PakValidation>, String> {
     let service = get_mod_service(&app)?;
-    service.install_mod(PathBuf::from(file_path).as_path())
+    service.validate_mods()
+}
+
+/// Dashboard counts in one call. Conflicts and pending updates come from
+/// their last runs and say whether they are stale. With the game folder
+/// offline the counts come from the last saved listing, flagged `offline`.
+#[tauri::command]
+async fn get_library_summary(app: AppHandle) -> Result<LibrarySummary, String> {
+    let pending_updates = |mods: &[ModInfo]| {
+        mod_updates::pending_count(&app, mods).unwrap_or_else(|e| {
+            log::warn!("[summary] Pending updates unavailable: {}", e);
+            CachedCount {
+                count: 0,
+                stale: true,
+            }
+        })
+    };
+    match get_mod_service(&app) {
+        Ok(service) => service.library_summary(pending_updates),
+        Err(e @ ModServiceError::GameDirectoryUnavailable { .. }) => {
+            let snapshot = game_directory::load_snapshot(&app).ok_or(e)?;
+            let service = build_mod_service(&app, false)?;
+            let mut summary = service.summarize(snapshot.mods, pending_updates);
+            summary.offline = true;
+            Ok(summary)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[tauri::command]
+async fn get_app_stats(app: AppHandle) -> Result<AppStats, String> {
+    get_mod_service(&app)?.app_stats()
+}
+
+#[tauri::command]
+async fn get_category_stats(app: AppHandle) -> Result<Vec<CategoryStats>, String> {
+    get_mod_service(&app)?.category_stats()
+}
+
+/// Per-character counts; mods without a character come last with a null
+/// `character`
+#[tauri::command]
+async fn get_character_stats(app: AppHandle) -> Result<Vec<CharacterStats>, String> {
+    get_mod_service(&app)?.character_stats()
+}
+
+#[tauri::command]
+async fn get_storage_breakdown(app: AppHandle) -> Result<StorageBreakdown, String> {
+    let service = get_mod_service(&app)?;
+    service.get_storage_breakdown()
+}
+
+/// What the last full scan visited and how long each part took. Works with
+/// the game folder offline, since the profile lives in app data.
+#[tauri::command]
+async fn get_scan_profile(app: AppHandle) -> Result<Option<ScanProfile>, String> {
+    let service = build_mod_service(&app, false)?;
+    service.last_scan_profile()
+}
+
+/// Recent timings of the major operations, as p50/p95 per operation, with
+/// the last scan's profile. For telling a regression from a bigger library
+/// or a slow disk; nothing leaves the machine.
+#[tauri::command]
+async fn get_performance_metrics(app: AppHandle) -> Result<PerformanceMetrics, String> {
+    let service = build_mod_service(&app, false)?;
+    Ok(PerformanceMetrics {
+        operations: perf_metrics::summarize(&service.metrics_path()),
+        scan_profile: service.last_scan_profile()?,
+    })
+}
+
+/// Forget every cached file hash; they're recomputed in the background
+/// after the next scan. Returns how many were dropped.
+#[tauri::command]
+async fn clear_hash_cache(app: AppHandle) -> Result<usize, String> {
+    let service = build_mod_service(&app, false)?;
+    service.clear_hash_cache()
+}
+
+#[tauri::command]
+async fn get_disabled_mods_overview(app: AppHandle) -> Result<Vec<DisabledModEntry>, String> {
+    let service = get_mod_service(&app)?;
+    service.get_disabled_mods_overview()
+}
+
+#[tauri::command]
+async fn purge_disabled_mods(
+    app: AppHandle,
+    older_than_days: u32,
+    dry_run: Option<bool>,
+) -> Result<DisabledPurgeReport, String> {
+    let service = get_mod_service(&app)?;
+    service.purge_disabled_mods(older_than_days, dry_run.unwrap_or(false))
+}
+
+/// Installs copying right now, by source path, for `cancel_mod_install`.
+/// Every pak of a group install shares one token.
+static INSTALLS: Mutex<BTreeMap<String, CancellationToken>> = Mutex::new(BTreeMap::new());
+
+/// Run `install` of `file_paths` on the blocking pool, so copying a multi-GB
+/// pak doesn't hold up other commands, emitting `mod-install-progress` as it
+/// copies. A second install of a file while one is copying is refused, as
+/// is any pak that fails `validate_pak_file`.
+async fn run_install<F>(
+    app: &AppHandle,
+    file_paths: &[String],
+    install: F,
+) -> Result<InstallResult, String>
+where
+    F: FnOnce(ModService, &InstallCopy) -> Result<InstallResult, String> + Send + 'static,
+{
+    let service = get_mod_service(app)?;
+    // A truncated download fails here rather than crashing the game later
+    for file_path in file_paths {
+        mod_service::validate_pak_file(std::path::Path::new(file_path))?;
+    }
+    let cancel = CancellationToken::new();
+    {
+        let mut installs = INSTALLS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(busy) = file_paths.iter().find(|path| installs.contains_key(*path)) {
+            return Err(format!("{} is already being installed", busy));
+        }
+        for file_path in file_paths {
+            installs.insert(file_path.clone(), cancel.clone());
+        }
+    }
+    let app_for_progress = app.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let progress = ProgressEmitter::new(move |progress: ModInstallProgress| {
+            let _ = app_for_progress.emit("mod-install-progress", progress);
+        });
+        let on_progress = |update: ModInstallProgress| {
+            if update.current == update.total {
+                progress.finish(update);
+            } else {
+                progress.emit(update.current as u64, update.total as u64, update);
+            }
+        };
+        install(
+            service,
+            &InstallCopy {
+                cancel,
+                on_progress: &on_progress,
+            },
+        )
+    })
+    .await
+    .map_err(|e| format!("Install task failed: {}", e));
+    {
+        let mut installs = INSTALLS.lock().unwrap_or_else(|e| e.into_inner());
+        for file_path in file_paths {
+            installs.remove(file_path);
+        }
+    }
+    result?
+}
+
+#[tauri::command]
+async fn install_mod(app: AppHandle, file_path: String) -> Result<InstallResult, String> {
+    log::info!("Installing mod from: {}", file_path);
+    let source = PathBuf::from(&file_path);
+    run_install(&app, &[file_path], move |service, copy| {
+        service.install_mod_with(&source, copy)
+    })
+    .await
+}
+
+/// Stop an install that is still copying `file_path`. The files copied so
+/// far are removed and the install fails with "Install cancelled". False if
+/// no install of it is copying.
+#[tauri::command]
+async fn cancel_mod_install(file_path: String) -> Result<bool, String> {
+    match INSTALLS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&file_path)
+    {
+        Some(cancel) => {
+            log::info!("Cancelling install of {}", file_path);
+            cancel.cancel();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
 }
 
 #[tauri::command]
@@ -610,14 +1012,17 @@ async fn install_mod_to_folder(
     app: AppHandle,
     file_path: String,
     folder_name: String,
-) -> Result<ModInfo, String> {
+) -> Result<InstallResult, String> {
     log::info!(
         "Installing mod from {} to folder: {}",
         file_path,
         folder_name
     );
-    let service = get_mod_service(&app)?;
-    service.install_mod_to_folder(PathBuf::from(file_path).as_path(), &folder_name)
+    let source = PathBuf::from(&file_path);
+    run_install(&app, &[file_path], move |service, copy| {
+        service.install_to_folder_with(&source, &folder_name, None, copy)
+    })
+    .await
 }
 
 #[tauri::command]
@@ -626,31 +1031,97 @@ async fn install_mod_to_folder_with_metadata(
     file_path: String,
     folder_name: String,
     metadata: ModMetadata,
-) -> Result<ModInfo, String> {
+) -> Result<InstallResult, String> {
     log::info!(
         "Installing mod from {} to folder {} with custom metadata",
         file_path,
         folder_name
     );
-    let service = get_mod_service(&app)?;
-    service.install_mod_to_folder_with_metadata(
-        PathBuf::from(file_path).as_path(),
-        &folder_name,
-        metadata,
-    )
+    let source = PathBuf::from(&file_path);
+    run_install(&app, &[file_path], move |service, copy| {
+        let timer = service.timer("install_mod");
+        let result = service.install_to_folder_with(&source, &folder_name, Some(metadata), copy)?;
+        timer.finish(1);
+        Ok(result)
+    })
+    .await
 }
 
 #[tauri::command]
-async fn enable_mod(app: AppHandle, mod_id: String, enabled: bool) -> Result<(), String> {
+async fn enable_mod(
+    app: AppHandle,
+    mod_id: String,
+    enabled: bool,
+    exclusive_in_group: Option<bool>,
+) -> Result<ModInfo, String> {
     log::info!("Setting mod {} enabled status to: {}", mod_id, enabled);
+    let service = listed_mod_service(&app).await?;
+    let timer = service.timer("enable_mod");
+    // Enabling one variant turns the rest of its group off
+    let mod_info = if enabled && exclusive_in_group.unwrap_or(false) {
+        service.enable_variant(&mod_id)?
+    } else {
+        service.enable_mod(&mod_id, enabled)?
+    };
+    timer.finish(1);
+    Ok(mod_info)
+}
+
+/// Paks in one folder that look like variants of the same mod, to offer
+/// grouping them
+#[tauri::command]
+async fn detect_variant_groups(app: AppHandle) -> Result<Vec<VariantGroup>, String> {
+    let service = get_mod_service(&app)?;
+    service.detect_variant_groups()
+}
+
+/// Group mods as variants of one mod under `group_name`, or ungroup them
+/// when it's missing or blank
+#[tauri::command]
+async fn group_mods_as_variants(
+    app: AppHandle,
+    mod_ids: Vec<String>,
+    group_name: Option<String>,
+) -> Result<Vec<ModInfo>, String> {
     let service = get_mod_service(&app)?;
-    service.enable_mod(&mod_id, enabled)
+    service.group_mods_as_variants(&mod_ids, group_name.as_deref())
+}
+
+/// Write the mod list in `format` to `dest_path` for sharing, and return it
+#[tauri::command]
+async fn export_mod_list(
+    app: AppHandle,
+    format: ModListFormat,
+    include_disabled: bool,
+    dest_path: String,
+) -> Result<String, String> {
+    let service = get_mod_service(&app)?;
+    let list = service.export_list(format, include_disabled)?;
+    std::fs::write(&dest_path, &list).map_err(|e| format!("Failed to write mod list: {}", e))?;
+    log::info!("Exported mod list as {:?} to {}", format, dest_path);
+    Ok(list)
+}
+
+/// Set a mod list shared by `export_mod_list` (JSON) against the library.
+/// With `apply`, enable the listed mods that are installed but disabled.
+#[tauri::command]
+async fn import_mod_list(
+    app: AppHandle,
+    json: String,
+    apply: bool,
+) -> Result<ModListImportReport, String> {
+    if apply {
+        read_only::check("import_mod_list")?;
+    }
+    let service = get_mod_service(&app)?;
+    let _pause = apply.then(file_watcher::pause);
+    service.import_list(&json, apply)
 }
 
 /// Enable/disable many mods in one call. Loops in the backend so bulk
 /// operations avoid one IPC round-trip (and one query refetch) per mod.
-/// Returns the number successfully toggled; individual failures are logged
-/// and skipped rather than aborting the whole batch.
+/// Returns the refreshed info for each mod successfully toggled; individual
+/// failures are logged and skipped rather than aborting the whole batch.
 #[derive(Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct BulkToggleProgress {
@@ -659,48 +1130,205 @@ struct BulkToggleProgress {
     enabled: bool,
 }
 
+/// `reason` is recorded on the mods being disabled (e.g. to settle a
+/// conflict); without one it counts as a manual toggle.
 #[tauri::command]
 async fn set_mods_enabled(
     app: AppHandle,
     mod_ids: Vec<String>,
     enabled: bool,
-) -> Result<usize, String> {
+    reason: Option<DisabledReason>,
+) -> Result<Vec<ToggledMod>, String> {
+    let reason = reason.unwrap_or(DisabledReason::UserAction);
+    let timer = get_mod_service(&app)?.timer("set_mods_enabled");
+    let toggled = toggle_mods_with_progress(&app, &mod_ids, enabled, &reason)?;
+    timer.finish(toggled.len());
+    Ok(toggled)
+}
+
+/// Mods matching every field set in `query`, e.g. everything disabled for a
+/// given reason
+#[tauri::command]
+async fn search_mods(app: AppHandle, query: ModQuery) -> Result<Vec<ModInfo>, String> {
+    let service = get_mod_service(&app)?;
+    Ok(service
+        .get_all_mods()?
+        .into_iter()
+        .filter(|m| query.matches(m))
+        .collect())
+}
+
+/// A page of the mods matching `query`, in `sort` order, and how many match
+/// in all, so the list needn't ship every mod on each refresh
+#[tauri::command]
+async fn query_mods(
+    app: AppHandle,
+    query: ModQuery,
+    sort: Option<SortSpec>,
+    offset: usize,
+    limit: usize,
+) -> Result<ModPage, String> {
+    let mods = list_mods(&app).await?;
+    Ok(mod_service::page_mods(
+        mods,
+        Some(&query),
+        sort.as_ref(),
+        offset,
+        limit,
+    ))
+}
+
+#[tauri::command]
+async fn get_scan_warnings(app: AppHandle) -> Result<Vec<ScanWarning>, String> {
+    get_mod_service(&app)?.scan_warnings()
+}
+
+/// What `set_mods_enabled` would move for the same arguments, without moving
+/// anything
+#[tauri::command]
+async fn preview_set_mods_enabled(
+    app: AppHandle,
+    mod_ids: Vec<String>,
+    enabled: bool,
+) -> Result<TogglePreview, String> {
+    let service = get_mod_service(&app)?;
+    if enabled {
+        service.preview_toggle(&mod_ids, &[])
+    } else {
+        service.preview_toggle(&[], &mod_ids)
+    }
+}
+
+/// Bulk toggle that reports `bulk-toggle-progress` to the frontend; shared by
+/// `set_mods_enabled` and `set_collection_enabled`
+fn toggle_mods_with_progress(
+    app: &AppHandle,
+    mod_ids: &[String],
+    enabled: bool,
+    reason: &DisabledReason,
+) -> Result<Vec<ToggledMod>, String> {
+    Ok(bulk_toggle_with_progress(app, mod_ids, enabled, reason)?.toggled)
+}
+
+/// [`toggle_mods_with_progress`] that also says which mods failed and why
+fn bulk_toggle_with_progress(
+    app: &AppHandle,
+    mod_ids: &[String],
+    enabled: bool,
+    reason: &DisabledReason,
+) -> Result<BulkToggleReport, String> {
     log::info!(
         "Bulk setting {} mod(s) enabled status to: {}",
         mod_ids.len(),
         enabled
     );
-    let service = get_mod_service(&app)?;
+    let service = get_mod_service(app)?;
     let app_for_progress = app.clone();
-    let ok = service.set_mods_enabled(&mod_ids, enabled, move |current, total| {
-        let _ = app_for_progress.emit(
-            "bulk-toggle-progress",
-            BulkToggleProgress {
-                current,
-                total,
-                enabled,
-            },
-        );
+    let progress = ProgressEmitter::new(move |progress: BulkToggleProgress| {
+        let _ = app_for_progress.emit("bulk-toggle-progress", progress);
+    });
+    let report = service.enable_mods_bulk(mod_ids, enabled, reason, |current, total| {
+        let update = BulkToggleProgress {
+            current,
+            total,
+            enabled,
+        };
+        if current == total {
+            progress.finish(update);
+        } else {
+            progress.emit(current as u64, total as u64, update);
+        }
     })?;
-    log::info!("Bulk toggle complete: {}/{} succeeded", ok, mod_ids.len());
-    Ok(ok)
+    log::info!(
+        "Bulk toggle complete: {}/{} succeeded",
+        report.toggled.len(),
+        mod_ids.len()
+    );
+    Ok(report)
+}
+
+/// Enable or disable many mods from one library scan. A mod that can't be
+/// moved is reported in `failed` without stopping the rest.
+#[tauri::command]
+async fn enable_mods_bulk(
+    app: AppHandle,
+    mod_ids: Vec<String>,
+    enabled: bool,
+) -> Result<BulkToggleReport, String> {
+    let timer = get_mod_service(&app)?.timer("enable_mods_bulk");
+    let report = bulk_toggle_with_progress(&app, &mod_ids, enabled, &DisabledReason::UserAction)?;
+    timer.finish(mod_ids.len());
+    Ok(report)
 }
 
+/// Move a mod to the trash, or delete it for good with `permanent`. Mods in
+/// a profile or collection are refused with `ModInUse` unless `force` is
+/// set.
 #[tauri::command]
-async fn delete_mod(app: AppHandle, mod_id: String) -> Result<(), String> {
+async fn delete_mod(
+    app: AppHandle,
+    mod_id: String,
+    force: Option<bool>,
+    permanent: Option<bool>,
+) -> Result<(), DeleteModError> {
     log::info!("Deleting mod: {}", mod_id);
+    let service = listed_mod_service(&app).await?;
+    service.delete_mod(&mod_id, force.unwrap_or(false), permanent.unwrap_or(false))
+}
+
+/// Deleted mods that can still be restored, most recent first
+#[tauri::command]
+async fn list_trashed_mods(app: AppHandle) -> Result<Vec<TrashedMod>, String> {
+    let service = get_mod_service(&app)?;
+    Ok(service.list_trashed_mods())
+}
+
+/// Put a deleted mod back where it was, metadata and thumbnail included
+#[tauri::command]
+async fn restore_mod(app: AppHandle, mod_id: String) -> Result<ModInfo, String> {
+    log::info!("Restoring mod from the trash: {}", mod_id);
+    let service = get_mod_service(&app)?;
+    service.restore_mod(&mod_id)
+}
+
+/// Delete every trashed mod for good. Returns how many were removed.
+#[tauri::command]
+async fn empty_trash(app: AppHandle) -> Result<usize, String> {
+    let service = get_mod_service(&app)?;
+    service.empty_trash()
+}
+
+/// Category and character auto-detection would give a pak, with how
+/// confident each guess is
+#[tauri::command]
+async fn preview_mod_metadata(
+    app: AppHandle,
+    file_path: String,
+) -> Result<MetadataSuggestion, String> {
     let service = get_mod_service(&app)?;
-    service.delete_mod(&mod_id)
+    Ok(service.preview_mod_metadata(std::path::Path::new(&file_path)))
+}
+
+/// Installed mods whose character likely came from a weak file name guess
+#[tauri::command]
+async fn find_low_confidence_assignments(
+    app: AppHandle,
+) -> Result<Vec<LowConfidenceAssignment>, String> {
+    let service = get_mod_service(&app)?;
+    service.find_low_confidence_assignments()
+}
+
+/// Profiles and collections a mod belongs to, for the delete confirmation
+#[tauri::command]
+async fn get_mod_references(app: AppHandle, mod_id: String) -> Result<ModReferences, String> {
+    let service = get_mod_service(&app)?;
+    service.get_mod_references(&mod_id)
 }
 
 /// Marker file the NSIS uninstaller checks before offering mod cleanup.
 /// No marker = the uninstaller never prompts and never touches mods.
 fn uninstall_cleanup_marker(app: &AppHandle) -> Result<std::path::PathBuf, String> {
-    Ok(app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {e}"))?
-        .join("remove-mods-on-uninstall.txt"))
+    Ok(app_data_dir(app)?.join("remove-mods-on-uninstall.txt"))
 }
 
 #[tauri::command]
@@ -720,71 +1348,424 @@ async fn set_uninstall_cleanup(app: AppHandle, enabled: bool) -> Result<(), Stri
     Ok(())
 }
 
-/// Delete many mods in one call — single scan, one folder sweep at the end.
+/// What `delete_mods` would remove for these ids, for the delete confirmation
+#[tauri::command]
+async fn preview_delete(app: AppHandle, mod_ids: Vec<String>) -> Result<DeletePreview, String> {
+    let service = get_mod_service(&app)?;
+    service.preview_delete(&mod_ids)
+}
+
+/// Delete many mods in one call — single scan, removing only what
+/// `preview_delete` lists for the same ids, to the trash unless `permanent`
+/// is set.
+/// Refused with `ModInUse` if any is in a profile or collection, unless
+/// `force` is set.
+#[tauri::command]
+async fn delete_mods(
+    app: AppHandle,
+    mod_ids: Vec<String>,
+    force: Option<bool>,
+    permanent: Option<bool>,
+) -> Result<usize, DeleteModError> {
+    log::info!("Bulk deleting {} mod(s)", mod_ids.len());
+    let service = get_mod_service(&app)?;
+    let timer = service.timer("delete_mods");
+    let deleted =
+        service.delete_mods(&mod_ids, force.unwrap_or(false), permanent.unwrap_or(false))?;
+    timer.finish(deleted);
+    Ok(deleted)
+}
+
+/// Install several paks from one archive as a single mod (parent + add-ons),
+/// validated, copied and cancellable like a single install
+#[tauri::command]
+async fn install_mod_group(
+    app: AppHandle,
+    pak_files: Vec<String>,
+    group_name: String,
+    category: types::ModCategory,
+) -> Result<InstallResult, String> {
+    log::info!(
+        "Installing {} pak(s) as group \"{}\"",
+        pak_files.len(),
+        group_name
+    );
+    let installed = run_install(&app, &pak_files, {
+        let pak_files = pak_files.clone();
+        let group_name = group_name.clone();
+        move |service, copy| service.install_mod_group_with(&pak_files, &group_name, category, copy)
+    })
+    .await?;
+    notifications::notify(
+        &app,
+        NotificationKind::InstallComplete,
+        &t("notify.installComplete.title", &[]),
+        &i18n::tn(
+            "notify.installComplete.group",
+            pak_files.len(),
+            &[("name", &group_name)],
+        ),
+    );
+    Ok(installed)
+}
+
+#[tauri::command]
+async fn update_mod_metadata(
+    app: AppHandle,
+    mod_id: String,
+    metadata: ModMetadata,
+) -> Result<ModInfo, MetadataUpdateError> {
+    log::info!("Updating metadata for mod: {}", mod_id);
+    let service = listed_mod_service(&app).await?;
+    service.update_metadata(&mod_id, metadata)
+}
+
+/// Change only the given metadata fields (camelCase names). Unlike
+/// `update_mod_metadata` this never conflicts, so quick toggles like
+/// favorites and profile membership use it.
+#[tauri::command]
+async fn patch_mod_metadata(
+    app: AppHandle,
+    mod_id: String,
+    patch: serde_json::Map<String, serde_json::Value>,
+) -> Result<ModInfo, String> {
+    let service = get_mod_service(&app)?;
+    service.patch_metadata(&mod_id, &patch)
+}
+
+/// A mod's metadata as pretty-printed JSON, for the raw editor
+#[tauri::command]
+async fn get_mod_metadata_raw(app: AppHandle, mod_id: String) -> Result<String, String> {
+    let service = get_mod_service(&app)?;
+    service.metadata_raw(&mod_id)
+}
+
+/// Save hand-edited metadata JSON. Parse errors come back with their line
+/// and column, refused fields as validation issues.
+#[tauri::command]
+async fn set_mod_metadata_raw(
+    app: AppHandle,
+    mod_id: String,
+    json: String,
+) -> Result<ModInfo, MetadataUpdateError> {
+    log::info!("Saving raw metadata for mod: {}", mod_id);
+    let service = get_mod_service(&app)?;
+    service.set_metadata_raw(&mod_id, &json)
+}
+
+/// Flip a mod's favorite flag. Returns the mod and the new favorites count.
+#[tauri::command]
+async fn toggle_favorite(app: AppHandle, mod_id: String) -> Result<FavoriteToggle, String> {
+    let service = get_mod_service(&app)?;
+    service.toggle_favorite(&mod_id)
+}
+
+/// Favorite mods, sorted by character then title
+#[tauri::command]
+async fn get_favorites(app: AppHandle) -> Result<Vec<ModInfo>, String> {
+    let service = get_mod_service(&app)?;
+    service.get_favorites()
+}
+
+#[tauri::command]
+async fn remove_profile_from_all_mods(app: AppHandle, profile_id: String) -> Result<usize, String> {
+    log::info!("Removing profile {} from all mods", profile_id);
+    let service = get_mod_service(&app)?;
+    profile_service::forget_profile(&app, &profile_id)?;
+    service.remove_profile_from_all_mods(&profile_id)
+}
+
+/// Move every mod in one category (optionally narrowed by a query) to
+/// another, e.g. to sweep miscategorized mods into `Other`. Change events are
+/// held back until the whole batch is done. Returns the number of mods moved.
+#[tauri::command]
+async fn reassign_category(
+    app: AppHandle,
+    from: ModCategory,
+    to: ModCategory,
+    filter: Option<ModQuery>,
+) -> Result<usize, String> {
+    let service = get_mod_service(&app)?;
+    let _pause = file_watcher::pause();
+    service.reassign_category(&from, &to, filter.as_ref())
+}
+
+/// Find-and-replace in one metadata text field across the library (or the
+/// mods matching `filter`). Returns each change as before/after; `dry_run`
+/// only previews them. Change events are held back while titles move
+/// folders.
+#[tauri::command]
+async fn find_replace_metadata(
+    app: AppHandle,
+    field: MetadataTextField,
+    find: String,
+    replace: String,
+    filter: Option<ModQuery>,
+    regex: Option<bool>,
+    dry_run: Option<bool>,
+) -> Result<Vec<MetadataReplacement>, String> {
+    let service = get_mod_service(&app)?;
+    let _pause = file_watcher::pause();
+    service.find_replace_metadata(
+        field,
+        &find,
+        &replace,
+        filter.as_ref(),
+        regex.unwrap_or(false),
+        dry_run.unwrap_or(false),
+    )
+}
+
+/// Set the character, and optionally costume, of the selected mods in one
+/// go, moving the enabled ones into the character's folder. Change events
+/// are held back until every mod has moved. Returns each mod with its
+/// previous ID so the selection can follow it.
+#[tauri::command]
+async fn assign_character(
+    app: AppHandle,
+    mod_ids: Vec<String>,
+    character: Character,
+    costume: Option<String>,
+) -> Result<Vec<ToggledMod>, AssignCharacterError> {
+    let service = get_mod_service(&app)?;
+    let _pause = file_watcher::pause();
+    service.assign_character(
+        &mod_ids,
+        &character,
+        costume.as_deref(),
+        game_process_running().unwrap_or(false),
+    )
+}
+
+/// Mods whose folder no longer matches their metadata, e.g. after moving
+/// files in Explorer
+#[tauri::command]
+async fn get_layout_drift(app: AppHandle) -> Result<Vec<LayoutDrift>, String> {
+    let service = get_mod_service(&app)?;
+    service.get_layout_drift()
+}
+
+/// Move the selected drifted mods to where their metadata says they belong
+#[tauri::command]
+async fn apply_layout(app: AppHandle, mod_ids: Vec<String>) -> Result<Vec<ToggledMod>, String> {
+    let service = get_mod_service(&app)?;
+    let _pause = file_watcher::pause();
+    service.apply_layout(&mod_ids)
+}
+
+/// Keep the selected mods where they are, even across metadata edits
+#[tauri::command]
+async fn accept_layout(app: AppHandle, mod_ids: Vec<String>) -> Result<usize, String> {
+    let service = get_mod_service(&app)?;
+    service.accept_layout(&mod_ids)
+}
+
+/// The moves `organize_mods` would make, for the user to look over first
+#[tauri::command]
+async fn preview_organize_mods(app: AppHandle) -> Result<Vec<OrganizeMove>, String> {
+    let service = get_mod_service(&app)?;
+    service.preview_organize_mods()
+}
+
+/// File loose mods into their folders: all of them, or only the `approved`
+/// moves from `preview_organize_mods`
+#[tauri::command]
+async fn organize_mods(
+    app: AppHandle,
+    approved: Option<Vec<OrganizeMove>>,
+) -> Result<OrganizationResult, String> {
+    let _pause = file_watcher::pause();
+    let service = get_mod_service(&app)?;
+    let timer = service.timer("organize_mods");
+    let result = service.organize_loose_mods(approved.as_deref())?;
+    timer.finish(result.moved_mods);
+    Ok(result)
+}
+
+#[tauri::command]
+async fn merge_duplicate_folders(app: AppHandle) -> Result<FolderMergeReport, String> {
+    let service = get_mod_service(&app)?;
+    service.merge_duplicate_folders()
+}
+
+#[tauri::command]
+async fn migrate_metadata_to_path_ids(
+    app: AppHandle,
+    mod_ids: Option<Vec<String>>,
+    dry_run: Option<bool>,
+) -> Result<IdMigrationReport, String> {
+    let service = get_mod_service(&app)?;
+    service.migrate_metadata_to_path_ids(mod_ids.as_deref(), dry_run.unwrap_or(false))
+}
+
+#[tauri::command]
+async fn recover_orphaned_metadata(app: AppHandle) -> Result<usize, String> {
+    let service = get_mod_service(&app)?;
+    service.recover_orphaned_metadata()
+}
+
+#[tauri::command]
+async fn detect_split_mods(app: AppHandle) -> Result<Vec<SplitMod>, String> {
+    let service = get_mod_service(&app)?;
+    service.detect_split_mods()
+}
+
+#[tauri::command]
+async fn repair_split_mod(
+    app: AppHandle,
+    mod_id: String,
+    prefer: SplitSide,
+) -> Result<ModInfo, String> {
+    log::info!("Repairing split mod {} on the {:?} side", mod_id, prefer);
+    let service = get_mod_service(&app)?;
+    service.repair_split_mod(&mod_id, prefer)
+}
+
+/// Keep one copy of a mod found both enabled and disabled in place, deleting
+/// the other
 #[tauri::command]
-async fn delete_mods(app: AppHandle, mod_ids: Vec<String>) -> Result<usize, String> {
-    log::info!("Bulk deleting {} mod(s)", mod_ids.len());
+async fn resolve_duplicate(
+    app: AppHandle,
+    mod_id: String,
+    keep: ModLocation,
+) -> Result<ModInfo, String> {
+    log::info!("Resolving duplicate {}, keeping {:?}", mod_id, keep);
     let service = get_mod_service(&app)?;
-    service.delete_mods(&mod_ids)
+    let _pause = file_watcher::pause();
+    service.resolve_duplicate(&mod_id, keep)
 }
 
-/// Install several paks from one archive as a single mod (parent + add-ons).
+/// Mods installed more than once under different IDs, grouped by their
+/// identical .pak
 #[tauri::command]
-async fn install_mod_group(
+async fn find_duplicate_mods(app: AppHandle) -> Result<Vec<DuplicateModGroup>, String> {
+    let service = get_mod_service(&app)?;
+    let timer = service.timer("find_duplicate_mods");
+    let groups = service.find_duplicate_mods()?;
+    timer.finish(groups.len());
+    Ok(groups)
+}
+
+/// Keep one of a group of identical mods, moving the rest to the trash
+/// after filling in the kept copy's metadata from theirs
+#[tauri::command]
+async fn resolve_duplicate_mods(
     app: AppHandle,
-    pak_files: Vec<String>,
-    group_name: String,
-    category: types::ModCategory,
+    keep_mod_id: String,
+    remove_mod_ids: Vec<String>,
 ) -> Result<ModInfo, String> {
     log::info!(
-        "Installing {} pak(s) as group \"{}\"",
-        pak_files.len(),
-        group_name
+        "Keeping {} over {} identical copies",
+        keep_mod_id,
+        remove_mod_ids.len()
     );
     let service = get_mod_service(&app)?;
-    service.install_mod_group(&pak_files, &group_name, category)
+    let _pause = file_watcher::pause();
+    service.merge_duplicate_mods(&keep_mod_id, &remove_mod_ids)
 }
 
+/// Startup maintenance: reunite split mods that have one obvious fix
 #[tauri::command]
-async fn update_mod_metadata(
-    app: AppHandle,
-    mod_id: String,
-    metadata: ModMetadata,
-) -> Result<ModInfo, String> {
-    log::info!("Updating metadata for mod: {}", mod_id);
+async fn repair_split_mods(app: AppHandle) -> Result<SplitRepairReport, String> {
     let service = get_mod_service(&app)?;
-    service.update_metadata(&mod_id, metadata)
+    service.repair_split_mods()
 }
 
 #[tauri::command]
-async fn remove_profile_from_all_mods(app: AppHandle, profile_id: String) -> Result<usize, String> {
-    log::info!("Removing profile {} from all mods", profile_id);
+async fn detect_legacy_disabled_files(app: AppHandle) -> Result<Vec<LegacyDisabledMod>, String> {
     let service = get_mod_service(&app)?;
-    service.remove_profile_from_all_mods(&profile_id)
+    service.detect_legacy_disabled_files()
 }
 
+/// Startup maintenance: flag mods whose files were replaced outside the
+/// manager since it last looked, and tell the frontend with a
+/// `mods-externally-modified` event
 #[tauri::command]
-async fn organize_mods(app: AppHandle) -> Result<usize, String> {
+async fn reconcile_external_changes(app: AppHandle) -> Result<Vec<String>, String> {
     let service = get_mod_service(&app)?;
-    service.organize_loose_mods()
+    let mod_ids = service.reconcile_external_changes()?;
+    if !mod_ids.is_empty() {
+        let _ = app.emit(
+            "mods-externally-modified",
+            ExternallyModifiedMods {
+                mod_ids: mod_ids.clone(),
+            },
+        );
+    }
+    Ok(mod_ids)
 }
 
+/// Startup maintenance: move mods renamed `*.disabled` by older managers
+/// into disabled-mods
 #[tauri::command]
-async fn merge_duplicate_folders(app: AppHandle) -> Result<usize, String> {
+async fn convert_legacy_disabled(
+    app: AppHandle,
+    mod_ids: Option<Vec<String>>,
+    dry_run: Option<bool>,
+) -> Result<LegacyDisabledReport, String> {
     let service = get_mod_service(&app)?;
-    service.merge_duplicate_folders()
+    service.convert_legacy_disabled(mod_ids.as_deref(), dry_run.unwrap_or(false))
 }
 
+/// Startup maintenance: turn the Electron-era `thumbnails/` folder into
+/// per-mod thumbnails, setting aside images that match no mod
 #[tauri::command]
-async fn migrate_metadata_to_path_ids(app: AppHandle) -> Result<usize, String> {
+async fn consolidate_thumbnail_storage(
+    app: AppHandle,
+) -> Result<ThumbnailConsolidationReport, String> {
     let service = get_mod_service(&app)?;
-    service.migrate_metadata_to_path_ids()
+    service.consolidate_thumbnail_storage()
 }
 
+/// Switch how mods are disabled and move the already disabled ones to the
+/// new layout. The setting is saved even if some mods fail to convert; they
+/// keep working where they are.
 #[tauri::command]
-async fn recover_orphaned_metadata(app: AppHandle) -> Result<usize, String> {
+async fn convert_disable_strategy(
+    app: AppHandle,
+    strategy: DisableStrategy,
+    dry_run: Option<bool>,
+) -> Result<DisableStrategyReport, String> {
+    let dry_run = dry_run.unwrap_or(false);
     let service = get_mod_service(&app)?;
-    service.recover_orphaned_metadata()
+    let _pause = file_watcher::pause();
+    let report = service.convert_disable_strategy(strategy, dry_run)?;
+    if !dry_run {
+        let mut settings = load_app_settings(&app)?;
+        settings.disable_strategy = strategy;
+        save_app_settings_internal(&app, &settings)?;
+    }
+    Ok(report)
+}
+
+/// Leave a folder under ~mods alone from now on. Returns the updated list.
+#[tauri::command]
+async fn add_ignored_path(app: AppHandle, pattern: String) -> Result<Vec<String>, String> {
+    let pattern = ignored_paths::normalize_pattern(&pattern);
+    if pattern.is_empty() {
+        return Err("Enter a folder to ignore".to_string());
+    }
+    let mut settings = load_app_settings(&app)?;
+    if !settings.ignored_paths.contains(&pattern) {
+        settings.ignored_paths.push(pattern);
+        save_app_settings_internal(&app, &settings)?;
+    }
+    Ok(settings.ignored_paths)
+}
+
+/// Manage a previously ignored folder again. Returns the updated list.
+#[tauri::command]
+async fn remove_ignored_path(app: AppHandle, pattern: String) -> Result<Vec<String>, String> {
+    let pattern = ignored_paths::normalize_pattern(&pattern);
+    let mut settings = load_app_settings(&app)?;
+    let before = settings.ignored_paths.len();
+    settings
+        .ignored_paths
+        .retain(|p| ignored_paths::normalize_pattern(p) != pattern);
+    if settings.ignored_paths.len() != before {
+        save_app_settings_internal(&app, &settings)?;
+    }
+    Ok(settings.ignored_paths)
 }
 
 #[tauri::command]
@@ -799,6 +1780,31 @@ async fn enforce_addon_load_order(app: AppHandle) -> Result<usize, String> {
     service.enforce_addon_load_order()
 }
 
+/// Put a mod at `priority` in the load order (an `NNN_` top folder prefix),
+/// or clear it with `None`
+#[tauri::command]
+async fn set_mod_priority(
+    app: AppHandle,
+    mod_id: String,
+    priority: Option<u32>,
+) -> Result<ModInfo, String> {
+    let service = get_mod_service(&app)?;
+    let _pause = file_watcher::pause();
+    service.set_mod_priority(&mod_id, priority)
+}
+
+/// Load the mods in the given order, first first
+#[tauri::command]
+async fn reorder_mods(
+    app: AppHandle,
+    mod_ids_in_order: Vec<String>,
+) -> Result<Vec<ModInfo>, String> {
+    log::info!("Reordering {} mods", mod_ids_in_order.len());
+    let service = get_mod_service(&app)?;
+    let _pause = file_watcher::pause();
+    service.reorder_mods(&mod_ids_in_order)
+}
+
 #[tauri::command]
 async fn migrate_to_costume_folders(app: AppHandle) -> Result<usize, String> {
     let service = get_mod_service(&app)?;
@@ -811,89 +1817,17 @@ async fn download_nexus_mod(
     url: String,
     mod_name: String,
 ) -> Result<String, String> {
-    use tokio::io::AsyncWriteExt;
-
-    log::info!("Downloading mod from Nexus: {} ({})", mod_name, url);
-
-    let client = reqwest::Client::new();
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("Download request failed: {e}"))?;
-
-    if !response.status().is_success() {
-        return Err(format!(
-            "Download failed with status: {}",
-            response.status()
-        ));
+    if demo::is_enabled() {
+        let file_path = demo::fake_download(&mod_name)?;
+        log::info!("[demo] Stood in for {} with {:?}", mod_name, file_path);
+        return Ok(file_path.to_string_lossy().to_string());
     }
 
-    let total_size = response.content_length().unwrap_or(0);
-
-    // Get the filename from Content-Disposition header or URL
-    let filename = response
-        .headers()
-        .get("content-disposition")
-        .and_then(|v| v.to_str().ok())
-        .and_then(|v| {
-            v.split("filename=")
-                .nth(1)
-                .map(|f| f.trim_matches('"').to_string())
-        })
-        .unwrap_or_else(|| {
-            url.split('/')
-                .last()
-                .unwrap_or("nexus_mod.zip")
-                .split('?')
-                .next()
-                .unwrap_or("nexus_mod.zip")
-                .to_string()
-        });
-
-    let temp_dir = std::env::temp_dir().join("marvel_rivals_nexus");
-    std::fs::create_dir_all(&temp_dir).map_err(|e| format!("Failed to create temp dir: {e}"))?;
-
-    let file_path = temp_dir.join(&filename);
-
-    // Stream download in chunks with progress reporting
-    let mut stream = response.bytes_stream();
-    let mut file = tokio::fs::File::create(&file_path)
-        .await
-        .map_err(|e| format!("Failed to create file: {e}"))?;
-
-    let mut downloaded: u64 = 0;
-    let mut last_progress: u64 = 0;
-
-    use futures_util::StreamExt;
-    while let Some(chunk) = stream.next().await {
-        let chunk = chunk.map_err(|e| format!("Download stream error: {e}"))?;
-        file.write_all(&chunk)
-            .await
-            .map_err(|e| format!("Failed to write chunk: {e}"))?;
-
-        downloaded += chunk.len() as u64;
-
-        // Emit progress every 1% or every 100KB
-        if total_size > 0 {
-            let progress = (downloaded * 100) / total_size;
-            if progress != last_progress {
-                last_progress = progress;
-                let _ = app.emit("nexus-download-progress", progress);
-            }
-        }
-    }
+    log::info!("Downloading mod from Nexus: {} ({})", mod_name, url);
 
-    file.flush()
-        .await
-        .map_err(|e| format!("Failed to flush file: {e}"))?;
+    let file_path = download_service::download(&app, &url, "nexus_mod.zip", None).await?;
 
-    log::info!(
-        "Downloaded {} ({} bytes) to {:?}",
-        filename,
-        downloaded,
-        file_path
-    );
+    log::info!("Downloaded {} to {:?}", mod_name, file_path);
 
     Ok(file_path.to_string_lossy().to_string())
 }
@@ -939,11 +1873,7 @@ async fn log_total_mods_found(app: AppHandle) -> Result<(), String> {
 
 #[tauri::command]
 async fn get_metadata_directory(app: AppHandle) -> Result<String, String> {
-    let metadata_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?
-        .join("metadata");
+    let metadata_dir = app_data_dir(&app)?.join("metadata");
 
     metadata_dir
         .to_str()
@@ -964,11 +1894,7 @@ async fn copy_metadata_from_old_id(
 // ===== THUMBNAIL COMMANDS =====
 
 fn get_thumbnail_service(app: &AppHandle) -> Result<ThumbnailService, String> {
-    let metadata_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?
-        .join("metadata");
+    let metadata_dir = app_data_dir(app)?.join("metadata");
 
     Ok(ThumbnailService::new(metadata_dir))
 }
@@ -988,10 +1914,15 @@ async fn download_and_save_thumbnail(
 
     let service = get_thumbnail_service(&app)?;
 
-    let thumbnail_path = service
-        .download_and_save_thumbnail(&mod_id, &url, crop_data)
-        .await
-        .map_err(|e| format!("Failed to download and save thumbnail: {}", e))?;
+    let thumbnail_path = if demo::is_enabled() {
+        let placeholder = image::DynamicImage::ImageRgb8(demo::placeholder_thumbnail(url.len()));
+        service.save_thumbnail(&mod_id, &placeholder).await
+    } else {
+        service
+            .download_and_save_thumbnail(&mod_id, &url, crop_data)
+            .await
+    }
+    .map_err(|e| format!("Failed to download and save thumbnail: {}", e))?;
 
     Ok(thumbnail_path
         .to_str()
@@ -1052,6 +1983,50 @@ async fn delete_thumbnail(app: AppHandle, mod_id: String) -> Result<(), String>
         .map_err(|e| format!("Failed to delete thumbnail: {}", e))
 }
 
+/// Path of the uncropped original a mod's thumbnail was made from, for the
+/// re-crop dialog
+#[tauri::command]
+async fn get_thumbnail_source_path(
+    app: AppHandle,
+    mod_id: String,
+) -> Result<Option<String>, String> {
+    let service = get_thumbnail_service(&app)?;
+    Ok(service
+        .get_source_path(&mod_id)
+        .map(|path| path.to_string_lossy().to_string()))
+}
+
+/// Crop the thumbnail again from its stored original, without re-downloading
+#[tauri::command]
+async fn recrop_thumbnail(
+    app: AppHandle,
+    mod_id: String,
+    crop_data: CropData,
+) -> Result<String, String> {
+    log::info!("Re-cropping thumbnail for mod: {}", mod_id);
+
+    let service = get_thumbnail_service(&app)?;
+    let thumbnail_path = service
+        .recrop_thumbnail(&mod_id, &crop_data)
+        .map_err(|e| format!("Failed to re-crop thumbnail: {}", e))?;
+
+    Ok(thumbnail_path
+        .to_str()
+        .ok_or("Invalid thumbnail path")?
+        .to_string())
+}
+
+/// Delete every stored thumbnail original. Returns the bytes freed.
+#[tauri::command]
+async fn clear_thumbnail_sources(app: AppHandle) -> Result<u64, String> {
+    let service = get_thumbnail_service(&app)?;
+    let freed = service
+        .clear_sources()
+        .map_err(|e| format!("Failed to clear thumbnail originals: {}", e))?;
+    log::info!("Cleared thumbnail originals ({} bytes)", freed);
+    Ok(freed)
+}
+
 #[tauri::command]
 async fn get_temp_file_path(app: AppHandle, file_name: String) -> Result<String, String> {
     let temp_dir = app
@@ -1106,10 +2081,7 @@ async fn save_thumbnail_from_base64(
 // ===== SETTINGS COMMANDS =====
 
 fn get_settings_path(app: &AppHandle) -> Result<PathBuf, String> {
-    let app_data_dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    let app_data_dir = app_data_dir(app)?;
 
     std::fs::create_dir_all(&app_data_dir)
         .map_err(|e| format!("Failed to create app data directory: {}", e))?;
@@ -1117,27 +2089,45 @@ fn get_settings_path(app: &AppHandle) -> Result<PathBuf, String> {
     Ok(app_data_dir.join("settings.json"))
 }
 
+/// Current settings, from the shared cache when possible. Only the first
+/// call (and the first after an external edit) touches the disk.
 fn load_app_settings(app: &AppHandle) -> Result<AppSettings, String> {
+    let cache = app.state::<SettingsCache>();
+    if let Some(settings) = cache.read().as_ref() {
+        return Ok(settings.clone());
+    }
+
+    let mut slot = cache.write();
+    // Another command may have filled it while we waited for the lock
+    if let Some(settings) = slot.as_ref() {
+        return Ok(settings.clone());
+    }
+    let settings = read_settings_file(app)?;
+    *slot = Some(settings.clone());
+    Ok(settings)
+}
+
+fn read_settings_file(app: &AppHandle) -> Result<AppSettings, String> {
     let settings_path = get_settings_path(app)?;
 
     if !settings_path.exists() {
         // Auto-detect game directory on first run
         let mut settings = AppSettings::default();
-        settings.game_directory = detect_game_directory();
+        let found = game_detection::find_game_directories(&settings.game_folder_names);
+        if let Some(found) = found.into_iter().next() {
+            settings.game_directory = Some(found.path);
+            settings.game_directory_source = Some(found.source);
+        }
 
         // Save the auto-detected settings
         if settings.game_directory.is_some() {
-            let _ = save_app_settings_internal(app, &settings);
+            let _ = write_settings_file(app, &settings);
         }
 
         return Ok(settings);
     }
 
-    let content = std::fs::read_to_string(&settings_path)
-        .map_err(|e| format!("Failed to read settings: {}", e))?;
-
-    let settings: AppSettings =
-        serde_json::from_str(&content).map_err(|e| format!("Failed to parse settings: {}", e))?;
+    let settings = parse_settings_file(&settings_path)?;
 
     // Refresh the uninstaller's mods-path file (existing installs won't have it
     // until settings are next saved otherwise)
@@ -1146,26 +2136,39 @@ fn load_app_settings(app: &AppHandle) -> Result<AppSettings, String> {
     Ok(settings)
 }
 
-fn detect_game_directory() -> Option<PathBuf> {
-    // Default Steam installation path
-    let default_path = PathBuf::from(r"C:\Program Files (x86)\Steam\steamapps\common\MarvelRivals");
+fn parse_settings_file(settings_path: &std::path::Path) -> Result<AppSettings, String> {
+    let content = std::fs::read_to_string(settings_path)
+        .map_err(|e| format!("Failed to read settings: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse settings: {}", e))
+}
 
-    if default_path.exists() {
-        log::info!("Auto-detected Marvel Rivals at: {:?}", default_path);
-        return Some(default_path);
+/// Save settings and update the cache under one write lock, so concurrent
+/// saves from two windows are applied one after the other
+fn save_app_settings_internal(app: &AppHandle, settings: &AppSettings) -> Result<(), String> {
+    let cache = app.state::<SettingsCache>();
+    {
+        let mut slot = cache.write();
+        write_settings_file(app, settings)?;
+        *slot = Some(settings.clone());
     }
-
-    log::warn!("Could not auto-detect Marvel Rivals installation");
-    None
+    // The game folder, ignored paths or scan depth may have changed
+    mod_cache::invalidate();
+    fs_ops::set_feed_enabled(settings.file_activity_events);
+    settings_cache::emit_settings_changed(app, settings);
+    Ok(())
 }
 
-fn save_app_settings_internal(app: &AppHandle, settings: &AppSettings) -> Result<(), String> {
+fn write_settings_file(app: &AppHandle, settings: &AppSettings) -> Result<(), String> {
     let settings_path = get_settings_path(app)?;
 
     let json = serde_json::to_string_pretty(&settings)
         .map_err(|e| format!("Failed to serialize settings: {}", e))?;
 
-    std::fs::write(&settings_path, json).map_err(|e| format!("Failed to write settings: {}", e))?;
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = settings_path.with_extension("tmp");
+    std::fs::write(&temp_path, json).map_err(|e| format!("Failed to write settings: {}", e))?;
+    std::fs::rename(&temp_path, &settings_path)
+        .map_err(|e| format!("Failed to finalize settings: {}", e))?;
 
     write_uninstall_info(app, settings);
 
@@ -1181,14 +2184,8 @@ fn write_uninstall_info(app: &AppHandle, settings: &AppSettings) {
     };
     let info_path = settings_path.with_file_name("game-mods-dir.txt");
 
-    match &settings.game_directory {
-        Some(game_dir) => {
-            let mods_dir = game_dir
-                .join("MarvelGame")
-                .join("Marvel")
-                .join("Content")
-                .join("Paks")
-                .join("~mods");
+    match effective_mods_directory(settings) {
+        Some(mods_dir) => {
             if let Err(e) = std::fs::write(&info_path, mods_dir.to_string_lossy().as_bytes()) {
                 log::warn!("Failed to write uninstall info: {e}");
             }
@@ -1199,59 +2196,51 @@ fn write_uninstall_info(app: &AppHandle, settings: &AppSettings) {
     }
 }
 
-#[tauri::command]
-async fn get_app_settings(app: AppHandle) -> Result<AppSettings, String> {
-    load_app_settings(&app)
+/// The folder mods are installed into: the `mod_directory` override when
+/// set, otherwise the game's ~mods folder
+fn effective_mods_directory(settings: &AppSettings) -> Option<PathBuf> {
+    settings.mod_directory.clone().or_else(|| {
+        settings
+            .game_directory
+            .as_deref()
+            .map(ModService::default_mods_directory)
+    })
 }
 
 #[tauri::command]
-async fn save_app_settings(app: AppHandle, settings: AppSettings) -> Result<(), String> {
-    log::info!("Saving app settings");
-    save_app_settings_internal(&app, &settings)
+async fn get_effective_mods_directory(app: AppHandle) -> Result<Option<PathBuf>, String> {
+    Ok(effective_mods_directory(&load_app_settings(&app)?))
 }
 
 #[tauri::command]
-async fn show_in_folder(file_path: String) -> Result<(), String> {
-    use std::process::Command;
-
-    log::info!("Opening folder for file: {}", file_path);
-
-    #[cfg(target_os = "windows")]
-    {
-        Command::new("explorer")
-            .args(["/select,", &file_path])
-            .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
-    }
+async fn get_app_settings(app: AppHandle) -> Result<AppSettings, String> {
+    load_app_settings(&app)
+}
 
-    #[cfg(target_os = "macos")]
-    {
-        Command::new("open")
-            .args(["-R", &file_path])
-            .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
+#[tauri::command]
+async fn save_app_settings(app: AppHandle, mut settings: AppSettings) -> Result<(), String> {
+    log::info!("Saving app settings");
+    // Backend-owned; a frontend copy from before the last check mustn't
+    // reset it, and read-only mode only changes through its own commands
+    if let Ok(current) = load_app_settings(&app) {
+        settings.last_mod_update_check = current.last_mod_update_check;
+        settings.read_only_mode = current.read_only_mode;
     }
-
-    #[cfg(target_os = "linux")]
-    {
-        // Use xdg-open to open the parent directory
-        use std::path::Path;
-        let path = Path::new(&file_path);
-        if let Some(parent) = path.parent() {
-            Command::new("xdg-open")
-                .arg(parent)
-                .spawn()
-                .map_err(|e| format!("Failed to open folder: {}", e))?;
-        }
+    if let Some(dir) = &settings.mod_directory {
+        ModService::check_mods_directory(dir)?;
     }
-
+    save_app_settings_internal(&app, &settings)?;
+    hotkeys::apply(&app, &settings.global_hotkeys);
     Ok(())
 }
 
 #[tauri::command]
 async fn is_game_running() -> Result<bool, String> {
     log::info!("Checking if Marvel Rivals is running");
+    game_process_running()
+}
 
+fn game_process_running() -> Result<bool, String> {
     #[cfg(target_os = "windows")]
     {
         use std::os::windows::process::CommandExt;
@@ -1282,6 +2271,102 @@ async fn is_game_running() -> Result<bool, String> {
     }
 }
 
+/// Fingerprint of the game's own paks (name, size, mtime). A change between
+/// launches means the game was patched, which commonly breaks mods.
+fn game_build_fingerprint(game_dir: &std::path::Path) -> Option<String> {
+    use sha2::{Digest, Sha256};
+
+    let paks_dir = game_dir
+        .join("MarvelGame")
+        .join("Marvel")
+        .join("Content")
+        .join("Paks");
+    let mut entries: Vec<String> = std::fs::read_dir(&paks_dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            let modified = meta
+                .modified()
+                .ok()?
+                .duration_since(UNIX_EPOCH)
+                .ok()?
+                .as_secs();
+            Some(format!(
+                "{}:{}:{}",
+                e.file_name().to_string_lossy(),
+                meta.len(),
+                modified
+            ))
+        })
+        .collect();
+
+    if entries.is_empty() {
+        return None;
+    }
+    entries.sort();
+
+    let mut hasher = Sha256::new();
+    hasher.update(entries.join("\n").as_bytes());
+    Some(format!("{:x}", hasher.finalize()))
+}
+
+/// Compare the game's pak fingerprint against the one stored last launch and
+/// notify if it changed. The first run only records the fingerprint.
+fn check_for_game_update(app: &AppHandle) {
+    let Ok(settings) = load_app_settings(app) else {
+        return;
+    };
+    let Some(game_dir) = settings.game_directory else {
+        return;
+    };
+    let Some(current) = game_build_fingerprint(&game_dir) else {
+        return;
+    };
+    let Ok(settings_path) = get_settings_path(app) else {
+        return;
+    };
+    let fingerprint_path = settings_path.with_file_name("game-build-fingerprint.txt");
+
+    let previous = std::fs::read_to_string(&fingerprint_path).ok();
+    if previous.as_deref().map(str::trim) == Some(current.as_str()) {
+        return;
+    }
+
+    if let Err(e) = std::fs::write(&fingerprint_path, &current) {
+        log::warn!("Failed to store game build fingerprint: {e}");
+    }
+
+    if previous.is_some() {
+        log::info!("🎮 Game files changed since last launch - game was likely updated");
+        // Read-only mode leaves mods alone; the plain notice goes out instead
+        if settings.quarantine_on_game_update && !read_only::is_enabled() {
+            match quarantine::enter(app, &current) {
+                Ok(state) => notifications::notify(
+                    app,
+                    NotificationKind::GameUpdateDetected,
+                    &t("notify.gameUpdated.title", &[]),
+                    &i18n::tn("notify.gameUpdated.quarantined", state.mod_ids.len(), &[]),
+                ),
+                Err(e) => log::warn!("[quarantine] Failed to quarantine mods: {e}"),
+            }
+            return;
+        }
+        let enabled = get_mod_service(app)
+            .map_err(String::from)
+            .and_then(|service| service.get_all_mods())
+            .map(|mods| mods.iter().filter(|m| m.enabled).count())
+            .unwrap_or(0);
+        notifications::notify(
+            app,
+            NotificationKind::GameUpdateDetected,
+            &t("notify.gameUpdated.title", &[]),
+            &i18n::tn("notify.gameUpdated.check", enabled, &[]),
+        );
+    }
+}
+
 // ===== SKIP INTROS MOD COMMANDS =====
 
 fn get_movies_logo_path(app: &AppHandle) -> Result<PathBuf, String> {
@@ -1523,55 +2608,327 @@ async fn uninstall_skip_intros(app: AppHandle) -> Result<(), String> {
 fn create_app_menu(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     log::info!("📋 Setting up native menu system");
 
+    // Set the menu for the app
+    app.set_menu(build_app_menu(app.handle())?)?;
+
+    log::info!("   ✅ Menu system ready");
+    Ok(())
+}
+
+/// The app menu with labels in the current locale. Rebuilt by `set_locale`.
+fn build_app_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let name = "Tauri Template";
+
     // Build the main application submenu
-    let app_submenu = SubmenuBuilder::new(app, "Tauri Template")
-        .item(&MenuItemBuilder::with_id("about", "About Tauri Template").build(app)?)
+    let app_submenu = SubmenuBuilder::new(app, name)
+        .item(&MenuItemBuilder::with_id("about", t("menu.about", &[("app", &name)])).build(app)?)
         .separator()
-        .item(&MenuItemBuilder::with_id("check-updates", "Check for Updates...").build(app)?)
+        .item(&MenuItemBuilder::with_id("check-updates", t("menu.checkUpdates", &[])).build(app)?)
         .separator()
         .item(
-            &MenuItemBuilder::with_id("preferences", "Preferences...")
+            &MenuItemBuilder::with_id("preferences", t("menu.preferences", &[]))
                 .accelerator("CmdOrCtrl+,")
                 .build(app)?,
         )
         .separator()
-        .item(&PredefinedMenuItem::hide(app, Some("Hide Tauri Template"))?)
-        .item(&PredefinedMenuItem::hide_others(app, None)?)
-        .item(&PredefinedMenuItem::show_all(app, None)?)
-        .separator()
-        .item(&PredefinedMenuItem::quit(app, Some("Quit Tauri Template"))?)
-        .build()?;
-
-    // Build the View submenu
-    let view_submenu = SubmenuBuilder::new(app, "View")
         .item(
-            &MenuItemBuilder::with_id("toggle-left-sidebar", "Toggle Left Sidebar")
-                .accelerator("CmdOrCtrl+1")
+            &MenuItemBuilder::with_id("open-mods-folder", t("menu.openModsFolder", &[]))
                 .build(app)?,
         )
         .item(
-            &MenuItemBuilder::with_id("toggle-right-sidebar", "Toggle Right Sidebar")
-                .accelerator("CmdOrCtrl+2")
+            &MenuItemBuilder::with_id("open-metadata-folder", t("menu.openMetadataFolder", &[]))
                 .build(app)?,
         )
+        .separator()
+        .item(&PredefinedMenuItem::hide(
+            app,
+            Some(t("menu.hide", &[("app", &name)]).as_str()),
+        )?)
+        .item(&PredefinedMenuItem::hide_others(app, None)?)
+        .item(&PredefinedMenuItem::show_all(app, None)?)
+        .separator()
+        .item(&PredefinedMenuItem::quit(
+            app,
+            Some(t("menu.quit", &[("app", &name)]).as_str()),
+        )?)
+        .build()?;
+
+    // Build the View submenu. The sidebar items are checked to match the
+    // main window and kept so they can follow it.
+    let state = app.state::<MenuState>();
+    let sidebars = state.sidebars();
+    let left_sidebar = CheckMenuItemBuilder::with_id(
+        menu_state::TOGGLE_LEFT_SIDEBAR,
+        t("menu.toggleLeftSidebar", &[]),
+    )
+    .accelerator("CmdOrCtrl+1")
+    .checked(sidebars.left)
+    .build(app)?;
+    let right_sidebar = CheckMenuItemBuilder::with_id(
+        menu_state::TOGGLE_RIGHT_SIDEBAR,
+        t("menu.toggleRightSidebar", &[]),
+    )
+    .accelerator("CmdOrCtrl+2")
+    .checked(sidebars.right)
+    .build(app)?;
+    let view_submenu = SubmenuBuilder::new(app, t("menu.view", &[]))
+        .item(&left_sidebar)
+        .item(&right_sidebar)
         .build()?;
+    state.set_sidebar_items(left_sidebar, right_sidebar);
+    if read_only::is_enabled() {
+        view_submenu.append(&PredefinedMenuItem::separator(app)?)?;
+        view_submenu.append(
+            &MenuItemBuilder::with_id("read-only-mode", t("menu.readOnlyMode", &[]))
+                .enabled(false)
+                .build(app)?,
+        )?;
+    }
 
     // Build the main menu with submenus
-    let menu = MenuBuilder::new(app)
+    MenuBuilder::new(app)
         .item(&app_submenu)
         .item(&view_submenu)
-        .build()?;
+        .build()
+}
 
-    // Set the menu for the app
-    app.set_menu(menu)?;
+/// Register commands with the invoke handler and record their names in
+/// [`COMMANDS`], so the list `get_backend_info` reports can't drift from what
+/// is actually registered
+macro_rules! commands {
+    ($($command:ident),* $(,)?) => {
+        /// Every command registered with the invoke handler
+        pub const COMMANDS: &[&str] = &[$(stringify!($command)),*];
 
-    log::info!("   ✅ Menu system ready");
-    Ok(())
+        fn invoke_handler() -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static {
+            tauri::generate_handler![$($command),*]
+        }
+    };
 }
 
+commands![
+    greet,
+    load_preferences,
+    save_preferences,
+    set_locale,
+    set_sidebar_menu_state,
+    send_native_notification,
+    test_notification,
+    notify_install_complete,
+    get_pending_mod_updates,
+    dismiss_mod_update,
+    save_emergency_data,
+    load_emergency_data,
+    cleanup_old_recovery_files,
+    // Migration
+    migrate_electron_data,
+    // Mod management
+    get_all_mods,
+    get_mod_cards,
+    get_mod,
+    get_scan_warnings,
+    detect_mod_conflicts,
+    get_pak_technical_info,
+    verify_mods,
+    verify_mods_integrity,
+    restore_mods_directory,
+    validate_mod,
+    validate_mods,
+    get_storage_breakdown,
+    get_scan_profile,
+    get_performance_metrics,
+    clear_hash_cache,
+    get_disabled_mods_overview,
+    purge_disabled_mods,
+    analyze_library_cleanup,
+    apply_cleanup_suggestions,
+    get_library_summary,
+    get_app_stats,
+    get_category_stats,
+    get_character_stats,
+    install_mod,
+    cancel_mod_install,
+    install_mod_to_folder,
+    install_mod_to_folder_with_metadata,
+    enable_mod,
+    set_mods_enabled,
+    enable_mods_bulk,
+    detect_variant_groups,
+    group_mods_as_variants,
+    export_mod_list,
+    import_mod_list,
+    search_mods,
+    query_mods,
+    preview_set_mods_enabled,
+    delete_mod,
+    list_trashed_mods,
+    restore_mod,
+    empty_trash,
+    get_mod_references,
+    preview_mod_metadata,
+    find_low_confidence_assignments,
+    update_mod_metadata,
+    patch_mod_metadata,
+    get_mod_metadata_raw,
+    set_mod_metadata_raw,
+    toggle_favorite,
+    get_favorites,
+    remove_profile_from_all_mods,
+    reassign_category,
+    find_replace_metadata,
+    assign_character,
+    get_layout_drift,
+    apply_layout,
+    accept_layout,
+    // Profiles
+    get_active_profile,
+    preview_profile_apply,
+    apply_profile,
+    create_profile,
+    create_profile_from_favorites,
+    list_profiles,
+    rename_profile,
+    delete_profile,
+    show_in_folder,
+    open_mod_folder,
+    open_mods_root,
+    open_metadata_folder,
+    is_game_running,
+    analyze_game_logs,
+    // Safe mode
+    get_safe_mode_status,
+    enter_safe_mode,
+    exit_safe_mode,
+    // Read-only mode
+    get_read_only_mode,
+    enter_read_only_mode,
+    exit_read_only_mode,
+    // Demo mode
+    demo_nexus_request,
+    // Game update quarantine
+    get_quarantine_status,
+    release_quarantine,
+    // Costume service
+    get_costumes_for_character,
+    get_all_costumes,
+    get_costume,
+    sync_costumes,
+    export_costume_overrides,
+    validate_costume_override_file,
+    get_costume_service_status,
+    retry_costume_initialization,
+    // Thumbnails
+    download_and_save_thumbnail,
+    save_thumbnail_from_file,
+    save_thumbnail_from_base64,
+    get_thumbnail_path,
+    delete_thumbnail,
+    get_thumbnail_source_path,
+    recrop_thumbnail,
+    clear_thumbnail_sources,
+    import_thumbnails_from_folder,
+    get_temp_file_path,
+    // Files outside app data, read for the frontend
+    read_dropped_file_info,
+    read_image_for_preview,
+    // Files sent from the webview in chunks
+    begin_file_import,
+    append_file_chunk,
+    finish_file_import,
+    cancel_file_import,
+    // Settings
+    get_app_settings,
+    get_effective_mods_directory,
+    get_disk_usage,
+    save_app_settings,
+    detect_game_directories,
+    // File watching
+    start_file_watcher,
+    stop_file_watcher,
+    force_rescan,
+    refresh_mods,
+    get_game_directory_status,
+    // Game file integrity
+    verify_base_game_untouched,
+    rebaseline_game_files,
+    // Archive extraction
+    extract_archive,
+    detect_mods_in_archive,
+    extract_and_detect_mods,
+    preview_archive,
+    // Folder organization
+    organize_mods,
+    preview_organize_mods,
+    merge_duplicate_folders,
+    migrate_metadata_to_path_ids,
+    migrate_to_costume_folders,
+    recover_orphaned_metadata,
+    detect_split_mods,
+    repair_split_mod,
+    repair_split_mods,
+    resolve_duplicate,
+    find_duplicate_mods,
+    resolve_duplicate_mods,
+    reconcile_external_changes,
+    detect_legacy_disabled_files,
+    convert_legacy_disabled,
+    consolidate_thumbnail_storage,
+    convert_disable_strategy,
+    add_ignored_path,
+    remove_ignored_path,
+    run_library_repair,
+    get_library_repair_checkpoint,
+    relocate_misplaced_mods,
+    enforce_addon_load_order,
+    set_mod_priority,
+    reorder_mods,
+    log_total_mods_found,
+    get_metadata_directory,
+    copy_metadata_from_old_id,
+    // Skip Intros mod
+    get_skip_intros_status,
+    install_skip_intros,
+    uninstall_skip_intros,
+    // Bulk operations
+    preview_delete,
+    delete_mods,
+    install_mod_group,
+    // Uninstaller behavior
+    get_uninstall_cleanup,
+    set_uninstall_cleanup,
+    // Collections
+    list_collections,
+    create_collection,
+    delete_collection,
+    add_to_collection,
+    remove_from_collection,
+    reorder_collection,
+    set_collection_enabled,
+    export_collection,
+    // Nexus Mods
+    download_nexus_mod,
+    install_mod_from_path,
+    // Windows
+    open_mod_detail_window,
+    list_downloads,
+    pause_download,
+    resume_download,
+    cancel_download,
+    // Handshake
+    get_backend_info,
+];
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // `--cli <command>` runs headless and exits before any window exists
+    if let Some(code) = cli::run_from_args(std::env::args()) {
+        std::process::exit(code);
+    }
+
     tauri::Builder::default()
+        .manage(SettingsCache::default())
+        .manage(ModCache::default())
+        .manage(MenuState::default())
         .plugin(tauri_plugin_single_instance::init(|app, args, _cwd| {
             // When a second instance tries to open (e.g. from NXM link),
             // forward the args to the existing instance
@@ -1621,10 +2978,10 @@ pub fn run() {
         )
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_persisted_scope::init())
-        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(hotkeys::plugin())
         .setup(|app| {
             log::info!("");
             log::info!("==========================================================");
@@ -1632,77 +2989,146 @@ pub fn run() {
             log::info!("==========================================================");
             log::info!("");
 
-            // Set up native menu system
+            // Switch to the demo library before anything reads app data
+            demo::init(app.handle(), std::env::args());
+
+            // Set up native menu system, in the saved locale, showing
+            // read-only mode if it's on
+            i18n::init(app.handle());
+            read_only::init(app.handle(), std::env::args());
             if let Err(e) = create_app_menu(app) {
                 log::error!("Failed to create app menu: {e}");
                 return Err(e);
             }
 
-            // Initialize costume service
-            log::info!("");
-            if let Err(e) = initialize_costume_service(app.handle()) {
-                log::error!("Failed to initialize costume service: {e}");
-                // Don't fail app startup if costume data fails to load
-                // The app can still function without costume data
+            // Apply metadata changes a previous run queued but never wrote
+            match app_data_dir(app.handle()) {
+                Ok(data_dir) => match write_behind::replay_journal(&data_dir.join("metadata")) {
+                    Ok(0) => {}
+                    Ok(count) => {
+                        log::info!("[write-behind] Replayed {count} pending metadata write(s)")
+                    }
+                    Err(e) => log::warn!("[write-behind] Failed to replay metadata journal: {e}"),
+                },
+                Err(e) => log::warn!("[write-behind] {e}"),
+            }
+
+            // Notice mods gone since last session, before a scan forgets them
+            mods_integrity::check_on_startup(app.handle());
+
+            // Initialize costume service. A failure is logged and kept for
+            // get_costume_service_status; the app still works without
+            // costume data, and retry_costume_initialization can reload it.
+            let _ = initialize_costume_service(app.handle());
+
+            // Drop cached settings when settings.json is edited outside the app
+            match get_settings_path(app.handle()) {
+                Ok(path) => {
+                    if let Err(e) = settings_cache::watch_settings_file(app.handle(), path) {
+                        log::warn!("[settings] {e}");
+                    }
+                }
+                Err(e) => log::warn!("[settings] {e}"),
+            }
+
+            // Register the safe mode global hotkey from saved settings, and
+            // turn on the file activity feed if the user opted in
+            match load_app_settings(app.handle()) {
+                Ok(settings) => {
+                    hotkeys::apply(app.handle(), &settings.global_hotkeys);
+                    fs_ops::set_feed_enabled(settings.file_activity_events);
+                }
+                Err(e) => log::warn!("Failed to load settings for global hotkeys: {e}"),
             }
+            fs_ops::start_feed(app.handle().clone());
+
+            windows::restore_main_window(app.handle());
+            read_only::show_initial_state(app.handle());
+
+            // Fallback polling for mod folder changes the watcher misses
+            rescan::start_background_rescan(app.handle().clone());
+
+            // Notice the game's drive being unplugged or coming back
+            game_directory::start_monitor(app.handle().clone());
+
+            // Drop chunked imports the webview stopped sending
+            file_import::start_sweeper();
+
+            // Downloads that were running when the app last closed
+            download_service::resume_interrupted(app.handle());
+
+            // Daily Nexus check for newer versions of installed mods
+            mod_updates::start_scheduler(app.handle().clone());
+
+            // Record which mods load cleanly each time the game closes
+            quarantine::start_session_monitor(app.handle().clone());
+
+            // Game patch detection hits the disk, keep it off the startup path
+            let update_check_handle = app.handle().clone();
+            std::thread::spawn(move || check_for_game_update(&update_check_handle));
+
+            // Deleted mods past the trash's retention period; read-only mode
+            // keeps them until it's turned off
+            let trash_handle = app.handle().clone();
+            std::thread::spawn(move || {
+                if read_only::check("trash purge").is_err() {
+                    return;
+                }
+                match get_mod_service(&trash_handle)
+                    .map_err(String::from)
+                    .and_then(|s| s.purge_expired_trash())
+                {
+                    Ok(purged) => log::debug!("Purged {} expired trashed mod(s)", purged),
+                    Err(e) => log::warn!("Failed to purge the trash: {}", e),
+                }
+            });
 
             log::info!("");
             log::info!("✅ Application initialized successfully");
             log::info!("==========================================================");
             log::info!("");
 
-            // Set up menu event handlers
+            // Menu events go to the main window; the details window has no
+            // sidebars or dialogs of its own
             app.on_menu_event(move |app, event| {
                 log::debug!("Menu event received: {:?}", event.id());
 
                 match event.id().as_ref() {
                     "about" => {
                         log::info!("About menu item clicked");
-                        // Emit event to React for handling
-                        match app.emit("menu-about", ()) {
-                            Ok(_) => log::debug!("Successfully emitted menu-about event"),
-                            Err(e) => log::error!("Failed to emit menu-about event: {e}"),
-                        }
+                        windows::emit_to_main(app, "menu-about");
                     }
                     "check-updates" => {
                         log::info!("Check for Updates menu item clicked");
-                        // Emit event to React for handling
-                        match app.emit("menu-check-updates", ()) {
-                            Ok(_) => log::debug!("Successfully emitted menu-check-updates event"),
-                            Err(e) => log::error!("Failed to emit menu-check-updates event: {e}"),
-                        }
+                        windows::emit_to_main(app, "menu-check-updates");
                     }
                     "preferences" => {
                         log::info!("Preferences menu item clicked");
-                        // Emit event to React for handling
-                        match app.emit("menu-preferences", ()) {
-                            Ok(_) => log::debug!("Successfully emitted menu-preferences event"),
-                            Err(e) => log::error!("Failed to emit menu-preferences event: {e}"),
+                        windows::emit_to_main(app, "menu-preferences");
+                    }
+                    "open-mods-folder" => {
+                        log::info!("Open Mods Folder menu item clicked");
+                        if let Err(e) = folders::open_mods_root_folder(app) {
+                            log::error!("Failed to open mods folder: {e}");
                         }
                     }
-                    "toggle-left-sidebar" => {
-                        log::info!("Toggle Left Sidebar menu item clicked");
-                        // Emit event to React for handling
-                        match app.emit("menu-toggle-left-sidebar", ()) {
-                            Ok(_) => {
-                                log::debug!("Successfully emitted menu-toggle-left-sidebar event")
-                            }
-                            Err(e) => {
-                                log::error!("Failed to emit menu-toggle-left-sidebar event: {e}")
-                            }
+                    "open-metadata-folder" => {
+                        log::info!("Open Metadata Folder menu item clicked");
+                        if let Err(e) = folders::open_metadata_dir(app) {
+                            log::error!("Failed to open metadata folder: {e}");
                         }
                     }
-                    "toggle-right-sidebar" => {
+                    menu_state::TOGGLE_LEFT_SIDEBAR => {
+                        log::info!("Toggle Left Sidebar menu item clicked");
+                        app.state::<MenuState>()
+                            .toggle_sidebar(menu_state::TOGGLE_LEFT_SIDEBAR);
+                        windows::emit_to_main(app, "menu-toggle-left-sidebar");
+                    }
+                    menu_state::TOGGLE_RIGHT_SIDEBAR => {
                         log::info!("Toggle Right Sidebar menu item clicked");
-                        // Emit event to React for handling
-                        match app.emit("menu-toggle-right-sidebar", ()) {
-                            Ok(_) => {
-                                log::debug!("Successfully emitted menu-toggle-right-sidebar event")
-                            }
-                            Err(e) => {
-                                log::error!("Failed to emit menu-toggle-right-sidebar event: {e}")
-                            }
-                        }
+                        app.state::<MenuState>()
+                            .toggle_sidebar(menu_state::TOGGLE_RIGHT_SIDEBAR);
+                        windows::emit_to_main(app, "menu-toggle-right-sidebar");
                     }
                     _ => {
                         log::debug!("Unhandled menu event: {:?}", event.id());
@@ -1712,76 +3138,14 @@ pub fn run() {
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            greet,
-            load_preferences,
-            save_preferences,
-            send_native_notification,
-            save_emergency_data,
-            load_emergency_data,
-            cleanup_old_recovery_files,
-            // Migration
-            migrate_electron_data,
-            // Mod management
-            get_all_mods,
-            detect_mod_conflicts,
-            install_mod,
-            install_mod_to_folder,
-            install_mod_to_folder_with_metadata,
-            enable_mod,
-            set_mods_enabled,
-            delete_mod,
-            update_mod_metadata,
-            remove_profile_from_all_mods,
-            show_in_folder,
-            is_game_running,
-            // Costume service
-            get_costumes_for_character,
-            get_all_costumes,
-            get_costume,
-            sync_costumes,
-            // Thumbnails
-            download_and_save_thumbnail,
-            save_thumbnail_from_file,
-            save_thumbnail_from_base64,
-            get_thumbnail_path,
-            delete_thumbnail,
-            get_temp_file_path,
-            // Settings
-            get_app_settings,
-            save_app_settings,
-            // File watching
-            start_file_watcher,
-            stop_file_watcher,
-            // Archive extraction
-            extract_archive,
-            detect_mods_in_archive,
-            extract_and_detect_mods,
-            // Folder organization
-            organize_mods,
-            merge_duplicate_folders,
-            migrate_metadata_to_path_ids,
-            migrate_to_costume_folders,
-            recover_orphaned_metadata,
-            relocate_misplaced_mods,
-            enforce_addon_load_order,
-            log_total_mods_found,
-            get_metadata_directory,
-            copy_metadata_from_old_id,
-            // Skip Intros mod
-            get_skip_intros_status,
-            install_skip_intros,
-            uninstall_skip_intros,
-            // Bulk operations
-            delete_mods,
-            install_mod_group,
-            // Uninstaller behavior
-            get_uninstall_cleanup,
-            set_uninstall_cleanup,
-            // Nexus Mods
-            download_nexus_mod,
-            install_mod_from_path
-        ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .invoke_handler(read_only::guard(invoke_handler()))
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                if let Err(e) = write_behind::flush_all() {
+                    log::error!("[write-behind] Failed to flush metadata on exit: {e}");
+                }
+            }
+        });
 }
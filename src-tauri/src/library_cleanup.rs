@@ -0,0 +1,465 @@
+//! Suggestions for shrinking the library: the same mod installed twice,
+//! older Nexus files of a mod whose newer file is also installed, mods
+//! disabled for a long time, and mods for characters the user has hidden.
+//! Analysis only reports; nothing is removed until
+//! `apply_cleanup_suggestions` is called with the suggestions to act on,
+//! which goes through the normal bulk delete.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+
+use crate::mod_service::ModService;
+use crate::types::{Character, DeleteModError, ModInfo};
+
+/// Days a mod has to stay disabled before it's suggested for deletion, when
+/// the caller doesn't say
+pub const DEFAULT_DISABLED_DAYS: u32 = 90;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CleanupKind {
+    /// Mods whose files are byte for byte the same
+    ExactDuplicate,
+    /// Older Nexus files of a mod with a newer file installed
+    Superseded,
+    LongDisabled,
+    /// Mods for a character in `hidden_characters`
+    HiddenCharacter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CleanupAction {
+    /// Delete `mod_ids`
+    Delete,
+    /// Delete `mod_ids`, the older ones, and keep `keep`
+    KeepNewest,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupSuggestion {
+    /// Derived from what the suggestion removes, so it stops matching once
+    /// the library changes under it
+    pub id: String,
+    pub kind: CleanupKind,
+    pub action: CleanupAction,
+    /// Mods the action deletes
+    pub mod_ids: Vec<String>,
+    /// Mods kept in their place, for duplicates and superseded files
+    pub keep: Vec<String>,
+    pub reason: String,
+    /// Disk space deleting `mod_ids` frees
+    pub bytes_saved: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupAnalysis {
+    pub suggestions: Vec<CleanupSuggestion>,
+    /// Space freed by applying every suggestion; a mod in several counts once
+    pub bytes_saved: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupSummary {
+    pub applied: usize,
+    /// Requested suggestions that no longer match the library
+    pub stale: Vec<String>,
+    pub deleted: usize,
+    pub bytes_freed: u64,
+}
+
+fn suggestion(
+    kind: CleanupKind,
+    action: CleanupAction,
+    mod_ids: Vec<String>,
+    keep: Vec<String>,
+    reason: String,
+    sizes: &HashMap<&str, u64>,
+) -> CleanupSuggestion {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{:?}", kind));
+    for id in mod_ids.iter().chain(&keep) {
+        hasher.update([0]);
+        hasher.update(id);
+    }
+    CleanupSuggestion {
+        id: format!("{:x}", hasher.finalize())[..16].to_string(),
+        kind,
+        action,
+        bytes_saved: mod_ids.iter().filter_map(|id| sizes.get(id.as_str())).sum(),
+        mod_ids,
+        keep,
+        reason,
+    }
+}
+
+/// Mods installed more than once. The enabled copy is kept, or else the
+/// most recently installed one.
+fn exact_duplicates(
+    service: &ModService,
+    mods: &[ModInfo],
+    sizes: &HashMap<&str, u64>,
+) -> Vec<CleanupSuggestion> {
+    // Only mods sharing a size can match, so the rest are never hashed
+    let mut by_size: BTreeMap<u64, Vec<&ModInfo>> = BTreeMap::new();
+    for mod_info in mods.iter().filter(|m| m.metadata.parent_mod_id.is_none()) {
+        by_size
+            .entry(mod_info.file_size)
+            .or_default()
+            .push(mod_info);
+    }
+    let mut by_hash: BTreeMap<String, Vec<&ModInfo>> = BTreeMap::new();
+    for same_size in by_size.into_values().filter(|group| group.len() > 1) {
+        for mod_info in same_size {
+            if let Ok(hash) = service.files_content_hash(mod_info) {
+                by_hash.entry(hash).or_default().push(mod_info);
+            }
+        }
+    }
+
+    by_hash
+        .into_values()
+        .filter(|copies| copies.len() > 1)
+        .map(|mut copies| {
+            copies.sort_by_key(|m| std::cmp::Reverse((m.enabled, m.install_date)));
+            let keep = copies.remove(0);
+            suggestion(
+                CleanupKind::ExactDuplicate,
+                CleanupAction::Delete,
+                copies.iter().map(|m| m.id.clone()).collect(),
+                vec![keep.id.clone()],
+                format!(
+                    "\"{}\" is installed {} times with identical files",
+                    keep.metadata.title,
+                    copies.len() + 1
+                ),
+                sizes,
+            )
+        })
+        .collect()
+}
+
+/// Older files of a Nexus mod with a newer file also installed. Nexus file
+/// IDs only go up, so the highest is the newest.
+fn superseded(mods: &[ModInfo], sizes: &HashMap<&str, u64>) -> Vec<CleanupSuggestion> {
+    let mut by_nexus_mod: BTreeMap<i32, Vec<(&ModInfo, i32)>> = BTreeMap::new();
+    for mod_info in mods {
+        if let (Some(nexus_mod_id), Some(file_id)) = (
+            mod_info.metadata.nexus_mod_id,
+            mod_info.metadata.nexus_file_id,
+        ) {
+            by_nexus_mod
+                .entry(nexus_mod_id)
+                .or_default()
+                .push((mod_info, file_id));
+        }
+    }
+
+    by_nexus_mod
+        .into_values()
+        .filter_map(|files| {
+            let newest = files.iter().map(|(_, file_id)| *file_id).max()?;
+            let (keep, older): (Vec<_>, Vec<_>) = files
+                .into_iter()
+                .partition(|(_, file_id)| *file_id == newest);
+            if older.is_empty() {
+                return None;
+            }
+            Some(suggestion(
+                CleanupKind::Superseded,
+                CleanupAction::KeepNewest,
+                older.iter().map(|(m, _)| m.id.clone()).collect(),
+                keep.iter().map(|(m, _)| m.id.clone()).collect(),
+                format!(
+                    "{} older file(s) of \"{}\" are installed alongside the newest",
+                    older.len(),
+                    keep[0].0.metadata.title
+                ),
+                sizes,
+            ))
+        })
+        .collect()
+}
+
+/// Suggestions for `mods`, the whole library
+fn analyze(
+    service: &ModService,
+    mods: &[ModInfo],
+    disabled_days: u32,
+    hidden_characters: &[Character],
+    now: DateTime<Utc>,
+) -> CleanupAnalysis {
+    let sizes: HashMap<&str, u64> = mods.iter().map(|m| (m.id.as_str(), m.file_size)).collect();
+
+    let mut suggestions = exact_duplicates(service, mods, &sizes);
+    suggestions.extend(superseded(mods, &sizes));
+
+    // Mods with no known disable date are never suggested, like the purge
+    let long_disabled: Vec<&ModInfo> = mods
+        .iter()
+        .filter(|m| !m.enabled)
+        .filter(|m| {
+            m.metadata
+                .disabled_at
+                .is_some_and(|at| (now - at).num_days() >= i64::from(disabled_days))
+        })
+        .collect();
+    if !long_disabled.is_empty() {
+        suggestions.push(suggestion(
+            CleanupKind::LongDisabled,
+            CleanupAction::Delete,
+            long_disabled.iter().map(|m| m.id.clone()).collect(),
+            Vec::new(),
+            format!(
+                "{} mod(s) disabled for over {} days",
+                long_disabled.len(),
+                disabled_days
+            ),
+            &sizes,
+        ));
+    }
+
+    for character in hidden_characters {
+        let ids: Vec<String> = mods
+            .iter()
+            .filter(|m| m.metadata.character.as_ref() == Some(character))
+            .map(|m| m.id.clone())
+            .collect();
+        if !ids.is_empty() {
+            suggestions.push(suggestion(
+                CleanupKind::HiddenCharacter,
+                CleanupAction::Delete,
+                ids,
+                Vec::new(),
+                format!("Mods for {}, whom you don't play", character),
+                &sizes,
+            ));
+        }
+    }
+
+    let all_removed: BTreeSet<&str> = suggestions
+        .iter()
+        .flat_map(|s| s.mod_ids.iter().map(String::as_str))
+        .collect();
+    let bytes_saved = all_removed.iter().filter_map(|id| sizes.get(id)).sum();
+    log::info!(
+        "[cleanup] {} suggestion(s), {} bytes",
+        suggestions.len(),
+        bytes_saved
+    );
+    CleanupAnalysis {
+        suggestions,
+        bytes_saved,
+    }
+}
+
+/// Act on the suggestions with these IDs, re-analyzing first so only ones
+/// that still match the library run. All their mods go in one bulk delete,
+/// which refuses mods in profiles or collections unless `force` is set.
+fn apply(
+    service: &ModService,
+    suggestion_ids: &[String],
+    disabled_days: u32,
+    hidden_characters: &[Character],
+    force: bool,
+    now: DateTime<Utc>,
+) -> Result<CleanupSummary, DeleteModError> {
+    let mods = service.get_all_mods()?;
+    let analysis = analyze(service, &mods, disabled_days, hidden_characters, now);
+    let chosen: Vec<&CleanupSuggestion> = analysis
+        .suggestions
+        .iter()
+        .filter(|s| suggestion_ids.contains(&s.id))
+        .collect();
+    let stale: Vec<String> = suggestion_ids
+        .iter()
+        .filter(|id| !chosen.iter().any(|s| &&s.id == id))
+        .cloned()
+        .collect();
+
+    // A kept mod is never deleted, even if another suggestion lists it
+    let kept: BTreeSet<&String> = chosen.iter().flat_map(|s| &s.keep).collect();
+    let to_delete: Vec<String> = chosen
+        .iter()
+        .flat_map(|s| &s.mod_ids)
+        .filter(|id| !kept.contains(id))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .cloned()
+        .collect();
+
+    // Cleanup is for freeing space, so its deletes skip the trash
+    let deleted = if to_delete.is_empty() {
+        0
+    } else {
+        service.delete_mods(&to_delete, force, true)?
+    };
+    // Only mods that are really gone count towards the space freed
+    let remaining: BTreeSet<String> = service.get_all_mods()?.into_iter().map(|m| m.id).collect();
+    let summary = CleanupSummary {
+        applied: chosen.len(),
+        stale,
+        deleted,
+        bytes_freed: mods
+            .iter()
+            .filter(|m| to_delete.contains(&m.id) && !remaining.contains(&m.id))
+            .map(|m| m.file_size)
+            .sum(),
+    };
+    log::info!(
+        "[cleanup] Applied {} suggestion(s): deleted {} mod(s), {} bytes",
+        summary.applied,
+        summary.deleted,
+        summary.bytes_freed
+    );
+    Ok(summary)
+}
+
+/// Cleanup suggestions with the space each would free. Nothing is changed.
+/// `disabled_days` defaults to 90.
+#[tauri::command]
+pub async fn analyze_library_cleanup(
+    app: AppHandle,
+    disabled_days: Option<u32>,
+) -> Result<CleanupAnalysis, String> {
+    let service = crate::get_mod_service(&app)?;
+    let settings = crate::load_app_settings(&app)?;
+    Ok(analyze(
+        &service,
+        &service.get_all_mods()?,
+        disabled_days.unwrap_or(DEFAULT_DISABLED_DAYS),
+        &settings.hidden_characters,
+        Utc::now(),
+    ))
+}
+
+/// Delete what the chosen suggestions from `analyze_library_cleanup` list.
+/// Pass the same `disabled_days` the analysis used.
+#[tauri::command]
+pub async fn apply_cleanup_suggestions(
+    app: AppHandle,
+    suggestion_ids: Vec<String>,
+    disabled_days: Option<u32>,
+    force: Option<bool>,
+) -> Result<CleanupSummary, DeleteModError> {
+    let service = crate::get_mod_service(&app)?;
+    let settings = crate::load_app_settings(&app)?;
+    let _pause = crate::file_watcher::pause();
+    apply(
+        &service,
+        &suggestion_ids,
+        disabled_days.unwrap_or(DEFAULT_DISABLED_DAYS),
+        &settings.hidden_characters,
+        force.unwrap_or(false),
+        Utc::now(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::{fixed_now, metadata, Fixture};
+    use crate::types::{ModCategory, ModMetadata};
+
+    #[test]
+    fn test_cleanup_is_suggested_then_applied() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let install = |stem: &str, folder: &str, size: usize, edit: &dyn Fn(&mut ModMetadata)| {
+            let mut md = metadata(folder, ModCategory::Skins);
+            edit(&mut md);
+            service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, size, Some(100)),
+                    &format!("Skins/{folder}"),
+                    md,
+                )
+                .unwrap()
+                .id
+        };
+        let twin_a = install("TwinA_P", "Twin-A", 10, &|_| {});
+        let twin_b = install("TwinB_P", "Twin-B", 10, &|_| {});
+        let old = install("Old_P", "Old", 30, &|md| {
+            md.nexus_mod_id = Some(7);
+            md.nexus_file_id = Some(100);
+        });
+        let new = install("New_P", "New", 40, &|md| {
+            md.nexus_mod_id = Some(7);
+            md.nexus_file_id = Some(200);
+        });
+        let shelved = install("Shelved_P", "Shelved", 50, &|_| {});
+        let shelved = service.enable_mod(&shelved, false).unwrap().id;
+        let hidden = install("Hidden_P", "Hidden", 60, &|md| {
+            md.character = Some(Character::Psylocke);
+        });
+
+        let mods = service.get_all_mods().unwrap();
+        let later = fixed_now() + chrono::Duration::days(100);
+        let analysis = analyze(&service, &mods, 90, &[Character::Psylocke], later);
+        let find = |kind| {
+            analysis
+                .suggestions
+                .iter()
+                .find(|s| s.kind == kind)
+                .unwrap()
+        };
+        let duplicate = find(CleanupKind::ExactDuplicate);
+        let mut twins = [duplicate.mod_ids.clone(), duplicate.keep.clone()].concat();
+        twins.sort();
+        let mut expected = vec![twin_a, twin_b];
+        expected.sort();
+        assert_eq!(twins, expected);
+        let superseded = find(CleanupKind::Superseded);
+        assert_eq!(
+            (&superseded.mod_ids, &superseded.keep),
+            (&vec![old.clone()], &vec![new.clone()])
+        );
+        assert_eq!(superseded.bytes_saved, 30 + 100 + 4);
+        assert_eq!(find(CleanupKind::LongDisabled).mod_ids, [shelved.clone()]);
+        assert_eq!(find(CleanupKind::HiddenCharacter).mod_ids, [hidden.clone()]);
+        // Not yet disabled long enough
+        assert!(analyze(&service, &mods, 90, &[], fixed_now())
+            .suggestions
+            .iter()
+            .all(|s| s.kind != CleanupKind::LongDisabled));
+
+        // Analysis changes nothing
+        assert_eq!(service.get_all_mods().unwrap().len(), 6);
+
+        let summary = apply(
+            &service,
+            &[
+                duplicate.id.clone(),
+                superseded.id.clone(),
+                "gone".to_string(),
+            ],
+            90,
+            &[Character::Psylocke],
+            false,
+            later,
+        )
+        .unwrap();
+        assert_eq!((summary.applied, summary.deleted), (2, 2));
+        assert_eq!(summary.stale, ["gone"]);
+        assert_eq!(
+            summary.bytes_freed,
+            duplicate.bytes_saved + superseded.bytes_saved
+        );
+        let left: Vec<String> = service
+            .get_all_mods()
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert!(!left.contains(&old) && left.contains(&new));
+        assert!(left.contains(&shelved) && left.contains(&hidden));
+        assert_eq!(left.len(), 4);
+    }
+}
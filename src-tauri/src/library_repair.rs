@@ -0,0 +1,404 @@
+//! The "fix my library" wizard: the maintenance passes the app otherwise runs
+//! one at a time at startup, picked by the user and run together in the order
+//! they depend on each other. Finished steps are checkpointed so a run that
+//! fails or is interrupted picks up where it stopped.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+use crate::mod_service::ModService;
+use crate::types::IdMigrationOutcome;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LibraryRepairStep {
+    /// Reunite paks and companions split between ~mods and the disabled folder
+    RepairSplitMods,
+    /// Move metadata from filename-based to path-based IDs
+    MigrateIds,
+    /// Fold the legacy `thumbnails/` folder into per-mod thumbnails
+    ConsolidateThumbnails,
+    /// Merge character folders that differ only in spaces or hyphens
+    MergeDuplicateFolders,
+    /// Give metadata orphaned by old folder renames back to its mod
+    RecoverOrphanedMetadata,
+    /// Remove mod folders left empty
+    CleanEmptyFolders,
+}
+
+impl LibraryRepairStep {
+    /// Every step, in the order they run. Split mods are whole again before
+    /// IDs are worked out from their paths, thumbnails land under the IDs
+    /// mods end up with, and folder moves come last so the passes that
+    /// follow them can tidy up after.
+    pub const ALL: [LibraryRepairStep; 6] = [
+        LibraryRepairStep::RepairSplitMods,
+        LibraryRepairStep::MigrateIds,
+        LibraryRepairStep::ConsolidateThumbnails,
+        LibraryRepairStep::MergeDuplicateFolders,
+        LibraryRepairStep::RecoverOrphanedMetadata,
+        LibraryRepairStep::CleanEmptyFolders,
+    ];
+}
+
+/// A run that hasn't finished yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryRepairCheckpoint {
+    pub steps: Vec<LibraryRepairStep>,
+    pub completed: Vec<LibraryRepairStep>,
+    pub started_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryRepairStepResult {
+    pub step: LibraryRepairStep,
+    /// Things fixed, or in a dry run things that would be
+    pub changes: usize,
+    /// What they are, for the wizard to list
+    pub items: Vec<String>,
+    /// Already done by an earlier run that didn't finish
+    pub resumed: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryRepairReport {
+    pub dry_run: bool,
+    pub steps: Vec<LibraryRepairStepResult>,
+    /// False when a step failed; the steps after it didn't run
+    pub completed: bool,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LibraryRepairProgress {
+    step: LibraryRepairStep,
+    current: usize,
+    total: usize,
+    dry_run: bool,
+}
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = crate::app_data_dir(app)?;
+    Ok(app_data_dir.join("library-repair.json"))
+}
+
+pub fn load_checkpoint(app: &AppHandle) -> Result<Option<LibraryRepairCheckpoint>, String> {
+    let path = state_path(app)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read library repair checkpoint: {e}"))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse library repair checkpoint: {e}"))
+}
+
+fn save_checkpoint(app: &AppHandle, checkpoint: &LibraryRepairCheckpoint) -> Result<(), String> {
+    let path = state_path(app)?;
+    let json = serde_json::to_string_pretty(checkpoint)
+        .map_err(|e| format!("Failed to serialize library repair checkpoint: {e}"))?;
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json)
+        .map_err(|e| format!("Failed to write library repair checkpoint: {e}"))?;
+    std::fs::rename(&temp_path, &path)
+        .map_err(|e| format!("Failed to finalize library repair checkpoint: {e}"))
+}
+
+fn clear_checkpoint(app: &AppHandle) -> Result<(), String> {
+    let path = state_path(app)?;
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .map_err(|e| format!("Failed to remove library repair checkpoint: {e}"))?;
+    }
+    Ok(())
+}
+
+/// The requested steps in run order, each once
+fn ordered_steps(requested: &[LibraryRepairStep]) -> Vec<LibraryRepairStep> {
+    LibraryRepairStep::ALL
+        .into_iter()
+        .filter(|step| requested.contains(step))
+        .collect()
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Run one step, or with `dry_run` only look at what it would do. Returns
+/// the number of changes and a line describing each.
+fn run_step(
+    service: &ModService,
+    step: LibraryRepairStep,
+    dry_run: bool,
+) -> Result<(usize, Vec<String>), String> {
+    match step {
+        LibraryRepairStep::RepairSplitMods => {
+            if dry_run {
+                let split = service.detect_split_mods()?;
+                let changes = split.iter().filter(|m| m.auto_repairable).count();
+                let items = split
+                    .into_iter()
+                    .map(|m| {
+                        if m.auto_repairable {
+                            m.name
+                        } else {
+                            format!("{} (needs to be resolved by hand)", m.name)
+                        }
+                    })
+                    .collect();
+                Ok((changes, items))
+            } else {
+                let report = service.repair_split_mods()?;
+                let items = report
+                    .unresolved
+                    .into_iter()
+                    .map(|m| format!("{} (needs to be resolved by hand)", m.name))
+                    .collect();
+                Ok((report.repaired, items))
+            }
+        }
+        LibraryRepairStep::MigrateIds => {
+            let report = service.migrate_metadata_to_path_ids(None, dry_run)?;
+            let items = report
+                .entries
+                .into_iter()
+                .filter(|entry| entry.outcome == IdMigrationOutcome::Migrated)
+                .map(|entry| entry.name)
+                .collect();
+            Ok((report.migrated_count + report.resumed_count, items))
+        }
+        LibraryRepairStep::ConsolidateThumbnails => {
+            if dry_run {
+                let files = service.legacy_thumbnail_files();
+                Ok((files.len(), files.iter().map(|f| display_name(f)).collect()))
+            } else {
+                let report = service.consolidate_thumbnail_storage()?;
+                let items = report
+                    .unclaimed
+                    .iter()
+                    .map(|f| format!("{} (unclaimed)", display_name(f)))
+                    .collect();
+                Ok((report.migrated, items))
+            }
+        }
+        LibraryRepairStep::MergeDuplicateFolders => {
+            if dry_run {
+                let groups = service.find_duplicate_folders()?;
+                let changes = groups.iter().map(|paths| paths.len() - 1).sum();
+                let items = groups
+                    .iter()
+                    .flat_map(|paths| {
+                        paths[1..].iter().map(|source| {
+                            format!("{} → {}", display_name(source), display_name(&paths[0]))
+                        })
+                    })
+                    .collect();
+                Ok((changes, items))
+            } else {
+                let report = service.merge_duplicate_folders()?;
+                let items = report
+                    .actions
+                    .iter()
+                    .map(|entry| {
+                        format!(
+                            "{} → {}",
+                            entry.source.display(),
+                            entry.destination.display()
+                        )
+                    })
+                    .collect();
+                Ok((report.merged_folders, items))
+            }
+        }
+        LibraryRepairStep::RecoverOrphanedMetadata => {
+            if dry_run {
+                let recoverable = service.find_recoverable_metadata()?;
+                let items = recoverable
+                    .iter()
+                    .map(|(old_id, new_id)| format!("{old_id} → {new_id}"))
+                    .collect();
+                Ok((recoverable.len(), items))
+            } else {
+                Ok((service.recover_orphaned_metadata()?, Vec::new()))
+            }
+        }
+        LibraryRepairStep::CleanEmptyFolders => {
+            if dry_run {
+                let folders = service.find_empty_mod_folders()?;
+                let items = folders.iter().map(|f| f.display().to_string()).collect();
+                Ok((folders.len(), items))
+            } else {
+                Ok((service.cleanup_empty_mod_folders()?, Vec::new()))
+            }
+        }
+    }
+}
+
+/// Run the chosen repair steps in dependency order. A real run records each
+/// finished step, so after a failure the same call skips straight to the
+/// step that failed; the checkpoint is dropped once every step is done.
+/// Progress goes out as `library-repair-progress` events.
+#[tauri::command]
+pub async fn run_library_repair(
+    app: AppHandle,
+    steps: Vec<LibraryRepairStep>,
+    dry_run: Option<bool>,
+) -> Result<LibraryRepairReport, String> {
+    let dry_run = dry_run.unwrap_or(false);
+    let steps = ordered_steps(&steps);
+    let service = crate::get_mod_service(&app)?;
+    let _pause = crate::file_watcher::pause();
+
+    let mut checkpoint = match load_checkpoint(&app)? {
+        Some(checkpoint) if !dry_run => checkpoint,
+        _ => LibraryRepairCheckpoint {
+            steps: Vec::new(),
+            completed: Vec::new(),
+            started_at: Utc::now(),
+        },
+    };
+    checkpoint.steps = steps.clone();
+    log::info!(
+        "[library-repair] Running {:?}{}",
+        steps,
+        if dry_run { " (dry run)" } else { "" }
+    );
+
+    let mut report = LibraryRepairReport {
+        dry_run,
+        steps: Vec::new(),
+        completed: true,
+    };
+    let total = steps.len();
+    for (index, step) in steps.into_iter().enumerate() {
+        if checkpoint.completed.contains(&step) {
+            report.steps.push(LibraryRepairStepResult {
+                step,
+                changes: 0,
+                items: Vec::new(),
+                resumed: true,
+                error: None,
+            });
+            continue;
+        }
+
+        let _ = app.emit(
+            "library-repair-progress",
+            LibraryRepairProgress {
+                step,
+                current: index + 1,
+                total,
+                dry_run,
+            },
+        );
+        let mut result = LibraryRepairStepResult {
+            step,
+            changes: 0,
+            items: Vec::new(),
+            resumed: false,
+            error: None,
+        };
+        match run_step(&service, step, dry_run) {
+            Ok((changes, items)) => {
+                result.changes = changes;
+                result.items = items;
+                report.steps.push(result);
+            }
+            Err(e) => {
+                log::warn!("[library-repair] {:?} failed: {}", step, e);
+                result.error = Some(e);
+                report.steps.push(result);
+                report.completed = false;
+                break;
+            }
+        }
+
+        if !dry_run {
+            checkpoint.completed.push(step);
+            save_checkpoint(&app, &checkpoint)?;
+        }
+    }
+
+    if !dry_run && report.completed {
+        clear_checkpoint(&app)?;
+    }
+    log::info!(
+        "[library-repair] {} change(s){}",
+        report.steps.iter().map(|s| s.changes).sum::<usize>(),
+        if report.completed {
+            ""
+        } else {
+            ", stopped early"
+        }
+    );
+    Ok(report)
+}
+
+/// The unfinished run a repair would resume, if any
+#[tauri::command]
+pub async fn get_library_repair_checkpoint(
+    app: AppHandle,
+) -> Result<Option<LibraryRepairCheckpoint>, String> {
+    load_checkpoint(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::Fixture;
+
+    #[test]
+    fn test_steps_run_in_dependency_order() {
+        let steps: Vec<LibraryRepairStep> = serde_json::from_str(
+            r#"["cleanEmptyFolders", "repairSplitMods", "cleanEmptyFolders"]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ordered_steps(&steps),
+            vec![
+                LibraryRepairStep::RepairSplitMods,
+                LibraryRepairStep::CleanEmptyFolders
+            ]
+        );
+        assert!(serde_json::from_str::<LibraryRepairStep>(r#""cleanJunk""#).is_err());
+    }
+
+    #[test]
+    fn test_dry_run_previews_without_changing_anything() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        fx.installed_mod("Skins/Black Widow/Mod-A", "ModA_P");
+        fx.installed_mod("Skins/Black-Widow/Mod-B", "ModB_P");
+        std::fs::create_dir_all(fx.mods_dir().join("Skins/Hulk/Leftover")).unwrap();
+
+        let (changes, items) =
+            run_step(&service, LibraryRepairStep::MergeDuplicateFolders, true).unwrap();
+        assert_eq!(changes, 1);
+        assert_eq!(items, vec!["Black Widow → Black-Widow".to_string()]);
+        let (changes, _) = run_step(&service, LibraryRepairStep::CleanEmptyFolders, true).unwrap();
+        assert_eq!(changes, 1);
+        assert!(fx.mods_dir().join("Skins/Black Widow").exists());
+        assert!(fx.mods_dir().join("Skins/Hulk/Leftover").exists());
+
+        let (changes, _) =
+            run_step(&service, LibraryRepairStep::MergeDuplicateFolders, false).unwrap();
+        assert_eq!(changes, 1);
+        assert!(!fx.mods_dir().join("Skins/Black Widow").exists());
+        let (changes, _) = run_step(&service, LibraryRepairStep::CleanEmptyFolders, false).unwrap();
+        assert_eq!(changes, 1);
+        assert!(!fx.mods_dir().join("Skins/Hulk/Leftover").exists());
+    }
+}
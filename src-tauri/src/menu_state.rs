@@ -0,0 +1,137 @@
+//! Handles to the menu items that change after the menu is built, kept in
+//! managed state. The menu is rebuilt whenever the locale or read-only mode
+//! changes, so the state they show is remembered here and re-applied to the
+//! new items.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::menu::CheckMenuItem;
+use tauri::{AppHandle, Manager, Wry};
+
+pub const TOGGLE_LEFT_SIDEBAR: &str = "toggle-left-sidebar";
+pub const TOGGLE_RIGHT_SIDEBAR: &str = "toggle-right-sidebar";
+
+/// Which sidebars the main window shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidebarState {
+    pub left: bool,
+    pub right: bool,
+}
+
+impl Default for SidebarState {
+    /// Both shown, as the frontend starts before restoring its layout
+    fn default() -> Self {
+        Self {
+            left: true,
+            right: true,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Handles {
+    left_sidebar: Option<CheckMenuItem<Wry>>,
+    right_sidebar: Option<CheckMenuItem<Wry>>,
+    sidebars: SidebarState,
+}
+
+#[derive(Default)]
+pub struct MenuState {
+    handles: Mutex<Handles>,
+}
+
+impl MenuState {
+    /// The sidebar state the items should show, for building them
+    pub fn sidebars(&self) -> SidebarState {
+        self.lock().sidebars
+    }
+
+    /// Keep the sidebar items of a newly built menu
+    pub fn set_sidebar_items(&self, left: CheckMenuItem<Wry>, right: CheckMenuItem<Wry>) {
+        let mut handles = self.lock();
+        handles.left_sidebar = Some(left);
+        handles.right_sidebar = Some(right);
+    }
+
+    /// Show `sidebars` in the menu
+    pub fn set_sidebars(&self, sidebars: SidebarState) {
+        let mut handles = self.lock();
+        handles.sidebars = sidebars;
+        handles.apply();
+    }
+
+    /// Flip one sidebar's check ahead of the frontend, which confirms with
+    /// `set_sidebar_menu_state` once its layout changed. Worked out from the
+    /// remembered state, so it's right whether or not the platform already
+    /// toggled the item itself.
+    pub fn toggle_sidebar(&self, item_id: &str) {
+        let mut handles = self.lock();
+        match item_id {
+            TOGGLE_LEFT_SIDEBAR => handles.sidebars.left = !handles.sidebars.left,
+            TOGGLE_RIGHT_SIDEBAR => handles.sidebars.right = !handles.sidebars.right,
+            _ => return,
+        }
+        handles.apply();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Handles> {
+        self.handles.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Handles {
+    fn apply(&self) {
+        for (item, checked) in [
+            (&self.left_sidebar, self.sidebars.left),
+            (&self.right_sidebar, self.sidebars.right),
+        ] {
+            if let Some(item) = item {
+                if let Err(e) = item.set_checked(checked) {
+                    log::warn!("[menu] Failed to update {:?}: {}", item.id(), e);
+                }
+            }
+        }
+    }
+}
+
+/// Check the View menu's sidebar items to match the main window's layout.
+/// Called by the frontend on startup and whenever a sidebar is shown or
+/// hidden.
+#[tauri::command]
+pub async fn set_sidebar_menu_state(app: AppHandle, left: bool, right: bool) -> Result<(), String> {
+    app.state::<MenuState>()
+        .set_sidebars(SidebarState { left, right });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidebar_toggles_follow_the_remembered_state() {
+        let state = MenuState::default();
+        assert_eq!(state.sidebars(), SidebarState::default());
+
+        state.toggle_sidebar(TOGGLE_LEFT_SIDEBAR);
+        assert_eq!(
+            state.sidebars(),
+            SidebarState {
+                left: false,
+                right: true
+            }
+        );
+        state.toggle_sidebar("preferences");
+        assert!(!state.sidebars().left);
+
+        // The frontend's layout wins over the optimistic flip
+        state.set_sidebars(SidebarState {
+            left: true,
+            right: false,
+        });
+        state.toggle_sidebar(TOGGLE_RIGHT_SIDEBAR);
+        assert!(state.sidebars().left && state.sidebars().right);
+    }
+}
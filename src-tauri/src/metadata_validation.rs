@@ -0,0 +1,397 @@
+//! Checks every metadata write goes through. Text is trimmed and cut to a
+//! sane length, tags are deduplicated, timestamps from the future are pulled
+//! back to now. Only input the app can't work with (an empty title, or a
+//! category or original folder that would put files outside the mod
+//! folders) is rejected; anything doubtful but usable, like a costume the
+//! costume data doesn't list for the character, is let through with a
+//! warning.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::mod_service::contained_folder;
+use crate::types::{Character, MetadataUpdateError, ModCategory, ModMetadata};
+
+/// Titles name the mod's folder, so they stay well inside Windows path limits
+pub const MAX_TITLE_CHARS: usize = 120;
+pub const MAX_DESCRIPTION_CHARS: usize = 10_000;
+pub const MAX_NOTES_CHARS: usize = 10_000;
+/// Subtitle, author and version
+pub const MAX_SHORT_TEXT_CHARS: usize = 120;
+pub const MAX_TAG_CHARS: usize = 40;
+pub const MAX_TAGS: usize = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueSeverity {
+    /// The write is refused
+    Error,
+    /// The write goes ahead, possibly with the value adjusted
+    Warning,
+}
+
+/// A problem with one metadata field, for the UI to show next to its input
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    /// camelCase field name, as in `ModMetadata`'s JSON
+    pub field: String,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn error(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            severity: IssueSeverity::Error,
+            message: message.into(),
+        }
+    }
+
+    pub fn warning(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.to_string(),
+            severity: IssueSeverity::Warning,
+            message: message.into(),
+        }
+    }
+}
+
+/// Metadata ready to save, with what was adjusted or looked doubtful
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizedMetadata {
+    pub metadata: ModMetadata,
+    pub warnings: Vec<ValidationIssue>,
+}
+
+/// One line per issue, for callers that can only report a string
+pub fn describe_issues(issues: &[ValidationIssue]) -> String {
+    issues
+        .iter()
+        .map(|issue| format!("{}: {}", issue.field, issue.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Validate and normalize metadata about to be written, checking the costume
+/// against the loaded costume data
+pub fn validate_metadata(
+    metadata: &ModMetadata,
+    now: DateTime<Utc>,
+) -> Result<NormalizedMetadata, Vec<ValidationIssue>> {
+    validate_with(metadata, now, |character, costume| {
+        crate::costume_service::costume_exists(&character.to_string(), costume)
+    })
+}
+
+/// Parse metadata JSON edited by hand. Syntax and type errors come back
+/// with their line and column. Fields the app doesn't know would be dropped
+/// on save, so they are refused as likely typos; a `null` for an optional
+/// field the app leaves out is fine.
+pub fn parse_metadata_json(json: &str) -> Result<ModMetadata, MetadataUpdateError> {
+    let syntax = |e: serde_json::Error| MetadataUpdateError::Syntax {
+        message: e.to_string(),
+        line: e.line(),
+        column: e.column(),
+    };
+    let value: serde_json::Value = serde_json::from_str(json).map_err(syntax)?;
+    let metadata: ModMetadata = serde_json::from_str(json).map_err(syntax)?;
+
+    let known = serde_json::to_value(&metadata).map_err(|e| e.to_string())?;
+    let issues: Vec<ValidationIssue> = value
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(field, value)| known.get(field.as_str()).is_none() && !value.is_null())
+        .map(|(field, _)| ValidationIssue::error(field, "Unknown field"))
+        .collect();
+    if !issues.is_empty() {
+        return Err(MetadataUpdateError::Invalid { issues });
+    }
+    Ok(metadata)
+}
+
+/// `validate_metadata` with the costume lookup passed in. The lookup answers
+/// `None` when it can't tell, which is never worth a warning.
+fn validate_with(
+    metadata: &ModMetadata,
+    now: DateTime<Utc>,
+    costume_exists: impl Fn(&Character, &str) -> Option<bool>,
+) -> Result<NormalizedMetadata, Vec<ValidationIssue>> {
+    let mut metadata = metadata.clone();
+    let mut issues = Vec::new();
+
+    metadata.title = limit(metadata.title.trim(), MAX_TITLE_CHARS, "title", &mut issues);
+    if metadata.title.is_empty() {
+        issues.push(ValidationIssue::error("title", "Title can't be empty"));
+    }
+    metadata.description = limit(
+        metadata.description.trim(),
+        MAX_DESCRIPTION_CHARS,
+        "description",
+        &mut issues,
+    );
+    metadata.notes = metadata
+        .notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(|text| limit(text, MAX_NOTES_CHARS, "notes", &mut issues));
+    for (field, value) in [
+        ("subtitle", &mut metadata.subtitle),
+        ("author", &mut metadata.author),
+        ("version", &mut metadata.version),
+    ] {
+        *value = value
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(|text| limit(text, MAX_SHORT_TEXT_CHARS, field, &mut issues));
+    }
+
+    metadata.tags = normalize_tags(&metadata.tags, &mut issues);
+
+    match (&metadata.character, metadata.costume.as_deref()) {
+        (None, Some(costume)) => issues.push(ValidationIssue::warning(
+            "costume",
+            format!("Costume \"{costume}\" is set without a character"),
+        )),
+        (Some(character), Some(costume)) if costume_exists(character, costume) == Some(false) => {
+            issues.push(ValidationIssue::warning(
+                "costume",
+                format!("{character} has no costume \"{costume}\""),
+            ))
+        }
+        _ => {}
+    }
+
+    // Both name folders that mods are moved into
+    if let ModCategory::Unknown(category) = &metadata.category {
+        let category = category.trim();
+        if category.is_empty()
+            || category.contains(['/', '\\', ':'])
+            || category.chars().all(|c| c == '.')
+        {
+            issues.push(ValidationIssue::error(
+                "category",
+                format!("\"{category}\" can't be used as a category folder"),
+            ));
+        }
+    }
+    if let Some(folder) = metadata.original_folder_path.as_deref() {
+        if contained_folder(folder).is_none() {
+            issues.push(ValidationIssue::error(
+                "originalFolderPath",
+                "Folder must stay inside the mods folder",
+            ));
+        }
+    }
+
+    // Clocks on other machines (synced metadata) or hand edits
+    for (field, value) in [
+        ("createdAt", &mut metadata.created_at),
+        ("updatedAt", &mut metadata.updated_at),
+        ("installDate", &mut metadata.install_date),
+    ] {
+        if *value > now {
+            *value = now;
+            issues.push(ValidationIssue::warning(
+                field,
+                "Date was in the future; set to now",
+            ));
+        }
+    }
+
+    if issues.iter().any(|i| i.severity == IssueSeverity::Error) {
+        return Err(issues);
+    }
+    Ok(NormalizedMetadata {
+        metadata,
+        warnings: issues,
+    })
+}
+
+/// `text` cut to `max` characters, with a warning when it was longer
+fn limit(text: &str, max: usize, field: &str, issues: &mut Vec<ValidationIssue>) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    issues.push(ValidationIssue::warning(
+        field,
+        format!("Shortened to {max} characters"),
+    ));
+    text.chars()
+        .take(max)
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+/// Trimmed, lowercase, without empties or repeats, in their original order
+fn normalize_tags(tags: &[String], issues: &mut Vec<ValidationIssue>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() || normalized.contains(&tag) {
+            continue;
+        }
+        normalized.push(limit(&tag, MAX_TAG_CHARS, "tags", issues));
+    }
+    if normalized.len() > MAX_TAGS {
+        normalized.truncate(MAX_TAGS);
+        issues.push(ValidationIssue::warning(
+            "tags",
+            format!("Only the first {MAX_TAGS} tags were kept"),
+        ));
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::{date, metadata};
+    use crate::types::ModCategory;
+
+    fn now() -> DateTime<Utc> {
+        date("2025-06-01T00:00:00Z")
+    }
+
+    fn validate(metadata: &ModMetadata) -> Result<NormalizedMetadata, Vec<ValidationIssue>> {
+        validate_with(metadata, now(), |character, costume| {
+            Some(*character == Character::Hulk && costume == "maestro")
+        })
+    }
+
+    fn fields(issues: &[ValidationIssue]) -> Vec<&str> {
+        issues.iter().map(|i| i.field.as_str()).collect()
+    }
+
+    #[test]
+    fn test_empty_title_is_rejected() {
+        let issues = validate(&metadata("   ", ModCategory::Skins)).unwrap_err();
+        assert_eq!(
+            issues,
+            [ValidationIssue::error("title", "Title can't be empty")]
+        );
+    }
+
+    #[test]
+    fn test_text_is_trimmed_and_limited() {
+        let mut meta = metadata("  Storm  ", ModCategory::Skins);
+        meta.description = format!("  {}  ", "x".repeat(50_000));
+        meta.author = Some("   ".to_string());
+        meta.version = Some(" 1.2 ".to_string());
+
+        let normalized = validate(&meta).unwrap();
+        assert_eq!(normalized.metadata.title, "Storm");
+        assert_eq!(
+            normalized.metadata.description.chars().count(),
+            MAX_DESCRIPTION_CHARS
+        );
+        assert_eq!(normalized.metadata.author, None);
+        assert_eq!(normalized.metadata.version.as_deref(), Some("1.2"));
+        assert_eq!(fields(&normalized.warnings), ["description"]);
+
+        // Cut on a character boundary, not a byte one
+        let long_title = "é".repeat(MAX_TITLE_CHARS + 5);
+        let normalized = validate(&metadata(&long_title, ModCategory::Skins)).unwrap();
+        assert_eq!(normalized.metadata.title.chars().count(), MAX_TITLE_CHARS);
+    }
+
+    #[test]
+    fn test_tags_are_deduplicated_and_lowercased() {
+        let mut meta = metadata("Storm", ModCategory::Skins);
+        meta.tags = ["Lore ", "lore", "", "  ", "NSFW", "Lore"]
+            .map(String::from)
+            .to_vec();
+        let normalized = validate(&meta).unwrap();
+        assert_eq!(normalized.metadata.tags, ["lore", "nsfw"]);
+        assert!(normalized.warnings.is_empty());
+
+        meta.tags = (0..MAX_TAGS + 3).map(|i| format!("tag{i}")).collect();
+        let normalized = validate(&meta).unwrap();
+        assert_eq!(normalized.metadata.tags.len(), MAX_TAGS);
+        assert_eq!(fields(&normalized.warnings), ["tags"]);
+    }
+
+    #[test]
+    fn test_unknown_costume_is_only_a_warning() {
+        let mut meta = metadata("Hulk", ModCategory::Skins);
+        meta.character = Some(Character::Hulk);
+        meta.costume = Some("maestro".to_string());
+        assert!(validate(&meta).unwrap().warnings.is_empty());
+
+        meta.costume = Some("made-up".to_string());
+        let normalized = validate(&meta).unwrap();
+        assert_eq!(normalized.metadata.costume.as_deref(), Some("made-up"));
+        assert_eq!(fields(&normalized.warnings), ["costume"]);
+
+        meta.character = None;
+        assert_eq!(fields(&validate(&meta).unwrap().warnings), ["costume"]);
+
+        // Nothing to check against: no warning
+        meta.character = Some(Character::Hulk);
+        let unknown = validate_with(&meta, now(), |_, _| None).unwrap();
+        assert!(unknown.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_folders_outside_the_mod_folders_are_rejected() {
+        let mut meta = metadata("Storm", ModCategory::Unknown("Maps".to_string()));
+        meta.original_folder_path = Some("Skins/Storm".to_string());
+        assert!(validate(&meta).is_ok());
+
+        for category in ["../../x", "Skins/Storm", "..", " ", "C:"] {
+            meta.category = ModCategory::Unknown(category.to_string());
+            assert_eq!(fields(&validate(&meta).unwrap_err()), ["category"]);
+        }
+
+        meta.category = ModCategory::Skins;
+        for folder in ["../outside", "/etc", "Skins/../../x", "C:\\Windows"] {
+            meta.original_folder_path = Some(folder.to_string());
+            assert_eq!(
+                fields(&validate(&meta).unwrap_err()),
+                ["originalFolderPath"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_metadata_json() {
+        let meta = metadata("Storm", ModCategory::Skins);
+        let json = serde_json::to_string_pretty(&meta).unwrap();
+        assert_eq!(parse_metadata_json(&json).unwrap().title, "Storm");
+
+        // The comma after the title left out: the error is where the next
+        // field starts, on line 3
+        let broken = json.replacen("\",\n", "\"\n", 1);
+        match parse_metadata_json(&broken) {
+            Err(MetadataUpdateError::Syntax { line, .. }) => assert_eq!(line, 3),
+            other => panic!("expected a syntax error, got {:?}", other.map(|m| m.title)),
+        }
+
+        let mut value = serde_json::to_value(&meta).unwrap();
+        value["parentModId"] = serde_json::Value::Null;
+        value["tittle"] = "Storm".into();
+        match parse_metadata_json(&value.to_string()) {
+            Err(MetadataUpdateError::Invalid { issues }) => assert_eq!(fields(&issues), ["tittle"]),
+            other => panic!(
+                "expected an unknown field, got {:?}",
+                other.map(|m| m.title)
+            ),
+        }
+    }
+
+    #[test]
+    fn test_future_dates_are_pulled_back() {
+        let mut meta = metadata("Storm", ModCategory::Skins);
+        meta.updated_at = date("2030-01-01T00:00:00Z");
+        let normalized = validate(&meta).unwrap();
+        assert_eq!(normalized.metadata.updated_at, now());
+        assert_eq!(normalized.metadata.created_at, meta.created_at);
+        assert_eq!(fields(&normalized.warnings), ["updatedAt"]);
+    }
+}
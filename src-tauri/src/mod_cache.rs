@@ -0,0 +1,243 @@
+//! The last library scan, kept in managed state so listing commands don't
+//! each walk both mod folders and read every metadata file. A file change
+//! anywhere (an `fs_ops` move, the watcher, a settings save) makes the next
+//! read rescan; a metadata save only re-reads that one mod. The folder
+//! fingerprint catches what neither saw, like a drive swapped while the
+//! watcher was down. Everything touching the disk runs on a blocking thread.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+use std::time::SystemTime;
+
+use tauri::{AppHandle, Manager};
+use tokio::sync::RwLock;
+
+use crate::mod_service::ModService;
+use crate::types::{ModInfo, SortDirection, SortKey, SortSpec};
+
+type Fingerprint = BTreeMap<PathBuf, Option<SystemTime>>;
+
+struct Changes {
+    /// Bumped by every file change; a scan from an older generation is stale
+    generation: u64,
+    /// Mods whose metadata changed since the cache last looked
+    mods: BTreeSet<String>,
+}
+
+static CHANGES: Mutex<Changes> = Mutex::new(Changes {
+    generation: 0,
+    mods: BTreeSet::new(),
+});
+
+fn changes() -> MutexGuard<'static, Changes> {
+    CHANGES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Files in the mod folders changed: the next read rescans
+pub fn invalidate() {
+    changes().generation += 1;
+}
+
+/// Only `mod_id`'s metadata changed: the next read re-reads that one mod
+pub fn invalidate_mod(mod_id: &str) {
+    changes().mods.insert(mod_id.to_string());
+}
+
+struct CachedScan {
+    mods_directory: PathBuf,
+    generation: u64,
+    fingerprint: Fingerprint,
+    mods: Vec<ModInfo>,
+}
+
+impl CachedScan {
+    fn is_current(&self, service: &ModService, generation: u64, fingerprint: &Fingerprint) -> bool {
+        self.mods_directory == service.mods_directory()
+            && self.generation == generation
+            && self.fingerprint == *fingerprint
+    }
+
+    /// Re-read the mods in `stale` in place. False when one can't be
+    /// patched (its pak is gone, or it shares its files' ID with a copy
+    /// disabled in place) and the library needs a full scan.
+    fn refresh(&mut self, service: &ModService, stale: &BTreeSet<String>) -> bool {
+        for id in stale {
+            // Metadata of a mod this library doesn't list, or not yet
+            let Some(index) = self.mods.iter().position(|m| &m.id == id) else {
+                continue;
+            };
+            let twinned = self.mods[index].duplicate_of.is_some()
+                || self
+                    .mods
+                    .iter()
+                    .any(|m| m.duplicate_of.as_deref() == Some(id.as_str()));
+            if twinned {
+                return false;
+            }
+            match service.reload_mod(&self.mods[index]) {
+                Some(fresh) => self.mods[index] = fresh,
+                None => return false,
+            }
+        }
+        // A new title moves the mod, as in `get_all_mods`
+        crate::sorting::sort_mods(
+            &mut self.mods,
+            &SortSpec {
+                key: SortKey::Title,
+                direction: SortDirection::Asc,
+            },
+        );
+        true
+    }
+}
+
+/// Run `f` on a blocking thread, with its own copy of `service`
+async fn on_blocking_thread<T, F>(service: &ModService, f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&ModService) -> T + Send + 'static,
+{
+    let service = service.clone();
+    tauri::async_runtime::spawn_blocking(move || f(&service))
+        .await
+        .map_err(|e| format!("Mod scan failed: {e}"))
+}
+
+#[derive(Default)]
+pub struct ModCache {
+    scan: RwLock<Option<CachedScan>>,
+}
+
+impl ModCache {
+    /// The library as `service` would scan it, and whether any of it was
+    /// read from disk just now rather than served as cached
+    pub async fn mods(&self, service: &ModService) -> Result<(Vec<ModInfo>, bool), String> {
+        let fingerprint = on_blocking_thread(service, ModService::folder_fingerprint).await?;
+        let (generation, clean) = {
+            let changes = changes();
+            (changes.generation, changes.mods.is_empty())
+        };
+        if clean {
+            let scan = self.scan.read().await;
+            if let Some(cached) = scan
+                .as_ref()
+                .filter(|c| c.is_current(service, generation, &fingerprint))
+            {
+                return Ok((cached.mods.clone(), false));
+            }
+        }
+
+        let mut scan = self.scan.write().await;
+        // Taken under the write lock, so a concurrent read that already
+        // refreshed these doesn't leave them for us to miss
+        let (generation, stale) = {
+            let mut changes = changes();
+            (changes.generation, std::mem::take(&mut changes.mods))
+        };
+        // Taken out while it's patched off this thread; left empty if the
+        // patch fails, or the future is dropped, so the next read rescans
+        if let Some(mut cached) = scan
+            .take()
+            .filter(|c| c.is_current(service, generation, &fingerprint))
+        {
+            let (cached, patched) = on_blocking_thread(service, move |service| {
+                let patched = cached.refresh(service, &stale);
+                (cached, patched)
+            })
+            .await?;
+            if patched {
+                let mods = cached.mods.clone();
+                *scan = Some(cached);
+                return Ok((mods, true));
+            }
+        }
+
+        let mods = on_blocking_thread(service, ModService::get_all_mods).await??;
+        *scan = Some(CachedScan {
+            mods_directory: service.mods_directory().to_path_buf(),
+            generation,
+            fingerprint,
+            mods: mods.clone(),
+        });
+        Ok((mods, true))
+    }
+
+    /// Drop the cached scan, so the next read rescans whatever changed
+    pub async fn clear(&self) {
+        *self.scan.write().await = None;
+    }
+}
+
+/// The library, rescanned when `force` is set or anything changed since the
+/// last scan
+#[tauri::command]
+pub async fn refresh_mods(app: AppHandle, force: bool) -> Result<Vec<ModInfo>, String> {
+    if force {
+        app.state::<ModCache>().clear().await;
+    }
+    crate::list_mods(&app).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::Fixture;
+    use tauri::async_runtime::block_on;
+
+    #[test]
+    fn test_metadata_changes_patch_the_cached_scan() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let hulk = service
+            .install_mod_to_folder(&fx.source_mod("Hulk_P", 8, Some(64)), "Hulk")
+            .unwrap();
+        service
+            .install_mod_to_folder(&fx.source_mod("Storm_P", 8, Some(64)), "Storm")
+            .unwrap();
+
+        let cache = ModCache::default();
+        let (mods, _) = block_on(cache.mods(&service)).unwrap();
+        assert_eq!(mods.len(), 2);
+
+        service.toggle_favorite(&hulk.id).unwrap();
+        let mut scan = CachedScan {
+            mods_directory: service.mods_directory().to_path_buf(),
+            generation: 0,
+            fingerprint: service.folder_fingerprint(),
+            mods,
+        };
+        assert!(scan.refresh(&service, &BTreeSet::from([hulk.id.clone()])));
+        let favorites: Vec<&str> = scan
+            .mods
+            .iter()
+            .filter(|m| m.is_favorite)
+            .map(|m| m.id.as_str())
+            .collect();
+        assert_eq!(favorites, [hulk.id.as_str()]);
+
+        // Whatever the cache holds, reads see the change too
+        let (mods, _) = block_on(cache.mods(&service)).unwrap();
+        assert!(mods.iter().any(|m| m.id == hulk.id && m.is_favorite));
+
+        // A mod whose files left can't be patched
+        service.delete_mod(&hulk.id, true, true).unwrap();
+        assert!(!scan.refresh(&service, &BTreeSet::from([hulk.id.clone()])));
+        let (mods, _) = block_on(cache.mods(&service)).unwrap();
+        assert_eq!(mods.len(), 1);
+    }
+
+    #[test]
+    fn test_folder_fingerprint_follows_moves() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let storm = fx.installed_mod("Skins/Storm/Weather", "Storm_P");
+        let before = service.folder_fingerprint();
+        assert!(before.contains_key(&fx.mods_dir().join("Skins").join("Storm")));
+
+        // Moving the pak out of ~mods shows without reading any file
+        std::fs::create_dir_all(fx.disabled_dir()).unwrap();
+        std::fs::rename(&storm, fx.disabled_dir().join("Storm_P.pak")).unwrap();
+        assert_ne!(service.folder_fingerprint(), before);
+    }
+}
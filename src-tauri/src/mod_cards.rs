@@ -0,0 +1,164 @@
+//! Slim per-mod payload for drawing the library grid. `get_all_mods` sends
+//! every mod's full metadata (descriptions, tags, Nexus fields, file lists),
+//! several megabytes for a large library; a card carries only what a grid
+//! tile shows, a page at a time. The detail panel fetches the full `ModInfo`
+//! with `get_mod`.
+
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::mod_service::page_mods;
+use crate::sorting;
+use crate::types::{Character, ModCategory, ModInfo, ModQuery, SortSpec};
+
+/// What a grid tile shows of a mod
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModCard {
+    pub id: String,
+    pub title: String,
+    pub character: Option<Character>,
+    pub category: ModCategory,
+    pub costume: Option<String>,
+    pub enabled: bool,
+    pub favorite: bool,
+    pub nsfw: bool,
+    pub thumbnail_path: Option<PathBuf>,
+    /// Modification time of the thumbnail in milliseconds, to tell the
+    /// webview a replaced thumbnail at the same path is new
+    pub thumbnail_version: Option<u64>,
+    pub file_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModCardPage {
+    pub cards: Vec<ModCard>,
+    /// Mods matching the filter, across all pages
+    pub total: usize,
+}
+
+impl ModCard {
+    pub fn from_mod(mod_info: &ModInfo) -> Self {
+        let thumbnail_version = mod_info
+            .thumbnail_path
+            .as_ref()
+            .and_then(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|since| since.as_millis() as u64);
+        Self {
+            id: mod_info.id.clone(),
+            title: sorting::title(mod_info).to_string(),
+            character: mod_info.character.clone(),
+            category: mod_info.category.clone(),
+            costume: mod_info.metadata.costume.clone(),
+            enabled: mod_info.enabled,
+            favorite: mod_info.is_favorite,
+            nsfw: mod_info.metadata.is_nsfw,
+            thumbnail_path: mod_info.thumbnail_path.clone(),
+            thumbnail_version,
+            file_size: mod_info.file_size,
+        }
+    }
+}
+
+/// `limit` cards from `offset` of the mods matching `filter`, in `sort`
+/// order. Only the cards on the page touch the disk, for their thumbnails.
+fn card_page(
+    mods: Vec<ModInfo>,
+    filter: Option<&ModQuery>,
+    sort: Option<&SortSpec>,
+    offset: usize,
+    limit: usize,
+) -> ModCardPage {
+    let page = page_mods(mods, filter, sort, offset, limit);
+    ModCardPage {
+        total: page.total,
+        cards: page.mods.iter().map(ModCard::from_mod).collect(),
+    }
+}
+
+/// A page of grid cards from one library scan (or the saved listing while
+/// the game folder is offline)
+#[tauri::command]
+pub async fn get_mod_cards(
+    app: AppHandle,
+    filter: Option<ModQuery>,
+    sort: Option<SortSpec>,
+    offset: usize,
+    limit: usize,
+) -> Result<ModCardPage, String> {
+    let mods = crate::list_mods(&app).await?;
+    Ok(card_page(
+        mods,
+        filter.as_ref(),
+        sort.as_ref(),
+        offset,
+        limit,
+    ))
+}
+
+/// One mod in full, for the detail panel
+#[tauri::command]
+pub async fn get_mod(app: AppHandle, mod_id: String) -> Result<ModInfo, String> {
+    crate::list_mods(&app)
+        .await?
+        .into_iter()
+        .find(|m| m.id == mod_id)
+        .ok_or_else(|| format!("Mod not found: {}", mod_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::{metadata, Fixture};
+    use crate::types::{SortDirection, SortKey};
+
+    #[test]
+    fn test_cards_are_paged_and_much_smaller_than_mods() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        for (stem, title) in [
+            ("Mod10_P", "Mod 10"),
+            ("Mod2_P", "Mod 2"),
+            ("Mod1_P", "Mod 1"),
+        ] {
+            let mut metadata = metadata(title, ModCategory::Skins);
+            metadata.description = "A long description. ".repeat(40);
+            metadata.tags = vec!["skin".to_string(), "recolor".to_string()];
+            service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 8, Some(64)),
+                    &format!("Skins/{stem}"),
+                    metadata,
+                )
+                .unwrap();
+        }
+        let mods = service.get_all_mods().unwrap();
+        let sort = SortSpec {
+            key: SortKey::Title,
+            direction: SortDirection::Asc,
+        };
+
+        let page = card_page(mods.clone(), None, Some(&sort), 1, 5);
+        assert_eq!(page.total, 3);
+        let titles: Vec<&str> = page.cards.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, ["Mod 2", "Mod 10"]);
+
+        let filter = ModQuery {
+            search: Some("mod 1".to_string()),
+            ..ModQuery::default()
+        };
+        let filtered = card_page(mods.clone(), Some(&filter), Some(&sort), 0, 1);
+        assert_eq!((filtered.total, filtered.cards.len()), (2, 1));
+
+        let full = serde_json::to_string(&mods).unwrap().len();
+        let cards = serde_json::to_string(&card_page(mods, None, None, 0, 3).cards)
+            .unwrap()
+            .len();
+        assert!(cards * 5 < full, "cards {cards} bytes, mods {full} bytes");
+    }
+}
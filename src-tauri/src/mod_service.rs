@@ -1,30 +1,167 @@
+use crate::collections::CollectionStore;
+use crate::disk_space::{self, format_size};
+use crate::fs_ops;
+use crate::hash_cache;
+use crate::ignored_paths::IgnoredPaths;
+use crate::metadata_validation::{describe_issues, parse_metadata_json, validate_metadata};
+use crate::mod_cache;
+use crate::perf_metrics;
+use crate::progress::{CancellationToken, ProgressEmitter};
+use crate::thumbnail_service::{find_preview_image, ThumbnailService};
 use crate::types::*;
-use chrono::Utc;
+use crate::write_behind;
+use chrono::{DateTime, Utc};
 use sha2::{Digest, Sha256};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
 use walkdir::WalkDir;
 
 const SUPPORTED_EXTENSIONS: &[&str] = &[".pak"];
 
+/// Files that belong to a pak and travel with it wherever it goes: the
+/// IoStore .ucas/.utoc pair and the .sig some mods are signed with. Matched
+/// on the pak's stem, ignoring case.
+pub const COMPANION_EXTENSIONS: &[&str] = &["ucas", "utoc", "sig"];
+
+/// The IoStore companions, which only work as a pair
+const IOSTORE_EXTENSIONS: &[&str] = &["ucas", "utoc"];
+
+/// Every extension a mod's files can have
+const MOD_FILE_EXTENSIONS: &[&str] = &["pak", "ucas", "utoc", "sig"];
+
+/// Metadata fields that change with a single click and never affect where a
+/// mod lives on disk. Edits touching only these go through the write-behind.
+const LIGHT_METADATA_FIELDS: &[&str] = &[
+    "isFavorite",
+    "profileIds",
+    "updatedAt",
+    "notes",
+    "history",
+    "variantGroup",
+];
+
+/// Held across every metadata read-check-write. Each command builds its own
+/// `ModService`, so two windows saving the same mod only meet here.
+static METADATA_LOCK: Mutex<()> = Mutex::new(());
+
+/// Lists the ID moves a path-ID migration still has to finish, so a run cut
+/// short resumes instead of leaving a mix of old and new IDs. Not `.json`,
+/// which every metadata scan would pick up.
+const ID_MIGRATION_CHECKPOINT: &str = "id-migration.checkpoint";
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IdMigrationCheckpoint {
+    /// (old ID, new ID) pairs not finished yet
+    remaining: Vec<(String, String)>,
+}
+
+/// Conflict count from the last `detect_mod_conflicts` per library, with a
+/// fingerprint of the enabled mods it was computed over
+static CONFLICT_CACHE: Mutex<BTreeMap<PathBuf, (u64, usize)>> = Mutex::new(BTreeMap::new());
+
+/// How many characters the library summary ranks
+const TOP_CHARACTERS: usize = 5;
+
+/// Folder under the metadata directory deleted mods are moved to, one
+/// `<mod_id>/` folder each. A directory, so metadata scans pass over it.
+const TRASH_DIR: &str = "trash";
+
+/// Written in each trash folder before anything is moved into it
+const TRASH_MANIFEST: &str = "manifest.json";
+
+/// Days a deleted mod can still be restored before the trash is purged
+pub const TRASH_RETENTION_DAYS: i64 = 30;
+
+/// Highest load priority, the most a three-digit `NNN_` file name prefix
+/// can hold
+pub const MAX_PRIORITY: u32 = 999;
+
+/// Gap `reorder_mods` leaves between neighbours, so a mod can later be
+/// slotted in between without renumbering the rest
+const PRIORITY_STEP: u32 = 10;
+
+fn lock_metadata() -> MutexGuard<'static, ()> {
+    METADATA_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Where an install reports the bytes it has copied, and the token that
+/// stops it. The total covers the pak and its companions, so the
+/// percentage only goes up.
+pub struct InstallCopy<'a> {
+    pub cancel: CancellationToken,
+    pub on_progress: &'a dyn Fn(ModInstallProgress),
+}
+
+fn ignore_install_progress(_: ModInstallProgress) {}
+
+impl Default for InstallCopy<'_> {
+    fn default() -> Self {
+        Self {
+            cancel: CancellationToken::new(),
+            on_progress: &ignore_install_progress,
+        }
+    }
+}
+
+/// Files an install has copied so far, and anything off about them
+#[derive(Default)]
+struct InstallLog {
+    copied_files: Vec<AssociatedFileSize>,
+    missing_companions: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl InstallLog {
+    fn finish(self, mod_info: ModInfo) -> InstallResult {
+        InstallResult {
+            mod_info,
+            copied_files: self.copied_files,
+            missing_companions: self.missing_companions,
+            warnings: self.warnings,
+        }
+    }
+}
+
+/// Source of "now" for metadata timestamps, swappable in tests
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+#[derive(Clone)]
 pub struct ModService {
     mods_directory: PathBuf,
     disabled_mods_directory: PathBuf,
     metadata_directory: PathBuf,
     thumbnails_directory: PathBuf,
+    clock: Arc<dyn Clock>,
+    // Profile that newly installed mods join, if auto-assign is on
+    active_profile_id: Option<String>,
+    disable_strategy: DisableStrategy,
+    // Folders under ~mods the user has asked the manager to leave alone
+    ignored_paths: IgnoredPaths,
+    // How many folder levels scans and cleanup descend; None for all
+    max_scan_depth: Option<usize>,
+    // The library as the scan cache last listed it, so a command on one mod
+    // finds it without a rescan. Each hit is re-read before use.
+    listing: Vec<ModInfo>,
 }
 
 impl ModService {
     pub fn new(game_directory: PathBuf, metadata_directory: PathBuf) -> Self {
-        // Construct the full path to the mods directory
-        // Path: MarvelRivals\MarvelGame\Marvel\Content\Paks\~mods
-        let mods_directory = game_directory
-            .join("MarvelGame")
-            .join("Marvel")
-            .join("Content")
-            .join("Paks")
-            .join("~mods");
+        let mods_directory = Self::default_mods_directory(&game_directory);
 
         let disabled_mods_directory = metadata_directory.join("disabled-mods");
         let thumbnails_directory = metadata_directory
@@ -37,6 +174,114 @@ impl ModService {
             disabled_mods_directory,
             metadata_directory,
             thumbnails_directory,
+            clock: Arc::new(SystemClock),
+            active_profile_id: None,
+            disable_strategy: DisableStrategy::Move,
+            ignored_paths: IgnoredPaths::default(),
+            max_scan_depth: None,
+            listing: Vec::new(),
+        }
+    }
+
+    /// Where the game loads mods from unless settings point elsewhere:
+    /// MarvelRivals\MarvelGame\Marvel\Content\Paks\~mods
+    pub fn default_mods_directory(game_directory: &Path) -> PathBuf {
+        game_directory
+            .join("MarvelGame")
+            .join("Marvel")
+            .join("Content")
+            .join("Paks")
+            .join("~mods")
+    }
+
+    /// Install into and scan `dir` instead of the game's own ~mods folder;
+    /// None keeps the default
+    pub fn with_mods_directory(mut self, dir: Option<PathBuf>) -> Self {
+        if let Some(dir) = dir {
+            self.mods_directory = dir;
+        }
+        self
+    }
+
+    /// Whether `dir` can stand in for ~mods: it has to exist, be a folder
+    /// and take new files
+    pub fn check_mods_directory(dir: &Path) -> Result<(), String> {
+        if !dir.exists() {
+            return Err(format!("Mod folder does not exist: {}", dir.display()));
+        }
+        if !dir.is_dir() {
+            return Err(format!("Mod folder is not a folder: {}", dir.display()));
+        }
+        let probe = dir.join(".mod-manager-write-test");
+        std::fs::write(&probe, b"")
+            .map_err(|e| format!("Mod folder is not writable: {} ({})", dir.display(), e))?;
+        let _ = std::fs::remove_file(&probe);
+        Ok(())
+    }
+
+    /// Replace the clock used for new timestamps
+    #[cfg(test)]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Add newly installed mods to this profile
+    pub fn with_active_profile(mut self, profile_id: Option<String>) -> Self {
+        self.active_profile_id = profile_id;
+        self
+    }
+
+    /// Disable mods by moving them to disabled-mods or renaming them in place
+    pub fn with_disable_strategy(mut self, strategy: DisableStrategy) -> Self {
+        self.disable_strategy = strategy;
+        self
+    }
+
+    /// Leave folders matching these patterns (relative to ~mods) alone
+    pub fn with_ignored_paths(mut self, patterns: &[String]) -> Self {
+        self.ignored_paths = IgnoredPaths::new(patterns);
+        self
+    }
+
+    /// Only look this many levels below ~mods and disabled-mods when
+    /// scanning and cleaning up. 1 is the root alone; the default layout
+    /// (`Skins/Hulk/Mod/x.pak`) needs 4. 0 is taken as no limit.
+    pub fn with_max_scan_depth(mut self, depth: Option<usize>) -> Self {
+        self.max_scan_depth = depth.filter(|&depth| depth > 0);
+        self
+    }
+
+    /// Look single mods up in `mods`, the scan cache's listing, instead of
+    /// rescanning the library
+    pub fn with_listing(mut self, mods: Vec<ModInfo>) -> Self {
+        self.listing = mods;
+        self
+    }
+
+    /// A walker over `root` that stays within the scan depth limit
+    fn walk(&self, root: &Path) -> WalkDir {
+        let walker = WalkDir::new(root).follow_links(false);
+        match self.max_scan_depth {
+            Some(depth) => walker.max_depth(depth),
+            None => walker,
+        }
+    }
+
+    /// Whether `path`, inside ~mods, is in or is an ignored folder
+    fn is_ignored(&self, path: &Path) -> bool {
+        path.strip_prefix(&self.mods_directory)
+            .is_ok_and(|relative| self.ignored_paths.matches(relative))
+    }
+
+    /// Append the active profile (if any) to a new mod's profile list
+    fn assign_active_profile(&self, metadata: &mut ModMetadata) {
+        let Some(profile_id) = &self.active_profile_id else {
+            return;
+        };
+        let profile_ids = metadata.profile_ids.get_or_insert_with(Vec::new);
+        if !profile_ids.contains(profile_id) {
+            profile_ids.push(profile_id.clone());
         }
     }
 
@@ -44,33 +289,17 @@ impl ModService {
     /// Maps lowercase filename (without extension) -> full path.
     fn build_thumbnail_index(&self) -> HashMap<String, PathBuf> {
         let mut index = HashMap::new();
-        let thumb_extensions: HashSet<&str> =
-            ["webp", "png", "jpg", "jpeg"].iter().copied().collect();
 
-        // Index metadata directory thumbnails (new format: {mod_id}_thumbnail.png)
+        // Thumbnails live in the metadata directory as {mod_id}_thumbnail.png
         if let Ok(entries) = fs::read_dir(&self.metadata_directory) {
             for entry in entries.flatten() {
                 let path = entry.path();
-                if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                    if thumb_extensions.contains(ext.to_lowercase().as_str()) {
-                        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                            index.insert(stem.to_lowercase(), path);
-                        }
-                    }
+                if !has_extension(&path, "png") {
+                    continue;
                 }
-            }
-        }
-
-        // Index thumbnails directory
-        if let Ok(entries) = fs::read_dir(&self.thumbnails_directory) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                    if thumb_extensions.contains(ext.to_lowercase().as_str()) {
-                        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                            // Don't overwrite metadata dir entries (higher priority)
-                            index.entry(stem.to_lowercase()).or_insert(path);
-                        }
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    if stem.ends_with("_thumbnail") {
+                        index.insert(stem.to_lowercase(), path);
                     }
                 }
             }
@@ -84,15 +313,32 @@ impl ModService {
         self.ensure_directory_exists(&self.mods_directory)?;
         self.ensure_directory_exists(&self.disabled_mods_directory)?;
 
+        let started = std::time::Instant::now();
+        let mut profile = ScanProfile {
+            scanned_at: self.clock.now(),
+            max_scan_depth: self.max_scan_depth,
+            files: 0,
+            directories: 0,
+            duration_ms: 0,
+            phases: Vec::new(),
+        };
+
         // Build thumbnail index once instead of per-mod file existence checks
         let thumbnail_index = self.build_thumbnail_index();
+        profile.phases.push(ScanPhase {
+            name: "thumbnails".to_string(),
+            files: thumbnail_index.len(),
+            directories: 1,
+            duration_ms: started.elapsed().as_millis() as u64,
+        });
 
         let mut mods = Vec::new();
         let mut processed_paths = HashSet::new();
         let mut processed_ids = HashSet::new();
 
         // Scan active mods directory
-        self.scan_directory_with_deduplication(
+        let phase_started = std::time::Instant::now();
+        let (files, directories) = self.scan_directory_with_deduplication(
             &self.mods_directory,
             &mut mods,
             &mut processed_paths,
@@ -100,11 +346,18 @@ impl ModService {
             true,
             &thumbnail_index,
         )?;
+        profile.phases.push(ScanPhase {
+            name: "mods".to_string(),
+            files,
+            directories,
+            duration_ms: phase_started.elapsed().as_millis() as u64,
+        });
 
         let active_count = mods.len();
 
         // Scan disabled mods directory
-        self.scan_directory_with_deduplication(
+        let phase_started = std::time::Instant::now();
+        let (files, directories) = self.scan_directory_with_deduplication(
             &self.disabled_mods_directory,
             &mut mods,
             &mut processed_paths,
@@ -112,38 +365,248 @@ impl ModService {
             false,
             &thumbnail_index,
         )?;
+        profile.phases.push(ScanPhase {
+            name: "disabled".to_string(),
+            files,
+            directories,
+            duration_ms: phase_started.elapsed().as_millis() as u64,
+        });
+
+        // Natural title order ("Mod 2" before "Mod 10"); callers re-sort
+        // with their own SortSpec when they have one
+        crate::sorting::sort_mods(
+            &mut mods,
+            &SortSpec {
+                key: SortKey::Title,
+                direction: SortDirection::Asc,
+            },
+        );
 
-        // Sort by name
-        mods.sort_by(|a, b| a.name.cmp(&b.name));
+        profile.files = profile.phases.iter().map(|p| p.files).sum();
+        profile.directories = profile.phases.iter().map(|p| p.directories).sum();
+        profile.duration_ms = started.elapsed().as_millis() as u64;
+        if let Err(e) = self.save_scan_profile(&profile) {
+            log::warn!("[scan] Failed to save scan profile: {}", e);
+        }
+        perf_metrics::record(&self.metrics_path(), "scan", started.elapsed(), mods.len());
 
         Ok(mods)
     }
 
+    /// `scan-profile.json`, beside the metadata folder
+    fn scan_profile_path(&self) -> PathBuf {
+        self.metadata_directory
+            .parent()
+            .map(|p| p.join("scan-profile.json"))
+            .unwrap_or_else(|| self.metadata_directory.join("scan-profile.json"))
+    }
+
+    /// `hash-cache.json`, beside the metadata folder
+    pub fn hash_cache_path(&self) -> PathBuf {
+        self.metadata_directory
+            .parent()
+            .map(|p| p.join(hash_cache::FILE_NAME))
+            .unwrap_or_else(|| self.metadata_directory.join(hash_cache::FILE_NAME))
+    }
+
+    /// `performance-metrics.json`, beside the metadata folder
+    pub fn metrics_path(&self) -> PathBuf {
+        self.metadata_directory
+            .parent()
+            .map(|p| p.join(perf_metrics::FILE_NAME))
+            .unwrap_or_else(|| self.metadata_directory.join(perf_metrics::FILE_NAME))
+    }
+
+    /// Start timing a run of `operation`, recorded with this library's
+    /// metrics when it finishes
+    pub fn timer(&self, operation: &'static str) -> perf_metrics::Timer {
+        perf_metrics::Timer::start(self.metrics_path(), operation)
+    }
+
+    /// SHA-256 of a mod file's contents, read only if it changed since it
+    /// was last hashed. Everything that compares file contents goes through
+    /// here.
+    pub fn content_hash(&self, path: &Path) -> Result<String, String> {
+        hash_cache::get_or_compute(&self.hash_cache_path(), path)
+    }
+
+    /// One hash over all of a mod's files, in extension order so it doesn't
+    /// depend on the order they were found in
+    pub fn files_content_hash(&self, mod_info: &ModInfo) -> Result<String, String> {
+        let mut files = mod_info.associated_files.clone();
+        files.sort_by_key(|path| path.extension().map(|ext| ext.to_ascii_lowercase()));
+        let mut hasher = Sha256::new();
+        for file in &files {
+            hasher.update(self.content_hash(file)?);
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// The pak and companion files of `mods`, what background hashing covers
+    pub fn hashable_files(&self, mods: &[ModInfo]) -> Vec<PathBuf> {
+        mods.iter()
+            .flat_map(|m| &m.associated_files)
+            .filter(|path| {
+                MOD_FILE_EXTENSIONS
+                    .iter()
+                    .any(|ext| has_extension(path, ext))
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub fn clear_hash_cache(&self) -> Result<usize, String> {
+        hash_cache::clear(&self.hash_cache_path())
+    }
+
+    fn save_scan_profile(&self, profile: &ScanProfile) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(profile)
+            .map_err(|e| format!("Failed to serialize scan profile: {}", e))?;
+        fs::write(self.scan_profile_path(), json)
+            .map_err(|e| format!("Failed to write scan profile: {}", e))
+    }
+
+    /// What the last full scan visited and how long each part took, if a
+    /// scan has run
+    pub fn last_scan_profile(&self) -> Result<Option<ScanProfile>, String> {
+        let path = self.scan_profile_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json =
+            fs::read_to_string(&path).map_err(|e| format!("Failed to read scan profile: {}", e))?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| format!("Failed to parse scan profile: {}", e))
+    }
+
+    /// The live `~mods` directory mods are installed into
+    pub fn mods_directory(&self) -> &Path {
+        &self.mods_directory
+    }
+
+    /// Where disabled mods are parked, mirroring their ~mods folder layout
+    pub fn disabled_mods_directory(&self) -> &Path {
+        &self.disabled_mods_directory
+    }
+
+    /// The collections store (`collections.json` beside the metadata folder)
+    pub fn collections(&self) -> CollectionStore {
+        let path = self
+            .metadata_directory
+            .parent()
+            .map(|p| p.join("collections.json"))
+            .unwrap_or_else(|| self.metadata_directory.join("collections.json"));
+        CollectionStore::new(path, self.clock.clone())
+    }
+
+    /// Keep collection memberships pointing at mods whose path-based ID
+    /// changed. Runs alongside every add-on re-pointing pass.
+    fn remap_collection_members(&self, remap: &HashMap<String, String>) {
+        match self.collections().remap_mod_ids(remap) {
+            Ok(0) => {}
+            Ok(count) => log::info!("[collections] Re-pointed {} membership(s)", count),
+            Err(e) => log::warn!("[collections] Failed to re-point memberships: {}", e),
+        }
+    }
+
+    /// Cheap change detector for the mod folders: file count and newest
+    /// mtime per top-level folder of ~mods and disabled-mods. Only walks
+    /// directory entries, never opens files, so it is safe to poll.
+    pub fn directory_fingerprint(&self) -> BTreeMap<String, (usize, u64)> {
+        let mut fingerprint = BTreeMap::new();
+
+        for (label, root) in [
+            ("mods", &self.mods_directory),
+            ("disabled", &self.disabled_mods_directory),
+        ] {
+            for entry in WalkDir::new(root)
+                .follow_links(false)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+            {
+                let top = entry
+                    .path()
+                    .strip_prefix(root)
+                    .ok()
+                    .and_then(|rel| rel.components().next())
+                    .filter(|_| entry.depth() > 1)
+                    .map(|c| c.as_os_str().to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let modified = entry
+                    .metadata()
+                    .ok()
+                    .and_then(|m| m.modified().ok())
+                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+
+                let slot = fingerprint
+                    .entry(format!("{label}/{top}"))
+                    .or_insert((0, 0));
+                slot.0 += 1;
+                slot.1 = slot.1.max(modified);
+            }
+        }
+
+        fingerprint
+    }
+
+    /// Cheaper still, for checking on every cached read: the mtime of each
+    /// folder under ~mods and disabled-mods, which moves whenever a file or
+    /// folder in it is added, removed or renamed. Files are never stat'ed,
+    /// so one overwritten in place is left to the watcher.
+    pub fn folder_fingerprint(&self) -> BTreeMap<PathBuf, Option<std::time::SystemTime>> {
+        [&self.mods_directory, &self.disabled_mods_directory]
+            .into_iter()
+            .flat_map(|root| {
+                WalkDir::new(root)
+                    .follow_links(false)
+                    .into_iter()
+                    .filter_entry(|e| e.file_type().is_dir())
+                    .filter_map(|e| e.ok())
+            })
+            .map(|entry| {
+                let modified = entry.metadata().ok().and_then(|m| m.modified().ok());
+                (entry.into_path(), modified)
+            })
+            .collect()
+    }
+
     /// Install a mod from a file path
-    pub fn install_mod(&self, file_path: &Path) -> Result<ModInfo, String> {
+    pub fn install_mod(&self, file_path: &Path) -> Result<InstallResult, String> {
+        self.install_mod_with(file_path, &InstallCopy::default())
+    }
+
+    /// [`install_mod`](Self::install_mod) reporting its copy through `copy`
+    pub fn install_mod_with(
+        &self,
+        file_path: &Path,
+        copy: &InstallCopy,
+    ) -> Result<InstallResult, String> {
         // Validate file extension
         if !self.is_mod_file(file_path) {
             return Err("Invalid file type. Only .pak files are supported.".to_string());
         }
 
+        self.check_install_space(&[file_path])?;
+
         // Copy file to mods directory
-        let file_name = file_path
+        let mut log = InstallLog::default();
+        let dest_path = self.copy_mod_files(file_path, &self.mods_directory, &mut log, copy)?;
+        let file_name = dest_path
             .file_name()
-            .ok_or("Invalid file path")?
-            .to_str()
+            .and_then(|n| n.to_str())
             .ok_or("Invalid file name")?;
 
-        let dest_path = self.mods_directory.join(file_name);
-
-        fs::copy(file_path, &dest_path).map_err(|e| format!("Failed to copy mod file: {}", e))?;
-
         // Create mod info
-        self.create_mod_info(&dest_path, file_name, true, None)
-            .ok_or_else(|| "Failed to create mod info".to_string())
+        let mod_info = self
+            .create_mod_info(&dest_path, file_name, true, None)
+            .ok_or_else(|| "Failed to create mod info".to_string())?;
+        Ok(log.finish(mod_info))
     }
 
-    /// Install a mod to a specific folder within the mods directory
-    /// This is used for organizing mods from archives into their own folders
     /// Install a mod to a folder with custom metadata (all in one operation)
     pub fn install_mod_to_folder_with_metadata(
         &self,
@@ -151,55 +614,116 @@ impl ModService {
         folder_name: &str,
         metadata: ModMetadata,
     ) -> Result<ModInfo, String> {
+        self.install_to_folder(file_path, folder_name, Some(metadata))
+            .map(|result| result.mod_info)
+    }
+
+    pub fn install_mod_to_folder(
+        &self,
+        file_path: &Path,
+        folder_name: &str,
+    ) -> Result<ModInfo, String> {
+        self.install_to_folder(file_path, folder_name, None)
+            .map(|result| result.mod_info)
+    }
+
+    /// Install a mod to a specific folder within the mods directory, with
+    /// the given metadata or defaults worked out from the file name. This is
+    /// used for organizing mods from archives into their own folders.
+    pub fn install_to_folder(
+        &self,
+        file_path: &Path,
+        folder_name: &str,
+        metadata: Option<ModMetadata>,
+    ) -> Result<InstallResult, String> {
+        self.install_to_folder_with(file_path, folder_name, metadata, &InstallCopy::default())
+    }
+
+    /// [`install_to_folder`](Self::install_to_folder) reporting its copy
+    /// through `copy`
+    pub fn install_to_folder_with(
+        &self,
+        file_path: &Path,
+        folder_name: &str,
+        metadata: Option<ModMetadata>,
+        copy: &InstallCopy,
+    ) -> Result<InstallResult, String> {
         // Validate file extension
         if !self.is_mod_file(file_path) {
             return Err("Invalid file type. Only .pak files are supported.".to_string());
         }
 
-        // Create folder in mods directory. The folder name arrives from the
-        // frontend with '/' separators; join segment-by-segment so the path
-        // uses native separators — a mixed-separator path hashes to a
-        // different mod ID than the scanner computes for the same file.
-        let folder_path = folder_name
-            .split(['/', '\\'])
-            .filter(|part| !part.is_empty())
-            .fold(self.mods_directory.clone(), |path, part| path.join(part));
+        let mut log = InstallLog::default();
+        // Refuse bad metadata before anything is copied
+        let metadata = match metadata {
+            Some(metadata) => {
+                let normalized = validate_metadata(&metadata, self.clock.now())
+                    .map_err(|issues| MetadataUpdateError::Invalid { issues }.to_string())?;
+                log.warnings.extend(
+                    normalized
+                        .warnings
+                        .iter()
+                        .map(|issue| format!("{}: {}", issue.field, issue.message)),
+                );
+                Some(normalized.metadata)
+            }
+            None => None,
+        };
+        self.check_install_space(&[file_path])?;
+        let folder_path = self.install_folder_path(folder_name, &mut log);
+        let new_folder = !folder_path.exists();
         self.ensure_directory_exists(&folder_path)?;
-
-        // Get file name
-        let file_name = file_path
+        let dest_path = self
+            .copy_mod_files(file_path, &folder_path, &mut log, copy)
+            .inspect_err(|_| {
+                // Leaves it alone unless the failed copy left it empty
+                if new_folder {
+                    let _ = fs::remove_dir(&folder_path);
+                }
+            })?;
+        let file_name = dest_path
             .file_name()
-            .ok_or("Invalid file path")?
-            .to_str()
-            .ok_or("Invalid file name")?;
-
-        let dest_path = folder_path.join(file_name);
-
-        // Copy main pak file
-        fs::copy(file_path, &dest_path).map_err(|e| format!("Failed to copy mod file: {}", e))?;
-
-        // Copy associated files (.ucas, .utoc) if they exist
-        let base_name = file_path
-            .file_stem()
-            .and_then(|s| s.to_str())
+            .and_then(|n| n.to_str())
             .ok_or("Invalid file name")?;
 
-        let source_directory = file_path.parent().ok_or("Invalid directory")?;
+        let source_name = file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string());
+        let Some(mut metadata) = metadata else {
+            // Create mod info
+            let mut mod_info = self
+                .create_mod_info(&dest_path, file_name, true, None)
+                .ok_or_else(|| "Failed to create mod info".to_string())?;
+            mod_info.metadata.record_history(
+                self.clock.now(),
+                ModHistoryAction::Installed,
+                source_name,
+            );
 
-        for ext in &[".ucas", ".utoc"] {
-            let companion_source = source_directory.join(format!("{}{}", base_name, ext));
-            if companion_source.exists() {
-                let companion_dest = folder_path.join(format!("{}{}", base_name, ext));
-                fs::copy(&companion_source, &companion_dest)
-                    .map_err(|e| format!("Failed to copy companion file: {}", e))?;
-            }
-        }
+            // CRITICAL FIX: Save the default metadata immediately after installation
+            // This ensures the mod is reliably findable when update_metadata is called shortly after
+            self.save_metadata(&mod_info.id, &mod_info.metadata)?;
+            log::info!(
+                "Saved initial metadata for newly installed mod: {}",
+                mod_info.id
+            );
+            return Ok(log.finish(mod_info));
+        };
 
         // Create mod info with the provided metadata
         let clean_file_name = file_name.replace(".disabled", "");
         let mod_id = self.generate_mod_id_from_path(&dest_path, &clean_file_name);
 
-        // Save the provided metadata immediately
+        // Save the provided metadata immediately. The files are new, so
+        // whatever was known about the previous ones no longer applies.
+        self.assign_active_profile(&mut metadata);
+        metadata.file_fingerprint = None;
+        metadata.externally_modified_at = None;
+        // Installing over a mod carries on its history
+        if let Ok(Some(previous)) = self.load_metadata(&mod_id) {
+            metadata.history = previous.history;
+        }
+        metadata.record_history(self.clock.now(), ModHistoryAction::Installed, source_name);
         self.save_metadata(&mod_id, &metadata)?;
         log::info!("Saved custom metadata for newly installed mod: {}", mod_id);
 
@@ -207,206 +731,336 @@ impl ModService {
         let metadata_fs =
             fs::metadata(&dest_path).map_err(|e| format!("Failed to get file metadata: {}", e))?;
         let associated_files = self.find_associated_files(&dest_path).unwrap_or_default();
-        let thumbnail_path = self.find_thumbnail(&mod_id, &clean_file_name);
+        let associated_file_sizes = file_sizes(&associated_files);
+        self.adopt_preview_image(&mod_id, file_path);
+        let thumbnail_path = self.find_thumbnail(&mod_id);
 
-        Ok(ModInfo {
+        Ok(log.finish(ModInfo {
             id: mod_id.clone(),
             name: metadata.title.clone(),
             category: metadata.category.clone(),
             character: metadata.character.clone(),
             enabled: true,
+            location: ModLocation::Active,
+            duplicate_of: None,
             is_favorite: metadata.is_favorite,
+            disabled_reason: None,
             file_path: dest_path,
             thumbnail_path,
-            file_size: metadata_fs.len(),
+            file_size: total_size(&associated_file_sizes),
+            pak_size: metadata_fs.len(),
+            associated_file_sizes,
             install_date: metadata.install_date,
             last_modified: metadata_fs
                 .modified()
                 .ok()
                 .map(|t| t.into())
-                .unwrap_or(Utc::now()),
+                .unwrap_or_else(|| self.clock.now()),
             original_file_name: clean_file_name,
             associated_files,
             metadata,
-        })
+        }))
     }
 
-    pub fn install_mod_to_folder(
-        &self,
-        file_path: &Path,
-        folder_name: &str,
-    ) -> Result<ModInfo, String> {
-        // Validate file extension
-        if !self.is_mod_file(file_path) {
-            return Err("Invalid file type. Only .pak files are supported.".to_string());
-        }
-
-        // Create folder in mods directory. The folder name arrives from the
-        // frontend with '/' separators; join segment-by-segment so the path
-        // uses native separators — a mixed-separator path hashes to a
-        // different mod ID than the scanner computes for the same file.
-        let folder_path = folder_name
+    /// The folder under ~mods an install should go to. The folder name
+    /// arrives from the frontend with '/' separators; join segment-by-segment
+    /// so the path uses native separators — a mixed-separator path hashes to
+    /// a different mod ID than the scanner computes for the same file.
+    /// Characters Windows won't allow in a folder name are dropped, and noted.
+    fn install_folder_path(&self, folder_name: &str, log: &mut InstallLog) -> PathBuf {
+        folder_name
             .split(['/', '\\'])
             .filter(|part| !part.is_empty())
-            .fold(self.mods_directory.clone(), |path, part| path.join(part));
-        self.ensure_directory_exists(&folder_path)?;
+            .fold(self.mods_directory.clone(), |path, part| {
+                let cleaned = clean_folder_segment(part);
+                if cleaned != part {
+                    log.warnings
+                        .push(format!("Folder \"{part}\" was renamed to \"{cleaned}\""));
+                }
+                if cleaned.is_empty() {
+                    path
+                } else {
+                    path.join(cleaned)
+                }
+            })
+    }
+
+    /// Refuse an install of `pak_files` and their companions that won't fit
+    /// on the mods drive, before anything is written
+    fn check_install_space(&self, pak_files: &[&Path]) -> Result<(), String> {
+        let needed: u64 = pak_files
+            .iter()
+            .flat_map(|pak| std::iter::once(pak.to_path_buf()).chain(find_companions(pak, "")))
+            .map(|file| fs::metadata(file).map(|m| m.len()).unwrap_or(0))
+            .sum();
+        disk_space::ensure_free_space(&self.mods_directory, needed)
+    }
 
-        // Get file name
+    /// Copy a pak and whichever companions sit next to it into
+    /// `folder_path`, recording every file in `log`. Companions are named
+    /// after the pak, whatever case the archive gave their stem, since the
+    /// game pairs them by name. Returns the copied pak.
+    fn copy_mod_files(
+        &self,
+        file_path: &Path,
+        folder_path: &Path,
+        log: &mut InstallLog,
+        copy: &InstallCopy,
+    ) -> Result<PathBuf, String> {
         let file_name = file_path
             .file_name()
             .ok_or("Invalid file path")?
             .to_str()
             .ok_or("Invalid file name")?;
-
-        let dest_path = folder_path.join(file_name);
-
-        // Copy main pak file
-        fs::copy(file_path, &dest_path).map_err(|e| format!("Failed to copy mod file: {}", e))?;
-
-        // Copy associated files (.ucas, .utoc) if they exist
         let base_name = file_path
             .file_stem()
             .and_then(|s| s.to_str())
             .ok_or("Invalid file name")?;
 
-        let source_directory = file_path.parent().ok_or("Invalid directory")?;
-
-        for ext in &[".ucas", ".utoc"] {
-            let companion_source = source_directory.join(format!("{}{}", base_name, ext));
-            if companion_source.exists() {
-                let companion_dest = folder_path.join(format!("{}{}", base_name, ext));
-                fs::copy(&companion_source, &companion_dest)
-                    .map_err(|e| format!("Failed to copy companion file: {}", e))?;
+        let dest_path = folder_path.join(file_name);
+        let mut companions = Vec::new();
+        let mut missing = Vec::new();
+        let found = find_companions(file_path, "");
+        for ext in COMPANION_EXTENSIONS {
+            let companion = format!("{}.{}", base_name, ext);
+            match found.iter().find(|path| has_extension(path, ext)) {
+                Some(source) => companions.push((source.clone(), folder_path.join(&companion))),
+                None if IOSTORE_EXTENSIONS.contains(ext) => missing.push(companion),
+                None => {}
             }
         }
+        // IoStore mods ship both; a plain pak ships neither
+        if missing.len() == 1 {
+            log.warnings.push(format!(
+                "{} is an IoStore mod without {}; it will most likely not load",
+                file_name, missing[0]
+            ));
+            log.missing_companions.append(&mut missing);
+        }
 
-        // Create mod info
-        let mod_info = self
-            .create_mod_info(&dest_path, file_name, true, None)
-            .ok_or_else(|| "Failed to create mod info".to_string())?;
-
-        // CRITICAL FIX: Save the default metadata immediately after installation
-        // This ensures the mod is reliably findable when update_metadata is called shortly after
-        self.save_metadata(&mod_info.id, &mod_info.metadata)?;
-        log::info!(
-            "Saved initial metadata for newly installed mod: {}",
-            mod_info.id
-        );
-
-        Ok(mod_info)
-    }
-
-    /// Organize loose mods into proper folder structure
-    /// Returns the number of mods that were organized
-    pub fn organize_loose_mods(&self) -> Result<usize, String> {
-        log::info!("🔍 Checking for loose mods...");
-        let all_mods = self.get_all_mods()?;
-        let mut organized_count = 0;
+        // Copy main pak file, then the companions, each beside its final
+        // name, so a cancelled install leaves any files it would replace
+        let files: Vec<(PathBuf, PathBuf)> =
+            std::iter::once((file_path.to_path_buf(), dest_path.clone()))
+                .chain(companions)
+                .collect();
+        let total: u64 = files
+            .iter()
+            .map(|(source, _)| fs::metadata(source).map(|m| m.len()).unwrap_or(0))
+            .sum();
+        let mut done = 0u64;
+        let mut staged: Vec<(PathBuf, PathBuf, PathBuf, u64)> = Vec::new();
+        for (index, (source, dest)) in files.into_iter().enumerate() {
+            let file_name = dest
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            let partial = dest.with_file_name(format!("{}.installing", file_name));
+            let copied = fs_ops::copy_verified_chunked(&source, &partial, &copy.cancel, |chunk| {
+                done += chunk;
+                (copy.on_progress)(ModInstallProgress {
+                    current_file: file_name.clone(),
+                    current: done as usize,
+                    total: total as usize,
+                    status: "copying".to_string(),
+                });
+            });
+            let size = match copied {
+                Ok(size) => size,
+                Err(e) => {
+                    for (_, _, partial, _) in &staged {
+                        let _ = fs_ops::remove_file(partial);
+                    }
+                    return Err(if e.to_string() == fs_ops::COPY_CANCELLED {
+                        "Install cancelled".to_string()
+                    } else if index == 0 {
+                        format!("Failed to copy mod file: {}", e)
+                    } else {
+                        format!("Failed to copy companion file: {}", e)
+                    });
+                }
+            };
+            staged.push((source, dest, partial, size));
+        }
 
-        for mod_info in all_mods {
-            if !mod_info.enabled {
-                continue;
-            }
-
-            let file_path = &mod_info.file_path;
-            let parent_dir = file_path.parent().ok_or("Invalid file path")?;
-            let is_loose = parent_dir == self.mods_directory;
-
-            if is_loose {
-                let old_mod_id = mod_info.id.clone();
-
-                // Build folder structure: Category/Character/ModName/ or Category/Character/ModName-Costume/ if costume specified
-                let mut folder_parts = vec![mod_info.category.to_string()];
-
-                // Include character subfolder if character is specified
-                if let Some(ref character) = mod_info.character {
-                    folder_parts.push(sanitize_folder_name(&character.to_string()));
+        // Files moved into place so far, each with what it replaced set
+        // aside, so a failure partway puts the folder back as it was rather
+        // than leaving a pak without its .ucas
+        let mut placed: Vec<(&PathBuf, Option<PathBuf>)> = Vec::new();
+        let logged_before = log.copied_files.len();
+        for (index, (source, dest, partial, size)) in staged.iter().enumerate() {
+            let replaced = dest.exists().then(|| {
+                let name = dest.file_name().unwrap_or_default().to_string_lossy();
+                dest.with_file_name(format!("{}.replaced", name))
+            });
+            let moved = fs_ops::clear_readonly(dest)
+                .and_then(|_| match &replaced {
+                    Some(backup) => fs_ops::rename(dest, backup),
+                    None => Ok(()),
+                })
+                .and_then(|_| fs_ops::rename(partial, dest));
+            if let Err(e) = moved {
+                if let Some(backup) = replaced.as_ref().filter(|backup| backup.exists()) {
+                    let _ = fs_ops::rename(backup, dest);
                 }
-
-                // Include costume in folder name if specified to avoid conflicts
-                // e.g., "Cool-Mod-Phoenix-Demon" instead of just "Cool-Mod"
-                let folder_name = if let Some(ref costume) = mod_info.metadata.costume {
-                    format!("{}-{}", mod_info.name, costume)
-                } else {
-                    mod_info.name.clone()
-                };
-                folder_parts.push(sanitize_folder_name(&folder_name));
-
-                let target_folder = folder_parts
-                    .iter()
-                    .fold(self.mods_directory.clone(), |path, part| path.join(part));
-
-                fs::create_dir_all(&target_folder)
-                    .map_err(|e| format!("Failed to create target directory: {}", e))?;
-
-                // Find the main .pak file for ID generation
-                let pak_file = mod_info
-                    .associated_files
-                    .iter()
-                    .find(|f| f.extension().and_then(|e| e.to_str()) == Some("pak"));
-
-                let mut new_pak_path: Option<PathBuf> = None;
-
-                for associated_file in &mod_info.associated_files {
-                    let file_name = associated_file.file_name().ok_or("Invalid file name")?;
-                    let target_path = target_folder.join(file_name);
-                    fs::rename(associated_file, &target_path)
-                        .map_err(|e| format!("Failed to move file: {}", e))?;
-
-                    // Track the new pak file location
-                    if Some(associated_file) == pak_file {
-                        new_pak_path = Some(target_path);
+                // Don't leave this one or the rest as stray .installing files
+                for (_, _, partial, _) in &staged[index..] {
+                    let _ = fs_ops::remove_file(partial);
+                }
+                for (dest, backup) in placed.iter().rev() {
+                    let _ = fs_ops::remove_file(dest);
+                    if let Some(backup) = backup {
+                        let _ = fs_ops::rename(backup, dest);
                     }
                 }
+                log.copied_files.truncate(logged_before);
+                return Err(format!("Failed to move {:?} into place: {}", dest, e));
+            }
+            if replaced.is_some() {
+                log.warnings.push(format!(
+                    "Replaced the existing {} in {}",
+                    dest.file_name().unwrap_or_default().to_string_lossy(),
+                    folder_path.display()
+                ));
+            }
+            placed.push((dest, replaced));
+            // "Last modified" shows the mod's build date; the install date
+            // is kept separately
+            if let Err(e) = fs::metadata(source)
+                .and_then(|m| m.modified())
+                .and_then(|modified| fs_ops::set_modified(dest, modified))
+            {
+                log::debug!("Failed to keep modification time of {:?}: {}", dest, e);
+            }
+            log.copied_files.push(AssociatedFileSize {
+                path: dest.clone(),
+                size: *size,
+            });
+        }
+        for backup in placed.into_iter().filter_map(|(_, backup)| backup) {
+            let _ = fs_ops::remove_file(&backup);
+        }
+        (copy.on_progress)(ModInstallProgress {
+            current_file: file_name.to_string(),
+            current: done as usize,
+            total: total as usize,
+            status: "done".to_string(),
+        });
+
+        Ok(dest_path)
+    }
 
-                // Migrate metadata to new ID (path changed)
-                if let Some(new_path) = new_pak_path {
-                    let file_name = new_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                    let new_mod_id = self.generate_mod_id_from_path(&new_path, file_name);
+    /// Where `organize_loose_mods` would move each loose mod, without
+    /// touching anything
+    pub fn preview_organize_mods(&self) -> Result<Vec<OrganizeMove>, String> {
+        self.flush_pending_metadata()?;
+        Ok(self
+            .plan_organize()?
+            .into_iter()
+            .map(|(_, planned)| planned)
+            .collect())
+    }
 
-                    if new_mod_id != old_mod_id {
-                        // Save metadata under new ID
-                        if let Err(e) = self.save_metadata(&new_mod_id, &mod_info.metadata) {
-                            log::warn!("Failed to save metadata for organized mod: {}", e);
-                        } else {
-                            log::info!("   📝 Migrated metadata: {} → {}", old_mod_id, new_mod_id);
-                        }
+    /// Enabled mods sitting loose in ~mods, each with the move that files it
+    /// under the folder its metadata implies. Mods whose layout the user
+    /// accepted, or whose folder is ignored, stay loose.
+    fn plan_organize(&self) -> Result<Vec<(ModInfo, OrganizeMove)>, String> {
+        let mut planned = Vec::new();
+        for mod_info in self.get_all_mods()? {
+            if !mod_info.enabled
+                || !mod_info.metadata.manage_layout
+                || self.is_ignored(&mod_info.file_path)
+                || mod_info.file_path.parent() != Some(self.mods_directory.as_path())
+            {
+                continue;
+            }
+            let target_folder = self.expected_folder_for(&mod_info.metadata);
+            // Moving it into an ignored folder would hide it from the library
+            if self.is_ignored(&target_folder) {
+                log::info!(
+                    "   Leaving {} loose, its folder {:?} is ignored",
+                    mod_info.name,
+                    target_folder
+                );
+                continue;
+            }
+            let Some(file_name) = mod_info.file_path.file_name() else {
+                continue;
+            };
+            let organize = OrganizeMove {
+                mod_id: mod_info.id.clone(),
+                title: mod_info.metadata.title.clone(),
+                source_path: mod_info.file_path.clone(),
+                destination_path: target_folder.join(file_name),
+                category: mod_info.metadata.category.clone(),
+                character: mod_info.metadata.character.clone(),
+                confidence: self.organize_confidence(&mod_info),
+            };
+            planned.push((mod_info, organize));
+        }
+        Ok(planned)
+    }
 
-                        // Migrate thumbnail if exists
-                        let old_thumb = self
-                            .metadata_directory
-                            .join(format!("{}_thumbnail.png", old_mod_id));
-                        if old_thumb.exists() {
-                            let new_thumb = self
-                                .metadata_directory
-                                .join(format!("{}_thumbnail.png", new_mod_id));
-                            let _ = fs::copy(&old_thumb, &new_thumb);
-                            let _ = fs::remove_file(&old_thumb);
-                        }
+    /// How far the folder a loose mod is headed for can be trusted: what
+    /// detection makes of its file name when the metadata agrees with it,
+    /// Low when the two disagree on the category or character
+    fn organize_confidence(&self, mod_info: &ModInfo) -> DetectionConfidence {
+        let suggestion = self.preview_mod_metadata(&mod_info.file_path);
+        if suggestion.category != mod_info.metadata.category
+            || suggestion.character != mod_info.metadata.character
+        {
+            return DetectionConfidence::Low;
+        }
+        suggestion
+            .character_confidence
+            .map_or(suggestion.category_confidence, |character| {
+                character.min(suggestion.category_confidence)
+            })
+    }
 
-                        // Re-point any add-ons that referenced the old parent ID.
-                        // Without this, auto-organizing a loose parent on startup
-                        // changes its path-based ID and orphans its add-ons.
-                        if let Err(e) = self.migrate_addon_parent_ids(&old_mod_id, &new_mod_id) {
-                            log::warn!(
-                                "Failed to migrate addon parent IDs for organized mod: {}",
-                                e
-                            );
-                        }
+    /// Organize loose mods into proper folder structure. With `approved`,
+    /// only those of the previewed moves are made, and only if the mod is
+    /// still headed for the same place; a mod that fails to move is
+    /// reported in `errors` and the rest carry on.
+    pub fn organize_loose_mods(
+        &self,
+        approved: Option<&[OrganizeMove]>,
+    ) -> Result<OrganizationResult, String> {
+        let started = std::time::Instant::now();
+        self.flush_pending_metadata()?;
+        log::info!("🔍 Checking for loose mods...");
+        let mut planned = self.plan_organize()?;
+        if let Some(approved) = approved {
+            planned.retain(|(_, organize)| {
+                approved.iter().any(|a| {
+                    a.mod_id == organize.mod_id && a.destination_path == organize.destination_path
+                })
+            });
+        }
 
-                        // Delete old metadata
-                        let _ = self.delete_metadata(&old_mod_id);
-                    }
+        let mut result = OrganizationResult {
+            total_mods: planned.len(),
+            moved_mods: 0,
+            error_count: 0,
+            errors: Vec::new(),
+            duration: 0,
+        };
+        for (mod_info, organize) in planned {
+            match self.organize_mod(&mod_info, &organize.destination_path) {
+                Ok(()) => result.moved_mods += 1,
+                Err(e) => {
+                    log::warn!("   Failed to organize {}: {}", organize.title, e);
+                    result.errors.push(format!("{}: {}", organize.title, e));
                 }
-
-                organized_count += 1;
             }
         }
+        result.error_count = result.errors.len();
 
-        if organized_count > 0 {
+        if result.moved_mods > 0 {
             log::info!(
                 "   ✅ Organized {} loose mod(s) into folders",
-                organized_count
+                result.moved_mods
             );
 
             // Clean up any empty folders after organizing
@@ -415,24 +1069,162 @@ impl ModService {
                     log::info!("   ✅ Cleaned up {} empty folder(s)", cleaned);
                 }
             }
-        } else {
+        } else if result.error_count == 0 {
             log::info!("   ✅ All mods already organized");
         }
 
-        Ok(organized_count)
+        result.duration = started.elapsed().as_millis() as u64;
+        Ok(result)
     }
 
-    /// Merge duplicate character folders (e.g., "Black Widow" and "Black-Widow")
-    /// This happens when folder naming inconsistencies occur
-    /// Returns the number of folders merged
-    pub fn merge_duplicate_folders(&self) -> Result<usize, String> {
-        use std::collections::HashMap;
+    /// Move one loose mod's files beside `new_pak_path` and carry its
+    /// metadata, thumbnail and references over to the new ID
+    fn organize_mod(&self, mod_info: &ModInfo, new_pak_path: &Path) -> Result<(), String> {
+        let _scope = fs_ops::mod_scope(&mod_info.id);
+        let target_folder = new_pak_path.parent().ok_or("Invalid file path")?;
+        fs::create_dir_all(target_folder)
+            .map_err(|e| format!("Failed to create target directory: {}", e))?;
 
-        log::info!("🔍 Checking for duplicate folders...");
-        let mut merged_count = 0;
-        // old mod ID -> new mod ID for every pak whose path (and so ID) changes,
-        // used to re-point add-ons at the end
-        let mut id_remap: HashMap<String, String> = HashMap::new();
+        let targets = mod_info
+            .associated_files
+            .iter()
+            .map(|file| {
+                file.file_name()
+                    .map(|name| target_folder.join(name))
+                    .ok_or_else(|| "Invalid file name".to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        rename_all(&mod_info.associated_files, &targets)?;
+
+        // Path-based ID changed with the folder
+        self.migrate_mod_identity(mod_info, new_pak_path, &mod_info.metadata)?;
+        Ok(())
+    }
+
+    /// Where a mod with this metadata belongs:
+    /// Category/Character/Title, or Category/Character/Title-Costume when a
+    /// costume is specified, to avoid conflicts between mods for different
+    /// costumes ("Cool-Mod-Phoenix-Demon" instead of just "Cool-Mod").
+    pub fn expected_folder_for(&self, metadata: &ModMetadata) -> PathBuf {
+        // All categories include character subfolder when character is specified.
+        // The load priority goes on the category, the top folder, since the
+        // game mounts by full path.
+        let category = sanitize_folder_name(&metadata.category.to_string());
+        let mut folder_parts = vec![match metadata.priority {
+            Some(priority) => format!("{:03}_{}", priority, category),
+            None => category,
+        }];
+        if let Some(ref character) = metadata.character {
+            folder_parts.push(sanitize_folder_name(&character.to_string()));
+        }
+
+        let folder_name = if let Some(ref costume) = metadata.costume {
+            format!("{}-{}", metadata.title, costume)
+        } else {
+            metadata.title.clone()
+        };
+        let sanitized_folder = sanitize_folder_name(&folder_name);
+        // Add-ons get an "aa-" prefix so they mount BEFORE their parent pak.
+        // For these body retexture conflicts the first mounted pak wins, so
+        // the add-on must load ahead of the parent it overrides.
+        let sanitized_folder = if metadata.parent_mod_id.is_some() {
+            format!("aa-{sanitized_folder}")
+        } else {
+            sanitized_folder
+        };
+        folder_parts.push(sanitized_folder);
+        folder_parts
+            .iter()
+            .fold(self.mods_directory.clone(), |path, part| path.join(part))
+    }
+
+    /// Enabled mods in a folder other than the one their metadata implies.
+    /// Loose mods aren't listed, `organize_loose_mods` files them at
+    /// startup; neither are mods whose layout the user has accepted.
+    pub fn get_layout_drift(&self) -> Result<Vec<LayoutDrift>, String> {
+        Ok(self
+            .drifted_mods()?
+            .into_iter()
+            .filter(|(mod_info, _)| mod_info.metadata.manage_layout)
+            .filter_map(|(mod_info, expected_folder)| {
+                Some(LayoutDrift {
+                    current_folder: mod_info.file_path.parent()?.to_path_buf(),
+                    expected_folder,
+                    mod_id: mod_info.id,
+                    name: mod_info.name,
+                })
+            })
+            .collect())
+    }
+
+    /// Enabled mods in a folder of their own that isn't their expected one,
+    /// with that expected folder, whether or not their layout is managed
+    fn drifted_mods(&self) -> Result<Vec<(ModInfo, PathBuf)>, String> {
+        self.flush_pending_metadata()?;
+        let mut drifted = Vec::new();
+        for mod_info in self.get_all_mods()? {
+            let Some(current_folder) = mod_info.file_path.parent() else {
+                continue;
+            };
+            let expected_folder = self.expected_folder_for(&mod_info.metadata);
+            if !mod_info.enabled
+                || current_folder == self.mods_directory
+                || current_folder == expected_folder
+            {
+                continue;
+            }
+            drifted.push((mod_info, expected_folder));
+        }
+        Ok(drifted)
+    }
+
+    /// Move the given mods to the folder their metadata implies, the same way
+    /// a metadata edit would. Mods whose layout was accepted are managed
+    /// again. Returns the mods that were moved.
+    pub fn apply_layout(&self, mod_ids: &[String]) -> Result<Vec<ToggledMod>, String> {
+        let mut moved = Vec::new();
+        for (mod_info, _) in self.drifted_mods()? {
+            if !mod_ids.contains(&mod_info.id) {
+                continue;
+            }
+            let _lock = lock_metadata();
+            let mut metadata = self
+                .load_metadata(&mod_info.id)?
+                .unwrap_or(mod_info.metadata);
+            metadata.manage_layout = true;
+            // Unchanged metadata would otherwise count as a light change
+            match self.write_metadata_with(&mod_info.id, metadata, true, None) {
+                Ok(moved_mod) => moved.push(ToggledMod {
+                    previous_id: mod_info.id,
+                    mod_info: moved_mod,
+                }),
+                Err(e) => log::warn!("[layout] Failed to move {}: {}", mod_info.id, e),
+            }
+        }
+        log::info!("[layout] Moved {} of {} mod(s)", moved.len(), mod_ids.len());
+        Ok(moved)
+    }
+
+    /// Keep the given mods where they are: metadata edits stop moving them
+    /// and they no longer count as drift. Returns how many were changed.
+    pub fn accept_layout(&self, mod_ids: &[String]) -> Result<usize, String> {
+        let mut patch = serde_json::Map::new();
+        patch.insert("manageLayout".to_string(), false.into());
+        let mut accepted = 0;
+        for mod_id in mod_ids {
+            match self.patch_metadata(mod_id, &patch) {
+                Ok(_) => accepted += 1,
+                Err(e) => log::warn!("[layout] Failed to accept layout of {}: {}", mod_id, e),
+            }
+        }
+        Ok(accepted)
+    }
+
+    /// Character folders that differ only in spaces, hyphens or case ("Black
+    /// Widow" and "Black-Widow"), grouped within each category. The folder
+    /// the rest merge into comes first: the hyphenated one, our standard.
+    pub fn find_duplicate_folders(&self) -> Result<Vec<Vec<PathBuf>>, String> {
+        let mut groups = Vec::new();
 
         // Scan category folders (Skins, UI, Audio, Gameplay)
         for category_entry in fs::read_dir(&self.mods_directory)
@@ -442,7 +1234,7 @@ impl ModService {
                 category_entry.map_err(|e| format!("Failed to read category entry: {}", e))?;
             let category_path = category_entry.path();
 
-            if !category_path.is_dir() {
+            if !category_path.is_dir() || self.is_ignored(&category_path) {
                 continue;
             }
 
@@ -457,7 +1249,7 @@ impl ModService {
                     char_entry.map_err(|e| format!("Failed to read character entry: {}", e))?;
                 let char_path = char_entry.path();
 
-                if !char_path.is_dir() {
+                if !char_path.is_dir() || self.is_ignored(&char_path) {
                     continue;
                 }
 
@@ -471,125 +1263,126 @@ impl ModService {
                 }
             }
 
-            // Merge duplicate folders WITHIN this category only
-            for (_normalized_name, paths) in character_folders {
-                if paths.len() > 1 {
-                    // Keep the folder with the hyphenated name (our standard)
-                    let target_folder = paths
-                        .iter()
-                        .find(|p| {
-                            p.file_name()
-                                .and_then(|n| n.to_str())
-                                .map(|s| s.contains('-'))
-                                .unwrap_or(false)
-                        })
-                        .or_else(|| paths.first())
-                        .ok_or_else(|| "No valid target folder found".to_string())?;
+            for (_normalized_name, mut paths) in character_folders {
+                if paths.len() < 2 {
+                    continue;
+                }
+                // Keep the folder with the hyphenated name (our standard)
+                if let Some(index) = paths.iter().position(|p| {
+                    p.file_name()
+                        .and_then(|n| n.to_str())
+                        .map(|s| s.contains('-'))
+                        .unwrap_or(false)
+                }) {
+                    paths.swap(0, index);
+                }
+                groups.push(paths);
+            }
+        }
 
-                    // Merge other folders into the target
-                    for source_folder in paths.iter() {
-                        if source_folder == target_folder {
-                            continue;
-                        }
+        Ok(groups)
+    }
 
-                        log::info!(
-                            "   🔄 Merging {:?} → {:?}",
-                            source_folder.file_name(),
-                            target_folder.file_name()
-                        );
+    /// Merge duplicate character folders (e.g., "Black Widow" and "Black-Widow")
+    /// This happens when folder naming inconsistencies occur
+    /// Mod folders found on both sides are compared file by file: identical
+    /// copies are dropped, differing ones are both kept and reported
+    pub fn merge_duplicate_folders(&self) -> Result<FolderMergeReport, String> {
+        use std::collections::HashMap;
 
-                        // Move all mods from source to target
-                        for entry in fs::read_dir(source_folder)
-                            .map_err(|e| format!("Failed to read source folder: {}", e))?
-                        {
-                            let entry =
-                                entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-                            let source_mod_folder = entry.path();
-
-                            if source_mod_folder.is_dir() {
-                                if let Some(mod_name) = source_mod_folder.file_name() {
-                                    let target_mod_folder = target_folder.join(mod_name);
-
-                                    if target_mod_folder.exists() {
-                                        log::warn!(
-                                            "      ⚠️  Target already exists, skipping: {:?}",
-                                            mod_name
-                                        );
-                                        continue;
-                                    }
+        self.flush_pending_metadata()?;
+        log::info!("🔍 Checking for duplicate folders...");
+        let mut report = FolderMergeReport::default();
+        // old mod ID -> new mod ID for every pak whose path (and so ID) changes,
+        // used to re-point add-ons at the end
+        let mut id_remap: HashMap<String, String> = HashMap::new();
 
-                                    // Move the mod folder
-                                    fs::rename(&source_mod_folder, &target_mod_folder)
-                                        .map_err(|e| format!("Failed to move mod folder: {}", e))?;
-
-                                    // Migrate metadata for EVERY .pak that moved — the
-                                    // folder can also hold add-on paks, and skipping
-                                    // them orphans their metadata (detached add-ons)
-                                    for pak_entry in WalkDir::new(&target_mod_folder)
-                                        .into_iter()
-                                        .filter_map(|e| e.ok())
-                                    {
-                                        let pak_path = pak_entry.path().to_path_buf();
-                                        if pak_path.extension().and_then(|e| e.to_str())
-                                            != Some("pak")
-                                        {
-                                            continue;
-                                        }
-                                        let file_name = pak_path
-                                            .file_name()
-                                            .and_then(|n| n.to_str())
-                                            .unwrap_or("");
-
-                                        // Reconstruct where this pak lived before the move
-                                        let old_pak_path =
-                                            match pak_path.strip_prefix(&target_mod_folder) {
-                                                Ok(rel) => source_mod_folder.join(rel),
-                                                Err(_) => source_mod_folder.join(file_name),
-                                            };
-                                        let old_id = self
-                                            .generate_mod_id_from_path(&old_pak_path, file_name);
-                                        let new_id =
-                                            self.generate_mod_id_from_path(&pak_path, file_name);
-                                        if old_id == new_id {
-                                            continue;
-                                        }
-
-                                        // Migrate metadata if it exists
-                                        if let Ok(Some(metadata)) = self.load_metadata(&old_id) {
-                                            log::info!(
-                                                "      📝 Migrating metadata: {} → {}",
-                                                old_id,
-                                                new_id
-                                            );
-                                            let _ = self.save_metadata(&new_id, &metadata);
-
-                                            // Copy thumbnail if exists
-                                            let old_thumb = self
-                                                .metadata_directory
-                                                .join(format!("{}_thumbnail.png", old_id));
-                                            if old_thumb.exists() {
-                                                let new_thumb = self
-                                                    .metadata_directory
-                                                    .join(format!("{}_thumbnail.png", new_id));
-                                                let _ = fs::copy(&old_thumb, &new_thumb);
-                                            }
-
-                                            // Delete old metadata
-                                            let _ = self.delete_metadata(&old_id);
-                                        }
-
-                                        // Record for the add-on re-pointing pass below
-                                        id_remap.insert(old_id, new_id);
-                                    }
-                                }
-                            }
-                        }
+        for paths in self.find_duplicate_folders()? {
+            let target_folder = &paths[0];
+            for source_folder in &paths[1..] {
+                log::info!(
+                    "   🔄 Merging {:?} → {:?}",
+                    source_folder.file_name(),
+                    target_folder.file_name()
+                );
 
-                        // Delete the now-empty source folder
-                        self.delete_directory_with_retry(source_folder, 3)?;
-                        merged_count += 1;
+                // Move all mods from source to target
+                for entry in fs::read_dir(source_folder)
+                    .map_err(|e| format!("Failed to read source folder: {}", e))?
+                {
+                    let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+                    let source_mod_folder = entry.path();
+                    if !source_mod_folder.is_dir() {
+                        continue;
                     }
+                    let Some(mod_name) = source_mod_folder.file_name() else {
+                        continue;
+                    };
+                    let existing = target_folder.join(mod_name);
+
+                    // A same-named folder is either the same mod twice
+                    // or two versions of it; never let one silently win
+                    let (destination, action, collided_with) = if !existing.exists() {
+                        (existing, FolderMergeAction::Moved, None)
+                    } else if mod_folders_identical(&source_mod_folder, &existing, |path| {
+                        self.content_hash(path)
+                    }) {
+                        log::info!(
+                            "      🗑️  Identical copy already in target, removing {:?}",
+                            mod_name
+                        );
+                        self.remove_duplicate_mod_folder(
+                            &source_mod_folder,
+                            &existing,
+                            &mut id_remap,
+                        )?;
+                        report.actions.push(FolderMergeEntry {
+                            source: source_mod_folder,
+                            destination: existing,
+                            action: FolderMergeAction::DuplicateRemoved,
+                            collided_with: None,
+                        });
+                        continue;
+                    } else {
+                        let renamed = free_sibling_path(&existing);
+                        log::warn!(
+                            "      ⚠️  {:?} differs from the copy in the target, keeping both as {:?}",
+                            mod_name,
+                            renamed.file_name()
+                        );
+                        (renamed, FolderMergeAction::KeptBoth, Some(existing))
+                    };
+
+                    fs_ops::rename(&source_mod_folder, &destination)
+                        .map_err(|e| format!("Failed to move mod folder: {}", e))?;
+                    // Migrates EVERY .pak that moved — the folder can
+                    // also hold add-on paks, and skipping them orphans
+                    // their metadata (detached add-ons)
+                    self.migrate_moved_folder_metadata(
+                        &source_mod_folder,
+                        &destination,
+                        &mut id_remap,
+                    );
+                    report.actions.push(FolderMergeEntry {
+                        source: source_mod_folder,
+                        destination,
+                        action,
+                        collided_with,
+                    });
+                }
+
+                // Delete the source folder only if everything moved out.
+                // Skipped collisions stay behind rather than being
+                // deleted along with the folder.
+                if !is_folder_empty(source_folder)? {
+                    log::warn!(
+                        "      ⚠️  {:?} still has mods that could not be merged, keeping it",
+                        source_folder.file_name()
+                    );
+                    continue;
                 }
+                fs_ops::remove_dir_all_retry(source_folder, 3)?;
+                report.merged_folders += 1;
             }
         }
 
@@ -604,60 +1397,123 @@ impl ModService {
             }
         }
 
-        if merged_count > 0 {
+        if report.merged_folders > 0 {
             log::info!(
                 "   ✅ Merged {} duplicate character folder(s)",
-                merged_count
+                report.merged_folders
             );
         } else {
             log::info!("   ✅ No duplicate folders found");
         }
 
-        Ok(merged_count)
+        Ok(report)
     }
 
-    /// Clean up empty mod folders (but keep category and character folders)
-    /// Returns the number of empty folders removed
-    pub fn cleanup_empty_mod_folders(&self) -> Result<usize, String> {
-        let mut removed_count = 0;
-        let mut folders_to_remove = Vec::new();
+    /// Delete `source`, a mod folder with the same files as `kept`. Its
+    /// metadata moves over where `kept` has none of its own.
+    fn remove_duplicate_mod_folder(
+        &self,
+        source: &Path,
+        kept: &Path,
+        id_remap: &mut HashMap<String, String>,
+    ) -> Result<(), String> {
+        for pak_entry in WalkDir::new(source).into_iter().filter_map(|e| e.ok()) {
+            let pak_path = pak_entry.path();
+            if !has_extension(pak_path, "pak") {
+                continue;
+            }
+            let Ok(relative) = pak_path.strip_prefix(source) else {
+                continue;
+            };
+            let file_name = pak_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let old_id = self.generate_mod_id_from_path(pak_path, file_name);
+            let new_id = self.generate_mod_id_from_path(&kept.join(relative), file_name);
+            self.move_metadata_to_id(&old_id, &new_id)?;
+            id_remap.insert(old_id, new_id);
+        }
+        fs_ops::remove_dir_all_retry(source, 3)
+    }
+
+    /// Empty mod folders, children before parents. Category and character
+    /// folders don't count even when empty.
+    pub fn find_empty_mod_folders(&self) -> Result<Vec<PathBuf>, String> {
+        let mut empty_folders = Vec::new();
 
         // Collect all directories to check
-        for entry in WalkDir::new(&self.mods_directory)
+        for entry in self
+            .walk(&self.mods_directory)
             .min_depth(1) // Skip the ~mods directory itself
             .into_iter()
+            .filter_entry(|e| !self.is_ignored(e.path()))
             .filter_map(|e| e.ok())
             .filter(|e| e.file_type().is_dir())
         {
             let path = entry.path();
-
-            // Skip if it's a direct child of ~mods (category folder)
-            if path.parent() == Some(self.mods_directory.as_path()) {
+            if self.is_kept_folder(path) {
                 continue;
             }
 
-            // Check if folder name matches a known character (keep character folders)
-            if let Some(folder_name) = path.file_name().and_then(|n| n.to_str()) {
-                let is_character_folder = Character::all_characters().iter().any(|character| {
-                    let sanitized_char = sanitize_folder_name(&character.to_string());
-                    sanitized_char.eq_ignore_ascii_case(folder_name)
-                });
-
-                if is_character_folder {
-                    continue;
-                }
-            }
-
             // Check if folder is empty (no files, only empty subdirectories)
             if is_folder_empty(path)? {
-                folders_to_remove.push(path.to_path_buf());
+                empty_folders.push(path.to_path_buf());
             }
         }
 
-        // Remove empty folders (in reverse order to remove children before parents)
-        folders_to_remove.sort_by(|a, b| b.cmp(a));
-        for folder in folders_to_remove {
-            match fs::remove_dir_all(&folder) {
+        // Reverse order, so children are removed before their parents
+        empty_folders.sort_by(|a, b| b.cmp(a));
+        Ok(empty_folders)
+    }
+
+    /// Category folders (direct children of ~mods) and character folders,
+    /// which stay even when empty
+    fn is_kept_folder(&self, path: &Path) -> bool {
+        if path.parent() == Some(self.mods_directory.as_path()) {
+            return true;
+        }
+        let Some(folder_name) = path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        Character::all_characters().iter().any(|character| {
+            sanitize_folder_name(&character.to_string()).eq_ignore_ascii_case(folder_name)
+        })
+    }
+
+    /// Folders under ~mods that would hold no files once `removed` are gone,
+    /// children before parents. Kept folders never count.
+    fn folders_emptied_by(&self, removed: &HashSet<PathBuf>) -> Vec<PathBuf> {
+        let mut candidates = BTreeSet::new();
+        for file in removed {
+            for dir in file.ancestors().skip(1) {
+                if !dir.starts_with(&self.mods_directory)
+                    || dir == self.mods_directory
+                    || self.is_kept_folder(dir)
+                    || self.is_ignored(dir)
+                {
+                    break;
+                }
+                candidates.insert(dir.to_path_buf());
+            }
+        }
+        let mut emptied: Vec<PathBuf> = candidates
+            .into_iter()
+            .filter(|dir| {
+                WalkDir::new(dir)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|e| !e.file_type().is_dir())
+                    .all(|e| removed.contains(e.path()))
+            })
+            .collect();
+        emptied.reverse();
+        emptied
+    }
+
+    /// Clean up empty mod folders (but keep category and character folders)
+    /// Returns the number of empty folders removed
+    pub fn cleanup_empty_mod_folders(&self) -> Result<usize, String> {
+        let mut removed_count = 0;
+        for folder in self.find_empty_mod_folders()? {
+            match fs_ops::remove_dir_all_retry(&folder, 1) {
                 Ok(_) => {
                     removed_count += 1;
                 }
@@ -671,61 +1527,298 @@ impl ModService {
     }
 
     /// Enable or disable a mod
-    /// Preserves metadata and folder structure across enable/disable operations
-    pub fn enable_mod(&self, mod_id: &str, enabled: bool) -> Result<(), String> {
+    /// Preserves metadata and folder structure across enable/disable operations.
+    /// Returns the mod as it is after the move: the path-based ID, `file_path`
+    /// and `associated_files` all change, so callers should replace their copy.
+    pub fn enable_mod(&self, mod_id: &str, enabled: bool) -> Result<ModInfo, String> {
+        self.flush_pending_metadata()?;
+        let mod_info = self.find_listed_mod(mod_id)?.ok_or("Mod not found")?;
         // Single-mod path: re-point add-ons inline (scans metadata once).
-        self.enable_mod_inner(mod_id, enabled, true).map(|_| ())
+        let (pak_path, _) =
+            self.enable_mod_inner(mod_info, enabled, &DisabledReason::UserAction, true)?;
+        self.mod_info_at(&pak_path, enabled)
     }
 
-    /// Core enable/disable. When `migrate_addons` is true, any add-ons pointing
-    /// at this mod's old ID are re-pointed inline (one full metadata scan). Bulk
-    /// callers pass `false` and instead re-point all add-ons in a single pass
-    /// afterward, avoiding an O(mods²) scan. Returns the mod's new ID if it
-    /// changed (the path-based ID changes whenever the file moves).
-    fn enable_mod_inner(
-        &self,
-        mod_id: &str,
-        enabled: bool,
-        migrate_addons: bool,
-    ) -> Result<Option<String>, String> {
-        log::info!(
-            "[enable_mod] {} mod: {}",
-            if enabled { "Enabling" } else { "Disabling" },
-            mod_id
-        );
+    /// Enable a mod and disable the other enabled mods of its variant
+    /// group, since two variants of a skin loaded together break it. A mod
+    /// outside any group is just enabled.
+    pub fn enable_variant(&self, mod_id: &str) -> Result<ModInfo, String> {
+        self.flush_pending_metadata()?;
+        let all_mods = self.get_all_mods()?;
+        let mod_info = all_mods
+            .iter()
+            .find(|m| m.id == mod_id)
+            .cloned()
+            .ok_or("Mod not found")?;
+        if let Some(group) = mod_info.metadata.variant_group.as_deref() {
+            let reason = DisabledReason::ConflictResolution {
+                with: vec![mod_info.metadata.title.clone()],
+            };
+            for other in all_mods.iter().filter(|m| {
+                m.id != mod_id && m.enabled && m.metadata.variant_group.as_deref() == Some(group)
+            }) {
+                log::info!("[variants] Disabling {} for {}", other.name, mod_info.name);
+                self.enable_mod_inner(other.clone(), false, &reason, true)?;
+            }
+        }
+        if mod_info.enabled {
+            return self.mod_info_at(&mod_info.file_path, true);
+        }
+        let (pak_path, _) =
+            self.enable_mod_inner(mod_info, true, &DisabledReason::UserAction, true)?;
+        self.mod_info_at(&pak_path, true)
+    }
 
-        // Step 1: Get current mod info and load existing metadata
-        let mod_info = self.find_mod_by_id(mod_id)?.ok_or("Mod not found")?;
+    /// Paks sharing a folder whose names start the same way, likely
+    /// variants from one archive, for the user to confirm with
+    /// `group_mods_as_variants`. Mods already in a group, add-ons and their
+    /// parents are left out; loose paks in ~mods never group.
+    pub fn detect_variant_groups(&self) -> Result<Vec<VariantGroup>, String> {
+        let mods = self.get_all_mods()?;
+        let parents: HashSet<&str> = mods
+            .iter()
+            .filter_map(|m| m.metadata.parent_mod_id.as_deref())
+            .collect();
 
-        // Load metadata with fallback - create from mod_info if not found
-        // This ensures we never lose metadata and the operation can proceed
-        let mut metadata = match self.load_metadata(mod_id)? {
-            Some(m) => m,
-            None => {
-                log::warn!(
-                    "[enable_mod] Metadata not found for {}, creating from mod_info",
-                    mod_id
-                );
-                // Create metadata from the mod_info we already have
-                let mut new_metadata = mod_info.metadata.clone();
+        // Keyed by the folder under either root, so a disabled variant still
+        // meets its enabled siblings
+        let mut by_folder: BTreeMap<PathBuf, Vec<(&str, &ModInfo)>> = BTreeMap::new();
+        for mod_info in &mods {
+            if mod_info.duplicate_of.is_some()
+                || mod_info.metadata.variant_group.is_some()
+                || mod_info.metadata.parent_mod_id.is_some()
+                || parents.contains(mod_info.id.as_str())
+            {
+                continue;
+            }
+            let Some(stem) = mod_info.file_path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let Some(relative) = mod_info.file_path.parent().and_then(|folder| {
+                folder
+                    .strip_prefix(&self.mods_directory)
+                    .or_else(|_| folder.strip_prefix(&self.disabled_mods_directory))
+                    .ok()
+            }) else {
+                continue;
+            };
+            if relative.as_os_str().is_empty() {
+                continue;
+            }
+            by_folder
+                .entry(relative.to_path_buf())
+                .or_default()
+                .push((split_priority_prefix(stem).1, mod_info));
+        }
 
-                // Ensure original_folder_path is captured from current location
-                let current_file_path = PathBuf::from(&mod_info.file_path);
-                if let Some(current_parent) = current_file_path.parent() {
-                    // Try to extract relative path from either mods or disabled-mods directory
-                    if let Ok(relative) = current_parent.strip_prefix(&self.mods_directory) {
-                        new_metadata.original_folder_path =
-                            Some(relative.to_string_lossy().to_string());
-                    } else if let Ok(relative) =
-                        current_parent.strip_prefix(&self.disabled_mods_directory)
-                    {
-                        new_metadata.original_folder_path =
-                            Some(relative.to_string_lossy().to_string());
-                    }
+        let mut groups = Vec::new();
+        for (_, mut paks) in by_folder {
+            paks.sort_by_key(|(stem, _)| stem.to_lowercase());
+            // Sorted, so a name sharing a long start with the first of a
+            // run shares it with the whole run
+            let mut runs: Vec<Vec<(&str, &ModInfo)>> = Vec::new();
+            for pak in paks {
+                match runs.last_mut() {
+                    Some(run) if variant_prefix(run[0].0, pak.0).is_some() => run.push(pak),
+                    _ => runs.push(vec![pak]),
                 }
-
-                // Save the newly created metadata immediately
-                self.save_metadata(mod_id, &new_metadata)?;
+            }
+            for run in runs.into_iter().filter(|run| run.len() > 1) {
+                let (first_stem, first) = run[0];
+                let shared = variant_prefix(first_stem, run[run.len() - 1].0).unwrap_or(first_stem);
+                let folder = first.file_path.parent().unwrap_or(&first.file_path);
+                groups.push(VariantGroup {
+                    name: shared.replace(['_', '-'], " ").trim().to_string(),
+                    folder: folder.to_path_buf(),
+                    mod_ids: run.iter().map(|(_, m)| m.id.clone()).collect(),
+                });
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Put `mod_ids` in the variant group `group_name`, or take them out of
+    /// their group when it's missing or blank. Returns the updated mods.
+    pub fn group_mods_as_variants(
+        &self,
+        mod_ids: &[String],
+        group_name: Option<&str>,
+    ) -> Result<Vec<ModInfo>, String> {
+        let group = group_name.map(str::trim).filter(|name| !name.is_empty());
+        let mut patch = serde_json::Map::new();
+        patch.insert("variantGroup".to_string(), group.into());
+        mod_ids
+            .iter()
+            .map(|mod_id| self.patch_metadata(mod_id, &patch))
+            .collect()
+    }
+
+    /// The library as a list to share, in `format`: every mod, or only the
+    /// enabled ones unless `include_disabled`
+    pub fn export_list(
+        &self,
+        format: ModListFormat,
+        include_disabled: bool,
+    ) -> Result<String, String> {
+        let mods: Vec<ModListEntry> = self
+            .get_all_mods()?
+            .iter()
+            // A copy disabled in place is listed once, by its twin
+            .filter(|m| m.duplicate_of.is_none() && (include_disabled || m.enabled))
+            .map(ModListEntry::from_mod)
+            .collect();
+        match format {
+            ModListFormat::Json => serde_json::to_string_pretty(&ModListExport {
+                exported_at: self.clock.now(),
+                mods,
+            })
+            .map_err(|e| format!("Failed to serialize mod list: {}", e)),
+            ModListFormat::Csv => Ok(mod_list_csv(&mods)),
+            ModListFormat::Markdown => Ok(mod_list_markdown(&mods)),
+        }
+    }
+
+    /// Set a list from `export_list` (as JSON) against the library: which of
+    /// its mods are installed, installed but disabled, or missing. Entries
+    /// are matched on Nexus mod ID, then title and character, then file
+    /// name, each installed mod to one entry at most. With `apply` the
+    /// disabled matches are enabled.
+    pub fn import_list(&self, json: &str, apply: bool) -> Result<ModListImportReport, String> {
+        let list: ModListExport =
+            serde_json::from_str(json).map_err(|e| format!("Not a mod list: {}", e))?;
+        let mods: Vec<ModInfo> = self
+            .get_all_mods()?
+            .into_iter()
+            .filter(|m| m.duplicate_of.is_none())
+            .collect();
+
+        let mut claimed: HashSet<&str> = HashSet::new();
+        let mut report = ModListImportReport::default();
+        for entry in list.mods {
+            let found = [
+                ModListMatchKind::NexusModId,
+                ModListMatchKind::TitleAndCharacter,
+                ModListMatchKind::FileName,
+            ]
+            .into_iter()
+            .find_map(|kind| {
+                mods.iter()
+                    .find(|m| {
+                        !claimed.contains(m.id.as_str()) && list_entry_matches(&entry, m, kind)
+                    })
+                    .map(|m| (m, kind))
+            });
+            match found {
+                Some((mod_info, matched_by)) => {
+                    claimed.insert(&mod_info.id);
+                    let matched = ModListMatch {
+                        entry,
+                        mod_id: mod_info.id.clone(),
+                        matched_by,
+                    };
+                    if mod_info.enabled {
+                        report.installed.push(matched);
+                    } else {
+                        report.disabled.push(matched);
+                    }
+                }
+                None => report.missing.push(MissingListedMod {
+                    nexus_url: entry.nexus_mod_id.map(nexus_mod_url),
+                    entry,
+                }),
+            }
+        }
+
+        if apply {
+            for matched in &report.disabled {
+                match self.enable_mod(&matched.mod_id, true) {
+                    Ok(mod_info) => report.enabled.push(mod_info.id),
+                    Err(e) => report
+                        .errors
+                        .push(format!("{}: {}", matched.entry.title, e)),
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Build a fresh ModInfo for a pak that was just moved
+    fn mod_info_at(&self, pak_path: &Path, enabled: bool) -> Result<ModInfo, String> {
+        let file_name = pak_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or("Invalid file name")?;
+        self.create_mod_info(pak_path, file_name, enabled, None)
+            .ok_or_else(|| format!("Failed to read mod at {:?}", pak_path))
+    }
+
+    /// Re-read a listed mod after its metadata changed, without a scan.
+    /// None when its pak is gone or now reads as a different mod.
+    pub fn reload_mod(&self, mod_info: &ModInfo) -> Option<ModInfo> {
+        let fresh = self
+            .mod_info_at(&mod_info.file_path, mod_info.enabled)
+            .ok()
+            .filter(|fresh| fresh.id == mod_info.id)?;
+        Some(ModInfo {
+            location: mod_info.location,
+            ..fresh
+        })
+    }
+
+    /// Core enable/disable. Disabling records `reason` in the metadata,
+    /// enabling clears it. When `migrate_addons` is true, any add-ons pointing
+    /// at this mod's old ID are re-pointed inline (one full metadata scan). Bulk
+    /// callers pass `false` and instead re-point all add-ons in a single pass
+    /// afterward, avoiding an O(mods²) scan. Returns the moved .pak path and
+    /// the mod's new ID if it changed (the path-based ID changes whenever the
+    /// file moves).
+    fn enable_mod_inner(
+        &self,
+        mod_info: ModInfo,
+        enabled: bool,
+        reason: &DisabledReason,
+        migrate_addons: bool,
+    ) -> Result<(PathBuf, Option<String>), String> {
+        let id = mod_info.id.clone();
+        let mod_id = id.as_str();
+        log::info!(
+            "[enable_mod] {} mod: {}",
+            if enabled { "Enabling" } else { "Disabling" },
+            mod_id
+        );
+        let _scope = fs_ops::mod_scope(mod_id);
+
+        // Step 1: Load existing metadata
+
+        // Load metadata with fallback - create from mod_info if not found
+        // This ensures we never lose metadata and the operation can proceed
+        let mut metadata = match self.load_metadata(mod_id)? {
+            Some(m) => m,
+            None => {
+                log::warn!(
+                    "[enable_mod] Metadata not found for {}, creating from mod_info",
+                    mod_id
+                );
+                // Create metadata from the mod_info we already have
+                let mut new_metadata = mod_info.metadata.clone();
+
+                // Ensure original_folder_path is captured from current location
+                let current_file_path = PathBuf::from(&mod_info.file_path);
+                if let Some(current_parent) = current_file_path.parent() {
+                    // Try to extract relative path from either mods or disabled-mods directory
+                    if let Ok(relative) = current_parent.strip_prefix(&self.mods_directory) {
+                        new_metadata.original_folder_path =
+                            Some(relative.to_string_lossy().to_string());
+                    } else if let Ok(relative) =
+                        current_parent.strip_prefix(&self.disabled_mods_directory)
+                    {
+                        new_metadata.original_folder_path =
+                            Some(relative.to_string_lossy().to_string());
+                    }
+                }
+
+                // Save the newly created metadata immediately
+                self.save_metadata(mod_id, &new_metadata)?;
                 log::info!(
                     "[enable_mod] Created and saved fallback metadata for {}",
                     mod_id
@@ -734,6 +1827,17 @@ impl ModService {
             }
         };
 
+        metadata.disabled_reason = (!enabled).then(|| reason.clone());
+        metadata.disabled_at = (!enabled).then(|| self.clock.now());
+        if enabled {
+            metadata.record_history(self.clock.now(), ModHistoryAction::Enabled, None);
+        } else {
+            // A plain toggle needs no explaining; safe mode, profiles and
+            // the like say which of them it was
+            let detail = (*reason != DisabledReason::UserAction).then(|| reason.kind().to_string());
+            metadata.record_history(self.clock.now(), ModHistoryAction::Disabled, detail);
+        }
+
         log::info!("[enable_mod] Current location: {:?}", mod_info.file_path);
         log::info!(
             "[enable_mod] Original folder path in metadata: {:?}",
@@ -775,63 +1879,66 @@ impl ModService {
             }
         }
 
-        // Step 3: Calculate destination path
-        let dest_folder = if enabled {
-            // Enabling: restore to original folder or organize by category/character
-            if let Some(ref original_path) = metadata.original_folder_path {
-                // Restore to original folder structure
-                self.mods_directory.join(original_path)
-            } else {
-                // No original path stored - organize by category, character, and costume
-                let mut folder_parts = vec![metadata.category.to_string()];
-                if let Some(ref character) = metadata.character {
-                    folder_parts.push(sanitize_folder_name(&character.to_string()));
-                }
-                // Include costume in folder name if specified to avoid conflicts
-                let folder_name = if let Some(ref costume) = metadata.costume {
-                    format!("{}-{}", metadata.title, costume)
-                } else {
-                    metadata.title.clone()
-                };
-                folder_parts.push(sanitize_folder_name(&folder_name));
-
-                let organized_path = folder_parts.join("/");
-                self.mods_directory.join(organized_path)
-            }
+        // Step 3: Rename in place, or move to the destination folder.
+        // Renaming keeps the folder, so the path-based ID stays the same.
+        let in_place = if enabled {
+            has_extension(&mod_info.file_path, "pakdisabled")
         } else {
-            // Disabling: move to disabled-mods preserving structure
-            if let Some(ref original_path) = metadata.original_folder_path {
-                // Preserve folder structure in disabled-mods
-                self.disabled_mods_directory.join(original_path)
+            self.disable_strategy == DisableStrategy::RenameInPlace
+        };
+        let new_file_paths = if in_place {
+            log::info!("[enable_mod] Renaming files in place");
+            rename_in_place(&mod_info.associated_files, !enabled)?
+        } else {
+            // Step 4: Create destination folder and move files
+            let dest_root = if enabled {
+                &self.mods_directory
             } else {
-                // Move to root of disabled-mods
-                self.disabled_mods_directory.clone()
+                &self.disabled_mods_directory
+            };
+            let (dest_folder, namespaced) = self.collision_free_destination(
+                mod_id,
+                &mod_info.associated_files,
+                self.toggle_destination(&metadata, enabled),
+            )?;
+            if namespaced && metadata.original_folder_path.is_none() {
+                // Remember where it was, so it goes back there and not into
+                // the subfolder
+                metadata.original_folder_path =
+                    [&self.mods_directory, &self.disabled_mods_directory]
+                        .iter()
+                        .find_map(|root| current_parent.strip_prefix(root).ok())
+                        .map(|relative| relative.to_string_lossy().to_string());
             }
-        };
-
-        log::info!("[enable_mod] Destination folder: {:?}", dest_folder);
+            metadata.collision_folder = dest_folder
+                .strip_prefix(dest_root)
+                .ok()
+                .filter(|_| namespaced)
+                .map(|folder| folder.to_string_lossy().to_string());
+            log::info!("[enable_mod] Destination folder: {:?}", dest_folder);
 
-        // Step 4: Create destination folder and move files
-        fs::create_dir_all(&dest_folder)
-            .map_err(|e| format!("Failed to create destination folder: {}", e))?;
+            fs::create_dir_all(&dest_folder)
+                .map_err(|e| format!("Failed to create destination folder: {}", e))?;
 
-        let mut new_file_paths = Vec::new();
-        for file_path in &mod_info.associated_files {
-            let file_name = file_path.file_name().ok_or("Invalid file path")?;
+            let mut new_file_paths = Vec::new();
+            for file_path in &mod_info.associated_files {
+                let file_name = file_path.file_name().ok_or("Invalid file path")?;
 
-            let dest_path = dest_folder.join(file_name);
+                let dest_path = dest_folder.join(file_name);
 
-            fs::rename(file_path, &dest_path).map_err(|e| format!("Failed to move file: {}", e))?;
+                fs_ops::move_file(file_path, &dest_path)?;
 
-            new_file_paths.push(dest_path);
-        }
+                new_file_paths.push(dest_path);
+            }
 
-        log::info!("[enable_mod] Moved {} files", new_file_paths.len());
+            log::info!("[enable_mod] Moved {} files", new_file_paths.len());
+            new_file_paths
+        };
 
         // Step 5: Calculate new mod ID from new path
         let new_main_file = new_file_paths
             .iter()
-            .find(|p| p.extension().and_then(|e| e.to_str()) == Some("pak"))
+            .find(|p| has_extension(p, "pak") || has_extension(p, "pakdisabled"))
             .ok_or("No .pak file found in moved files")?;
 
         let file_name = new_main_file
@@ -839,7 +1946,7 @@ impl ModService {
             .and_then(|n| n.to_str())
             .ok_or("Invalid file name")?;
 
-        let clean_file_name = file_name.replace(".disabled", "");
+        let clean_file_name = strip_disabled_marker(file_name);
         let new_mod_id = self.generate_mod_id_from_path(new_main_file, &clean_file_name);
 
         log::info!("[enable_mod] Old mod ID: {}", mod_id);
@@ -858,14 +1965,13 @@ impl ModService {
                 let new_thumb_path = self
                     .metadata_directory
                     .join(format!("{}_thumbnail.png", new_mod_id));
-                if let Err(e) = fs::copy(&old_thumb_path, &new_thumb_path) {
+                if let Err(e) = fs_ops::move_file(&old_thumb_path, &new_thumb_path) {
                     log::warn!("[enable_mod] Failed to migrate thumbnail: {}", e);
                 } else {
                     log::info!("[enable_mod] Migrated thumbnail to new ID");
-                    // Delete old thumbnail
-                    let _ = fs::remove_file(&old_thumb_path);
                 }
             }
+            self.migrate_thumbnail_source(mod_id, &new_mod_id);
 
             // Step 8: Re-point any add-ons that referenced the old parent ID.
             // Enabling/disabling moves the parent between ~mods and disabled-mods,
@@ -885,7 +1991,7 @@ impl ModService {
                 "[enable_mod] Successfully {} mod",
                 if enabled { "enabled" } else { "disabled" }
             );
-            Ok(Some(new_mod_id))
+            Ok((new_main_file.clone(), Some(new_mod_id)))
         } else {
             // Same ID - just update metadata in place
             self.save_metadata(mod_id, &metadata)?;
@@ -893,13 +1999,95 @@ impl ModService {
                 "[enable_mod] Successfully {} mod",
                 if enabled { "enabled" } else { "disabled" }
             );
-            Ok(None)
+            Ok((new_main_file.clone(), None))
+        }
+    }
+
+    /// `dest_folder`, or a subfolder of it named after the mod when one of
+    /// `files` would land on a same-named file with other content: another
+    /// mod shipping e.g. the same `Mod_P.pak`, whose file the move would
+    /// overwrite. Identical files are the same mod and are replaced. The
+    /// flag says whether the subfolder is used.
+    fn collision_free_destination(
+        &self,
+        mod_id: &str,
+        files: &[PathBuf],
+        dest_folder: PathBuf,
+    ) -> Result<(PathBuf, bool), String> {
+        let collides = |folder: &Path| {
+            files.iter().any(|file| {
+                let Some(name) = file.file_name() else {
+                    return false;
+                };
+                let dest = folder.join(name);
+                dest.exists()
+                    && dest != *file
+                    && match (self.content_hash(&dest), self.content_hash(file)) {
+                        (Ok(existing), Ok(moving)) => existing != moving,
+                        _ => true,
+                    }
+            })
+        };
+        if !collides(&dest_folder) {
+            return Ok((dest_folder, false));
+        }
+        let namespaced = dest_folder.join(mod_id);
+        if collides(&namespaced) {
+            return Err(format!(
+                "{:?} already holds other files with the same names",
+                namespaced
+            ));
+        }
+        log::warn!(
+            "[enable_mod] Another mod's files of the same name are in {:?}, using {:?}",
+            dest_folder,
+            namespaced
+        );
+        Ok((namespaced, true))
+    }
+
+    /// Folder a mod's files move to: on enabling its original folder, or
+    /// one organized by category, character and title; on disabling the
+    /// same layout inside disabled-mods
+    fn toggle_destination(&self, metadata: &ModMetadata, enabled: bool) -> PathBuf {
+        // Hand-edited metadata can hold anything; a stored path that would
+        // leave the mod folders is ignored rather than followed
+        let original_path = metadata.original_folder_path.as_deref().and_then(|path| {
+            let contained = contained_folder(path);
+            if contained.is_none() {
+                log::warn!(
+                    "[enable_mod] Ignoring original folder {:?} outside the mod folders",
+                    path
+                );
+            }
+            contained
+        });
+        if enabled {
+            // Enabling: restore to original folder or organize by category/character
+            if let Some(original_path) = original_path {
+                // Restore to original folder structure
+                self.mods_directory.join(original_path)
+            } else {
+                // No original path stored - where organizing would put it,
+                // load priority and add-on prefix included
+                self.expected_folder_for(metadata)
+            }
+        } else {
+            // Disabling: move to disabled-mods preserving structure
+            if let Some(original_path) = original_path {
+                // Preserve folder structure in disabled-mods
+                self.disabled_mods_directory.join(original_path)
+            } else {
+                // Move to root of disabled-mods
+                self.disabled_mods_directory.clone()
+            }
         }
     }
 
     /// Enable/disable many mods, re-pointing all add-ons in a single metadata
-    /// pass at the end instead of once per mod. Returns the number of mods
-    /// successfully toggled. Failures are logged and skipped.
+    /// pass at the end instead of once per mod. Returns the refreshed info for
+    /// every mod successfully toggled, keyed by the ID it had before. Failures
+    /// are logged and skipped.
     ///
     /// `on_progress(done, total)` is invoked after each mod so callers can drive
     /// a real progress bar / ETA. It runs on the same thread as the loop.
@@ -907,24 +2095,77 @@ impl ModService {
         &self,
         mod_ids: &[String],
         enabled: bool,
+        on_progress: F,
+    ) -> Result<Vec<ToggledMod>, String>
+    where
+        F: FnMut(usize, usize),
+    {
+        self.set_mods_enabled_for(mod_ids, enabled, &DisabledReason::UserAction, on_progress)
+    }
+
+    /// [`set_mods_enabled`](Self::set_mods_enabled) for code paths that
+    /// disable mods on the user's behalf, recording why
+    pub fn set_mods_enabled_for<F>(
+        &self,
+        mod_ids: &[String],
+        enabled: bool,
+        reason: &DisabledReason,
+        on_progress: F,
+    ) -> Result<Vec<ToggledMod>, String>
+    where
+        F: FnMut(usize, usize),
+    {
+        Ok(self
+            .enable_mods_bulk(mod_ids, enabled, reason, on_progress)?
+            .toggled)
+    }
+
+    /// [`set_mods_enabled_for`](Self::set_mods_enabled_for) that also reports
+    /// every mod it couldn't toggle, and why. The library is scanned once up
+    /// front rather than once per mod; moving one mod doesn't change another's
+    /// path-based ID.
+    pub fn enable_mods_bulk<F>(
+        &self,
+        mod_ids: &[String],
+        enabled: bool,
+        reason: &DisabledReason,
         mut on_progress: F,
-    ) -> Result<usize, String>
+    ) -> Result<BulkToggleReport, String>
     where
         F: FnMut(usize, usize),
     {
-        let mut ok = 0;
+        self.flush_pending_metadata()?;
         let total = mod_ids.len();
+        let mut report = BulkToggleReport::default();
+        let mut moved: Vec<(String, PathBuf)> = Vec::new();
         // old parent ID -> new parent ID, for every mod whose ID changed.
         let mut remap: HashMap<String, String> = HashMap::new();
+        // The first copy of a mod found twice, as `find_mod_by_id` would
+        let mut by_id: HashMap<String, ModInfo> = HashMap::new();
+        for mod_info in self.get_all_mods()? {
+            by_id.entry(mod_info.id.clone()).or_insert(mod_info);
+        }
 
         for (i, mod_id) in mod_ids.iter().enumerate() {
-            match self.enable_mod_inner(mod_id, enabled, false) {
-                Ok(Some(new_id)) => {
-                    remap.insert(mod_id.clone(), new_id);
-                    ok += 1;
+            let result = by_id
+                .remove(mod_id)
+                .ok_or_else(|| "Mod not found".to_string())
+                .and_then(|mod_info| self.enable_mod_inner(mod_info, enabled, reason, false));
+            match result {
+                Ok((pak_path, new_id)) => {
+                    // An unchanged ID has no add-on impact
+                    if let Some(new_id) = new_id {
+                        remap.insert(mod_id.clone(), new_id);
+                    }
+                    moved.push((mod_id.clone(), pak_path));
+                }
+                Err(e) => {
+                    log::warn!("[set_mods_enabled] Failed to toggle {}: {}", mod_id, e);
+                    report.failed.push(ToggleFailure {
+                        mod_id: mod_id.clone(),
+                        reason: e,
+                    });
                 }
-                Ok(None) => ok += 1, // toggled but ID unchanged (no add-on impact)
-                Err(e) => log::warn!("[set_mods_enabled] Failed to toggle {}: {}", mod_id, e),
             }
             on_progress(i + 1, total);
         }
@@ -936,7 +2177,125 @@ impl ModService {
             }
         }
 
-        Ok(ok)
+        // Read back after the remap so toggled add-ons carry their new parent ID
+        for (previous_id, pak_path) in moved {
+            match self.mod_info_at(&pak_path, enabled) {
+                Ok(mod_info) => report.toggled.push(ToggledMod {
+                    previous_id,
+                    mod_info,
+                }),
+                Err(e) => {
+                    log::warn!("[set_mods_enabled] {}", e);
+                    report.failed.push(ToggleFailure {
+                        mod_id: previous_id,
+                        reason: e,
+                    });
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Problems found on disk that explain why earlier operations on a mod
+    /// may have failed: mods with read-only files, and metadata files edited
+    /// by hand that don't parse or don't pass validation
+    pub fn scan_warnings(&self) -> Result<Vec<ScanWarning>, String> {
+        let mut warnings = Vec::new();
+        for m in self.get_all_mods()? {
+            let paths: Vec<PathBuf> = m
+                .associated_files
+                .iter()
+                .filter(|path| is_readonly(path))
+                .cloned()
+                .collect();
+            if !paths.is_empty() {
+                warnings.push(ScanWarning {
+                    message: format!(
+                        "{} has {} read-only file(s); the app clears the attribute \
+                         before deleting or overwriting them",
+                        m.name,
+                        paths.len()
+                    ),
+                    mod_id: m.id.clone(),
+                    kind: ScanWarningKind::ReadOnly,
+                    paths,
+                });
+            }
+            if let Some(problem) = self.metadata_file_problem(&m.id) {
+                warnings.push(ScanWarning {
+                    message: format!("Metadata for {}: {}", m.name, problem),
+                    paths: vec![self.metadata_directory.join(format!("{}.json", m.id))],
+                    mod_id: m.id,
+                    kind: ScanWarningKind::InvalidMetadata,
+                });
+            }
+        }
+        if !warnings.is_empty() {
+            log::warn!("[scan] {} warning(s) about mods on disk", warnings.len());
+        }
+        Ok(warnings)
+    }
+
+    /// What is wrong with a mod's metadata file as it is on disk: it doesn't
+    /// parse (the scan shows defaults instead), or validation refuses or
+    /// would adjust it
+    fn metadata_file_problem(&self, mod_id: &str) -> Option<String> {
+        let path = self.metadata_directory.join(format!("{}.json", mod_id));
+        let json = fs::read_to_string(path).ok()?;
+        let metadata = match serde_json::from_str::<ModMetadata>(&json) {
+            Ok(metadata) => metadata,
+            Err(e) => return Some(format!("can't be read ({}); showing defaults", e)),
+        };
+        match validate_metadata(&metadata, self.clock.now()) {
+            Ok(normalized) if normalized.warnings.is_empty() => None,
+            Ok(normalized) => Some(describe_issues(&normalized.warnings)),
+            Err(issues) => Some(describe_issues(&issues)),
+        }
+    }
+
+    /// What enabling `to_enable` and disabling `to_disable` would move. Mods
+    /// already in the requested state, or not found, are left out, the same
+    /// as when the batch is applied.
+    pub fn preview_toggle(
+        &self,
+        to_enable: &[String],
+        to_disable: &[String],
+    ) -> Result<TogglePreview, String> {
+        let mods = self.get_all_mods()?;
+        // Mods that exist and aren't in the requested state yet
+        let pending =
+            |id: &String, enabled: bool| mods.iter().find(|m| &m.id == id && m.enabled != enabled);
+        let enabling: Vec<&ModInfo> = to_enable
+            .iter()
+            .filter_map(|id| pending(id, true))
+            .collect();
+        let disabling: Vec<&ModInfo> = to_disable
+            .iter()
+            .filter_map(|id| pending(id, false))
+            .collect();
+
+        let cross_volume = (!enabling.is_empty() || !disabling.is_empty())
+            && !same_volume(&self.mods_directory, &self.disabled_mods_directory);
+        let peak_temporary_bytes = if cross_volume {
+            enabling
+                .iter()
+                .chain(&disabling)
+                .flat_map(|m| m.associated_file_sizes.iter().map(|f| f.size))
+                .max()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
+        Ok(TogglePreview {
+            enable_count: enabling.len(),
+            disable_count: disabling.len(),
+            bytes_to_enable: enabling.iter().map(|m| m.file_size).sum(),
+            bytes_to_disable: disabling.iter().map(|m| m.file_size).sum(),
+            cross_volume,
+            peak_temporary_bytes,
+        })
     }
 
     /// Re-point add-ons for many parents at once. Scans the metadata directory a
@@ -975,18 +2334,12 @@ impl ModService {
         }
 
         log::info!("[remap_addon_parent_ids] Scanned {} metadata files, re-pointed {} add-on(s) across {} moved parent(s)", scanned, migrated, remap.len());
+        self.remap_collection_members(remap);
         Ok(migrated)
     }
 
-    /// One-time repair for metadata orphaned by folder renames/merges that
-    /// happened before those moves re-pointed add-ons (pre-6.2.0). Mod IDs are
-    /// hashes of the full pak path, so for every current pak we hash
-    /// space/hyphen folder-name variants of its path; if a variant ID matches
-    /// an orphaned metadata file, that metadata belongs to this pak and is
-    /// migrated. Safe by construction: a hash match proves the old path.
-    /// Returns the number of metadata files recovered.
-    pub fn recover_orphaned_metadata(&self) -> Result<usize, String> {
-        // Collect every current pak
+    /// Every pak in the mods and disabled folders, enabled or not
+    fn all_pak_paths(&self) -> Vec<PathBuf> {
         let mut pak_paths: Vec<PathBuf> = Vec::new();
         for root in [&self.mods_directory, &self.disabled_mods_directory] {
             if !root.exists() {
@@ -999,16 +2352,21 @@ impl ModService {
                 }
             }
         }
+        pak_paths
+    }
 
-        let current_ids: HashSet<String> = pak_paths
+    fn pak_ids(&self, pak_paths: &[PathBuf]) -> HashSet<String> {
+        pak_paths
             .iter()
             .map(|p| {
                 let file_name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
                 self.generate_mod_id_from_path(p, file_name)
             })
-            .collect();
+            .collect()
+    }
 
-        // Metadata files whose mod no longer exists at the hashed path
+    /// Metadata files whose mod no longer exists at the hashed path
+    fn orphaned_metadata_ids(&self, current_ids: &HashSet<String>) -> HashSet<String> {
         let mut orphaned: HashSet<String> = HashSet::new();
         if let Ok(entries) = fs::read_dir(&self.metadata_directory) {
             for entry in entries.flatten() {
@@ -1025,17 +2383,14 @@ impl ModService {
                 }
             }
         }
+        orphaned
+    }
 
-        if !orphaned.is_empty() {
-            log::info!(
-                "[recover] {} orphaned metadata file(s), matching against current paks…",
-                orphaned.len()
-            );
-        }
-
-        // ID a pak would have had under a space/hyphen folder variant -> current ID
+    /// ID each pak would have had under a space/hyphen variant of its folder
+    /// names -> its current ID
+    fn path_variant_remap(&self, pak_paths: &[PathBuf]) -> HashMap<String, String> {
         let mut remap: HashMap<String, String> = HashMap::new();
-        for pak_path in &pak_paths {
+        for pak_path in pak_paths {
             let file_name = pak_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
             let current_id = self.generate_mod_id_from_path(pak_path, file_name);
             for variant in path_segment_variants(pak_path) {
@@ -1045,6 +2400,46 @@ impl ModService {
                 }
             }
         }
+        remap
+    }
+
+    /// Orphaned metadata `recover_orphaned_metadata` would give back to a
+    /// current pak, as (old ID, current ID) pairs sorted by old ID. Add-ons
+    /// it would reattach by title aren't included.
+    pub fn find_recoverable_metadata(&self) -> Result<Vec<(String, String)>, String> {
+        self.flush_pending_metadata()?;
+        let pak_paths = self.all_pak_paths();
+        let current_ids = self.pak_ids(&pak_paths);
+        let remap = self.path_variant_remap(&pak_paths);
+        let mut recoverable: Vec<(String, String)> = self
+            .orphaned_metadata_ids(&current_ids)
+            .into_iter()
+            .filter_map(|old_id| remap.get(&old_id).map(|new_id| (old_id, new_id.clone())))
+            .collect();
+        recoverable.sort();
+        Ok(recoverable)
+    }
+
+    /// One-time repair for metadata orphaned by folder renames/merges that
+    /// happened before those moves re-pointed add-ons (pre-6.2.0). Mod IDs are
+    /// hashes of the full pak path, so for every current pak we hash
+    /// space/hyphen folder-name variants of its path; if a variant ID matches
+    /// an orphaned metadata file, that metadata belongs to this pak and is
+    /// migrated. Safe by construction: a hash match proves the old path.
+    /// Returns the number of metadata files recovered.
+    pub fn recover_orphaned_metadata(&self) -> Result<usize, String> {
+        self.flush_pending_metadata()?;
+        let pak_paths = self.all_pak_paths();
+        let current_ids = self.pak_ids(&pak_paths);
+
+        let orphaned = self.orphaned_metadata_ids(&current_ids);
+        if !orphaned.is_empty() {
+            log::info!(
+                "[recover] {} orphaned metadata file(s), matching against current paks…",
+                orphaned.len()
+            );
+        }
+        let remap = self.path_variant_remap(&pak_paths);
 
         // Migrate orphaned metadata whose old ID matches a variant
         let mut recovered = 0;
@@ -1063,7 +2458,7 @@ impl ModService {
                     let new_thumb = self
                         .metadata_directory
                         .join(format!("{}_thumbnail.png", new_id));
-                    let _ = fs::copy(&old_thumb, &new_thumb);
+                    let _ = fs_ops::copy_verified(&old_thumb, &new_thumb);
                 }
 
                 let _ = self.delete_metadata(old_id);
@@ -1188,2275 +2583,8874 @@ impl ModService {
         Ok(recovered + repointed + reattached)
     }
 
-    /// Delete a mod
-    pub fn delete_mod(&self, mod_id: &str) -> Result<(), String> {
-        let mod_info = self.find_mod_by_id(mod_id)?.ok_or("Mod not found")?;
+    /// Find mods whose pak and companions are split between ~mods and
+    /// disabled-mods. The game crashes on a pak without its .ucas/.utoc.
+    pub fn detect_split_mods(&self) -> Result<Vec<SplitMod>, String> {
+        let enabled = mod_files_by_stem(&self.mods_directory);
+        let disabled = mod_files_by_stem(&self.disabled_mods_directory);
+        let is_pak = |path: &&PathBuf| has_extension(path, "pak");
 
-        // Delete all associated files
-        for file_path in &mod_info.associated_files {
-            fs::remove_file(file_path).map_err(|e| format!("Failed to delete file: {}", e))?;
-        }
+        let mut splits = Vec::new();
+        for (stem, enabled_files) in &enabled {
+            let Some(disabled_files) = disabled.get(stem) else {
+                continue;
+            };
+            let enabled_paks: Vec<&PathBuf> = enabled_files.iter().filter(is_pak).collect();
+            let disabled_paks: Vec<&PathBuf> = disabled_files.iter().filter(is_pak).collect();
 
-        // Delete metadata
-        self.delete_metadata(mod_id)?;
+            // A pak on each side is two copies of the mod, and companions
+            // without any pak aren't a mod; neither is a split
+            let (pak_path, pak_enabled) = match (enabled_paks.as_slice(), disabled_paks.as_slice())
+            {
+                ([pak], []) => ((*pak).clone(), true),
+                ([], [pak]) => ((*pak).clone(), false),
+                _ => continue,
+            };
+            let (pak_side, companion_side) = if pak_enabled {
+                (enabled_files, disabled_files)
+            } else {
+                (disabled_files, enabled_files)
+            };
 
-        // Delete thumbnail if exists
-        if let Some(thumbnail_path) = mod_info.thumbnail_path {
-            let _ = fs::remove_file(thumbnail_path);
-        }
+            let mut companion_names = HashSet::new();
+            let companions_together = companion_side
+                .iter()
+                .all(|f| f.parent() == companion_side[0].parent())
+                && companion_side
+                    .iter()
+                    .all(|f| companion_names.insert(f.file_name()));
 
-        // Clean up any empty folders after deleting
-        if let Ok(cleaned) = self.cleanup_empty_mod_folders() {
-            if cleaned > 0 {
-                log::info!("Cleaned up {} empty folder(s) after deletion", cleaned);
-            }
+            let file_name = pak_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            let mod_id = self.generate_mod_id_from_path(&pak_path, file_name);
+            splits.push(SplitMod {
+                mod_id,
+                name: stem.clone(),
+                pak_path,
+                pak_enabled,
+                enabled_files: enabled_files.clone(),
+                disabled_files: disabled_files.clone(),
+                auto_repairable: pak_side.len() == 1 && companions_together,
+            });
         }
 
-        Ok(())
+        splits.sort_by(|a, b| a.name.cmp(&b.name));
+        if !splits.is_empty() {
+            log::warn!(
+                "[split] Found {} mod(s) split between folders",
+                splits.len()
+            );
+        }
+        Ok(splits)
     }
 
-    /// Delete many mods in one pass. A single scan resolves every id (per-id
-    /// delete_mod would rescan the library once per mod), individual failures
-    /// are logged and skipped, and empty folders are swept once at the end.
-    /// Returns how many mods were fully deleted.
-    pub fn delete_mods(&self, mod_ids: &[String]) -> Result<usize, String> {
-        let all_mods = self.get_all_mods()?;
-        let wanted: HashSet<&str> = mod_ids.iter().map(|s| s.as_str()).collect();
-        let mut deleted = 0;
+    /// Reunite a split mod's files on the chosen side. Files move next to
+    /// the pak if it is already there, otherwise next to its companions.
+    pub fn repair_split_mod(&self, mod_id: &str, prefer: SplitSide) -> Result<ModInfo, String> {
+        self.flush_pending_metadata()?;
+        let split = self
+            .detect_split_mods()?
+            .into_iter()
+            .find(|s| s.mod_id == mod_id)
+            .ok_or_else(|| format!("Mod {} is not split between folders", mod_id))?;
+        self.reunite_split_mod(&split, prefer)
+    }
 
-        for mod_info in all_mods.into_iter().filter(|m| wanted.contains(m.id.as_str())) {
-            let mut failed = false;
-            for file_path in &mod_info.associated_files {
-                if let Err(e) = fs::remove_file(file_path) {
-                    log::warn!("[bulk-delete] Failed to delete {:?}: {}", file_path, e);
-                    failed = true;
-                }
-            }
-            let _ = self.delete_metadata(&mod_info.id);
-            if let Some(thumbnail_path) = mod_info.thumbnail_path {
-                let _ = fs::remove_file(thumbnail_path);
+    /// Settle a mod listed twice because it's both enabled and disabled in
+    /// place (see [`ModInfo::duplicate_of`]) by deleting the copy that isn't
+    /// in `keep`. `mod_id` can be either copy's ID. Keeping the disabled
+    /// copy leaves the mod disabled.
+    pub fn resolve_duplicate(&self, mod_id: &str, keep: ModLocation) -> Result<ModInfo, String> {
+        self.flush_pending_metadata()?;
+        let mods = self.get_all_mods()?;
+        let entry = mods
+            .iter()
+            .find(|m| m.id == mod_id)
+            .ok_or("Mod not found")?;
+        let primary_id = entry.duplicate_of.as_deref().unwrap_or(&entry.id);
+        let copies: Vec<&ModInfo> = mods
+            .iter()
+            .filter(|m| m.id == primary_id || m.duplicate_of.as_deref() == Some(primary_id))
+            .collect();
+        if copies.len() < 2 {
+            return Err(format!("{} has no duplicate to resolve", entry.name));
+        }
+        if !copies.iter().any(|m| m.location == keep) {
+            return Err(format!("{} has no {:?} copy to keep", entry.name, keep));
+        }
+
+        let _scope = fs_ops::mod_scope(primary_id);
+        for copy in copies.iter().filter(|m| m.location != keep) {
+            for file in &copy.associated_files {
+                fs_ops::remove_file(file)
+                    .map_err(|e| format!("Failed to delete {:?}: {}", file, e))?;
             }
-            if !failed {
-                deleted += 1;
+            log::info!(
+                "[duplicate] Deleted the {:?} copy of {} at {:?}",
+                copy.location,
+                copy.name,
+                copy.file_path
+            );
+        }
+
+        // Both copies share the metadata; make it match the one kept
+        {
+            let _lock = lock_metadata();
+            if let Some(mut metadata) = self.load_metadata(primary_id)? {
+                match keep {
+                    ModLocation::Active => {
+                        metadata.disabled_reason = None;
+                        metadata.disabled_at = None;
+                    }
+                    ModLocation::Disabled => {
+                        metadata
+                            .disabled_reason
+                            .get_or_insert(DisabledReason::UserAction);
+                        metadata.disabled_at.get_or_insert(self.clock.now());
+                    }
+                }
+                self.save_metadata(primary_id, &metadata)?;
             }
         }
+        self.find_mod_by_id(primary_id)?
+            .ok_or_else(|| format!("{} not found after resolving", entry.name))
+    }
 
-        if let Ok(cleaned) = self.cleanup_empty_mod_folders() {
-            if cleaned > 0 {
-                log::info!("Cleaned up {} empty folder(s) after bulk deletion", cleaned);
+    /// Mods whose .pak bytes are identical though they're installed under
+    /// different IDs, e.g. the same download put in two folders. Only paks
+    /// sharing a size are hashed, through the hash cache, so a rescan reads
+    /// only paks that changed.
+    pub fn find_duplicate_mods(&self) -> Result<Vec<DuplicateModGroup>, String> {
+        let mods = self.get_all_mods()?;
+        let mut by_size: BTreeMap<u64, Vec<&ModInfo>> = BTreeMap::new();
+        for mod_info in mods.iter().filter(|m| m.duplicate_of.is_none()) {
+            by_size.entry(mod_info.pak_size).or_default().push(mod_info);
+        }
+        let mut by_hash: BTreeMap<String, Vec<&ModInfo>> = BTreeMap::new();
+        for same_size in by_size.into_values().filter(|group| group.len() > 1) {
+            for mod_info in same_size {
+                match self.content_hash(&mod_info.file_path) {
+                    Ok(hash) => by_hash.entry(hash).or_default().push(mod_info),
+                    Err(e) => log::warn!("[duplicates] Skipping {}: {}", mod_info.id, e),
+                }
             }
         }
 
-        log::info!("[bulk-delete] Deleted {} of {} requested mod(s)", deleted, mod_ids.len());
-        Ok(deleted)
+        let mut groups: Vec<DuplicateModGroup> = by_hash
+            .into_iter()
+            .filter(|(_, copies)| copies.len() > 1)
+            .map(|(pak_hash, mut copies)| {
+                copies.sort_by_key(|m| std::cmp::Reverse((m.enabled, m.install_date)));
+                DuplicateModGroup {
+                    pak_hash,
+                    mods: copies
+                        .into_iter()
+                        .map(|m| DuplicateModCopy {
+                            mod_id: m.id.clone(),
+                            title: m.metadata.title.clone(),
+                            file_path: m.file_path.clone(),
+                            pak_size: m.pak_size,
+                            file_size: m.file_size,
+                            enabled: m.enabled,
+                            install_date: m.install_date,
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        groups.sort_by(|a, b| a.mods[0].title.cmp(&b.mods[0].title));
+        Ok(groups)
     }
 
-    /// Install many paks from one archive as a single mod entry: every pak
-    /// lands in one folder, the first becomes the parent, and the rest are
-    /// attached as add-ons under it — one card instead of hundreds.
-    pub fn install_mod_group(
+    /// Keep one of a set of identical mods (see
+    /// [`find_duplicate_mods`](Self::find_duplicate_mods)) and move the
+    /// others to the trash. What the kept copy's metadata leaves empty is
+    /// filled in from the others first, its thumbnail included, and it takes
+    /// over their profiles and collections. Refused if any of them isn't a
+    /// byte-identical copy of the one kept.
+    pub fn merge_duplicate_mods(
         &self,
-        pak_files: &[String],
-        group_name: &str,
-        category: ModCategory,
+        keep_mod_id: &str,
+        remove_mod_ids: &[String],
     ) -> Result<ModInfo, String> {
-        if pak_files.is_empty() {
-            return Err("No files to install".to_string());
+        self.flush_pending_metadata()?;
+        let mods = self.get_all_mods()?;
+        let find = |id: &str| {
+            mods.iter()
+                .find(|m| m.id == id)
+                .ok_or_else(|| format!("Mod not found: {}", id))
+        };
+        let keep = find(keep_mod_id)?;
+        let removed = remove_mod_ids
+            .iter()
+            .filter(|id| id.as_str() != keep_mod_id)
+            .map(|id| find(id))
+            .collect::<Result<Vec<&ModInfo>, String>>()?;
+        if removed.is_empty() {
+            return Err("No other copies to remove".to_string());
+        }
+        let keep_hash = self.content_hash(&keep.file_path)?;
+        for copy in &removed {
+            if self.content_hash(&copy.file_path)? != keep_hash {
+                return Err(format!(
+                    "{} is not an identical copy of {}",
+                    copy.metadata.title, keep.metadata.title
+                ));
+            }
         }
 
-        let folder_path = self
-            .mods_directory
-            .join(sanitize_folder_name(&category.to_string()))
-            .join(sanitize_folder_name(group_name));
-        self.ensure_directory_exists(&folder_path)?;
-
-        // Stable order so the parent choice is deterministic
-        let mut sorted: Vec<&String> = pak_files.iter().collect();
-        sorted.sort();
-
-        let now = Utc::now();
-        let base_metadata = |title: String, parent: Option<String>| ModMetadata {
-            title,
-            subtitle: None,
-            description: String::new(),
-            author: None,
-            version: None,
-            tags: Vec::new(),
-            category: category.clone(),
-            character: None,
-            costume: None,
-            is_favorite: false,
-            is_nsfw: false,
-            created_at: now,
-            updated_at: now,
-            install_date: now,
-            profile_ids: None,
-            nexus_mod_id: None,
-            nexus_file_id: None,
-            nexus_version: None,
-            original_folder_path: folder_path
-                .strip_prefix(&self.mods_directory)
-                .ok()
-                .map(|p| p.to_string_lossy().to_string()),
-            parent_mod_id: parent,
-        };
+        {
+            let _lock = lock_metadata();
+            let current = self
+                .load_metadata(keep_mod_id)?
+                .unwrap_or_else(|| keep.metadata.clone());
+            let copies: Vec<&ModMetadata> = removed.iter().map(|m| &m.metadata).collect();
+            self.save_metadata(keep_mod_id, &merge_duplicate_metadata(current, &copies))?;
+        }
+        if keep.thumbnail_path.is_none() {
+            if let Some(thumbnail) = removed.iter().find_map(|m| m.thumbnail_path.as_ref()) {
+                let dest = ThumbnailService::new(self.metadata_directory.clone())
+                    .get_thumbnail_path(keep_mod_id);
+                if let Err(e) = fs_ops::copy_verified(thumbnail, &dest) {
+                    log::warn!("[duplicates] Failed to carry over the thumbnail: {}", e);
+                }
+            }
+        }
+        let removed_ids: Vec<String> = removed.iter().map(|m| m.id.clone()).collect();
+        let collections = self.collections();
+        for collection in collections.list()? {
+            if collection.mod_ids.iter().any(|id| removed_ids.contains(id)) {
+                collections.add(&collection.id, &[keep_mod_id.to_string()])?;
+            }
+        }
 
-        let mut parent_id: Option<String> = None;
-        let mut parent_dest: Option<PathBuf> = None;
+        // Forced, since the kept copy has taken over their memberships
+        let deleted = self.delete_mods(&removed_ids, true, false)?;
+        log::info!(
+            "[duplicates] Kept {}, moved {} of {} copies to the trash",
+            keep_mod_id,
+            deleted,
+            removed_ids.len()
+        );
+        if deleted < removed_ids.len() {
+            return Err(format!(
+                "Only {} of {} copies could be removed",
+                deleted,
+                removed_ids.len()
+            ));
+        }
+        self.find_mod_by_id(keep_mod_id)?
+            .ok_or_else(|| format!("{} not found after merging", keep.name))
+    }
 
-        for source in sorted {
-            let source_path = Path::new(source);
-            let file_name = source_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .ok_or("Invalid file name in group")?;
-            let dest_path = folder_path.join(file_name);
+    /// Startup pass: repair every split mod that has only one sensible
+    /// fix and report the rest.
+    pub fn repair_split_mods(&self) -> Result<SplitRepairReport, String> {
+        self.flush_pending_metadata()?;
+        let mut report = SplitRepairReport::default();
 
-            fs::copy(source_path, &dest_path)
-                .map_err(|e| format!("Failed to copy {file_name}: {e}"))?;
+        for split in self.detect_split_mods()? {
+            if !split.auto_repairable {
+                log::warn!(
+                    "[split] {} needs manual repair ({} enabled, {} disabled file(s))",
+                    split.name,
+                    split.enabled_files.len(),
+                    split.disabled_files.len()
+                );
+                report.unresolved.push(split);
+                continue;
+            }
 
-            // Companion .ucas/.utoc travel with their pak
-            if let (Some(base_name), Some(source_dir)) = (
-                source_path.file_stem().and_then(|s| s.to_str()),
-                source_path.parent(),
-            ) {
-                for ext in &[".ucas", ".utoc"] {
-                    let companion = source_dir.join(format!("{base_name}{ext}"));
-                    if companion.exists() {
-                        fs::copy(&companion, folder_path.join(format!("{base_name}{ext}")))
-                            .map_err(|e| format!("Failed to copy companion: {e}"))?;
-                    }
+            // A toggle moves the pak first, so the companions are still where
+            // the mod was before it was interrupted, and where its metadata
+            // ID points. Undo the toggle rather than finish it.
+            let prefer = if split.pak_enabled {
+                SplitSide::Disabled
+            } else {
+                SplitSide::Enabled
+            };
+            match self.reunite_split_mod(&split, prefer) {
+                Ok(_) => report.repaired += 1,
+                Err(e) => {
+                    log::warn!("[split] Failed to repair {}: {}", split.name, e);
+                    report.unresolved.push(split);
                 }
             }
+        }
 
-            let mod_id = self.generate_mod_id_from_path(&dest_path, file_name);
-            let metadata = match &parent_id {
-                None => base_metadata(group_name.to_string(), None),
-                Some(pid) => base_metadata(self.extract_mod_name(file_name), Some(pid.clone())),
-            };
-            self.save_metadata(&mod_id, &metadata)?;
+        if report.repaired > 0 {
+            log::info!("[split] Repaired {} split mod(s)", report.repaired);
+        }
+        Ok(report)
+    }
 
-            if parent_id.is_none() {
-                parent_id = Some(mod_id);
-                parent_dest = Some(dest_path);
+    fn reunite_split_mod(&self, split: &SplitMod, prefer: SplitSide) -> Result<ModInfo, String> {
+        let enabled = prefer == SplitSide::Enabled;
+        let (kept, moving) = if enabled {
+            (&split.enabled_files, &split.disabled_files)
+        } else {
+            (&split.disabled_files, &split.enabled_files)
+        };
+        let anchor = kept
+            .iter()
+            .find(|f| has_extension(f, "pak"))
+            .or(kept.first())
+            .ok_or("Split mod has no files on the chosen side")?;
+        let target_dir = anchor.parent().ok_or("Invalid directory")?.to_path_buf();
+
+        // Check every destination up front so a collision leaves nothing
+        // half-moved
+        let mut destinations = Vec::new();
+        for file in moving {
+            let dest = target_dir.join(file.file_name().ok_or("Invalid file path")?);
+            if dest.exists() || destinations.contains(&dest) {
+                return Err(format!(
+                    "{:?} already exists, the split has to be resolved by hand",
+                    dest
+                ));
             }
+            destinations.push(dest);
+        }
+        for (file, dest) in moving.iter().zip(&destinations) {
+            fs_ops::move_file(file, dest)?;
         }
 
-        let parent_path = parent_dest.ok_or("Group install produced no parent")?;
-        let parent_name = parent_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or(group_name)
-            .to_string();
-        let metadata_fs = fs::metadata(&parent_path)
-            .map_err(|e| format!("Failed to read parent metadata: {e}"))?;
-        let associated_files = self.find_associated_files(&parent_path).unwrap_or_default();
-        let parent_id = parent_id.ok_or("Group install produced no parent id")?;
-        let metadata = self
-            .load_metadata(&parent_id)
-            .ok()
-            .flatten()
-            .ok_or("Parent metadata missing after group install")?;
+        let pak_file_name = split.pak_path.file_name().ok_or("Invalid file path")?;
+        let pak_path = target_dir.join(pak_file_name);
+        let new_id = self.generate_mod_id_from_path(&pak_path, &pak_file_name.to_string_lossy());
+        if new_id != split.mod_id {
+            self.move_metadata_to_id(&split.mod_id, &new_id)?;
+        }
 
         log::info!(
-            "[group-install] Installed {} pak(s) as \"{}\" ({} add-ons)",
-            pak_files.len(),
-            group_name,
-            pak_files.len().saturating_sub(1)
+            "[split] Reunited {} in {:?} ({} file(s) moved)",
+            split.name,
+            target_dir,
+            moving.len()
         );
-
-        Ok(ModInfo {
-            id: parent_id.clone(),
-            name: metadata.title.clone(),
-            category: metadata.category.clone(),
-            character: None,
-            enabled: true,
-            is_favorite: false,
-            file_path: parent_path,
-            thumbnail_path: self.find_thumbnail(&parent_id, &parent_name),
-            file_size: metadata_fs.len(),
-            install_date: metadata.install_date,
-            last_modified: metadata_fs.modified().ok().map(|t| t.into()).unwrap_or(now),
-            original_file_name: parent_name,
-            associated_files,
-            metadata,
-        })
+        self.mod_info_at(&pak_path, enabled)
     }
 
-    /// Migrate metadata and thumbnails from old filename-based IDs to new path-based IDs
-    /// This is a one-time migration for existing mods when switching ID generation methods
-    /// Returns the number of mods migrated
-    pub fn migrate_metadata_to_path_ids(&self) -> Result<usize, String> {
-        log::info!("🔍 Checking for metadata migration...");
-        let all_mods = self.get_all_mods()?;
-        let mut migrated_count = 0;
-        let mut skipped_count = 0;
-        let mut not_found_count = 0;
-
-        for mod_info in all_mods {
-            let file_path = PathBuf::from(&mod_info.file_path);
-            let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    /// Carry a mod's metadata, thumbnail and add-on links over to the ID it
+    /// has after its pak moved. Metadata already under the new ID is kept.
+    fn move_metadata_to_id(&self, old_id: &str, new_id: &str) -> Result<(), String> {
+        if let Some(metadata) = self.load_metadata(old_id)? {
+            if self.load_metadata(new_id)?.is_none() {
+                self.save_metadata(new_id, &metadata)?;
+            }
+            self.delete_metadata(old_id)?;
+        }
 
-            let clean_file_name = file_name.replace(".disabled", "");
+        let old_thumb = self
+            .metadata_directory
+            .join(format!("{}_thumbnail.png", old_id));
+        let new_thumb = self
+            .metadata_directory
+            .join(format!("{}_thumbnail.png", new_id));
+        if old_thumb.exists() && !new_thumb.exists() {
+            if let Err(e) = fs_ops::rename(&old_thumb, &new_thumb) {
+                log::warn!("[split] Failed to migrate thumbnail: {}", e);
+            }
+        }
+        self.migrate_thumbnail_source(old_id, new_id);
 
-            // Calculate both IDs
-            let old_id = self.generate_mod_id(&clean_file_name);
-            let new_id = self.generate_mod_id_from_path(&file_path, &clean_file_name);
+        self.migrate_addon_parent_ids(old_id, new_id)
+    }
 
-            // Skip if IDs are the same (shouldn't happen, but just in case)
-            if old_id == new_id {
-                skipped_count += 1;
+    /// Find mods older managers disabled by renaming them `*.pak.disabled`
+    /// (companions `*.ucas.disabled` / `*.utoc.disabled`, or left as they
+    /// were) inside ~mods. Depending on extension ordering the game still
+    /// mounts some of those files.
+    pub fn detect_legacy_disabled_files(&self) -> Result<Vec<LegacyDisabledMod>, String> {
+        let mut found = Vec::new();
+        for ((dir, stem), mut files) in legacy_disabled_files(&self.mods_directory) {
+            let pak_name = format!("{}.pak", stem);
+            let Some(pak_path) = files
+                .iter()
+                .find(|f| strip_disabled_suffix(f).eq_ignore_ascii_case(&pak_name))
+                .cloned()
+            else {
+                log::warn!(
+                    "[legacy] {:?} has .disabled companions but no pak, leaving them",
+                    dir.join(&stem)
+                );
                 continue;
-            }
+            };
 
-            // Check if new metadata already exists (already migrated)
-            if self.load_metadata(&new_id).ok().flatten().is_some() {
-                skipped_count += 1;
-                continue;
+            // Tools that only renamed the pak left the companions as they
+            // were; they belong to it unless an enabled copy sits alongside
+            if !dir.join(&pak_name).exists() {
+                for ext in COMPANION_EXTENSIONS {
+                    let companion = dir.join(format!("{}.{}", stem, ext));
+                    if companion.is_file() {
+                        files.push(companion);
+                    }
+                }
             }
 
-            // Try to load old metadata
-            if let Ok(Some(old_metadata)) = self.load_metadata(&old_id) {
-                log::info!(
-                    "Migrating metadata for mod: {} (old ID: {}, new ID: {})",
-                    mod_info.name,
-                    old_id,
-                    new_id
-                );
+            let relative = dir
+                .strip_prefix(&self.mods_directory)
+                .unwrap_or(Path::new(""));
+            found.push(LegacyDisabledMod {
+                mod_id: self.generate_mod_id_from_path(&pak_path, &pak_name),
+                name: self.extract_mod_name(&pak_name),
+                files,
+                target_path: self.disabled_mods_directory.join(relative).join(&pak_name),
+                error: None,
+            });
+        }
+
+        if !found.is_empty() {
+            log::warn!(
+                "[legacy] Found {} mod(s) disabled with a .disabled suffix",
+                found.len()
+            );
+        }
+        Ok(found)
+    }
 
-                // Save metadata with new ID
-                self.save_metadata(&new_id, &old_metadata)?;
+    /// Strip the `.disabled` suffix from legacy disabled mods and move them
+    /// into disabled-mods, keeping their folder layout and metadata.
+    /// `mod_ids` limits the run to those mods; `dry_run` only reports. A mod
+    /// that fails is reported with its error and the rest carry on.
+    pub fn convert_legacy_disabled(
+        &self,
+        mod_ids: Option<&[String]>,
+        dry_run: bool,
+    ) -> Result<LegacyDisabledReport, String> {
+        self.flush_pending_metadata()?;
+        let mut report = LegacyDisabledReport {
+            dry_run,
+            ..Default::default()
+        };
 
-                // Migrate thumbnail if it exists
-                // Check for thumbnail with old ID
-                let old_thumb_path = self
-                    .metadata_directory
-                    .join(format!("{}_thumbnail.png", old_id));
-                if old_thumb_path.exists() {
-                    let new_thumb_path = self
-                        .metadata_directory
-                        .join(format!("{}_thumbnail.png", new_id));
-                    if let Err(e) = fs::copy(&old_thumb_path, &new_thumb_path) {
-                        log::warn!("Failed to migrate thumbnail: {}", e);
-                    } else {
-                        log::debug!("Migrated thumbnail for {}", mod_info.name);
-                        // Delete old thumbnail after successful copy
-                        let _ = fs::remove_file(&old_thumb_path);
+        for mut legacy in self.detect_legacy_disabled_files()? {
+            if mod_ids.is_some_and(|ids| !ids.contains(&legacy.mod_id)) {
+                continue;
+            }
+            if !dry_run {
+                match self.convert_legacy_disabled_mod(&legacy) {
+                    Ok(()) => report.converted += 1,
+                    Err(e) => {
+                        log::warn!("[legacy] Failed to convert {}: {}", legacy.name, e);
+                        legacy.error = Some(e);
                     }
                 }
+            }
+            report.mods.push(legacy);
+        }
 
-                // Delete old metadata file after successful migration
-                let _ = self.delete_metadata(&old_id);
+        if report.converted > 0 {
+            log::info!(
+                "[legacy] Moved {} mod(s) into disabled-mods",
+                report.converted
+            );
+        }
+        Ok(report)
+    }
 
-                migrated_count += 1;
-            } else {
-                not_found_count += 1;
+    fn convert_legacy_disabled_mod(&self, legacy: &LegacyDisabledMod) -> Result<(), String> {
+        let _scope = fs_ops::mod_scope(&legacy.mod_id);
+        let target_dir = legacy.target_path.parent().ok_or("Invalid directory")?;
+
+        // Check every destination up front so a collision leaves nothing
+        // half-moved
+        let mut destinations = Vec::new();
+        for file in &legacy.files {
+            let dest = target_dir.join(strip_disabled_suffix(file));
+            if dest.exists() || destinations.contains(&dest) {
+                return Err(format!("{:?} already exists", dest));
             }
+            destinations.push(dest);
+        }
+        fs::create_dir_all(target_dir)
+            .map_err(|e| format!("Failed to create destination folder: {}", e))?;
+        for (file, dest) in legacy.files.iter().zip(&destinations) {
+            fs_ops::move_file(file, dest)?;
         }
 
-        if migrated_count > 0 {
-            log::info!("   ✅ Migrated metadata for {} mod(s)", migrated_count);
-        } else {
-            log::info!("   ✅ All metadata up to date");
+        let pak_name = legacy
+            .target_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or("Invalid file name")?;
+        let new_id = self.generate_mod_id_from_path(&legacy.target_path, pak_name);
+        self.move_metadata_to_id(&legacy.mod_id, &new_id)?;
+
+        // Enabling puts the mod back where it was found
+        if let Some(mut metadata) = self.load_metadata(&new_id)? {
+            let relative = target_dir
+                .strip_prefix(&self.disabled_mods_directory)
+                .unwrap_or(Path::new(""));
+            if metadata.original_folder_path.is_none() && !relative.as_os_str().is_empty() {
+                metadata.original_folder_path = Some(relative.to_string_lossy().to_string());
+            }
+            metadata
+                .disabled_reason
+                .get_or_insert(DisabledReason::UserAction);
+            metadata.disabled_at.get_or_insert_with(|| self.clock.now());
+            self.save_metadata(&new_id, &metadata)?;
         }
-        Ok(migrated_count)
-    }
 
-    /// Copy metadata from an old mod ID to the current mod
-    /// This is useful for recovering metadata after ID system changes
-    pub fn copy_metadata_from_old_id(
-        &self,
-        current_mod_id: &str,
-        old_mod_id: &str,
-    ) -> Result<(), String> {
         log::info!(
-            "Copying metadata from old ID {} to current ID {}",
-            old_mod_id,
-            current_mod_id
+            "[legacy] Converted {} to {:?}",
+            legacy.name,
+            legacy.target_path
         );
+        Ok(())
+    }
 
-        // Load metadata from old ID
-        let old_metadata = self
-            .load_metadata(old_mod_id)?
-            .ok_or_else(|| format!("No metadata found for old ID: {}", old_mod_id))?;
-
-        // Copy thumbnail BEFORE updating metadata (in case update fails)
-        let old_thumb_path = self
-            .metadata_directory
-            .join(format!("{}_thumbnail.png", old_mod_id));
-        if old_thumb_path.exists() {
-            let new_thumb_path = self
-                .metadata_directory
-                .join(format!("{}_thumbnail.png", current_mod_id));
-            fs::copy(&old_thumb_path, &new_thumb_path)
-                .map_err(|e| format!("Failed to copy thumbnail: {}", e))?;
-            log::info!("Copied thumbnail from {} to {}", old_mod_id, current_mod_id);
-        }
-
-        // Use update_metadata to save the metadata AND trigger folder rename if needed
-        // This ensures the mod folder gets organized properly based on the metadata
-        self.update_metadata(current_mod_id, old_metadata)?;
-        log::info!(
-            "Successfully copied and applied metadata from {} to {}",
-            old_mod_id,
-            current_mod_id
-        );
-
-        Ok(())
-    }
-
-    /// Move mod folders that sit directly under a category folder (e.g.
-    /// "Skins/MyMod") into their character subfolder ("Skins/Magik/MyMod")
-    /// when the character is known. Older app versions created folders
-    /// without the character level and nothing re-nested them afterwards.
-    /// Returns the number of folders moved.
-    pub fn relocate_misplaced_mods(&self) -> Result<usize, String> {
-        let all_mods = self.get_all_mods()?;
-        let mut moved = 0;
-        let mut id_remap: HashMap<String, String> = HashMap::new();
-        // Folders already moved this run (multi-pak folders appear once per pak)
-        let mut moved_dirs: HashSet<PathBuf> = HashSet::new();
-
-        for mod_info in all_mods {
-            let Some(character) = &mod_info.metadata.character else {
-                continue; // can't place without a character
-            };
-
-            let file_path = &mod_info.file_path;
-            if !file_path.starts_with(&self.mods_directory) {
-                continue; // disabled mods get re-organized when re-enabled
+    /// Record `game_build` as the last build each enabled mod loaded in
+    /// cleanly, skipping the mods in `failed`. Returns how many changed.
+    pub fn record_verified_game_build(
+        &self,
+        game_build: &str,
+        failed: &HashSet<String>,
+    ) -> Result<usize, String> {
+        self.flush_pending_metadata()?;
+        let mut updated = 0;
+        for mod_info in self.get_all_mods()? {
+            if !mod_info.enabled || failed.contains(&mod_info.id) {
+                continue;
             }
-            let Some(parent_dir) = file_path.parent() else {
+            let _lock = lock_metadata();
+            let Some(mut metadata) = self.load_metadata(&mod_info.id)? else {
                 continue;
             };
-            if parent_dir == self.mods_directory {
-                continue; // loose pak, organize_mods handles those
-            }
-            if moved_dirs.contains(parent_dir) {
+            if metadata.last_verified_game_build.as_deref() == Some(game_build) {
                 continue;
             }
+            metadata.last_verified_game_build = Some(game_build.to_string());
+            self.save_metadata(&mod_info.id, &metadata)?;
+            updated += 1;
+        }
+        Ok(updated)
+    }
 
-            // Only folders sitting directly under a category folder
-            let category_dir = self.mods_directory.join(sanitize_folder_name(
-                &mod_info.metadata.category.to_string(),
-            ));
-            if parent_dir.parent() != Some(category_dir.as_path()) {
+    /// Compare every mod's files with the fingerprint in its metadata and
+    /// flag the ones something other than the manager replaced: their
+    /// `updated_at` is bumped and `nexus_version` cleared, as the files are
+    /// no longer the release that was installed. Files are only hashed when
+    /// their size or time changed, and a matching hash just refreshes the
+    /// fingerprint. Mods without one get it recorded. Returns the IDs of
+    /// the mods flagged by this run.
+    pub fn reconcile_external_changes(&self) -> Result<Vec<String>, String> {
+        self.flush_pending_metadata()?;
+        let now = self.clock.now();
+        let mut modified = Vec::new();
+        // A duplicate shares its metadata with the copy listed first
+        for mod_info in self
+            .get_all_mods()?
+            .into_iter()
+            .filter(|m| m.duplicate_of.is_none())
+        {
+            let recorded = mod_info.metadata.file_fingerprint.as_ref();
+            let modified_at = latest_modified(&mod_info.associated_files);
+            if recorded.is_some_and(|fingerprint| {
+                fingerprint.size == mod_info.file_size && Some(fingerprint.modified) == modified_at
+            }) {
                 continue;
             }
+            let content_hash = match self.files_content_hash(&mod_info) {
+                Ok(hash) => hash,
+                Err(e) => {
+                    log::warn!("[reconcile] Skipping {}: {}", mod_info.id, e);
+                    continue;
+                }
+            };
+            let changed =
+                recorded.is_some_and(|fingerprint| fingerprint.content_hash != content_hash);
+
+            let _lock = lock_metadata();
+            let mut metadata = self
+                .load_metadata(&mod_info.id)?
+                .unwrap_or_else(|| mod_info.metadata.clone());
+            metadata.file_fingerprint = Some(FileFingerprint {
+                size: mod_info.file_size,
+                modified: modified_at.unwrap_or(mod_info.last_modified),
+                content_hash,
+            });
+            if changed {
+                log::info!(
+                    "[reconcile] {} was changed outside the manager",
+                    mod_info.id
+                );
+                metadata.externally_modified_at = Some(now);
+                metadata.updated_at = now;
+                metadata.nexus_version = None;
+                modified.push(mod_info.id.clone());
+            }
+            self.save_metadata(&mod_info.id, &metadata)?;
+        }
+        Ok(modified)
+    }
 
-            let char_folder = sanitize_folder_name(&character.to_string());
+    /// Move every disabled mod over to `strategy`'s layout: out of
+    /// disabled-mods and renamed in place in the ~mods folder enabling would
+    /// restore it to, or the other way round. Metadata, thumbnails and
+    /// add-on links follow the new IDs. `dry_run` only reports. A mod that
+    /// fails is reported with its error and left as it was; both layouts
+    /// work side by side, so a partly converted library is still usable.
+    pub fn convert_disable_strategy(
+        &self,
+        strategy: DisableStrategy,
+        dry_run: bool,
+    ) -> Result<DisableStrategyReport, String> {
+        self.flush_pending_metadata()?;
+        let mut report = DisableStrategyReport {
+            strategy,
+            dry_run,
+            ..Default::default()
+        };
 
-            // Never touch the character folder itself (a pak sitting loose in
-            // it) or any folder that contains nested mod folders
-            if parent_dir.file_name().and_then(|n| n.to_str()) == Some(char_folder.as_str()) {
-                continue;
-            }
-            let has_nested_mod_folders = WalkDir::new(parent_dir)
-                .min_depth(2)
-                .into_iter()
-                .filter_map(|e| e.ok())
-                .any(|e| e.path().extension().and_then(|x| x.to_str()) == Some("pak"));
-            if has_nested_mod_folders {
+        for mod_info in self.get_all_mods()? {
+            if mod_info.enabled {
                 continue;
             }
+            let renamed = has_extension(&mod_info.file_path, "pakdisabled");
+            let file_name = Path::new(&mod_info.original_file_name);
+            let target_path = match (strategy, renamed) {
+                (DisableStrategy::RenameInPlace, false) => toggled_in_place(
+                    &self
+                        .toggle_destination(&mod_info.metadata, true)
+                        .join(file_name),
+                    true,
+                ),
+                (DisableStrategy::Move, true) => {
+                    let folder = mod_info
+                        .file_path
+                        .parent()
+                        .and_then(|parent| parent.strip_prefix(&self.mods_directory).ok())
+                        .unwrap_or(Path::new(""));
+                    self.disabled_mods_directory.join(folder).join(file_name)
+                }
+                _ => continue,
+            };
 
-            let char_dir = category_dir.join(&char_folder);
-            let Some(folder_name) = parent_dir.file_name() else {
-                continue;
+            let mut conversion = DisableStrategyConversion {
+                mod_id: mod_info.id.clone(),
+                name: mod_info.name.clone(),
+                files: mod_info.associated_files.clone(),
+                target_path,
+                error: None,
             };
-            let target = char_dir.join(folder_name);
-            if target.exists() {
-                log::warn!("[relocate] Target already exists, skipping: {:?}", target);
-                continue;
-            }
-            if let Err(e) = fs::create_dir_all(&char_dir) {
-                log::warn!("[relocate] Failed to create {:?}: {}", char_dir, e);
-                continue;
+            if !dry_run {
+                match self.convert_disabled_mod(&conversion) {
+                    Ok(()) => report.converted += 1,
+                    Err(e) => {
+                        log::warn!(
+                            "[disable-strategy] Failed to convert {}: {}",
+                            mod_info.name,
+                            e
+                        );
+                        conversion.error = Some(e);
+                    }
+                }
             }
+            report.mods.push(conversion);
+        }
 
-            let source_dir = parent_dir.to_path_buf();
-            log::info!("[relocate] {:?} → {:?}", source_dir, target);
-            if let Err(e) = fs::rename(&source_dir, &target) {
-                log::warn!("[relocate] Failed to move {:?}: {}", source_dir, e);
-                continue;
-            }
-            moved_dirs.insert(source_dir.clone());
+        if report.converted > 0 {
+            log::info!(
+                "[disable-strategy] Converted {} disabled mod(s) to {:?}",
+                report.converted,
+                strategy
+            );
+        }
+        Ok(report)
+    }
 
-            // Migrate metadata for every pak that moved (path-based IDs changed)
-            self.migrate_moved_folder_metadata(&source_dir, &target, &mut id_remap);
-            moved += 1;
+    fn convert_disabled_mod(&self, conversion: &DisableStrategyConversion) -> Result<(), String> {
+        let _scope = fs_ops::mod_scope(&conversion.mod_id);
+        let target_dir = conversion.target_path.parent().ok_or("Invalid directory")?;
+        let in_place = has_extension(&conversion.target_path, "pakdisabled");
+
+        // Check every destination up front so a collision leaves nothing
+        // half-moved. An enabled copy where the renamed files would go
+        // counts too: both would get the same ID.
+        let mut destinations = Vec::new();
+        for file in &conversion.files {
+            let name = file.file_name().ok_or("Invalid file path")?;
+            let dest = toggled_in_place(&target_dir.join(name), in_place);
+            if dest.exists()
+                || destinations.contains(&dest)
+                || (in_place && toggled_in_place(&dest, false).exists())
+            {
+                return Err(format!("{:?} already exists", dest));
+            }
+            destinations.push(dest);
+        }
+        fs::create_dir_all(target_dir)
+            .map_err(|e| format!("Failed to create destination folder: {}", e))?;
+        for (file, dest) in conversion.files.iter().zip(&destinations) {
+            fs_ops::move_file(file, dest)?;
         }
 
-        // Re-point add-ons whose parents (or themselves) moved
-        if !id_remap.is_empty() {
-            let _ = self.remap_addon_parent_ids(&id_remap);
+        let pak_name = strip_disabled_marker(
+            conversion
+                .target_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .ok_or("Invalid file name")?,
+        );
+        let new_id = self.generate_mod_id_from_path(&conversion.target_path, &pak_name);
+        if new_id != conversion.mod_id {
+            self.move_metadata_to_id(&conversion.mod_id, &new_id)?;
         }
 
-        if moved > 0 {
-            log::info!(
-                "[relocate] Moved {} mod folder(s) into character folders",
-                moved
-            );
+        // Enabling puts the mod back where it was
+        if let Some(mut metadata) = self.load_metadata(&new_id)? {
+            let relative = target_dir
+                .strip_prefix(&self.disabled_mods_directory)
+                .or_else(|_| target_dir.strip_prefix(&self.mods_directory))
+                .unwrap_or(Path::new(""));
+            if metadata.original_folder_path.is_none() && !relative.as_os_str().is_empty() {
+                metadata.original_folder_path = Some(relative.to_string_lossy().to_string());
+                self.save_metadata(&new_id, &metadata)?;
+            }
         }
-        Ok(moved)
+
+        log::info!(
+            "[disable-strategy] Moved {} to {:?}",
+            conversion.name,
+            conversion.target_path
+        );
+        Ok(())
     }
 
-    /// Migrate metadata and thumbnails for every pak inside a folder that was
-    /// moved from `source_dir` to `target_dir`, recording old -> new IDs in
-    /// `id_remap` for a later add-on re-pointing pass.
-    fn migrate_moved_folder_metadata(
+    /// Delete a mod
+    /// Profiles and collections a mod belongs to, for confirming a delete
+    pub fn get_mod_references(&self, mod_id: &str) -> Result<ModReferences, String> {
+        let mod_info = self.find_mod_by_id(mod_id)?.ok_or("Mod not found")?;
+        Ok(mod_references(&mod_info, &self.collections().list()?))
+    }
+
+    /// Move a mod's files, metadata and thumbnail to the trash, or delete
+    /// them outright if `permanent` is set. A mod in a profile or collection
+    /// is refused with `ModInUse` unless `force` is set; forcing drops it
+    /// from its collections first, and its profile membership goes with its
+    /// metadata, so nothing is left pointing at it.
+    pub fn delete_mod(
         &self,
-        source_dir: &Path,
-        target_dir: &Path,
-        id_remap: &mut HashMap<String, String>,
-    ) {
-        for pak_entry in WalkDir::new(target_dir).into_iter().filter_map(|e| e.ok()) {
-            let pak_path = pak_entry.path().to_path_buf();
-            let name = pak_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-            if !(name.ends_with(".pak") || name.ends_with(".pak.disabled")) {
-                continue;
+        mod_id: &str,
+        force: bool,
+        permanent: bool,
+    ) -> Result<(), DeleteModError> {
+        let mod_info = self
+            .find_listed_mod(mod_id)?
+            .ok_or_else(|| "Mod not found".to_string())?;
+        let references = mod_references(&mod_info, &self.collections().list()?);
+        if !force && !references.is_empty() {
+            return Err(DeleteModError::ModInUse {
+                mods: vec![references],
+            });
+        }
+        if !references.collections.is_empty() {
+            self.collections().forget_mods(&[mod_id.to_string()])?;
+        }
+
+        let _scope = fs_ops::mod_scope(mod_id);
+        if permanent {
+            // Delete all associated files
+            for file_path in &mod_info.associated_files {
+                fs_ops::remove_file(file_path)
+                    .map_err(|e| format!("Failed to delete file: {}", e))?;
             }
-            let old_pak_path = match pak_path.strip_prefix(target_dir) {
-                Ok(rel) => source_dir.join(rel),
-                Err(_) => source_dir.join(name),
-            };
-            let old_id = self.generate_mod_id_from_path(&old_pak_path, name);
-            let new_id = self.generate_mod_id_from_path(&pak_path, name);
-            if old_id == new_id {
-                continue;
+
+            // Delete metadata
+            self.delete_metadata(mod_id)?;
+
+            // Delete thumbnail if exists
+            if let Some(thumbnail_path) = mod_info.thumbnail_path {
+                let _ = fs_ops::remove_file(&thumbnail_path);
             }
-            if let Ok(Some(metadata)) = self.load_metadata(&old_id) {
-                let _ = self.save_metadata(&new_id, &metadata);
-                let old_thumb = self
-                    .metadata_directory
-                    .join(format!("{}_thumbnail.png", old_id));
-                if old_thumb.exists() {
-                    let new_thumb = self
-                        .metadata_directory
-                        .join(format!("{}_thumbnail.png", new_id));
-                    let _ = fs::copy(&old_thumb, &new_thumb);
-                    let _ = fs::remove_file(&old_thumb);
-                }
-                let _ = self.delete_metadata(&old_id);
+            let _ = ThumbnailService::new(self.metadata_directory.clone()).delete_source(mod_id);
+        } else {
+            // The history goes to the trash with the metadata
+            let mut metadata = mod_info.metadata.clone();
+            metadata.record_history(self.clock.now(), ModHistoryAction::Deleted, None);
+            self.save_metadata(mod_id, &metadata)?;
+            write_behind::flush_dir(&self.metadata_directory)?;
+            let plan = self.plan_delete(std::slice::from_ref(&mod_info), &[mod_id.to_string()]);
+            self.move_to_trash(&plan.mods[0])?;
+        }
+
+        // Clean up any empty folders after deleting
+        if let Ok(cleaned) = self.cleanup_empty_mod_folders() {
+            if cleaned > 0 {
+                log::info!("Cleaned up {} empty folder(s) after deletion", cleaned);
             }
-            id_remap.insert(old_id, new_id);
         }
+
+        Ok(())
     }
 
-    /// Ensure add-on paks load BEFORE their parent mod. Marvel Rivals mounts
-    /// paks in alphabetical order of their full path, and for these body
-    /// retexture conflicts the FIRST mounted pak wins — an add-on sorting after
-    /// its parent is silently overridden in game. Fix: prefix the add-on's
-    /// folder with "aa-" so it mounts before the parent. Any legacy "zz-"
-    /// prefix (which forced the broken load-last behaviour) is stripped first.
-    /// Returns the number of add-ons adjusted.
-    pub fn enforce_addon_load_order(&self) -> Result<usize, String> {
+    /// What `delete_mods` would remove for these ids: each mod's files,
+    /// metadata and thumbnails, the folders left empty, and any file another
+    /// mod also lists (kept rather than deleted)
+    pub fn preview_delete(&self, mod_ids: &[String]) -> Result<DeletePreview, String> {
         let all_mods = self.get_all_mods()?;
+        Ok(self.plan_delete(&all_mods, mod_ids))
+    }
 
-        let path_by_id: HashMap<String, PathBuf> = all_mods
-            .iter()
-            .map(|m| (m.id.clone(), m.file_path.clone()))
-            .collect();
+    fn plan_delete(&self, all_mods: &[ModInfo], mod_ids: &[String]) -> DeletePreview {
+        let wanted: HashSet<&str> = mod_ids.iter().map(|s| s.as_str()).collect();
+        let mut owners: HashMap<&Path, Vec<&str>> = HashMap::new();
+        for mod_info in all_mods {
+            for file in &mod_info.associated_files {
+                owners.entry(file).or_default().push(&mod_info.id);
+            }
+        }
 
-        let mut adjusted = 0;
-        let mut id_remap: HashMap<String, String> = HashMap::new();
-        let mut moved_dirs: HashSet<PathBuf> = HashSet::new();
+        let thumbnails = ThumbnailService::new(self.metadata_directory.clone());
+        let mut preview = DeletePreview::default();
+        let mut shared: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+        let mut removed = HashSet::new();
+        for mod_info in all_mods.iter().filter(|m| wanted.contains(m.id.as_str())) {
+            let mut files = Vec::new();
+            for file in &mod_info.associated_files {
+                let others: Vec<String> = owners[file.as_path()]
+                    .iter()
+                    .filter(|id| !wanted.contains(*id))
+                    .map(|id| id.to_string())
+                    .collect();
+                if others.is_empty() {
+                    files.push(file.clone());
+                } else {
+                    shared.entry(file.clone()).or_default().extend(others);
+                }
+            }
+            let metadata_path = self
+                .metadata_directory
+                .join(format!("{}.json", mod_info.id));
+            let metadata_file = metadata_path.exists().then_some(metadata_path);
+            let thumbnail = mod_info.thumbnail_path.clone().filter(|path| path.exists());
+            removed.extend(files.iter().cloned());
+            removed.extend(thumbnail.iter().cloned());
+            preview.mods.push(ModDeletePlan {
+                mod_id: mod_info.id.clone(),
+                title: mod_info.metadata.title.clone(),
+                files,
+                metadata_file,
+                thumbnail,
+                thumbnail_source: thumbnails.get_source_path(&mod_info.id),
+            });
+        }
 
-        for mod_info in &all_mods {
-            let Some(parent_id) = &mod_info.metadata.parent_mod_id else {
-                continue;
-            };
-            let Some(parent_path) = path_by_id.get(parent_id) else {
-                continue; // detached add-on, nothing to order against
-            };
+        preview.missing = mod_ids
+            .iter()
+            .filter(|id| !all_mods.iter().any(|m| &m.id == *id))
+            .cloned()
+            .collect();
+        preview.folders = self.folders_emptied_by(&removed);
+        preview.shared_files = shared
+            .into_iter()
+            .map(|(path, mod_ids)| SharedModFile { path, mod_ids })
+            .collect();
+        preview
+    }
 
-            let addon_path = &mod_info.file_path;
-            // Only enabled mods participate in the game's load order
-            if !addon_path.starts_with(&self.mods_directory)
-                || !parent_path.starts_with(&self.mods_directory)
-            {
-                continue;
+    /// Delete many mods in one pass. A single scan resolves every id (per-id
+    /// delete_mod would rescan the library once per mod), individual failures
+    /// are logged and skipped. Removes exactly what `preview_delete` lists
+    /// for the same ids: files other mods share stay, and only the folders
+    /// the delete empties are removed. Returns how many mods were fully
+    /// deleted. Like `delete_mod`, the mods go to the trash unless
+    /// `permanent` is set, and nothing is deleted while any of them is in
+    /// use, unless `force` is set.
+    pub fn delete_mods(
+        &self,
+        mod_ids: &[String],
+        force: bool,
+        permanent: bool,
+    ) -> Result<usize, DeleteModError> {
+        if !permanent {
+            write_behind::flush_dir(&self.metadata_directory)?;
+        }
+        let all_mods = self.get_all_mods()?;
+        let wanted: HashSet<&str> = mod_ids.iter().map(|s| s.as_str()).collect();
+        if !force {
+            let collections = self.collections().list()?;
+            let in_use: Vec<ModReferences> = all_mods
+                .iter()
+                .filter(|m| wanted.contains(m.id.as_str()))
+                .map(|m| mod_references(m, &collections))
+                .filter(|references| !references.is_empty())
+                .collect();
+            if !in_use.is_empty() {
+                return Err(DeleteModError::ModInUse { mods: in_use });
             }
-
-            let Some(addon_dir) = addon_path.parent() else {
-                continue;
-            };
-            if moved_dirs.contains(addon_dir) {
+        }
+        let plan = self.plan_delete(&all_mods, mod_ids);
+        for file in &plan.shared_files {
+            log::warn!(
+                "[bulk-delete] Keeping {:?}, also listed by {:?}",
+                file.path,
+                file.mod_ids
+            );
+        }
+        let thumbnails = ThumbnailService::new(self.metadata_directory.clone());
+        let mut deleted_ids = Vec::new();
+
+        for mod_plan in plan.mods {
+            let _scope = fs_ops::mod_scope(&mod_plan.mod_id);
+            if !permanent {
+                match self.move_to_trash(&mod_plan) {
+                    Ok(_) => deleted_ids.push(mod_plan.mod_id),
+                    Err(e) => log::warn!(
+                        "[bulk-delete] Failed to move {} to the trash: {}",
+                        mod_plan.mod_id,
+                        e
+                    ),
+                }
                 continue;
             }
-            if addon_dir == self.mods_directory {
-                log::warn!(
-                    "[load-order] Add-on '{}' is loose in ~mods, skipping",
-                    mod_info.metadata.title
-                );
-                continue;
-            }
-            if Some(addon_dir) == parent_path.parent() {
-                // Same folder as the parent: filename order decides, and renaming
-                // the pak would break its .ucas/.utoc pairing. Leave it alone.
-                log::warn!(
-                    "[load-order] Add-on '{}' shares a folder with its parent, skipping",
-                    mod_info.metadata.title
-                );
-                continue;
-            }
-            let Some(folder_name) = addon_dir.file_name().and_then(|n| n.to_str()) else {
-                continue;
-            };
-            let Some(folder_parent) = addon_dir.parent() else {
-                continue;
-            };
-
-            // Compute the base folder name without any of our ordering prefixes,
-            // so we can both upgrade legacy "zz-" folders and add "aa-" cleanly.
-            let base_name = folder_name
-                .strip_prefix("aa-")
-                .or_else(|| folder_name.strip_prefix("zz-"))
-                .unwrap_or(folder_name);
-
-            // The game compares full paths case-insensitively. The add-on already
-            // loads before its parent only if it sorts lower AND already carries
-            // our "aa-" prefix (so it won't drift back above the parent later).
-            let addon_key = addon_path.to_string_lossy().to_lowercase();
-            let parent_key = parent_path.to_string_lossy().to_lowercase();
-            if folder_name.starts_with("aa-") && addon_key < parent_key {
-                continue; // already loads before the parent
+            let mut failed = false;
+            for file_path in &mod_plan.files {
+                if let Err(e) = fs_ops::remove_file(file_path) {
+                    log::warn!("[bulk-delete] Failed to delete {:?}: {}", file_path, e);
+                    failed = true;
+                }
             }
-
-            let target = folder_parent.join(format!("aa-{base_name}"));
-            if target == addon_dir {
-                continue; // nothing to do
+            let _ = self.delete_metadata(&mod_plan.mod_id);
+            if let Some(thumbnail_path) = mod_plan.thumbnail {
+                let _ = fs_ops::remove_file(&thumbnail_path);
             }
-            if target.exists() {
-                log::warn!("[load-order] Target already exists, skipping: {:?}", target);
-                continue;
+            if mod_plan.thumbnail_source.is_some() {
+                let _ = thumbnails.delete_source(&mod_plan.mod_id);
             }
-
-            let source_dir = addon_dir.to_path_buf();
-            if let Err(e) = fs::rename(&source_dir, &target) {
-                log::warn!("[load-order] Failed to rename {:?}: {}", source_dir, e);
-                continue;
+            if !failed {
+                deleted_ids.push(mod_plan.mod_id);
             }
-            log::info!(
-                "[load-order] '{}' now loads before its parent ({:?})",
-                mod_info.metadata.title,
-                target.file_name().unwrap_or_default()
-            );
-            moved_dirs.insert(source_dir.clone());
-
-            self.migrate_moved_folder_metadata(&source_dir, &target, &mut id_remap);
-            adjusted += 1;
         }
 
-        if !id_remap.is_empty() {
-            let _ = self.remap_addon_parent_ids(&id_remap);
+        if let Err(e) = self.collections().forget_mods(&deleted_ids) {
+            log::warn!("[collections] Failed to remove deleted mods: {}", e);
         }
 
-        if adjusted > 0 {
-            log::info!(
-                "[load-order] Adjusted load order for {} add-on(s)",
-                adjusted
-            );
+        // remove_dir refuses a folder something new has appeared in since
+        for folder in &plan.folders {
+            if let Err(e) = fs::remove_dir(folder) {
+                log::warn!("[bulk-delete] Failed to remove folder {:?}: {}", folder, e);
+            }
         }
-        Ok(adjusted)
-    }
-
-    /// Migrate existing mods to the new costume-based folder structure
-    /// This renames folders from "ModName" to "ModName-Costume" when a costume is set
-    /// Returns the number of mods migrated
-    pub fn migrate_to_costume_folders(&self) -> Result<usize, String> {
-        log::info!("🔍 Checking for mods needing costume folder migration...");
-        let all_mods = self.get_all_mods()?;
-        let mut migrated_count = 0;
 
-        for mod_info in all_mods {
-            // Skip if no costume is set - no migration needed
-            let costume = match &mod_info.metadata.costume {
-                Some(c) => c.clone(),
-                None => continue,
-            };
+        log::info!(
+            "[bulk-delete] Deleted {} of {} requested mod(s)",
+            deleted_ids.len(),
+            mod_ids.len()
+        );
+        Ok(deleted_ids.len())
+    }
 
-            // Skip if mod is loose (not in a folder)
-            let file_path = &mod_info.file_path;
-            let parent_dir = match file_path.parent() {
-                Some(p) => p,
-                None => continue,
-            };
+    fn trash_directory(&self) -> PathBuf {
+        self.metadata_directory.join(TRASH_DIR)
+    }
 
-            if parent_dir == self.mods_directory || parent_dir == self.disabled_mods_directory {
-                continue; // Loose mod, will be organized separately
+    /// The trash folder of `mod_id`, refusing IDs that would reach outside
+    /// the trash
+    fn trash_entry(&self, mod_id: &str) -> Result<PathBuf, String> {
+        let mut components = Path::new(mod_id).components();
+        match (components.next(), components.next()) {
+            (Some(std::path::Component::Normal(_)), None) => {
+                Ok(self.trash_directory().join(mod_id))
             }
+            _ => Err(format!("Invalid mod ID: {}", mod_id)),
+        }
+    }
 
-            // Get current folder name
-            let current_folder_name = match parent_dir.file_name().and_then(|n| n.to_str()) {
-                Some(name) => name.to_string(),
-                None => continue,
-            };
-
-            // Calculate expected folder name with costume
-            let expected_folder_name =
-                sanitize_folder_name(&format!("{}-{}", mod_info.name, costume));
+    /// Move what `plan` lists into the mod's trash folder. The manifest is
+    /// written first, so a restore can find every file even after a crash
+    /// mid-move; if a move fails the files already moved are put back, so
+    /// the mod is either whole in the library or whole in the trash.
+    fn move_to_trash(&self, plan: &ModDeletePlan) -> Result<TrashedMod, String> {
+        let entry = self.trash_entry(&plan.mod_id)?;
+        if entry.exists() {
+            // An earlier delete of a mod at the same path; the newer one wins
+            log::info!("[trash] Replacing the trashed copy of {}", plan.mod_id);
+            fs_ops::remove_dir_all_retry(&entry, 3)?;
+        }
+        fs::create_dir_all(&entry).map_err(|e| format!("Failed to create trash folder: {}", e))?;
 
-            // Skip if folder already has costume suffix
-            if current_folder_name == expected_folder_name {
-                continue;
-            }
+        let sources = plan
+            .files
+            .iter()
+            .chain(&plan.metadata_file)
+            .chain(&plan.thumbnail)
+            .chain(&plan.thumbnail_source);
+        let trashed = TrashedMod {
+            mod_id: plan.mod_id.clone(),
+            title: plan.title.clone(),
+            deleted_at: self.clock.now(),
+            files: sources
+                .enumerate()
+                .map(|(index, path)| TrashedFile {
+                    original_path: path.clone(),
+                    trashed_name: format!(
+                        "{}-{}",
+                        index,
+                        path.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    size: fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+                })
+                .collect(),
+        };
+        let json = serde_json::to_string_pretty(&trashed)
+            .map_err(|e| format!("Failed to serialize trash manifest: {}", e))?;
+        fs::write(entry.join(TRASH_MANIFEST), json)
+            .map_err(|e| format!("Failed to write trash manifest: {}", e))?;
 
-            // Skip if the folder name already ends with the costume name (already migrated)
-            let costume_suffix = format!("-{}", sanitize_folder_name(&costume));
-            if current_folder_name.ends_with(&costume_suffix) {
-                continue;
+        for file in &trashed.files {
+            if let Err(e) = fs_ops::move_file(&file.original_path, &entry.join(&file.trashed_name))
+            {
+                if let Err(undo) = self.restore_files(&entry, &trashed) {
+                    log::error!("[trash] Failed to put {} back: {}", plan.mod_id, undo);
+                    return Err(e);
+                }
+                let _ = fs::remove_dir_all(&entry);
+                return Err(e);
             }
+        }
+        log::info!(
+            "[trash] Moved {} ({} file(s)) to the trash",
+            plan.mod_id,
+            trashed.files.len()
+        );
+        Ok(trashed)
+    }
 
-            log::info!(
-                "   🔄 Migrating: {} → {}",
-                current_folder_name,
-                expected_folder_name
-            );
-
-            // Calculate new folder path
-            let parent_of_mod_folder = parent_dir.parent().ok_or("Invalid folder structure")?;
-            let new_folder = parent_of_mod_folder.join(&expected_folder_name);
-
-            // Check if target folder already exists
-            if new_folder.exists() {
-                log::warn!(
-                    "      ⚠️  Target folder already exists, skipping: {:?}",
-                    new_folder
-                );
-                continue;
+    /// Move a trash folder's files back where they came from. Files not in
+    /// the folder (never moved, or already back) are skipped; a file that
+    /// something else now occupies stops the restore before anything moves.
+    fn restore_files(&self, entry: &Path, trashed: &TrashedMod) -> Result<(), String> {
+        let pending: Vec<&TrashedFile> = trashed
+            .files
+            .iter()
+            .filter(|file| entry.join(&file.trashed_name).exists())
+            .collect();
+        if let Some(taken) = pending.iter().find(|file| file.original_path.exists()) {
+            return Err(format!(
+                "{:?} already exists; move it away to restore this mod",
+                taken.original_path
+            ));
+        }
+        for file in pending {
+            if let Some(parent) = file.original_path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to recreate {:?}: {}", parent, e))?;
             }
+            fs_ops::move_file(&entry.join(&file.trashed_name), &file.original_path)?;
+        }
+        Ok(())
+    }
 
-            // Rename the folder
-            match fs::rename(parent_dir, &new_folder) {
-                Ok(_) => {
-                    log::info!("      ✅ Folder renamed successfully");
+    fn read_trash_manifest(entry: &Path) -> Result<TrashedMod, String> {
+        let content = fs::read_to_string(entry.join(TRASH_MANIFEST))
+            .map_err(|e| format!("Failed to read trash manifest: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse trash manifest: {}", e))
+    }
 
-                    // Migrate metadata to new ID
-                    let new_pak_path = new_folder.join(file_path.file_name().unwrap());
-                    let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                    let new_mod_id = self.generate_mod_id_from_path(&new_pak_path, file_name);
+    /// Every trash folder, with its manifest if it can be read
+    fn trash_entries(&self) -> Vec<(PathBuf, Result<TrashedMod, String>)> {
+        let Ok(entries) = fs::read_dir(self.trash_directory()) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| path.is_dir())
+            .map(|path| {
+                let trashed = Self::read_trash_manifest(&path);
+                (path, trashed)
+            })
+            .collect()
+    }
 
-                    if new_mod_id != mod_info.id {
-                        // Save metadata under new ID
-                        if let Err(e) = self.save_metadata(&new_mod_id, &mod_info.metadata) {
-                            log::warn!("      ⚠️  Failed to save metadata: {}", e);
-                        }
+    /// Mods in the trash, most recently deleted first
+    pub fn list_trashed_mods(&self) -> Vec<TrashedMod> {
+        let mut trashed: Vec<TrashedMod> = self
+            .trash_entries()
+            .into_iter()
+            .filter_map(|(path, trashed)| {
+                trashed
+                    .map_err(|e| log::warn!("[trash] Skipping {:?}: {}", path, e))
+                    .ok()
+            })
+            .collect();
+        trashed.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        trashed
+    }
 
-                        // Migrate thumbnail
-                        let old_thumb = self
-                            .metadata_directory
-                            .join(format!("{}_thumbnail.png", mod_info.id));
-                        if old_thumb.exists() {
-                            let new_thumb = self
-                                .metadata_directory
-                                .join(format!("{}_thumbnail.png", new_mod_id));
-                            let _ = fs::copy(&old_thumb, &new_thumb);
-                            let _ = fs::remove_file(&old_thumb);
-                        }
+    /// Put a trashed mod back where it was deleted from, metadata and
+    /// thumbnail included. Its profile membership lives in the metadata and
+    /// comes back with it; collections it was dropped from don't.
+    pub fn restore_mod(&self, mod_id: &str) -> Result<ModInfo, String> {
+        let entry = self.trash_entry(mod_id)?;
+        if !entry.is_dir() {
+            return Err(format!("Mod is not in the trash: {}", mod_id));
+        }
+        let trashed = Self::read_trash_manifest(&entry)?;
+        let _scope = fs_ops::mod_scope(mod_id);
+        self.restore_files(&entry, &trashed)?;
+        fs_ops::remove_dir_all_retry(&entry, 3)?;
+        if let Ok(Some(mut metadata)) = self.load_metadata(mod_id) {
+            metadata.record_history(self.clock.now(), ModHistoryAction::Restored, None);
+            self.save_metadata(mod_id, &metadata)?;
+        }
+        log::info!("[trash] Restored {}", mod_id);
+        self.find_mod_by_id(mod_id)?
+            .ok_or_else(|| format!("Restored mod not found: {}", mod_id))
+    }
 
-                        // Re-point any add-ons referencing the old parent ID
-                        if let Err(e) = self.migrate_addon_parent_ids(&mod_info.id, &new_mod_id) {
-                            log::warn!("      ⚠️  Failed to migrate addon parent IDs: {}", e);
-                        }
+    /// Delete every trashed mod for good. Returns how many were removed.
+    pub fn empty_trash(&self) -> Result<usize, String> {
+        self.purge_trash_where(|_| true)
+    }
 
-                        // Delete old metadata
-                        let _ = self.delete_metadata(&mod_info.id);
-                        log::info!(
-                            "      ✅ Metadata migrated: {} → {}",
-                            mod_info.id,
-                            new_mod_id
-                        );
-                    }
+    /// Delete the trashed mods deleted more than [`TRASH_RETENTION_DAYS`]
+    /// ago. Returns how many were removed.
+    pub fn purge_expired_trash(&self) -> Result<usize, String> {
+        let cutoff = self.clock.now() - chrono::Duration::days(TRASH_RETENTION_DAYS);
+        self.purge_trash_where(|trashed| {
+            trashed
+                .as_ref()
+                .is_ok_and(|trashed| trashed.deleted_at < cutoff)
+        })
+    }
 
-                    migrated_count += 1;
-                }
-                Err(e) => {
-                    log::warn!("      ⚠️  Failed to rename folder: {}", e);
-                }
+    fn purge_trash_where(
+        &self,
+        expired: impl Fn(&Result<TrashedMod, String>) -> bool,
+    ) -> Result<usize, String> {
+        let mut purged = 0;
+        for (path, trashed) in self.trash_entries() {
+            if expired(&trashed) {
+                fs_ops::remove_dir_all_retry(&path, 3)?;
+                purged += 1;
             }
         }
-
-        if migrated_count > 0 {
-            log::info!(
-                "   ✅ Migrated {} mod(s) to costume folder structure",
-                migrated_count
-            );
-        } else {
-            log::info!("   ✅ All mods already using costume folder structure");
+        if purged > 0 {
+            log::info!("[trash] Purged {} trashed mod(s)", purged);
         }
+        Ok(purged)
+    }
 
-        Ok(migrated_count)
+    /// Install many paks from one archive as a single mod entry: every pak
+    /// lands in one folder, the first becomes the parent, and the rest are
+    /// attached as add-ons under it — one card instead of hundreds.
+    pub fn install_mod_group(
+        &self,
+        pak_files: &[String],
+        group_name: &str,
+        category: ModCategory,
+    ) -> Result<InstallResult, String> {
+        self.install_mod_group_with(pak_files, group_name, category, &InstallCopy::default())
     }
 
-    /// Update mod metadata
-    pub fn update_metadata(&self, mod_id: &str, metadata: ModMetadata) -> Result<ModInfo, String> {
-        log::info!("");
-        log::info!("==========================================================");
-        log::info!("📝 UPDATING MOD METADATA");
-        log::info!("   Title: {}", metadata.title);
-        log::info!("   Category: {}", metadata.category);
-        if let Some(ref character) = metadata.character {
-            log::info!("   Character: {}", character);
+    /// [`install_mod_group`](Self::install_mod_group) reporting its copies
+    /// through `copy`. A failed or cancelled copy takes the paks already
+    /// installed for the group back out, so no half a group is left behind.
+    pub fn install_mod_group_with(
+        &self,
+        pak_files: &[String],
+        group_name: &str,
+        category: ModCategory,
+        copy: &InstallCopy,
+    ) -> Result<InstallResult, String> {
+        if pak_files.is_empty() {
+            return Err("No files to install".to_string());
         }
-        log::info!("==========================================================");
-        log::info!("");
 
-        // Save metadata FIRST before any folder operations
-        self.save_metadata(mod_id, &metadata)?;
-        log::info!("✅ Metadata saved");
-        log::info!("");
+        let mut log = InstallLog::default();
+        let group_folder = sanitize_folder_name(group_name);
+        // Spaces becoming hyphens is the usual folder style, not worth a note
+        if group_folder != group_name.split_whitespace().collect::<Vec<_>>().join("-") {
+            log.warnings.push(format!(
+                "Folder \"{group_name}\" was renamed to \"{group_folder}\""
+            ));
+        }
+        let folder_path = self
+            .mods_directory
+            .join(sanitize_folder_name(&category.to_string()))
+            .join(group_folder);
+        let sources: Vec<&Path> = pak_files.iter().map(Path::new).collect();
+        self.check_install_space(&sources)?;
+        self.ensure_directory_exists(&folder_path)?;
 
-        // Get fresh mod data
-        let mods = self.get_all_mods()?;
-        let old_mod_option = mods.into_iter().find(|m| m.id == mod_id);
+        // Stable order so the parent choice is deterministic
+        let mut sorted: Vec<&String> = pak_files.iter().collect();
+        sorted.sort();
 
-        let old_mod = match old_mod_option {
-            Some(m) => {
-                log::info!("📍 Mod located at: {:?}", m.file_path);
-                log::info!("");
-                m
-            }
-            None => {
-                log::warn!("⚠️  Mod not found in scan, returning minimal info");
-                log::info!("");
-                log::info!("✅ METADATA UPDATE COMPLETE (mod not in scan)");
-                log::info!("==========================================================");
-                log::info!("");
-                return Ok(ModInfo {
-                    id: mod_id.to_string(),
-                    name: metadata.title.clone(),
-                    category: metadata.category.clone(),
-                    character: metadata.character.clone(),
-                    enabled: true,
-                    is_favorite: metadata.is_favorite,
-                    file_path: PathBuf::new(),
-                    thumbnail_path: None,
-                    file_size: 0,
-                    install_date: metadata.install_date,
-                    last_modified: Utc::now(),
-                    original_file_name: metadata.title.clone(),
-                    associated_files: Vec::new(),
-                    metadata: metadata.clone(),
-                });
-            }
+        let now = self.clock.now();
+        let base_metadata = |title: String, parent: Option<String>| ModMetadata {
+            title,
+            subtitle: None,
+            description: String::new(),
+            author: None,
+            version: None,
+            tags: Vec::new(),
+            category: category.clone(),
+            character: None,
+            costume: None,
+            is_favorite: false,
+            is_nsfw: false,
+            created_at: now,
+            updated_at: now,
+            install_date: now,
+            profile_ids: self.active_profile_id.clone().map(|id| vec![id]),
+            nexus_mod_id: None,
+            nexus_file_id: None,
+            nexus_version: None,
+            original_folder_path: folder_path
+                .strip_prefix(&self.mods_directory)
+                .ok()
+                .map(|p| p.to_string_lossy().to_string()),
+            parent_mod_id: parent,
+            disabled_reason: None,
+            disabled_at: None,
+            last_verified_game_build: None,
+            manage_layout: true,
+            file_fingerprint: None,
+            externally_modified_at: None,
+            collision_folder: None,
+            priority: None,
+            notes: None,
+            history: Vec::new(),
+            variant_group: None,
         };
 
-        // ==================== FOLDER RENAME CHECK ====================
-        log::info!("");
-        log::info!("📂 Checking if folder needs to be renamed...");
-        log::info!("   Mod: {}", metadata.title);
-        log::info!("");
-
-        let mod_file_path = PathBuf::from(&old_mod.file_path);
-        let parent_dir = mod_file_path.parent().ok_or("Invalid mod file path")?;
-        let is_in_folder = parent_dir != self.mods_directory;
+        let mut parent_id: Option<String> = None;
+        let mut parent_dest: Option<PathBuf> = None;
+        let mut saved_ids: Vec<String> = Vec::new();
 
-        if is_in_folder {
-            // Build expected folder structure
-            // All categories include character subfolder when character is specified
-            let mut folder_parts = vec![sanitize_folder_name(&metadata.category.to_string())];
+        for source in sorted {
+            let installed = self.install_group_member(
+                Path::new(source),
+                &folder_path,
+                &mut log,
+                copy,
+                |file_name| {
+                    let mut metadata = match &parent_id {
+                        None => base_metadata(group_name.to_string(), None),
+                        Some(pid) => {
+                            base_metadata(self.extract_mod_name(file_name), Some(pid.clone()))
+                        }
+                    };
+                    metadata.record_history(
+                        now,
+                        ModHistoryAction::Installed,
+                        Some(file_name.to_string()),
+                    );
+                    metadata
+                },
+            );
+            let (mod_id, dest_path) = match installed {
+                Ok(installed) => installed,
+                Err(e) => {
+                    for file in &log.copied_files {
+                        let _ = fs_ops::remove_file(&file.path);
+                    }
+                    for mod_id in &saved_ids {
+                        let _ = self.delete_metadata(mod_id);
+                    }
+                    let _ = fs::remove_dir(&folder_path);
+                    return Err(e);
+                }
+            };
+            saved_ids.push(mod_id.clone());
 
-            // Include character subfolder if character is specified
-            if let Some(ref character) = metadata.character {
-                folder_parts.push(sanitize_folder_name(&character.to_string()));
+            if parent_id.is_none() {
+                parent_id = Some(mod_id);
+                parent_dest = Some(dest_path);
             }
+        }
 
-            // Include costume in folder name if specified to avoid conflicts
-            // e.g., "Cool-Mod-Phoenix-Demon" instead of just "Cool-Mod"
-            let folder_name = if let Some(ref costume) = metadata.costume {
-                format!("{}-{}", metadata.title, costume)
-            } else {
-                metadata.title.clone()
-            };
-            let sanitized_folder = sanitize_folder_name(&folder_name);
-            // Add-ons get an "aa-" prefix so they mount BEFORE their parent pak.
-            // For these body retexture conflicts the first mounted pak wins, so
-            // the add-on must load ahead of the parent it overrides.
-            let sanitized_folder = if metadata.parent_mod_id.is_some() {
-                format!("aa-{sanitized_folder}")
-            } else {
-                sanitized_folder
-            };
-            folder_parts.push(sanitized_folder);
-            let new_folder = folder_parts
-                .iter()
-                .fold(self.mods_directory.clone(), |path, part| path.join(part));
+        let parent_path = parent_dest.ok_or("Group install produced no parent")?;
+        let parent_name = parent_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(group_name)
+            .to_string();
+        let metadata_fs = fs::metadata(&parent_path)
+            .map_err(|e| format!("Failed to read parent metadata: {e}"))?;
+        let associated_files = self.find_associated_files(&parent_path).unwrap_or_default();
+        let associated_file_sizes = file_sizes(&associated_files);
+        let parent_id = parent_id.ok_or("Group install produced no parent id")?;
+        let metadata = self
+            .load_metadata(&parent_id)
+            .ok()
+            .flatten()
+            .ok_or("Parent metadata missing after group install")?;
 
-            log::info!(
-                "   Current folder:  {:?}",
-                parent_dir.file_name().unwrap_or_default()
-            );
-            log::info!(
-                "   Expected folder: {:?}",
-                new_folder.file_name().unwrap_or_default()
-            );
-            log::info!("");
+        log::info!(
+            "[group-install] Installed {} pak(s) as \"{}\" ({} add-ons)",
+            pak_files.len(),
+            group_name,
+            pak_files.len().saturating_sub(1)
+        );
 
-            if parent_dir != new_folder {
-                // Check if there are multiple .pak files in the current folder
-                let pak_count = fs::read_dir(parent_dir)
-                    .map(|entries| {
-                        entries
-                            .filter_map(|e| e.ok())
-                            .filter(|e| {
-                                e.path()
-                                    .extension()
-                                    .and_then(|ext| ext.to_str())
-                                    .map(|ext| ext == "pak")
-                                    .unwrap_or(false)
-                            })
-                            .count()
-                    })
-                    .unwrap_or(0);
+        Ok(log.finish(ModInfo {
+            id: parent_id.clone(),
+            name: metadata.title.clone(),
+            category: metadata.category.clone(),
+            character: None,
+            enabled: true,
+            location: ModLocation::Active,
+            duplicate_of: None,
+            is_favorite: false,
+            disabled_reason: None,
+            file_path: parent_path,
+            thumbnail_path: self.find_thumbnail(&parent_id),
+            file_size: total_size(&associated_file_sizes),
+            pak_size: metadata_fs.len(),
+            associated_file_sizes,
+            install_date: metadata.install_date,
+            last_modified: metadata_fs.modified().ok().map(|t| t.into()).unwrap_or(now),
+            original_file_name: parent_name,
+            associated_files,
+            metadata,
+        }))
+    }
 
-                if pak_count > 1 {
-                    // Multiple .pak files exist - move only this mod's files to a new folder
-                    log::info!("   ⚠️  Multiple mods in folder ({} .pak files)", pak_count);
-                    log::info!("   🔄 Moving only this mod's files to new folder...");
+    /// Copy one pak of a group (companion .ucas/.utoc travel with it) and
+    /// save the metadata `metadata_for` gives its file name. Returns its ID
+    /// and where it landed.
+    fn install_group_member(
+        &self,
+        source: &Path,
+        folder_path: &Path,
+        log: &mut InstallLog,
+        copy: &InstallCopy,
+        metadata_for: impl FnOnce(&str) -> ModMetadata,
+    ) -> Result<(String, PathBuf), String> {
+        let dest_path = self.copy_mod_files(source, folder_path, log, copy)?;
+        let file_name = dest_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or("Invalid file name in group")?;
+        let mod_id = self.generate_mod_id_from_path(&dest_path, file_name);
+        self.save_metadata(&mod_id, &metadata_for(file_name))?;
+        Ok((mod_id, dest_path))
+    }
 
-                    // Create the new folder
-                    fs::create_dir_all(&new_folder)
-                        .map_err(|e| format!("Failed to create new folder: {}", e))?;
+    /// Migrate metadata and thumbnails from old filename-based IDs to new path-based IDs
+    /// This is a one-time migration for existing mods when switching ID generation methods
+    ///
+    /// `mod_ids` limits the run to those mods; `dry_run` only reports what
+    /// would happen. A checkpoint left by an interrupted run is finished first.
+    pub fn migrate_metadata_to_path_ids(
+        &self,
+        mod_ids: Option<&[String]>,
+        dry_run: bool,
+    ) -> Result<IdMigrationReport, String> {
+        self.flush_pending_metadata()?;
+        log::info!("🔍 Checking for metadata migration...");
+        let mut report = IdMigrationReport {
+            dry_run,
+            ..Default::default()
+        };
 
-                    // Move this mod's .pak file
-                    let pak_file_name = mod_file_path.file_name().ok_or("Invalid pak file name")?;
-                    let new_pak_path = new_folder.join(pak_file_name);
-                    fs::rename(&mod_file_path, &new_pak_path)
-                        .map_err(|e| format!("Failed to move pak file: {}", e))?;
+        let checkpoint_path = self.metadata_directory.join(ID_MIGRATION_CHECKPOINT);
+        if !dry_run {
+            if let Some(checkpoint) = read_id_migration_checkpoint(&checkpoint_path)? {
+                log::info!(
+                    "   Resuming interrupted migration ({} mod(s) left)",
+                    checkpoint.remaining.len()
+                );
+                for (old_id, new_id) in &checkpoint.remaining {
+                    self.move_metadata_id(old_id, new_id)?;
+                    report.resumed_count += 1;
+                }
+                let _ = fs::remove_file(&checkpoint_path);
+            }
+        }
 
-                    // Move associated files (same base name, different extensions)
-                    if let Some(base_name) = mod_file_path.file_stem() {
-                        for associated_file in &old_mod.associated_files {
-                            let associated_path = PathBuf::from(associated_file);
-                            if let Some(assoc_stem) = associated_path.file_stem() {
-                                if assoc_stem == base_name {
-                                    if let Some(file_name) = associated_path.file_name() {
-                                        let new_assoc_path = new_folder.join(file_name);
-                                        let _ = fs::rename(&associated_path, &new_assoc_path);
-                                    }
-                                }
-                            }
-                        }
-                    }
+        // Walk the pak files rather than get_all_mods, which saves fresh
+        // metadata under the new ID for every mod and would hide the old one
+        let mod_files: Vec<PathBuf> = [&self.mods_directory, &self.disabled_mods_directory]
+            .into_iter()
+            .flat_map(|dir| WalkDir::new(dir).into_iter().filter_map(|e| e.ok()))
+            .map(|entry| entry.into_path())
+            .filter(|path| path.is_file() && self.is_mod_file(path))
+            .collect();
 
-                    log::info!("   ✅ Mod files moved to new folder");
-                } else {
-                    // Single mod in folder - rename the entire folder
-                    log::info!("   ✅ RENAMING FOLDER");
+        let mut planned = Vec::new();
+        for file_path in mod_files {
+            let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
-                    // Create parent directories for new location
-                    if let Some(new_parent) = new_folder.parent() {
-                        fs::create_dir_all(new_parent)
-                            .map_err(|e| format!("Failed to create parent directory: {}", e))?;
-                    }
+            let clean_file_name = file_name.replace(".disabled", "");
 
-                    // Try to rename the folder
-                    match fs::rename(parent_dir, &new_folder) {
-                        Ok(_) => {
-                            log::info!("   ✅ Folder renamed successfully");
-                        }
-                        Err(e) => {
-                            log::warn!("   ⚠️  Direct rename failed: {}", e);
-                            log::info!("   🔄 Using copy+delete fallback...");
+            // Calculate both IDs
+            let old_id = self.generate_mod_id(&clean_file_name);
+            let new_id = self.generate_mod_id_from_path(&file_path, &clean_file_name);
+            if mod_ids.is_some_and(|ids| !ids.contains(&new_id)) {
+                continue;
+            }
+            let name = self.extract_mod_name(&clean_file_name);
 
-                            self.copy_directory_recursive(parent_dir, &new_folder)?;
-                            self.delete_directory_with_retry(parent_dir, 3)?;
+            // Same IDs (shouldn't happen) or new metadata already there
+            let outcome = if old_id == new_id || self.load_metadata(&new_id)?.is_some() {
+                report.already_migrated_count += 1;
+                IdMigrationOutcome::AlreadyMigrated
+            } else if self.load_metadata(&old_id)?.is_some() {
+                log::info!(
+                    "Migrating metadata for mod: {} (old ID: {}, new ID: {})",
+                    name,
+                    old_id,
+                    new_id
+                );
+                report.migrated_count += 1;
+                planned.push((old_id.clone(), new_id.clone()));
+                IdMigrationOutcome::Migrated
+            } else {
+                report.not_found_count += 1;
+                IdMigrationOutcome::NoOldMetadata
+            };
 
-                            log::info!("   ✅ Folder moved successfully via copy+delete");
-                        }
-                    }
-                }
-                log::info!("");
+            report.entries.push(IdMigrationEntry {
+                mod_id: new_id,
+                old_id,
+                name,
+                outcome,
+            });
+        }
 
-                // Migrate metadata to new ID (file path changed)
-                let new_file_path = new_folder.join(mod_file_path.file_name().unwrap());
-                let file_name_str = mod_file_path.file_name().unwrap().to_string_lossy();
-                let new_mod_id = self.generate_mod_id_from_path(&new_file_path, &file_name_str);
+        if !dry_run && !planned.is_empty() {
+            // Record the whole plan up front and shrink it as mods finish
+            let mut checkpoint = IdMigrationCheckpoint { remaining: planned };
+            write_id_migration_checkpoint(&checkpoint_path, &checkpoint)?;
+            while let Some((old_id, new_id)) = checkpoint.remaining.first().cloned() {
+                self.move_metadata_id(&old_id, &new_id)?;
+                checkpoint.remaining.remove(0);
+                write_id_migration_checkpoint(&checkpoint_path, &checkpoint)?;
+            }
+            let _ = fs::remove_file(&checkpoint_path);
+        }
 
-                log::info!("   🔄 Migrating metadata...");
-                log::info!("      Old ID: {}", mod_id);
-                log::info!("      New ID: {}", new_mod_id);
+        if report.migrated_count > 0 {
+            log::info!(
+                "   ✅ {} metadata for {} mod(s)",
+                if dry_run { "Would migrate" } else { "Migrated" },
+                report.migrated_count
+            );
+        } else {
+            log::info!("   ✅ All metadata up to date");
+        }
+        if report.not_found_count > 0 {
+            log::info!(
+                "   {} mod(s) have no metadata under either ID",
+                report.not_found_count
+            );
+        }
+        Ok(report)
+    }
 
-                // Migrate metadata to new ID
-                if let Ok(Some(saved_metadata)) = self.load_metadata(mod_id) {
-                    self.save_metadata(&new_mod_id, &saved_metadata)?;
-                    log::info!("      ✅ Metadata migrated");
-                } else {
-                    // Fallback: save the current metadata with the new ID
-                    self.save_metadata(&new_mod_id, &metadata)?;
-                    log::info!("      ✅ Metadata saved with new ID (fallback)");
-                }
+    /// Move one mod's metadata and thumbnail from `old_id` to `new_id`. Safe
+    /// to repeat: each step checks what an earlier, interrupted try did.
+    fn move_metadata_id(&self, old_id: &str, new_id: &str) -> Result<(), String> {
+        if self.load_metadata(new_id)?.is_none() {
+            if let Some(old_metadata) = self.load_metadata(old_id)? {
+                self.save_metadata(new_id, &old_metadata)?;
+            }
+        }
 
-                // Copy thumbnail if it exists
-                let old_thumb_path = self
-                    .metadata_directory
-                    .join(format!("{}_thumbnail.png", mod_id));
-                if old_thumb_path.exists() {
-                    let new_thumb_path = self
-                        .metadata_directory
-                        .join(format!("{}_thumbnail.png", new_mod_id));
-                    let _ = fs::copy(&old_thumb_path, &new_thumb_path);
-                    let _ = fs::remove_file(&old_thumb_path);
-                    log::info!("      ✅ Thumbnail migrated");
-                }
+        let old_thumb_path = self
+            .metadata_directory
+            .join(format!("{}_thumbnail.png", old_id));
+        if old_thumb_path.exists() {
+            let new_thumb_path = self
+                .metadata_directory
+                .join(format!("{}_thumbnail.png", new_id));
+            if new_thumb_path.exists() {
+                let _ = fs_ops::remove_file(&old_thumb_path);
+            } else if let Err(e) = fs_ops::move_file(&old_thumb_path, &new_thumb_path) {
+                log::warn!("Failed to migrate thumbnail: {}", e);
+            }
+        }
+        self.migrate_thumbnail_source(old_id, new_id);
 
-                // Always clean up old metadata
-                let _ = self.delete_metadata(mod_id);
-                log::info!("      ✅ Old metadata cleaned up");
+        self.delete_metadata(old_id)
+    }
 
-                // Migrate addons that reference the old parent ID
-                self.migrate_addon_parent_ids(mod_id, &new_mod_id)?;
+    /// Copy metadata from an old mod ID to the current mod
+    /// This is useful for recovering metadata after ID system changes
+    pub fn copy_metadata_from_old_id(
+        &self,
+        current_mod_id: &str,
+        old_mod_id: &str,
+    ) -> Result<(), String> {
+        log::info!(
+            "Copying metadata from old ID {} to current ID {}",
+            old_mod_id,
+            current_mod_id
+        );
 
-                log::info!("");
-                log::info!("✅ METADATA UPDATE COMPLETE");
-                log::info!("==========================================================");
-                log::info!("");
+        // Load metadata from old ID
+        let old_metadata = self
+            .load_metadata(old_mod_id)?
+            .ok_or_else(|| format!("No metadata found for old ID: {}", old_mod_id))?;
 
-                // Return the mod info with new ID
-                let is_enabled = !file_name_str.ends_with(".disabled");
-                return self
-                    .create_mod_info(&new_file_path, &file_name_str, is_enabled, None)
-                    .ok_or_else(|| "Failed to create mod info after folder rename".to_string());
-            } else {
-                log::info!("   ℹ️  Folder name is already correct");
-                log::info!("");
-                log::info!("✅ METADATA UPDATE COMPLETE (no folder rename needed)");
-                log::info!("==========================================================");
-                log::info!("");
-            }
-        } else {
-            log::info!("   ℹ️  Mod is loose in ~mods directory (no folder to rename)");
-            log::info!("");
-            log::info!("✅ METADATA UPDATE COMPLETE (loose mod)");
-            log::info!("==========================================================");
-            log::info!("");
+        // Copy thumbnail BEFORE updating metadata (in case update fails)
+        let old_thumb_path = self
+            .metadata_directory
+            .join(format!("{}_thumbnail.png", old_mod_id));
+        if old_thumb_path.exists() {
+            let new_thumb_path = self
+                .metadata_directory
+                .join(format!("{}_thumbnail.png", current_mod_id));
+            fs_ops::copy_verified(&old_thumb_path, &new_thumb_path)
+                .map_err(|e| format!("Failed to copy thumbnail: {}", e))?;
+            log::info!("Copied thumbnail from {} to {}", old_mod_id, current_mod_id);
         }
 
-        // Return updated mod info (metadata already saved above)
-        self.find_mod_by_id(mod_id)?
-            .ok_or_else(|| "Mod not found after update".to_string())
-    }
+        // Save the metadata AND trigger folder rename if needed. This is a
+        // deliberate overwrite, so it skips update_metadata's conflict check.
+        self.write_metadata(current_mod_id, old_metadata)?;
+        log::info!(
+            "Successfully copied and applied metadata from {} to {}",
+            old_mod_id,
+            current_mod_id
+        );
 
-    /// Remove a profile ID from all mods that have it
-    /// Returns the number of mods that were updated
-    pub fn remove_profile_from_all_mods(&self, profile_id: &str) -> Result<usize, String> {
-        log::info!("Removing profile {} from all mods", profile_id);
+        Ok(())
+    }
 
-        // Get all mods
+    /// Move mod folders that sit directly under a category folder (e.g.
+    /// "Skins/MyMod") into their character subfolder ("Skins/Magik/MyMod")
+    /// when the character is known. Older app versions created folders
+    /// without the character level and nothing re-nested them afterwards.
+    /// Returns the number of folders moved.
+    pub fn relocate_misplaced_mods(&self) -> Result<usize, String> {
+        self.flush_pending_metadata()?;
         let all_mods = self.get_all_mods()?;
-        let mut updated_count = 0;
+        let mut moved = 0;
+        let mut id_remap: HashMap<String, String> = HashMap::new();
+        // Folders already moved this run (multi-pak folders appear once per pak)
+        let mut moved_dirs: HashSet<PathBuf> = HashSet::new();
 
-        // Iterate through all mods
         for mod_info in all_mods {
-            // Load metadata
-            if let Some(mut metadata) = self.load_metadata(&mod_info.id)? {
-                // Check if this mod has profile_ids and contains the target profile
-                if let Some(ref mut profile_ids) = metadata.profile_ids {
-                    if profile_ids.contains(&profile_id.to_string()) {
-                        // Remove the profile ID
-                        profile_ids.retain(|id| id != profile_id);
-
-                        // If profile_ids is now empty, set it to None
-                        if profile_ids.is_empty() {
-                            metadata.profile_ids = None;
-                        }
-
-                        // Save the updated metadata
-                        self.save_metadata(&mod_info.id, &metadata)?;
-                        updated_count += 1;
+            let Some(character) = &mod_info.metadata.character else {
+                continue; // can't place without a character
+            };
 
-                        log::debug!("Removed profile {} from mod: {}", profile_id, mod_info.name);
-                    }
-                }
+            let file_path = &mod_info.file_path;
+            if !file_path.starts_with(&self.mods_directory) {
+                continue; // disabled mods get re-organized when re-enabled
+            }
+            let Some(parent_dir) = file_path.parent() else {
+                continue;
+            };
+            if parent_dir == self.mods_directory {
+                continue; // loose pak, organize_mods handles those
+            }
+            if moved_dirs.contains(parent_dir) {
+                continue;
             }
-        }
-
-        log::info!("Removed profile {} from {} mods", profile_id, updated_count);
-        Ok(updated_count)
-    }
-
-    // ===== Private Helper Methods =====
 
-    fn scan_directory_with_deduplication(
-        &self,
-        dir_path: &Path,
-        mods: &mut Vec<ModInfo>,
-        processed_paths: &mut HashSet<PathBuf>,
-        processed_ids: &mut HashSet<String>,
-        is_enabled: bool,
-        thumbnail_index: &HashMap<String, PathBuf>,
-    ) -> Result<(), String> {
-        let mut file_count = 0;
-        let mut pak_count = 0;
+            // Only folders sitting directly under a category folder
+            let category_dir = self.mods_directory.join(sanitize_folder_name(
+                &mod_info.metadata.category.to_string(),
+            ));
+            if parent_dir.parent() != Some(category_dir.as_path()) {
+                continue;
+            }
 
-        for entry in WalkDir::new(dir_path)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
+            let char_folder = sanitize_folder_name(&character.to_string());
 
-            if entry.file_type().is_file() {
-                file_count += 1;
+            // Never touch the character folder itself (a pak sitting loose in
+            // it) or any folder that contains nested mod folders
+            if parent_dir.file_name().and_then(|n| n.to_str()) == Some(char_folder.as_str()) {
+                continue;
             }
-
-            if !entry.file_type().is_file() || !self.is_mod_file(path) {
+            let has_nested_mod_folders = WalkDir::new(parent_dir)
+                .min_depth(2)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .any(|e| e.path().extension().and_then(|x| x.to_str()) == Some("pak"));
+            if has_nested_mod_folders {
                 continue;
             }
 
-            pak_count += 1;
-
-            let normalized_path = path.to_path_buf();
-            if processed_paths.contains(&normalized_path) {
+            let char_dir = category_dir.join(&char_folder);
+            let Some(folder_name) = parent_dir.file_name() else {
+                continue;
+            };
+            let target = char_dir.join(folder_name);
+            if target.exists() {
+                log::warn!("[relocate] Target already exists, skipping: {:?}", target);
+                continue;
+            }
+            if let Err(e) = fs::create_dir_all(&char_dir) {
+                log::warn!("[relocate] Failed to create {:?}: {}", char_dir, e);
                 continue;
             }
 
-            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let source_dir = parent_dir.to_path_buf();
+            log::info!("[relocate] {:?} → {:?}", source_dir, target);
+            if let Err(e) = fs_ops::rename(&source_dir, &target) {
+                log::warn!("[relocate] Failed to move {:?}: {}", source_dir, e);
+                continue;
+            }
+            moved_dirs.insert(source_dir.clone());
 
-            if let Some(mod_info) =
-                self.create_mod_info(path, file_name, is_enabled, Some(thumbnail_index))
-            {
-                if processed_ids.contains(&mod_info.id) {
-                    continue;
-                }
+            // Migrate metadata for every pak that moved (path-based IDs changed)
+            self.migrate_moved_folder_metadata(&source_dir, &target, &mut id_remap);
+            moved += 1;
+        }
 
-                processed_paths.insert(normalized_path);
-                processed_ids.insert(mod_info.id.clone());
-                mods.push(mod_info);
-            } else {
-                log::warn!("⚠️  Failed to create mod info for: {:?}", path);
-            }
+        // Re-point add-ons whose parents (or themselves) moved
+        if !id_remap.is_empty() {
+            let _ = self.remap_addon_parent_ids(&id_remap);
         }
 
-        Ok(())
+        if moved > 0 {
+            log::info!(
+                "[relocate] Moved {} mod folder(s) into character folders",
+                moved
+            );
+        }
+        Ok(moved)
     }
 
-    fn create_mod_info(
+    /// Migrate metadata and thumbnails for every pak inside a folder that was
+    /// moved from `source_dir` to `target_dir`, recording old -> new IDs in
+    /// `id_remap` for a later add-on re-pointing pass.
+    fn migrate_moved_folder_metadata(
         &self,
-        file_path: &Path,
-        file_name: &str,
-        is_enabled: bool,
-        thumbnail_index: Option<&HashMap<String, PathBuf>>,
-    ) -> Option<ModInfo> {
-        let metadata_result = fs::metadata(file_path);
-        if metadata_result.is_err() {
-            return None;
+        source_dir: &Path,
+        target_dir: &Path,
+        id_remap: &mut HashMap<String, String>,
+    ) {
+        for pak_entry in WalkDir::new(target_dir).into_iter().filter_map(|e| e.ok()) {
+            let pak_path = pak_entry.path().to_path_buf();
+            let name = pak_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if !(name.ends_with(".pak") || name.ends_with(".pak.disabled")) {
+                continue;
+            }
+            let old_pak_path = match pak_path.strip_prefix(target_dir) {
+                Ok(rel) => source_dir.join(rel),
+                Err(_) => source_dir.join(name),
+            };
+            let old_id = self.generate_mod_id_from_path(&old_pak_path, name);
+            let new_id = self.generate_mod_id_from_path(&pak_path, name);
+            if old_id == new_id {
+                continue;
+            }
+            if let Ok(Some(metadata)) = self.load_metadata(&old_id) {
+                let _ = self.save_metadata(&new_id, &metadata);
+                let old_thumb = self
+                    .metadata_directory
+                    .join(format!("{}_thumbnail.png", old_id));
+                if old_thumb.exists() {
+                    let new_thumb = self
+                        .metadata_directory
+                        .join(format!("{}_thumbnail.png", new_id));
+                    let _ = fs_ops::move_file(&old_thumb, &new_thumb);
+                }
+                self.migrate_thumbnail_source(&old_id, &new_id);
+                let _ = self.delete_metadata(&old_id);
+            }
+            id_remap.insert(old_id, new_id);
         }
-        let metadata_fs = metadata_result.ok()?;
+    }
 
-        let clean_file_name = file_name.replace(".disabled", "");
-        // Use path-based ID to prevent duplicates when same mod installed multiple times
-        let mod_id = self.generate_mod_id_from_path(file_path, &clean_file_name);
+    /// Ensure add-on paks load BEFORE their parent mod. Marvel Rivals mounts
+    /// paks in alphabetical order of their full path, and for these body
+    /// retexture conflicts the FIRST mounted pak wins — an add-on sorting after
+    /// its parent is silently overridden in game. Fix: prefix the add-on's
+    /// folder with "aa-" so it mounts before the parent. Any legacy "zz-"
+    /// prefix (which forced the broken load-last behaviour) is stripped first.
+    /// Returns the number of add-ons adjusted.
+    pub fn enforce_addon_load_order(&self) -> Result<usize, String> {
+        self.flush_pending_metadata()?;
+        let all_mods = self.get_all_mods()?;
 
-        // Load metadata if exists, or create and SAVE new metadata
-        // This ensures every mod always has persisted metadata for enable/disable operations
-        let (metadata, is_new_metadata) = match self.load_metadata(&mod_id).ok().flatten() {
-            Some(m) => (m, false),
-            None => {
-                let category = self.detect_category_from_path(file_path, &clean_file_name);
-                let character = self.detect_character_from_path(file_path, &clean_file_name);
-                let now = Utc::now();
+        let path_by_id: HashMap<String, PathBuf> = all_mods
+            .iter()
+            .map(|m| (m.id.clone(), m.file_path.clone()))
+            .collect();
 
-                // Extract and store original folder path for enable/disable operations
-                let original_folder_path = if let Some(parent) = file_path.parent() {
-                    // Only store if the mod is in a subfolder (not root of ~mods or disabled-mods)
-                    if parent != &self.mods_directory && parent != &self.disabled_mods_directory {
-                        // Try to get relative path from ~mods directory
-                        if let Ok(relative) = parent.strip_prefix(&self.mods_directory) {
-                            Some(relative.to_string_lossy().to_string())
-                        } else if let Ok(relative) =
-                            parent.strip_prefix(&self.disabled_mods_directory)
-                        {
-                            Some(relative.to_string_lossy().to_string())
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
+        let mut adjusted = 0;
+        let mut id_remap: HashMap<String, String> = HashMap::new();
+        let mut moved_dirs: HashSet<PathBuf> = HashSet::new();
 
-                (
-                    ModMetadata {
-                        title: self.extract_mod_name(&clean_file_name),
-                        subtitle: None,
-                        description: String::new(),
-                        author: None,
-                        version: None,
-                        tags: Vec::new(),
-                        category,
-                        character,
-                        costume: None,
-                        is_favorite: false,
-                        is_nsfw: false,
-                        created_at: now,
-                        updated_at: now,
-                        install_date: now,
-                        profile_ids: None,
-                        nexus_mod_id: None,
-                        nexus_file_id: None,
-                        nexus_version: None,
-                        original_folder_path,
-                        parent_mod_id: None,
-                    },
-                    true,
-                )
+        for mod_info in &all_mods {
+            let Some(parent_id) = &mod_info.metadata.parent_mod_id else {
+                continue;
+            };
+            let Some(parent_path) = path_by_id.get(parent_id) else {
+                continue; // detached add-on, nothing to order against
+            };
+
+            let addon_path = &mod_info.file_path;
+            // Only enabled mods participate in the game's load order
+            if !addon_path.starts_with(&self.mods_directory)
+                || !parent_path.starts_with(&self.mods_directory)
+            {
+                continue;
             }
-        };
 
-        // Auto-save new metadata to ensure it persists for enable/disable operations
-        // This prevents the "Metadata not found" error when toggling mods
-        if is_new_metadata {
-            if let Err(e) = self.save_metadata(&mod_id, &metadata) {
+            let Some(addon_dir) = addon_path.parent() else {
+                continue;
+            };
+            if moved_dirs.contains(addon_dir) {
+                continue;
+            }
+            if addon_dir == self.mods_directory {
                 log::warn!(
-                    "[create_mod_info] Failed to auto-save metadata for {}: {}",
-                    mod_id,
-                    e
+                    "[load-order] Add-on '{}' is loose in ~mods, skipping",
+                    mod_info.metadata.title
                 );
-            } else {
-                log::debug!(
-                    "[create_mod_info] Auto-saved metadata for new mod: {}",
-                    mod_id
+                continue;
+            }
+            if Some(addon_dir) == parent_path.parent() {
+                // Same folder as the parent: filename order decides, and renaming
+                // the pak would break its .ucas/.utoc pairing. Leave it alone.
+                log::warn!(
+                    "[load-order] Add-on '{}' shares a folder with its parent, skipping",
+                    mod_info.metadata.title
                 );
+                continue;
+            }
+            let Some(folder_name) = addon_dir.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Some(folder_parent) = addon_dir.parent() else {
+                continue;
+            };
+
+            // Compute the base folder name without any of our ordering prefixes,
+            // so we can both upgrade legacy "zz-" folders and add "aa-" cleanly.
+            let base_name = folder_name
+                .strip_prefix("aa-")
+                .or_else(|| folder_name.strip_prefix("zz-"))
+                .unwrap_or(folder_name);
+
+            // The game compares full paths case-insensitively. The add-on already
+            // loads before its parent only if it sorts lower AND already carries
+            // our "aa-" prefix (so it won't drift back above the parent later).
+            let addon_key = addon_path.to_string_lossy().to_lowercase();
+            let parent_key = parent_path.to_string_lossy().to_lowercase();
+            if folder_name.starts_with("aa-") && addon_key < parent_key {
+                continue; // already loads before the parent
+            }
+
+            let target = folder_parent.join(format!("aa-{base_name}"));
+            if target == addon_dir {
+                continue; // nothing to do
+            }
+            if target.exists() {
+                log::warn!("[load-order] Target already exists, skipping: {:?}", target);
+                continue;
+            }
+
+            let source_dir = addon_dir.to_path_buf();
+            if let Err(e) = fs_ops::rename(&source_dir, &target) {
+                log::warn!("[load-order] Failed to rename {:?}: {}", source_dir, e);
+                continue;
             }
+            log::info!(
+                "[load-order] '{}' now loads before its parent ({:?})",
+                mod_info.metadata.title,
+                target.file_name().unwrap_or_default()
+            );
+            moved_dirs.insert(source_dir.clone());
+
+            self.migrate_moved_folder_metadata(&source_dir, &target, &mut id_remap);
+            adjusted += 1;
         }
 
-        let associated_files = self.find_associated_files(file_path).ok()?;
+        if !id_remap.is_empty() {
+            let _ = self.remap_addon_parent_ids(&id_remap);
+        }
 
-        // Look for thumbnail using cached index if available, otherwise fall back to disk checks
-        let thumbnail_path = if let Some(index) = thumbnail_index {
-            self.find_thumbnail_cached(&mod_id, &clean_file_name, index)
-        } else {
-            self.find_thumbnail(&mod_id, &clean_file_name)
-        };
+        if adjusted > 0 {
+            log::info!(
+                "[load-order] Adjusted load order for {} add-on(s)",
+                adjusted
+            );
+        }
+        Ok(adjusted)
+    }
 
-        Some(ModInfo {
-            id: mod_id.clone(),
-            name: metadata.title.clone(),
-            category: metadata.category.clone(),
-            character: metadata.character.clone(),
-            enabled: is_enabled,
-            is_favorite: metadata.is_favorite,
-            file_path: file_path.to_path_buf(),
-            thumbnail_path,
-            file_size: metadata_fs.len(),
-            install_date: metadata.install_date,
-            last_modified: metadata_fs
-                .modified()
-                .ok()
-                .map(|t| t.into())
-                .unwrap_or(Utc::now()),
-            original_file_name: clean_file_name,
-            associated_files,
-            metadata,
-        })
+    /// ~mods or disabled-mods, whichever `path` is in
+    fn root_of(&self, path: &Path) -> Option<&Path> {
+        [&self.disabled_mods_directory, &self.mods_directory]
+            .into_iter()
+            .map(PathBuf::as_path)
+            .find(|root| path.starts_with(root))
     }
 
-    /// Fast thumbnail lookup using pre-built index (HashMap lookups instead of filesystem exists() calls)
-    fn find_thumbnail_cached(
+    /// The load priority the `NNN_` prefix of `path`'s top segment under
+    /// its root gives
+    fn priority_of(&self, path: &Path) -> Option<u32> {
+        let root = self.root_of(path)?;
+        let top = path.strip_prefix(root).ok()?.components().next()?;
+        split_priority_prefix(top.as_os_str().to_str()?).0
+    }
+
+    /// Put a mod at `priority` in the load order by prefixing its top folder
+    /// under ~mods with `NNN_` (`050_Skins/Hulk/...`), or its pak and
+    /// companions when they sit loose at the root; `None` takes the prefix
+    /// off. The game mounts paks in order of their lowercased full path, so
+    /// only the top segment decides which of two conflicting mods in
+    /// different folders loads first. The move changes the path-based ID,
+    /// so the metadata moves with it.
+    pub fn set_mod_priority(&self, mod_id: &str, priority: Option<u32>) -> Result<ModInfo, String> {
+        self.flush_pending_metadata()?;
+        let mod_info = self.find_mod_by_id(mod_id)?.ok_or("Mod not found")?;
+        self.set_priority_inner(mod_info, priority)
+    }
+
+    /// Give `mod_ids_in_order` evenly spaced priorities, first loading
+    /// first, from one scan. Returns the mods as they are afterwards, in
+    /// the same order.
+    pub fn reorder_mods(&self, mod_ids_in_order: &[String]) -> Result<Vec<ModInfo>, String> {
+        self.flush_pending_metadata()?;
+        let count = mod_ids_in_order.len() as u32;
+        if count > MAX_PRIORITY {
+            return Err(format!("Can't order more than {} mods", MAX_PRIORITY));
+        }
+        let step = (MAX_PRIORITY / (count + 1)).clamp(1, PRIORITY_STEP);
+        let mut by_id: HashMap<String, ModInfo> = self
+            .get_all_mods()?
+            .into_iter()
+            .map(|m| (m.id.clone(), m))
+            .collect();
+        let mut reordered = Vec::with_capacity(mod_ids_in_order.len());
+        for (position, mod_id) in (1..).zip(mod_ids_in_order) {
+            let mod_info = by_id
+                .remove(mod_id)
+                .ok_or_else(|| format!("Mod not found: {}", mod_id))?;
+            reordered.push(self.set_priority_inner(mod_info, Some(position * step))?);
+        }
+        log::info!("[load-order] Reordered {} mod(s)", reordered.len());
+        Ok(reordered)
+    }
+
+    fn set_priority_inner(
         &self,
-        mod_id: &str,
-        file_name: &str,
-        index: &HashMap<String, PathBuf>,
-    ) -> Option<PathBuf> {
-        // Check new format: {mod_id}_thumbnail
-        let new_key = format!("{}_thumbnail", mod_id).to_lowercase();
-        if let Some(path) = index.get(&new_key) {
-            return Some(path.clone());
+        mod_info: ModInfo,
+        priority: Option<u32>,
+    ) -> Result<ModInfo, String> {
+        if priority.is_some_and(|p| p > MAX_PRIORITY) {
+            return Err(format!("Priority must be at most {}", MAX_PRIORITY));
+        }
+        let root = self
+            .root_of(&mod_info.file_path)
+            .ok_or("Mod is outside the mod folders")?;
+        let _scope = fs_ops::mod_scope(&mod_info.id);
+        let targets: Vec<PathBuf> = mod_info
+            .associated_files
+            .iter()
+            .map(|file| match file.strip_prefix(root) {
+                Ok(relative) => root.join(with_priority_prefix(relative, priority)),
+                Err(_) => file.clone(),
+            })
+            .collect();
+        let new_folder = targets[0].parent().ok_or("Invalid file path")?;
+        fs::create_dir_all(new_folder)
+            .map_err(|e| format!("Failed to create {:?}: {}", new_folder, e))?;
+        if let Err(e) = rename_all(&mod_info.associated_files, &targets) {
+            remove_emptied_folders(new_folder, root);
+            return Err(e);
+        }
+        if let Some(old_folder) = mod_info.file_path.parent() {
+            remove_emptied_folders(old_folder, root);
+        }
+
+        let new_path = &targets[0];
+        let new_id = self.migrate_mod_identity(&mod_info, new_path, &mod_info.metadata)?;
+        {
+            let _lock = lock_metadata();
+            let mut metadata = self
+                .load_metadata(&new_id)?
+                .unwrap_or_else(|| mod_info.metadata.clone());
+            metadata.priority = priority;
+            // Toggling goes back to the stored folder, which has to carry
+            // the prefix too
+            metadata.original_folder_path = metadata.original_folder_path.map(|folder| {
+                with_priority_prefix(Path::new(&folder), priority)
+                    .to_string_lossy()
+                    .to_string()
+            });
+            self.save_metadata(&new_id, &metadata)?;
         }
+        log::info!(
+            "[load-order] '{}' priority {:?} → {:?}",
+            mod_info.metadata.title,
+            mod_info.metadata.priority,
+            priority
+        );
+        self.mod_info_at(new_path, mod_info.enabled)
+    }
+
+    /// Migrate existing mods to the new costume-based folder structure
+    /// This renames folders from "ModName" to "ModName-Costume" when a costume is set
+    /// Returns the number of mods migrated
+    pub fn migrate_to_costume_folders(&self) -> Result<usize, String> {
+        self.flush_pending_metadata()?;
+        log::info!("🔍 Checking for mods needing costume folder migration...");
+        let all_mods = self.get_all_mods()?;
+        let mut migrated_count = 0;
+
+        for mod_info in all_mods {
+            // Skip if no costume is set - no migration needed
+            let costume = match &mod_info.metadata.costume {
+                Some(c) => c.clone(),
+                None => continue,
+            };
+
+            // Skip if mod is loose (not in a folder)
+            let file_path = &mod_info.file_path;
+            let parent_dir = match file_path.parent() {
+                Some(p) => p,
+                None => continue,
+            };
+
+            if parent_dir == self.mods_directory || parent_dir == self.disabled_mods_directory {
+                continue; // Loose mod, will be organized separately
+            }
+
+            // Get current folder name
+            let current_folder_name = match parent_dir.file_name().and_then(|n| n.to_str()) {
+                Some(name) => name.to_string(),
+                None => continue,
+            };
+
+            // Calculate expected folder name with costume
+            let expected_folder_name =
+                sanitize_folder_name(&format!("{}-{}", mod_info.name, costume));
+
+            // Skip if folder already has costume suffix
+            if current_folder_name == expected_folder_name {
+                continue;
+            }
+
+            // Skip if the folder name already ends with the costume name (already migrated)
+            let costume_suffix = format!("-{}", sanitize_folder_name(&costume));
+            if current_folder_name.ends_with(&costume_suffix) {
+                continue;
+            }
+
+            log::info!(
+                "   🔄 Migrating: {} → {}",
+                current_folder_name,
+                expected_folder_name
+            );
+
+            // Calculate new folder path
+            let parent_of_mod_folder = parent_dir.parent().ok_or("Invalid folder structure")?;
+            let new_folder = parent_of_mod_folder.join(&expected_folder_name);
 
-        // Check mod_id-based thumbnails
-        if let Some(path) = index.get(&mod_id.to_lowercase()) {
-            return Some(path.clone());
+            // Check if target folder already exists
+            if new_folder.exists() {
+                log::warn!(
+                    "      ⚠️  Target folder already exists, skipping: {:?}",
+                    new_folder
+                );
+                continue;
+            }
+
+            // Rename the folder
+            match fs_ops::rename(parent_dir, &new_folder) {
+                Ok(_) => {
+                    log::info!("      ✅ Folder renamed successfully");
+
+                    // Migrate metadata to new ID
+                    let new_pak_path = new_folder.join(file_path.file_name().unwrap());
+                    let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                    let new_mod_id = self.generate_mod_id_from_path(&new_pak_path, file_name);
+
+                    if new_mod_id != mod_info.id {
+                        // Save metadata under new ID
+                        if let Err(e) = self.save_metadata(&new_mod_id, &mod_info.metadata) {
+                            log::warn!("      ⚠️  Failed to save metadata: {}", e);
+                        }
+
+                        // Migrate thumbnail
+                        let old_thumb = self
+                            .metadata_directory
+                            .join(format!("{}_thumbnail.png", mod_info.id));
+                        if old_thumb.exists() {
+                            let new_thumb = self
+                                .metadata_directory
+                                .join(format!("{}_thumbnail.png", new_mod_id));
+                            let _ = fs_ops::move_file(&old_thumb, &new_thumb);
+                        }
+                        self.migrate_thumbnail_source(&mod_info.id, &new_mod_id);
+
+                        // Re-point any add-ons referencing the old parent ID
+                        if let Err(e) = self.migrate_addon_parent_ids(&mod_info.id, &new_mod_id) {
+                            log::warn!("      ⚠️  Failed to migrate addon parent IDs: {}", e);
+                        }
+
+                        // Delete old metadata
+                        let _ = self.delete_metadata(&mod_info.id);
+                        log::info!(
+                            "      ✅ Metadata migrated: {} → {}",
+                            mod_info.id,
+                            new_mod_id
+                        );
+                    }
+
+                    migrated_count += 1;
+                }
+                Err(e) => {
+                    log::warn!("      ⚠️  Failed to rename folder: {}", e);
+                }
+            }
+        }
+
+        if migrated_count > 0 {
+            log::info!(
+                "   ✅ Migrated {} mod(s) to costume folder structure",
+                migrated_count
+            );
+        } else {
+            log::info!("   ✅ All mods already using costume folder structure");
+        }
+
+        Ok(migrated_count)
+    }
+
+    /// Update mod metadata. `metadata.updated_at` is the revision the caller
+    /// edited: if the stored copy has moved on since (saved from another
+    /// window), the edit is rejected with a conflict carrying the stored copy
+    /// rather than silently overwriting it.
+    pub fn update_metadata(
+        &self,
+        mod_id: &str,
+        metadata: ModMetadata,
+    ) -> Result<ModInfo, MetadataUpdateError> {
+        if let Err(issues) = validate_metadata(&metadata, self.clock.now()) {
+            return Err(MetadataUpdateError::Invalid { issues });
+        }
+        let _lock = lock_metadata();
+        let current = self.load_metadata(mod_id)?;
+        if let Some(current) = &current {
+            if current.updated_at != metadata.updated_at {
+                log::warn!(
+                    "[metadata] Rejecting stale edit of {} (based on {}, stored {})",
+                    mod_id,
+                    metadata.updated_at,
+                    current.updated_at
+                );
+                return Err(MetadataUpdateError::MetadataConflict {
+                    current: Box::new(current.clone()),
+                });
+            }
+        }
+        let change = match current {
+            Some(current) if current.title != metadata.title.trim() => (
+                ModHistoryAction::Renamed,
+                Some(format!("{} → {}", current.title, metadata.title.trim())),
+            ),
+            _ => (ModHistoryAction::Updated, None),
+        };
+        Ok(self.write_metadata_with(mod_id, metadata, false, Some(change))?)
+    }
+
+    /// Change individual metadata fields, given by their camelCase names.
+    /// The stored copy is read, patched and written under the metadata lock,
+    /// so concurrent patches never conflict, even on the same mod.
+    pub fn patch_metadata(
+        &self,
+        mod_id: &str,
+        patch: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<ModInfo, String> {
+        let _lock = lock_metadata();
+        let current = match self.load_metadata(mod_id)? {
+            Some(metadata) => metadata,
+            None => {
+                self.find_mod_by_id(mod_id)?
+                    .ok_or_else(|| format!("Mod not found: {}", mod_id))?
+                    .metadata
+            }
+        };
+
+        let mut value = serde_json::to_value(&current)
+            .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+        let fields = value.as_object_mut().ok_or("Metadata is not an object")?;
+        fields.extend(patch.clone());
+        let metadata: ModMetadata =
+            serde_json::from_value(value).map_err(|e| format!("Invalid metadata patch: {}", e))?;
+
+        // Unknown fields would otherwise be dropped without a word
+        let applied = serde_json::to_value(&metadata)
+            .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+        for (field, value) in patch {
+            if applied.get(field).unwrap_or(&serde_json::Value::Null) != value {
+                return Err(format!("Unknown metadata field: {}", field));
+            }
+        }
+
+        self.write_metadata(mod_id, metadata)
+    }
+
+    /// A mod's metadata as pretty-printed JSON, for editing by hand
+    pub fn metadata_raw(&self, mod_id: &str) -> Result<String, String> {
+        let metadata = match self.load_metadata(mod_id)? {
+            Some(metadata) => metadata,
+            None => {
+                self.find_mod_by_id(mod_id)?
+                    .ok_or_else(|| format!("Mod not found: {}", mod_id))?
+                    .metadata
+            }
+        };
+        serde_json::to_string_pretty(&metadata)
+            .map_err(|e| format!("Failed to serialize metadata: {}", e))
+    }
+
+    /// Replace a mod's metadata with hand-edited JSON. It has to parse and
+    /// pass validation before anything is written; then it is saved like
+    /// any other edit, so the mod's ID, install date and folder rules hold.
+    pub fn set_metadata_raw(
+        &self,
+        mod_id: &str,
+        json: &str,
+    ) -> Result<ModInfo, MetadataUpdateError> {
+        let metadata = parse_metadata_json(json)?;
+        if let Err(issues) = validate_metadata(&metadata, self.clock.now()) {
+            return Err(MetadataUpdateError::Invalid { issues });
+        }
+        let _lock = lock_metadata();
+        if self.load_metadata(mod_id)?.is_none() && self.find_mod_by_id(mod_id)?.is_none() {
+            return Err(format!("Mod not found: {}", mod_id).into());
+        }
+        Ok(self.write_metadata(mod_id, metadata)?)
+    }
+
+    /// Flip a mod's favorite flag, returning the mod and the new number of
+    /// favorites
+    pub fn toggle_favorite(&self, mod_id: &str) -> Result<FavoriteToggle, String> {
+        let is_favorite = match self.load_metadata(mod_id)? {
+            Some(metadata) => metadata.is_favorite,
+            None => {
+                self.find_mod_by_id(mod_id)?
+                    .ok_or_else(|| format!("Mod not found: {}", mod_id))?
+                    .is_favorite
+            }
+        };
+        let mut patch = serde_json::Map::new();
+        patch.insert("isFavorite".to_string(), (!is_favorite).into());
+        let mod_info = self.patch_metadata(mod_id, &patch)?;
+        let favorites_count = self
+            .get_all_mods()?
+            .iter()
+            .filter(|m| m.is_favorite)
+            .count();
+        Ok(FavoriteToggle {
+            mod_info,
+            favorites_count,
+        })
+    }
+
+    /// Favorite mods by character (mods without one last), then title
+    pub fn get_favorites(&self) -> Result<Vec<ModInfo>, String> {
+        let mut favorites: Vec<ModInfo> = self
+            .get_all_mods()?
+            .into_iter()
+            .filter(|m| m.is_favorite)
+            .collect();
+        crate::sorting::sort_mods(
+            &mut favorites,
+            &SortSpec {
+                key: SortKey::Character,
+                direction: SortDirection::Asc,
+            },
+        );
+        Ok(favorites)
+    }
+
+    /// Validate and normalize metadata about to be written, logging what
+    /// was adjusted. Rejected input comes back as one line per issue.
+    fn validated(&self, metadata: ModMetadata) -> Result<ModMetadata, String> {
+        let normalized = validate_metadata(&metadata, self.clock.now())
+            .map_err(|issues| MetadataUpdateError::Invalid { issues }.to_string())?;
+        if !normalized.warnings.is_empty() {
+            log::warn!(
+                "[metadata] {}: {}",
+                normalized.metadata.title,
+                describe_issues(&normalized.warnings)
+            );
+        }
+        Ok(normalized.metadata)
+    }
+
+    /// Save metadata and move the mod's folder to match, without checking
+    /// for conflicting edits
+    fn write_metadata(&self, mod_id: &str, metadata: ModMetadata) -> Result<ModInfo, String> {
+        self.write_metadata_with(mod_id, metadata, false, None)
+    }
+
+    /// `write_metadata`; `check_layout` checks the folder even when the
+    /// metadata hasn't changed in a way that could move it, and `change` is
+    /// added to the mod's history
+    fn write_metadata_with(
+        &self,
+        mod_id: &str,
+        metadata: ModMetadata,
+        check_layout: bool,
+        change: Option<(ModHistoryAction, Option<String>)>,
+    ) -> Result<ModInfo, String> {
+        let mut metadata = self.validated(metadata)?;
+        log::info!("");
+        log::info!("==========================================================");
+        log::info!("📝 UPDATING MOD METADATA");
+        log::info!("   Title: {}", metadata.title);
+        log::info!("   Category: {}", metadata.category);
+        if let Some(ref character) = metadata.character {
+            log::info!("   Character: {}", character);
+        }
+        log::info!("==========================================================");
+        log::info!("");
+
+        // The frontend round-trips the whole metadata object, so a stale or
+        // regenerated copy must not reset when the mod was first installed,
+        // its priority, which follows the top folder, or its history, which
+        // only the manager writes
+        let existing = self.load_metadata(mod_id)?;
+        if let Some(existing) = &existing {
+            metadata.install_date = existing.install_date;
+            metadata.created_at = existing.created_at;
+            metadata.priority = existing.priority;
+            metadata.history.clone_from(&existing.history);
+        }
+        metadata.updated_at = self.clock.now();
+        if let Some((action, detail)) = change {
+            metadata.record_history(metadata.updated_at, action, detail);
+        }
+
+        // Favorite and profile toggles can't move the mod, so skip the folder
+        // checks below and let the write-behind batch the disk write
+        if !check_layout && existing.is_some_and(|existing| is_light_change(&existing, &metadata)) {
+            self.save_metadata_deferred(mod_id, metadata.clone())?;
+            if let Some(mod_info) = self.find_listed_mod(mod_id)? {
+                log::info!("✅ Metadata queued (light change)");
+                return Ok(mod_info);
+            }
+            // Not in the scan: fall through and report it like any other edit
+        } else {
+            self.flush_pending_metadata()?;
+        }
+
+        // Save metadata FIRST before any folder operations
+        self.save_metadata(mod_id, &metadata)?;
+        log::info!("✅ Metadata saved");
+        log::info!("");
+
+        // Get fresh mod data
+        let old_mod_option = self.find_listed_mod(mod_id)?;
+
+        let old_mod = match old_mod_option {
+            Some(m) => {
+                log::info!("📍 Mod located at: {:?}", m.file_path);
+                log::info!("");
+                m
+            }
+            None => {
+                log::warn!("⚠️  Mod not found in scan, returning minimal info");
+                log::info!("");
+                log::info!("✅ METADATA UPDATE COMPLETE (mod not in scan)");
+                log::info!("==========================================================");
+                log::info!("");
+                return Ok(ModInfo {
+                    id: mod_id.to_string(),
+                    name: metadata.title.clone(),
+                    category: metadata.category.clone(),
+                    character: metadata.character.clone(),
+                    enabled: true,
+                    location: ModLocation::Active,
+                    duplicate_of: None,
+                    is_favorite: metadata.is_favorite,
+                    disabled_reason: None,
+                    file_path: PathBuf::new(),
+                    thumbnail_path: None,
+                    file_size: 0,
+                    pak_size: 0,
+                    associated_file_sizes: Vec::new(),
+                    install_date: metadata.install_date,
+                    last_modified: self.clock.now(),
+                    original_file_name: metadata.title.clone(),
+                    associated_files: Vec::new(),
+                    metadata: metadata.clone(),
+                });
+            }
+        };
+
+        // ==================== FOLDER RENAME CHECK ====================
+        log::info!("");
+        log::info!("📂 Checking if folder needs to be renamed...");
+        log::info!("   Mod: {}", metadata.title);
+        log::info!("");
+
+        let mod_file_path = PathBuf::from(&old_mod.file_path);
+        let parent_dir = mod_file_path.parent().ok_or("Invalid mod file path")?;
+        let is_in_folder = parent_dir != self.mods_directory;
+
+        if is_in_folder && !metadata.manage_layout {
+            log::info!("   ℹ️  Folder layout not managed for this mod, leaving it in place");
+            log::info!("");
+            log::info!("✅ METADATA UPDATE COMPLETE (layout not managed)");
+            log::info!("==========================================================");
+            log::info!("");
+        } else if is_in_folder {
+            let new_folder = self.expected_folder_for(&metadata);
+
+            log::info!(
+                "   Current folder:  {:?}",
+                parent_dir.file_name().unwrap_or_default()
+            );
+            log::info!(
+                "   Expected folder: {:?}",
+                new_folder.file_name().unwrap_or_default()
+            );
+            log::info!("");
+
+            if parent_dir != new_folder {
+                // Check if there are multiple .pak files in the current folder
+                let pak_count = fs::read_dir(parent_dir)
+                    .map(|entries| {
+                        entries
+                            .filter_map(|e| e.ok())
+                            .filter(|e| {
+                                e.path()
+                                    .extension()
+                                    .and_then(|ext| ext.to_str())
+                                    .map(|ext| ext == "pak")
+                                    .unwrap_or(false)
+                            })
+                            .count()
+                    })
+                    .unwrap_or(0);
+
+                if pak_count > 1 {
+                    // Multiple .pak files exist - move only this mod's files to a new folder
+                    log::info!("   ⚠️  Multiple mods in folder ({} .pak files)", pak_count);
+                    log::info!("   🔄 Moving only this mod's files to new folder...");
+
+                    // Create the new folder
+                    fs::create_dir_all(&new_folder)
+                        .map_err(|e| format!("Failed to create new folder: {}", e))?;
+
+                    // Move this mod's .pak file
+                    let pak_file_name = mod_file_path.file_name().ok_or("Invalid pak file name")?;
+                    let new_pak_path = new_folder.join(pak_file_name);
+                    fs_ops::rename(&mod_file_path, &new_pak_path)
+                        .map_err(|e| format!("Failed to move pak file: {}", e))?;
+
+                    // Move associated files (same base name, different extensions)
+                    if let Some(base_name) = mod_file_path.file_stem() {
+                        for associated_file in &old_mod.associated_files {
+                            let associated_path = PathBuf::from(associated_file);
+                            if let Some(assoc_stem) = associated_path.file_stem() {
+                                if assoc_stem == base_name {
+                                    if let Some(file_name) = associated_path.file_name() {
+                                        let new_assoc_path = new_folder.join(file_name);
+                                        let _ = fs_ops::rename(&associated_path, &new_assoc_path);
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    log::info!("   ✅ Mod files moved to new folder");
+                } else {
+                    // Single mod in folder - rename the entire folder
+                    log::info!("   ✅ RENAMING FOLDER");
+
+                    // Create parent directories for new location
+                    if let Some(new_parent) = new_folder.parent() {
+                        fs::create_dir_all(new_parent)
+                            .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+                    }
+
+                    // Try to rename the folder
+                    match fs_ops::rename(parent_dir, &new_folder) {
+                        Ok(_) => {
+                            log::info!("   ✅ Folder renamed successfully");
+                        }
+                        Err(e) => {
+                            log::warn!("   ⚠️  Direct rename failed: {}", e);
+                            log::info!("   🔄 Using copy+delete fallback...");
+
+                            let summary = fs_ops::copy_directory_recursive(
+                                parent_dir,
+                                &new_folder,
+                                &ProgressEmitter::new(|_: DirectoryCopyProgress| {}),
+                                &CancellationToken::new(),
+                            )?;
+                            log::info!(
+                                "   📦 Copied {} file(s), {} bytes in {} ms",
+                                summary.files_copied,
+                                summary.bytes_copied,
+                                summary.duration
+                            );
+                            fs_ops::remove_dir_all_retry(parent_dir, 3)?;
+
+                            log::info!("   ✅ Folder moved successfully via copy+delete");
+                        }
+                    }
+                }
+                log::info!("");
+
+                // Both branches moved the pak (file path changed, so the ID did)
+                let new_file_path = new_folder.join(mod_file_path.file_name().unwrap());
+                let file_name_str = mod_file_path.file_name().unwrap().to_string_lossy();
+                self.migrate_mod_identity(&old_mod, &new_file_path, &metadata)?;
+
+                log::info!("");
+                log::info!("✅ METADATA UPDATE COMPLETE");
+                log::info!("==========================================================");
+                log::info!("");
+
+                // Return the mod info with new ID
+                let is_enabled = !file_name_str.ends_with(".disabled");
+                return self
+                    .create_mod_info(&new_file_path, &file_name_str, is_enabled, None)
+                    .ok_or_else(|| "Failed to create mod info after folder rename".to_string());
+            } else {
+                log::info!("   ℹ️  Folder name is already correct");
+                log::info!("");
+                log::info!("✅ METADATA UPDATE COMPLETE (no folder rename needed)");
+                log::info!("==========================================================");
+                log::info!("");
+            }
+        } else {
+            log::info!("   ℹ️  Mod is loose in ~mods directory (no folder to rename)");
+            log::info!("");
+            log::info!("✅ METADATA UPDATE COMPLETE (loose mod)");
+            log::info!("==========================================================");
+            log::info!("");
+        }
+
+        // Return updated mod info (metadata already saved above)
+        self.find_mod_by_id(mod_id)?
+            .ok_or_else(|| "Mod not found after update".to_string())
+    }
+
+    /// Carry everything tied to a mod's ID or paths over once its files have
+    /// moved next to `new_path`: metadata (and with it the profile
+    /// memberships), thumbnail and its original, cached hashes, add-ons
+    /// pointing at it and collection memberships. `fallback` is saved when
+    /// nothing is stored under the old ID. Returns the new ID.
+    fn migrate_mod_identity(
+        &self,
+        old_mod: &ModInfo,
+        new_path: &Path,
+        fallback: &ModMetadata,
+    ) -> Result<String, String> {
+        let old_id = old_mod.id.as_str();
+        let file_name = new_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or("Invalid mod file path")?;
+        let new_id = self.generate_mod_id_from_path(new_path, file_name);
+        log::info!("   🔄 Migrating metadata...");
+        log::info!("      Old ID: {}", old_id);
+        log::info!("      New ID: {}", new_id);
+        if new_id == old_id {
+            return Ok(new_id);
+        }
+
+        let metadata = match self.load_metadata(old_id) {
+            Ok(Some(saved)) => saved,
+            _ => fallback.clone(),
+        };
+        self.save_metadata(&new_id, &metadata)?;
+
+        let old_thumb_path = self
+            .metadata_directory
+            .join(format!("{}_thumbnail.png", old_id));
+        if old_thumb_path.exists() {
+            let new_thumb_path = self
+                .metadata_directory
+                .join(format!("{}_thumbnail.png", new_id));
+            if let Err(e) = fs_ops::move_file(&old_thumb_path, &new_thumb_path) {
+                log::warn!("      Failed to migrate thumbnail: {}", e);
+            }
+        }
+        self.migrate_thumbnail_source(old_id, &new_id);
+
+        // A move keeps size and modification time, so the hashes still hold
+        if let Some(new_folder) = new_path.parent() {
+            let moves: Vec<(PathBuf, PathBuf)> = old_mod
+                .associated_files
+                .iter()
+                .filter_map(|old| Some((old.clone(), new_folder.join(old.file_name()?))))
+                .collect();
+            if let Err(e) = hash_cache::rename_paths(&self.hash_cache_path(), &moves) {
+                log::warn!("      Failed to migrate cached hashes: {}", e);
+            }
+        }
+
+        let _ = self.delete_metadata(old_id);
+        // Also re-points collection memberships
+        self.migrate_addon_parent_ids(old_id, &new_id)?;
+        log::info!("      ✅ Metadata, thumbnail and references migrated");
+        Ok(new_id)
+    }
+
+    /// Move every mod in `from` (optionally narrowed by `filter`) to `to`.
+    /// Enabled mods go through `update_metadata`, which moves their folders
+    /// under the new category; disabled mods stay put and only have their
+    /// remembered folder re-pointed, so they land in the right place when
+    /// re-enabled. Returns the number of mods reassigned.
+    pub fn reassign_category(
+        &self,
+        from: &ModCategory,
+        to: &ModCategory,
+        filter: Option<&ModQuery>,
+    ) -> Result<usize, String> {
+        if from == to {
+            return Ok(0);
+        }
+        self.flush_pending_metadata()?;
+        let targets: Vec<ModInfo> = self
+            .get_all_mods()?
+            .into_iter()
+            .filter(|m| &m.metadata.category == from && filter.is_none_or(|q| q.matches(m)))
+            .collect();
+        log::info!(
+            "[category] Reassigning {} mod(s) from {} to {}",
+            targets.len(),
+            from,
+            to
+        );
+
+        let mut reassigned = 0;
+        for mod_info in targets {
+            // Re-read: moving an earlier parent re-points its add-ons on disk,
+            // and the scanned copy would undo that
+            let mut metadata = self
+                .load_metadata(&mod_info.id)?
+                .unwrap_or(mod_info.metadata);
+            metadata.category = to.clone();
+
+            let result = if mod_info.enabled {
+                self.update_metadata(&mod_info.id, metadata)
+                    .map(|_| ())
+                    .map_err(String::from)
+            } else {
+                metadata.original_folder_path = metadata
+                    .original_folder_path
+                    .map(|folder| recategorize_folder(&folder, from, to));
+                metadata.updated_at = self.clock.now();
+                self.save_metadata(&mod_info.id, &metadata)
+            };
+            match result {
+                Ok(()) => reassigned += 1,
+                Err(e) => log::warn!("[category] Failed to reassign {}: {}", mod_info.id, e),
+            }
+        }
+
+        log::info!("[category] Reassigned {} mod(s)", reassigned);
+        Ok(reassigned)
+    }
+
+    /// Find-and-replace in one text field across the library, optionally
+    /// narrowed by `filter`: e.g. strip a pack's "[CoolPack] " prefix from
+    /// every title. Returns each changed mod's field before and after; with
+    /// `dry_run` nothing is saved. Changed titles are all saved first and
+    /// their folders renamed in one layout pass at the end.
+    pub fn find_replace_metadata(
+        &self,
+        field: MetadataTextField,
+        find: &str,
+        replace: &str,
+        filter: Option<&ModQuery>,
+        regex: bool,
+        dry_run: bool,
+    ) -> Result<Vec<MetadataReplacement>, String> {
+        let replacer = TextReplacer::new(find, replace, regex)?;
+        self.flush_pending_metadata()?;
+
+        let mut replacements = Vec::new();
+        let mut retitled = Vec::new();
+        for mod_info in self.get_all_mods()? {
+            if filter.is_some_and(|q| !q.matches(&mod_info)) {
+                continue;
+            }
+            let _lock = lock_metadata();
+            let mut metadata = self
+                .load_metadata(&mod_info.id)?
+                .unwrap_or(mod_info.metadata);
+            let before = metadata_field_text(&metadata, field);
+            replacer.apply(&mut metadata, field)?;
+            let mut metadata = match validate_metadata(&metadata, self.clock.now()) {
+                Ok(normalized) => normalized.metadata,
+                Err(issues) => {
+                    log::warn!(
+                        "[find-replace] Skipping {}: {}",
+                        mod_info.id,
+                        describe_issues(&issues)
+                    );
+                    continue;
+                }
+            };
+            let after = metadata_field_text(&metadata, field);
+            if after == before {
+                continue;
+            }
+
+            if !dry_run {
+                metadata.updated_at = self.clock.now();
+                self.save_metadata(&mod_info.id, &metadata)?;
+                if field == MetadataTextField::Title && mod_info.enabled && metadata.manage_layout {
+                    retitled.push(mod_info.id.clone());
+                }
+            }
+            replacements.push(MetadataReplacement {
+                mod_id: mod_info.id,
+                name: mod_info.name,
+                before,
+                after,
+            });
+        }
+
+        if !retitled.is_empty() {
+            self.apply_layout(&retitled)?;
+        }
+        log::info!(
+            "[find-replace] {} {} mod(s)",
+            if dry_run { "Would change" } else { "Changed" },
+            replacements.len()
+        );
+        Ok(replacements)
+    }
+
+    /// Set the character, and the costume or none, of the given mods at
+    /// once: "these 12 paks are all Psylocke". Metadata is saved for all of
+    /// them first; then the enabled ones whose layout is managed move in one
+    /// layout pass, with each character folder created once. Disabled mods
+    /// stay put and have their remembered folder re-pointed, like
+    /// `reassign_category`. Refused if `game_running` and any of the mods is
+    /// enabled, since the game holds their files. Returns every assigned mod
+    /// with the ID it had before.
+    pub fn assign_character(
+        &self,
+        mod_ids: &[String],
+        character: &Character,
+        costume: Option<&str>,
+        game_running: bool,
+    ) -> Result<Vec<ToggledMod>, AssignCharacterError> {
+        if let Some(costume) = costume {
+            if crate::costume_service::costume_exists(&character.to_string(), costume)
+                == Some(false)
+            {
+                return Err(AssignCharacterError::UnknownCostume {
+                    character: character.to_string(),
+                    costume: costume.to_string(),
+                });
+            }
+        }
+        self.flush_pending_metadata()?;
+        let targets: Vec<ModInfo> = self
+            .get_all_mods()?
+            .into_iter()
+            .filter(|m| mod_ids.contains(&m.id))
+            .collect();
+        if game_running {
+            let offenders: Vec<AssignOffender> = targets
+                .iter()
+                .filter(|m| m.enabled)
+                .map(|m| AssignOffender {
+                    mod_id: m.id.clone(),
+                    title: m.metadata.title.clone(),
+                })
+                .collect();
+            if !offenders.is_empty() {
+                return Err(AssignCharacterError::GameRunning { mods: offenders });
+            }
+        }
+        log::info!(
+            "[assign] Assigning {} mod(s) to {}{}",
+            targets.len(),
+            character,
+            costume.map(|c| format!(" ({c})")).unwrap_or_default()
+        );
+
+        // Character folder -> mods moving under it
+        let mut moves: BTreeMap<PathBuf, Vec<String>> = BTreeMap::new();
+        let mut assigned = Vec::new();
+        for mod_info in targets {
+            let _lock = lock_metadata();
+            let mut metadata = self
+                .load_metadata(&mod_info.id)?
+                .unwrap_or_else(|| mod_info.metadata.clone());
+            metadata.character = Some(character.clone());
+            metadata.costume = costume.map(str::to_string);
+            let mut metadata = validate_metadata(&metadata, self.clock.now())
+                .map_err(|issues| describe_issues(&issues))?
+                .metadata;
+            metadata.updated_at = self.clock.now();
+
+            let expected_folder = self.expected_folder_for(&metadata);
+            let current_folder = mod_info.file_path.parent().unwrap_or(&self.mods_directory);
+            // Mods whose layout was accepted stay where the user put them
+            let managed = metadata.manage_layout;
+            if managed && !mod_info.enabled && metadata.original_folder_path.is_some() {
+                metadata.original_folder_path = expected_folder
+                    .strip_prefix(&self.mods_directory)
+                    .ok()
+                    .map(|relative| relative.to_string_lossy().to_string());
+            } else if managed
+                && mod_info.enabled
+                && current_folder != self.mods_directory
+                && current_folder != expected_folder
+            {
+                if let Some(character_folder) = expected_folder.parent() {
+                    moves
+                        .entry(character_folder.to_path_buf())
+                        .or_default()
+                        .push(mod_info.id.clone());
+                }
+            }
+            self.save_metadata(&mod_info.id, &metadata)?;
+            assigned.push(mod_info.id);
+        }
+
+        let mut moved_ids = Vec::new();
+        for (character_folder, ids) in moves {
+            fs::create_dir_all(&character_folder)
+                .map_err(|e| format!("Failed to create folder {:?}: {}", character_folder, e))?;
+            moved_ids.extend(ids);
+        }
+        let moved: HashMap<String, ModInfo> = if moved_ids.is_empty() {
+            HashMap::new()
+        } else {
+            self.apply_layout(&moved_ids)?
+                .into_iter()
+                .map(|m| (m.previous_id, m.mod_info))
+                .collect()
+        };
+
+        let mut current: HashMap<String, ModInfo> = self
+            .get_all_mods()?
+            .into_iter()
+            .map(|m| (m.id.clone(), m))
+            .collect();
+        let assigned: Vec<ToggledMod> = assigned
+            .into_iter()
+            .filter_map(|previous_id| {
+                let mod_info = match moved.get(&previous_id) {
+                    Some(moved) => current.remove(&moved.id),
+                    None => current.remove(&previous_id),
+                }?;
+                Some(ToggledMod {
+                    previous_id,
+                    mod_info,
+                })
+            })
+            .collect();
+        log::info!(
+            "[assign] Assigned {} mod(s), moved {}",
+            assigned.len(),
+            moved.len()
+        );
+        Ok(assigned)
+    }
+
+    /// Remove a profile ID from all mods that have it
+    /// Returns the number of mods that were updated
+    pub fn remove_profile_from_all_mods(&self, profile_id: &str) -> Result<usize, String> {
+        log::info!("Removing profile {} from all mods", profile_id);
+
+        // Get all mods
+        let all_mods = self.get_all_mods()?;
+        let mut updated_count = 0;
+
+        // Iterate through all mods
+        for mod_info in all_mods {
+            // Load metadata
+            if let Some(mut metadata) = self.load_metadata(&mod_info.id)? {
+                // Check if this mod has profile_ids and contains the target profile
+                if let Some(ref mut profile_ids) = metadata.profile_ids {
+                    if profile_ids.contains(&profile_id.to_string()) {
+                        // Remove the profile ID
+                        profile_ids.retain(|id| id != profile_id);
+
+                        // If profile_ids is now empty, set it to None
+                        if profile_ids.is_empty() {
+                            metadata.profile_ids = None;
+                        }
+
+                        // Save the updated metadata
+                        self.save_metadata(&mod_info.id, &metadata)?;
+                        updated_count += 1;
+
+                        log::debug!("Removed profile {} from mod: {}", profile_id, mod_info.name);
+                    }
+                }
+            }
+        }
+
+        log::info!("Removed profile {} from {} mods", profile_id, updated_count);
+        Ok(updated_count)
+    }
+
+    /// Add a profile ID to every enabled mod, so the profile captures the
+    /// current setup. Returns the number of mods that were updated.
+    pub fn add_profile_to_enabled_mods(&self, profile_id: &str) -> Result<usize, String> {
+        let updated_count = self.add_profile_to_mods(profile_id, |m| m.enabled)?;
+        log::info!(
+            "Added profile {} to {} enabled mods",
+            profile_id,
+            updated_count
+        );
+        Ok(updated_count)
+    }
+
+    /// Tag every favorite with a profile, enabled or not
+    pub fn add_profile_to_favorites(&self, profile_id: &str) -> Result<usize, String> {
+        let updated_count = self.add_profile_to_mods(profile_id, |m| m.is_favorite)?;
+        log::info!(
+            "Added profile {} to {} favorite mods",
+            profile_id,
+            updated_count
+        );
+        Ok(updated_count)
+    }
+
+    fn add_profile_to_mods(
+        &self,
+        profile_id: &str,
+        include: impl Fn(&ModInfo) -> bool,
+    ) -> Result<usize, String> {
+        self.flush_pending_metadata()?;
+        let mut updated_count = 0;
+
+        for mod_info in self.get_all_mods()?.into_iter().filter(|m| include(m)) {
+            let mut metadata = mod_info.metadata;
+            let profile_ids = metadata.profile_ids.get_or_insert_with(Vec::new);
+            if profile_ids.iter().any(|id| id == profile_id) {
+                continue;
+            }
+            profile_ids.push(profile_id.to_string());
+            self.save_metadata(&mod_info.id, &metadata)?;
+            updated_count += 1;
+        }
+        Ok(updated_count)
+    }
+
+    // ===== Private Helper Methods =====
+
+    fn scan_directory_with_deduplication(
+        &self,
+        dir_path: &Path,
+        mods: &mut Vec<ModInfo>,
+        processed_paths: &mut HashSet<PathBuf>,
+        processed_ids: &mut HashSet<String>,
+        is_enabled: bool,
+        thumbnail_index: &HashMap<String, PathBuf>,
+    ) -> Result<(usize, usize), String> {
+        let mut file_count = 0;
+        let mut dir_count = 0;
+        let mut pak_count = 0;
+
+        for entry in self
+            .walk(dir_path)
+            .into_iter()
+            .filter_entry(|e| !self.is_ignored(e.path()))
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+
+            if entry.file_type().is_file() {
+                file_count += 1;
+            } else if entry.file_type().is_dir() {
+                dir_count += 1;
+            }
+
+            // Mods disabled in place stay in the active tree as .pakdisabled
+            let renamed_disabled = is_enabled && has_extension(path, "pakdisabled");
+            if !entry.file_type().is_file() || !(self.is_mod_file(path) || renamed_disabled) {
+                continue;
+            }
+
+            pak_count += 1;
+
+            let normalized_path = path.to_path_buf();
+            if processed_paths.contains(&normalized_path) {
+                continue;
+            }
+
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+            if let Some(mod_info) = self.create_mod_info(
+                path,
+                file_name,
+                is_enabled && !renamed_disabled,
+                Some(thumbnail_index),
+            ) {
+                if processed_ids.contains(&mod_info.id) {
+                    // A pak beside its own copy disabled in place normalizes
+                    // to the same ID. List both, the active copy under the
+                    // shared ID, so neither silently goes missing.
+                    let Some(first) = mods
+                        .iter_mut()
+                        .find(|m| m.id == mod_info.id && m.duplicate_of.is_none())
+                    else {
+                        continue;
+                    };
+                    if first.enabled == mod_info.enabled {
+                        continue;
+                    }
+                    let mut duplicate = mod_info;
+                    if duplicate.enabled {
+                        std::mem::swap(first, &mut duplicate);
+                    }
+                    log::warn!(
+                        "⚠️  {:?} is also installed as {:?}",
+                        first.file_path,
+                        duplicate.file_path
+                    );
+                    duplicate.duplicate_of = Some(first.id.clone());
+                    duplicate.id = duplicate_mod_id(&duplicate.file_path);
+                    processed_paths.insert(normalized_path);
+                    mods.push(duplicate);
+                    continue;
+                }
+
+                processed_paths.insert(normalized_path);
+                processed_ids.insert(mod_info.id.clone());
+                mods.push(mod_info);
+            } else {
+                log::warn!("⚠️  Failed to create mod info for: {:?}", path);
+            }
+        }
+
+        Ok((file_count, dir_count))
+    }
+
+    fn create_mod_info(
+        &self,
+        file_path: &Path,
+        file_name: &str,
+        is_enabled: bool,
+        thumbnail_index: Option<&HashMap<String, PathBuf>>,
+    ) -> Option<ModInfo> {
+        let metadata_result = fs::metadata(file_path);
+        if metadata_result.is_err() {
+            return None;
+        }
+        let metadata_fs = metadata_result.ok()?;
+
+        let clean_file_name = strip_disabled_marker(file_name);
+        // Use path-based ID to prevent duplicates when same mod installed multiple times
+        let mod_id = self.generate_mod_id_from_path(file_path, &clean_file_name);
+
+        // Load metadata if exists, or create and SAVE new metadata
+        // This ensures every mod always has persisted metadata for enable/disable operations.
+        // A file that doesn't parse (edited by hand) is left for the user to
+        // fix, with defaults shown meanwhile and a scan warning saying so.
+        let loaded = self.load_metadata(&mod_id);
+        let unreadable = loaded.is_err();
+        if let Err(e) = &loaded {
+            log::warn!("[create_mod_info] Showing defaults for {}: {}", mod_id, e);
+        }
+        let (mut metadata, is_new_metadata) = match loaded {
+            Ok(Some(m)) => (m, false),
+            _ => {
+                let MetadataSuggestion {
+                    category,
+                    character,
+                    ..
+                } = self.suggest_metadata(file_path, &clean_file_name);
+                let now = self.clock.now();
+                // Metadata can be regenerated long after install (lost JSON,
+                // ID change without migration). The pak's own timestamp is a
+                // far better install date than "now", and it doesn't jitter
+                // each time metadata is rebuilt.
+                let install_date = metadata_fs
+                    .created()
+                    .or_else(|_| metadata_fs.modified())
+                    .map(DateTime::<Utc>::from)
+                    .unwrap_or(now);
+
+                // Extract and store original folder path for enable/disable operations
+                let original_folder_path = if let Some(parent) = file_path.parent() {
+                    // Only store if the mod is in a subfolder (not root of ~mods or disabled-mods)
+                    if parent != &self.mods_directory && parent != &self.disabled_mods_directory {
+                        // Try to get relative path from ~mods directory
+                        if let Ok(relative) = parent.strip_prefix(&self.mods_directory) {
+                            Some(relative.to_string_lossy().to_string())
+                        } else if let Ok(relative) =
+                            parent.strip_prefix(&self.disabled_mods_directory)
+                        {
+                            Some(relative.to_string_lossy().to_string())
+                        } else {
+                            None
+                        }
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                };
+
+                (
+                    ModMetadata {
+                        title: self.extract_mod_name(&clean_file_name),
+                        subtitle: None,
+                        description: String::new(),
+                        author: None,
+                        version: None,
+                        tags: Vec::new(),
+                        category,
+                        character,
+                        costume: None,
+                        is_favorite: false,
+                        is_nsfw: false,
+                        created_at: now,
+                        updated_at: now,
+                        install_date,
+                        profile_ids: None,
+                        nexus_mod_id: None,
+                        nexus_file_id: None,
+                        nexus_version: None,
+                        original_folder_path,
+                        parent_mod_id: None,
+                        disabled_reason: None,
+                        disabled_at: None,
+                        last_verified_game_build: None,
+                        manage_layout: true,
+                        file_fingerprint: None,
+                        externally_modified_at: None,
+                        collision_folder: None,
+                        priority: None,
+                        notes: None,
+                        history: Vec::new(),
+                        variant_group: None,
+                    },
+                    !unreadable,
+                )
+            }
+        };
+        // The top folder (or the file name at the root) decides the load
+        // order, whatever was saved
+        metadata.priority = self.priority_of(file_path);
+
+        // Auto-save new metadata to ensure it persists for enable/disable operations
+        // This prevents the "Metadata not found" error when toggling mods
+        if is_new_metadata {
+            if let Err(e) = self.save_metadata(&mod_id, &metadata) {
+                log::warn!(
+                    "[create_mod_info] Failed to auto-save metadata for {}: {}",
+                    mod_id,
+                    e
+                );
+            } else {
+                log::debug!(
+                    "[create_mod_info] Auto-saved metadata for new mod: {}",
+                    mod_id
+                );
+            }
+        }
+
+        let associated_files = self.find_associated_files(file_path).ok()?;
+        let associated_file_sizes = file_sizes(&associated_files);
+
+        // Look for thumbnail using cached index if available, otherwise fall back to disk checks
+        let thumbnail_path = if let Some(index) = thumbnail_index {
+            self.find_thumbnail_cached(&mod_id, index)
+        } else {
+            self.find_thumbnail(&mod_id)
+        };
+
+        Some(ModInfo {
+            id: mod_id.clone(),
+            name: metadata.title.clone(),
+            category: metadata.category.clone(),
+            character: metadata.character.clone(),
+            enabled: is_enabled,
+            location: ModLocation::from_enabled(is_enabled),
+            duplicate_of: None,
+            is_favorite: metadata.is_favorite,
+            disabled_reason: metadata.disabled_reason.clone().filter(|_| !is_enabled),
+            file_path: file_path.to_path_buf(),
+            thumbnail_path,
+            file_size: total_size(&associated_file_sizes),
+            pak_size: metadata_fs.len(),
+            associated_file_sizes,
+            install_date: metadata.install_date,
+            last_modified: metadata_fs
+                .modified()
+                .ok()
+                .map(|t| t.into())
+                .unwrap_or_else(|| self.clock.now()),
+            original_file_name: clean_file_name,
+            associated_files,
+            metadata,
+        })
+    }
+
+    /// Fast thumbnail lookup using pre-built index (HashMap lookups instead of filesystem exists() calls)
+    fn find_thumbnail_cached(
+        &self,
+        mod_id: &str,
+        index: &HashMap<String, PathBuf>,
+    ) -> Option<PathBuf> {
+        let key = format!("{}_thumbnail", mod_id).to_lowercase();
+        index.get(&key).cloned()
+    }
+
+    /// Carry the stashed original a thumbnail was cropped from over to a
+    /// mod's new ID, alongside the thumbnail itself
+    fn migrate_thumbnail_source(&self, old_id: &str, new_id: &str) {
+        let thumbnails = ThumbnailService::new(self.metadata_directory.clone());
+        if let Err(e) = thumbnails.move_source(old_id, new_id) {
+            log::warn!("Failed to migrate thumbnail original: {}", e);
+        }
+    }
+
+    /// Use a preview image that shipped next to the source pak as the new
+    /// mod's thumbnail, unless it already has one. Best effort: a bad image
+    /// only costs the thumbnail, never the install.
+    fn adopt_preview_image(&self, mod_id: &str, source_pak: &Path) {
+        let thumbnails = ThumbnailService::new(self.metadata_directory.clone());
+        if thumbnails.thumbnail_exists(mod_id) {
+            return;
+        }
+        let Some(preview) = find_preview_image(source_pak) else {
+            return;
+        };
+        match thumbnails.adopt_image_file(mod_id, &preview) {
+            Ok(path) => log::info!(
+                "Adopted preview image {:?} as thumbnail {:?}",
+                preview,
+                path
+            ),
+            Err(e) => log::warn!("Failed to adopt preview image {:?}: {}", preview, e),
+        }
+    }
+
+    fn find_thumbnail(&self, mod_id: &str) -> Option<PathBuf> {
+        let thumbnail_path = self
+            .metadata_directory
+            .join(format!("{}_thumbnail.png", mod_id));
+        thumbnail_path.exists().then_some(thumbnail_path)
+    }
+
+    /// Files left in the Electron-era `thumbnails/` folder, sorted
+    pub fn legacy_thumbnail_files(&self) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(&self.thumbnails_directory) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Fold the Electron-era `thumbnails/` folder into per-mod thumbnails.
+    /// Each image goes to the mods the old lookup showed it for (named after
+    /// the mod ID, the pak, or the pak without `_P`) and is converted to
+    /// `{mod_id}_thumbnail.png`; mods that already have a thumbnail keep it.
+    /// Images nothing claims, or that fail to convert, are moved into
+    /// `unclaimed-thumbnails/` and the legacy folder is removed once empty.
+    pub fn consolidate_thumbnail_storage(&self) -> Result<ThumbnailConsolidationReport, String> {
+        let mut report = ThumbnailConsolidationReport::default();
+        let files = self.legacy_thumbnail_files();
+        if files.is_empty() {
+            let _ = fs::remove_dir(&self.thumbnails_directory);
+            return Ok(report);
+        }
+
+        // Lowercase stem -> image, preferring extensions in the order the
+        // old lookup probed them
+        let mut by_stem: HashMap<String, &PathBuf> = HashMap::new();
+        for ext in ["webp", "png", "jpg", "jpeg"] {
+            for file in files.iter().filter(|file| has_extension(file, ext)) {
+                if let Some(stem) = file.file_stem().and_then(|s| s.to_str()) {
+                    by_stem.entry(stem.to_lowercase()).or_insert(file);
+                }
+            }
+        }
+
+        let thumbnails = ThumbnailService::new(self.metadata_directory.clone());
+        let mut claimed: HashSet<&PathBuf> = HashSet::new();
+        let mut failed: HashSet<&PathBuf> = HashSet::new();
+        for mod_info in self.get_all_mods()? {
+            let file_name = mod_info
+                .file_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(strip_disabled_marker)
+                .unwrap_or_default();
+            let stem = Path::new(&file_name)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            let keys = [
+                mod_info.id.to_lowercase(),
+                stem.to_lowercase(),
+                stem.replace("_P", "").to_lowercase(),
+            ];
+            let Some(image) = keys.iter().find_map(|key| by_stem.get(key).copied()) else {
+                continue;
+            };
+            claimed.insert(image);
+            if thumbnails.thumbnail_exists(&mod_info.id) {
+                continue;
+            }
+            match thumbnails.adopt_image_file(&mod_info.id, image) {
+                Ok(_) => report.migrated += 1,
+                Err(e) => {
+                    log::warn!("[thumbnails] Failed to convert {:?}: {}", image, e);
+                    failed.insert(image);
+                }
+            }
+        }
+
+        let unclaimed_directory = self
+            .thumbnails_directory
+            .with_file_name("unclaimed-thumbnails");
+        for file in &files {
+            if claimed.contains(file) && !failed.contains(file) {
+                if let Err(e) = fs_ops::remove_file(file) {
+                    log::warn!("[thumbnails] Failed to remove {:?}: {}", file, e);
+                }
+                continue;
+            }
+            fs::create_dir_all(&unclaimed_directory)
+                .map_err(|e| format!("Failed to create unclaimed thumbnails folder: {}", e))?;
+            let mut dest = unclaimed_directory.join(file.file_name().unwrap_or_default());
+            if dest.exists() {
+                dest = free_file_path(&dest);
+            }
+            fs_ops::move_file(file, &dest)?;
+            report.unclaimed.push(dest);
+        }
+        // Only goes once empty; anything else in there is left alone
+        let _ = fs::remove_dir(&self.thumbnails_directory);
+
+        log::info!(
+            "[thumbnails] Moved {} legacy thumbnail(s) to their mods, {} unclaimed",
+            report.migrated,
+            report.unclaimed.len()
+        );
+        Ok(report)
+    }
+
+    /// Generate a unique mod ID from the full file path
+    /// This ensures that the same mod file installed multiple times from different
+    /// locations (e.g., different temp extractions) gets different IDs
+    ///
+    /// IMPORTANT: Normalizes the path by removing .disabled extension so that
+    /// enabled and disabled versions of the same mod get the same ID
+    fn generate_mod_id_from_path(&self, file_path: &Path, file_name: &str) -> String {
+        let mut hasher = Sha256::new();
+
+        // Normalize the path by removing .disabled extension (or the
+        // `disabled` renaming in place adds to `.pak`)
+        // This ensures enabled and disabled mods have the same ID
+        if let Some(path_str) = file_path.to_str() {
+            let mut normalized_path = strip_disabled_marker(path_str);
+            // Windows paths can arrive with mixed separators (frontend folder
+            // names use '/'); normalize so the same file always hashes to the
+            // same ID no matter which side built the path
+            if cfg!(windows) {
+                normalized_path = normalized_path.replace('/', "\\");
+            }
+            hasher.update(normalized_path.as_bytes());
+        } else {
+            // Fallback to file name if path is invalid
+            hasher.update(file_name.as_bytes());
+        }
+
+        let result = hasher.finalize();
+        format!("{:x}", result)[..16].to_string()
+    }
+
+    /// Legacy function kept for compatibility
+    #[allow(dead_code)]
+    fn generate_mod_id(&self, file_name: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(file_name.as_bytes());
+        let result = hasher.finalize();
+        format!("{:x}", result)[..16].to_string()
+    }
+
+    fn extract_mod_name(&self, file_name: &str) -> String {
+        let stem = Path::new(file_name)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Untitled Mod");
+
+        // Clean up common suffixes and prefixes. Only strip a trailing _P —
+        // a blanket replace mangles names like "D_Proficiency" → "Droficiency"
+        let (_, stem) = split_priority_prefix(stem);
+        let stem = stem.strip_suffix("_P").unwrap_or(stem);
+        let cleaned_stem = stem
+            .replace("_pak", "") // Remove _pak suffix
+            .replace("&", " "); // Replace ampersand with space
+
+        let parts: Vec<&str> = cleaned_stem
+            // Common patterns to remove - split by delimiters
+            .split(|c: char| c == '-' || c == '_' || c == '.')
+            .filter(|s| !s.is_empty())
+            // Skip common numeric patterns (version numbers, file IDs)
+            .filter(|s| {
+                // Keep it if it's not purely numeric or doesn't look like a version/ID
+                !s.chars().all(|c| c.is_numeric()) || s.len() <= 2
+            })
+            .collect();
+
+        // Filter out short uppercase prefixes at the start (like "A", "AC", "X", etc.)
+        let cleaned = parts
+            .iter()
+            .enumerate()
+            .filter(|(i, s)| {
+                if *i == 0 {
+                    // First word - keep it only if it's not a short uppercase prefix
+                    let is_short_uppercase =
+                        s.len() <= 2 && s.chars().all(|c| c.is_uppercase() || !c.is_alphabetic());
+                    !is_short_uppercase
+                } else {
+                    // Keep all other words
+                    true
+                }
+            })
+            .map(|(_, s)| *s)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        // Split camelCase words and title case
+        cleaned
+            .split_whitespace()
+            .flat_map(|word| self.split_camel_case(word))
+            .map(|word| {
+                // Skip capitalizing if it's an all-caps abbreviation
+                if word.len() <= 3 && word.chars().all(|c| c.is_uppercase() || !c.is_alphabetic()) {
+                    return word.to_string();
+                }
+
+                let mut chars = word.chars();
+                match chars.next() {
+                    None => String::new(),
+                    Some(first) => first
+                        .to_uppercase()
+                        .chain(chars.as_str().to_lowercase().chars())
+                        .collect(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Split a word on camelCase boundaries
+    fn split_camel_case<'a>(&self, word: &'a str) -> Vec<&'a str> {
+        let mut parts = Vec::new();
+        let mut last_split = 0;
+        let chars: Vec<char> = word.chars().collect();
+
+        for i in 1..chars.len() {
+            // Split if we find a lowercase followed by uppercase (camelCase)
+            // or multiple uppercase followed by uppercase then lowercase (XMLParser -> XML Parser)
+            if (chars[i - 1].is_lowercase() && chars[i].is_uppercase())
+                || (i > 1
+                    && chars[i - 2].is_uppercase()
+                    && chars[i - 1].is_uppercase()
+                    && chars[i].is_lowercase())
+            {
+                parts.push(&word[last_split..i]);
+                last_split = i;
+            }
+        }
+
+        // Add the remaining part
+        if last_split < word.len() {
+            parts.push(&word[last_split..]);
+        }
+
+        if parts.is_empty() {
+            vec![word]
+        } else {
+            parts
+        }
+    }
+
+    /// Category and character auto-detection would give a pak, judging by
+    /// its folders inside the mod directories and its file name
+    fn suggest_metadata(&self, file_path: &Path, file_name: &str) -> MetadataSuggestion {
+        crate::detection::suggest_metadata(
+            file_path,
+            file_name,
+            &[&self.mods_directory, &self.disabled_mods_directory],
+        )
+    }
+
+    /// What auto-detection makes of a pak, with how sure it is, so an
+    /// install dialog can skip asking about confident guesses
+    pub fn preview_mod_metadata(&self, file_path: &Path) -> MetadataSuggestion {
+        let file_name = file_path
+            .file_name()
+            .map(|name| name.to_string_lossy().replace(".disabled", ""))
+            .unwrap_or_default();
+        self.suggest_metadata(file_path, &file_name)
+    }
+
+    /// Installed mods whose character is what a weak file name guess would
+    /// give, and likely came from one. Nothing records where a character
+    /// came from, so this re-runs detection; characters set by hand that
+    /// differ from the guess are never listed.
+    pub fn find_low_confidence_assignments(&self) -> Result<Vec<LowConfidenceAssignment>, String> {
+        let mut assignments = Vec::new();
+        for mod_info in self.get_all_mods()? {
+            let Some(character) = mod_info.metadata.character.clone() else {
+                continue;
+            };
+            let suggestion = self.preview_mod_metadata(&mod_info.file_path);
+            if suggestion.character.as_ref() == Some(&character)
+                && suggestion.character_confidence == Some(DetectionConfidence::Low)
+            {
+                assignments.push(LowConfidenceAssignment {
+                    mod_id: mod_info.id,
+                    title: mod_info.metadata.title,
+                    character,
+                    file_name: mod_info.original_file_name,
+                });
+            }
+        }
+        Ok(assignments)
+    }
+
+    fn find_associated_files(&self, pak_file_path: &Path) -> Result<Vec<PathBuf>, String> {
+        if pak_file_path.file_stem().and_then(|s| s.to_str()).is_none() {
+            return Err("Invalid file name".to_string());
+        }
+
+        // Companions of a pak disabled in place were renamed with it
+        let suffix = if has_extension(pak_file_path, "pakdisabled") {
+            "disabled"
+        } else {
+            ""
+        };
+
+        let mut files = vec![pak_file_path.to_path_buf()];
+        files.extend(find_companions(pak_file_path, suffix));
+        Ok(files)
+    }
+
+    fn is_mod_file(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| SUPPORTED_EXTENSIONS.contains(&format!(".{}", ext).as_str()))
+            .unwrap_or(false)
+    }
+
+    pub fn find_mod_by_id(&self, mod_id: &str) -> Result<Option<ModInfo>, String> {
+        let all_mods = self.get_all_mods()?;
+        Ok(all_mods.into_iter().find(|m| m.id == mod_id))
+    }
+
+    /// [`find_mod_by_id`](Self::find_mod_by_id) served from the listing given
+    /// with [`with_listing`](Self::with_listing): only the listed mod is
+    /// re-read. The library is rescanned when it isn't listed, has moved, or
+    /// shares its ID with a copy disabled in place, as the scan cache does.
+    fn find_listed_mod(&self, mod_id: &str) -> Result<Option<ModInfo>, String> {
+        let twinned = |id: &str| {
+            self.listing.iter().any(|m| {
+                (m.id == id && m.duplicate_of.is_some()) || m.duplicate_of.as_deref() == Some(id)
+            })
+        };
+        let fresh = self
+            .listing
+            .iter()
+            .find(|m| m.id == mod_id)
+            .filter(|m| !twinned(&m.id))
+            .and_then(|m| self.reload_mod(m));
+        match fresh {
+            Some(mod_info) => Ok(Some(mod_info)),
+            None => self.find_mod_by_id(mod_id),
+        }
+    }
+
+    fn load_metadata(&self, mod_id: &str) -> Result<Option<ModMetadata>, String> {
+        if let Some(metadata) = write_behind::pending(&self.metadata_directory, mod_id) {
+            return Ok(Some(metadata));
+        }
+
+        let metadata_path = self.metadata_directory.join(format!("{}.json", mod_id));
+
+        if !metadata_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&metadata_path)
+            .map_err(|e| format!("Failed to read metadata: {}", e))?;
+
+        let metadata: ModMetadata = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse metadata: {}", e))?;
+
+        Ok(Some(metadata))
+    }
+
+    fn save_metadata(&self, mod_id: &str, metadata: &ModMetadata) -> Result<(), String> {
+        self.ensure_directory_exists(&self.metadata_directory)?;
+        write_behind::write_through(&self.metadata_directory, mod_id, metadata)?;
+        mod_cache::invalidate_mod(mod_id);
+        Ok(())
+    }
+
+    /// Save a light metadata change through the write-behind: readers see it
+    /// at once, the file is written after a short quiet period.
+    fn save_metadata_deferred(&self, mod_id: &str, metadata: ModMetadata) -> Result<(), String> {
+        self.ensure_directory_exists(&self.metadata_directory)?;
+        write_behind::defer(&self.metadata_directory, mod_id, metadata)?;
+        mod_cache::invalidate_mod(mod_id);
+        Ok(())
+    }
+
+    /// Write any deferred metadata changes for this library to disk now.
+    /// Operations that scan, move or rename metadata files call this first.
+    pub fn flush_pending_metadata(&self) -> Result<usize, String> {
+        write_behind::flush_dir(&self.metadata_directory)
+    }
+
+    fn delete_metadata(&self, mod_id: &str) -> Result<(), String> {
+        write_behind::discard(&self.metadata_directory, mod_id)?;
+        let metadata_path = self.metadata_directory.join(format!("{}.json", mod_id));
+        if metadata_path.exists() {
+            fs::remove_file(&metadata_path)
+                .map_err(|e| format!("Failed to delete metadata: {}", e))?;
+        }
+        mod_cache::invalidate_mod(mod_id);
+        Ok(())
+    }
+
+    /// Update all addon mods that reference an old parent ID to point to the new ID
+    fn migrate_addon_parent_ids(&self, old_id: &str, new_id: &str) -> Result<(), String> {
+        log::info!(
+            "      🔄 Scanning for addons with parent_mod_id = {}",
+            old_id
+        );
+        let mut migrated = 0;
+        let mut scanned = 0;
+
+        // Scan all metadata files in the metadata directory
+        if let Ok(entries) = fs::read_dir(&self.metadata_directory) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                    continue;
+                }
+                scanned += 1;
+
+                if let Ok(content) = fs::read_to_string(&path) {
+                    // Use a simple string check first for speed, then parse if match found
+                    if content.contains(old_id) {
+                        if let Ok(mut metadata) = serde_json::from_str::<ModMetadata>(&content) {
+                            log::info!(
+                                "      📄 Checking {:?} - parentModId: {:?}",
+                                path.file_name(),
+                                metadata.parent_mod_id
+                            );
+                            if metadata.parent_mod_id.as_deref() == Some(old_id) {
+                                metadata.parent_mod_id = Some(new_id.to_string());
+                                if let Ok(updated) = serde_json::to_string_pretty(&metadata) {
+                                    let _ = fs::write(&path, updated);
+                                    migrated += 1;
+                                    log::info!("      ✅ Updated addon: {:?}", path.file_name());
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        log::info!(
+            "      📊 Scanned {} metadata files, migrated {} addon(s)",
+            scanned,
+            migrated
+        );
+        self.remap_collection_members(&HashMap::from([(old_id.to_string(), new_id.to_string())]));
+        Ok(())
+    }
+
+    fn ensure_directory_exists(&self, path: &Path) -> Result<(), String> {
+        if !path.exists() {
+            fs::create_dir_all(path).map_err(|e| format!("Failed to create directory: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Aggregate disk usage per category, character and costume for the
+    /// storage treemap. Disabled mods are counted in the same tree but
+    /// tracked separately via `disabled_bytes`.
+    pub fn get_storage_breakdown(&self) -> Result<StorageBreakdown, String> {
+        let all_mods = self.get_all_mods()?;
+        let mut root = StorageNode::default();
+
+        for m in &all_mods {
+            let bytes = m.file_size;
+
+            root.add(bytes, m.enabled);
+            let category = root.child(&m.metadata.category.to_string());
+            category.add(bytes, m.enabled);
+
+            let character_name = m
+                .metadata
+                .character
+                .as_ref()
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "No Character".to_string());
+            let character = category.child(&character_name);
+            character.add(bytes, m.enabled);
+
+            if let Some(costume) = m.metadata.costume.as_deref() {
+                character.child(costume).add(bytes, m.enabled);
+            }
+        }
+
+        root.sort_by_size();
+
+        let sources = ThumbnailService::new(self.metadata_directory.clone()).list_sources();
+        Ok(StorageBreakdown {
+            total_bytes: root.total_bytes,
+            enabled_bytes: root.enabled_bytes,
+            disabled_bytes: root.disabled_bytes,
+            mod_count: root.mod_count,
+            categories: root.children,
+            thumbnail_source_count: sources.len(),
+            thumbnail_source_bytes: sources.iter().map(|(_, size)| size).sum(),
+        })
+    }
+
+    /// Everything disabled, in disabled-mods or renamed in place in ~mods,
+    /// longest disabled first, for auditing the store without enabling
+    /// anything. Metadata is read directly so none gets created for mods
+    /// that have lost theirs.
+    pub fn get_disabled_mods_overview(&self) -> Result<Vec<DisabledModEntry>, String> {
+        self.flush_pending_metadata()?;
+        let now = self.clock.now();
+
+        let mut groups: BTreeMap<(PathBuf, String), Vec<PathBuf>> = BTreeMap::new();
+        let disabled_in_place: Vec<String> = MOD_FILE_EXTENSIONS
+            .iter()
+            .map(|ext| format!("{}disabled", ext))
+            .collect();
+        let plain: Vec<String> = MOD_FILE_EXTENSIONS
+            .iter()
+            .map(|ext| ext.to_string())
+            .collect();
+        for (root, extensions) in [
+            (&self.disabled_mods_directory, plain),
+            (&self.mods_directory, disabled_in_place),
+        ] {
+            for entry in WalkDir::new(root)
+                .follow_links(false)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+            {
+                let path = entry.path();
+                if !extensions.iter().any(|ext| has_extension(path, ext)) {
+                    continue;
+                }
+                let stem = path.file_stem().and_then(|s| s.to_str());
+                let (Some(parent), Some(stem)) = (path.parent(), stem) else {
+                    continue;
+                };
+                groups
+                    .entry((parent.to_path_buf(), stem.to_string()))
+                    .or_default()
+                    .push(path.to_path_buf());
+            }
+        }
+
+        let mut entries = Vec::new();
+        for ((_, stem), files) in groups {
+            let size = files
+                .iter()
+                .filter_map(|f| fs::metadata(f).ok())
+                .map(|m| m.len())
+                .sum();
+            let pak_name = format!("{}.pak", stem);
+            let mod_id = files
+                .iter()
+                .find(|f| has_extension(f, "pak") || has_extension(f, "pakdisabled"))
+                .map(|pak| self.generate_mod_id_from_path(pak, &pak_name));
+            let metadata = match &mod_id {
+                Some(id) => self.load_metadata(id)?,
+                None => None,
+            };
+
+            let (disabled_since, disabled_since_estimated) =
+                match (&mod_id, metadata.as_ref().map(|m| m.disabled_at)) {
+                    (_, Some(Some(at))) => (Some(at), false),
+                    (Some(id), Some(None)) => {
+                        let path = self.metadata_directory.join(format!("{}.json", id));
+                        let written = fs::metadata(path).and_then(|m| m.modified()).ok();
+                        (written.map(DateTime::<Utc>::from), true)
+                    }
+                    _ => (None, false),
+                };
+
+            entries.push(DisabledModEntry {
+                mod_id,
+                title: metadata
+                    .as_ref()
+                    .map(|m| m.title.clone())
+                    .unwrap_or_else(|| self.extract_mod_name(&pak_name)),
+                files,
+                size,
+                disabled_since,
+                disabled_since_estimated,
+                disabled_days: disabled_since.map(|at| (now - at).num_days().max(0)),
+                disabled_reason: metadata.as_ref().and_then(|m| m.disabled_reason.clone()),
+                has_metadata: metadata.is_some(),
+            });
+        }
+
+        entries.sort_by_key(|e| (e.disabled_since.is_none(), e.disabled_since));
+        Ok(entries)
+    }
+
+    /// Permanently delete mods disabled for at least `older_than_days`,
+    /// with their metadata and thumbnails. Entries with no known disable
+    /// date are never purged. `dry_run` only reports what would go.
+    pub fn purge_disabled_mods(
+        &self,
+        older_than_days: u32,
+        dry_run: bool,
+    ) -> Result<DisabledPurgeReport, String> {
+        let thumbnails = ThumbnailService::new(self.metadata_directory.clone());
+        let mut report = DisabledPurgeReport {
+            dry_run,
+            ..Default::default()
+        };
+        let mut purged_ids = Vec::new();
+
+        for entry in self.get_disabled_mods_overview()? {
+            if entry
+                .disabled_days
+                .is_none_or(|days| days < i64::from(older_than_days))
+            {
+                continue;
+            }
+            if !dry_run {
+                let mut failed = false;
+                for file in &entry.files {
+                    if let Err(e) = fs_ops::remove_file(file) {
+                        log::warn!("[purge] Failed to delete {:?}: {}", file, e);
+                        failed = true;
+                    }
+                }
+                if failed {
+                    continue;
+                }
+                if let Some(id) = &entry.mod_id {
+                    let _ = self.delete_metadata(id);
+                    let _ = fs_ops::remove_file(
+                        &self
+                            .metadata_directory
+                            .join(format!("{}_thumbnail.png", id)),
+                    );
+                    let _ = thumbnails.delete_source(id);
+                    purged_ids.push(id.clone());
+                }
+            }
+            report.bytes_reclaimed += entry.size;
+            report.purged.push(entry);
+        }
+
+        if !dry_run {
+            if let Err(e) = self.collections().forget_mods(&purged_ids) {
+                log::warn!("[collections] Failed to remove purged mods: {}", e);
+            }
+            // Folders left empty go too; remove_dir refuses non-empty ones
+            for dir in WalkDir::new(&self.disabled_mods_directory)
+                .min_depth(1)
+                .contents_first(true)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_dir())
+            {
+                let _ = fs::remove_dir(dir.path());
+            }
+        }
+
+        log::info!(
+            "[purge] {} {} disabled mod(s) older than {} day(s), {} bytes",
+            if dry_run { "Would purge" } else { "Purged" },
+            report.purged.len(),
+            older_than_days,
+            report.bytes_reclaimed
+        );
+        Ok(report)
+    }
+
+    /// Raw pak/utoc values for one mod, to help work out why it doesn't load
+    pub fn get_pak_technical_info(&self, mod_id: &str) -> Result<PakTechnicalInfo, String> {
+        let all_mods = self.get_all_mods()?;
+        let mod_info = all_mods
+            .iter()
+            .find(|m| m.id == mod_id)
+            .ok_or("Mod not found")?;
+        let working: Vec<PakTechnicalInfo> = all_mods
+            .iter()
+            .filter(|m| m.enabled && m.id != mod_id)
+            .map(read_pak_technical_info)
+            .collect();
+        let common = common_mount_point(working.iter().filter_map(|i| i.mount_point()));
+        Ok(check_mount_point(
+            read_pak_technical_info(mod_info),
+            common.as_deref(),
+        ))
+    }
+
+    /// Look for mods that install fine but may not load. For now that is a
+    /// mount point unlike the one the library's working (enabled) mods share,
+    /// which usually means the mod was built for an older game version.
+    pub fn verify_mods(&self) -> Result<Vec<ModVerificationIssue>, String> {
+        let all_mods = self.get_all_mods()?;
+        let infos: Vec<PakTechnicalInfo> = all_mods.iter().map(read_pak_technical_info).collect();
+        let common = common_mount_point(
+            all_mods
+                .iter()
+                .zip(&infos)
+                .filter(|(m, _)| m.enabled)
+                .filter_map(|(_, info)| info.mount_point()),
+        );
+
+        let mut issues = Vec::new();
+        for (mod_info, info) in all_mods.iter().zip(infos) {
+            let info = check_mount_point(info, common.as_deref());
+            if !info.mount_point_outlier {
+                continue;
+            }
+            issues.push(ModVerificationIssue {
+                mod_id: mod_info.id.clone(),
+                title: mod_info.metadata.title.clone(),
+                kind: ModVerificationKind::PossiblyOutdated,
+                message: format!(
+                    "Mounts to {} while working mods use {}; possibly built for an older game version",
+                    info.mount_point().unwrap_or_default(),
+                    info.common_mount_point.as_deref().unwrap_or_default()
+                ),
+                info,
+            });
+        }
+
+        if !issues.is_empty() {
+            log::warn!(
+                "[verify] {} mod(s) mount somewhere unusual for this library",
+                issues.len()
+            );
+        }
+        Ok(issues)
+    }
+
+    /// Which of `listed`, mods as listed earlier (say last session), have
+    /// lost files since: the pak and everything with it, or only some
+    /// companions. Also whether ~mods itself is gone.
+    pub fn verify_integrity(&self, listed: &[ModInfo]) -> ModsIntegrityReport {
+        let mut issues = Vec::new();
+        let mut recoverable = Vec::new();
+        for mod_info in listed.iter().filter(|m| m.duplicate_of.is_none()) {
+            let mut missing_files: Vec<PathBuf> = std::iter::once(&mod_info.file_path)
+                .chain(&mod_info.associated_files)
+                .filter(|path| !path.exists())
+                .cloned()
+                .collect();
+            missing_files.sort();
+            missing_files.dedup();
+            if missing_files.is_empty() {
+                continue;
+            }
+            let pak_present = mod_info.file_path.exists();
+            if mod_info.enabled && !pak_present && self.backup_of(&mod_info.file_path).is_some() {
+                recoverable.push(mod_info.id.clone());
+            }
+            issues.push(ModIntegrityIssue {
+                mod_id: mod_info.id.clone(),
+                title: mod_info.metadata.title.clone(),
+                enabled: mod_info.enabled,
+                pak_present,
+                missing_files,
+            });
+        }
+
+        ModsIntegrityReport {
+            mods_directory: self.mods_directory.clone(),
+            mods_directory_missing: !self.mods_directory.is_dir(),
+            issues,
+            recoverable,
+        }
+    }
+
+    /// Recreate ~mods if it's gone, and put back the mods `listed` as
+    /// enabled whose files vanished but have a copy at the same place in
+    /// disabled-mods. The copies are moved rather than copied, so the mod
+    /// isn't listed twice. Returns the mods put back.
+    pub fn restore_mods_directory(&self, listed: &[ModInfo]) -> Result<Vec<ModInfo>, String> {
+        self.ensure_directory_exists(&self.mods_directory)?;
+        let mut restored = Vec::new();
+        for mod_info in listed
+            .iter()
+            .filter(|m| m.enabled && m.duplicate_of.is_none() && !m.file_path.exists())
+        {
+            if self.backup_of(&mod_info.file_path).is_none() {
+                continue;
+            }
+            let files: BTreeSet<&PathBuf> = std::iter::once(&mod_info.file_path)
+                .chain(&mod_info.associated_files)
+                .collect();
+            let put_back = files.into_iter().try_for_each(|file| {
+                let Some(backup) = self.backup_of(file) else {
+                    return Ok(());
+                };
+                if let Some(parent) = file.parent() {
+                    self.ensure_directory_exists(parent)?;
+                }
+                fs_ops::move_file(&backup, file)
+            });
+            if let Err(e) = put_back {
+                log::warn!(
+                    "[integrity] Failed to put back {}: {}",
+                    mod_info.metadata.title,
+                    e
+                );
+                continue;
+            }
+            // Same path, so the same ID and metadata as before
+            match self.mod_info_at(&mod_info.file_path, true) {
+                Ok(mod_info) => restored.push(mod_info),
+                Err(e) => log::warn!("[integrity] {}", e),
+            }
+        }
+        log::info!(
+            "[integrity] Put back {} mod(s) from disabled-mods",
+            restored.len()
+        );
+        Ok(restored)
+    }
+
+    /// The copy in disabled-mods of `path` under ~mods, if there is one
+    fn backup_of(&self, path: &Path) -> Option<PathBuf> {
+        let relative = path.strip_prefix(&self.mods_directory).ok()?;
+        Some(self.disabled_mods_directory.join(relative)).filter(|backup| backup.is_file())
+    }
+
+    /// Check an installed mod's pak with [`validate_pak_file`], and that its
+    /// IoStore companions come as a pair
+    pub fn validate_mod(&self, mod_id: &str) -> Result<PakValidation, String> {
+        let mod_info = self.find_mod_by_id(mod_id)?.ok_or("Mod not found")?;
+        Ok(validate_mod_files(&mod_info))
+    }
+
+    /// [`validate_mod`](Self::validate_mod) for every mod, from one scan.
+    /// Returns only the mods with something wrong.
+    pub fn validate_mods(&self) -> Result<Vec<PakValidation>, String> {
+        let failed: Vec<PakValidation> = self
+            .get_all_mods()?
+            .iter()
+            .map(validate_mod_files)
+            .filter(|validation| !validation.is_ok())
+            .collect();
+        if !failed.is_empty() {
+            log::warn!("[validate] {} mod(s) failed validation", failed.len());
+        }
+        Ok(failed)
+    }
+
+    /// Mods overriding the same game assets, compared by the asset paths
+    /// their `.utoc` lists. A parent mod and its own add-on are expected to
+    /// share assets (that's how add-ons layer), so those pairs are not
+    /// reported; only clashes between unrelated mods are. Disabled mods are
+    /// compared too with `include_disabled`, to see what enabling one would
+    /// clash with.
+    pub fn detect_mod_conflicts(&self, include_disabled: bool) -> Result<Vec<ModConflict>, String> {
+        let all_mods = self.get_all_mods()?;
+        // A second copy of a mod found both enabled and disabled shares its
+        // ID and files; it isn't another mod
+        let compared: Vec<&ModInfo> = all_mods
+            .iter()
+            .filter(|m| (m.enabled || include_disabled) && m.duplicate_of.is_none())
+            .collect();
+        let enabled_ids: HashSet<&str> = compared
+            .iter()
+            .filter(|m| m.enabled)
+            .map(|m| m.id.as_str())
+            .collect();
+
+        // For each compared mod, read the asset paths its .utoc overrides along
+        // with a content hash per asset (from the TOC's chunk metas), so that
+        // byte-identical overlaps can be ignored.
+        // Keyed by mod id -> map of asset path -> content hash.
+        let mut mod_assets: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut load_key: HashMap<String, String> = HashMap::new();
+        let mut title_by_id: HashMap<String, String> = HashMap::new();
+
+        for m in &compared {
+            title_by_id.insert(m.id.clone(), m.metadata.title.clone());
+            // The game mounts by lowercased full path; first wins.
+            load_key.insert(m.id.clone(), m.file_path.to_string_lossy().to_lowercase());
+
+            let utoc = m
+                .associated_files
+                .iter()
+                .find(|p| p.extension().and_then(|e| e.to_str()) == Some("utoc"));
+            let Some(utoc) = utoc else {
+                continue; // loose .pak with no utoc — nothing to compare
+            };
+            match read_utoc_assets(utoc) {
+                Ok(assets) => {
+                    mod_assets.insert(m.id.clone(), assets);
+                }
+                Err(e) => {
+                    log::warn!("[conflicts] Could not read {:?}: {}", utoc, e);
+                }
+            }
+        }
+
+        // A mod is "related" to another (and so not a conflict) when one is the
+        // other's parent, or they share the same parent (sibling add-ons).
+        let parent_of: HashMap<String, Option<String>> = compared
+            .iter()
+            .map(|m| (m.id.clone(), m.metadata.parent_mod_id.clone()))
+            .collect();
+        let related = |a: &str, b: &str| -> bool {
+            let pa = parent_of.get(a).cloned().flatten();
+            let pb = parent_of.get(b).cloned().flatten();
+            pa.as_deref() == Some(b)
+                || pb.as_deref() == Some(a)
+                || (pa.is_some() && pa == pb)
+        };
+
+        // asset path -> mod ids that override it
+        let mut owners: HashMap<String, Vec<String>> = HashMap::new();
+        for (id, assets) in &mod_assets {
+            for a in assets.keys() {
+                owners.entry(a.clone()).or_default().push(id.clone());
+            }
+        }
+
+        // Group clashes by the set of mods involved.
+        // key = sorted mod-id list -> set of shared asset paths
+        let mut grouped: HashMap<Vec<String>, HashSet<String>> = HashMap::new();
+        for (asset, ids) in &owners {
+            if ids.len() < 2 {
+                continue;
+            }
+            // Keep only ids that are unrelated to at least one other id here.
+            let mut unrelated_ids: Vec<String> = Vec::new();
+            for id in ids {
+                let conflicts_with_someone = ids
+                    .iter()
+                    .any(|other| other != id && !related(id, other));
+                if conflicts_with_someone {
+                    unrelated_ids.push(id.clone());
+                }
+            }
+            if unrelated_ids.len() < 2 {
+                continue; // every owner is a relative of the others — expected layering
+            }
+            unrelated_ids.sort();
+            unrelated_ids.dedup();
+
+            // If every unrelated owner ships byte-identical content for this
+            // asset (same chunk hashes), it doesn't matter who wins — authors
+            // often bundle the same untouched base-game file. Skip it. A
+            // missing hash is treated as different (flag rather than hide).
+            let mut hashes: Vec<Option<&String>> = Vec::new();
+            for id in &unrelated_ids {
+                hashes.push(mod_assets.get(id).and_then(|a| a.get(asset)));
+            }
+            let all_known = hashes.iter().all(|h| h.is_some());
+            if all_known {
+                let first = hashes[0];
+                if hashes.iter().all(|h| *h == first) {
+                    continue; // identical content everywhere — harmless overlap
+                }
+            }
+
+            grouped
+                .entry(unrelated_ids)
+                .or_default()
+                .insert(asset.clone());
+        }
+
+        // Build the result list.
+        let mut conflicts: Vec<ModConflict> = Vec::new();
+        for (ids, assets) in grouped {
+            let severity = conflict_severity(&ids, &assets, &mod_assets);
+            // The winner is the enabled mod whose load-order key sorts first.
+            let winner = ids
+                .iter()
+                .filter(|id| enabled_ids.contains(id.as_str()))
+                .min_by(|a, b| {
+                    load_key
+                        .get(*a)
+                        .map(String::as_str)
+                        .unwrap_or("")
+                        .cmp(load_key.get(*b).map(String::as_str).unwrap_or(""))
+                })
+                .cloned();
+
+            let mods: Vec<ConflictMod> = {
+                let mut v: Vec<ConflictMod> = ids
+                    .iter()
+                    .map(|id| ConflictMod {
+                        id: id.clone(),
+                        title: title_by_id
+                            .get(id)
+                            .cloned()
+                            .unwrap_or_else(|| id.clone()),
+                        wins: Some(id) == winner.as_ref(),
+                        enabled: enabled_ids.contains(id.as_str()),
+                    })
+                    .collect();
+                // winner first
+                v.sort_by(|a, b| b.wins.cmp(&a.wins).then(a.title.cmp(&b.title)));
+                v
+            };
+
+            let mut asset_stems: Vec<String> = assets
+                .iter()
+                .map(|a| {
+                    Path::new(a)
+                        .file_stem()
+                        .and_then(|s| s.to_str())
+                        .unwrap_or(a)
+                        .to_string()
+                })
+                .collect();
+            asset_stems.sort();
+            asset_stems.dedup();
+
+            let mut kinds: Vec<String> = asset_stems
+                .iter()
+                .map(|a| classify_asset(a))
+                .collect();
+            kinds.sort();
+            kinds.dedup();
+
+            conflicts.push(ModConflict {
+                mods,
+                kinds,
+                assets: asset_stems,
+                severity,
+            });
+        }
+
+        // Most-overlapping conflicts first.
+        conflicts.sort_by(|a, b| b.assets.len().cmp(&a.assets.len()));
+
+        // The dashboard counts clashes between enabled mods only
+        if !include_disabled {
+            CONFLICT_CACHE
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(
+                    self.metadata_directory.clone(),
+                    (enabled_fingerprint(&all_mods), conflicts.len()),
+                );
+        }
+        Ok(conflicts)
+    }
+
+    /// Counts for the dashboard from one scan. Conflicts come from the last
+    /// `detect_mod_conflicts`, marked stale if the enabled set changed since.
+    /// `pending_updates` counts the updates that apply to the scanned mods.
+    pub fn library_summary(
+        &self,
+        pending_updates: impl FnOnce(&[ModInfo]) -> CachedCount,
+    ) -> Result<LibrarySummary, String> {
+        let mut summary = self.summarize(self.get_all_mods()?, pending_updates);
+        summary.ignored_folders_with_paks = self.ignored_folders_with_paks().len();
+        Ok(summary)
+    }
+
+    /// The outermost ignored folders under ~mods that contain a pak
+    pub fn ignored_folders_with_paks(&self) -> Vec<PathBuf> {
+        if self.ignored_paths.is_empty() {
+            return Vec::new();
+        }
+        let mut folders = Vec::new();
+        let mut walker = WalkDir::new(&self.mods_directory).min_depth(1).into_iter();
+        while let Some(entry) = walker.next() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if !entry.file_type().is_dir() || !self.is_ignored(entry.path()) {
+                continue;
+            }
+            // Folders below this one are part of it
+            walker.skip_current_dir();
+            let has_pak = WalkDir::new(entry.path())
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .any(|e| e.file_type().is_file() && has_extension(e.path(), "pak"));
+            if has_pak {
+                folders.push(entry.into_path());
+            }
+        }
+        folders
+    }
+
+    /// Enabled, disabled and total counts and size over the whole library
+    pub fn app_stats(&self) -> Result<AppStats, String> {
+        Ok(aggregate_stats(&self.get_all_mods()?).0)
+    }
+
+    /// Mod counts per category, most first
+    pub fn category_stats(&self) -> Result<Vec<CategoryStats>, String> {
+        Ok(aggregate_stats(&self.get_all_mods()?).1)
+    }
+
+    /// Mod counts per character, most first, with mods without a character
+    /// in a last, unassigned (`None`) entry
+    pub fn character_stats(&self) -> Result<Vec<CharacterStats>, String> {
+        Ok(aggregate_stats(&self.get_all_mods()?).2)
+    }
+
+    /// Dashboard counts for an already known list of mods, such as the last
+    /// listing saved before the game folder went offline
+    pub fn summarize(
+        &self,
+        mods: Vec<ModInfo>,
+        pending_updates: impl FnOnce(&[ModInfo]) -> CachedCount,
+    ) -> LibrarySummary {
+        let (totals, categories, mut characters) = aggregate_stats(&mods);
+        // Unassigned mods aren't a character to rank
+        characters.retain(|c| c.character.is_some());
+        characters.truncate(TOP_CHARACTERS);
+
+        let fingerprint = enabled_fingerprint(&mods);
+        let conflicts = match CONFLICT_CACHE
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&self.metadata_directory)
+        {
+            Some(&(cached, count)) => CachedCount {
+                count,
+                stale: cached != fingerprint,
+            },
+            None => CachedCount {
+                count: 0,
+                stale: true,
+            },
+        };
+
+        LibrarySummary {
+            totals,
+            categories,
+            top_characters: characters,
+            missing_thumbnails: mods.iter().filter(|m| m.thumbnail_path.is_none()).count(),
+            conflicts,
+            pending_updates: pending_updates(&mods),
+            last_install: mods.iter().map(|m| m.install_date).max(),
+            ignored_folders_with_paks: 0,
+            unresolved_duplicates: mods.iter().filter(|m| m.duplicate_of.is_some()).count(),
+            offline: false,
+        }
+    }
+}
+
+/// `limit` of the mods matching `filter` from `offset`, in `sort` order,
+/// and how many matched in all
+pub fn page_mods(
+    mut mods: Vec<ModInfo>,
+    filter: Option<&ModQuery>,
+    sort: Option<&SortSpec>,
+    offset: usize,
+    limit: usize,
+) -> ModPage {
+    if let Some(filter) = filter {
+        mods.retain(|m| filter.matches(m));
+    }
+    if let Some(sort) = sort {
+        crate::sorting::sort_mods(&mut mods, sort);
+    }
+    ModPage {
+        total: mods.len(),
+        mods: mods.into_iter().skip(offset).take(limit).collect(),
+    }
+}
+
+/// The Nexus page of mod `nexus_mod_id`
+pub fn nexus_mod_url(nexus_mod_id: i32) -> String {
+    format!(
+        "https://www.nexusmods.com/{}/mods/{}",
+        crate::mod_updates::GAME_DOMAIN,
+        nexus_mod_id
+    )
+}
+
+/// Whether `mod_info` is the mod `entry` lists, going by `kind`
+fn list_entry_matches(entry: &ModListEntry, mod_info: &ModInfo, kind: ModListMatchKind) -> bool {
+    let metadata = &mod_info.metadata;
+    match kind {
+        ModListMatchKind::NexusModId => {
+            entry.nexus_mod_id.is_some() && entry.nexus_mod_id == metadata.nexus_mod_id
+        }
+        ModListMatchKind::TitleAndCharacter => {
+            entry.title == metadata.title && entry.character == metadata.character
+        }
+        ModListMatchKind::FileName => entry
+            .original_file_name
+            .eq_ignore_ascii_case(&mod_info.original_file_name),
+    }
+}
+
+/// `mods` as CSV with a header row. Fields with commas, quotes or line
+/// breaks are quoted, doubling any quotes inside.
+fn mod_list_csv(mods: &[ModListEntry]) -> String {
+    fn field(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+    fn optional<T: ToString>(value: &Option<T>) -> String {
+        value
+            .as_ref()
+            .map(|v| field(&v.to_string()))
+            .unwrap_or_default()
+    }
+
+    let mut csv = String::from(
+        "Title,Character,Category,Author,Version,Nexus Mod ID,Nexus File ID,Enabled,File Size,File Name\n",
+    );
+    for entry in mods {
+        let row = [
+            field(&entry.title),
+            optional(&entry.character),
+            field(&entry.category.to_string()),
+            optional(&entry.author),
+            optional(&entry.version),
+            optional(&entry.nexus_mod_id),
+            optional(&entry.nexus_file_id),
+            entry.enabled.to_string(),
+            entry.file_size.to_string(),
+            field(&entry.original_file_name),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// `mods` as Markdown tables, one per character (A to Z, mods without one
+/// last), titles linking to Nexus where the mod came from there
+fn mod_list_markdown(mods: &[ModListEntry]) -> String {
+    fn cell(value: &str) -> String {
+        value.replace('|', "\\|").replace(['\n', '\r'], " ")
+    }
+
+    let mut by_character: BTreeMap<(bool, String), Vec<&ModListEntry>> = BTreeMap::new();
+    for entry in mods {
+        let key = match &entry.character {
+            Some(character) => (false, character.to_string()),
+            None => (true, "No character".to_string()),
+        };
+        by_character.entry(key).or_default().push(entry);
+    }
+
+    let mut markdown = format!("# Marvel Rivals mods\n\n{} mod(s)\n", mods.len());
+    for ((_, character), entries) in by_character {
+        markdown.push_str(&format!("\n## {}\n\n", character));
+        markdown.push_str("| Mod | Category | Author | Version | Enabled | Size |\n");
+        markdown.push_str("| --- | --- | --- | --- | --- | --- |\n");
+        for entry in entries {
+            let title = cell(&entry.title);
+            let title = match entry.nexus_mod_id {
+                Some(id) => format!(
+                    "[{}]({})",
+                    title.replace('[', "\\[").replace(']', "\\]"),
+                    nexus_mod_url(id)
+                ),
+                None => title,
+            };
+            markdown.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} |\n",
+                title,
+                cell(&entry.category.to_string()),
+                cell(entry.author.as_deref().unwrap_or("")),
+                cell(entry.version.as_deref().unwrap_or("")),
+                if entry.enabled { "Yes" } else { "No" },
+                format_size(entry.file_size)
+            ));
+        }
+    }
+    markdown
+}
+
+/// Totals, per-category and per-character counts of `mods` in one pass.
+/// Both lists are sorted most first; mods without a character are counted
+/// under `None`, which goes last.
+fn aggregate_stats(mods: &[ModInfo]) -> (AppStats, Vec<CategoryStats>, Vec<CharacterStats>) {
+    let mut totals = AppStats {
+        total_mods: 0,
+        enabled_mods: 0,
+        disabled_mods: 0,
+        total_size: 0,
+    };
+    let mut categories: Vec<CategoryStats> = Vec::new();
+    let mut characters: Vec<CharacterStats> = Vec::new();
+    for m in mods {
+        let (enabled, disabled) = if m.enabled { (1, 0) } else { (0, 1) };
+        totals.total_mods += 1;
+        totals.enabled_mods += enabled;
+        totals.disabled_mods += disabled;
+        totals.total_size += m.file_size;
+
+        match categories.iter_mut().find(|c| c.category == m.category) {
+            Some(stats) => {
+                stats.count += 1;
+                stats.enabled += enabled;
+                stats.disabled += disabled;
+            }
+            None => categories.push(CategoryStats {
+                category: m.category.clone(),
+                count: 1,
+                enabled,
+                disabled,
+            }),
+        }
+
+        match characters.iter_mut().find(|c| c.character == m.character) {
+            Some(stats) => {
+                stats.count += 1;
+                stats.enabled += enabled;
+                stats.disabled += disabled;
+            }
+            None => characters.push(CharacterStats {
+                character: m.character.clone(),
+                count: 1,
+                enabled,
+                disabled,
+            }),
+        }
+    }
+    categories.sort_by(|a, b| b.count.cmp(&a.count));
+    characters.sort_by(|a, b| {
+        a.character
+            .is_none()
+            .cmp(&b.character.is_none())
+            .then(b.count.cmp(&a.count))
+    });
+    (totals, categories, characters)
+}
+
+/// True when `new` differs from `old` only in [`LIGHT_METADATA_FIELDS`]
+fn is_light_change(old: &ModMetadata, new: &ModMetadata) -> bool {
+    let without_light_fields = |metadata: &ModMetadata| {
+        let mut value = serde_json::to_value(metadata).ok()?;
+        let object = value.as_object_mut()?;
+        for field in LIGHT_METADATA_FIELDS {
+            object.remove(*field);
+        }
+        Some(value)
+    };
+    matches!(
+        (without_light_fields(old), without_light_fields(new)),
+        (Some(a), Some(b)) if a == b
+    )
+}
+
+/// Longest folder name made from a title, in grapheme clusters, so an emoji
+/// or accented letter at the cut is kept whole or dropped whole
+const MAX_FOLDER_NAME_GRAPHEMES: usize = 100;
+
+/// Bound on the same name in UTF-16 units, what Windows counts against its
+/// 255-unit limit. Only long emoji sequences get near it.
+const MAX_FOLDER_NAME_UTF16: usize = 200;
+
+/// A folder name for a title, category or character. CJK and emoji are
+/// kept; only what Windows refuses is dropped. The title is normalized to
+/// NFC first, so the same title typed or pasted differently always gives
+/// byte-identical folder names.
+fn sanitize_folder_name(name: &str) -> String {
+    let cleaned: String = name
+        .nfc()
+        // Remove invalid Windows filename characters: < > : " / \ | ? *
+        .filter(|c| {
+            !matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') && !c.is_control()
+        })
+        .collect();
+    // Collapse whitespace runs into single hyphens
+    let joined = cleaned.split_whitespace().collect::<Vec<_>>().join("-");
+    let trimmed = joined.trim_matches(|c| c == '-' || c == '.');
+
+    let mut name = String::new();
+    let mut utf16_len = 0;
+    for grapheme in trimmed.graphemes(true).take(MAX_FOLDER_NAME_GRAPHEMES) {
+        utf16_len += grapheme.encode_utf16().count();
+        if utf16_len > MAX_FOLDER_NAME_UTF16 {
+            break;
+        }
+        name.push_str(grapheme);
+    }
+    // The cut can leave a hyphen or dot at the end, which Windows strips
+    name.trim_end_matches(['-', '.']).to_string()
+}
+
+/// One segment of a requested install folder without the characters Windows
+/// won't allow in a folder name, or the trailing dots and spaces it strips
+fn clean_folder_segment(segment: &str) -> String {
+    segment
+        .chars()
+        .filter(|c| !matches!(c, '<' | '>' | ':' | '"' | '|' | '?' | '*') && !c.is_control())
+        .collect::<String>()
+        .trim_end_matches(['.', ' '])
+        .to_string()
+}
+
+/// A stored folder path relative to ~mods or disabled-mods, each segment
+/// cleaned like [`clean_folder_segment`]. None when it climbs out (`..`),
+/// is absolute or names a drive, so joining it can't leave the root.
+pub(crate) fn contained_folder(folder: &str) -> Option<PathBuf> {
+    if folder.starts_with(['/', '\\']) || Path::new(folder).is_absolute() {
+        return None;
+    }
+    let mut path = PathBuf::new();
+    for segment in folder.split(['/', '\\']) {
+        match segment {
+            "" | "." => continue,
+            ".." => return None,
+            // "C:" or "C:foo", a drive prefix on Windows
+            _ if segment.contains(':') => return None,
+            _ => {}
+        }
+        let cleaned = clean_folder_segment(segment);
+        if !cleaned.is_empty() {
+            path.push(cleaned);
+        }
+    }
+    Some(path)
+}
+
+/// Swap the leading category folder of a relative mod folder path
+fn recategorize_folder(folder: &str, from: &ModCategory, to: &ModCategory) -> String {
+    let (first, rest) = match folder.find(['/', '\\']) {
+        Some(index) => folder.split_at(index),
+        None => (folder, ""),
+    };
+    let (priority, category) = split_priority_prefix(first);
+    if category.eq_ignore_ascii_case(&sanitize_folder_name(&from.to_string())) {
+        let to = sanitize_folder_name(&to.to_string());
+        match priority {
+            Some(priority) => format!("{:03}_{}{}", priority, to, rest),
+            None => format!("{}{}", to, rest),
+        }
+    } else {
+        folder.to_string()
+    }
+}
+
+/// Compiled size allowed for a user-supplied find-and-replace pattern
+const REPLACE_REGEX_SIZE_LIMIT: usize = 1 << 20;
+/// Longest one field may take to process. The regex crate runs in time
+/// linear in the input and never backtracks, so this only trips on huge
+/// fields with costly patterns; it keeps a bad pattern from stalling the
+/// whole library.
+const REPLACE_FIELD_TIME_LIMIT: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// The find and replace of `find_replace_metadata`. Literal finds are case
+/// sensitive; regex replacements may refer to groups (`$1`).
+enum TextReplacer {
+    Literal {
+        find: String,
+        replace: String,
+    },
+    Regex {
+        regex: regex::Regex,
+        replace: String,
+    },
+}
+
+impl TextReplacer {
+    fn new(find: &str, replace: &str, regex: bool) -> Result<Self, String> {
+        if find.is_empty() {
+            return Err("Nothing to find".to_string());
+        }
+        if !regex {
+            return Ok(TextReplacer::Literal {
+                find: find.to_string(),
+                replace: replace.to_string(),
+            });
+        }
+        let regex = regex::RegexBuilder::new(find)
+            .size_limit(REPLACE_REGEX_SIZE_LIMIT)
+            .dfa_size_limit(REPLACE_REGEX_SIZE_LIMIT)
+            .build()
+            .map_err(|e| format!("Invalid pattern: {}", e))?;
+        Ok(TextReplacer::Regex {
+            regex,
+            replace: replace.to_string(),
+        })
+    }
+
+    fn replace(&self, text: &str) -> Result<String, String> {
+        let started = std::time::Instant::now();
+        let replaced = match self {
+            TextReplacer::Literal { find, replace } => text.replace(find.as_str(), replace),
+            TextReplacer::Regex { regex, replace } => {
+                regex.replace_all(text, replace.as_str()).into_owned()
+            }
+        };
+        if started.elapsed() > REPLACE_FIELD_TIME_LIMIT {
+            return Err("Pattern is too slow; try a simpler one".to_string());
+        }
+        Ok(replaced)
+    }
+
+    /// Apply to `field` of `metadata`. Empty tags and authors are dropped
+    /// later, by validation.
+    fn apply(&self, metadata: &mut ModMetadata, field: MetadataTextField) -> Result<(), String> {
+        match field {
+            MetadataTextField::Title => metadata.title = self.replace(&metadata.title)?,
+            MetadataTextField::Description => {
+                metadata.description = self.replace(&metadata.description)?
+            }
+            MetadataTextField::Author => {
+                let author = self.replace(metadata.author.as_deref().unwrap_or_default())?;
+                metadata.author = Some(author);
+            }
+            MetadataTextField::Tags => {
+                let mut tags = Vec::new();
+                for tag in &metadata.tags {
+                    tags.push(self.replace(tag)?);
+                }
+                metadata.tags = tags;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `field` of `metadata` as shown in find-and-replace results
+fn metadata_field_text(metadata: &ModMetadata, field: MetadataTextField) -> String {
+    match field {
+        MetadataTextField::Title => metadata.title.clone(),
+        MetadataTextField::Description => metadata.description.clone(),
+        MetadataTextField::Author => metadata.author.clone().unwrap_or_default(),
+        MetadataTextField::Tags => metadata.tags.join(", "),
+    }
+}
+
+/// Every pak and companion file under `dir`, grouped by file stem
+fn mod_files_by_stem(dir: &Path) -> HashMap<String, Vec<PathBuf>> {
+    let mut files: HashMap<String, Vec<PathBuf>> = HashMap::new();
+    for entry in WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        if !MOD_FILE_EXTENSIONS
+            .iter()
+            .any(|ext| has_extension(path, ext))
+        {
+            continue;
+        }
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            files
+                .entry(stem.to_string())
+                .or_default()
+                .push(path.to_path_buf());
+        }
+    }
+    files
+}
+
+/// Whether two mod folders hold the same pak and companion files at the same
+/// relative paths with the same contents, compared by `hash`. Anything
+/// unreadable counts as a difference, which keeps both copies.
+fn mod_folders_identical(
+    a: &Path,
+    b: &Path,
+    hash: impl Fn(&Path) -> Result<String, String>,
+) -> bool {
+    let mod_files = |dir: &Path| -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = WalkDir::new(dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| {
+                MOD_FILE_EXTENSIONS
+                    .iter()
+                    .any(|ext| has_extension(e.path(), ext))
+            })
+            .filter_map(|e| e.path().strip_prefix(dir).ok().map(Path::to_path_buf))
+            .collect();
+        files.sort();
+        files
+    };
+    let files = mod_files(a);
+    if files.is_empty() || files != mod_files(b) {
+        return false;
+    }
+    files
+        .iter()
+        .all(|rel| files_identical(&a.join(rel), &b.join(rel), &hash).unwrap_or(false))
+}
+
+/// Compare sizes first, then content hashes
+fn files_identical(
+    a: &Path,
+    b: &Path,
+    hash: impl Fn(&Path) -> Result<String, String>,
+) -> Result<bool, String> {
+    let size = |path: &Path| {
+        fs::metadata(path)
+            .map(|m| m.len())
+            .map_err(|e| e.to_string())
+    };
+    if size(a)? != size(b)? {
+        return Ok(false);
+    }
+    Ok(hash(a)? == hash(b)?)
+}
+
+/// `path` with `-2`, `-3`, ... appended to its name, whichever is free first
+fn free_sibling_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    (2..)
+        .map(|n| path.with_file_name(format!("{}-{}", name, n)))
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded range")
+}
+
+/// `path` with `-2`, `-3`, ... inserted before its extension, whichever is
+/// free first
+fn free_file_path(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (2..)
+        .map(|n| path.with_file_name(format!("{}-{}{}", stem, n, extension)))
+        .find(|candidate| !candidate.exists())
+        .expect("unbounded range")
+}
+
+/// Every `*.pak.disabled` and companion `*.ucas.disabled` etc. under `dir`,
+/// grouped by folder and file stem
+fn legacy_disabled_files(dir: &Path) -> BTreeMap<(PathBuf, String), Vec<PathBuf>> {
+    let mut files: BTreeMap<(PathBuf, String), Vec<PathBuf>> = BTreeMap::new();
+    for entry in WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        if !has_extension(path, "disabled") {
+            continue;
+        }
+        let original = Path::new(strip_disabled_suffix(path));
+        if !MOD_FILE_EXTENSIONS
+            .iter()
+            .any(|ext| has_extension(original, ext))
+        {
+            continue;
+        }
+        let stem = original.file_stem().and_then(|s| s.to_str());
+        let (Some(parent), Some(stem)) = (path.parent(), stem) else {
+            continue;
+        };
+        files
+            .entry((parent.to_path_buf(), stem.to_string()))
+            .or_default()
+            .push(path.to_path_buf());
+    }
+    files
+}
+
+/// File name of `path` without a trailing `.disabled`
+fn strip_disabled_suffix(path: &Path) -> &str {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let cut = name.len().saturating_sub(".disabled".len());
+    match name.get(cut..) {
+        Some(suffix) if suffix.eq_ignore_ascii_case(".disabled") => &name[..cut],
+        _ => name,
+    }
+}
+
+/// Name or path as it is when enabled: without a legacy `.disabled` suffix
+/// or the `disabled` renaming in place appends to the extension
+fn strip_disabled_marker(name: &str) -> String {
+    let name = name.replace(".disabled", "");
+    let lower = name.to_ascii_lowercase();
+    if MOD_FILE_EXTENSIONS
+        .iter()
+        .any(|ext| lower.ends_with(&format!(".{}disabled", ext)))
+    {
+        return name[..name.len() - "disabled".len()].to_string();
+    }
+    name
+}
+
+/// ID for the second copy of a duplicated mod, from its path as it is, so
+/// unlike the shared ID it keeps the disabled marker
+fn duplicate_mod_id(path: &Path) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"duplicate:");
+    hasher.update(path.to_string_lossy().as_bytes());
+    format!("{:x}", hasher.finalize())[..16].to_string()
+}
+
+/// `path` with its extension switched to the renamed-in-place form
+/// (`.pakdisabled`) when `disabled`, or back to the plain one
+fn toggled_in_place(path: &Path, disabled: bool) -> PathBuf {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    let base = if extension.to_ascii_lowercase().ends_with("disabled") {
+        &extension[..extension.len() - "disabled".len()]
+    } else {
+        extension
+    };
+    if disabled {
+        path.with_extension(format!("{}disabled", base))
+    } else {
+        path.with_extension(base)
+    }
+}
+
+/// Rename a mod's files to or from the disabled-in-place form. Every target
+/// is checked first so a collision renames nothing, and a failed rename
+/// puts back the ones already done.
+fn rename_in_place(files: &[PathBuf], disable: bool) -> Result<Vec<PathBuf>, String> {
+    let targets: Vec<PathBuf> = files
+        .iter()
+        .map(|file| toggled_in_place(file, disable))
+        .collect();
+    rename_all(files, &targets)?;
+    Ok(targets)
+}
+
+/// Rename each of `files` to the matching entry of `targets`, all or
+/// nothing: a taken target renames nothing, and a failed rename puts back
+/// the ones already done
+fn rename_all(files: &[PathBuf], targets: &[PathBuf]) -> Result<(), String> {
+    if let Some(taken) = targets
+        .iter()
+        .zip(files)
+        .find(|(target, file)| target != file && target.exists())
+        .map(|(target, _)| target)
+    {
+        return Err(format!("{:?} already exists", taken));
+    }
+    for (done, (file, target)) in files.iter().zip(targets).enumerate() {
+        if let Err(e) = fs_ops::rename(file, target) {
+            for (file, target) in files.iter().zip(targets).take(done) {
+                let _ = fs_ops::rename(target, file);
+            }
+            return Err(format!("Failed to rename {:?}: {}", file, e));
+        }
+    }
+    Ok(())
+}
+
+/// Shortest shared start, in characters, that makes two pak names variants
+/// of one mod
+const MIN_VARIANT_PREFIX: usize = 4;
+
+/// The start `a` and `b` share, when it's long enough to call them variants
+/// of one mod: [`MIN_VARIANT_PREFIX`] characters and at least half the
+/// shorter name, not counting its `_P` suffix. Case is ignored, and
+/// separators at the end of the shared start are dropped.
+fn variant_prefix<'a>(a: &'a str, b: &str) -> Option<&'a str> {
+    let base_len = |stem: &str| {
+        let suffix = stem.len() > 2 && stem.to_ascii_lowercase().ends_with("_p");
+        stem.len() - if suffix { 2 } else { 0 }
+    };
+    let shared: usize = a
+        .chars()
+        .zip(b.chars())
+        .take_while(|(x, y)| x.to_lowercase().eq(y.to_lowercase()))
+        .map(|(x, _)| x.len_utf8())
+        .sum();
+    let prefix = a[..shared].trim_end_matches(['_', '-', ' ', '.']);
+    let long_enough = prefix.chars().count() >= MIN_VARIANT_PREFIX
+        && prefix.len() * 2 >= base_len(a).min(base_len(b));
+    long_enough.then_some(prefix)
+}
+
+/// The load priority a file name's `NNN_` prefix gives, and the name
+/// without it
+fn split_priority_prefix(name: &str) -> (Option<u32>, &str) {
+    let bytes = name.as_bytes();
+    if bytes.len() > 4 && bytes[..3].iter().all(u8::is_ascii_digit) && bytes[3] == b'_' {
+        (name[..3].parse().ok(), &name[4..])
+    } else {
+        (None, name)
+    }
+}
+
+/// `relative`, a path under ~mods or disabled-mods, with its top segment
+/// carrying `priority` as its prefix, or none at all
+fn with_priority_prefix(relative: &Path, priority: Option<u32>) -> PathBuf {
+    let mut components = relative.components();
+    let Some(top) = components.next().and_then(|c| c.as_os_str().to_str()) else {
+        return relative.to_path_buf();
+    };
+    let (_, base) = split_priority_prefix(top);
+    let top = match priority {
+        Some(priority) => PathBuf::from(format!("{:03}_{}", priority, base)),
+        None => PathBuf::from(base),
+    };
+    let rest = components.as_path();
+    if rest.as_os_str().is_empty() {
+        top
+    } else {
+        top.join(rest)
+    }
+}
+
+/// Remove `folder` and then its parents while they are empty, stopping at
+/// `root`
+fn remove_emptied_folders(folder: &Path, root: &Path) {
+    for dir in folder.ancestors().take_while(|dir| *dir != root) {
+        // remove_dir refuses a folder that still holds something
+        if !dir.starts_with(root) || fs::remove_dir(dir).is_err() {
+            break;
+        }
+    }
+}
+
+/// The companions of `pak` in its folder, in [`COMPANION_EXTENSIONS`]
+/// order: files with its stem and a companion extension followed by
+/// `suffix` (`"disabled"` for a pak disabled in place). Stem and extension
+/// are compared ignoring case, since repackers don't keep it consistent;
+/// the folder is only listed when no exact match exists.
+pub fn find_companions(pak: &Path, suffix: &str) -> Vec<PathBuf> {
+    let (Some(stem), Some(dir)) = (pak.file_stem().and_then(|s| s.to_str()), pak.parent()) else {
+        return Vec::new();
+    };
+    let mut siblings: Option<Vec<PathBuf>> = None;
+    let mut companions = Vec::new();
+    for ext in COMPANION_EXTENSIONS {
+        let wanted = format!("{}.{}{}", stem, ext, suffix);
+        let exact = dir.join(&wanted);
+        if exact.is_file() {
+            companions.push(exact);
+            continue;
+        }
+        let siblings = siblings.get_or_insert_with(|| {
+            fs::read_dir(dir)
+                .map(|entries| {
+                    entries
+                        .filter_map(|e| e.ok())
+                        .map(|e| e.path())
+                        .filter(|path| path.is_file())
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+        let found = siblings.iter().find(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.eq_ignore_ascii_case(&wanted))
+        });
+        companions.extend(found.cloned());
+    }
+    companions
+}
+
+fn has_extension(path: &Path, ext: &str) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(ext))
+}
+
+/// Identifies the enabled set (paths, sizes, timestamps) a conflict scan
+/// looked at, so a cached result can tell when it no longer applies
+fn enabled_fingerprint(mods: &[ModInfo]) -> u64 {
+    use std::hash::{Hash, Hasher};
+
+    let mut enabled: Vec<(&Path, u64, DateTime<Utc>)> = mods
+        .iter()
+        .filter(|m| m.enabled)
+        .map(|m| (m.file_path.as_path(), m.file_size, m.last_modified))
+        .collect();
+    enabled.sort();
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    enabled.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn read_id_migration_checkpoint(path: &Path) -> Result<Option<IdMigrationCheckpoint>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read migration checkpoint: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse migration checkpoint: {}", e))
+}
+
+fn write_id_migration_checkpoint(
+    path: &Path,
+    checkpoint: &IdMigrationCheckpoint,
+) -> Result<(), String> {
+    let json = serde_json::to_string(checkpoint)
+        .map_err(|e| format!("Failed to serialize migration checkpoint: {}", e))?;
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, json)
+        .map_err(|e| format!("Failed to write migration checkpoint: {}", e))?;
+    fs::rename(&temp_path, path)
+        .map_err(|e| format!("Failed to finalize migration checkpoint: {}", e))
+}
+
+fn is_readonly(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.permissions().readonly())
+}
+
+/// Whether two directories live on the same volume, so moving between them
+/// is a rename rather than a copy. Either may not exist yet; the nearest
+/// existing ancestor stands in for it.
+fn same_volume(a: &Path, b: &Path) -> bool {
+    #[cfg(windows)]
+    {
+        use std::path::Component;
+        let prefix = |path: &Path| match path.components().next() {
+            Some(Component::Prefix(prefix)) => {
+                Some(prefix.as_os_str().to_string_lossy().to_lowercase())
+            }
+            _ => None,
+        };
+        prefix(a) == prefix(b)
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let device = |path: &Path| {
+            path.ancestors()
+                .find_map(|dir| fs::metadata(dir).ok())
+                .map(|m| m.dev())
+        };
+        device(a) == device(b)
+    }
+    #[cfg(not(any(windows, unix)))]
+    {
+        let _ = (a, b);
+        true
+    }
+}
+
+/// Size of every file in a mod's file set (pak + companions). Files that
+/// can't be stat'ed report 0 rather than failing the whole scan.
+fn file_sizes(files: &[PathBuf]) -> Vec<AssociatedFileSize> {
+    files
+        .iter()
+        .map(|path| AssociatedFileSize {
+            path: path.clone(),
+            size: fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        })
+        .collect()
+}
+
+fn total_size(sizes: &[AssociatedFileSize]) -> u64 {
+    sizes.iter().map(|f| f.size).sum()
+}
+
+/// The most recent modification time among `files`
+fn latest_modified(files: &[PathBuf]) -> Option<DateTime<Utc>> {
+    files
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .max()
+        .map(DateTime::from)
+}
+
+/// Check if a folder is completely empty (no files, only empty subdirectories)
+fn is_folder_empty(path: &Path) -> Result<bool, String> {
+    for entry in fs::read_dir(path).map_err(|e| format!("Failed to read directory: {}", e))? {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let entry_path = entry.path();
+
+        if entry_path.is_file() {
+            // Found a file, not empty
+            return Ok(false);
+        } else if entry_path.is_dir() {
+            // Recursively check subdirectory
+            if !is_folder_empty(&entry_path)? {
+                return Ok(false);
+            }
+        }
+    }
+
+    // No files found, folder is empty
+    Ok(true)
+}
+
+/// Lowercased title up to the first variant separator: a '-' with a space on
+/// either side. "Reshaped Idol - No Nip", "Reshaped Idol -No Nip" and
+/// "Reshaped Idol" all yield "reshaped idol".
+fn base_title(title: &str) -> String {
+    let lower = title.to_lowercase();
+    let chars: Vec<char> = lower.chars().collect();
+    for i in 0..chars.len() {
+        if chars[i] == '-' {
+            let space_before = i > 0 && chars[i - 1] == ' ';
+            let space_after = i + 1 < chars.len() && chars[i + 1] == ' ';
+            if space_before || space_after {
+                let head: String = chars[..i].iter().collect();
+                return head.trim().to_string();
+            }
+        }
+    }
+    lower.trim().to_string()
+}
+
+/// All single-segment space<->hyphen variants of a path. Used to reconstruct
+/// where a pak lived before a character folder was renamed or merged
+/// (e.g. "Black Widow" vs "Black-Widow").
+fn path_segment_variants(path: &Path) -> Vec<PathBuf> {
+    use std::path::Component;
+
+    let comps: Vec<Component> = path.components().collect();
+    let mut variants = Vec::new();
+
+    for (i, comp) in comps.iter().enumerate() {
+        let Component::Normal(os) = *comp else {
+            continue;
+        };
+        let Some(seg) = os.to_str() else { continue };
+
+        for replaced in [seg.replace('-', " "), seg.replace(' ', "-")] {
+            if replaced == seg {
+                continue;
+            }
+            let mut variant = PathBuf::new();
+            for (j, comp) in comps.iter().enumerate() {
+                if i == j {
+                    variant.push(&replaced);
+                } else {
+                    variant.push(comp.as_os_str());
+                }
+            }
+            variants.push(variant);
+        }
+    }
+
+    variants
+}
+
+// ===== UE5 IoStore (.utoc) asset reader =====
+//
+// We only need the list of game asset paths a mod overrides — that lives in the
+// uncompressed "directory index" of the .utoc, so no Oodle/decompression is
+// required. The directory index begins with a mount-point FString ("../../../"
+// for these mods); we locate it directly rather than summing the variable TOC
+// sections (some paks include a perfect-hash block that shifts the offset).
+
+const NONE_ENTRY: u32 = 0xFFFF_FFFF;
+
+fn read_u32(buf: &[u8], off: usize) -> Option<u32> {
+    buf.get(off..off + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_i32(buf: &[u8], off: usize) -> Option<i32> {
+    buf.get(off..off + 4)
+        .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Read an Unreal FString at `off`. Returns (string, new_offset).
+/// Positive length => UTF-8 (incl. trailing NUL); negative => UTF-16LE.
+fn read_fstring(buf: &[u8], off: usize) -> Option<(String, usize)> {
+    let n = read_i32(buf, off)?;
+    let mut off = off + 4;
+    if n == 0 {
+        return Some((String::new(), off));
+    }
+    if n < 0 {
+        let count = (-n) as usize;
+        let bytes = buf.get(off..off + count * 2)?;
+        off += count * 2;
+        let u16s: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        let s = String::from_utf16_lossy(&u16s)
+            .trim_end_matches('\u{0}')
+            .to_string();
+        Some((s, off))
+    } else {
+        let count = n as usize;
+        let bytes = buf.get(off..off + count)?;
+        off += count;
+        let s = String::from_utf8_lossy(bytes)
+            .trim_end_matches('\u{0}')
+            .to_string();
+        Some((s, off))
+    }
+}
+
+/// Parse the FIoDirectoryIndexResource starting at `start`, returning every
+/// file it lists as (full path, chunk index). The chunk index (the entry's
+/// user_data) points into the TOC's chunk tables, letting callers look up the
+/// per-chunk content hash.
+fn parse_directory_index(buf: &[u8], start: usize) -> Option<Vec<(String, u32)>> {
+    let (_mount, mut off) = read_fstring(buf, start)?;
+
+    let ndir = read_u32(buf, off)? as usize;
+    off += 4;
+    // dir entries: name, first_child, next_sibling, first_file (4 x u32)
+    let mut dirs: Vec<[u32; 4]> = Vec::with_capacity(ndir);
+    for _ in 0..ndir {
+        let e = [
+            read_u32(buf, off)?,
+            read_u32(buf, off + 4)?,
+            read_u32(buf, off + 8)?,
+            read_u32(buf, off + 12)?,
+        ];
+        off += 16;
+        dirs.push(e);
+    }
+
+    let nfile = read_u32(buf, off)? as usize;
+    off += 4;
+    // file entries: name, next_file, user_data (3 x u32)
+    let mut files: Vec<[u32; 3]> = Vec::with_capacity(nfile);
+    for _ in 0..nfile {
+        let e = [
+            read_u32(buf, off)?,
+            read_u32(buf, off + 4)?,
+            read_u32(buf, off + 8)?,
+        ];
+        off += 12;
+        files.push(e);
+    }
+
+    let nstr = read_u32(buf, off)? as usize;
+    off += 4;
+    let mut strings: Vec<String> = Vec::with_capacity(nstr);
+    for _ in 0..nstr {
+        let (s, n) = read_fstring(buf, off)?;
+        off = n;
+        strings.push(s);
+    }
+
+    let name = |i: u32| -> &str {
+        if i != NONE_ENTRY && (i as usize) < strings.len() {
+            &strings[i as usize]
+        } else {
+            ""
+        }
+    };
+
+    let mut out: Vec<(String, u32)> = Vec::new();
+    if dirs.is_empty() {
+        return Some(out);
+    }
+
+    // Iterative depth-first walk to avoid recursion limits / cycles.
+    // stack holds (dir_index, parent_path).
+    let mut stack: Vec<(u32, String)> = vec![(0, String::new())];
+    let mut guard = 0usize;
+    while let Some((di, prefix)) = stack.pop() {
+        guard += 1;
+        if di == NONE_ENTRY || di as usize >= dirs.len() || guard > 1_000_000 {
+            continue;
+        }
+        let d = dirs[di as usize];
+        let nm = name(d[0]);
+        let path = if nm.is_empty() {
+            prefix.clone()
+        } else {
+            format!("{}/{}", prefix.trim_end_matches('/'), nm)
+        };
+
+        // files in this directory: (name, next_file, user_data=chunk index)
+        let mut fi = d[3];
+        while fi != NONE_ENTRY && (fi as usize) < files.len() {
+            let fe = files[fi as usize];
+            let fname = name(fe[0]);
+            if !fname.is_empty() {
+                out.push((format!("{}/{}", path.trim_end_matches('/'), fname), fe[2]));
+            }
+            fi = fe[1];
+        }
+
+        // child directories (push siblings + this child)
+        let mut c = d[1];
+        while c != NONE_ENTRY && (c as usize) < dirs.len() {
+            stack.push((c, path.clone()));
+            c = dirs[c as usize][2];
         }
+    }
 
-        // Check filename-based thumbnails
-        let base_name = Path::new(file_name).file_stem().and_then(|s| s.to_str())?;
-
-        if let Some(path) = index.get(&base_name.to_lowercase()) {
-            return Some(path.clone());
-        }
+    Some(out)
+}
 
-        // Try with _P removed
-        let alt_name = base_name.replace("_P", "").to_lowercase();
-        if let Some(path) = index.get(&alt_name) {
-            return Some(path.clone());
-        }
+/// Find the mount point string "../../../" and back up 4 bytes to its
+/// length prefix. We locate the directory index this way rather than
+/// summing section sizes (some paks include a perfect-hash block that
+/// shifts the offset).
+fn find_directory_index(buf: &[u8]) -> Result<usize, String> {
+    let needle = b"../../../";
+    let mloc = buf
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .ok_or("directory index not found")?;
+    if mloc < 4 {
+        return Err("malformed directory index".into());
+    }
+    Ok(mloc - 4)
+}
 
-        None
+/// TOC format version and mount point of a `.utoc`
+fn read_utoc_header(path: &Path) -> Result<(u8, String), String> {
+    let buf = fs::read(path).map_err(|e| format!("read utoc: {}", e))?;
+    if buf.len() < 0x90 || &buf[0..16] != b"-==--==--==--==-" {
+        return Err("not a TOC file".into());
     }
+    let start = find_directory_index(&buf)?;
+    let (mount_point, _) = read_fstring(&buf, start).ok_or("truncated directory index")?;
+    Ok((buf[16], mount_point))
+}
 
-    fn find_thumbnail(&self, mod_id: &str, file_name: &str) -> Option<PathBuf> {
-        // Try common thumbnail formats
-        let extensions = ["webp", "png", "jpg", "jpeg"];
+pub(crate) const PAK_MAGIC: u32 = 0x5A6F_12E1;
 
-        // First priority: Check for new thumbnail format in metadata directory
-        let new_thumbnail_path = self
-            .metadata_directory
-            .join(format!("{}_thumbnail.png", mod_id));
-        if new_thumbnail_path.exists() {
-            return Some(new_thumbnail_path);
-        }
+/// The smallest pak footer: magic, version, index offset and size, and the
+/// index hash
+const PAK_FOOTER_LEN: u64 = 44;
 
-        // Second priority: Check for mod_id-based thumbnails in thumbnails directory (from old Electron app)
-        for ext in &extensions {
-            let id_thumbnail = format!("{}.{}", mod_id, ext);
-            let id_path = self.thumbnails_directory.join(&id_thumbnail);
+/// Refuse a pak that can't load: empty, too short to hold a footer, with no
+/// footer magic near its end (a truncated download loses it) or an index
+/// that runs past the end of the file
+pub fn validate_pak_file(path: &Path) -> Result<(), String> {
+    use std::io::{Read, Seek, SeekFrom};
 
-            if id_path.exists() {
-                return Some(id_path);
-            }
-        }
+    let corrupt = |reason: &str| {
+        format!(
+            "File appears to be a corrupted or incomplete .pak ({})",
+            reason
+        )
+    };
+    let mut file = fs::File::open(path).map_err(|e| format!("Can't open {:?}: {}", path, e))?;
+    let len = file
+        .metadata()
+        .map_err(|e| format!("Can't read {:?}: {}", path, e))?
+        .len();
+    if len == 0 {
+        return Err(corrupt("empty file"));
+    }
+    if len < PAK_FOOTER_LEN {
+        return Err(corrupt("too small to hold a footer"));
+    }
+
+    let mut tail = Vec::new();
+    file.seek(SeekFrom::Start(len.saturating_sub(1024)))
+        .and_then(|_| file.read_to_end(&mut tail))
+        .map_err(|e| format!("Can't read {:?}: {}", path, e))?;
+    let magic = PAK_MAGIC.to_le_bytes();
+    let at = tail
+        .windows(4)
+        .rposition(|w| w == magic)
+        .ok_or_else(|| corrupt("missing magic"))?;
+    let read_u64 = |off: usize| {
+        tail.get(off..off + 8)
+            .map(|b| u64::from_le_bytes(b.try_into().unwrap_or_default()))
+    };
+    let (Some(index_offset), Some(index_size)) = (read_u64(at + 8), read_u64(at + 16)) else {
+        return Err(corrupt("truncated footer"));
+    };
+    if index_offset.saturating_add(index_size) > len {
+        return Err(corrupt("index runs past the end of the file"));
+    }
+    Ok(())
+}
 
-        // Fall back to filename-based thumbnails
-        let base_name = Path::new(file_name).file_stem().and_then(|s| s.to_str())?;
+/// [`validate_pak_file`] for a mod's pak, with a warning for each IoStore
+/// companion missing its partner
+fn validate_mod_files(mod_info: &ModInfo) -> PakValidation {
+    let has = |ext: &str| {
+        mod_info
+            .associated_files
+            .iter()
+            .any(|f| has_extension(f, ext) || has_extension(f, &format!("{}disabled", ext)))
+    };
+    let warnings = match (has("ucas"), has("utoc")) {
+        (true, false) => vec!["Has a .ucas but no .utoc; the game won't load it".to_string()],
+        (false, true) => vec!["Has a .utoc but no .ucas; the game won't load it".to_string()],
+        _ => Vec::new(),
+    };
+    PakValidation {
+        mod_id: mod_info.id.clone(),
+        title: mod_info.metadata.title.clone(),
+        pak_path: mod_info.file_path.clone(),
+        error: validate_pak_file(&mod_info.file_path).err(),
+        warnings,
+    }
+}
 
-        for ext in &extensions {
-            let thumbnail_name = format!("{}.{}", base_name, ext);
-            let thumbnail_path = self.thumbnails_directory.join(&thumbnail_name);
+/// Pak format version and mount point from a `.pak` footer and the start of
+/// its index. The footer layout varies by version, so the magic is searched
+/// for near the end of the file. The mount point is None when the index is
+/// encrypted.
+fn read_pak_header(path: &Path) -> Result<(u32, Option<String>), String> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = fs::File::open(path).map_err(|e| format!("open pak: {}", e))?;
+    let len = file
+        .metadata()
+        .map_err(|e| format!("read pak: {}", e))?
+        .len();
+    let read_at = |file: &mut fs::File, at: u64, size: u64| -> Result<Vec<u8>, String> {
+        let mut buf = vec![0u8; size.min(len.saturating_sub(at)) as usize];
+        file.seek(SeekFrom::Start(at))
+            .and_then(|_| file.read_exact(&mut buf))
+            .map_err(|e| format!("read pak: {}", e))?;
+        Ok(buf)
+    };
 
-            if thumbnail_path.exists() {
-                return Some(thumbnail_path);
-            }
+    let tail_start = len.saturating_sub(1024);
+    let tail = read_at(&mut file, tail_start, 1024)?;
+    let magic = PAK_MAGIC.to_le_bytes();
+    let at = tail
+        .windows(4)
+        .rposition(|w| w == magic)
+        .ok_or("pak footer not found")?;
+    let version = read_u32(&tail, at + 4).ok_or("truncated pak footer")?;
+    let index_offset = tail
+        .get(at + 8..at + 16)
+        .map(|b| u64::from_le_bytes(b.try_into().unwrap_or_default()))
+        .ok_or("truncated pak footer")?;
+    if index_offset >= len {
+        return Err("pak index offset out of range".into());
+    }
 
-            // Also try with _P removed (common pattern)
-            let alt_name = base_name.replace("_P", "");
-            let alt_thumbnail = format!("{}.{}", alt_name, ext);
-            let alt_path = self.thumbnails_directory.join(&alt_thumbnail);
+    // An encrypted index reads as noise; only trust a plausible path
+    let index = read_at(&mut file, index_offset, 1024)?;
+    let mount_point = read_i32(&index, 0)
+        .filter(|n| (1..1024).contains(n))
+        .and_then(|_| read_fstring(&index, 0))
+        .map(|(s, _)| s)
+        .filter(|s| s.starts_with("../") || s.starts_with('/'));
+    Ok((version, mount_point))
+}
 
-            if alt_path.exists() {
-                log::debug!("Found thumbnail (alternate): {:?}", alt_path);
-                return Some(alt_path);
+/// What the pak and .utoc of a mod say; anything unreadable is noted in
+/// `errors`
+fn read_pak_technical_info(mod_info: &ModInfo) -> PakTechnicalInfo {
+    let mut info = PakTechnicalInfo {
+        mod_id: mod_info.id.clone(),
+        pak_path: mod_info.file_path.clone(),
+        pak_version: None,
+        pak_mount_point: None,
+        utoc_path: mod_info
+            .associated_files
+            .iter()
+            .find(|p| has_extension(p, "utoc"))
+            .cloned(),
+        toc_version: None,
+        utoc_mount_point: None,
+        common_mount_point: None,
+        mount_point_outlier: false,
+        errors: Vec::new(),
+    };
+    match read_pak_header(&mod_info.file_path) {
+        Ok((version, mount_point)) => {
+            info.pak_version = Some(version);
+            info.pak_mount_point = mount_point;
+        }
+        Err(e) => info.errors.push(format!("pak: {}", e)),
+    }
+    if let Some(utoc) = &info.utoc_path {
+        match read_utoc_header(utoc) {
+            Ok((version, mount_point)) => {
+                info.toc_version = Some(version);
+                info.utoc_mount_point = Some(mount_point);
             }
+            Err(e) => info.errors.push(format!("utoc: {}", e)),
         }
-
-        None
     }
+    info
+}
 
-    /// Generate a unique mod ID from the full file path
-    /// This ensures that the same mod file installed multiple times from different
-    /// locations (e.g., different temp extractions) gets different IDs
-    ///
-    /// IMPORTANT: Normalizes the path by removing .disabled extension so that
-    /// enabled and disabled versions of the same mod get the same ID
-    fn generate_mod_id_from_path(&self, file_path: &Path, file_name: &str) -> String {
-        let mut hasher = Sha256::new();
+/// Compare a mod's mount point against the library's common one
+fn check_mount_point(mut info: PakTechnicalInfo, common: Option<&str>) -> PakTechnicalInfo {
+    info.mount_point_outlier = match (info.mount_point(), common) {
+        (Some(mount_point), Some(common)) => normalize_mount_point(mount_point) != common,
+        _ => false,
+    };
+    info.common_mount_point = common.map(str::to_string);
+    info
+}
 
-        // Normalize the path by removing .disabled extension
-        // This ensures enabled and disabled mods have the same ID
-        if let Some(path_str) = file_path.to_str() {
-            let mut normalized_path = path_str.replace(".disabled", "");
-            // Windows paths can arrive with mixed separators (frontend folder
-            // names use '/'); normalize so the same file always hashes to the
-            // same ID no matter which side built the path
-            if cfg!(windows) {
-                normalized_path = normalized_path.replace('/', "\\");
+/// `keep` with the fields it leaves empty filled in from `copies`, earlier
+/// copies first. Nexus IDs and version come as a set. Favorites and profile
+/// membership are combined.
+fn merge_duplicate_metadata(mut keep: ModMetadata, copies: &[&ModMetadata]) -> ModMetadata {
+    fn fill<T: Clone>(field: &mut Option<T>, from: &Option<T>) {
+        if field.is_none() {
+            field.clone_from(from);
+        }
+    }
+    for copy in copies {
+        if keep.description.trim().is_empty() {
+            keep.description.clone_from(&copy.description);
+        }
+        if keep.tags.is_empty() {
+            keep.tags.clone_from(&copy.tags);
+        }
+        fill(&mut keep.subtitle, &copy.subtitle);
+        fill(&mut keep.author, &copy.author);
+        fill(&mut keep.version, &copy.version);
+        fill(&mut keep.costume, &copy.costume);
+        fill(&mut keep.notes, &copy.notes);
+        if keep.nexus_mod_id.is_none() && copy.nexus_mod_id.is_some() {
+            keep.nexus_mod_id = copy.nexus_mod_id;
+            keep.nexus_file_id = copy.nexus_file_id;
+            keep.nexus_version.clone_from(&copy.nexus_version);
+        }
+        keep.is_favorite |= copy.is_favorite;
+        for profile_id in copy.profile_ids.iter().flatten() {
+            let profiles = keep.profile_ids.get_or_insert_with(Vec::new);
+            if !profiles.contains(profile_id) {
+                profiles.push(profile_id.clone());
             }
-            hasher.update(normalized_path.as_bytes());
-        } else {
-            // Fallback to file name if path is invalid
-            hasher.update(file_name.as_bytes());
         }
+    }
+    keep
+}
 
-        let result = hasher.finalize();
-        format!("{:x}", result)[..16].to_string()
+fn mod_references(mod_info: &ModInfo, collections: &[ModCollection]) -> ModReferences {
+    ModReferences {
+        mod_id: mod_info.id.clone(),
+        title: mod_info.metadata.title.clone(),
+        profiles: mod_info.metadata.profile_ids.clone().unwrap_or_default(),
+        collections: collections
+            .iter()
+            .filter(|c| c.mod_ids.contains(&mod_info.id))
+            .map(|c| c.id.clone())
+            .collect(),
     }
+}
 
-    /// Legacy function kept for compatibility
-    #[allow(dead_code)]
-    fn generate_mod_id(&self, file_name: &str) -> String {
-        let mut hasher = Sha256::new();
-        hasher.update(file_name.as_bytes());
-        let result = hasher.finalize();
-        format!("{:x}", result)[..16].to_string()
+/// Mount points compare case-insensitively, with one trailing slash
+fn normalize_mount_point(mount_point: &str) -> String {
+    format!("{}/", mount_point.trim_end_matches('/').to_lowercase())
+}
+
+/// Fewest working mods worth comparing against
+const MIN_MOUNT_POINT_SAMPLES: usize = 3;
+
+/// The mount point most of `mount_points` share, when at least three
+/// quarters of a big enough sample agree on it
+fn common_mount_point<'a>(mount_points: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut total = 0;
+    for mount_point in mount_points {
+        let key = normalize_mount_point(mount_point);
+        *counts.entry(key).or_default() += 1;
+        total += 1;
     }
+    let (common, count) = counts.into_iter().max_by_key(|(_, count)| *count)?;
+    (total >= MIN_MOUNT_POINT_SAMPLES && count * 4 >= total * 3).then_some(common)
+}
 
-    fn extract_mod_name(&self, file_name: &str) -> String {
-        let stem = Path::new(file_name)
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("Untitled Mod");
+/// Read the assets a mod's `.utoc` overrides, as a map of the `.uasset` path
+/// to a content hash. The hash combines the TOC chunk hashes of the asset AND
+/// its sibling payload files (`.ubulk`/`.uexp` share the same stem), so two
+/// mods shipping byte-identical copies of an asset produce the same value.
+fn read_utoc_assets(path: &Path) -> Result<HashMap<String, String>, String> {
+    let buf = fs::read(path).map_err(|e| format!("read utoc: {}", e))?;
+    if buf.len() < 0x90 || &buf[0..16] != b"-==--==--==--==-" {
+        return Err("not a TOC file".into());
+    }
+    // header: magic[16] version u8 reserved u8 reserved u16, then u32 fields:
+    // header_size, entry_count, block_count, block_size, method_count,
+    // method_len, compression_block_size, dir_index_size, ...
+    let dir_index_size = read_u32(&buf, 16 + 4 + 4 * 7)
+        .ok_or("truncated header")? as usize;
 
-        // Clean up common suffixes and prefixes. Only strip a trailing _P —
-        // a blanket replace mangles names like "D_Proficiency" → "Droficiency"
-        let stem = stem.strip_suffix("_P").unwrap_or(stem);
-        let cleaned_stem = stem
-            .replace("_pak", "") // Remove _pak suffix
-            .replace("&", " "); // Replace ampersand with space
+    let start = find_directory_index(&buf)?;
 
-        let parts: Vec<&str> = cleaned_stem
-            // Common patterns to remove - split by delimiters
-            .split(|c: char| c == '-' || c == '_' || c == '.')
-            .filter(|s| !s.is_empty())
-            // Skip common numeric patterns (version numbers, file IDs)
-            .filter(|s| {
-                // Keep it if it's not purely numeric or doesn't look like a version/ID
-                !s.chars().all(|c| c.is_numeric()) || s.len() <= 2
-            })
-            .collect();
+    let files = parse_directory_index(&buf, start)
+        .ok_or_else(|| String::from("failed to parse directory index"))?;
 
-        // Filter out short uppercase prefixes at the start (like "A", "AC", "X", etc.)
-        let cleaned = parts
+    // Chunk metas follow the directory index: one FIoStoreTocEntryMeta per
+    // chunk = 32-byte content hash + 1 flag byte (33 bytes each).
+    let metas_start = start + dir_index_size;
+    let chunk_hash_hex = |idx: u32| -> Option<String> {
+        let s = metas_start + (idx as usize) * 33;
+        buf.get(s..s + 32)
+            .map(|h| h.iter().map(|b| format!("{b:02x}")).collect())
+    };
+
+    // Group files by stem so payload siblings feed into the asset's hash.
+    let mut stem_parts: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    for (fpath, chunk_idx) in &files {
+        let (stem, ext) = match fpath.rsplit_once('.') {
+            Some((s, e)) => (s.to_string(), e.to_string()),
+            None => (fpath.clone(), String::new()),
+        };
+        let h = chunk_hash_hex(*chunk_idx).unwrap_or_default();
+        stem_parts.entry(stem).or_default().push((ext, h));
+    }
+
+    let mut out: HashMap<String, String> = HashMap::new();
+    for (stem, mut parts) in stem_parts {
+        if !parts.iter().any(|(e, _)| e == "uasset") {
+            continue;
+        }
+        parts.sort();
+        let combined: String = parts
             .iter()
-            .enumerate()
-            .filter(|(i, s)| {
-                if *i == 0 {
-                    // First word - keep it only if it's not a short uppercase prefix
-                    let is_short_uppercase =
-                        s.len() <= 2 && s.chars().all(|c| c.is_uppercase() || !c.is_alphabetic());
-                    !is_short_uppercase
-                } else {
-                    // Keep all other words
-                    true
-                }
-            })
-            .map(|(_, s)| *s)
+            .map(|(e, h)| format!("{e}:{h}"))
             .collect::<Vec<_>>()
-            .join(" ");
+            .join("|");
+        out.insert(format!("{stem}.uasset"), combined);
+    }
+    Ok(out)
+}
 
-        // Split camelCase words and title case
-        cleaned
-            .split_whitespace()
-            .flat_map(|word| self.split_camel_case(word))
-            .map(|word| {
-                // Skip capitalizing if it's an all-caps abbreviation
-                if word.len() <= 3 && word.chars().all(|c| c.is_uppercase() || !c.is_alphabetic()) {
-                    return word.to_string();
-                }
+/// `Full` when `shared` is every asset one of the mods `ids` overrides
+fn conflict_severity(
+    ids: &[String],
+    shared: &HashSet<String>,
+    mod_assets: &HashMap<String, HashMap<String, String>>,
+) -> ConflictSeverity {
+    let covered = ids.iter().any(|id| {
+        mod_assets
+            .get(id)
+            .is_some_and(|assets| assets.keys().all(|a| shared.contains(a)))
+    });
+    if covered {
+        ConflictSeverity::Full
+    } else {
+        ConflictSeverity::Partial
+    }
+}
 
-                let mut chars = word.chars();
-                match chars.next() {
-                    None => String::new(),
-                    Some(first) => first
-                        .to_uppercase()
-                        .chain(chars.as_str().to_lowercase().chars())
-                        .collect(),
-                }
-            })
-            .collect::<Vec<_>>()
-            .join(" ")
+/// Map an asset file stem to a human-readable conflict "kind" for the UI.
+fn classify_asset(stem: &str) -> String {
+    let s = stem.to_lowercase();
+    if s.starts_with("sk_") || s.contains("_skeleton") || s.contains("_lobby") {
+        "Body Mesh".to_string()
+    } else if s.contains("_body_") || s.ends_with("_body") || s.contains("body_0") {
+        "Body Skin".to_string()
+    } else if s.contains("_hair") {
+        "Hair".to_string()
+    } else if s.contains("_head") || s.contains("_face") || s.contains("_eyes") {
+        "Face".to_string()
+    } else if s.contains("_equip") || s.contains("_custom") || s.contains("_cloth") {
+        "Outfit".to_string()
+    } else if s.contains("_weapon") || s.contains("_rifle") || s.contains("_sword") {
+        "Weapon".to_string()
+    } else if s.starts_with("mi_") || s.starts_with("m_") {
+        "Material".to_string()
+    } else {
+        "Other".to_string()
+    }
+}
+/// Temp-directory fixtures for ModService tests: a fake game install, an app
+/// data metadata directory, and a scratch "downloads" folder for install
+/// sources. Everything lives under one TempDir that is removed on drop.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Clock pinned to a single instant
+    pub struct FixedClock(pub DateTime<Utc>);
+
+    impl Clock for FixedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0
+        }
     }
 
-    /// Split a word on camelCase boundaries
-    fn split_camel_case<'a>(&self, word: &'a str) -> Vec<&'a str> {
-        let mut parts = Vec::new();
-        let mut last_split = 0;
-        let chars: Vec<char> = word.chars().collect();
+    /// The instant every fixture service reports as "now"
+    pub fn fixed_now() -> DateTime<Utc> {
+        date("2025-06-15T12:00:00Z")
+    }
 
-        for i in 1..chars.len() {
-            // Split if we find a lowercase followed by uppercase (camelCase)
-            // or multiple uppercase followed by uppercase then lowercase (XMLParser -> XML Parser)
-            if (chars[i - 1].is_lowercase() && chars[i].is_uppercase())
-                || (i > 1
-                    && chars[i - 2].is_uppercase()
-                    && chars[i - 1].is_uppercase()
-                    && chars[i].is_lowercase())
-            {
-                parts.push(&word[last_split..i]);
-                last_split = i;
-            }
+    pub fn date(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    pub struct Fixture {
+        _root: TempDir,
+        pub game_dir: PathBuf,
+        pub metadata_dir: PathBuf,
+        pub source_dir: PathBuf,
+    }
+
+    impl Fixture {
+        pub fn new() -> Self {
+            let root = TempDir::new().expect("create temp dir");
+            let game_dir = root.path().join("MarvelRivals");
+            let metadata_dir = root.path().join("app_data").join("metadata");
+            let source_dir = root.path().join("downloads");
+
+            let fixture = Self {
+                _root: root,
+                game_dir,
+                metadata_dir,
+                source_dir,
+            };
+            fs::create_dir_all(fixture.mods_dir()).unwrap();
+            fs::create_dir_all(&fixture.metadata_dir).unwrap();
+            fs::create_dir_all(&fixture.source_dir).unwrap();
+            fixture
         }
 
-        // Add the remaining part
-        if last_split < word.len() {
-            parts.push(&word[last_split..]);
+        pub fn service(&self) -> ModService {
+            ModService::new(self.game_dir.clone(), self.metadata_dir.clone())
+                .with_clock(Arc::new(FixedClock(fixed_now())))
         }
 
-        if parts.is_empty() {
-            vec![word]
-        } else {
-            parts
+        pub fn mods_dir(&self) -> PathBuf {
+            self.game_dir
+                .join("MarvelGame")
+                .join("Marvel")
+                .join("Content")
+                .join("Paks")
+                .join("~mods")
         }
-    }
 
-    fn detect_category_from_path(&self, file_path: &Path, file_name: &str) -> ModCategory {
-        // First check the folder structure
-        let path_str = file_path.to_string_lossy().to_lowercase();
+        pub fn disabled_dir(&self) -> PathBuf {
+            self.metadata_dir.join("disabled-mods")
+        }
 
-        // Check if the path contains category folders
-        if path_str.contains("\\skins\\") || path_str.contains("/skins/") {
-            return ModCategory::Skins;
+        /// Write `{stem}.pak` (and `.ucas`/`.utoc` when `ucas_size` is set)
+        /// into `dir`. Returns the pak path. The .utoc is always 4 bytes.
+        pub fn write_mod(
+            &self,
+            dir: &Path,
+            stem: &str,
+            pak_size: usize,
+            ucas_size: Option<usize>,
+        ) -> PathBuf {
+            let pak = dir.join(format!("{stem}.pak"));
+            write_file(&pak, pak_size);
+            if let Some(ucas_size) = ucas_size {
+                write_file(&dir.join(format!("{stem}.ucas")), ucas_size);
+                write_file(&dir.join(format!("{stem}.utoc")), 4);
+            }
+            pak
         }
-        if path_str.contains("\\ui\\") || path_str.contains("/ui/") {
-            return ModCategory::UI;
+
+        /// A mod waiting in the downloads folder, ready to be installed
+        pub fn source_mod(&self, stem: &str, pak_size: usize, ucas_size: Option<usize>) -> PathBuf {
+            self.write_mod(&self.source_dir, stem, pak_size, ucas_size)
         }
-        if path_str.contains("\\audio\\") || path_str.contains("/audio/") {
-            return ModCategory::Audio;
+
+        /// A mod already sitting in ~mods at `folder` (relative, '/'-separated)
+        pub fn installed_mod(&self, folder: &str, stem: &str) -> PathBuf {
+            let dir = folder
+                .split('/')
+                .filter(|part| !part.is_empty())
+                .fold(self.mods_dir(), |path, part| path.join(part));
+            self.write_mod(&dir, stem, 8, Some(64))
         }
-        if path_str.contains("\\gameplay\\") || path_str.contains("/gameplay/") {
-            return ModCategory::Gameplay;
+
+        /// Number of metadata JSON files (disabled-mods lives alongside them
+        /// but is a directory, so it isn't counted)
+        pub fn metadata_count(&self) -> usize {
+            fs::read_dir(&self.metadata_dir)
+                .unwrap()
+                .flatten()
+                .filter(|e| e.path().extension().and_then(|x| x.to_str()) == Some("json"))
+                .count()
         }
+    }
 
-        // Fallback to filename detection
-        self.detect_category(file_name)
+    pub fn write_file(path: &Path, size: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; size]).unwrap();
     }
 
-    fn detect_category(&self, file_name: &str) -> ModCategory {
-        let lower_name = file_name.to_lowercase();
+    /// Metadata with fixed timestamps so assertions don't depend on the clock
+    pub fn metadata(title: &str, category: ModCategory) -> ModMetadata {
+        let date = date("2025-01-01T00:00:00Z");
+        ModMetadata {
+            title: title.to_string(),
+            subtitle: None,
+            description: String::new(),
+            author: None,
+            version: None,
+            tags: Vec::new(),
+            category,
+            character: None,
+            costume: None,
+            is_favorite: false,
+            is_nsfw: false,
+            created_at: date,
+            updated_at: date,
+            install_date: date,
+            profile_ids: None,
+            nexus_mod_id: None,
+            nexus_file_id: None,
+            nexus_version: None,
+            original_folder_path: None,
+            parent_mod_id: None,
+            disabled_reason: None,
+            disabled_at: None,
+            last_verified_game_build: None,
+            manage_layout: true,
+            file_fingerprint: None,
+            externally_modified_at: None,
+            collision_folder: None,
+            priority: None,
+            notes: None,
+            history: Vec::new(),
+            variant_group: None,
+        }
+    }
+}
 
-        for category in [
-            ModCategory::UI,
-            ModCategory::Audio,
-            ModCategory::Skins,
-            ModCategory::Gameplay,
-        ] {
-            if category
-                .keywords()
-                .iter()
-                .any(|keyword| lower_name.contains(keyword))
-            {
-                return category;
-            }
-        }
-
-        ModCategory::Skins // Default
-    }
-
-    fn detect_character_from_path(&self, file_path: &Path, file_name: &str) -> Option<Character> {
-        // First check the folder structure for character names
-        let path_str = file_path.to_string_lossy().to_lowercase();
-
-        let characters = [
-            // Vanguards
-            Character::CaptainAmerica,
-            Character::DoctorStrange,
-            Character::Groot,
-            Character::Hulk,
-            Character::Magneto,
-            Character::PeniParker,
-            Character::TheThing,
-            Character::Thor,
-            Character::Venom,
-            // Duelists
-            Character::Angela,
-            Character::Blade,
-            Character::BlackPanther,
-            Character::BlackWidow,
-            Character::Daredevil,
-            Character::EmmaFrost,
-            Character::Hawkeye,
-            Character::Hela,
-            Character::HumanTorch,
-            Character::IronFist,
-            Character::Magik,
-            Character::MisterFantastic,
-            Character::MoonKnight,
-            Character::Namor,
-            Character::Phoenix,
-            Character::Psylocke,
-            Character::ScarletWitch,
-            Character::SpiderMan,
-            Character::SquirrelGirl,
-            Character::StarLord,
-            Character::Storm,
-            Character::ThePunisher,
-            Character::Ultron,
-            Character::WinterSoldier,
-            Character::Wolverine,
-            // Strategists
-            Character::AdamWarlock,
-            Character::CloakAndDagger,
-            Character::InvisibleWoman,
-            Character::IronMan,
-            Character::JeffTheLandShark,
-            Character::Loki,
-            Character::LunaSnow,
-            Character::Mantis,
-            Character::RocketRaccoon,
-        ];
+#[cfg(test)]
+mod tests {
+    use super::fixtures::{date, fixed_now, metadata, write_file, Fixture};
+    use super::*;
+
+    #[test]
+    fn test_install_scan_toggle_round_trip() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let source = fx.source_mod("CoolSkin_P", 10, Some(1000));
+
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &source,
+                "Skins/Cool-Skin",
+                metadata("Cool Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        let folder = fx.mods_dir().join("Skins").join("Cool-Skin");
+        assert_eq!(installed.file_path, folder.join("CoolSkin_P.pak"));
+
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), 1);
+        assert!(mods[0].enabled);
+        assert_eq!(mods[0].id, installed.id);
+        assert_eq!(mods[0].associated_files.len(), 3);
+        assert_eq!(mods[0].pak_size, 10);
+        assert_eq!(mods[0].file_size, 10 + 1000 + 4);
+
+        // Disable: files move to disabled-mods keeping their folder structure
+        let disabled = service.enable_mod(&installed.id, false).unwrap();
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), 1);
+        assert!(!mods[0].enabled);
+        // The returned info already points at the new location
+        assert!(!disabled.enabled);
+        assert_eq!(disabled.id, mods[0].id);
+        assert_eq!(disabled.file_path, mods[0].file_path);
+        assert_eq!(disabled.associated_files.len(), 3);
+        assert!(disabled
+            .associated_files
+            .iter()
+            .all(|f| f.starts_with(fx.disabled_dir())));
+        assert_eq!(
+            mods[0].file_path,
+            fx.disabled_dir()
+                .join("Skins")
+                .join("Cool-Skin")
+                .join("CoolSkin_P.pak")
+        );
+        assert_eq!(mods[0].metadata.title, "Cool Skin");
+        assert!(!folder.join("CoolSkin_P.pak").exists());
+        assert_eq!(fx.metadata_count(), 1);
+
+        // Enable: back to the original path, so the original ID comes back too
+        service.enable_mod(&mods[0].id, true).unwrap();
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), 1);
+        assert!(mods[0].enabled);
+        assert_eq!(mods[0].id, installed.id);
+        assert_eq!(mods[0].metadata.title, "Cool Skin");
+        assert_eq!(fx.metadata_count(), 1);
+    }
 
-        // Check path for character folders
-        for character in &characters {
-            for keyword in character.keywords() {
-                let folder_pattern = format!("\\{}\\", keyword);
-                let folder_pattern_unix = format!("/{}/", keyword);
-                if path_str.contains(&folder_pattern) || path_str.contains(&folder_pattern_unix) {
-                    return Some(character.clone());
-                }
-            }
-        }
-
-        // Fallback to filename detection
-        self.detect_character(file_name)
-    }
-
-    fn detect_character(&self, file_name: &str) -> Option<Character> {
-        let lower_name = file_name.to_lowercase();
-
-        // Simple keyword matching - can be enhanced with scoring like in TypeScript version
-        let characters = [
-            // Vanguards
-            Character::CaptainAmerica,
-            Character::DoctorStrange,
-            Character::Groot,
-            Character::Hulk,
-            Character::Magneto,
-            Character::PeniParker,
-            Character::TheThing,
-            Character::Thor,
-            Character::Venom,
-            // Duelists
-            Character::Angela,
-            Character::Blade,
-            Character::BlackPanther,
-            Character::BlackWidow,
-            Character::Daredevil,
-            Character::EmmaFrost,
-            Character::Hawkeye,
-            Character::Hela,
-            Character::HumanTorch,
-            Character::IronFist,
-            Character::Magik,
-            Character::MisterFantastic,
-            Character::MoonKnight,
-            Character::Namor,
-            Character::Phoenix,
-            Character::Psylocke,
-            Character::ScarletWitch,
-            Character::SpiderMan,
-            Character::SquirrelGirl,
-            Character::StarLord,
-            Character::Storm,
-            Character::ThePunisher,
-            Character::Ultron,
-            Character::WinterSoldier,
-            Character::Wolverine,
-            // Strategists
-            Character::AdamWarlock,
-            Character::CloakAndDagger,
-            Character::InvisibleWoman,
-            Character::IronMan,
-            Character::JeffTheLandShark,
-            Character::Loki,
-            Character::LunaSnow,
-            Character::Mantis,
-            Character::RocketRaccoon,
-        ];
+    #[test]
+    fn test_toggle_keeps_folders_three_deep() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut storm_metadata = metadata("My Storm", ModCategory::Skins);
+        storm_metadata.notes = Some("Lightning recolor".to_string());
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("Storm_P", 10, Some(100)),
+                "Skins/Storm/My-Mod",
+                storm_metadata,
+            )
+            .unwrap();
+        let nested = |root: PathBuf| root.join("Skins").join("Storm").join("My-Mod");
+        assert_eq!(
+            installed.file_path,
+            nested(fx.mods_dir()).join("Storm_P.pak")
+        );
+        let thumbnail = |id: &str| fx.metadata_dir.join(format!("{}_thumbnail.png", id));
+        fs::write(thumbnail(&installed.id), b"png").unwrap();
+
+        // Disabled under the same three folders, with metadata and thumbnail
+        // following the new ID
+        let disabled = service.enable_mod(&installed.id, false).unwrap();
+        assert_ne!(disabled.id, installed.id);
+        assert_eq!(
+            disabled.file_path,
+            nested(fx.disabled_dir()).join("Storm_P.pak")
+        );
+        assert!(disabled
+            .associated_files
+            .iter()
+            .all(|f| f.parent() == Some(nested(fx.disabled_dir()).as_path())));
+        assert!(!nested(fx.mods_dir()).join("Storm_P.pak").exists());
+        let listed = service.find_mod_by_id(&disabled.id).unwrap().unwrap();
+        assert_eq!(listed.metadata.title, "My Storm");
+        assert_eq!(listed.metadata.notes.as_deref(), Some("Lightning recolor"));
+        assert_eq!(listed.thumbnail_path, Some(thumbnail(&disabled.id)));
+        assert!(!thumbnail(&installed.id).exists());
+        assert_eq!(fx.metadata_count(), 1);
+
+        // Enabled back into the original folder, under the original ID
+        let enabled = service.enable_mod(&disabled.id, true).unwrap();
+        assert_eq!(enabled.id, installed.id);
+        assert_eq!(enabled.file_path, installed.file_path);
+        assert_eq!(enabled.associated_files.len(), 3);
+        assert!(thumbnail(&installed.id).exists());
+        assert!(!thumbnail(&disabled.id).exists());
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), 1);
+        assert_eq!(mods[0].metadata.notes.as_deref(), Some("Lightning recolor"));
+        assert_eq!(fx.metadata_count(), 1);
+    }
 
-        for character in characters {
-            if character
-                .keywords()
-                .iter()
-                .any(|keyword| lower_name.contains(keyword))
-            {
-                return Some(character);
-            }
+    #[test]
+    fn test_toggle_destination_stays_inside_the_mod_folders() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut meta = metadata("Sneaky", ModCategory::Unknown("../../x".to_string()));
+        let enabled = service.toggle_destination(&meta, true);
+        assert_eq!(enabled, fx.mods_dir().join("x").join("Sneaky"));
+
+        for escape in [
+            "../../Windows",
+            "/etc",
+            "\\\\server\\share",
+            "C:\\Games",
+            "Skins/../..",
+        ] {
+            meta.original_folder_path = Some(escape.to_string());
+            assert!(service
+                .toggle_destination(&meta, true)
+                .starts_with(fx.mods_dir()));
+            assert_eq!(service.toggle_destination(&meta, false), fx.disabled_dir());
         }
 
-        None
-    }
-
-    fn find_associated_files(&self, pak_file_path: &Path) -> Result<Vec<PathBuf>, String> {
-        let mut files = vec![pak_file_path.to_path_buf()];
+        // Windows-style separators from an older save still nest
+        meta.original_folder_path = Some("Skins\\Storm\\My-Mod".to_string());
+        assert_eq!(
+            service.toggle_destination(&meta, false),
+            fx.disabled_dir().join("Skins").join("Storm").join("My-Mod")
+        );
+        assert_eq!(
+            contained_folder("Skins/./Storm/"),
+            Some(PathBuf::from("Skins/Storm"))
+        );
 
-        let base_name = pak_file_path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .ok_or("Invalid file name")?;
+        // Without a stored folder it goes where organizing would put it,
+        // load priority and add-on prefix included
+        meta.original_folder_path = None;
+        meta.priority = Some(10);
+        meta.parent_mod_id = Some("base".to_string());
+        assert_eq!(
+            service.toggle_destination(&meta, true),
+            fx.mods_dir().join("010_x").join("aa-Sneaky")
+        );
+    }
 
-        let directory = pak_file_path.parent().ok_or("Invalid directory")?;
+    #[test]
+    fn test_listed_mod_is_found_without_a_rescan() {
+        let fx = Fixture::new();
+        let hulk = fx
+            .service()
+            .install_mod_to_folder(&fx.source_mod("Hulk_P", 8, Some(64)), "Skins/Hulk")
+            .unwrap()
+            .mod_info;
+        let service = fx
+            .service()
+            .with_listing(fx.service().get_all_mods().unwrap());
+        fs::remove_file(service.scan_profile_path()).unwrap();
+
+        let mut edited = hulk.metadata.clone();
+        edited.notes = Some("Re-read on its own".to_string());
+        service.save_metadata(&hulk.id, &edited).unwrap();
+        let found = service.find_listed_mod(&hulk.id).unwrap().unwrap();
+        assert_eq!(found.metadata.notes, edited.notes);
+        assert!(service.last_scan_profile().unwrap().is_none());
+
+        // Moved since it was listed: the library is scanned for it
+        let disabled = service.enable_mod(&hulk.id, false).unwrap();
+        let found = service.find_listed_mod(&disabled.id).unwrap().unwrap();
+        assert_eq!(found.file_path, disabled.file_path);
+        assert!(service.find_listed_mod(&hulk.id).unwrap().is_none());
+        assert!(service.last_scan_profile().unwrap().is_some());
+    }
 
-        // Look for .ucas and .utoc files
-        for ext in &[".ucas", ".utoc"] {
-            let companion_file = directory.join(format!("{}{}", base_name, ext));
-            if companion_file.exists() {
-                files.push(companion_file);
-            }
+    #[test]
+    fn test_set_mods_enabled_reports_new_ids() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        fx.installed_mod("Skins/Mod-A", "ModA_P");
+        fx.installed_mod("Skins/Mod-B", "ModB_P");
+        let before = service.get_all_mods().unwrap();
+        let ids: Vec<String> = before.iter().map(|m| m.id.clone()).collect();
+
+        let toggled = service.set_mods_enabled(&ids, false, |_, _| {}).unwrap();
+        assert_eq!(toggled.len(), 2);
+
+        let after = service.get_all_mods().unwrap();
+        for old in &before {
+            let entry = toggled.iter().find(|t| t.previous_id == old.id).unwrap();
+            assert!(!entry.mod_info.enabled);
+            assert_ne!(entry.mod_info.id, old.id);
+            let scanned = after.iter().find(|m| m.id == entry.mod_info.id).unwrap();
+            assert_eq!(scanned.file_path, entry.mod_info.file_path);
         }
+    }
 
-        Ok(files)
+    #[test]
+    fn test_enable_mods_bulk_reports_failures_and_keeps_going() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        fx.installed_mod("Skins/Mod-A", "ModA_P");
+        fx.installed_mod("Skins/Mod-B", "ModB_P");
+        let mut ids: Vec<String> = service
+            .get_all_mods()
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        ids.insert(1, "missing".to_string());
+
+        let mut progress = Vec::new();
+        let report = service
+            .enable_mods_bulk(&ids, false, &DisabledReason::UserAction, |done, total| {
+                progress.push((done, total))
+            })
+            .unwrap();
+        assert_eq!(progress, [(1, 3), (2, 3), (3, 3)]);
+        assert_eq!(report.toggled.len(), 2);
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].mod_id, "missing");
+        assert_eq!(report.failed[0].reason, "Mod not found");
+        assert!(service.get_all_mods().unwrap().iter().all(|m| !m.enabled));
     }
 
-    fn is_mod_file(&self, path: &Path) -> bool {
-        path.extension()
-            .and_then(|ext| ext.to_str())
-            .map(|ext| SUPPORTED_EXTENSIONS.contains(&format!(".{}", ext).as_str()))
-            .unwrap_or(false)
+    #[test]
+    fn test_update_metadata_renames_folder_and_migrates_id() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let source = fx.source_mod("CoolSkin_P", 10, Some(100));
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &source,
+                "Skins/Old-Name",
+                metadata("Old Name", ModCategory::Skins),
+            )
+            .unwrap();
+        let old_thumb = fx
+            .metadata_dir
+            .join(format!("{}_thumbnail.png", installed.id));
+        fs::write(&old_thumb, b"png").unwrap();
+
+        let mut updated_metadata = installed.metadata.clone();
+        updated_metadata.title = "New Name".to_string();
+        let updated = service
+            .update_metadata(&installed.id, updated_metadata)
+            .unwrap();
+
+        let new_folder = fx.mods_dir().join("Skins").join("New-Name");
+        assert_eq!(updated.file_path, new_folder.join("CoolSkin_P.pak"));
+        assert!(new_folder.join("CoolSkin_P.ucas").exists());
+        assert!(!fx.mods_dir().join("Skins").join("Old-Name").exists());
+
+        // Path changed, so the ID did too; metadata and thumbnail follow it
+        assert_ne!(updated.id, installed.id);
+        assert_eq!(updated.metadata.title, "New Name");
+        assert!(service.load_metadata(&installed.id).unwrap().is_none());
+        assert!(!old_thumb.exists());
+        assert!(fx
+            .metadata_dir
+            .join(format!("{}_thumbnail.png", updated.id))
+            .exists());
+        assert_eq!(fx.metadata_count(), 1);
     }
 
-    fn find_mod_by_id(&self, mod_id: &str) -> Result<Option<ModInfo>, String> {
-        let all_mods = self.get_all_mods()?;
-        Ok(all_mods.into_iter().find(|m| m.id == mod_id))
+    #[test]
+    fn test_variants_group_by_name_and_enable_exclusively() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let install = |stem: &str| {
+            service
+                .install_mod_to_folder(&fx.source_mod(stem, 8, Some(64)), "Skins/Storm")
+                .unwrap()
+        };
+        let red = install("Storm_Red_P");
+        let blue = install("Storm_Blue_P");
+        install("Thunderbolt_P");
+
+        let groups = service.detect_variant_groups().unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "Storm");
+        let mut grouped = groups[0].mod_ids.clone();
+        grouped.sort();
+        let mut expected = vec![red.id.clone(), blue.id.clone()];
+        expected.sort();
+        assert_eq!(grouped, expected);
+
+        service
+            .group_mods_as_variants(&grouped, Some("Storm"))
+            .unwrap();
+        assert!(service.detect_variant_groups().unwrap().is_empty());
+
+        // Enabling the disabled variant turns the other one off
+        let red = service.enable_mod(&red.id, false).unwrap();
+        let red = service.enable_variant(&red.id).unwrap();
+        assert!(red.enabled);
+        let mods = service.get_all_mods().unwrap();
+        let blue = mods.iter().find(|m| m.name == blue.name).unwrap();
+        assert!(!blue.enabled);
+        assert_eq!(blue.metadata.variant_group.as_deref(), Some("Storm"));
+        assert!(matches!(
+            blue.disabled_reason,
+            Some(DisabledReason::ConflictResolution { .. })
+        ));
     }
 
-    fn load_metadata(&self, mod_id: &str) -> Result<Option<ModMetadata>, String> {
-        let metadata_path = self.metadata_directory.join(format!("{}.json", mod_id));
+    #[test]
+    fn test_mods_directory_override_replaces_the_game_folder() {
+        let fx = Fixture::new();
+        let custom = fx.source_dir.parent().unwrap().join("CustomMods");
+        assert!(ModService::check_mods_directory(&custom).is_err());
+        fs::create_dir_all(&custom).unwrap();
+        ModService::check_mods_directory(&custom).unwrap();
+        let pak = fx.source_mod("Hulk_P", 8, Some(64));
+        assert!(ModService::check_mods_directory(&pak).is_err());
+
+        let service = fx.service().with_mods_directory(Some(custom.clone()));
+        assert_eq!(service.mods_directory(), custom);
+        let installed = service.install_mod_to_folder(&pak, "Hulk").unwrap();
+        assert!(installed.file_path.starts_with(&custom));
+        assert!(!fx.mods_dir().join("Hulk").exists());
+        assert_eq!(service.get_all_mods().unwrap().len(), 1);
+        // The default service still scans the game's ~mods
+        assert!(fx.service().get_all_mods().unwrap().is_empty());
+    }
 
-        if !metadata_path.exists() {
-            return Ok(None);
-        }
+    #[test]
+    fn test_export_list_as_json_csv_and_markdown() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut hulk = metadata("Hulk, \"Gamma\" Edition", ModCategory::Skins);
+        hulk.character = Some(Character::Hulk);
+        hulk.author = Some("Banner".to_string());
+        hulk.nexus_mod_id = Some(123);
+        service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("Hulk_P", 8, Some(64)),
+                "Skins/Hulk",
+                hulk,
+            )
+            .unwrap();
+        let crosshair = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("Crosshair_P", 8, None),
+                "UI/Crosshair",
+                metadata("Crosshair", ModCategory::UI),
+            )
+            .unwrap();
+        service.enable_mod(&crosshair.id, false).unwrap();
+
+        let json = service.export_list(ModListFormat::Json, true).unwrap();
+        let export: ModListExport = serde_json::from_str(&json).unwrap();
+        assert_eq!(export.mods.len(), 2);
+        let hulk = export
+            .mods
+            .iter()
+            .find(|m| m.nexus_mod_id == Some(123))
+            .unwrap();
+        assert_eq!(hulk.character, Some(Character::Hulk));
+        assert_eq!(hulk.file_size, 8 + 64 + 4);
+        assert_eq!(hulk.original_file_name, "Hulk_P.pak");
+        let enabled_only = service.export_list(ModListFormat::Json, false).unwrap();
+        let export: ModListExport = serde_json::from_str(&enabled_only).unwrap();
+        assert_eq!(export.mods.len(), 1);
+
+        let csv = service.export_list(ModListFormat::Csv, true).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Title,Character,Category"));
+        assert!(csv.contains(
+            "\"Hulk, \"\"Gamma\"\" Edition\",Hulk,Skins,Banner,,123,,true,76,Hulk_P.pak"
+        ));
+        assert!(csv.contains("Crosshair,,UI,,,,,false,8,Crosshair_P.pak"));
+
+        let markdown = service.export_list(ModListFormat::Markdown, true).unwrap();
+        let hulk_at = markdown.find("## Hulk").unwrap();
+        let none_at = markdown.find("## No character").unwrap();
+        assert!(hulk_at < none_at);
+        assert!(markdown.contains(
+            "[Hulk, \"Gamma\" Edition](https://www.nexusmods.com/marvelrivals/mods/123)"
+        ));
+        assert!(markdown.contains("| Crosshair | UI |  |  | No | 8 B |"));
+    }
 
-        let content = fs::read_to_string(&metadata_path)
-            .map_err(|e| format!("Failed to read metadata: {}", e))?;
+    #[test]
+    fn test_import_list_matches_by_nexus_id_then_title_then_file_name() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let install = |stem: &str, folder: &str, metadata: ModMetadata| {
+            service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 8, Some(64)),
+                    folder,
+                    metadata,
+                )
+                .unwrap()
+        };
+        let mut hulk = metadata("Hulk Skin", ModCategory::Skins);
+        hulk.nexus_mod_id = Some(123);
+        let hulk = install("Hulk_P", "Skins/Hulk", hulk);
+        let mut storm = metadata("Storm Skin", ModCategory::Skins);
+        storm.character = Some(Character::Storm);
+        let storm = install("Storm_P", "Skins/Storm", storm);
+        let storm = service.enable_mod(&storm.id, false).unwrap();
+        let crosshair = install("Crosshair_P", "UI", metadata("Crosshair", ModCategory::UI));
+
+        let entry = |title: &str, character, nexus_mod_id, file_name: &str| ModListEntry {
+            title: title.to_string(),
+            character,
+            category: ModCategory::Skins,
+            author: None,
+            version: None,
+            nexus_mod_id,
+            nexus_file_id: None,
+            enabled: true,
+            file_size: 0,
+            original_file_name: file_name.to_string(),
+        };
+        let json = serde_json::to_string(&ModListExport {
+            exported_at: fixed_now(),
+            mods: vec![
+                entry("Renamed on Nexus", None, Some(123), "Other.pak"),
+                entry("Storm Skin", Some(Character::Storm), None, "Other.pak"),
+                entry("My Crosshair", None, None, "crosshair_p.pak"),
+                entry("Gone", None, Some(999), "Gone_P.pak"),
+            ],
+        })
+        .unwrap();
 
-        let metadata: ModMetadata = serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse metadata: {}", e))?;
+        let report = service.import_list(&json, false).unwrap();
+        let matched = |list: &[ModListMatch]| -> Vec<(String, ModListMatchKind)> {
+            list.iter()
+                .map(|m| (m.mod_id.clone(), m.matched_by))
+                .collect()
+        };
+        assert_eq!(
+            matched(&report.installed),
+            [
+                (hulk.id.clone(), ModListMatchKind::NexusModId),
+                (crosshair.id.clone(), ModListMatchKind::FileName),
+            ]
+        );
+        assert_eq!(
+            matched(&report.disabled),
+            [(storm.id.clone(), ModListMatchKind::TitleAndCharacter)]
+        );
+        assert_eq!(report.missing.len(), 1);
+        assert_eq!(
+            report.missing[0].nexus_url.as_deref(),
+            Some("https://www.nexusmods.com/marvelrivals/mods/999")
+        );
+        assert!(report.enabled.is_empty());
+        assert!(!service.find_mod_by_id(&storm.id).unwrap().unwrap().enabled);
+
+        let applied = service.import_list(&json, true).unwrap();
+        assert_eq!(applied.enabled.len(), 1);
+        assert!(applied.errors.is_empty());
+        let storm = service
+            .find_mod_by_id(&applied.enabled[0])
+            .unwrap()
+            .unwrap();
+        assert!(storm.enabled);
+    }
 
-        Ok(Some(metadata))
+    #[test]
+    fn test_integrity_finds_lost_files_and_restores_from_disabled_mods() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let hulk = service
+            .install_mod_to_folder(&fx.source_mod("Hulk_P", 8, Some(64)), "Skins/Hulk")
+            .unwrap();
+        let storm = service
+            .install_mod_to_folder(&fx.source_mod("Storm_P", 8, Some(64)), "Skins/Storm")
+            .unwrap();
+        let listed = service.get_all_mods().unwrap();
+        assert!(!service.verify_integrity(&listed).has_problems());
+
+        // A companion gone while the pak stays
+        let storm_ucas = storm.file_path.with_extension("ucas");
+        fs::remove_file(&storm_ucas).unwrap();
+        let report = service.verify_integrity(&listed);
+        assert!(!report.mods_directory_missing);
+        assert_eq!(report.issues.len(), 1);
+        assert!(report.issues[0].pak_present);
+        assert_eq!(report.issues[0].missing_files, [storm_ucas]);
+
+        // A patch wipes ~mods; Hulk has a copy in disabled-mods
+        let backup = fx.disabled_dir().join("Skins").join("Hulk");
+        fx.write_mod(&backup, "Hulk_P", 8, Some(64));
+        fs::remove_dir_all(fx.mods_dir()).unwrap();
+        let report = service.verify_integrity(&listed);
+        assert!(report.mods_directory_missing);
+        assert_eq!(report.issues.len(), 2);
+        assert!(report.issues.iter().all(|issue| !issue.pak_present));
+        assert_eq!(report.recoverable, [hulk.id.clone()]);
+
+        let restored = service.restore_mods_directory(&listed).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].id, hulk.id);
+        assert!(restored[0].enabled);
+        assert!(hulk.file_path.exists());
+        assert!(!backup.join("Hulk_P.pak").exists());
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), 1);
     }
 
-    fn save_metadata(&self, mod_id: &str, metadata: &ModMetadata) -> Result<(), String> {
-        self.ensure_directory_exists(&self.metadata_directory)?;
+    #[test]
+    fn test_notes_persist_and_history_follows_the_mod() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let installed = service
+            .install_mod_to_folder(&fx.source_mod("Hulk_P", 8, Some(64)), "Skins/Hulk")
+            .unwrap();
+
+        let mut edited = installed.metadata.clone();
+        edited.title = "Gamma Hulk".to_string();
+        edited.notes = Some("  Clips through cape on Victory pose ".to_string());
+        // A stale copy from the frontend can't rewrite the history
+        edited.history.clear();
+        let updated = service.update_metadata(&installed.id, edited).unwrap();
+        let disabled = service.enable_mod(&updated.id, false).unwrap();
+        let enabled = service.enable_mod(&disabled.id, true).unwrap();
+
+        let metadata = service.load_metadata(&enabled.id).unwrap().unwrap();
+        assert_eq!(
+            metadata.notes.as_deref(),
+            Some("Clips through cape on Victory pose")
+        );
+        let actions: Vec<ModHistoryAction> = metadata.history.iter().map(|e| e.action).collect();
+        assert_eq!(
+            actions,
+            [
+                ModHistoryAction::Installed,
+                ModHistoryAction::Renamed,
+                ModHistoryAction::Disabled,
+                ModHistoryAction::Enabled,
+            ]
+        );
+        assert_eq!(metadata.history[0].detail.as_deref(), Some("Hulk_P.pak"));
+        let renamed = metadata.history[1].detail.clone().unwrap_or_default();
+        assert!(renamed.ends_with("→ Gamma Hulk"), "{renamed}");
+
+        // Metadata saved before notes and history still loads
+        let mut legacy = serde_json::to_value(&installed.metadata).unwrap();
+        legacy
+            .as_object_mut()
+            .unwrap()
+            .retain(|field, _| field != "notes" && field != "history");
+        let legacy: ModMetadata = serde_json::from_value(legacy).unwrap();
+        assert!(legacy.notes.is_none() && legacy.history.is_empty());
+
+        let mut capped = metadata;
+        for _ in 0..MAX_MOD_HISTORY {
+            capped.record_history(fixed_now(), ModHistoryAction::Updated, None);
+        }
+        assert_eq!(capped.history.len(), MAX_MOD_HISTORY);
+        assert_eq!(capped.history[0].action, ModHistoryAction::Updated);
+    }
 
-        let metadata_path = self.metadata_directory.join(format!("{}.json", mod_id));
-        let json = serde_json::to_string_pretty(metadata)
-            .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+    #[test]
+    fn test_priority_prefixes_the_top_folder_and_follows_the_new_id() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let install = |stem: &str, title: &str| {
+            service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 8, Some(64)),
+                    &format!("Skins/{title}"),
+                    metadata(title, ModCategory::Skins),
+                )
+                .unwrap()
+        };
+        let hulk = install("Hulk_P", "Hulk");
+        let venom = install("Venom_P", "Venom");
+
+        let hulk = service.set_mod_priority(&hulk.id, Some(50)).unwrap();
+        let folder = fx.mods_dir().join("050_Skins").join("Hulk");
+        assert_eq!(hulk.file_path, folder.join("Hulk_P.pak"));
+        assert!(folder.join("Hulk_P.ucas").exists());
+        assert!(folder.join("Hulk_P.utoc").exists());
+        assert!(!fx.mods_dir().join("Skins").join("Hulk").exists());
+        assert_eq!(hulk.metadata.priority, Some(50));
+        assert_eq!(hulk.metadata.title, "Hulk");
+        assert_eq!(fx.metadata_count(), 2);
+        assert!(service.set_mod_priority(&hulk.id, Some(1000)).is_err());
+
+        // One pass, first loads first: the game mounts by lowercased full
+        // path, so that order has to hold across folders too
+        let ids = vec![venom.id.clone(), hulk.id.clone()];
+        let reordered = service.reorder_mods(&ids).unwrap();
+        let load_order = |mods: &[ModInfo]| {
+            let mut paths: Vec<(String, String)> = mods
+                .iter()
+                .flat_map(|m| {
+                    m.associated_files.iter().map(|file| {
+                        let relative = file.strip_prefix(fx.mods_dir()).unwrap();
+                        (relative.to_string_lossy().to_lowercase(), m.id.clone())
+                    })
+                })
+                .collect();
+            paths.sort();
+            paths.dedup_by(|a, b| a.1 == b.1);
+            paths.into_iter().map(|(_, id)| id).collect::<Vec<_>>()
+        };
+        let reordered_ids: Vec<String> = reordered.iter().map(|m| m.id.clone()).collect();
+        assert_eq!(load_order(&reordered), reordered_ids);
+        assert_eq!(
+            reordered[0].file_path,
+            fx.mods_dir()
+                .join("010_Skins")
+                .join("Venom")
+                .join("Venom_P.pak")
+        );
+        assert_eq!(
+            reordered[1].file_path,
+            fx.mods_dir()
+                .join("020_Skins")
+                .join("Hulk")
+                .join("Hulk_P.pak")
+        );
 
-        fs::write(&metadata_path, json).map_err(|e| format!("Failed to write metadata: {}", e))?;
+        // The prefix survives a round trip through disabled-mods
+        let disabled = service.enable_mod(&reordered[1].id, false).unwrap();
+        let hulk = service.enable_mod(&disabled.id, true).unwrap();
+        assert_eq!(hulk.file_path, reordered[1].file_path);
+        assert_eq!(hulk.metadata.priority, Some(20));
 
-        Ok(())
-    }
+        let hulk = service.set_mod_priority(&hulk.id, None).unwrap();
+        assert_eq!(
+            hulk.file_path,
+            fx.mods_dir().join("Skins").join("Hulk").join("Hulk_P.pak")
+        );
+        assert_eq!(hulk.metadata.priority, None);
 
-    fn delete_metadata(&self, mod_id: &str) -> Result<(), String> {
-        let metadata_path = self.metadata_directory.join(format!("{}.json", mod_id));
-        if metadata_path.exists() {
-            fs::remove_file(&metadata_path)
-                .map_err(|e| format!("Failed to delete metadata: {}", e))?;
-        }
-        Ok(())
+        // Prefixed by hand: the name leaves the prefix out, the priority doesn't
+        fx.installed_mod("030_Skins/Thor", "030_Thor_P");
+        let thor = service
+            .get_all_mods()
+            .unwrap()
+            .into_iter()
+            .find(|m| m.metadata.priority == Some(30))
+            .unwrap();
+        assert_eq!(thor.name, "Thor");
+
+        // A loose pak has no folder to prefix, so its own name carries it
+        let loki_pak = fx.write_mod(&fx.mods_dir(), "Loki_P", 8, Some(64));
+        let loki = service
+            .get_all_mods()
+            .unwrap()
+            .into_iter()
+            .find(|m| m.file_path == loki_pak)
+            .unwrap();
+        let loki = service.set_mod_priority(&loki.id, Some(5)).unwrap();
+        assert_eq!(loki.file_path, fx.mods_dir().join("005_Loki_P.pak"));
+        assert_eq!(loki.metadata.priority, Some(5));
     }
 
-    /// Update all addon mods that reference an old parent ID to point to the new ID
-    fn migrate_addon_parent_ids(&self, old_id: &str, new_id: &str) -> Result<(), String> {
-        log::info!(
-            "      🔄 Scanning for addons with parent_mod_id = {}",
-            old_id
+    #[test]
+    fn test_split_from_shared_folder_keeps_thumbnail_and_memberships() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut alpha_metadata = metadata("Alpha", ModCategory::Skins);
+        alpha_metadata.profile_ids = Some(vec!["raid-night".to_string()]);
+        let alpha = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("Alpha_P", 10, Some(100)),
+                "Skins/Pack",
+                alpha_metadata,
+            )
+            .unwrap();
+        service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("Beta_P", 12, Some(100)),
+                "Skins/Pack",
+                metadata("Beta", ModCategory::Skins),
+            )
+            .unwrap();
+        let old_thumb = fx.metadata_dir.join(format!("{}_thumbnail.png", alpha.id));
+        fs::write(&old_thumb, b"png").unwrap();
+        let collections = service.collections();
+        let theme = collections.create("Theme", None).unwrap();
+        collections.add(&theme.id, &[alpha.id.clone()]).unwrap();
+        let hash = service.content_hash(&alpha.file_path).unwrap();
+
+        let mut edited = alpha.metadata.clone();
+        edited.title = "Alpha Prime".to_string();
+        let updated = service.update_metadata(&alpha.id, edited).unwrap();
+
+        // Only Alpha's files left the shared folder
+        let pack = fx.mods_dir().join("Skins").join("Pack");
+        let new_pak = fx
+            .mods_dir()
+            .join("Skins")
+            .join("Alpha-Prime")
+            .join("Alpha_P.pak");
+        assert_eq!(updated.file_path, new_pak);
+        assert!(pack.join("Beta_P.pak").exists());
+        assert!(!pack.join("Alpha_P.ucas").exists());
+
+        // Everything keyed to the old ID follows the new one
+        assert_ne!(updated.id, alpha.id);
+        let new_thumb = fx
+            .metadata_dir
+            .join(format!("{}_thumbnail.png", updated.id));
+        assert_eq!(updated.thumbnail_path, Some(new_thumb));
+        assert!(!old_thumb.exists());
+        assert_eq!(
+            updated.metadata.profile_ids,
+            Some(vec!["raid-night".to_string()])
         );
-        let mut migrated = 0;
-        let mut scanned = 0;
+        assert_eq!(
+            collections.get(&theme.id).unwrap().mod_ids,
+            [updated.id.clone()]
+        );
+        assert_eq!(
+            hash_cache::cached(&service.hash_cache_path(), &new_pak),
+            Some(hash)
+        );
+    }
 
-        // Scan all metadata files in the metadata directory
-        if let Ok(entries) = fs::read_dir(&self.metadata_directory) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().and_then(|e| e.to_str()) != Some("json") {
-                    continue;
-                }
-                scanned += 1;
+    #[test]
+    fn test_install_reports_copied_and_missing_files() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let complete = service
+            .install_to_folder(&fx.source_mod("Hulk_P", 8, Some(64)), "Skins/Hulk", None)
+            .unwrap();
+        let sizes: Vec<u64> = complete.copied_files.iter().map(|f| f.size).collect();
+        assert_eq!(sizes, vec![8, 64, 4]);
+        assert!(complete.missing_companions.is_empty());
+        assert!(complete.warnings.is_empty());
+
+        // An IoStore pak that lost its utoc, into a folder Windows can't name
+        let pak = fx.source_mod("Venom_P", 8, Some(64));
+        fs::remove_file(pak.with_extension("utoc")).unwrap();
+        let broken = service
+            .install_to_folder(&pak, "Skins/Venom?/Classic.", None)
+            .unwrap();
+        assert_eq!(broken.missing_companions, vec!["Venom_P.utoc".to_string()]);
+        assert_eq!(broken.copied_files.len(), 2);
+        let installed_at = &broken.mod_info.file_path;
+        assert!(installed_at.ends_with("Skins/Venom/Classic/Venom_P.pak"));
+        assert_eq!(broken.warnings.len(), 3);
+
+        // Installing the same pak again replaces it, and says so
+        let again = service
+            .install_to_folder(&pak, "Skins/Venom/Classic", None)
+            .unwrap();
+        assert_eq!(again.warnings.len(), 3);
+        assert!(again.warnings[1].starts_with("Replaced the existing Venom_P.pak"));
+    }
 
-                if let Ok(content) = fs::read_to_string(&path) {
-                    // Use a simple string check first for speed, then parse if match found
-                    if content.contains(old_id) {
-                        if let Ok(mut metadata) = serde_json::from_str::<ModMetadata>(&content) {
-                            log::info!(
-                                "      📄 Checking {:?} - parentModId: {:?}",
-                                path.file_name(),
-                                metadata.parent_mod_id
-                            );
-                            if metadata.parent_mod_id.as_deref() == Some(old_id) {
-                                metadata.parent_mod_id = Some(new_id.to_string());
-                                if let Ok(updated) = serde_json::to_string_pretty(&metadata) {
-                                    let _ = fs::write(&path, updated);
-                                    migrated += 1;
-                                    log::info!("      ✅ Updated addon: {:?}", path.file_name());
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+    #[test]
+    fn test_sig_and_differently_cased_companions_travel_with_the_pak() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        write_file(&fx.source_dir.join("Hulk_P.pak"), 8);
+        write_file(&fx.source_dir.join("hulk_p.ucas"), 64);
+        write_file(&fx.source_dir.join("hulk_p.UTOC"), 4);
+        write_file(&fx.source_dir.join("Hulk_P.sig"), 2);
+        let installed = service
+            .install_to_folder(&fx.source_dir.join("Hulk_P.pak"), "Skins/Hulk", None)
+            .unwrap();
+        assert!(installed.missing_companions.is_empty());
+        let folder = fx.mods_dir().join("Skins").join("Hulk");
+        for name in ["Hulk_P.pak", "Hulk_P.ucas", "Hulk_P.utoc", "Hulk_P.sig"] {
+            assert!(folder.join(name).exists(), "{name} not installed");
         }
 
-        log::info!(
-            "      📊 Scanned {} metadata files, migrated {} addon(s)",
-            scanned,
-            migrated
+        // Dropped into ~mods by hand, with the companions cased differently
+        let pak = fx.installed_mod("Skins/Venom", "Venom_P");
+        let dir = pak.parent().unwrap().to_path_buf();
+        fs::rename(dir.join("Venom_P.ucas"), dir.join("venom_p.ucas")).unwrap();
+        write_file(&dir.join("VENOM_P.sig"), 2);
+        let venom = service
+            .get_all_mods()
+            .unwrap()
+            .into_iter()
+            .find(|m| m.file_path == pak)
+            .unwrap();
+        assert_eq!(venom.associated_files.len(), 4);
+
+        let disabled = service.enable_mod(&venom.id, false).unwrap();
+        assert!(venom.associated_files.iter().all(|f| !f.exists()));
+        assert_eq!(disabled.associated_files.len(), 4);
+        let enabled = service.enable_mod(&disabled.id, true).unwrap();
+        assert_eq!(enabled.associated_files.len(), 4);
+        assert!(enabled.associated_files.iter().all(|f| f.exists()));
+    }
+
+    #[test]
+    fn test_install_progress_and_cancel() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let source = fx.source_mod("Hulk_P", 8, Some(64));
+        let events = Mutex::new(Vec::new());
+        let record = |p: ModInstallProgress| events.lock().unwrap().push(p);
+        let installed = service
+            .install_to_folder_with(
+                &source,
+                "Skins/Hulk",
+                None,
+                &InstallCopy {
+                    cancel: CancellationToken::new(),
+                    on_progress: &record,
+                },
+            )
+            .unwrap();
+        let events = events.into_inner().unwrap();
+        let done: Vec<(usize, usize)> = events.iter().map(|p| (p.current, p.total)).collect();
+        assert_eq!(done, vec![(8, 76), (72, 76), (76, 76), (76, 76)]);
+        assert_eq!(events.last().unwrap().status, "done");
+
+        // Cancelled partway through the ucas: the pak it already copied isn't
+        // kept, and the installed files it would have replaced are untouched
+        let folder = installed.mod_info.file_path.parent().unwrap().to_path_buf();
+        fs::write(fx.source_dir.join("Hulk_P.ucas"), vec![1u8; 128]).unwrap();
+        let cancel = CancellationToken::new();
+        let cancel_in_ucas = |p: ModInstallProgress| {
+            if p.current_file == "Hulk_P.ucas" {
+                cancel.cancel();
+            }
+        };
+        let err = service
+            .install_to_folder_with(
+                &source,
+                "Skins/Hulk",
+                None,
+                &InstallCopy {
+                    cancel: cancel.clone(),
+                    on_progress: &cancel_in_ucas,
+                },
+            )
+            .unwrap_err();
+        assert_eq!(err, "Install cancelled");
+        let mut left: Vec<(String, u64)> = fs::read_dir(&folder)
+            .unwrap()
+            .map(|e| e.unwrap())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext != "json"))
+            .map(|e| {
+                let size = e.metadata().unwrap().len();
+                (e.file_name().to_string_lossy().to_string(), size)
+            })
+            .collect();
+        left.sort();
+        assert_eq!(
+            left,
+            vec![
+                ("Hulk_P.pak".to_string(), 8),
+                ("Hulk_P.ucas".to_string(), 64),
+                ("Hulk_P.utoc".to_string(), 4),
+            ]
         );
-        Ok(())
     }
 
-    /// Recursively copy a directory and all its contents
-    fn copy_directory_recursive(&self, source: &Path, destination: &Path) -> Result<(), String> {
-        log::info!("Copying directory from {:?} to {:?}", source, destination);
+    #[test]
+    fn test_cancelled_group_install_takes_back_the_paks_already_copied() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let pak_files: Vec<String> = ["Alpha_P", "Beta_P"]
+            .iter()
+            .map(|stem| {
+                fx.source_mod(stem, 8, Some(64))
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        let cancel = CancellationToken::new();
+        let cancel_in_beta = |p: ModInstallProgress| {
+            if p.current_file.starts_with("Beta_P") {
+                cancel.cancel();
+            }
+        };
+        let err = service
+            .install_mod_group_with(
+                &pak_files,
+                "Pack",
+                ModCategory::Skins,
+                &InstallCopy {
+                    cancel: cancel.clone(),
+                    on_progress: &cancel_in_beta,
+                },
+            )
+            .unwrap_err();
+        assert_eq!(err, "Install cancelled");
+        assert!(!fx.mods_dir().join("Skins").join("Pack").exists());
+        assert_eq!(fx.metadata_count(), 0);
+        assert!(service.get_all_mods().unwrap().is_empty());
+
+        let installed = service
+            .install_mod_group(&pak_files, "Pack", ModCategory::Skins)
+            .unwrap();
+        assert_eq!(installed.mod_info.metadata.title, "Pack");
+        assert_eq!(service.get_all_mods().unwrap().len(), 2);
+    }
 
-        // Create the destination directory
-        fs::create_dir_all(destination)
-            .map_err(|e| format!("Failed to create destination directory: {}", e))?;
+    #[test]
+    fn test_failed_move_into_place_restores_the_files_it_replaced() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let folder = fx.mods_dir().join("Skins").join("Hulk");
+        fx.write_mod(&folder, "Hulk_P", 3, Some(30));
+        // Nothing can be set aside under this name, so the .ucas can't be
+        // moved into place after the pak already was
+        write_file(&folder.join("Hulk_P.ucas.replaced").join("keep"), 1);
+
+        let source = fx.source_mod("Hulk_P", 8, Some(64));
+        let err = service
+            .install_to_folder_with(&source, "Skins/Hulk", None, &InstallCopy::default())
+            .unwrap_err();
+        assert!(err.starts_with("Failed to move"), "{err}");
+        let size = |name: &str| fs::metadata(folder.join(name)).unwrap().len();
+        assert_eq!((size("Hulk_P.pak"), size("Hulk_P.ucas")), (3, 30));
+        let stray: Vec<_> = fs::read_dir(&folder)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| name.ends_with(".installing") || name.ends_with("pak.replaced"))
+            .collect();
+        assert!(stray.is_empty(), "{stray:?}");
+    }
 
-        // Walk through the source directory
-        for entry in WalkDir::new(source).into_iter().filter_map(|e| e.ok()) {
-            let path = entry.path();
+    #[test]
+    fn test_install_date_survives_rename_and_toggle() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let source = fx.source_mod("CoolSkin_P", 10, Some(100));
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &source,
+                "Skins/Old-Name",
+                metadata("Old Name", ModCategory::Skins),
+            )
+            .unwrap();
+        let original = date("2025-01-01T00:00:00Z");
+        assert_eq!(installed.metadata.install_date, original);
+
+        // The edit form sends back whatever dates it has; they must not win
+        let mut edited = installed.metadata.clone();
+        edited.title = "New Name".to_string();
+        edited.install_date = date("2030-01-01T00:00:00Z");
+        edited.created_at = date("2030-01-01T00:00:00Z");
+        let renamed = service.update_metadata(&installed.id, edited).unwrap();
+        assert_ne!(renamed.id, installed.id);
+        assert_eq!(renamed.metadata.install_date, original);
+        assert_eq!(renamed.metadata.created_at, original);
+        assert_eq!(renamed.metadata.updated_at, fixed_now());
+
+        service.enable_mod(&renamed.id, false).unwrap();
+        let disabled = service.get_all_mods().unwrap().remove(0);
+        assert!(!disabled.enabled);
+        assert_eq!(disabled.metadata.install_date, original);
+
+        service.enable_mod(&disabled.id, true).unwrap();
+        let enabled = service.get_all_mods().unwrap().remove(0);
+        assert!(enabled.enabled);
+        assert_eq!(enabled.id, renamed.id);
+        assert_eq!(enabled.metadata.install_date, original);
+        assert_eq!(enabled.install_date, original);
+    }
 
-            // Get relative path from source
-            let relative_path = path
-                .strip_prefix(source)
-                .map_err(|e| format!("Failed to compute relative path: {}", e))?;
+    #[test]
+    fn test_unicode_title_gives_a_stable_folder() {
+        // NFD and NFC spellings of the same title land in the same folder
+        let composed = "蜘蛛侠 – 黑猫联动❤️ Pok\u{e9}mon";
+        let decomposed = "蜘蛛侠 – 黑猫联动❤️ Poke\u{301}mon";
+        assert_eq!(
+            sanitize_folder_name(decomposed).as_bytes(),
+            "蜘蛛侠-–-黑猫联动❤️-Pok\u{e9}mon".as_bytes()
+        );
+        // Emoji at the cut are kept or dropped whole, never split
+        let family = "👨\u{200d}👩\u{200d}👧";
+        let long = format!("{}{}", "a".repeat(99), family);
+        assert_eq!(sanitize_folder_name(&format!("{long}b")), long);
+        let families = sanitize_folder_name(&family.repeat(40));
+        assert!(families.encode_utf16().count() <= MAX_FOLDER_NAME_UTF16);
+
+        let fx = Fixture::new();
+        let service = fx.service();
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("Spider_P", 10, Some(100)),
+                "Skins/Spider",
+                metadata("Spider", ModCategory::Skins),
+            )
+            .unwrap();
+        let mut edited = installed.metadata.clone();
+        edited.title = decomposed.to_string();
+        let renamed = service.update_metadata(&installed.id, edited).unwrap();
+        let folder = renamed.file_path.parent().unwrap().to_path_buf();
+        assert_eq!(folder, service.expected_folder_for(&renamed.metadata));
+
+        // Saving the same title again, in either form, moves nothing
+        let mut again = renamed.metadata.clone();
+        again.title = composed.to_string();
+        assert_eq!(service.expected_folder_for(&again), folder);
+        let saved = service.update_metadata(&renamed.id, again).unwrap();
+        assert_eq!(saved.id, renamed.id);
+        assert_eq!(saved.file_path, renamed.file_path);
+        assert!(service.get_layout_drift().unwrap().is_empty());
+    }
 
-            let dest_path = destination.join(relative_path);
+    #[test]
+    fn test_favorite_toggle_is_written_behind() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let source = fx.source_mod("FavSkin_P", 10, Some(100));
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &source,
+                "Skins/Fav-Skin",
+                metadata("Fav Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        let metadata_file = fx.metadata_dir.join(format!("{}.json", installed.id));
+        let journal = fx.metadata_dir.join(write_behind::JOURNAL_FILE);
+        let on_disk = || -> ModMetadata {
+            serde_json::from_str(&fs::read_to_string(&metadata_file).unwrap()).unwrap()
+        };
 
-            if path.is_dir() {
-                // Create directory in destination
-                fs::create_dir_all(&dest_path)
-                    .map_err(|e| format!("Failed to create directory {:?}: {}", dest_path, e))?;
-            } else {
-                // Copy file to destination
-                if let Some(parent) = dest_path.parent() {
-                    fs::create_dir_all(parent)
-                        .map_err(|e| format!("Failed to create parent directory: {}", e))?;
-                }
+        let mut favorite = installed.metadata.clone();
+        favorite.is_favorite = true;
+        let updated = service.update_metadata(&installed.id, favorite).unwrap();
+        assert_eq!(updated.id, installed.id);
+        assert!(updated.is_favorite);
+
+        // Readers see it at once; the file waits for the debounce
+        assert!(service.get_all_mods().unwrap()[0].is_favorite);
+        assert!(!on_disk().is_favorite);
+        assert!(journal.exists());
+
+        assert_eq!(service.flush_pending_metadata().unwrap(), 1);
+        assert!(on_disk().is_favorite);
+        assert!(!journal.exists());
+
+        // Exit before the flush: the journal alone restores the change, and a
+        // line torn mid-append is skipped
+        let mut unfavorite = on_disk();
+        unfavorite.is_favorite = false;
+        service.update_metadata(&installed.id, unfavorite).unwrap();
+        let entries = fs::read_to_string(&journal).unwrap();
+        write_behind::discard(&fx.metadata_dir, &installed.id).unwrap();
+        fs::write(&journal, entries + "{\"modId\":\"tor").unwrap();
+        assert!(on_disk().is_favorite);
+        assert_eq!(write_behind::replay_journal(&fx.metadata_dir).unwrap(), 1);
+        assert!(!on_disk().is_favorite);
+        assert!(!journal.exists());
+    }
 
-                fs::copy(path, &dest_path)
-                    .map_err(|e| format!("Failed to copy file {:?}: {}", path, e))?;
+    #[test]
+    fn test_metadata_is_validated_on_write() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut meta = metadata("  Cool Skin ", ModCategory::Skins);
+        meta.tags = vec!["Lore".to_string(), "lore ".to_string()];
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("CoolSkin_P", 10, None),
+                "Skins/Cool-Skin",
+                meta,
+            )
+            .unwrap();
+        assert_eq!(installed.metadata.title, "Cool Skin");
+        assert_eq!(installed.metadata.tags, ["lore"]);
+
+        // An empty title is refused and nothing is written
+        let mut blank = installed.metadata.clone();
+        blank.title = "   ".to_string();
+        match service.update_metadata(&installed.id, blank) {
+            Err(MetadataUpdateError::Invalid { issues }) => {
+                assert_eq!(issues[0].field, "title")
             }
+            other => panic!("expected invalid, got {:?}", other.map(|m| m.id)),
         }
+        let stored = service.load_metadata(&installed.id).unwrap().unwrap();
+        assert_eq!(stored.title, "Cool Skin");
+    }
 
-        log::info!("Successfully copied directory");
-        Ok(())
+    #[test]
+    fn test_raw_metadata_editing() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("CoolSkin_P", 10, None),
+                "Skins/Cool-Skin",
+                metadata("Cool Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        let raw = service.metadata_raw(&installed.id).unwrap();
+
+        // Nothing is written until the JSON parses and validates
+        let broken = raw.replacen("\"Cool Skin\"", "\"Cool Skin", 1);
+        assert!(matches!(
+            service.set_metadata_raw(&installed.id, &broken),
+            Err(MetadataUpdateError::Syntax { line: 2, .. })
+        ));
+        let blank = raw.replacen("\"Cool Skin\"", "\" \"", 1);
+        assert!(matches!(
+            service.set_metadata_raw(&installed.id, &blank),
+            Err(MetadataUpdateError::Invalid { .. })
+        ));
+        assert_eq!(service.metadata_raw(&installed.id).unwrap(), raw);
+
+        let edited = raw.replacen("\"author\": null", "\"author\": \"Someone\"", 1);
+        let saved = service.set_metadata_raw(&installed.id, &edited).unwrap();
+        assert_eq!(saved.metadata.author.as_deref(), Some("Someone"));
+        assert!(service.scan_warnings().unwrap().is_empty());
+
+        // A file broken outside the app is kept and reported, not replaced
+        // with defaults
+        let path = fx.metadata_dir.join(format!("{}.json", saved.id));
+        fs::write(&path, "{ \"title\": ").unwrap();
+        assert_eq!(service.get_all_mods().unwrap().len(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{ \"title\": ");
+        let warnings = service.scan_warnings().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, ScanWarningKind::InvalidMetadata);
+        assert_eq!(warnings[0].paths, [path]);
     }
 
-    /// Delete a directory with retry logic to handle file locks
-    /// This helps prevent folder duplication when files are temporarily locked
-    fn delete_directory_with_retry(&self, path: &Path, max_retries: u32) -> Result<(), String> {
-        use std::thread;
-        use std::time::Duration;
+    #[test]
+    fn test_concurrent_metadata_edits() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("CoolSkin_P", 10, Some(100)),
+                "Skins/Cool-Skin",
+                metadata("Cool Skin", ModCategory::Skins),
+            )
+            .unwrap();
+
+        // Two windows load the same mod; the first save wins...
+        let mut first = installed.metadata.clone();
+        first.costume = Some("symbiote".to_string());
+        let mut second = installed.metadata.clone();
+        second.author = Some("Someone".to_string());
+        let saved = service.update_metadata(&installed.id, first).unwrap();
+
+        // ...and the second is rejected with what is stored now
+        let current = match service.update_metadata(&saved.id, second) {
+            Err(MetadataUpdateError::MetadataConflict { current }) => current,
+            other => panic!("expected a conflict, got {:?}", other.map(|m| m.id)),
+        };
+        assert_eq!(current.costume.as_deref(), Some("symbiote"));
+        assert_eq!(current.author, None);
+
+        // Retrying on top of the stored copy goes through
+        let mut retry = *current;
+        retry.author = Some("Someone".to_string());
+        let saved = service.update_metadata(&saved.id, retry).unwrap();
+        assert_eq!(saved.metadata.costume.as_deref(), Some("symbiote"));
+        assert_eq!(saved.metadata.author.as_deref(), Some("Someone"));
+
+        // Patches apply to whatever is stored, so they never conflict
+        let patch = |json: serde_json::Value| json.as_object().unwrap().clone();
+        let patched = service
+            .patch_metadata(&saved.id, &patch(serde_json::json!({ "isFavorite": true })))
+            .unwrap();
+        assert!(patched.is_favorite);
+        assert_eq!(patched.metadata.author.as_deref(), Some("Someone"));
+        let patched = service
+            .patch_metadata(
+                &saved.id,
+                &patch(serde_json::json!({ "profileIds": ["a"] })),
+            )
+            .unwrap();
+        assert!(patched.is_favorite);
+        assert_eq!(patched.metadata.profile_ids, Some(vec!["a".to_string()]));
+        assert!(service
+            .patch_metadata(&saved.id, &patch(serde_json::json!({ "colour": "red" })))
+            .is_err());
+    }
 
-        let mut last_error = None;
+    #[test]
+    fn test_preview_toggle() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let on = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("OnSkin_P", 10, Some(100)),
+                "Skins/On-Skin",
+                metadata("On Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        let off = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("OffSkin_P", 20, Some(200)),
+                "Skins/Off-Skin",
+                metadata("Off Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        let off = service.enable_mod(&off.id, false).unwrap();
+
+        let preview = service
+            .preview_toggle(&[off.id.clone()], &[on.id.clone()])
+            .unwrap();
+        assert_eq!(
+            preview,
+            TogglePreview {
+                enable_count: 1,
+                disable_count: 1,
+                // .pak + .ucas + 4-byte .utoc
+                bytes_to_enable: 224,
+                bytes_to_disable: 114,
+                // Both live under the same temp dir
+                cross_volume: false,
+                peak_temporary_bytes: 0,
+            }
+        );
 
-        for attempt in 0..max_retries {
-            match fs::remove_dir_all(path) {
-                Ok(_) => {
-                    log::info!("Successfully deleted directory: {:?}", path);
-                    return Ok(());
-                }
-                Err(e) => {
-                    last_error = Some(e);
-                    if attempt < max_retries - 1 {
-                        // Exponential backoff: 100ms, 200ms, 400ms
-                        let delay_ms = 100 * (2_u64.pow(attempt));
-                        log::warn!(
-                            "Failed to delete directory (attempt {}/{}): {}. Retrying in {}ms...",
-                            attempt + 1,
-                            max_retries,
-                            last_error.as_ref().unwrap(),
-                            delay_ms
-                        );
-                        thread::sleep(Duration::from_millis(delay_ms));
-                    }
-                }
+        // Mods already in the requested state, or unknown, move nothing
+        let noop = service
+            .preview_toggle(&[on.id.clone(), "missing".to_string()], &[off.id])
+            .unwrap();
+        assert_eq!(noop, TogglePreview::default());
+        // Previewing never moves anything
+        assert!(fx.mods_dir().join("Skins/On-Skin/OnSkin_P.pak").exists());
+    }
+
+    #[test]
+    fn test_read_only_mods() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let set_readonly = |files: &[PathBuf]| {
+            for file in files {
+                let mut permissions = fs::metadata(file).unwrap().permissions();
+                permissions.set_readonly(true);
+                fs::set_permissions(file, permissions).unwrap();
             }
-        }
+        };
+        let install = |stem: &str, title: &str| {
+            service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 10, Some(100)),
+                    &format!("Skins/{}", sanitize_folder_name(title)),
+                    metadata(title, ModCategory::Skins),
+                )
+                .unwrap()
+        };
 
-        // All retries failed
-        Err(format!(
-            "Failed to delete directory {:?} after {} attempts: {}",
-            path,
-            max_retries,
-            last_error.unwrap()
-        ))
+        // A read-only source installs as a writable copy
+        let source = fx.source_mod("BackupSkin_P", 10, Some(100));
+        set_readonly(&[source.clone()]);
+        let restored = service
+            .install_mod_to_folder_with_metadata(
+                &source,
+                "Skins/Backup-Skin",
+                metadata("Backup Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        assert!(!is_readonly(&restored.file_path));
+        assert!(service.scan_warnings().unwrap().is_empty());
+
+        // Read-only files are reported, then deleted anyway
+        let doomed = install("DoomedSkin_P", "Doomed Skin");
+        set_readonly(&doomed.associated_files);
+        let warnings = service.scan_warnings().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].mod_id, doomed.id);
+        assert_eq!(warnings[0].kind, ScanWarningKind::ReadOnly);
+        assert_eq!(warnings[0].paths.len(), 3);
+        service.delete_mod(&doomed.id, false, true).unwrap();
+        assert!(!doomed.file_path.exists());
+
+        // Toggling and organizing still work
+        let toggled = install("ToggledSkin_P", "Toggled Skin");
+        set_readonly(&toggled.associated_files);
+        let disabled = service.enable_mod(&toggled.id, false).unwrap();
+        let enabled = service.enable_mod(&disabled.id, true).unwrap();
+        assert!(enabled.file_path.starts_with(fx.mods_dir()));
+
+        let loose = fx.installed_mod("", "LooseSkin_P");
+        set_readonly(&[loose.clone(), loose.with_extension("ucas")]);
+        assert_eq!(service.organize_loose_mods(None).unwrap().moved_mods, 1);
+        assert!(!loose.exists());
     }
 
-    fn ensure_directory_exists(&self, path: &Path) -> Result<(), String> {
-        if !path.exists() {
-            fs::create_dir_all(path).map_err(|e| format!("Failed to create directory: {}", e))?;
-        }
-        Ok(())
+    #[test]
+    fn test_find_low_confidence_assignments() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        fx.installed_mod("Skins/Recolor", "widow_P");
+        fx.installed_mod("Skins/Hulk", "big_P");
+        let fixed = fx.installed_mod("Skins/Other", "widow2_P");
+
+        // Metadata is created by the scan, from the same guesses
+        let mods = service.get_all_mods().unwrap();
+        let fixed = mods.iter().find(|m| m.file_path == fixed).unwrap();
+        let mut meta = fixed.metadata.clone();
+        meta.character = Some(Character::Hulk);
+        service.save_metadata(&fixed.id, &meta).unwrap();
+
+        let weak = service.find_low_confidence_assignments().unwrap();
+        assert_eq!(weak.len(), 1);
+        assert_eq!(weak[0].character, Character::BlackWidow);
+        assert_eq!(weak[0].file_name, "widow_P.pak");
+
+        let preview = service.preview_mod_metadata(&fx.mods_dir().join("Skins/Hulk/big_P.pak"));
+        assert_eq!(preview.character, Some(Character::Hulk));
+        assert_eq!(
+            preview.character_confidence,
+            Some(DetectionConfidence::High)
+        );
     }
 
-    /// Detect conflicts between enabled mods: two or more mods overriding the
-    /// same game asset. A parent mod and its own add-on are expected to share
-    /// assets (that's how add-ons layer), so those pairs are NOT reported; only
-    /// clashes between unrelated mods are returned.
-    pub fn detect_mod_conflicts(&self) -> Result<Vec<ModConflict>, String> {
-        let all_mods = self.get_all_mods()?;
-        let enabled: Vec<&ModInfo> = all_mods.iter().filter(|m| m.enabled).collect();
+    #[test]
+    fn test_favorites() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut ids = HashMap::new();
+        for (title, character) in [
+            ("Zeta", Some(Character::Storm)),
+            ("Alpha", Some(Character::Storm)),
+            ("Loose", None),
+            ("Other", Some(Character::Groot)),
+            ("Plain", Some(Character::Groot)),
+        ] {
+            let mut meta = metadata(title, ModCategory::Skins);
+            meta.character = character;
+            let installed = service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(&format!("{title}_P"), 10, None),
+                    &format!("Skins/{title}"),
+                    meta,
+                )
+                .unwrap();
+            ids.insert(title, installed.id);
+        }
 
-        // For each enabled mod, read the asset paths its .utoc overrides along
-        // with a content hash per asset (from the TOC's chunk metas), so that
-        // byte-identical overlaps can be ignored.
-        // Keyed by mod id -> map of asset path -> content hash.
-        let mut mod_assets: HashMap<String, HashMap<String, String>> = HashMap::new();
-        let mut load_key: HashMap<String, String> = HashMap::new();
-        let mut title_by_id: HashMap<String, String> = HashMap::new();
+        let mut count = 0;
+        for title in ["Zeta", "Alpha", "Loose", "Other"] {
+            let toggled = service.toggle_favorite(&ids[title]).unwrap();
+            assert!(toggled.mod_info.is_favorite);
+            count += 1;
+            assert_eq!(toggled.favorites_count, count);
+        }
+        let toggled = service.toggle_favorite(&ids["Other"]).unwrap();
+        assert!(!toggled.mod_info.is_favorite);
+        assert_eq!(toggled.favorites_count, 3);
 
-        for m in &enabled {
-            title_by_id.insert(m.id.clone(), m.metadata.title.clone());
-            // The game mounts by lowercased full path; first wins.
-            load_key.insert(m.id.clone(), m.file_path.to_string_lossy().to_lowercase());
+        let titles = |mods: Vec<ModInfo>| -> Vec<String> {
+            mods.into_iter().map(|m| m.metadata.title).collect()
+        };
+        assert_eq!(
+            titles(service.get_favorites().unwrap()),
+            vec!["Alpha", "Zeta", "Loose"]
+        );
 
-            let utoc = m
-                .associated_files
-                .iter()
-                .find(|p| p.extension().and_then(|e| e.to_str()) == Some("utoc"));
-            let Some(utoc) = utoc else {
-                continue; // loose .pak with no utoc — nothing to compare
-            };
-            match read_utoc_assets(utoc) {
-                Ok(assets) => {
-                    mod_assets.insert(m.id.clone(), assets);
-                }
-                Err(e) => {
-                    log::warn!("[conflicts] Could not read {:?}: {}", utoc, e);
-                }
-            }
+        // Favorites first, enabled ones ahead of disabled ones
+        let disabled = service.enable_mod(&ids["Alpha"], false).unwrap();
+        let mut all = service.get_all_mods().unwrap();
+        crate::sorting::sort_mods(
+            &mut all,
+            &SortSpec {
+                key: SortKey::EnabledFavoritesFirst,
+                direction: SortDirection::Asc,
+            },
+        );
+        assert_eq!(
+            titles(all),
+            vec!["Loose", "Zeta", "Alpha", "Other", "Plain"]
+        );
+
+        // A profile from favorites includes disabled favorites too
+        assert_eq!(service.add_profile_to_favorites("favs").unwrap(), 3);
+        let tagged: Vec<String> = service
+            .get_all_mods()
+            .unwrap()
+            .into_iter()
+            .filter(|m| m.metadata.profile_ids.is_some())
+            .map(|m| m.id)
+            .collect();
+        assert_eq!(tagged.len(), 3);
+        assert!(tagged.contains(&disabled.id));
+    }
+
+    #[test]
+    fn test_active_profile_assignment() {
+        let fx = Fixture::new();
+        let kept = fx
+            .service()
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("KeptSkin_P", 10, None),
+                "Skins/Kept-Skin",
+                metadata("Kept Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        let dropped = fx
+            .service()
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("DroppedSkin_P", 10, None),
+                "Skins/Dropped-Skin",
+                metadata("Dropped Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        assert_eq!(kept.metadata.profile_ids, None);
+        fx.service().enable_mod(&dropped.id, false).unwrap();
+
+        // Saving the current setup only picks up what is enabled
+        let service = fx.service();
+        assert_eq!(service.add_profile_to_enabled_mods("profile_a").unwrap(), 1);
+        assert_eq!(service.add_profile_to_enabled_mods("profile_a").unwrap(), 0);
+        let mods = service.get_all_mods().unwrap();
+        let profiles_of = |title: &str| {
+            mods.iter()
+                .find(|m| m.metadata.title == title)
+                .unwrap()
+                .metadata
+                .profile_ids
+                .clone()
+        };
+        assert_eq!(
+            profiles_of("Kept Skin"),
+            Some(vec!["profile_a".to_string()])
+        );
+        assert_eq!(profiles_of("Dropped Skin"), None);
+
+        // With a profile active, new installs join it alongside their own
+        let service = fx
+            .service()
+            .with_active_profile(Some("profile_a".to_string()));
+        let mut tagged = metadata("New Skin", ModCategory::Skins);
+        tagged.profile_ids = Some(vec!["profile_b".to_string()]);
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("NewSkin_P", 10, None),
+                "Skins/New-Skin",
+                tagged,
+            )
+            .unwrap();
+        assert_eq!(
+            installed.metadata.profile_ids,
+            Some(vec!["profile_b".to_string(), "profile_a".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_split_mod_is_rolled_back_on_startup() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("SplitSkin_P", 10, Some(100)),
+                "Skins/Split-Skin",
+                metadata("Split Skin", ModCategory::Skins),
+            )
+            .unwrap();
+
+        // Crash while disabling: the pak already moved, its companions didn't
+        let stray = fx
+            .disabled_dir()
+            .join("Skins")
+            .join("Split-Skin")
+            .join("SplitSkin_P.pak");
+        fs::create_dir_all(stray.parent().unwrap()).unwrap();
+        fs::rename(&installed.file_path, &stray).unwrap();
+
+        let splits = service.detect_split_mods().unwrap();
+        assert_eq!(splits.len(), 1);
+        assert!(!splits[0].pak_enabled);
+        assert!(splits[0].auto_repairable);
+        assert_eq!(splits[0].enabled_files.len(), 2);
+
+        let report = service.repair_split_mods().unwrap();
+        assert_eq!(report.repaired, 1);
+        assert!(report.unresolved.is_empty());
+        assert!(service.detect_split_mods().unwrap().is_empty());
+
+        // Back where it started, under its original ID and metadata
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), 1);
+        assert_eq!(mods[0].id, installed.id);
+        assert!(mods[0].enabled);
+        assert_eq!(mods[0].associated_files.len(), 3);
+        assert_eq!(mods[0].metadata.title, "Split Skin");
+        assert!(!stray.exists());
+    }
+
+    #[test]
+    fn test_ambiguous_split_mod_needs_a_chosen_side() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let pak = fx.installed_mod("Skins/Odd-Skin", "OddSkin_P");
+        let utoc = pak.with_extension("utoc");
+        let stray = fx
+            .disabled_dir()
+            .join("Skins")
+            .join("Odd-Skin")
+            .join("OddSkin_P.utoc");
+        fs::create_dir_all(stray.parent().unwrap()).unwrap();
+        fs::rename(&utoc, &stray).unwrap();
+
+        // The pak shares its side with the .ucas, so startup leaves it alone
+        let report = service.repair_split_mods().unwrap();
+        assert_eq!(report.repaired, 0);
+        assert_eq!(report.unresolved.len(), 1);
+        assert!(stray.exists());
+
+        let mod_id = report.unresolved[0].mod_id.clone();
+        let repaired = service
+            .repair_split_mod(&mod_id, SplitSide::Enabled)
+            .unwrap();
+        assert_eq!(repaired.id, mod_id);
+        assert!(repaired.enabled);
+        assert_eq!(repaired.associated_files.len(), 3);
+        assert!(utoc.exists());
+        assert!(!stray.exists());
+        assert!(service
+            .repair_split_mod(&mod_id, SplitSide::Enabled)
+            .is_err());
+    }
+
+    #[test]
+    fn test_mod_enabled_and_disabled_in_place_is_listed_twice() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let pak = fx.installed_mod("Skins/Venom", "Venom_P");
+        for ext in ["pak", "ucas", "utoc"] {
+            let copy = pak.with_extension(format!("{ext}disabled"));
+            fs::copy(pak.with_extension(ext), copy).unwrap();
         }
 
-        // A mod is "related" to another (and so not a conflict) when one is the
-        // other's parent, or they share the same parent (sibling add-ons).
-        let parent_of: HashMap<String, Option<String>> = enabled
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), 2);
+        let active = mods
             .iter()
-            .map(|m| (m.id.clone(), m.metadata.parent_mod_id.clone()))
-            .collect();
-        let related = |a: &str, b: &str| -> bool {
-            let pa = parent_of.get(a).cloned().flatten();
-            let pb = parent_of.get(b).cloned().flatten();
-            pa.as_deref() == Some(b)
-                || pb.as_deref() == Some(a)
-                || (pa.is_some() && pa == pb)
+            .find(|m| m.location == ModLocation::Active)
+            .unwrap();
+        let disabled = mods
+            .iter()
+            .find(|m| m.location == ModLocation::Disabled)
+            .unwrap();
+        assert_eq!((&active.file_path, &active.duplicate_of), (&pak, &None));
+        assert_eq!(disabled.duplicate_of.as_ref(), Some(&active.id));
+        assert_ne!(disabled.id, active.id);
+        let pending = |_: &[ModInfo]| CachedCount {
+            count: 0,
+            stale: false,
         };
+        let summary = service.summarize(mods.clone(), pending);
+        assert_eq!(summary.unresolved_duplicates, 1);
+
+        let kept = service
+            .resolve_duplicate(&disabled.id, ModLocation::Disabled)
+            .unwrap();
+        assert_eq!(kept.id, active.id);
+        assert!(!kept.enabled);
+        assert_eq!(kept.associated_files.len(), 3);
+        assert!(!pak.exists());
+        assert_eq!(service.get_all_mods().unwrap().len(), 1);
+        assert!(service
+            .resolve_duplicate(&kept.id, ModLocation::Active)
+            .is_err());
+    }
 
-        // asset path -> mod ids that override it
-        let mut owners: HashMap<String, Vec<String>> = HashMap::new();
-        for (id, assets) in &mod_assets {
-            for a in assets.keys() {
-                owners.entry(a.clone()).or_default().push(id.clone());
-            }
-        }
+    #[test]
+    fn test_organize_loose_mods() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let loose = fx.installed_mod("", "LooseMod_P");
 
-        // Group clashes by the set of mods involved.
-        // key = sorted mod-id list -> set of shared asset paths
-        let mut grouped: HashMap<Vec<String>, HashSet<String>> = HashMap::new();
-        for (asset, ids) in &owners {
-            if ids.len() < 2 {
-                continue;
-            }
-            // Keep only ids that are unrelated to at least one other id here.
-            let mut unrelated_ids: Vec<String> = Vec::new();
-            for id in ids {
-                let conflicts_with_someone = ids
-                    .iter()
-                    .any(|other| other != id && !related(id, other));
-                if conflicts_with_someone {
-                    unrelated_ids.push(id.clone());
-                }
-            }
-            if unrelated_ids.len() < 2 {
-                continue; // every owner is a relative of the others — expected layering
-            }
-            unrelated_ids.sort();
-            unrelated_ids.dedup();
+        let before = service.get_all_mods().unwrap();
+        assert_eq!(before.len(), 1);
+        assert_eq!(before[0].file_path, loose);
 
-            // If every unrelated owner ships byte-identical content for this
-            // asset (same chunk hashes), it doesn't matter who wins — authors
-            // often bundle the same untouched base-game file. Skip it. A
-            // missing hash is treated as different (flag rather than hide).
-            let mut hashes: Vec<Option<&String>> = Vec::new();
-            for id in &unrelated_ids {
-                hashes.push(mod_assets.get(id).and_then(|a| a.get(asset)));
-            }
-            let all_known = hashes.iter().all(|h| h.is_some());
-            if all_known {
-                let first = hashes[0];
-                if hashes.iter().all(|h| *h == first) {
-                    continue; // identical content everywhere — harmless overlap
-                }
-            }
+        assert_eq!(service.organize_loose_mods(None).unwrap().moved_mods, 1);
 
-            grouped
-                .entry(unrelated_ids)
-                .or_default()
-                .insert(asset.clone());
+        let after = service.get_all_mods().unwrap();
+        assert_eq!(after.len(), 1);
+        let mut expected = fx.mods_dir().join(before[0].category.to_string());
+        if let Some(ref character) = before[0].character {
+            expected = expected.join(sanitize_folder_name(&character.to_string()));
         }
+        expected = expected.join(sanitize_folder_name(&before[0].name));
+        assert_eq!(after[0].file_path, expected.join("LooseMod_P.pak"));
+        assert_eq!(after[0].associated_files.len(), 3);
+        assert_eq!(after[0].metadata.title, before[0].metadata.title);
+        assert!(!loose.exists());
+
+        // Already organized: nothing left to do
+        assert_eq!(service.organize_loose_mods(None).unwrap().moved_mods, 0);
+    }
 
-        // Build the result list.
-        let mut conflicts: Vec<ModConflict> = Vec::new();
-        for (ids, assets) in grouped {
-            // The winner is the mod whose load-order key sorts first.
-            let winner = ids
-                .iter()
-                .min_by(|a, b| {
-                    load_key
-                        .get(*a)
-                        .map(String::as_str)
-                        .unwrap_or("")
-                        .cmp(load_key.get(*b).map(String::as_str).unwrap_or(""))
-                })
-                .cloned();
+    #[test]
+    fn test_organize_preview_moves_only_approved_mods() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let kept = fx.installed_mod("", "LooseKept_P");
+        let moved = fx.installed_mod("", "LooseMoved_P");
+
+        let preview = service.preview_organize_mods().unwrap();
+        assert_eq!(preview.len(), 2);
+        assert!(kept.exists() && moved.exists());
+        let approved: Vec<OrganizeMove> = preview
+            .into_iter()
+            .filter(|m| m.source_path == moved)
+            .collect();
+        assert!(approved[0].destination_path.ends_with("LooseMoved_P.pak"));
+
+        let result = service.organize_loose_mods(Some(&approved)).unwrap();
+        assert_eq!((result.total_mods, result.moved_mods), (1, 1));
+        assert!(result.errors.is_empty());
+        assert!(kept.exists());
+        assert!(approved[0].destination_path.exists() && !moved.exists());
+
+        // A move previewed for somewhere else is no longer approved
+        let mut stale = service.preview_organize_mods().unwrap();
+        stale[0].destination_path = fx.mods_dir().join("Elsewhere").join("LooseKept_P.pak");
+        let result = service.organize_loose_mods(Some(&stale)).unwrap();
+        assert_eq!(result.total_mods, 0);
+        assert!(kept.exists());
+    }
 
-            let mods: Vec<ConflictMod> = {
-                let mut v: Vec<ConflictMod> = ids
-                    .iter()
-                    .map(|id| ConflictMod {
-                        id: id.clone(),
-                        title: title_by_id
-                            .get(id)
-                            .cloned()
-                            .unwrap_or_else(|| id.clone()),
-                        wins: Some(id) == winner.as_ref(),
-                    })
-                    .collect();
-                // winner first
-                v.sort_by(|a, b| b.wins.cmp(&a.wins).then(a.title.cmp(&b.title)));
-                v
-            };
+    #[test]
+    fn test_organize_preview_is_unsure_when_metadata_disagrees_with_detection() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        fx.installed_mod("", "LooseHulk_P");
 
-            let mut asset_stems: Vec<String> = assets
-                .iter()
-                .map(|a| {
-                    Path::new(a)
-                        .file_stem()
-                        .and_then(|s| s.to_str())
-                        .unwrap_or(a)
-                        .to_string()
-                })
-                .collect();
-            asset_stems.sort();
-            asset_stems.dedup();
+        let detected = service.preview_organize_mods().unwrap().remove(0);
+        let mut meta = metadata("Loose Hulk", ModCategory::Other);
+        meta.character = detected.character.clone();
+        service.save_metadata(&detected.mod_id, &meta).unwrap();
 
-            let mut kinds: Vec<String> = asset_stems
-                .iter()
-                .map(|a| classify_asset(a))
-                .collect();
-            kinds.sort();
-            kinds.dedup();
+        let edited = service.preview_organize_mods().unwrap().remove(0);
+        assert_eq!(edited.category, ModCategory::Other);
+        assert_eq!(edited.confidence, DetectionConfidence::Low);
+    }
 
-            conflicts.push(ModConflict {
-                mods,
-                kinds,
-                assets: asset_stems,
-            });
-        }
+    #[test]
+    fn test_merge_duplicate_folders_genuine_duplicate() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        fx.installed_mod("Skins/Black Widow/Mod-A", "ModA_P");
+        fx.installed_mod("Skins/Black-Widow/Mod-B", "ModB_P");
 
-        // Most-overlapping conflicts first.
-        conflicts.sort_by(|a, b| b.assets.len().cmp(&a.assets.len()));
-        Ok(conflicts)
+        // Mark mod A so we can tell its metadata survived the move
+        let mods = service.get_all_mods().unwrap();
+        let mod_a = mods
+            .iter()
+            .find(|m| m.original_file_name == "ModA_P.pak")
+            .unwrap();
+        let mut favorite = mod_a.metadata.clone();
+        favorite.is_favorite = true;
+        service.save_metadata(&mod_a.id, &favorite).unwrap();
+
+        let report = service.merge_duplicate_folders().unwrap();
+        assert_eq!(report.merged_folders, 1);
+        assert_eq!(report.actions.len(), 1);
+        assert_eq!(report.actions[0].action, FolderMergeAction::Moved);
+
+        let target = fx.mods_dir().join("Skins").join("Black-Widow");
+        assert!(!fx.mods_dir().join("Skins").join("Black Widow").exists());
+        assert!(target.join("Mod-A").join("ModA_P.pak").exists());
+        assert!(target.join("Mod-B").join("ModB_P.pak").exists());
+
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), 2);
+        let mod_a = mods
+            .iter()
+            .find(|m| m.original_file_name == "ModA_P.pak")
+            .unwrap();
+        assert!(mod_a.is_favorite);
+        assert_eq!(fx.metadata_count(), 2);
     }
-}
 
-/// Sanitize a string to be used as a folder name
-/// Removes or replaces invalid characters for Windows file systems
-fn sanitize_folder_name(name: &str) -> String {
-    name
-        // Remove invalid Windows filename characters: < > : " / \ | ? *
-        .chars()
-        .filter(|c| !matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*'))
-        .collect::<String>()
-        // Replace multiple spaces with single space
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
-        // Replace spaces with hyphens
-        .replace(' ', "-")
-        // Remove leading/trailing hyphens and periods
-        .trim_matches(|c| c == '-' || c == '.' || c == ' ')
-        // Limit length to 100 characters
-        .chars()
-        .take(100)
-        .collect::<String>()
-        // Return default if empty
-        .into()
-}
+    #[test]
+    fn test_merge_duplicate_folders_keeps_colliding_mods() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        // Same mod folder name on both sides, different mods inside
+        fx.installed_mod("Skins/Black Widow/Cool-Mod", "OldVersion_P");
+        fx.installed_mod("Skins/Black-Widow/Cool-Mod", "NewVersion_P");
+
+        let report = service.merge_duplicate_folders().unwrap();
+        assert_eq!(report.merged_folders, 1);
+
+        // Neither copy is lost: the moved one gets a suffix and is reported
+        let target = fx.mods_dir().join("Skins").join("Black-Widow");
+        assert!(target.join("Cool-Mod-2/OldVersion_P.pak").exists());
+        assert!(target.join("Cool-Mod/NewVersion_P.pak").exists());
+        assert_eq!(report.actions.len(), 1);
+        assert_eq!(report.actions[0].action, FolderMergeAction::KeptBoth);
+        assert_eq!(report.actions[0].destination, target.join("Cool-Mod-2"));
+        assert_eq!(
+            report.actions[0].collided_with,
+            Some(target.join("Cool-Mod"))
+        );
+        assert_eq!(service.get_all_mods().unwrap().len(), 2);
+    }
 
-/// Check if a folder is completely empty (no files, only empty subdirectories)
-fn is_folder_empty(path: &Path) -> Result<bool, String> {
-    for entry in fs::read_dir(path).map_err(|e| format!("Failed to read directory: {}", e))? {
-        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
-        let entry_path = entry.path();
+    #[test]
+    fn test_merge_duplicate_folders_drops_identical_copies() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        // The same mod installed under both spellings; only the stray copy
+        // has metadata
+        let stray = fx.installed_mod("Skins/Black Widow/Cool-Mod", "Cool_P");
+        let kept = fx.installed_mod("Skins/Black-Widow/Cool-Mod", "Cool_P");
+        let stray_id = service.generate_mod_id_from_path(&stray, "Cool_P.pak");
+        let kept_id = service.generate_mod_id_from_path(&kept, "Cool_P.pak");
+        service
+            .save_metadata(&stray_id, &metadata("Cool Mod", ModCategory::Skins))
+            .unwrap();
+
+        let report = service.merge_duplicate_folders().unwrap();
+        assert_eq!(report.merged_folders, 1);
+        assert_eq!(report.actions.len(), 1);
+        assert_eq!(
+            report.actions[0].action,
+            FolderMergeAction::DuplicateRemoved
+        );
+        assert!(!fx.mods_dir().join("Skins").join("Black Widow").exists());
+        assert!(kept.exists());
+        assert!(service.load_metadata(&stray_id).unwrap().is_none());
+        assert_eq!(
+            service.load_metadata(&kept_id).unwrap().unwrap().title,
+            "Cool Mod"
+        );
 
-        if entry_path.is_file() {
-            // Found a file, not empty
-            return Ok(false);
-        } else if entry_path.is_dir() {
-            // Recursively check subdirectory
-            if !is_folder_empty(&entry_path)? {
-                return Ok(false);
-            }
+        // Same name, same size, different bytes is not a duplicate
+        let other = fx.installed_mod("UI/Black Widow/Cool-Mod", "Cool_P");
+        fx.installed_mod("UI/Black-Widow/Cool-Mod", "Cool_P");
+        fs::write(&other, [1u8; 8]).unwrap();
+        let report = service.merge_duplicate_folders().unwrap();
+        assert_eq!(report.actions[0].action, FolderMergeAction::KeptBoth);
+    }
+
+    #[test]
+    fn test_ignored_paths_are_left_alone() {
+        let fx = Fixture::new();
+        let service = fx.service().with_ignored_paths(&["_backup".to_string()]);
+        fx.installed_mod("Skins/Storm/Weather", "Storm_P");
+        fx.installed_mod("_backup/Skins/Black Widow/Old", "Old_P");
+        fx.installed_mod("_backup/Skins/Black-Widow/Older", "Older_P");
+        fs::create_dir_all(fx.mods_dir().join("_backup/empty")).unwrap();
+
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), 1);
+        assert_eq!(mods[0].original_file_name, "Storm_P.pak");
+        assert!(service.find_duplicate_folders().unwrap().is_empty());
+        assert!(service.find_empty_mod_folders().unwrap().is_empty());
+        assert_eq!(
+            service.ignored_folders_with_paks(),
+            vec![fx.mods_dir().join("_backup")]
+        );
+
+        // Without the setting it's all part of the library again
+        assert_eq!(fx.service().get_all_mods().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_scan_depth_limit_and_profile() {
+        let fx = Fixture::new();
+        fx.installed_mod("Skins/Storm/Weather", "Storm_P");
+        fx.installed_mod("Old/2023/backup/Skins/Hulk/Maestro", "Maestro_P");
+        fs::create_dir_all(fx.mods_dir().join("Old/2023/backup/Skins/empty")).unwrap();
+        assert!(fx.service().last_scan_profile().unwrap().is_none());
+
+        let service = fx.service().with_max_scan_depth(Some(4));
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), 1);
+        assert_eq!(mods[0].original_file_name, "Storm_P.pak");
+        // Empty folders past the limit aren't looked at either
+        assert!(service.find_empty_mod_folders().unwrap().is_empty());
+
+        let profile = service.last_scan_profile().unwrap().unwrap();
+        assert_eq!(profile.max_scan_depth, Some(4));
+        let names: Vec<&str> = profile.phases.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["thumbnails", "mods", "disabled"]);
+        let mods_phase = &profile.phases[1];
+        // Storm's three files; ~mods, Skins/Storm/Weather and
+        // Old/2023/backup/Skins
+        assert_eq!((mods_phase.files, mods_phase.directories), (3, 8));
+        assert!(profile.files >= 3 && profile.directories >= 8);
+
+        // 0 means no limit
+        let service = fx.service().with_max_scan_depth(Some(0));
+        assert_eq!(service.get_all_mods().unwrap().len(), 2);
+        assert_eq!(
+            service.find_empty_mod_folders().unwrap(),
+            vec![fx.mods_dir().join("Old/2023/backup/Skins/empty")]
+        );
+        let profile = service.last_scan_profile().unwrap().unwrap();
+        assert_eq!(profile.max_scan_depth, None);
+    }
+
+    #[test]
+    fn test_delete_mod_cleans_up() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let source = fx.source_mod("CoolSkin_P", 10, Some(100));
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &source,
+                "Skins/Cool-Skin",
+                metadata("Cool Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        fs::write(
+            fx.metadata_dir
+                .join(format!("{}_thumbnail.png", installed.id)),
+            b"png",
+        )
+        .unwrap();
+
+        service.delete_mod(&installed.id, false, true).unwrap();
+
+        assert!(service.get_all_mods().unwrap().is_empty());
+        assert_eq!(fx.metadata_count(), 0);
+        assert!(!fx
+            .metadata_dir
+            .join(format!("{}_thumbnail.png", installed.id))
+            .exists());
+        // The mod's own folder goes, the category folder stays
+        assert!(!fx.mods_dir().join("Skins").join("Cool-Skin").exists());
+        assert!(fx.mods_dir().join("Skins").exists());
+        // The install source is untouched
+        assert!(source.exists());
+    }
+
+    #[test]
+    fn test_deleted_mods_wait_in_the_trash() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("CoolSkin_P", 10, Some(100)),
+                "Skins/Cool-Skin",
+                metadata("Cool Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        let thumbnail = fx
+            .metadata_dir
+            .join(format!("{}_thumbnail.png", installed.id));
+        fs::write(&thumbnail, b"png").unwrap();
+
+        service.delete_mod(&installed.id, false, false).unwrap();
+        assert!(service.get_all_mods().unwrap().is_empty());
+        assert_eq!(fx.metadata_count(), 0);
+        assert!(!thumbnail.exists());
+        let trashed = service.list_trashed_mods();
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].title, "Cool Skin");
+        assert_eq!(trashed[0].deleted_at, fixed_now());
+        // The pak, its companions, the metadata and the thumbnail
+        assert_eq!(trashed[0].files.len(), 5);
+
+        let restored = service.restore_mod(&installed.id).unwrap();
+        assert_eq!(restored.id, installed.id);
+        assert_eq!(restored.metadata.title, "Cool Skin");
+        assert!(thumbnail.exists());
+        assert!(service.list_trashed_mods().is_empty());
+        assert!(service.restore_mod(&installed.id).is_err());
+        assert!(service.restore_mod("../metadata").is_err());
+
+        // Kept for the retention period, then purged
+        service
+            .delete_mods(&[installed.id.clone()], false, false)
+            .unwrap();
+        assert_eq!(service.purge_expired_trash().unwrap(), 0);
+        let later = fx
+            .service()
+            .with_clock(Arc::new(super::fixtures::FixedClock(
+                fixed_now() + chrono::Duration::days(TRASH_RETENTION_DAYS + 1),
+            )));
+        assert_eq!(later.purge_expired_trash().unwrap(), 1);
+        assert!(service.list_trashed_mods().is_empty());
+        assert!(service.restore_mod(&installed.id).is_err());
+    }
+
+    #[test]
+    fn test_identical_paks_are_found_and_merged() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let source = fx.source_mod("CoolSkin_P", 8, Some(64));
+        let keep = service
+            .install_mod_to_folder_with_metadata(
+                &source,
+                "Skins/Cool",
+                metadata("Cool Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        let mut filled_in = metadata("Cool Skin (copy)", ModCategory::Skins);
+        filled_in.description = "From Nexus".to_string();
+        filled_in.nexus_mod_id = Some(42);
+        filled_in.nexus_file_id = Some(7);
+        filled_in.profile_ids = Some(vec!["raid-night".to_string()]);
+        let copy = service
+            .install_mod_to_folder_with_metadata(&source, "Downloads/Cool", filled_in)
+            .unwrap();
+        fs::write(
+            fx.metadata_dir.join(format!("{}_thumbnail.png", copy.id)),
+            b"png",
+        )
+        .unwrap();
+        // Same size, different bytes
+        let other = fx.source_mod("OtherSkin_P", 8, Some(64));
+        fs::write(&other, b"abcdefgh").unwrap();
+        let other = service
+            .install_mod_to_folder_with_metadata(
+                &other,
+                "Skins/Other",
+                metadata("Other Skin", ModCategory::Skins),
+            )
+            .unwrap();
+
+        let groups = service.find_duplicate_mods().unwrap();
+        assert_eq!(groups.len(), 1);
+        let mut ids: Vec<&str> = groups[0].mods.iter().map(|m| m.mod_id.as_str()).collect();
+        ids.sort();
+        let mut expected = vec![keep.id.as_str(), copy.id.as_str()];
+        expected.sort();
+        assert_eq!(ids, expected);
+
+        let refused = service.merge_duplicate_mods(&keep.id, &[other.id.clone()]);
+        assert!(refused.unwrap_err().contains("not an identical copy"));
+
+        let merged = service
+            .merge_duplicate_mods(&keep.id, &[copy.id.clone()])
+            .unwrap();
+        assert_eq!(merged.metadata.title, "Cool Skin");
+        assert_eq!(merged.metadata.description, "From Nexus");
+        assert_eq!(
+            (merged.metadata.nexus_mod_id, merged.metadata.nexus_file_id),
+            (Some(42), Some(7))
+        );
+        assert_eq!(
+            merged.metadata.profile_ids,
+            Some(vec!["raid-night".to_string()])
+        );
+        assert!(merged.thumbnail_path.is_some());
+        assert_eq!(service.get_all_mods().unwrap().len(), 2);
+        assert_eq!(service.list_trashed_mods()[0].mod_id, copy.id);
+        assert!(service.find_duplicate_mods().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delete_preview_matches_delete() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let pak_a = fx.installed_mod("Skins/Pair", "ModA_P");
+        let pak_b = fx.installed_mod("Skins/Pair", "ModB_P");
+        fx.installed_mod("Skins/Solo", "ModC_P");
+        let stale = fx.mods_dir().join("Skins/Stale");
+        fs::create_dir_all(&stale).unwrap();
+        let mods = service.get_all_mods().unwrap();
+        let id_of = |pak: &Path| {
+            mods.iter()
+                .find(|m| m.file_path == pak)
+                .map(|m| m.id.clone())
+                .unwrap()
+        };
+        let (a, b) = (id_of(&pak_a), id_of(&pak_b));
+
+        // The folder still holds B's files, so it stays
+        let preview = service.preview_delete(&[a.clone()]).unwrap();
+        assert_eq!(preview.mods.len(), 1);
+        assert_eq!(preview.mods[0].files.len(), 3);
+        assert!(preview.folders.is_empty());
+        assert!(preview.shared_files.is_empty());
+
+        let both = vec![a.clone(), b.clone(), "nope".to_string()];
+        let preview = service.preview_delete(&both).unwrap();
+        assert_eq!(preview.folders, vec![fx.mods_dir().join("Skins/Pair")]);
+        assert_eq!(preview.missing, vec!["nope".to_string()]);
+
+        // A file another mod also lists is flagged and left out
+        let mut tangled = mods.clone();
+        let b_info = tangled.iter_mut().find(|m| m.id == b).unwrap();
+        b_info.associated_files.push(pak_a.clone());
+        let plan = service.plan_delete(&tangled, &[a.clone()]);
+        assert_eq!(plan.mods[0].files.len(), 2);
+        assert!(!plan.mods[0].files.contains(&pak_a));
+        assert_eq!(plan.shared_files[0].path, pak_a);
+        assert_eq!(plan.shared_files[0].mod_ids, vec![b.clone()]);
+
+        assert_eq!(service.delete_mods(&both, false, true).unwrap(), 2);
+        assert!(!fx.mods_dir().join("Skins/Pair").exists());
+        assert_eq!(service.get_all_mods().unwrap().len(), 1);
+        // An empty folder the preview didn't list is left alone
+        assert!(stale.exists());
+    }
+
+    #[test]
+    fn test_id_migration_is_scoped_and_resumable() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut ids = Vec::new();
+        for stem in ["ModA_P", "ModB_P", "ModC_P"] {
+            let pak = fx.installed_mod(&format!("Skins/{stem}"), stem);
+            let file_name = format!("{stem}.pak");
+            let old_id = service.generate_mod_id(&file_name);
+            let new_id = service.generate_mod_id_from_path(&pak, &file_name);
+            ids.push((old_id, new_id));
         }
+        let [(old_a, new_a), (old_b, new_b), (_, new_c)] = ids.try_into().unwrap();
+        // A and B have metadata under the old filename IDs, C has none at all
+        service
+            .save_metadata(&old_a, &metadata("Mod A", ModCategory::Skins))
+            .unwrap();
+        service
+            .save_metadata(&old_b, &metadata("Mod B", ModCategory::Skins))
+            .unwrap();
+        let thumb = |id: &str| fx.metadata_dir.join(format!("{id}_thumbnail.png"));
+        fs::write(thumb(&old_a), b"png").unwrap();
+        let counts = |r: &IdMigrationReport| {
+            (
+                r.migrated_count,
+                r.already_migrated_count,
+                r.not_found_count,
+            )
+        };
+
+        // A dry run reports without touching anything
+        let report = service.migrate_metadata_to_path_ids(None, true).unwrap();
+        assert!(report.dry_run);
+        assert_eq!(counts(&report), (2, 0, 1));
+        let outcome_of = |report: &IdMigrationReport, id: &str| {
+            report
+                .entries
+                .iter()
+                .find(|e| e.mod_id == id)
+                .map(|e| e.outcome)
+        };
+        assert_eq!(
+            outcome_of(&report, &new_c),
+            Some(IdMigrationOutcome::NoOldMetadata)
+        );
+        assert!(service.load_metadata(&new_a).unwrap().is_none());
+        assert!(thumb(&old_a).exists());
+
+        // Scoped to A, only A moves, thumbnail included
+        let report = service
+            .migrate_metadata_to_path_ids(Some(std::slice::from_ref(&new_a)), false)
+            .unwrap();
+        assert_eq!(report.entries.len(), 1);
+        assert_eq!(
+            outcome_of(&report, &new_a),
+            Some(IdMigrationOutcome::Migrated)
+        );
+        let migrated = service.load_metadata(&new_a).unwrap().unwrap();
+        assert_eq!(migrated.title, "Mod A");
+        assert!(service.load_metadata(&old_a).unwrap().is_none());
+        assert!(thumb(&new_a).exists() && !thumb(&old_a).exists());
+        assert!(service.load_metadata(&new_b).unwrap().is_none());
+
+        // A run interrupted after copying B's metadata left its checkpoint
+        // behind; the next run finishes B even when scoped elsewhere
+        service
+            .save_metadata(&new_b, &metadata("Mod B", ModCategory::Skins))
+            .unwrap();
+        let checkpoint = fx.metadata_dir.join(ID_MIGRATION_CHECKPOINT);
+        write_id_migration_checkpoint(
+            &checkpoint,
+            &IdMigrationCheckpoint {
+                remaining: vec![(old_b.clone(), new_b.clone())],
+            },
+        )
+        .unwrap();
+        let report = service
+            .migrate_metadata_to_path_ids(Some(std::slice::from_ref(&new_c)), false)
+            .unwrap();
+        assert_eq!(report.resumed_count, 1);
+        assert!(!checkpoint.exists());
+        assert!(service.load_metadata(&old_b).unwrap().is_none());
+
+        let report = service.migrate_metadata_to_path_ids(None, false).unwrap();
+        assert_eq!(counts(&report), (0, 2, 1));
     }
 
-    // No files found, folder is empty
-    Ok(true)
-}
+    #[test]
+    fn test_legacy_disabled_files_are_converted() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        // An older manager renamed the pak and ucas but left the utoc alone
+        let dir = fx.mods_dir().join("Skins").join("Old");
+        let target = fx.disabled_dir().join("Skins").join("Old");
+        write_file(&dir.join("old_P.pak.disabled"), 8);
+        write_file(&dir.join("old_P.ucas.DISABLED"), 64);
+        write_file(&dir.join("old_P.utoc"), 4);
+        // Companions without a pak aren't a mod
+        write_file(&fx.mods_dir().join("stray_P.utoc.disabled"), 4);
+        let kept = fx.installed_mod("Skins/Kept", "kept_P");
+
+        let found = service.detect_legacy_disabled_files().unwrap();
+        assert_eq!(found.len(), 1);
+        let old_id = found[0].mod_id.clone();
+        assert_eq!(found[0].files.len(), 3);
+        assert_eq!(found[0].target_path, target.join("old_P.pak"));
+        service
+            .save_metadata(&old_id, &metadata("Old Mod", ModCategory::Skins))
+            .unwrap();
+
+        // A dry run reports without moving anything
+        let report = service.convert_legacy_disabled(None, true).unwrap();
+        assert!(report.dry_run);
+        assert_eq!((report.converted, report.mods.len()), (0, 1));
+        assert!(dir.join("old_P.pak.disabled").exists());
+
+        let report = service.convert_legacy_disabled(None, false).unwrap();
+        assert_eq!(report.converted, 1);
+        assert!(report.mods[0].error.is_none());
+        assert!(fs::read_dir(&dir).unwrap().next().is_none());
+        for name in ["old_P.pak", "old_P.ucas", "old_P.utoc"] {
+            assert!(target.join(name).is_file(), "{name} not moved");
+        }
 
-/// Lowercased title up to the first variant separator: a '-' with a space on
-/// either side. "Reshaped Idol - No Nip", "Reshaped Idol -No Nip" and
-/// "Reshaped Idol" all yield "reshaped idol".
-fn base_title(title: &str) -> String {
-    let lower = title.to_lowercase();
-    let chars: Vec<char> = lower.chars().collect();
-    for i in 0..chars.len() {
-        if chars[i] == '-' {
-            let space_before = i > 0 && chars[i - 1] == ' ';
-            let space_after = i + 1 < chars.len() && chars[i + 1] == ' ';
-            if space_before || space_after {
-                let head: String = chars[..i].iter().collect();
-                return head.trim().to_string();
-            }
+        // The mod now shows up disabled with its metadata and a way back
+        let mods = service.get_all_mods().unwrap();
+        let converted = mods.iter().find(|m| m.name == "Old Mod").unwrap();
+        assert!(!converted.enabled);
+        assert_eq!(converted.disabled_reason, Some(DisabledReason::UserAction));
+        assert_eq!(
+            converted.metadata.original_folder_path.as_deref(),
+            Some(Path::new("Skins").join("Old").to_str().unwrap())
+        );
+        assert!(service.load_metadata(&old_id).unwrap().is_none());
+        assert!(kept.exists());
+        assert!(service.detect_legacy_disabled_files().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_same_named_mods_round_trip_through_disabled_mods() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let size = |path: &Path| fs::metadata(path).unwrap().len();
+        let first = fx.write_mod(&fx.mods_dir(), "Mod_P", 8, Some(64));
+        let first_id = service.get_all_mods().unwrap()[0].id.clone();
+        let first = service.enable_mod(&first_id, false).unwrap();
+
+        // Another mod shipping the same file names lands on top of it
+        let second_pak = fx.write_mod(&fx.mods_dir(), "Mod_P", 16, Some(128));
+        let second_id = service
+            .get_all_mods()
+            .unwrap()
+            .into_iter()
+            .find(|m| m.enabled)
+            .unwrap()
+            .id;
+        let second = service.enable_mod(&second_id, false).unwrap();
+        let namespaced = first.file_path.parent().unwrap().join(&second_id);
+        assert_eq!(second.file_path, namespaced.join("Mod_P.pak"));
+        assert_eq!(second.metadata.collision_folder, Some(second_id));
+        assert_eq!((size(&first.file_path), size(&second.file_path)), (8, 16));
+        assert_eq!(service.get_all_mods().unwrap().len(), 2);
+
+        // Re-enabling puts each back without touching the other
+        let second = service.enable_mod(&second.id, true).unwrap();
+        assert_eq!(second.file_path, second_pak);
+        assert_eq!(second.metadata.collision_folder, None);
+        let first = service.enable_mod(&first.id, true).unwrap();
+        assert_ne!(first.file_path, second.file_path);
+        assert_eq!((size(&first.file_path), size(&second.file_path)), (8, 16));
+        let ucas = first.file_path.with_extension("ucas");
+        assert_eq!(size(&ucas), 64);
+    }
+
+    #[test]
+    fn test_rename_in_place_strategy() {
+        let fx = Fixture::new();
+        let service = fx
+            .service()
+            .with_disable_strategy(DisableStrategy::RenameInPlace);
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("CoolSkin_P", 10, Some(100)),
+                "Skins/Cool-Skin",
+                metadata("Cool Skin", ModCategory::Skins),
+            )
+            .unwrap();
+        let folder = fx.mods_dir().join("Skins").join("Cool-Skin");
+
+        // Renamed where it is, under the same ID
+        let disabled = service.enable_mod(&installed.id, false).unwrap();
+        assert_eq!(disabled.id, installed.id);
+        assert!(!disabled.enabled);
+        assert_eq!(disabled.file_path, folder.join("CoolSkin_P.pakdisabled"));
+        for name in ["CoolSkin_P.ucasdisabled", "CoolSkin_P.utocdisabled"] {
+            assert!(folder.join(name).exists());
         }
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods.len(), 1);
+        assert!(!mods[0].enabled);
+        assert_eq!(mods[0].file_size, 10 + 100 + 4);
+        let overview = service.get_disabled_mods_overview().unwrap();
+        assert_eq!(overview[0].mod_id.as_deref(), Some(installed.id.as_str()));
+
+        let enabled = service.enable_mod(&installed.id, true).unwrap();
+        assert_eq!(enabled.id, installed.id);
+        assert_eq!(enabled.file_path, folder.join("CoolSkin_P.pak"));
+
+        // Converting moves it into disabled-mods and back, metadata along
+        service.enable_mod(&installed.id, false).unwrap();
+        let report = service
+            .convert_disable_strategy(DisableStrategy::Move, false)
+            .unwrap();
+        assert_eq!(report.converted, 1);
+        let moved = fx
+            .disabled_dir()
+            .join("Skins")
+            .join("Cool-Skin")
+            .join("CoolSkin_P.pak");
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods[0].file_path, moved);
+        assert_eq!(mods[0].metadata.title, "Cool Skin");
+        assert!(!folder.join("CoolSkin_P.pakdisabled").exists());
+
+        let report = service
+            .convert_disable_strategy(DisableStrategy::RenameInPlace, false)
+            .unwrap();
+        assert_eq!(report.converted, 1);
+        let mods = service.get_all_mods().unwrap();
+        assert_eq!(mods[0].id, installed.id);
+        assert_eq!(mods[0].file_path, folder.join("CoolSkin_P.pakdisabled"));
+        assert_eq!(mods[0].metadata.title, "Cool Skin");
     }
-    lower.trim().to_string()
-}
 
-/// All single-segment space<->hyphen variants of a path. Used to reconstruct
-/// where a pak lived before a character folder was renamed or merged
-/// (e.g. "Black Widow" vs "Black-Widow").
-fn path_segment_variants(path: &Path) -> Vec<PathBuf> {
-    use std::path::Component;
+    #[test]
+    fn test_record_verified_game_build() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut ids = Vec::new();
+        for stem in ["Good_P", "Broken_P", "Off_P"] {
+            let installed = service
+                .install_mod_to_folder(&fx.source_mod(stem, 8, Some(64)), stem)
+                .unwrap();
+            ids.push(installed.id);
+        }
+        let off = service.enable_mod(&ids[2], false).unwrap();
+
+        let failed = HashSet::from([ids[1].clone()]);
+        let record = || service.record_verified_game_build("build-2", &failed);
+        assert_eq!(record().unwrap(), 1);
+        // Already recorded for this build
+        assert_eq!(record().unwrap(), 0);
+        let build_of = |id: &str| {
+            service
+                .load_metadata(id)
+                .unwrap()
+                .unwrap()
+                .last_verified_game_build
+        };
+        assert_eq!(build_of(&ids[0]).as_deref(), Some("build-2"));
+        assert_eq!(build_of(&ids[1]), None);
+        assert_eq!(build_of(&off.id), None);
+    }
 
-    let comps: Vec<Component> = path.components().collect();
-    let mut variants = Vec::new();
+    #[test]
+    fn test_reconcile_external_changes() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut md = metadata("Venom", ModCategory::Skins);
+        md.nexus_version = Some("1.0".to_string());
+        let installed = service
+            .install_mod_to_folder_with_metadata(
+                &fx.source_mod("Venom_P", 8, Some(64)),
+                "Skins/Venom",
+                md,
+            )
+            .unwrap();
+
+        // The first run only records what's there
+        assert!(service.reconcile_external_changes().unwrap().is_empty());
+        assert!(service.reconcile_external_changes().unwrap().is_empty());
+        let ucas = installed.file_path.with_extension("ucas");
+        let set_mtime = |seconds| {
+            filetime::set_file_mtime(&ucas, filetime::FileTime::from_unix_time(seconds, 0)).unwrap()
+        };
 
-    for (i, comp) in comps.iter().enumerate() {
-        let Component::Normal(os) = *comp else {
-            continue;
+        // Touched but identical: the fingerprint is refreshed, nothing flagged
+        set_mtime(1_700_000_000);
+        assert!(service.reconcile_external_changes().unwrap().is_empty());
+        let stored = service.load_metadata(&installed.id).unwrap().unwrap();
+        assert_eq!(stored.nexus_version.as_deref(), Some("1.0"));
+        assert_eq!(stored.externally_modified_at, None);
+
+        // Same size, different bytes, in a companion file
+        fs::write(&ucas, [1u8; 64]).unwrap();
+        set_mtime(1_700_000_100);
+        assert_eq!(
+            service.reconcile_external_changes().unwrap(),
+            [installed.id.clone()]
+        );
+        let stored = service.load_metadata(&installed.id).unwrap().unwrap();
+        assert_eq!(stored.externally_modified_at, Some(fixed_now()));
+        assert_eq!(stored.updated_at, fixed_now());
+        assert_eq!(stored.nexus_version, None);
+        // Flagged once, not on every run
+        assert!(service.reconcile_external_changes().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_library_summary() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        // Character mods are skins, the one without a character is UI
+        for (stem, title, character, installed) in [
+            ("HulkA_P", "Hulk A", Some(Character::Hulk), "2025-02-01"),
+            ("HulkB_P", "Hulk B", Some(Character::Hulk), "2025-07-01"),
+            ("ThorA_P", "Thor A", Some(Character::Thor), "2025-03-01"),
+            ("Hud_P", "Hud", None, "2025-01-01"),
+        ] {
+            let category = match character {
+                Some(_) => ModCategory::Skins,
+                None => ModCategory::UI,
+            };
+            let mut meta = metadata(title, category);
+            meta.character = character;
+            meta.install_date = date(&format!("{installed}T00:00:00Z"));
+            service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 10, Some(100)),
+                    title,
+                    meta,
+                )
+                .unwrap();
+        }
+        let id_of = |title: &str| {
+            service
+                .get_all_mods()
+                .unwrap()
+                .into_iter()
+                .find(|m| m.metadata.title == title)
+                .unwrap()
+                .id
         };
-        let Some(seg) = os.to_str() else { continue };
+        service.enable_mod(&id_of("Thor A"), false).unwrap();
 
-        for replaced in [seg.replace('-', " "), seg.replace(' ', "-")] {
-            if replaced == seg {
-                continue;
-            }
-            let mut variant = PathBuf::new();
-            for (j, comp) in comps.iter().enumerate() {
-                if i == j {
-                    variant.push(&replaced);
-                } else {
-                    variant.push(comp.as_os_str());
-                }
+        let pending = CachedCount {
+            count: 2,
+            stale: false,
+        };
+        let summary = service.library_summary(|_| pending).unwrap();
+        assert_eq!(summary.totals.total_mods, 4);
+        assert_eq!(summary.totals.enabled_mods, 3);
+        assert_eq!(summary.totals.disabled_mods, 1);
+        assert_eq!(summary.totals.total_size, 4 * 114);
+        assert_eq!(summary.categories[0].category, ModCategory::Skins);
+        assert_eq!(summary.categories[0].count, 3);
+        assert_eq!(summary.categories[0].disabled, 1);
+        let top: Vec<_> = summary
+            .top_characters
+            .iter()
+            .map(|c| (c.character.clone(), c.count))
+            .collect();
+        assert_eq!(
+            top,
+            vec![(Some(Character::Hulk), 2), (Some(Character::Thor), 1)]
+        );
+        // The full breakdown keeps the mod without a character, last
+        let characters = service.character_stats().unwrap();
+        assert_eq!(characters.len(), 3);
+        assert_eq!(characters[2].character, None);
+        assert_eq!(characters[2].count, 1);
+        assert_eq!(summary.missing_thumbnails, 4);
+        assert_eq!(summary.pending_updates, pending);
+        assert_eq!(summary.last_install, Some(date("2025-07-01T00:00:00Z")));
+
+        // No conflict scan yet, then a fresh one until the enabled set changes
+        assert!(summary.conflicts.stale);
+        service.detect_mod_conflicts(false).unwrap();
+        let summary = service.library_summary(|_| pending).unwrap();
+        assert_eq!(
+            summary.conflicts,
+            CachedCount {
+                count: 0,
+                stale: false
             }
-            variants.push(variant);
-        }
+        );
+        service.enable_mod(&id_of("Hud"), false).unwrap();
+        let summary = service.library_summary(|_| pending).unwrap();
+        assert!(summary.conflicts.stale);
     }
 
-    variants
-}
-
-// ===== UE5 IoStore (.utoc) asset reader =====
-//
-// We only need the list of game asset paths a mod overrides — that lives in the
-// uncompressed "directory index" of the .utoc, so no Oodle/decompression is
-// required. The directory index begins with a mount-point FString ("../../../"
-// for these mods); we locate it directly rather than summing the variable TOC
-// sections (some paks include a perfect-hash block that shifts the offset).
+    #[test]
+    fn test_conflict_severity() {
+        let assets = |paths: &[&str]| -> HashMap<String, String> {
+            paths
+                .iter()
+                .map(|p| (p.to_string(), String::new()))
+                .collect()
+        };
+        let mod_assets = HashMap::from([
+            ("a".to_string(), assets(&["Body.uasset", "Hair.uasset"])),
+            ("b".to_string(), assets(&["Body.uasset", "Face.uasset"])),
+            ("c".to_string(), assets(&["Body.uasset"])),
+        ]);
+        let shared = HashSet::from(["Body.uasset".to_string()]);
+
+        let ids = ["a".to_string(), "b".to_string()];
+        let severity = conflict_severity(&ids, &shared, &mod_assets);
+        assert_eq!(severity, ConflictSeverity::Partial);
+        // c changes nothing else, so it has no effect while a wins
+        let ids = ["a".to_string(), "c".to_string()];
+        let severity = conflict_severity(&ids, &shared, &mod_assets);
+        assert_eq!(severity, ConflictSeverity::Full);
+    }
 
-const NONE_ENTRY: u32 = 0xFFFF_FFFF;
+    #[test]
+    fn test_disabled_reason() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut ids = Vec::new();
+        for (stem, title) in [("Hand_P", "By Hand"), ("Safe_P", "Safe Mode")] {
+            let installed = service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 10, Some(100)),
+                    &format!("Skins/{title}"),
+                    metadata(title, ModCategory::Skins),
+                )
+                .unwrap();
+            assert_eq!(installed.disabled_reason, None);
+            ids.push(installed.id);
+        }
 
-fn read_u32(buf: &[u8], off: usize) -> Option<u32> {
-    buf.get(off..off + 4)
-        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
-}
+        let by_hand = service.enable_mod(&ids[0], false).unwrap();
+        assert_eq!(by_hand.disabled_reason, Some(DisabledReason::UserAction));
+        let safe = service
+            .set_mods_enabled_for(&ids[1..], false, &DisabledReason::SafeMode, |_, _| {})
+            .unwrap()
+            .remove(0)
+            .mod_info;
+        assert_eq!(safe.disabled_reason, Some(DisabledReason::SafeMode));
+
+        // Filterable by kind, and it survives a rescan
+        let query = ModQuery {
+            disabled_reason: Some("safeMode".to_string()),
+            ..Default::default()
+        };
+        let matched: Vec<String> = service
+            .get_all_mods()
+            .unwrap()
+            .into_iter()
+            .filter(|m| query.matches(m))
+            .map(|m| m.metadata.title)
+            .collect();
+        assert_eq!(matched, vec!["Safe Mode"]);
 
-fn read_i32(buf: &[u8], off: usize) -> Option<i32> {
-    buf.get(off..off + 4)
-        .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
-}
+        // Enabling clears it
+        let enabled = service.enable_mod(&safe.id, true).unwrap();
+        assert_eq!(enabled.disabled_reason, None);
+        assert_eq!(enabled.metadata.disabled_reason, None);
 
-/// Read an Unreal FString at `off`. Returns (string, new_offset).
-/// Positive length => UTF-8 (incl. trailing NUL); negative => UTF-16LE.
-fn read_fstring(buf: &[u8], off: usize) -> Option<(String, usize)> {
-    let n = read_i32(buf, off)?;
-    let mut off = off + 4;
-    if n == 0 {
-        return Some((String::new(), off));
+        let profile = DisabledReason::Profile {
+            profile_id: "raid".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&profile).unwrap(),
+            serde_json::json!({ "kind": "profile", "profileId": "raid" })
+        );
     }
-    if n < 0 {
-        let count = (-n) as usize;
-        let bytes = buf.get(off..off + count * 2)?;
-        off += count * 2;
-        let u16s: Vec<u16> = bytes
-            .chunks_exact(2)
-            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+
+    #[test]
+    fn test_find_replace_metadata() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        for (stem, title) in [
+            ("Storm_P", "[CoolPack] Storm"),
+            ("Hulk_P", "[CoolPack] Hulk"),
+            ("Venom_P", "Venom"),
+        ] {
+            let mut meta = metadata(title, ModCategory::Skins);
+            meta.author = Some("unknown".to_string());
+            service
+                .install_mod_to_folder_with_metadata(&fx.source_mod(stem, 10, None), "", meta)
+                .unwrap();
+        }
+        service.organize_loose_mods(None).unwrap();
+        let titles = || {
+            let mut titles: Vec<String> = service
+                .get_all_mods()
+                .unwrap()
+                .into_iter()
+                .map(|m| m.metadata.title)
+                .collect();
+            titles.sort();
+            titles
+        };
+        let strip = |dry_run| {
+            service.find_replace_metadata(
+                MetadataTextField::Title,
+                r"^\[CoolPack\]\s*",
+                "",
+                None,
+                true,
+                dry_run,
+            )
+        };
+
+        // A dry run shows the change without making it
+        let preview = strip(true).unwrap();
+        assert_eq!(preview.len(), 2);
+        assert!(preview
+            .iter()
+            .any(|r| r.before == "[CoolPack] Storm" && r.after == "Storm"));
+        assert_eq!(titles(), ["Venom", "[CoolPack] Hulk", "[CoolPack] Storm"]);
+
+        assert_eq!(strip(false).unwrap().len(), 2);
+        assert_eq!(titles(), ["Hulk", "Storm", "Venom"]);
+        // Renamed mods end up in the folder their new title implies
+        for mod_info in service.get_all_mods().unwrap() {
+            assert_eq!(
+                mod_info.file_path.parent().unwrap(),
+                service.expected_folder_for(&mod_info.metadata)
+            );
+        }
+
+        // Replacing an author with nothing clears it, within the filter only
+        let filter = ModQuery {
+            search: Some("venom".to_string()),
+            ..Default::default()
+        };
+        let cleared = service
+            .find_replace_metadata(
+                MetadataTextField::Author,
+                "unknown",
+                "",
+                Some(&filter),
+                false,
+                false,
+            )
+            .unwrap();
+        assert_eq!(cleared.len(), 1);
+        let authors: Vec<Option<String>> = service
+            .get_all_mods()
+            .unwrap()
+            .into_iter()
+            .map(|m| m.metadata.author)
             .collect();
-        let s = String::from_utf16_lossy(&u16s)
-            .trim_end_matches('\u{0}')
-            .to_string();
-        Some((s, off))
-    } else {
-        let count = n as usize;
-        let bytes = buf.get(off..off + count)?;
-        off += count;
-        let s = String::from_utf8_lossy(bytes)
-            .trim_end_matches('\u{0}')
-            .to_string();
-        Some((s, off))
+        assert_eq!(authors.iter().filter(|a| a.is_none()).count(), 1);
+
+        assert!(service
+            .find_replace_metadata(MetadataTextField::Title, "(", "", None, true, true)
+            .unwrap_err()
+            .contains("Invalid pattern"));
     }
-}
 
-/// Parse the FIoDirectoryIndexResource starting at `start`, returning every
-/// file it lists as (full path, chunk index). The chunk index (the entry's
-/// user_data) points into the TOC's chunk tables, letting callers look up the
-/// per-chunk content hash.
-fn parse_directory_index(buf: &[u8], start: usize) -> Option<Vec<(String, u32)>> {
-    let (_mount, mut off) = read_fstring(buf, start)?;
+    #[test]
+    fn test_query_mods_filters_sorts_and_pages() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        for (stem, title, category, spider, tag) in [
+            ("Black_P", "Black Suit", ModCategory::Skins, true, "dark"),
+            ("Miles_P", "Miles Swap", ModCategory::Skins, true, "red"),
+            ("Hud_P", "Clean HUD", ModCategory::UI, false, "minimal"),
+        ] {
+            let mut metadata = metadata(title, category);
+            metadata.character = spider.then_some(Character::SpiderMan);
+            metadata.tags = vec![tag.to_string()];
+            metadata.is_nsfw = stem == "Miles_P";
+            service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 8, None),
+                    &format!("Mods/{stem}"),
+                    metadata,
+                )
+                .unwrap();
+        }
+        let mods = service.get_all_mods().unwrap();
+        let sort = SortSpec {
+            key: SortKey::Title,
+            direction: SortDirection::Asc,
+        };
+        let titles = |query: ModQuery, offset: usize, limit: usize| {
+            let page = page_mods(mods.clone(), Some(&query), Some(&sort), offset, limit);
+            let titles: Vec<String> = page.mods.into_iter().map(|m| m.metadata.title).collect();
+            (titles, page.total)
+        };
+        let search = |text: &str| ModQuery {
+            search: Some(text.to_string()),
+            ..Default::default()
+        };
 
-    let ndir = read_u32(buf, off)? as usize;
-    off += 4;
-    // dir entries: name, first_child, next_sibling, first_file (4 x u32)
-    let mut dirs: Vec<[u32; 4]> = Vec::with_capacity(ndir);
-    for _ in 0..ndir {
-        let e = [
-            read_u32(buf, off)?,
-            read_u32(buf, off + 4)?,
-            read_u32(buf, off + 8)?,
-            read_u32(buf, off + 12)?,
-        ];
-        off += 16;
-        dirs.push(e);
+        // Matches the character, ignoring the hyphen, one page at a time
+        let (page, total) = titles(search("spider man"), 1, 1);
+        assert_eq!((page, total), (vec!["Miles Swap".to_string()], 2));
+        assert_eq!(titles(search("BLACK-suit"), 0, 10).1, 1);
+        assert_eq!(titles(search("minimal"), 0, 10).0, ["Clean HUD"]);
+        let safe_skins = ModQuery {
+            category: Some(ModCategory::Skins),
+            nsfw: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(titles(safe_skins, 0, 10).0, ["Black Suit"]);
+        let favorites = ModQuery {
+            favorite: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(titles(favorites, 0, 10), (Vec::new(), 0));
     }
 
-    let nfile = read_u32(buf, off)? as usize;
-    off += 4;
-    // file entries: name, next_file, user_data (3 x u32)
-    let mut files: Vec<[u32; 3]> = Vec::with_capacity(nfile);
-    for _ in 0..nfile {
-        let e = [
-            read_u32(buf, off)?,
-            read_u32(buf, off + 4)?,
-            read_u32(buf, off + 8)?,
-        ];
-        off += 12;
-        files.push(e);
-    }
+    #[test]
+    fn test_reassign_category() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        for (stem, folder, title) in [
+            ("OddPak_P", "Skins/Odd-Pak", "Odd Pak"),
+            ("WeirdPak_P", "Skins/Weird-Pak", "Weird Pak"),
+            ("RealSkin_P", "Skins/Real-Skin", "Real Skin"),
+        ] {
+            service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 10, Some(100)),
+                    folder,
+                    metadata(title, ModCategory::Skins),
+                )
+                .unwrap();
+        }
+        let find = |title: &str| {
+            service
+                .get_all_mods()
+                .unwrap()
+                .into_iter()
+                .find(|m| m.metadata.title == title)
+                .unwrap()
+        };
+        service.enable_mod(&find("Weird Pak").id, false).unwrap();
 
-    let nstr = read_u32(buf, off)? as usize;
-    off += 4;
-    let mut strings: Vec<String> = Vec::with_capacity(nstr);
-    for _ in 0..nstr {
-        let (s, n) = read_fstring(buf, off)?;
-        off = n;
-        strings.push(s);
+        let filter = ModQuery {
+            search: Some("pak".to_string()),
+            ..Default::default()
+        };
+        let moved = service
+            .reassign_category(&ModCategory::Skins, &ModCategory::Other, Some(&filter))
+            .unwrap();
+        assert_eq!(moved, 2);
+
+        // Enabled mods move under the new category folder
+        let odd = find("Odd Pak");
+        assert_eq!(odd.metadata.category, ModCategory::Other);
+        assert_eq!(
+            odd.file_path,
+            fx.mods_dir().join("Other/Odd-Pak/OddPak_P.pak")
+        );
+        assert!(!fx.mods_dir().join("Skins").join("Odd-Pak").exists());
+
+        // Disabled mods stay put but come back under the new category
+        let weird = find("Weird Pak");
+        assert!(!weird.enabled);
+        assert!(weird.file_path.starts_with(fx.disabled_dir()));
+        assert_eq!(weird.metadata.category, ModCategory::Other);
+        assert_eq!(
+            weird.metadata.original_folder_path.as_deref(),
+            Some("Other/Weird-Pak")
+        );
+        let weird = service.enable_mod(&weird.id, true).unwrap();
+        assert!(weird.file_path.starts_with(fx.mods_dir().join("Other")));
+
+        // Mods outside the filter keep their category
+        assert_eq!(find("Real Skin").metadata.category, ModCategory::Skins);
+        assert_eq!(
+            service
+                .reassign_category(&ModCategory::Other, &ModCategory::Other, None)
+                .unwrap(),
+            0
+        );
     }
 
-    let name = |i: u32| -> &str {
-        if i != NONE_ENTRY && (i as usize) < strings.len() {
-            &strings[i as usize]
-        } else {
-            ""
+    #[test]
+    fn test_assign_character() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut ids = Vec::new();
+        for (stem, folder, title) in [
+            ("First_P", "Skins/First", "First"),
+            ("Second_P", "Skins/Second", "Second"),
+            ("Shelved_P", "Skins/Shelved", "Shelved"),
+        ] {
+            let installed = service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 10, Some(100)),
+                    folder,
+                    metadata(title, ModCategory::Skins),
+                )
+                .unwrap();
+            ids.push(installed.id);
         }
-    };
+        ids[2] = service.enable_mod(&ids[2], false).unwrap().id;
+
+        // Enabled mods can't move while the game runs
+        match service.assign_character(&ids, &Character::Psylocke, None, true) {
+            Err(AssignCharacterError::GameRunning { mods }) => assert_eq!(
+                mods.iter().map(|m| m.title.as_str()).collect::<Vec<_>>(),
+                ["First", "Second"]
+            ),
+            other => panic!("expected GameRunning, got {:?}", other),
+        }
+        assert!(fx.mods_dir().join("Skins/First").exists());
 
-    let mut out: Vec<(String, u32)> = Vec::new();
-    if dirs.is_empty() {
-        return Some(out);
+        let assigned = service
+            .assign_character(&ids, &Character::Psylocke, Some("demon"), false)
+            .unwrap();
+        assert_eq!(
+            assigned
+                .iter()
+                .map(|m| m.previous_id.as_str())
+                .collect::<Vec<_>>(),
+            ids.iter().map(String::as_str).collect::<Vec<_>>()
+        );
+        for moved in &assigned[..2] {
+            let info = &moved.mod_info;
+            assert_eq!(info.metadata.character, Some(Character::Psylocke));
+            assert_eq!(info.metadata.costume.as_deref(), Some("demon"));
+            assert_eq!(
+                info.file_path,
+                fx.mods_dir().join(format!(
+                    "Skins/Psylocke/{}-demon/{}_P.pak",
+                    info.metadata.title, info.metadata.title
+                ))
+            );
+            assert_ne!(info.id, moved.previous_id);
+        }
+        assert!(!fx.mods_dir().join("Skins/First").exists());
+
+        // The disabled mod stays put and comes back in the new folder
+        let shelved = &assigned[2];
+        assert_eq!(shelved.mod_info.id, shelved.previous_id);
+        assert_eq!(
+            shelved.mod_info.metadata.original_folder_path.as_deref(),
+            Some("Skins/Psylocke/Shelved-demon")
+        );
+        let shelved = service.enable_mod(&shelved.mod_info.id, true).unwrap();
+        assert_eq!(
+            shelved.file_path,
+            fx.mods_dir()
+                .join("Skins/Psylocke/Shelved-demon/Shelved_P.pak")
+        );
     }
 
-    // Iterative depth-first walk to avoid recursion limits / cycles.
-    // stack holds (dir_index, parent_path).
-    let mut stack: Vec<(u32, String)> = vec![(0, String::new())];
-    let mut guard = 0usize;
-    while let Some((di, prefix)) = stack.pop() {
-        guard += 1;
-        if di == NONE_ENTRY || di as usize >= dirs.len() || guard > 1_000_000 {
-            continue;
+    #[test]
+    fn test_layout_drift_apply_and_accept() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        for (stem, folder, title) in [
+            ("Moved_P", "Skins/Moved-By-Hand", "Moved"),
+            ("Kept_P", "Skins/My-Folder", "Kept"),
+            ("Fine_P", "Skins/Fine", "Fine"),
+        ] {
+            service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 10, Some(100)),
+                    folder,
+                    metadata(title, ModCategory::Skins),
+                )
+                .unwrap();
         }
-        let d = dirs[di as usize];
-        let nm = name(d[0]);
-        let path = if nm.is_empty() {
-            prefix.clone()
-        } else {
-            format!("{}/{}", prefix.trim_end_matches('/'), nm)
+        let find = |title: &str| {
+            service
+                .get_all_mods()
+                .unwrap()
+                .into_iter()
+                .find(|m| m.metadata.title == title)
+                .unwrap()
         };
 
-        // files in this directory: (name, next_file, user_data=chunk index)
-        let mut fi = d[3];
-        while fi != NONE_ENTRY && (fi as usize) < files.len() {
-            let fe = files[fi as usize];
-            let fname = name(fe[0]);
-            if !fname.is_empty() {
-                out.push((format!("{}/{}", path.trim_end_matches('/'), fname), fe[2]));
-            }
-            fi = fe[1];
-        }
+        let drift = service.get_layout_drift().unwrap();
+        assert_eq!(drift.len(), 2);
+        let moved = drift.iter().find(|d| d.name == "Moved").unwrap();
+        assert_eq!(
+            moved.current_folder,
+            fx.mods_dir().join("Skins/Moved-By-Hand")
+        );
+        assert_eq!(moved.expected_folder, fx.mods_dir().join("Skins/Moved"));
+
+        // Accepted mods stay put, even when their metadata is edited
+        assert_eq!(service.accept_layout(&[find("Kept").id]).unwrap(), 1);
+        let mut kept = find("Kept").metadata;
+        kept.author = Some("Someone".to_string());
+        let kept = service.update_metadata(&find("Kept").id, kept).unwrap();
+        let kept_folder = fx.mods_dir().join("Skins/My-Folder");
+        assert!(kept.file_path.starts_with(kept_folder));
+        assert_eq!(service.get_layout_drift().unwrap().len(), 1);
+
+        let applied = service
+            .apply_layout(&[moved.mod_id.clone(), find("Fine").id])
+            .unwrap();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].previous_id, moved.mod_id);
+        assert_eq!(
+            applied[0].mod_info.file_path,
+            fx.mods_dir().join("Skins/Moved/Moved_P.pak")
+        );
+        assert!(service.get_layout_drift().unwrap().is_empty());
+    }
 
-        // child directories (push siblings + this child)
-        let mut c = d[1];
-        while c != NONE_ENTRY && (c as usize) < dirs.len() {
-            stack.push((c, path.clone()));
-            c = dirs[c as usize][2];
+    #[test]
+    fn test_disabled_mods_overview_and_purge() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let mut ids = Vec::new();
+        for (stem, title) in [("Old_P", "Old Mod"), ("Recent_P", "Recent Mod")] {
+            let installed = service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 10, Some(100)),
+                    &format!("Skins/{title}"),
+                    metadata(title, ModCategory::Skins),
+                )
+                .unwrap();
+            let disabled = service.enable_mod(&installed.id, false).unwrap();
+            assert_eq!(disabled.metadata.disabled_at, Some(fixed_now()));
+            ids.push(disabled.id);
         }
+        // Pretend the first one was disabled long ago
+        let mut old = service.load_metadata(&ids[0]).unwrap().unwrap();
+        old.disabled_at = Some(date("2025-01-01T00:00:00Z"));
+        service.save_metadata(&ids[0], &old).unwrap();
+        // A companion whose pak is gone
+        write_file(&fx.disabled_dir().join("Stray").join("Stray_P.utoc"), 4);
+
+        let overview = service.get_disabled_mods_overview().unwrap();
+        let titles: Vec<&str> = overview.iter().map(|e| e.title.as_str()).collect();
+        assert_eq!(titles, vec!["Old Mod", "Recent Mod", "Stray"]);
+        assert_eq!(overview[0].mod_id.as_deref(), Some(ids[0].as_str()));
+        assert_eq!(overview[0].disabled_days, Some(165));
+        assert_eq!(overview[0].size, 114);
+        assert!(overview[0].has_metadata && !overview[0].disabled_since_estimated);
+        assert_eq!(
+            overview[0].disabled_reason,
+            Some(DisabledReason::UserAction)
+        );
+        assert_eq!(overview[1].disabled_days, Some(0));
+        assert_eq!(overview[2].mod_id, None);
+        assert_eq!(overview[2].disabled_days, None);
+        assert!(!overview[2].has_metadata);
+
+        // A dry run deletes nothing
+        let report = service.purge_disabled_mods(30, true).unwrap();
+        assert_eq!((report.purged.len(), report.bytes_reclaimed), (1, 114));
+        assert!(overview[0].files.iter().all(|f| f.exists()));
+
+        let thumb = fx.metadata_dir.join(format!("{}_thumbnail.png", ids[0]));
+        fs::write(&thumb, b"png").unwrap();
+        let report = service.purge_disabled_mods(30, false).unwrap();
+        assert_eq!(report.bytes_reclaimed, 114);
+        assert!(overview[0].files.iter().all(|f| !f.exists()));
+        assert!(!fx.disabled_dir().join("Skins").join("Old Mod").exists());
+        assert!(service.load_metadata(&ids[0]).unwrap().is_none());
+        assert!(!thumb.exists());
+        // The recent mod and the undated stray file stay
+        let left: Vec<String> = service
+            .get_disabled_mods_overview()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.title)
+            .collect();
+        assert_eq!(left, vec!["Recent Mod", "Stray"]);
     }
 
-    Some(out)
-}
+    fn fstring(text: &str) -> Vec<u8> {
+        let mut out = ((text.len() + 1) as i32).to_le_bytes().to_vec();
+        out.extend_from_slice(text.as_bytes());
+        out.push(0);
+        out
+    }
 
-/// Read the assets a mod's `.utoc` overrides, as a map of the `.uasset` path
-/// to a content hash. The hash combines the TOC chunk hashes of the asset AND
-/// its sibling payload files (`.ubulk`/`.uexp` share the same stem), so two
-/// mods shipping byte-identical copies of an asset produce the same value.
-fn read_utoc_assets(path: &Path) -> Result<HashMap<String, String>, String> {
-    let buf = fs::read(path).map_err(|e| format!("read utoc: {}", e))?;
-    if buf.len() < 0x90 || &buf[0..16] != b"-==--==--==--==-" {
-        return Err("not a TOC file".into());
+    /// A pak with some data, an index starting with `mount_point`, and a
+    /// footer pointing at it
+    fn write_pak(path: &Path, version: u32, mount_point: &str) {
+        let mut buf = vec![0u8; 32];
+        let index_offset = buf.len() as u64;
+        let index = fstring(mount_point);
+        buf.extend_from_slice(&index);
+        buf.extend_from_slice(&[0u8; 17]);
+        buf.extend_from_slice(&PAK_MAGIC.to_le_bytes());
+        buf.extend_from_slice(&version.to_le_bytes());
+        buf.extend_from_slice(&index_offset.to_le_bytes());
+        buf.extend_from_slice(&(index.len() as u64).to_le_bytes());
+        buf.extend_from_slice(&[0u8; 20]);
+        fs::write(path, buf).unwrap();
     }
-    // header: magic[16] version u8 reserved u8 reserved u16, then u32 fields:
-    // header_size, entry_count, block_count, block_size, method_count,
-    // method_len, compression_block_size, dir_index_size, ...
-    let dir_index_size = read_u32(&buf, 16 + 4 + 4 * 7)
-        .ok_or("truncated header")? as usize;
 
-    // Find the mount point string "../../../" and back up 4 bytes to its
-    // length prefix. We locate the directory index this way rather than
-    // summing section sizes (some paks include a perfect-hash block that
-    // shifts the offset).
-    let needle = b"../../../";
-    let mloc = buf
-        .windows(needle.len())
-        .position(|w| w == needle)
-        .ok_or("directory index not found")?;
-    if mloc < 4 {
-        return Err("malformed directory index".into());
+    fn write_utoc(path: &Path, version: u8, mount_point: &str) {
+        let mut buf = b"-==--==--==--==-".to_vec();
+        buf.push(version);
+        buf.resize(0x90, 0);
+        buf.extend_from_slice(&fstring(mount_point));
+        fs::write(path, buf).unwrap();
     }
-    let start = mloc - 4;
 
-    let files = parse_directory_index(&buf, start)
-        .ok_or_else(|| String::from("failed to parse directory index"))?;
+    #[test]
+    fn test_common_mount_point_needs_a_clear_majority() {
+        let game = "../../../Marvel/Content/";
+        assert_eq!(
+            common_mount_point([game, "../../../marvel/content", game, "../../../Engine/"]),
+            Some("../../../marvel/content/".to_string())
+        );
+        // Too few samples, or no value shared by three quarters of them
+        assert_eq!(common_mount_point([game, game]), None);
+        assert_eq!(
+            common_mount_point([game, game, "../../../A/", "../../../B/"]),
+            None
+        );
+    }
 
-    // Chunk metas follow the directory index: one FIoStoreTocEntryMeta per
-    // chunk = 32-byte content hash + 1 flag byte (33 bytes each).
-    let metas_start = start + dir_index_size;
-    let chunk_hash_hex = |idx: u32| -> Option<String> {
-        let s = metas_start + (idx as usize) * 33;
-        buf.get(s..s + 32)
-            .map(|h| h.iter().map(|b| format!("{b:02x}")).collect())
-    };
+    #[test]
+    fn test_verify_mods_flags_unusual_mount_points() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        for stem in ["one_P", "two_P", "three_P", "old_P"] {
+            let pak = fx.installed_mod(&format!("Skins/{stem}"), stem);
+            let mount_point = if stem == "old_P" {
+                "../../../Engine/"
+            } else {
+                "../../../Marvel/Content/"
+            };
+            write_pak(&pak, 11, "../../../");
+            write_utoc(&pak.with_extension("utoc"), 8, mount_point);
+        }
+        // Unreadable files are reported, not fatal
+        fx.installed_mod("Skins/Junk", "junk_P");
+
+        let mods = service.get_all_mods().unwrap();
+        let by_file = |name: &str| mods.iter().find(|m| m.original_file_name == name).unwrap();
+
+        let issues = service.verify_mods().unwrap();
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.kind, ModVerificationKind::PossiblyOutdated);
+        assert_eq!(issue.mod_id, by_file("old_P.pak").id);
+        assert_eq!(issue.info.pak_version, Some(11));
+        assert_eq!(issue.info.pak_mount_point.as_deref(), Some("../../../"));
+        assert_eq!(issue.info.toc_version, Some(8));
+        assert_eq!(issue.info.mount_point(), Some("../../../Engine/"));
+        assert_eq!(
+            issue.info.common_mount_point.as_deref(),
+            Some("../../../marvel/content/")
+        );
 
-    // Group files by stem so payload siblings feed into the asset's hash.
-    let mut stem_parts: HashMap<String, Vec<(String, String)>> = HashMap::new();
-    for (fpath, chunk_idx) in &files {
-        let (stem, ext) = match fpath.rsplit_once('.') {
-            Some((s, e)) => (s.to_string(), e.to_string()),
-            None => (fpath.clone(), String::new()),
-        };
-        let h = chunk_hash_hex(*chunk_idx).unwrap_or_default();
-        stem_parts.entry(stem).or_default().push((ext, h));
+        let info = service
+            .get_pak_technical_info(&by_file("junk_P.pak").id)
+            .unwrap();
+        assert_eq!(info.pak_version, None);
+        assert_eq!(info.errors.len(), 2);
+        assert!(!info.mount_point_outlier);
     }
 
-    let mut out: HashMap<String, String> = HashMap::new();
-    for (stem, mut parts) in stem_parts {
-        if !parts.iter().any(|(e, _)| e == "uasset") {
-            continue;
-        }
-        parts.sort();
-        let combined: String = parts
-            .iter()
-            .map(|(e, h)| format!("{e}:{h}"))
-            .collect::<Vec<_>>()
-            .join("|");
-        out.insert(format!("{stem}.uasset"), combined);
+    #[test]
+    fn test_truncated_paks_fail_validation() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let pak = fx.source_dir.join("Hulk_P.pak");
+        let reason = |pak: &Path| validate_pak_file(pak).unwrap_err();
+        write_file(&pak, 0);
+        assert!(reason(&pak).ends_with("(empty file)"));
+        write_file(&pak, 100);
+        assert_eq!(
+            reason(&pak),
+            "File appears to be a corrupted or incomplete .pak (missing magic)"
+        );
+        write_pak(&pak, 11, "../../../");
+        validate_pak_file(&pak).unwrap();
+        let whole = fs::read(&pak).unwrap();
+        fs::write(&pak, &whole[..whole.len() - 30]).unwrap();
+        assert!(reason(&pak).ends_with("(truncated footer)"));
+
+        // A .utoc without its .ucas is only a warning
+        let installed = fx.installed_mod("Skins/Venom", "Venom_P");
+        write_pak(&installed, 11, "../../../");
+        fs::remove_file(installed.with_extension("ucas")).unwrap();
+        fx.installed_mod("Skins/Junk", "Junk_P");
+        let failed = service.validate_mods().unwrap();
+        assert_eq!(failed.len(), 2);
+        let venom = failed.iter().find(|v| v.pak_path == installed).unwrap();
+        assert_eq!(venom.error, None);
+        assert_eq!(venom.warnings.len(), 1);
+        let junk = failed.iter().find(|v| v.pak_path != installed).unwrap();
+        assert!(junk.error.is_some());
+        assert!(service.validate_mod(&venom.mod_id).unwrap().error.is_none());
     }
-    Ok(out)
-}
 
-/// Map an asset file stem to a human-readable conflict "kind" for the UI.
-fn classify_asset(stem: &str) -> String {
-    let s = stem.to_lowercase();
-    if s.starts_with("sk_") || s.contains("_skeleton") || s.contains("_lobby") {
-        "Body Mesh".to_string()
-    } else if s.contains("_body_") || s.ends_with("_body") || s.contains("body_0") {
-        "Body Skin".to_string()
-    } else if s.contains("_hair") {
-        "Hair".to_string()
-    } else if s.contains("_head") || s.contains("_face") || s.contains("_eyes") {
-        "Face".to_string()
-    } else if s.contains("_equip") || s.contains("_custom") || s.contains("_cloth") {
-        "Outfit".to_string()
-    } else if s.contains("_weapon") || s.contains("_rifle") || s.contains("_sword") {
-        "Weapon".to_string()
-    } else if s.starts_with("mi_") || s.starts_with("m_") {
-        "Material".to_string()
-    } else {
-        "Other".to_string()
+    #[test]
+    fn test_consolidate_thumbnail_storage() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        service
+            .install_mod_to_folder(&fx.source_mod("Hulk_P", 8, None), "Hulk")
+            .unwrap();
+        let venom = service
+            .install_mod_to_folder(&fx.source_mod("VenomClassic_P", 8, None), "Venom")
+            .unwrap();
+
+        let app_data = fx.metadata_dir.parent().unwrap();
+        let legacy = app_data.join("thumbnails");
+        fs::create_dir_all(&legacy).unwrap();
+        let img = image::RgbImage::new(32, 18);
+        // Named after the pak without _P, and after the mod ID
+        img.save(legacy.join("Hulk.jpg")).unwrap();
+        img.save(legacy.join(format!("{}.png", venom.id))).unwrap();
+        fs::write(legacy.join("Unknown.webp"), b"not a mod").unwrap();
+
+        let report = service.consolidate_thumbnail_storage().unwrap();
+        assert_eq!(report.migrated, 2);
+        assert_eq!(
+            report.unclaimed,
+            vec![app_data.join("unclaimed-thumbnails").join("Unknown.webp")]
+        );
+        assert!(!legacy.exists());
+
+        let mods = service.get_all_mods().unwrap();
+        for mod_info in &mods {
+            let expected = fx
+                .metadata_dir
+                .join(format!("{}_thumbnail.png", mod_info.id));
+            assert_eq!(mod_info.thumbnail_path.as_ref(), Some(&expected));
+        }
+        // Nothing left to do once the legacy folder is gone
+        let report = service.consolidate_thumbnail_storage().unwrap();
+        assert_eq!(report.migrated, 0);
+        assert!(report.unclaimed.is_empty());
     }
 }
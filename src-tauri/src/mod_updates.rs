@@ -0,0 +1,470 @@
+//! Daily background check for newer versions of mods installed from Nexus.
+//! Results are kept in `mod-updates.json` so the UI can list them lazily,
+//! and versions the user chose to skip stay muted across checks.
+
+use chrono::{DateTime, Duration as ChronoDuration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::i18n::{t, tn};
+use crate::notifications::{self, NotificationKind};
+use crate::types::{CachedCount, ModInfo};
+
+/// Name of the state file in the app data folder
+pub const STATE_FILE: &str = "mod-updates.json";
+
+const NEXUS_API_BASE: &str = "https://api.nexusmods.com/v1";
+pub(crate) const GAME_DOMAIN: &str = "marvelrivals";
+
+/// Minimum time between two checks
+const CHECK_INTERVAL: ChronoDuration = ChronoDuration::hours(24);
+
+/// How often the scheduler wakes to see whether a check is due
+const TICK: Duration = Duration::from_secs(15 * 60);
+
+/// Requests left in the hourly/daily Nexus quota that the check never
+/// touches, so browsing and downloads keep working afterwards
+const RATE_LIMIT_RESERVE: u64 = 20;
+
+/// Pause between requests, to stay well clear of burst limits
+const REQUEST_SPACING: Duration = Duration::from_millis(250);
+
+/// A mod with a newer version on Nexus
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModUpdate {
+    pub mod_id: String,
+    pub title: String,
+    pub nexus_mod_id: i32,
+    pub installed_version: Option<String>,
+    pub latest_version: String,
+}
+
+/// A version the user chose to skip. Keyed by the Nexus ID, since local mod
+/// IDs change whenever a mod is moved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DismissedUpdate {
+    nexus_mod_id: i32,
+    version: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct UpdateState {
+    pending: Vec<ModUpdate>,
+    dismissed: Vec<DismissedUpdate>,
+}
+
+impl UpdateState {
+    fn is_dismissed(&self, nexus_mod_id: i32, version: &str) -> bool {
+        self.dismissed
+            .iter()
+            .any(|d| d.nexus_mod_id == nexus_mod_id && same_version(&d.version, version))
+    }
+}
+
+/// `mod-updates.json` as last read or written. `None` until first use.
+static STATE: Mutex<Option<UpdateState>> = Mutex::new(None);
+
+/// The fields of Nexus' mod endpoint the check needs
+#[derive(Debug, Deserialize)]
+struct NexusMod {
+    version: Option<String>,
+    updated_timestamp: Option<i64>,
+}
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::app_data_dir(app)?.join(STATE_FILE))
+}
+
+fn read_state(path: &Path) -> Result<UpdateState, String> {
+    if !path.exists() {
+        return Ok(UpdateState::default());
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read mod updates: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse mod updates: {}", e))
+}
+
+fn write_state(path: &Path, state: &UpdateState) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize mod updates: {}", e))?;
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, json).map_err(|e| format!("Failed to write mod updates: {}", e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("Failed to finalize mod updates: {}", e))
+}
+
+/// Run `f` on the cached state and write it back
+fn update_state<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&mut UpdateState) -> Result<T, String>,
+) -> Result<T, String> {
+    let path = state_path(app)?;
+    let mut cached = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    let mut state = match cached.take() {
+        Some(state) => state,
+        None => read_state(&path)?,
+    };
+    let result = f(&mut state);
+    let written = write_state(&path, &state);
+    *cached = Some(state);
+    written?;
+    result
+}
+
+/// Run `f` on the cached state without writing it back
+fn read_cached<T>(app: &AppHandle, f: impl FnOnce(&UpdateState) -> T) -> Result<T, String> {
+    let path = state_path(app)?;
+    let mut cached = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    let state = match cached.take() {
+        Some(state) => state,
+        None => read_state(&path)?,
+    };
+    let result = f(&state);
+    *cached = Some(state);
+    Ok(result)
+}
+
+/// Versions are free-form on Nexus; ignore case, whitespace and a leading "v"
+fn same_version(a: &str, b: &str) -> bool {
+    let normalize = |v: &str| v.trim().trim_start_matches(['v', 'V']).to_lowercase();
+    normalize(a) == normalize(b)
+}
+
+/// Whether the Nexus listing is newer than what is installed. Without a
+/// recorded version, fall back to comparing the upload time with the
+/// install date.
+fn has_update(installed: &ModInfo, latest: &NexusMod) -> Option<String> {
+    let latest_version = latest.version.as_deref()?.trim();
+    if latest_version.is_empty() {
+        return None;
+    }
+    let newer = match installed.metadata.nexus_version.as_deref() {
+        Some(version) => !same_version(version, latest_version),
+        None => latest
+            .updated_timestamp
+            .and_then(|ts| Utc.timestamp_opt(ts, 0).single())
+            .is_some_and(|updated| updated > installed.metadata.install_date),
+    };
+    newer.then(|| latest_version.to_string())
+}
+
+/// The update `latest` offers over the installed mod, if it's newer
+fn update_for(installed: &ModInfo, nexus_mod_id: i32, latest: &NexusMod) -> Option<ModUpdate> {
+    has_update(installed, latest).map(|version| ModUpdate {
+        mod_id: installed.id.clone(),
+        title: installed.metadata.title.clone(),
+        nexus_mod_id,
+        installed_version: installed.metadata.nexus_version.clone(),
+        latest_version: version,
+    })
+}
+
+fn is_due(last_check: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    last_check.is_none_or(|last| now - last >= CHECK_INTERVAL)
+}
+
+/// The lower of the hourly and daily quota left, if Nexus reported them
+fn remaining_quota(response: &reqwest::Response) -> Option<u64> {
+    ["x-rl-hourly-remaining", "x-rl-daily-remaining"]
+        .iter()
+        .filter_map(|header| response.headers().get(*header)?.to_str().ok()?.parse().ok())
+        .min()
+}
+
+/// Look up every Nexus-linked mod, record the ones with a newer version, and
+/// send one digest notification. Returns the number of pending updates.
+async fn run_check(app: &AppHandle, api_key: &str) -> Result<usize, String> {
+    let mods = crate::get_mod_service(app)?.get_all_mods()?;
+    // One request per Nexus page, however many local mods came from it
+    let mut by_nexus_id: BTreeMap<i32, &ModInfo> = BTreeMap::new();
+    for mod_info in &mods {
+        if let Some(nexus_id) = mod_info.metadata.nexus_mod_id {
+            by_nexus_id.entry(nexus_id).or_insert(mod_info);
+        }
+    }
+    log::info!("[mod-updates] Checking {} Nexus mod(s)", by_nexus_id.len());
+
+    let client = reqwest::Client::new();
+    let mut found = Vec::new();
+    let mut checked = HashSet::new();
+    for (nexus_id, mod_info) in by_nexus_id {
+        let endpoint = format!("/games/{GAME_DOMAIN}/mods/{nexus_id}.json");
+        if crate::demo::is_enabled() {
+            checked.insert(nexus_id);
+            let latest = crate::demo::nexus_response(&endpoint)
+                .and_then(|listing| serde_json::from_value(listing).map_err(|e| e.to_string()));
+            match latest {
+                Ok(latest) => found.extend(update_for(mod_info, nexus_id, &latest)),
+                Err(e) => log::warn!("[mod-updates] No demo listing for {nexus_id}: {e}"),
+            }
+            continue;
+        }
+        let url = format!("{NEXUS_API_BASE}{endpoint}");
+        let response = client
+            .get(&url)
+            .header("apikey", api_key)
+            .header("Application-Name", "Marvel Rivals Mod Manager")
+            .header("Application-Version", env!("CARGO_PKG_VERSION"))
+            .send()
+            .await
+            .map_err(|e| format!("Nexus request failed: {e}"))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            log::warn!("[mod-updates] Rate limited, stopping early");
+            break;
+        }
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err("Nexus rejected the API key".to_string());
+        }
+        let quota = remaining_quota(&response);
+        checked.insert(nexus_id);
+        if status.is_success() {
+            match response.json::<NexusMod>().await {
+                Ok(latest) => found.extend(update_for(mod_info, nexus_id, &latest)),
+                Err(e) => log::warn!("[mod-updates] Bad response for {nexus_id}: {e}"),
+            }
+        } else {
+            // Hidden or deleted pages answer 404; skip them
+            log::warn!("[mod-updates] Nexus mod {nexus_id}: {status}");
+        }
+
+        if quota.is_some_and(|left| left <= RATE_LIMIT_RESERVE) {
+            log::warn!("[mod-updates] Nexus quota nearly used up, stopping early");
+            break;
+        }
+        tokio::time::sleep(REQUEST_SPACING).await;
+    }
+
+    // Results for mods not reached this time (stopped early) carry over
+    let pending = update_state(app, |state| {
+        found.retain(|u| !state.is_dismissed(u.nexus_mod_id, &u.latest_version));
+        state.pending.retain(|u| !checked.contains(&u.nexus_mod_id));
+        state.pending.extend(found);
+        Ok(state.pending.clone())
+    })?;
+
+    log::info!("[mod-updates] {} update(s) available", pending.len());
+    if !pending.is_empty() {
+        let _ = app.emit("mod-updates-available", pending.len());
+        let body = if pending.len() == 1 {
+            t("notify.modUpdates.single", &[("title", &pending[0].title)])
+        } else {
+            tn("notify.modUpdates.several", pending.len(), &[])
+        };
+        notifications::notify(
+            app,
+            NotificationKind::ModUpdateAvailable,
+            &t("notify.modUpdates.title", &[]),
+            &body,
+        );
+    }
+    Ok(pending.len())
+}
+
+/// Run the check if it is enabled, an API key is set, and the last run was
+/// over a day ago. The timestamp is only advanced by a completed check.
+async fn check_if_due(app: &AppHandle) -> Result<(), String> {
+    let settings = crate::load_app_settings(app)?;
+    if !settings.auto_check_mod_updates || !is_due(settings.last_mod_update_check, Utc::now()) {
+        return Ok(());
+    }
+    // Demo mode answers from canned listings and needs no key
+    let api_key = if crate::demo::is_enabled() {
+        String::new()
+    } else {
+        let Some(api_key) = crate::read_preferences(app)?
+            .nexus_api_key
+            .filter(|key| !key.trim().is_empty())
+        else {
+            return Ok(());
+        };
+        api_key
+    };
+
+    run_check(app, &api_key).await?;
+
+    // Re-read so a save made during the check isn't undone
+    let mut settings = crate::load_app_settings(app)?;
+    settings.last_mod_update_check = Some(Utc::now());
+    crate::save_app_settings_internal(app, &settings)
+}
+
+/// Pending updates for `mods` as of the last check, stale once a check is
+/// due again (or none ever ran)
+pub fn pending_count(app: &AppHandle, mods: &[ModInfo]) -> Result<CachedCount, String> {
+    let last_check = crate::load_app_settings(app)?.last_mod_update_check;
+    read_cached(app, |state| CachedCount {
+        count: state
+            .pending
+            .iter()
+            .filter(|u| {
+                mods.iter()
+                    .any(|m| m.metadata.nexus_mod_id == Some(u.nexus_mod_id))
+            })
+            .count(),
+        stale: is_due(last_check, Utc::now()),
+    })
+}
+
+/// Spawn the scheduler. It checks at most once per [`CHECK_INTERVAL`], the
+/// first time shortly after startup.
+pub fn start_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            // Let startup (organize, migrations) settle before going online
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            if let Err(e) = check_if_due(&app).await {
+                log::warn!("[mod-updates] Check failed: {e}");
+            }
+            tokio::time::sleep(TICK).await;
+        }
+    });
+}
+
+/// Updates found by the last check, pointed at the mods' current IDs. Mods
+/// removed since, or whose update was dismissed, are left out.
+#[tauri::command]
+pub async fn get_pending_mod_updates(app: AppHandle) -> Result<Vec<ModUpdate>, String> {
+    let mods = crate::get_mod_service(&app)?.get_all_mods()?;
+    read_cached(&app, |state| {
+        state
+            .pending
+            .iter()
+            .filter_map(|update| {
+                let current = mods
+                    .iter()
+                    .find(|m| m.metadata.nexus_mod_id == Some(update.nexus_mod_id))?;
+                Some(ModUpdate {
+                    mod_id: current.id.clone(),
+                    ..update.clone()
+                })
+            })
+            .collect()
+    })
+}
+
+/// Mute one version of a mod's update. Newer versions still show up.
+#[tauri::command]
+pub async fn dismiss_mod_update(
+    app: AppHandle,
+    mod_id: String,
+    version: String,
+) -> Result<(), String> {
+    let nexus_mod_id = crate::get_mod_service(&app)?
+        .find_mod_by_id(&mod_id)?
+        .and_then(|m| m.metadata.nexus_mod_id)
+        .ok_or_else(|| format!("Mod {mod_id} is not linked to Nexus"))?;
+    log::info!("[mod-updates] Dismissing {version} for Nexus mod {nexus_mod_id}");
+
+    update_state(&app, |state| {
+        if !state.is_dismissed(nexus_mod_id, &version) {
+            state.dismissed.push(DismissedUpdate {
+                nexus_mod_id,
+                version: version.clone(),
+            });
+        }
+        state.pending.retain(|u| {
+            !(u.nexus_mod_id == nexus_mod_id && same_version(&u.latest_version, &version))
+        });
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::{date, metadata};
+    use crate::types::{ModCategory, ModLocation};
+
+    fn installed(version: Option<&str>) -> ModInfo {
+        let mut metadata = metadata("Cool Skin", ModCategory::Skins);
+        metadata.nexus_mod_id = Some(42);
+        metadata.nexus_version = version.map(str::to_string);
+        ModInfo {
+            id: "abc".to_string(),
+            name: metadata.title.clone(),
+            category: metadata.category.clone(),
+            character: None,
+            enabled: true,
+            location: ModLocation::Active,
+            duplicate_of: None,
+            is_favorite: false,
+            disabled_reason: None,
+            file_path: PathBuf::new(),
+            thumbnail_path: None,
+            file_size: 0,
+            pak_size: 0,
+            associated_file_sizes: Vec::new(),
+            install_date: metadata.install_date,
+            last_modified: metadata.install_date,
+            original_file_name: String::new(),
+            associated_files: Vec::new(),
+            metadata,
+        }
+    }
+
+    fn listing(version: &str, updated: &str) -> NexusMod {
+        NexusMod {
+            version: Some(version.to_string()),
+            updated_timestamp: Some(date(updated).timestamp()),
+        }
+    }
+
+    #[test]
+    fn test_has_update() {
+        // Known versions compare loosely
+        let v1 = installed(Some("1.0"));
+        assert_eq!(
+            has_update(&v1, &listing("V1.0 ", "2026-01-01T00:00:00Z")),
+            None
+        );
+        assert_eq!(
+            has_update(&v1, &listing("1.1", "2026-01-01T00:00:00Z")),
+            Some("1.1".to_string())
+        );
+
+        // Unknown versions go by upload time vs. install date (2025-01-01)
+        let unknown = installed(None);
+        assert_eq!(
+            has_update(&unknown, &listing("2.0", "2024-06-01T00:00:00Z")),
+            None
+        );
+        assert_eq!(
+            has_update(&unknown, &listing("2.0", "2025-06-01T00:00:00Z")),
+            Some("2.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_check_schedule_and_dismissals() {
+        let now = date("2026-03-02T12:00:00Z");
+        assert!(is_due(None, now));
+        assert!(!is_due(Some(date("2026-03-02T00:00:00Z")), now));
+        assert!(is_due(Some(date("2026-03-01T12:00:00Z")), now));
+
+        let state = UpdateState {
+            pending: Vec::new(),
+            dismissed: vec![DismissedUpdate {
+                nexus_mod_id: 42,
+                version: "1.1".to_string(),
+            }],
+        };
+        assert!(state.is_dismissed(42, "v1.1"));
+        assert!(!state.is_dismissed(42, "1.2"));
+        assert!(!state.is_dismissed(7, "1.1"));
+    }
+}
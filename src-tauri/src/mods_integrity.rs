@@ -0,0 +1,103 @@
+//! Watchdog for mods vanishing behind the manager's back, usually a game
+//! patch wiping or moving the Paks folder: the library would go on listing
+//! mods the game no longer loads. A fresh scan only sees what's left, so the
+//! check runs against the listing saved last session (see
+//! [`game_directory::save_snapshot`]), taken at startup before the first
+//! scan replaces it.
+
+use std::sync::{Mutex, MutexGuard};
+
+use tauri::{AppHandle, Emitter};
+
+use crate::types::{ModInfo, ModsIntegrityReport};
+use crate::{file_watcher, game_directory};
+
+/// The last session's listing, kept until the check comes back clean or the
+/// mods are put back
+struct Baseline {
+    mods: Vec<ModInfo>,
+    /// ~mods was gone at startup, before a scan could recreate it
+    mods_directory_missing: bool,
+}
+
+static BASELINE: Mutex<Option<Baseline>> = Mutex::new(None);
+
+fn baseline() -> MutexGuard<'static, Option<Baseline>> {
+    BASELINE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Take the last session's listing and check it in the background, emitting
+/// `mods-integrity-report` when something is gone. Call from setup, before
+/// anything lists the mods.
+pub fn check_on_startup(app: &AppHandle) {
+    let Some(snapshot) = game_directory::load_snapshot(app) else {
+        return;
+    };
+    let Ok(service) = crate::get_mod_service(app) else {
+        return;
+    };
+    *baseline() = Some(Baseline {
+        mods: snapshot.mods,
+        mods_directory_missing: !service.mods_directory().is_dir(),
+    });
+
+    let app = app.clone();
+    std::thread::spawn(move || match verify(&app) {
+        Ok(report) if report.has_problems() => {
+            log::warn!(
+                "[integrity] {} mod(s) lost files since last session{}",
+                report.issues.len(),
+                if report.mods_directory_missing {
+                    ", ~mods is gone"
+                } else {
+                    ""
+                }
+            );
+            let _ = app.emit("mods-integrity-report", &report);
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("[integrity] Startup check failed: {}", e),
+    });
+}
+
+/// The mods as listed before: the startup baseline while it stands,
+/// otherwise the last saved listing
+fn listed_mods(app: &AppHandle) -> Vec<ModInfo> {
+    if let Some(baseline) = baseline().as_ref() {
+        return baseline.mods.clone();
+    }
+    game_directory::load_snapshot(app)
+        .map(|snapshot| snapshot.mods)
+        .unwrap_or_default()
+}
+
+fn verify(app: &AppHandle) -> Result<ModsIntegrityReport, String> {
+    let service = crate::get_mod_service(app)?;
+    let mut report = service.verify_integrity(&listed_mods(app));
+    let mut baseline = baseline();
+    if let Some(previous) = baseline.as_ref() {
+        report.mods_directory_missing |= previous.mods_directory_missing;
+    }
+    if !report.has_problems() {
+        *baseline = None;
+    }
+    Ok(report)
+}
+
+/// Mods listed before whose files are no longer all on disk, and whether
+/// ~mods itself went missing
+#[tauri::command]
+pub async fn verify_mods_integrity(app: AppHandle) -> Result<ModsIntegrityReport, String> {
+    verify(&app)
+}
+
+/// Recreate ~mods and put back the enabled mods that have a copy in
+/// disabled-mods. Returns the mods put back.
+#[tauri::command]
+pub async fn restore_mods_directory(app: AppHandle) -> Result<Vec<ModInfo>, String> {
+    let service = crate::get_mod_service(&app)?;
+    let _pause = file_watcher::pause();
+    let restored = service.restore_mods_directory(&listed_mods(&app))?;
+    *baseline() = None;
+    Ok(restored)
+}
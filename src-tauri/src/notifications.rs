@@ -0,0 +1,172 @@
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::i18n::{t, tn};
+use crate::types::NotificationSettings;
+
+/// Backend events that can raise a native notification.
+#[derive(Debug, Clone, Copy)]
+pub enum NotificationKind {
+    InstallComplete,
+    ExtractionComplete,
+    GameUpdateDetected,
+    ModUpdateAvailable,
+}
+
+impl NotificationKind {
+    fn is_enabled(&self, settings: &NotificationSettings) -> bool {
+        match self {
+            NotificationKind::InstallComplete => settings.on_install_complete,
+            NotificationKind::ExtractionComplete => settings.on_extraction_complete,
+            NotificationKind::GameUpdateDetected => settings.on_game_update_detected,
+            NotificationKind::ModUpdateAvailable => settings.on_mod_update_available,
+        }
+    }
+}
+
+/// What happened to a notification shown on request. `Delivered` means the
+/// OS accepted it; Focus Assist and the like can still keep it off screen.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum NotificationOutcome {
+    Delivered,
+    /// The OS refused it, usually because notifications are turned off for
+    /// this app (Windows: Settings > System > Notifications)
+    Blocked {
+        error: String,
+    },
+}
+
+/// True when one of the app's windows currently has focus. The user is
+/// already looking at the app in that case, so the in-app UI is enough.
+fn app_window_focused(app: &AppHandle) -> bool {
+    app.webview_windows()
+        .values()
+        .any(|window| window.is_focused().unwrap_or(false))
+}
+
+/// Show a native notification for `kind` if the user enabled it in settings
+/// and no app window is focused. Failures are logged, never returned —
+/// a missed notification should not fail the operation that triggered it.
+pub fn notify(app: &AppHandle, kind: NotificationKind, title: &str, body: &str) {
+    let settings = match crate::load_app_settings(app) {
+        Ok(settings) => settings.notifications,
+        Err(e) => {
+            log::warn!("[notify] Could not load settings, skipping {kind:?}: {e}");
+            return;
+        }
+    };
+
+    if !kind.is_enabled(&settings) {
+        log::debug!("[notify] {kind:?} disabled in settings");
+        return;
+    }
+
+    if app_window_focused(app) {
+        log::debug!("[notify] Window focused, skipping {kind:?}");
+        return;
+    }
+
+    show(app, title, body);
+}
+
+/// Show a native notification unconditionally. Used for direct feedback to
+/// an action the user just took outside the app (e.g. a global hotkey).
+pub fn show(app: &AppHandle, title: &str, body: &str) {
+    try_show(app, title, Some(body));
+}
+
+/// Show a native notification and report whether the OS took it
+pub fn try_show(app: &AppHandle, title: &str, body: Option<&str>) -> NotificationOutcome {
+    #[cfg(not(mobile))]
+    {
+        use tauri_plugin_notification::NotificationExt;
+
+        let mut notification = app.notification().builder().title(title);
+        if let Some(body) = body {
+            notification = notification.body(body);
+        }
+        match notification.show() {
+            Ok(_) => {
+                log::info!("[notify] {title}: {}", body.unwrap_or_default());
+                NotificationOutcome::Delivered
+            }
+            Err(e) => {
+                log::warn!("[notify] Failed to show notification \"{title}\": {e}");
+                NotificationOutcome::Blocked {
+                    error: e.to_string(),
+                }
+            }
+        }
+    }
+
+    #[cfg(mobile)]
+    {
+        let _ = (app, title, body);
+        NotificationOutcome::Blocked {
+            error: "Native notifications not supported on mobile".to_string(),
+        }
+    }
+}
+
+/// Send a canned notification from settings so users can check that
+/// notifications reach them at all. Ignores the per-event settings and focus.
+#[tauri::command]
+pub async fn test_notification(app: AppHandle) -> Result<NotificationOutcome, String> {
+    Ok(try_show(
+        &app,
+        &t("notify.test.title", &[]),
+        Some(&t("notify.test.body", &[])),
+    ))
+}
+
+/// Called by the frontend once an install from an archive or a batch of
+/// dropped paks finishes, since it installs the paks one command at a time.
+#[tauri::command]
+pub async fn notify_install_complete(
+    app: AppHandle,
+    source_name: String,
+    installed: usize,
+    failed: usize,
+) -> Result<(), String> {
+    let body = if failed > 0 {
+        tn(
+            "notify.installComplete.bodyWithFailures",
+            installed,
+            &[("source", &source_name), ("failed", &failed)],
+        )
+    } else {
+        tn(
+            "notify.installComplete.body",
+            installed,
+            &[("source", &source_name)],
+        )
+    };
+    notify(
+        &app,
+        NotificationKind::InstallComplete,
+        &t("notify.installComplete.title", &[]),
+        &body,
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outcome_serializes_with_status_tag() {
+        let blocked = NotificationOutcome::Blocked {
+            error: "Toast notifier unavailable".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&blocked).unwrap(),
+            serde_json::json!({ "status": "blocked", "error": "Toast notifier unavailable" })
+        );
+        assert_eq!(
+            serde_json::to_value(NotificationOutcome::Delivered).unwrap(),
+            serde_json::json!({ "status": "delivered" })
+        );
+    }
+}
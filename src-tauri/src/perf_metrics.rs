@@ -0,0 +1,174 @@
+//! Local timings of the operations users notice when they slow down: scans,
+//! installs, toggles, deletes, organizing and conflict checks. The last
+//! [`WINDOW`] runs of each are kept in `performance-metrics.json` beside the
+//! metadata folder, so "it got slower after the update" can be checked
+//! against numbers. Nothing is sent anywhere.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::OperationMetrics;
+
+pub const FILE_NAME: &str = "performance-metrics.json";
+
+/// Runs kept per operation
+const WINDOW: usize = 100;
+
+/// Recent runs by operation name
+type Samples = BTreeMap<String, VecDeque<Sample>>;
+
+/// Loaded metrics, by metrics file
+static METRICS: Mutex<BTreeMap<PathBuf, Samples>> = Mutex::new(BTreeMap::new());
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Sample {
+    at: DateTime<Utc>,
+    duration_ms: u64,
+    /// How many things the run handled (mods scanned, deleted, toggled...)
+    items: usize,
+}
+
+/// Times one run of an operation. Only runs that finish are recorded; a
+/// timer dropped on an error path records nothing.
+pub struct Timer {
+    file: PathBuf,
+    operation: &'static str,
+    started: Instant,
+}
+
+impl Timer {
+    pub fn start(file: PathBuf, operation: &'static str) -> Self {
+        Self {
+            file,
+            operation,
+            started: Instant::now(),
+        }
+    }
+
+    /// Record the run, which handled `items` things
+    pub fn finish(self, items: usize) {
+        record(&self.file, self.operation, self.started.elapsed(), items);
+    }
+}
+
+fn load(file: &Path) -> Samples {
+    let Ok(json) = fs::read_to_string(file) else {
+        return Samples::new();
+    };
+    serde_json::from_str(&json).unwrap_or_else(|e| {
+        log::warn!("[perf] Ignoring unreadable {:?}: {}", file, e);
+        Samples::new()
+    })
+}
+
+fn save(file: &Path, samples: &Samples) -> Result<(), String> {
+    let json = serde_json::to_string(samples)
+        .map_err(|e| format!("Failed to serialize performance metrics: {}", e))?;
+    fs::write(file, json).map_err(|e| format!("Failed to write performance metrics: {}", e))
+}
+
+/// Add a run of `operation` to the metrics in `file`, dropping the oldest
+/// beyond the window
+pub fn record(file: &Path, operation: &str, duration: Duration, items: usize) {
+    let mut metrics = METRICS.lock().unwrap_or_else(|e| e.into_inner());
+    let samples = metrics
+        .entry(file.to_path_buf())
+        .or_insert_with(|| load(file));
+    let runs = samples.entry(operation.to_string()).or_default();
+    runs.push_back(Sample {
+        at: Utc::now(),
+        duration_ms: duration.as_millis() as u64,
+        items,
+    });
+    while runs.len() > WINDOW {
+        runs.pop_front();
+    }
+    // Written under the lock so two runs finishing together can't
+    // interleave their writes
+    if let Err(e) = save(file, samples) {
+        log::warn!("[perf] {}", e);
+    }
+}
+
+/// The nearest-rank percentile `p` (0-100) of sorted durations
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    let rank = (sorted.len() * p).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Timings per operation over the runs kept, by operation name
+pub fn summarize(file: &Path) -> Vec<OperationMetrics> {
+    let mut metrics = METRICS.lock().unwrap_or_else(|e| e.into_inner());
+    let samples = metrics
+        .entry(file.to_path_buf())
+        .or_insert_with(|| load(file));
+    samples
+        .iter()
+        .filter_map(|(operation, runs)| {
+            let last = runs.back()?;
+            let mut durations: Vec<u64> = runs.iter().map(|s| s.duration_ms).collect();
+            durations.sort_unstable();
+            Some(OperationMetrics {
+                operation: operation.clone(),
+                samples: runs.len(),
+                p50_ms: percentile(&durations, 50),
+                p95_ms: percentile(&durations, 95),
+                max_ms: durations.last().copied().unwrap_or_default(),
+                last_ms: last.duration_ms,
+                last_items: last.items,
+                last_at: last.at,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_keep_a_rolling_window() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join(FILE_NAME);
+        assert!(summarize(&file).is_empty());
+
+        for ms in 1..=20 {
+            record(&file, "scan", Duration::from_millis(ms), ms as usize);
+        }
+        record(&file, "delete_mods", Duration::from_millis(7), 2);
+        Timer::start(file.clone(), "delete_mods").finish(3);
+
+        let metrics = summarize(&file);
+        assert_eq!(
+            metrics
+                .iter()
+                .map(|m| m.operation.as_str())
+                .collect::<Vec<_>>(),
+            ["delete_mods", "scan"]
+        );
+        let scan = &metrics[1];
+        assert_eq!(scan.samples, 20);
+        assert_eq!((scan.p50_ms, scan.p95_ms, scan.max_ms), (10, 19, 20));
+        assert_eq!((scan.last_ms, scan.last_items), (20, 20));
+        assert_eq!(metrics[0].last_items, 3);
+
+        // Saved, and read back from disk
+        METRICS.lock().unwrap().remove(&file);
+        assert_eq!(summarize(&file)[1].samples, 20);
+
+        // Only the last WINDOW runs are kept
+        for _ in 0..WINDOW {
+            record(&file, "scan", Duration::from_millis(500), 1);
+        }
+        let scan = &summarize(&file)[1];
+        assert_eq!(scan.samples, WINDOW);
+        assert_eq!(scan.p50_ms, 500);
+    }
+}
@@ -0,0 +1,480 @@
+//! Backend side of mod profiles. Profiles (ID, name, timestamps) are kept in
+//! `profiles.json` in app data; how they look (color, icon) stays in the
+//! frontend. Membership is the `profile_ids` list in each mod's metadata,
+//! which follows a mod through moves that change its path-based ID. This
+//! module manages and applies profiles and remembers which one was applied
+//! last, so new installs can join it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+use crate::app_data_dir;
+use crate::mod_service::ModService;
+use crate::sorting;
+use crate::types::{
+    DisabledReason, ModInfo, ModProfile, ProfileApplySummary, SortDirection, SortKey, SortSpec,
+    TogglePreview, ToggledMod,
+};
+
+/// Name of the state file in the app data folder
+pub const STATE_FILE: &str = "profile-state.json";
+
+/// Name of the profile list in the app data folder
+pub const PROFILES_FILE: &str = "profiles.json";
+
+/// Serializes read-modify-write cycles on profiles.json across commands
+static PROFILES_LOCK: Mutex<()> = Mutex::new(());
+
+/// A profile as saved; its members are read from mod metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredProfile {
+    id: String,
+    name: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfilesFile {
+    #[serde(default)]
+    profiles: Vec<StoredProfile>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProfileState {
+    #[serde(default)]
+    active_profile_id: Option<String>,
+}
+
+/// `profile-state.json` as last read or written. `None` until first use.
+static STATE: Mutex<Option<ProfileState>> = Mutex::new(None);
+
+fn read_state(path: &Path) -> Result<ProfileState, String> {
+    if !path.exists() {
+        return Ok(ProfileState::default());
+    }
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read profile state: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse profile state: {}", e))
+}
+
+fn write_state(path: &Path, state: &ProfileState) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    }
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize profile state: {}", e))?;
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, json).map_err(|e| format!("Failed to write profile state: {}", e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("Failed to finalize profile state: {}", e))
+}
+
+/// The last applied profile, if any
+pub fn active_profile_id(app: &AppHandle) -> Result<Option<String>, String> {
+    active_profile_id_in(&app_data_dir(app)?)
+}
+
+/// [`active_profile_id`] for the app data in `app_data_dir`
+pub fn active_profile_id_in(app_data_dir: &Path) -> Result<Option<String>, String> {
+    let mut cached = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    if cached.is_none() {
+        *cached = Some(read_state(&app_data_dir.join(STATE_FILE))?);
+    }
+    Ok(cached.as_ref().and_then(|s| s.active_profile_id.clone()))
+}
+
+fn set_active_profile_id(app: &AppHandle, profile_id: Option<String>) -> Result<(), String> {
+    set_active_profile_id_in(&app_data_dir(app)?, profile_id)
+}
+
+fn set_active_profile_id_in(app_data_dir: &Path, profile_id: Option<String>) -> Result<(), String> {
+    let mut cached = STATE.lock().unwrap_or_else(|e| e.into_inner());
+    let state = ProfileState {
+        active_profile_id: profile_id,
+    };
+    write_state(&app_data_dir.join(STATE_FILE), &state)?;
+    *cached = Some(state);
+    Ok(())
+}
+
+/// Clear the active profile if it is the one being deleted
+pub fn forget_profile(app: &AppHandle, profile_id: &str) -> Result<(), String> {
+    if active_profile_id(app)?.as_deref() == Some(profile_id) {
+        set_active_profile_id(app, None)?;
+    }
+    Ok(())
+}
+
+fn read_profiles(app_data_dir: &Path) -> Result<Vec<StoredProfile>, String> {
+    let path = app_data_dir.join(PROFILES_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read profiles: {}", e))?;
+    let file: ProfilesFile =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse profiles: {}", e))?;
+    Ok(file.profiles)
+}
+
+fn write_profiles(app_data_dir: &Path, profiles: Vec<StoredProfile>) -> Result<(), String> {
+    fs::create_dir_all(app_data_dir)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    let path = app_data_dir.join(PROFILES_FILE);
+    let json = serde_json::to_string_pretty(&ProfilesFile { profiles })
+        .map_err(|e| format!("Failed to serialize profiles: {}", e))?;
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, json).map_err(|e| format!("Failed to write profiles: {}", e))?;
+    fs::rename(&temp_path, &path).map_err(|e| format!("Failed to finalize profiles: {}", e))
+}
+
+/// A trimmed profile name, refused if empty or already used by another
+/// profile (ignoring case)
+fn checked_name(profiles: &[StoredProfile], name: &str, except_id: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    let taken = profiles
+        .iter()
+        .any(|p| p.id != except_id && p.name.to_lowercase() == name.to_lowercase());
+    if taken {
+        return Err(format!("A profile named \"{}\" already exists", name));
+    }
+    Ok(name.to_string())
+}
+
+fn with_members(profile: StoredProfile, mods: &[ModInfo]) -> ModProfile {
+    let members = profile_members(mods, &profile.id);
+    let mut member_mods: Vec<ModInfo> = mods
+        .iter()
+        .filter(|m| members.contains(&m.id))
+        .cloned()
+        .collect();
+    sorting::sort_mods(
+        &mut member_mods,
+        &SortSpec {
+            key: SortKey::Title,
+            direction: SortDirection::Asc,
+        },
+    );
+    ModProfile {
+        id: profile.id,
+        name: profile.name,
+        mod_ids: member_mods.into_iter().map(|m| m.id).collect(),
+        created_at: profile.created_at,
+        updated_at: profile.updated_at,
+    }
+}
+
+/// Every saved profile, with its members among `mods`
+fn list_profiles_in(app_data_dir: &Path, mods: &[ModInfo]) -> Result<Vec<ModProfile>, String> {
+    let _guard = PROFILES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(read_profiles(app_data_dir)?
+        .into_iter()
+        .map(|p| with_members(p, mods))
+        .collect())
+}
+
+/// Save a new, empty profile
+fn create_profile_in(
+    app_data_dir: &Path,
+    profile_id: &str,
+    name: &str,
+    now: DateTime<Utc>,
+) -> Result<(), String> {
+    let _guard = PROFILES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut profiles = read_profiles(app_data_dir)?;
+    if profiles.iter().any(|p| p.id == profile_id) {
+        return Err(format!("Profile already exists: {}", profile_id));
+    }
+    let name = checked_name(&profiles, name, profile_id)?;
+    profiles.push(StoredProfile {
+        id: profile_id.to_string(),
+        name,
+        created_at: now,
+        updated_at: now,
+    });
+    write_profiles(app_data_dir, profiles)
+}
+
+fn rename_profile_in(
+    app_data_dir: &Path,
+    profile_id: &str,
+    name: &str,
+    now: DateTime<Utc>,
+) -> Result<(), String> {
+    let _guard = PROFILES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut profiles = read_profiles(app_data_dir)?;
+    let name = checked_name(&profiles, name, profile_id)?;
+    let profile = profiles
+        .iter_mut()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| format!("Profile not found: {}", profile_id))?;
+    profile.name = name;
+    profile.updated_at = now;
+    write_profiles(app_data_dir, profiles)
+}
+
+/// Drop a profile from the list. Profiles made before the list existed
+/// aren't in it, which is fine.
+fn delete_profile_in(app_data_dir: &Path, profile_id: &str) -> Result<(), String> {
+    let _guard = PROFILES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut profiles = read_profiles(app_data_dir)?;
+    let before = profiles.len();
+    profiles.retain(|p| p.id != profile_id);
+    if profiles.len() == before {
+        return Ok(());
+    }
+    write_profiles(app_data_dir, profiles)
+}
+
+/// A profile by ID with its current members
+fn profile_by_id(app: &AppHandle, profile_id: &str) -> Result<ModProfile, String> {
+    let mods = crate::get_mod_service(app)?.get_all_mods()?;
+    list_profiles_in(&app_data_dir(app)?, &mods)?
+        .into_iter()
+        .find(|p| p.id == profile_id)
+        .ok_or_else(|| format!("Profile not found: {}", profile_id))
+}
+
+/// Mods in a profile: those tagged with it, plus add-ons whose parent is.
+/// Add-ons installed before the profile existed still follow their parent.
+fn profile_members(mods: &[ModInfo], profile_id: &str) -> HashSet<String> {
+    let tagged: HashSet<String> = mods
+        .iter()
+        .filter(|m| {
+            m.metadata
+                .profile_ids
+                .as_ref()
+                .is_some_and(|ids| ids.iter().any(|id| id == profile_id))
+        })
+        .map(|m| m.id.clone())
+        .collect();
+    mods.iter()
+        .filter(|m| {
+            tagged.contains(&m.id)
+                || m.metadata
+                    .parent_mod_id
+                    .as_ref()
+                    .is_some_and(|parent| tagged.contains(parent))
+        })
+        .map(|m| m.id.clone())
+        .collect()
+}
+
+/// IDs to enable and to disable so that exactly a profile's mods are on
+fn plan_apply(mods: &[ModInfo], profile_id: &str) -> (Vec<String>, Vec<String>) {
+    let members = profile_members(mods, profile_id);
+    mods.iter()
+        .filter(|m| m.enabled != members.contains(&m.id))
+        .map(|m| m.id.clone())
+        .partition(|id| members.contains(id))
+}
+
+/// Apply a profile without the app running, for the CLI: the same toggles
+/// as `apply_profile`, without progress events or a confirmation step
+pub fn apply_profile_in(
+    service: &ModService,
+    app_data_dir: &Path,
+    profile_id: &str,
+) -> Result<Vec<ToggledMod>, String> {
+    let (to_enable, to_disable) = plan_apply(&service.get_all_mods()?, profile_id);
+    let reason = DisabledReason::Profile {
+        profile_id: profile_id.to_string(),
+    };
+    let mut toggled = service.set_mods_enabled_for(&to_disable, false, &reason, |_, _| {})?;
+    toggled.extend(service.set_mods_enabled_for(&to_enable, true, &reason, |_, _| {})?);
+
+    set_active_profile_id_in(app_data_dir, Some(profile_id.to_string()))?;
+    Ok(toggled)
+}
+
+#[tauri::command]
+pub async fn get_active_profile(app: AppHandle) -> Result<Option<String>, String> {
+    active_profile_id(&app)
+}
+
+/// What applying a profile would move, without moving anything
+#[tauri::command]
+pub async fn preview_profile_apply(
+    app: AppHandle,
+    profile_id: String,
+) -> Result<TogglePreview, String> {
+    let service = crate::get_mod_service(&app)?;
+    let (to_enable, to_disable) = plan_apply(&service.get_all_mods()?, &profile_id);
+    service.preview_toggle(&to_enable, &to_disable)
+}
+
+/// Enable exactly the mods in a profile, disable the rest, and make it the
+/// active profile. Returns how many mods were enabled and disabled, and any
+/// that couldn't be. Moving data is only done with `confirmed`, after the
+/// user has seen `preview_profile_apply`.
+#[tauri::command]
+pub async fn apply_profile(
+    app: AppHandle,
+    profile_id: String,
+    confirmed: bool,
+) -> Result<ProfileApplySummary, String> {
+    log::info!("[profiles] Applying profile {}", profile_id);
+    let service = crate::get_mod_service(&app)?;
+    let timer = service.timer("apply_profile");
+    let mods = service.get_all_mods()?;
+    let (to_enable, to_disable) = plan_apply(&mods, &profile_id);
+    if !confirmed && !(to_enable.is_empty() && to_disable.is_empty()) {
+        return Err("Applying this profile moves mods; preview and confirm it first".to_string());
+    }
+
+    // Disabling only moves the mods being disabled, so the IDs to enable
+    // are still valid afterwards
+    let reason = DisabledReason::Profile {
+        profile_id: profile_id.clone(),
+    };
+    let disabled = crate::bulk_toggle_with_progress(&app, &to_disable, false, &reason)?;
+    let enabled = crate::bulk_toggle_with_progress(&app, &to_enable, true, &reason)?;
+    let summary = ProfileApplySummary {
+        enabled: enabled.toggled.len(),
+        disabled: disabled.toggled.len(),
+        failed: disabled.failed.into_iter().chain(enabled.failed).collect(),
+    };
+
+    set_active_profile_id(&app, Some(profile_id))?;
+    timer.finish(summary.enabled + summary.disabled);
+    Ok(summary)
+}
+
+/// Every profile saved in the backend, with its member mod IDs
+#[tauri::command]
+pub async fn list_profiles(app: AppHandle) -> Result<Vec<ModProfile>, String> {
+    let mods = crate::get_mod_service(&app)?.get_all_mods()?;
+    list_profiles_in(&app_data_dir(&app)?, &mods)
+}
+
+#[tauri::command]
+pub async fn rename_profile(
+    app: AppHandle,
+    profile_id: String,
+    name: String,
+) -> Result<ModProfile, String> {
+    rename_profile_in(&app_data_dir(&app)?, &profile_id, &name, Utc::now())?;
+    profile_by_id(&app, &profile_id)
+}
+
+/// Delete a profile and take it off every mod that was in it. Returns the
+/// number of mods updated.
+#[tauri::command]
+pub async fn delete_profile(app: AppHandle, profile_id: String) -> Result<usize, String> {
+    log::info!("[profiles] Deleting profile {}", profile_id);
+    let updated = crate::remove_profile_from_all_mods(app.clone(), profile_id.clone()).await?;
+    delete_profile_in(&app_data_dir(&app)?, &profile_id)?;
+    Ok(updated)
+}
+
+/// Save a new profile. With `from_current_state`, every currently enabled
+/// mod joins it, so "save my setup as a profile" is one call. The frontend
+/// may pass the ID it keeps the profile's look under; otherwise one is made.
+#[tauri::command]
+pub async fn create_profile(
+    app: AppHandle,
+    name: String,
+    profile_id: Option<String>,
+    from_current_state: bool,
+) -> Result<ModProfile, String> {
+    let profile_id = profile_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    log::info!(
+        "[profiles] Creating profile {} (from current state: {})",
+        profile_id,
+        from_current_state
+    );
+    create_profile_in(&app_data_dir(&app)?, &profile_id, &name, Utc::now())?;
+    if from_current_state {
+        crate::get_mod_service(&app)?.add_profile_to_enabled_mods(&profile_id)?;
+    }
+    profile_by_id(&app, &profile_id)
+}
+
+/// Save a new profile holding the current favorites, enabled or not
+#[tauri::command]
+pub async fn create_profile_from_favorites(
+    app: AppHandle,
+    name: String,
+    profile_id: Option<String>,
+) -> Result<ModProfile, String> {
+    let profile_id = profile_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    log::info!("[profiles] Creating profile {} from favorites", profile_id);
+    create_profile_in(&app_data_dir(&app)?, &profile_id, &name, Utc::now())?;
+    crate::get_mod_service(&app)?.add_profile_to_favorites(&profile_id)?;
+    profile_by_id(&app, &profile_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::{fixed_now, metadata, Fixture};
+    use crate::types::ModCategory;
+
+    #[test]
+    fn test_profiles_are_saved_with_members_from_metadata() {
+        let fx = Fixture::new();
+        let service = fx.service();
+        let app_data = fx.metadata_dir.parent().unwrap().to_path_buf();
+        for (stem, title) in [
+            ("Venom_P", "Venom"),
+            ("Carnage_P", "Carnage"),
+            ("Hud_P", "Hud"),
+        ] {
+            service
+                .install_mod_to_folder_with_metadata(
+                    &fx.source_mod(stem, 10, Some(100)),
+                    title,
+                    metadata(title, ModCategory::Skins),
+                )
+                .unwrap();
+        }
+        let id_of = |title: &str| {
+            let mods = service.get_all_mods().unwrap();
+            mods.into_iter()
+                .find(|m| m.metadata.title == title)
+                .unwrap()
+                .id
+        };
+        service.enable_mod(&id_of("Hud"), false).unwrap();
+
+        create_profile_in(&app_data, "p1", " Symbiotes ", fixed_now()).unwrap();
+        assert!(create_profile_in(&app_data, "p2", "symbiotes", fixed_now()).is_err());
+        assert!(create_profile_in(&app_data, "p2", "  ", fixed_now()).is_err());
+        service.add_profile_to_enabled_mods("p1").unwrap();
+
+        let profiles = list_profiles_in(&app_data, &service.get_all_mods().unwrap()).unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].name, "Symbiotes");
+        assert_eq!(profiles[0].mod_ids, [id_of("Carnage"), id_of("Venom")]);
+
+        // Members follow a mod whose ID changes when it moves
+        service.enable_mod(&id_of("Venom"), false).unwrap();
+        rename_profile_in(&app_data, "p1", "Symbiote skins", fixed_now()).unwrap();
+        let profiles = list_profiles_in(&app_data, &service.get_all_mods().unwrap()).unwrap();
+        assert_eq!(profiles[0].name, "Symbiote skins");
+        assert_eq!(profiles[0].mod_ids, [id_of("Carnage"), id_of("Venom")]);
+
+        let (to_enable, to_disable) = plan_apply(&service.get_all_mods().unwrap(), "p1");
+        assert_eq!((to_enable, to_disable), (vec![id_of("Venom")], vec![]));
+
+        delete_profile_in(&app_data, "p1").unwrap();
+        delete_profile_in(&app_data, "unknown").unwrap();
+        assert!(read_profiles(&app_data).unwrap().is_empty());
+    }
+}
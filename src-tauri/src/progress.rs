@@ -0,0 +1,160 @@
+//! Rate limiting for progress events. One event per archive entry or per mod
+//! floods the IPC channel on big batches (archives with thousands of small
+//! files) and visibly stalls the webview. Also home to the token long jobs
+//! check to stop early.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// At most one update per interval...
+pub const MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+/// ...unless at least this fraction of the total got done since the last one
+pub const MIN_STEP: f64 = 0.01;
+
+/// Decides which updates go out: the first, then any that arrive
+/// [`MIN_INTERVAL`] after the last one sent or [`MIN_STEP`] of the total
+/// further along, whichever comes first.
+#[derive(Debug, Default)]
+pub struct ProgressThrottle {
+    // When the last update went out, and how far along it was
+    last: Option<(Instant, u64)>,
+}
+
+impl ProgressThrottle {
+    /// Whether an update `done` out of `total` (in any unit) arriving at
+    /// `now` should be sent. Records it as sent if so.
+    pub fn should_emit(&mut self, now: Instant, done: u64, total: u64) -> bool {
+        let due = match self.last {
+            None => true,
+            Some((sent_at, sent_done)) => {
+                let advanced = done.saturating_sub(sent_done);
+                now.saturating_duration_since(sent_at) >= MIN_INTERVAL
+                    || (total > 0 && advanced as f64 >= total as f64 * MIN_STEP)
+            }
+        };
+        if due {
+            self.last = Some((now, done));
+        }
+        due
+    }
+}
+
+/// Wraps a progress callback with a [`ProgressThrottle`]. Callers report
+/// every step with [`emit`](Self::emit) and the final state with
+/// [`finish`](Self::finish), which is never dropped.
+pub struct ProgressEmitter<F> {
+    sink: F,
+    throttle: Mutex<ProgressThrottle>,
+}
+
+impl<F> ProgressEmitter<F> {
+    pub fn new(sink: F) -> Self {
+        Self {
+            sink,
+            throttle: Mutex::new(ProgressThrottle::default()),
+        }
+    }
+
+    /// Forward `progress` if the throttle lets it through
+    pub fn emit<P>(&self, done: u64, total: u64, progress: P)
+    where
+        F: Fn(P),
+    {
+        let due = self
+            .throttle
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .should_emit(Instant::now(), done, total);
+        if due {
+            (self.sink)(progress);
+        }
+    }
+
+    /// Forward the final update unconditionally
+    pub fn finish<P>(&self, progress: P)
+    where
+        F: Fn(P),
+    {
+        (self.sink)(progress);
+    }
+}
+
+/// Shared flag a long job checks between steps. Clones share the flag, so
+/// the job keeps one and whoever can cancel it keeps another.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_throttle_limits_by_time() {
+        let start = Instant::now();
+        let mut throttle = ProgressThrottle::default();
+
+        // The first update always goes out
+        assert!(throttle.should_emit(start, 0, 1_000_000));
+        // Tiny steps inside the interval are dropped...
+        for ms in [1, 20, 50, 99] {
+            assert!(!throttle.should_emit(start + Duration::from_millis(ms), ms, 1_000_000));
+        }
+        // ...until the interval has passed since the last one sent
+        assert!(throttle.should_emit(start + MIN_INTERVAL, 100, 1_000_000));
+        assert!(!throttle.should_emit(start + Duration::from_millis(150), 150, 1_000_000));
+        assert!(throttle.should_emit(start + Duration::from_millis(200), 200, 1_000_000));
+    }
+
+    #[test]
+    fn test_throttle_lets_big_steps_through_early() {
+        let start = Instant::now();
+        let mut throttle = ProgressThrottle::default();
+        assert!(throttle.should_emit(start, 0, 1000));
+
+        // 1% of 1000 is 10: 9 more is held back, 10 more goes out at once
+        assert!(!throttle.should_emit(start, 9, 1000));
+        assert!(throttle.should_emit(start, 10, 1000));
+        assert!(!throttle.should_emit(start, 19, 1000));
+        assert!(throttle.should_emit(start, 500, 1000));
+
+        // Unknown totals only limit by time
+        let mut unknown = ProgressThrottle::default();
+        assert!(unknown.should_emit(start, 0, 0));
+        assert!(!unknown.should_emit(start, 1_000_000, 0));
+        assert!(unknown.should_emit(start + MIN_INTERVAL, 1_000_001, 0));
+    }
+
+    #[test]
+    fn test_emitter_always_sends_the_final_update() {
+        let sent = RefCell::new(Vec::new());
+        let emitter = ProgressEmitter::new(|p: u64| sent.borrow_mut().push(p));
+
+        // A burst of 10,000 one-byte steps collapses to a handful of events
+        for done in 1..=10_000u64 {
+            emitter.emit(done, 1_000_000, done);
+        }
+        emitter.finish(10_000);
+
+        let sent = sent.into_inner();
+        assert!(sent.len() < 100, "sent {} events", sent.len());
+        assert_eq!(sent.first(), Some(&1));
+        assert_eq!(sent.last(), Some(&10_000));
+    }
+}
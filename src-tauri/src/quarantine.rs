@@ -0,0 +1,216 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::types::DisabledReason;
+
+/// How often the session monitor checks whether the game is running
+const SESSION_POLL: Duration = Duration::from_secs(30);
+
+/// Quarantine disables every enabled mod when a game update is detected,
+/// since big patches tend to make old skins crash the game on startup. Like
+/// safe mode it remembers which mods it disabled, so they can be brought
+/// back all at once or a few at a time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuarantineState {
+    pub active: bool,
+    /// IDs of the quarantined mods, as they are while disabled
+    pub mod_ids: Vec<String>,
+    /// Fingerprint of the game build that triggered it
+    pub game_build: Option<String>,
+    pub quarantined_at: Option<DateTime<Utc>>,
+}
+
+/// Which quarantined mods to re-enable: `"all"` or a list of IDs
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuarantineRelease {
+    All,
+    #[serde(untagged)]
+    Mods(Vec<String>),
+}
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = crate::app_data_dir(app)?;
+    Ok(app_data_dir.join("quarantine.json"))
+}
+
+pub fn load_state(app: &AppHandle) -> Result<QuarantineState, String> {
+    let path = state_path(app)?;
+    if !path.exists() {
+        return Ok(QuarantineState::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read quarantine state: {e}"))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse quarantine state: {e}"))
+}
+
+fn save_state(app: &AppHandle, state: &QuarantineState) -> Result<(), String> {
+    let path = state_path(app)?;
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize quarantine state: {e}"))?;
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json)
+        .map_err(|e| format!("Failed to write quarantine state: {e}"))?;
+    std::fs::rename(&temp_path, &path)
+        .map_err(|e| format!("Failed to finalize quarantine state: {e}"))
+}
+
+/// Disable every enabled mod because the game changed to `game_build`. Mods
+/// enabled while an earlier quarantine is still open join it.
+pub fn enter(app: &AppHandle, game_build: &str) -> Result<QuarantineState, String> {
+    let mut state = load_state(app)?;
+    let service = crate::get_mod_service(app)?;
+    let to_disable: Vec<String> = service
+        .get_all_mods()?
+        .into_iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.id)
+        .collect();
+
+    log::info!(
+        "[quarantine] Game updated, disabling {} mod(s)",
+        to_disable.len()
+    );
+    let _pause = crate::file_watcher::pause();
+    // Record the IDs the mods have once disabled, as safe mode does
+    let disabled = service.set_mods_enabled_for(
+        &to_disable,
+        false,
+        &DisabledReason::GameUpdateQuarantine,
+        |_, _| {},
+    )?;
+    state
+        .mod_ids
+        .extend(disabled.into_iter().map(|toggled| toggled.mod_info.id));
+    state.active = true;
+    state.game_build = Some(game_build.to_string());
+    state.quarantined_at = Some(Utc::now());
+
+    save_state(app, &state)?;
+    let _ = app.emit("quarantine-changed", &state);
+    Ok(state)
+}
+
+/// The quarantined IDs `release` picks; IDs that aren't quarantined are
+/// ignored
+fn released_ids(quarantined: &[String], release: &QuarantineRelease) -> Vec<String> {
+    match release {
+        QuarantineRelease::All => quarantined.to_vec(),
+        QuarantineRelease::Mods(ids) => quarantined
+            .iter()
+            .filter(|id| ids.contains(id))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// Re-enable quarantined mods. Returns how many were restored; the rest stay
+/// quarantined, minus any enabled or deleted by hand in the meantime.
+pub fn release(app: &AppHandle, release: &QuarantineRelease) -> Result<usize, String> {
+    let mut state = load_state(app)?;
+    if !state.active {
+        return Ok(0);
+    }
+
+    let chosen = released_ids(&state.mod_ids, release);
+    log::info!("[quarantine] Releasing {} mod(s)", chosen.len());
+    let service = crate::get_mod_service(app)?;
+    let _pause = crate::file_watcher::pause();
+    let restored = service.set_mods_enabled(&chosen, true, |_, _| {})?.len();
+
+    let still_quarantined: HashSet<String> = service
+        .get_all_mods()?
+        .into_iter()
+        .filter(|m| m.disabled_reason == Some(DisabledReason::GameUpdateQuarantine))
+        .map(|m| m.id)
+        .collect();
+    state.mod_ids.retain(|id| still_quarantined.contains(id));
+    if state.mod_ids.is_empty() {
+        state = QuarantineState::default();
+    }
+
+    save_state(app, &state)?;
+    let _ = app.emit("quarantine-changed", &state);
+    Ok(restored)
+}
+
+/// Watch for the game closing. After each session every enabled mod the game
+/// log has no complaint about gets the current build recorded as its
+/// `last_verified_game_build`.
+pub fn start_session_monitor(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut was_running = false;
+        loop {
+            std::thread::sleep(SESSION_POLL);
+            let running = crate::game_process_running().unwrap_or(false);
+            if was_running && !running {
+                if let Err(e) = record_session(&app) {
+                    log::warn!("[quarantine] Failed to record game session: {e}");
+                }
+            }
+            was_running = running;
+        }
+    });
+}
+
+fn record_session(app: &AppHandle) -> Result<(), String> {
+    let settings = crate::load_app_settings(app)?;
+    let game_dir = settings
+        .game_directory
+        .ok_or("Game directory not configured")?;
+    let game_build =
+        crate::game_build_fingerprint(&game_dir).ok_or("Could not fingerprint the game build")?;
+    let failed: HashSet<String> = crate::game_logs::find_game_log_issues(app)?
+        .into_iter()
+        .map(|issue| issue.mod_id)
+        .collect();
+
+    let verified = crate::get_mod_service(app)?.record_verified_game_build(&game_build, &failed)?;
+    log::info!(
+        "[quarantine] Game session ended: {} mod(s) newly verified, {} failed to load",
+        verified,
+        failed.len()
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_quarantine_status(app: AppHandle) -> Result<QuarantineState, String> {
+    load_state(&app)
+}
+
+/// Re-enable all quarantined mods (`"all"`) or the listed ones
+#[tauri::command]
+pub async fn release_quarantine(app: AppHandle, mode: QuarantineRelease) -> Result<usize, String> {
+    release(&app, &mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_modes() {
+        let all: QuarantineRelease = serde_json::from_str(r#""all""#).unwrap();
+        assert_eq!(all, QuarantineRelease::All);
+        let some: QuarantineRelease = serde_json::from_str(r#"["a", "x"]"#).unwrap();
+        assert_eq!(
+            some,
+            QuarantineRelease::Mods(vec!["a".to_string(), "x".to_string()])
+        );
+        assert!(serde_json::from_str::<QuarantineRelease>(r#""some""#).is_err());
+
+        let quarantined = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(released_ids(&quarantined, &all), quarantined);
+        // Only quarantined mods are released
+        assert_eq!(released_ids(&quarantined, &some), vec!["a".to_string()]);
+    }
+}
@@ -0,0 +1,284 @@
+//! Read-only mode, for streamed or shared setups: the library can be
+//! browsed, searched and checked for conflicts, but nothing that changes
+//! mods, their metadata, profiles or collections runs. Enforced where
+//! commands are dispatched, so a command that forgets to check can't slip
+//! through, and by the hotkey and game update paths that change mods
+//! without a command. The file watcher keeps running, so changes made
+//! outside the app still show up.
+//!
+//! Turned on by `enter_read_only_mode` (saved in settings) or for one
+//! session by launching with `--read-only`. Only `exit_read_only_mode`
+//! turns it off; saving settings keeps whatever is stored.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+use tauri::ipc::{Invoke, InvokeBody};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::i18n::t;
+
+/// Launch argument turning read-only mode on for that session only
+pub const LAUNCH_ARG: &str = "--read-only";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Commands refused while read-only: everything that changes mod files,
+/// metadata, thumbnails, profiles or collections. Called with `dryRun: true`
+/// they only report, and are let through.
+const MUTATING_COMMANDS: &[&str] = &[
+    "migrate_electron_data",
+    "purge_disabled_mods",
+    "apply_cleanup_suggestions",
+    "install_mod",
+    "install_mod_to_folder",
+    "install_mod_to_folder_with_metadata",
+    "enable_mod",
+    "set_mods_enabled",
+    "enable_mods_bulk",
+    "group_mods_as_variants",
+    "delete_mod",
+    "restore_mod",
+    "empty_trash",
+    "update_mod_metadata",
+    "patch_mod_metadata",
+    "set_mod_metadata_raw",
+    "toggle_favorite",
+    "remove_profile_from_all_mods",
+    "reassign_category",
+    "find_replace_metadata",
+    "assign_character",
+    "apply_layout",
+    "accept_layout",
+    "apply_profile",
+    "create_profile",
+    "create_profile_from_favorites",
+    "rename_profile",
+    "delete_profile",
+    "enter_safe_mode",
+    "exit_safe_mode",
+    "release_quarantine",
+    "download_and_save_thumbnail",
+    "save_thumbnail_from_file",
+    "save_thumbnail_from_base64",
+    "delete_thumbnail",
+    "recrop_thumbnail",
+    "clear_thumbnail_sources",
+    "import_thumbnails_from_folder",
+    "organize_mods",
+    "merge_duplicate_folders",
+    "migrate_metadata_to_path_ids",
+    "migrate_to_costume_folders",
+    "recover_orphaned_metadata",
+    "repair_split_mod",
+    "repair_split_mods",
+    "restore_mods_directory",
+    "resolve_duplicate",
+    "resolve_duplicate_mods",
+    "reconcile_external_changes",
+    "convert_legacy_disabled",
+    "consolidate_thumbnail_storage",
+    "convert_disable_strategy",
+    "run_library_repair",
+    "relocate_misplaced_mods",
+    "enforce_addon_load_order",
+    "set_mod_priority",
+    "reorder_mods",
+    "copy_metadata_from_old_id",
+    "install_skip_intros",
+    "uninstall_skip_intros",
+    "delete_mods",
+    "install_mod_group",
+    "create_collection",
+    "delete_collection",
+    "add_to_collection",
+    "remove_from_collection",
+    "reorder_collection",
+    "set_collection_enabled",
+    "download_nexus_mod",
+    "install_mod_from_path",
+];
+
+/// A change refused because read-only mode is on. Serialized with a `code`
+/// tag like the other typed command errors.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "code", rename_all = "camelCase")]
+pub enum ReadOnlyError {
+    ReadOnlyMode { action: String },
+}
+
+impl std::fmt::Display for ReadOnlyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ReadOnlyMode { action } => {
+                write!(f, "{}", t("error.readOnlyMode", &[("action", action)]))
+            }
+        }
+    }
+}
+
+impl From<ReadOnlyError> for String {
+    fn from(error: ReadOnlyError) -> Self {
+        error.to_string()
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Refuse `action` while read-only, for changes made outside a command
+pub fn check(action: &str) -> Result<(), ReadOnlyError> {
+    if is_enabled() {
+        log::info!("[read-only] Refused {action}");
+        return Err(ReadOnlyError::ReadOnlyMode {
+            action: action.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn is_refused(command: &str, args: &InvokeBody) -> bool {
+    let dry_run = match args {
+        InvokeBody::Json(args) => args.get("dryRun").and_then(|v| v.as_bool()) == Some(true),
+        InvokeBody::Raw(_) => false,
+    };
+    MUTATING_COMMANDS.contains(&command) && !dry_run
+}
+
+/// Wrap the invoke handler so mutating commands are refused with
+/// `ReadOnlyMode` while read-only, before they run
+pub fn guard(
+    handler: impl Fn(Invoke) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke) -> bool + Send + Sync + 'static {
+    move |invoke: Invoke| {
+        if is_enabled() && is_refused(invoke.message.command(), invoke.message.payload()) {
+            let command = invoke.message.command().to_string();
+            log::info!("[read-only] Refused {command}");
+            invoke
+                .resolver
+                .reject(ReadOnlyError::ReadOnlyMode { action: command });
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+/// Turn the mode on at startup if settings or the launch arguments ask
+/// for it
+pub fn init(app: &AppHandle, args: impl IntoIterator<Item = String>) {
+    let from_args = args.into_iter().any(|arg| arg == LAUNCH_ARG);
+    let from_settings = crate::load_app_settings(app)
+        .map(|settings| settings.read_only_mode)
+        .unwrap_or(false);
+    if from_args || from_settings {
+        ENABLED.store(true, Ordering::SeqCst);
+        log::info!(
+            "[read-only] Read-only mode on ({})",
+            if from_args { LAUNCH_ARG } else { "settings" }
+        );
+    }
+}
+
+/// Show the mode in the main window's title and the menu, and tell every
+/// window
+fn show_state(app: &AppHandle) -> Result<(), String> {
+    let enabled = is_enabled();
+    if let Some(window) = app.get_webview_window(crate::windows::MAIN_WINDOW) {
+        let title = app
+            .config()
+            .app
+            .windows
+            .first()
+            .map(|config| config.title.clone())
+            .unwrap_or_else(|| app.package_info().name.clone());
+        let title = if enabled {
+            t("readOnly.windowTitle", &[("app", &title)])
+        } else {
+            title
+        };
+        let _ = window.set_title(&title);
+    }
+    let menu = crate::build_app_menu(app).map_err(|e| format!("Failed to rebuild menu: {e}"))?;
+    app.set_menu(menu)
+        .map_err(|e| format!("Failed to rebuild menu: {e}"))?;
+    app.emit("read-only-mode-changed", enabled)
+        .map_err(|e| format!("Failed to emit read-only-mode-changed: {e}"))
+}
+
+/// Apply the startup state to the window title and menu
+pub fn show_initial_state(app: &AppHandle) {
+    if is_enabled() {
+        if let Err(e) = show_state(app) {
+            log::warn!("[read-only] {e}");
+        }
+    }
+}
+
+fn set_enabled(app: &AppHandle, enabled: bool) -> Result<bool, String> {
+    let mut settings = crate::load_app_settings(app)?;
+    settings.read_only_mode = enabled;
+    crate::save_app_settings_internal(app, &settings)?;
+    ENABLED.store(enabled, Ordering::SeqCst);
+    log::info!(
+        "[read-only] Read-only mode {}",
+        if enabled { "on" } else { "off" }
+    );
+    show_state(app)?;
+    Ok(enabled)
+}
+
+#[tauri::command]
+pub async fn get_read_only_mode() -> Result<bool, String> {
+    Ok(is_enabled())
+}
+
+/// Refuse every change to the library from now on, across restarts
+#[tauri::command]
+pub async fn enter_read_only_mode(app: AppHandle) -> Result<bool, String> {
+    set_enabled(&app, true)
+}
+
+/// Allow changes again. The only way to turn the mode off.
+#[tauri::command]
+pub async fn exit_read_only_mode(app: AppHandle) -> Result<bool, String> {
+    set_enabled(&app, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutating_commands_are_registered() {
+        for command in MUTATING_COMMANDS {
+            assert!(
+                crate::COMMANDS.contains(command),
+                "{command} isn't a registered command"
+            );
+        }
+        // Reads, and the way out, always work
+        for command in [
+            "get_all_mods",
+            "detect_mod_conflicts",
+            "exit_read_only_mode",
+        ] {
+            assert!(!MUTATING_COMMANDS.contains(&command));
+        }
+    }
+
+    #[test]
+    fn test_dry_runs_are_allowed() {
+        let args = |json| InvokeBody::Json(serde_json::from_str(json).unwrap());
+        assert!(is_refused("organize_mods", &args("{}")));
+        assert!(is_refused(
+            "purge_disabled_mods",
+            &args(r#"{"dryRun":false}"#)
+        ));
+        assert!(!is_refused(
+            "purge_disabled_mods",
+            &args(r#"{"dryRun":true}"#)
+        ));
+        assert!(!is_refused("get_all_mods", &args("{}")));
+    }
+}
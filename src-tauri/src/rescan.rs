@@ -0,0 +1,86 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::file_watcher::{is_paused, is_watcher_healthy};
+use crate::mod_cache::ModCache;
+use crate::types::ModInfo;
+
+/// How often the loop wakes to re-read settings. The actual rescan interval
+/// comes from `BackgroundRescanSettings::interval_minutes`.
+const TICK: Duration = Duration::from_secs(30);
+
+type Fingerprint = BTreeMap<String, (usize, u64)>;
+
+/// Fingerprint from the last reconciliation (background or forced)
+static LAST_FINGERPRINT: Mutex<Option<Fingerprint>> = Mutex::new(None);
+
+/// Compare the current directory fingerprint with the last one seen. Returns
+/// true if it changed since the previous check; the first check only records.
+fn fingerprint_changed(app: &AppHandle) -> Result<bool, String> {
+    let service = crate::get_mod_service(app)?;
+    let current = service.directory_fingerprint();
+
+    let mut last = LAST_FINGERPRINT
+        .lock()
+        .map_err(|e| format!("Failed to lock rescan state: {e}"))?;
+    let changed = last.as_ref().is_some_and(|previous| *previous != current);
+    *last = Some(current);
+    Ok(changed)
+}
+
+/// Spawn the low-frequency reconciliation loop. It idles while the watcher is
+/// healthy (unless configured otherwise) and emits the standard
+/// `mods-directory-changed` event only when the fingerprint differs.
+pub fn start_background_rescan(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_run = Instant::now();
+
+        loop {
+            std::thread::sleep(TICK);
+
+            let Ok(settings) = crate::load_app_settings(&app) else {
+                continue;
+            };
+            let rescan = settings.background_rescan;
+            if !rescan.enabled || settings.game_directory.is_none() {
+                continue;
+            }
+            if is_watcher_healthy() && !rescan.run_when_watcher_healthy {
+                continue;
+            }
+            // A bulk operation is mid-flight and will refresh when done
+            if is_paused() {
+                continue;
+            }
+            if last_run.elapsed() < Duration::from_secs(rescan.interval_minutes.max(1) * 60) {
+                continue;
+            }
+            last_run = Instant::now();
+
+            match fingerprint_changed(&app) {
+                Ok(true) => {
+                    log::info!("[rescan] Mod folders changed outside the app, refreshing");
+                    let _ = app.emit("mods-directory-changed", ());
+                }
+                Ok(false) => log::debug!("[rescan] No changes detected"),
+                Err(e) => log::warn!("[rescan] Fingerprint check failed: {e}"),
+            }
+        }
+    });
+}
+
+/// Manual refresh: rescan now, reset the background fingerprint so the loop
+/// doesn't report the same change again, and notify listeners.
+#[tauri::command]
+pub async fn force_rescan(app: AppHandle) -> Result<Vec<ModInfo>, String> {
+    log::info!("[rescan] Forced rescan requested");
+    let _ = fingerprint_changed(&app)?;
+
+    app.state::<ModCache>().clear().await;
+    let mods = crate::list_mods(&app).await?;
+
+    let _ = app.emit("mods-directory-changed", ());
+    Ok(mods)
+}
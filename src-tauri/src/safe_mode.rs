@@ -0,0 +1,121 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter};
+
+use crate::types::DisabledReason;
+
+/// Safe mode disables every enabled mod in one go and remembers exactly which
+/// ones it touched, so exiting only re-enables those (mods the user had
+/// disabled beforehand stay disabled).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeModeState {
+    pub active: bool,
+    /// IDs of the mods safe mode disabled, as they are in disabled-mods
+    pub disabled_mod_ids: Vec<String>,
+    pub entered_at: Option<DateTime<Utc>>,
+}
+
+fn state_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = crate::app_data_dir(app)?;
+    Ok(app_data_dir.join("safe-mode.json"))
+}
+
+pub fn load_state(app: &AppHandle) -> Result<SafeModeState, String> {
+    let path = state_path(app)?;
+    if !path.exists() {
+        return Ok(SafeModeState::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read safe mode state: {e}"))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse safe mode state: {e}"))
+}
+
+fn save_state(app: &AppHandle, state: &SafeModeState) -> Result<(), String> {
+    let path = state_path(app)?;
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize safe mode state: {e}"))?;
+
+    // Write to a temporary file first, then rename (atomic operation)
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, json)
+        .map_err(|e| format!("Failed to write safe mode state: {e}"))?;
+    std::fs::rename(&temp_path, &path)
+        .map_err(|e| format!("Failed to finalize safe mode state: {e}"))
+}
+
+/// Disable every currently enabled mod and record which ones were disabled.
+pub fn enter(app: &AppHandle) -> Result<SafeModeState, String> {
+    let current = load_state(app)?;
+    if current.active {
+        return Ok(current);
+    }
+
+    let service = crate::get_mod_service(app)?;
+    let to_disable: Vec<String> = service
+        .get_all_mods()?
+        .into_iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.id)
+        .collect();
+
+    log::info!(
+        "[safe-mode] Entering safe mode, disabling {} mod(s)",
+        to_disable.len()
+    );
+    // Disabling moves files, which changes their path-based IDs, so record
+    // the IDs the mods have now rather than the ones we asked for.
+    let disabled_mod_ids = service
+        .set_mods_enabled_for(&to_disable, false, &DisabledReason::SafeMode, |_, _| {})?
+        .into_iter()
+        .map(|toggled| toggled.mod_info.id)
+        .collect();
+
+    let state = SafeModeState {
+        active: true,
+        disabled_mod_ids,
+        entered_at: Some(Utc::now()),
+    };
+    save_state(app, &state)?;
+    let _ = app.emit("safe-mode-changed", &state);
+    Ok(state)
+}
+
+/// Re-enable the mods safe mode disabled. Returns how many were restored.
+pub fn exit(app: &AppHandle) -> Result<usize, String> {
+    let current = load_state(app)?;
+    if !current.active {
+        return Ok(0);
+    }
+
+    log::info!(
+        "[safe-mode] Exiting safe mode, re-enabling {} mod(s)",
+        current.disabled_mod_ids.len()
+    );
+    let service = crate::get_mod_service(app)?;
+    let restored = service
+        .set_mods_enabled(&current.disabled_mod_ids, true, |_, _| {})?
+        .len();
+
+    let state = SafeModeState::default();
+    save_state(app, &state)?;
+    let _ = app.emit("safe-mode-changed", &state);
+    Ok(restored)
+}
+
+#[tauri::command]
+pub async fn get_safe_mode_status(app: AppHandle) -> Result<SafeModeState, String> {
+    load_state(&app)
+}
+
+#[tauri::command]
+pub async fn enter_safe_mode(app: AppHandle) -> Result<SafeModeState, String> {
+    enter(&app)
+}
+
+#[tauri::command]
+pub async fn exit_safe_mode(app: AppHandle) -> Result<usize, String> {
+    exit(&app)
+}
@@ -0,0 +1,111 @@
+use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::sync::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::types::AppSettings;
+
+/// Parsed `settings.json`, shared by every command. `None` until the first
+/// read, and again after the file changes outside the app.
+///
+/// Writers hold the write lock across the whole save (disk write + cache
+/// update), so two windows saving at once can't interleave.
+#[derive(Default)]
+pub struct SettingsCache {
+    settings: RwLock<Option<AppSettings>>,
+    // Kept alive for as long as the cache is managed
+    watcher: Mutex<Option<RecommendedWatcher>>,
+}
+
+impl SettingsCache {
+    pub fn read(&self) -> RwLockReadGuard<'_, Option<AppSettings>> {
+        // The cached value is replaced whole, so a poisoned lock still holds
+        // a consistent one
+        self.settings.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, Option<AppSettings>> {
+        self.settings.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Tell every window the settings changed
+pub fn emit_settings_changed(app: &AppHandle, settings: &AppSettings) {
+    if let Err(e) = app.emit("settings-changed", settings) {
+        log::warn!("[settings] Failed to emit settings-changed: {e}");
+    }
+}
+
+/// Re-read the file after an external edit. Our own saves land here too;
+/// they already updated the cache, so nothing is emitted for them.
+fn reload_from_disk(app: &AppHandle) {
+    let cache = app.state::<SettingsCache>();
+    let previous = {
+        let mut slot = cache.write();
+        slot.take()
+    };
+
+    match crate::load_app_settings(app) {
+        Ok(current) => {
+            let changed = previous.is_none_or(|previous| {
+                serde_json::to_value(&previous).ok() != serde_json::to_value(&current).ok()
+            });
+            if changed {
+                log::info!("[settings] settings.json changed on disk, reloaded");
+                emit_settings_changed(app, &current);
+                crate::fs_ops::set_feed_enabled(current.file_activity_events);
+            }
+        }
+        // Half-written or hand-edited into invalid JSON: stay uncached and
+        // let the next read report the error
+        Err(e) => log::warn!("[settings] Failed to reload settings: {e}"),
+    }
+}
+
+fn is_settings_event(event: &Event, settings_path: &Path) -> bool {
+    event
+        .paths
+        .iter()
+        .any(|path| path.file_name() == settings_path.file_name())
+}
+
+/// Watch `settings.json` so edits made outside the app (another instance,
+/// a text editor, a sync client) invalidate the cache
+pub fn watch_settings_file(app: &AppHandle, settings_path: PathBuf) -> Result<(), String> {
+    let dir = settings_path
+        .parent()
+        .ok_or("Invalid settings path")?
+        .to_path_buf();
+    let (tx, rx) = channel::<notify::Result<Event>>();
+
+    // Watch the folder, not the file: atomic saves replace the file, which
+    // would drop a watch placed on it directly
+    let mut watcher = RecommendedWatcher::new(
+        move |res| {
+            let _ = tx.send(res);
+        },
+        Config::default(),
+    )
+    .map_err(|e| format!("Failed to create settings watcher: {e}"))?;
+    watcher
+        .watch(&dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch settings folder: {e}"))?;
+
+    let handle = app.clone();
+    std::thread::spawn(move || {
+        for event in rx {
+            match event {
+                Ok(event) if is_settings_event(&event, &settings_path) => {
+                    reload_from_disk(&handle);
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("[settings] Watcher error: {e}"),
+            }
+        }
+    });
+
+    let cache = app.state::<SettingsCache>();
+    *cache.watcher.lock().unwrap_or_else(|e| e.into_inner()) = Some(watcher);
+    Ok(())
+}
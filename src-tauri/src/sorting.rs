@@ -0,0 +1,121 @@
+use std::cmp::Ordering;
+
+use crate::types::{ModInfo, SortDirection, SortKey, SortSpec};
+
+/// Compare strings the way a person would: case-insensitive, with runs of
+/// digits compared by value so "Mod 2" sorts before "Mod 10". Case only
+/// breaks ties between otherwise equal strings.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    natural_cmp_folded(a, b).then_with(|| a.cmp(b))
+}
+
+fn natural_cmp_folded(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    // Same number written with different zero padding ("2" vs "02") only
+    // decides the order if nothing else does; fewer zeros first
+    let mut padding = Ordering::Equal;
+
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return padding,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x_run = take_digits(&mut a_chars);
+                let y_run = take_digits(&mut b_chars);
+                let ordering = compare_digit_runs(&x_run, &y_run);
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                if padding == Ordering::Equal {
+                    padding = x_run.len().cmp(&y_run.len());
+                }
+            }
+            (Some(x), Some(y)) => {
+                let ordering = x.to_lowercase().cmp(y.to_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut run = String::new();
+    while let Some(c) = chars.peek().copied().filter(|c| c.is_ascii_digit()) {
+        run.push(c);
+        chars.next();
+    }
+    run
+}
+
+/// Compare two digit strings by numeric value without parsing, so arbitrarily
+/// long runs can't overflow.
+fn compare_digit_runs(a: &str, b: &str) -> Ordering {
+    let a = a.trim_start_matches('0');
+    let b = b.trim_start_matches('0');
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// The title shown for a mod: its metadata title, else its name
+pub(crate) fn title(mod_info: &ModInfo) -> &str {
+    if mod_info.metadata.title.is_empty() {
+        &mod_info.name
+    } else {
+        &mod_info.metadata.title
+    }
+}
+
+/// Sort mods in place. Ties always fall back to natural title order (always
+/// ascending) so the result is stable across rescans.
+pub fn sort_mods(mods: &mut [ModInfo], spec: &SortSpec) {
+    mods.sort_by(|a, b| {
+        let primary = match spec.key {
+            SortKey::Title => natural_cmp(title(a), title(b)),
+            SortKey::InstallDate => a.install_date.cmp(&b.install_date),
+            SortKey::LastModified => a.last_modified.cmp(&b.last_modified),
+            SortKey::FileSize => a.file_size.cmp(&b.file_size),
+            // Mods without a character go last in ascending order
+            SortKey::Character => match (&a.character, &b.character) {
+                (Some(x), Some(y)) => natural_cmp(&x.to_string(), &y.to_string()),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            SortKey::EnabledFirst => b.enabled.cmp(&a.enabled),
+            SortKey::EnabledFavoritesFirst => {
+                (b.is_favorite, b.enabled).cmp(&(a.is_favorite, a.enabled))
+            }
+        };
+        let primary = match spec.direction {
+            SortDirection::Asc => primary,
+            SortDirection::Desc => primary.reverse(),
+        };
+        primary.then_with(|| natural_cmp(title(a), title(b)))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_cmp() {
+        let mut names = vec!["Mod 10", "mod 2", "Mod 1", "Mod 02", "Alpha", "Mod 2b"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            names,
+            vec!["Alpha", "Mod 1", "mod 2", "Mod 02", "Mod 2b", "Mod 10"]
+        );
+
+        assert_eq!(natural_cmp("v1.2.10", "v1.2.9"), Ordering::Greater);
+        assert_eq!(
+            natural_cmp("99999999999999999999999", "100000000000000000000000"),
+            Ordering::Less
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! Bulk thumbnail import for users coming from manual setups, who keep a
+//! folder of preview images named after their paks (`VenomClassic_P.png`)
+//! or their mods' titles. Images are matched to installed mods and run
+//! through the same pipeline as a thumbnail picked by hand.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+use crate::progress::ProgressEmitter;
+use crate::types::ModInfo;
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+
+/// Share of words two titles must have in common to count as a match
+const MIN_TITLE_SIMILARITY: f64 = 0.6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ThumbnailMatchStrategy {
+    /// Image named after the pak, with or without the `_P` suffix
+    Filename,
+    /// Image named after the mod's title, loosely
+    Title,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailImportMatch {
+    pub image_path: PathBuf,
+    pub mod_id: String,
+    pub title: String,
+    /// Why saving the thumbnail failed, for entries in `failed`
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailImportReport {
+    pub imported: Vec<ThumbnailImportMatch>,
+    /// Matched, but the mod already had a thumbnail and `overwrite` was off
+    pub skipped: Vec<ThumbnailImportMatch>,
+    pub failed: Vec<ThumbnailImportMatch>,
+    /// Images that matched no mod, or more than one, for assigning by hand
+    pub unmatched: Vec<PathBuf>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThumbnailImportProgress {
+    current: usize,
+    total: usize,
+}
+
+/// Images directly inside `dir`, sorted by name
+fn list_images(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read folder: {}", e))?;
+    let mut images: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| IMAGE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        })
+        .collect();
+    images.sort();
+    Ok(images)
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// Lowercased pak stem without the `_P` suffix, as `find_thumbnail` matches
+fn pak_key(stem: &str) -> String {
+    let stem = stem.to_lowercase();
+    stem.strip_suffix("_p").unwrap_or(&stem).to_string()
+}
+
+/// Lowercased words of a title or file name, punctuation dropped
+fn title_words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect()
+}
+
+/// Words in common over words in either
+fn title_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// The one mod an image belongs to. Images that fit several mods equally
+/// well (the same pak installed twice, near-identical titles) stay
+/// unmatched rather than guessing.
+fn match_image<'a>(
+    image: &Path,
+    mods: &'a [ModInfo],
+    strategy: ThumbnailMatchStrategy,
+) -> Option<&'a ModInfo> {
+    match strategy {
+        ThumbnailMatchStrategy::Filename => {
+            let key = pak_key(&file_stem(image));
+            let mut found = mods
+                .iter()
+                .filter(|m| pak_key(&file_stem(Path::new(&m.original_file_name))) == key);
+            let first = found.next()?;
+            found.next().is_none().then_some(first)
+        }
+        ThumbnailMatchStrategy::Title => {
+            let words = title_words(&file_stem(image));
+            let mut scored: Vec<(f64, &ModInfo)> = mods
+                .iter()
+                .map(|m| (title_similarity(&words, &title_words(&m.metadata.title)), m))
+                .filter(|(score, _)| *score >= MIN_TITLE_SIMILARITY)
+                .collect();
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+            match scored.as_slice() {
+                [(_, only)] => Some(*only),
+                [(best, m), (next, _), ..] if best > next => Some(*m),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Pair each image with a mod. A mod matched by several images keeps the
+/// first; the rest go to the unmatched list with the images nothing fit.
+fn match_images<'a>(
+    images: &[PathBuf],
+    mods: &'a [ModInfo],
+    strategy: ThumbnailMatchStrategy,
+) -> (Vec<(PathBuf, &'a ModInfo)>, Vec<PathBuf>) {
+    let mut matched = Vec::new();
+    let mut unmatched = Vec::new();
+    let mut taken: HashSet<&str> = HashSet::new();
+    for image in images {
+        match match_image(image, mods, strategy) {
+            Some(m) if taken.insert(m.id.as_str()) => {
+                matched.push((image.clone(), m));
+            }
+            _ => unmatched.push(image.clone()),
+        }
+    }
+    (matched, unmatched)
+}
+
+/// Import every image in `dir_path` that matches an installed mod. Mods
+/// that already have a thumbnail keep it unless `overwrite` is set.
+/// Progress goes out as `thumbnail-import-progress` events.
+#[tauri::command]
+pub async fn import_thumbnails_from_folder(
+    app: AppHandle,
+    dir_path: String,
+    match_strategy: ThumbnailMatchStrategy,
+    overwrite: Option<bool>,
+) -> Result<ThumbnailImportReport, String> {
+    let overwrite = overwrite.unwrap_or(false);
+    let images = list_images(Path::new(&dir_path))?;
+    let mods = crate::get_mod_service(&app)?.get_all_mods()?;
+    let (matched, unmatched) = match_images(&images, &mods, match_strategy);
+    log::info!(
+        "[thumbnail-import] {} of {} image(s) in {} matched a mod ({:?})",
+        matched.len(),
+        images.len(),
+        dir_path,
+        match_strategy
+    );
+
+    let thumbnails = crate::get_thumbnail_service(&app)?;
+    let app_for_progress = app.clone();
+    let progress = ProgressEmitter::new(move |progress: ThumbnailImportProgress| {
+        let _ = app_for_progress.emit("thumbnail-import-progress", progress);
+    });
+    let total = matched.len();
+    let mut report = ThumbnailImportReport {
+        unmatched,
+        ..Default::default()
+    };
+
+    for (index, (image_path, mod_info)) in matched.into_iter().enumerate() {
+        let mut entry = ThumbnailImportMatch {
+            image_path,
+            mod_id: mod_info.id.clone(),
+            title: mod_info.metadata.title.clone(),
+            error: None,
+        };
+        if mod_info.thumbnail_path.is_some() && !overwrite {
+            report.skipped.push(entry);
+        } else {
+            match thumbnails
+                .save_thumbnail_from_file(&mod_info.id, &entry.image_path, None)
+                .await
+            {
+                Ok(_) => report.imported.push(entry),
+                Err(e) => {
+                    log::warn!(
+                        "[thumbnail-import] Failed to import {:?}: {}",
+                        entry.image_path,
+                        e
+                    );
+                    entry.error = Some(e.to_string());
+                    report.failed.push(entry);
+                }
+            }
+        }
+        let current = index + 1;
+        progress.emit(
+            current as u64,
+            total as u64,
+            ThumbnailImportProgress { current, total },
+        );
+    }
+    progress.finish(ThumbnailImportProgress {
+        current: total,
+        total,
+    });
+
+    log::info!(
+        "[thumbnail-import] Imported {}, kept {} existing, {} failed, {} unmatched",
+        report.imported.len(),
+        report.skipped.len(),
+        report.failed.len(),
+        report.unmatched.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mod_service::fixtures::metadata;
+    use crate::types::{ModCategory, ModLocation};
+
+    fn mod_info(id: &str, file_name: &str, title: &str) -> ModInfo {
+        let metadata = metadata(title, ModCategory::Skins);
+        ModInfo {
+            id: id.to_string(),
+            name: title.to_string(),
+            category: ModCategory::Skins,
+            character: None,
+            enabled: true,
+            location: ModLocation::Active,
+            duplicate_of: None,
+            is_favorite: false,
+            disabled_reason: None,
+            file_path: PathBuf::from(file_name),
+            thumbnail_path: None,
+            install_date: metadata.install_date,
+            last_modified: metadata.updated_at,
+            metadata,
+            file_size: 0,
+            pak_size: 0,
+            associated_file_sizes: Vec::new(),
+            original_file_name: file_name.to_string(),
+            associated_files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_match_by_filename() {
+        let mods = vec![
+            mod_info("venom", "VenomClassic_P.pak", "Classic Venom"),
+            mod_info("storm", "Storm_P.pak", "Storm Recolor"),
+            mod_info("storm-copy", "Storm_P.pak", "Storm Recolor"),
+        ];
+        let images: Vec<PathBuf> = [
+            "VenomClassic_P.png",
+            "venomclassic.jpg",
+            "Storm.png",
+            "Unknown.png",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+
+        let (matched, unmatched) = match_images(&images, &mods, ThumbnailMatchStrategy::Filename);
+        // With or without _P, case-insensitive; the second image for the
+        // same mod and the pak installed twice are left for the user
+        let pairs: Vec<(&str, &str)> = matched
+            .iter()
+            .map(|(image, m)| (image.to_str().unwrap(), m.id.as_str()))
+            .collect();
+        assert_eq!(pairs, vec![("VenomClassic_P.png", "venom")]);
+        assert_eq!(
+            unmatched,
+            vec![
+                PathBuf::from("venomclassic.jpg"),
+                PathBuf::from("Storm.png"),
+                PathBuf::from("Unknown.png"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_match_by_title() {
+        let mods = vec![
+            mod_info("venom", "a_P.pak", "Classic Venom"),
+            mod_info("storm", "b_P.pak", "Storm - Weather Witch Recolor"),
+            mod_info("storm2", "c_P.pak", "Storm Weather Witch"),
+        ];
+        let images: Vec<PathBuf> = ["venom_classic.png", "Storm Weather Witch Recolor.webp"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+
+        let (matched, unmatched) = match_images(&images, &mods, ThumbnailMatchStrategy::Title);
+        let ids: Vec<&str> = matched.iter().map(|(_, m)| m.id.as_str()).collect();
+        // Word order and punctuation don't matter; the closest title wins
+        assert_eq!(ids, vec!["venom", "storm"]);
+        assert!(unmatched.is_empty());
+
+        let (matched, _) = match_images(
+            &[PathBuf::from("Witch.png")],
+            &mods,
+            ThumbnailMatchStrategy::Title,
+        );
+        assert!(matched.is_empty());
+    }
+}
@@ -3,6 +3,10 @@ use reqwest;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+/// Images bigger than this go through the resumable downloader, so a
+/// dropped connection doesn't start them over
+const LARGE_IMAGE_BYTES: u64 = 4 * 1024 * 1024;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CropData {
     pub x: u32,
@@ -32,6 +36,13 @@ impl std::fmt::Display for ThumbnailError {
 
 impl std::error::Error for ThumbnailError {}
 
+/// Longest side of a stashed original. Anything bigger is scaled down; the
+/// thumbnail itself is only 1920x1080, so this still leaves room to re-crop.
+const SOURCE_MAX_SIDE: u32 = 3840;
+
+/// Formats an original may be stashed in, by extension
+const SOURCE_EXTENSIONS: &[&str] = &["png", "webp"];
+
 pub struct ThumbnailService {
     metadata_dir: PathBuf,
 }
@@ -43,6 +54,14 @@ impl ThumbnailService {
 
     /// Downloads an image from a URL (supports both HTTP URLs and data URLs)
     pub async fn download_image(&self, url: &str) -> Result<DynamicImage, ThumbnailError> {
+        Ok(self.fetch_image(url).await?.0)
+    }
+
+    /// [`download_image`](Self::download_image), plus the format it came in
+    async fn fetch_image(
+        &self,
+        url: &str,
+    ) -> Result<(DynamicImage, Option<ImageFormat>), ThumbnailError> {
         // Check if this is a data URL (e.g., data:image/png;base64,...)
         if url.starts_with("data:") {
             // Parse data URL
@@ -63,23 +82,32 @@ impl ThumbnailService {
             let img = image::load_from_memory(&bytes)
                 .map_err(|e| ThumbnailError::ImageProcessingFailed(e.to_string()))?;
 
-            return Ok(img);
+            return Ok((img, image::guess_format(&bytes).ok()));
         }
 
         // Regular HTTP/HTTPS URL - download it
         // Create HTTP client with custom Accept header
-        // Request formats we support (no AVIF) so server sends compatible format
-        let client = reqwest::Client::new();
-        let response = client
-            .get(url)
-            .header(
-                "Accept",
+        // Request formats we support (no AVIF) so server sends compatible format.
+        // Default headers so retries of large images send them too
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::ACCEPT,
+            reqwest::header::HeaderValue::from_static(
                 "image/webp,image/png,image/jpeg,image/*;q=0.9,*/*;q=0.8",
-            )
-            .header(
-                "User-Agent",
+            ),
+        );
+        headers.insert(
+            reqwest::header::USER_AGENT,
+            reqwest::header::HeaderValue::from_static(
                 "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36",
-            )
+            ),
+        );
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .map_err(|e| ThumbnailError::DownloadFailed(e.to_string()))?;
+        let response = client
+            .get(url)
             .send()
             .await
             .map_err(|e| ThumbnailError::DownloadFailed(e.to_string()))?;
@@ -91,16 +119,26 @@ impl ThumbnailService {
             )));
         }
 
-        let bytes = response
-            .bytes()
-            .await
-            .map_err(|e| ThumbnailError::DownloadFailed(e.to_string()))?;
+        let bytes = if response
+            .content_length()
+            .is_some_and(|len| len > LARGE_IMAGE_BYTES)
+        {
+            crate::download_service::download_detached(&client, url, response)
+                .await
+                .map_err(ThumbnailError::DownloadFailed)?
+        } else {
+            response
+                .bytes()
+                .await
+                .map_err(|e| ThumbnailError::DownloadFailed(e.to_string()))?
+                .to_vec()
+        };
 
         // Load image from bytes
         let img = image::load_from_memory(&bytes)
             .map_err(|e| ThumbnailError::ImageProcessingFailed(e.to_string()))?;
 
-        Ok(img)
+        Ok((img, image::guess_format(&bytes).ok()))
     }
 
     /// Center-crops an image to a target aspect ratio without stretching
@@ -172,6 +210,22 @@ impl ThumbnailService {
         mod_id: &str,
         img: &DynamicImage,
     ) -> Result<PathBuf, ThumbnailError> {
+        self.write_thumbnail(mod_id, img)
+    }
+
+    /// Adopt an image file (e.g. a preview shipped in a mod archive) as a
+    /// mod's thumbnail. Synchronous so the install path can call it directly.
+    pub fn adopt_image_file(
+        &self,
+        mod_id: &str,
+        file_path: &Path,
+    ) -> Result<PathBuf, ThumbnailError> {
+        let img = image::open(file_path)
+            .map_err(|e| ThumbnailError::ImageProcessingFailed(e.to_string()))?;
+        self.write_thumbnail(mod_id, &img)
+    }
+
+    fn write_thumbnail(&self, mod_id: &str, img: &DynamicImage) -> Result<PathBuf, ThumbnailError> {
         // Ensure metadata directory exists
         std::fs::create_dir_all(&self.metadata_dir)
             .map_err(|e| ThumbnailError::IoError(e.to_string()))?;
@@ -201,7 +255,7 @@ impl ThumbnailService {
         self.get_thumbnail_path(mod_id).exists()
     }
 
-    /// Deletes a mod's thumbnail
+    /// Deletes a mod's thumbnail and the original it was cropped from
     pub fn delete_thumbnail(&self, mod_id: &str) -> Result<(), ThumbnailError> {
         let thumbnail_path = self.get_thumbnail_path(mod_id);
 
@@ -210,9 +264,127 @@ impl ThumbnailService {
                 .map_err(|e| ThumbnailError::IoError(e.to_string()))?;
         }
 
+        self.delete_source(mod_id)
+    }
+
+    /// The stashed original a mod's thumbnail was cropped from, if any
+    pub fn get_source_path(&self, mod_id: &str) -> Option<PathBuf> {
+        SOURCE_EXTENSIONS
+            .iter()
+            .map(|ext| self.source_path(mod_id, ext))
+            .find(|path| path.exists())
+    }
+
+    fn source_path(&self, mod_id: &str, ext: &str) -> PathBuf {
+        self.metadata_dir
+            .join(format!("{}_thumbnail_source.{}", mod_id, ext))
+    }
+
+    /// Keep the uncropped image so the crop can be redone later. WebP
+    /// originals stay WebP (lossless), everything else is stored as PNG.
+    fn stash_source(
+        &self,
+        mod_id: &str,
+        img: &DynamicImage,
+        format: Option<ImageFormat>,
+    ) -> Result<PathBuf, ThumbnailError> {
+        std::fs::create_dir_all(&self.metadata_dir)
+            .map_err(|e| ThumbnailError::IoError(e.to_string()))?;
+        let (format, ext) = match format {
+            Some(ImageFormat::WebP) => (ImageFormat::WebP, "webp"),
+            _ => (ImageFormat::Png, "png"),
+        };
+
+        let source = if img.width().max(img.height()) > SOURCE_MAX_SIDE {
+            self.resize_image(img, SOURCE_MAX_SIDE, SOURCE_MAX_SIDE)
+        } else {
+            img.clone()
+        };
+        // Only one original per mod, whatever format the last one had
+        self.delete_source(mod_id)?;
+        let path = self.source_path(mod_id, ext);
+        source
+            .save_with_format(&path, format)
+            .map_err(|e| ThumbnailError::ImageProcessingFailed(e.to_string()))?;
+        Ok(path)
+    }
+
+    /// Stash the original, logging instead of failing: losing it only costs
+    /// the ability to re-crop later
+    fn try_stash_source(&self, mod_id: &str, img: &DynamicImage, format: Option<ImageFormat>) {
+        if let Err(e) = self.stash_source(mod_id, img, format) {
+            log::warn!("[thumbnail] Failed to keep original for {}: {}", mod_id, e);
+        }
+    }
+
+    /// Deletes a mod's stashed original, keeping the thumbnail
+    pub fn delete_source(&self, mod_id: &str) -> Result<(), ThumbnailError> {
+        for ext in SOURCE_EXTENSIONS {
+            let path = self.source_path(mod_id, ext);
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(|e| ThumbnailError::IoError(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Carry a stashed original over to a mod's new ID
+    pub fn move_source(&self, old_id: &str, new_id: &str) -> Result<(), ThumbnailError> {
+        for ext in SOURCE_EXTENSIONS {
+            let old_path = self.source_path(old_id, ext);
+            if old_path.exists() {
+                std::fs::rename(&old_path, self.source_path(new_id, ext))
+                    .map_err(|e| ThumbnailError::IoError(e.to_string()))?;
+            }
+        }
         Ok(())
     }
 
+    /// Every stashed original in the metadata folder, with its size
+    pub fn list_sources(&self) -> Vec<(PathBuf, u64)> {
+        let Ok(entries) = std::fs::read_dir(&self.metadata_dir) else {
+            return Vec::new();
+        };
+        entries
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name().to_string_lossy().to_lowercase();
+                SOURCE_EXTENSIONS
+                    .iter()
+                    .any(|ext| name.ends_with(&format!("_thumbnail_source.{}", ext)))
+            })
+            .map(|entry| {
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                (entry.path(), size)
+            })
+            .collect()
+    }
+
+    /// Delete every stashed original. Returns the bytes freed.
+    pub fn clear_sources(&self) -> Result<u64, ThumbnailError> {
+        let mut freed = 0;
+        for (path, size) in self.list_sources() {
+            std::fs::remove_file(&path).map_err(|e| ThumbnailError::IoError(e.to_string()))?;
+            freed += size;
+        }
+        Ok(freed)
+    }
+
+    /// Redo the crop from the stashed original, without downloading again
+    pub fn recrop_thumbnail(
+        &self,
+        mod_id: &str,
+        crop_data: &CropData,
+    ) -> Result<PathBuf, ThumbnailError> {
+        let source = self.get_source_path(mod_id).ok_or_else(|| {
+            ThumbnailError::IoError(format!("No original image kept for mod {}", mod_id))
+        })?;
+        let img = image::open(&source)
+            .map_err(|e| ThumbnailError::ImageProcessingFailed(e.to_string()))?;
+        let cropped = self.crop_image(&img, crop_data)?;
+        self.write_thumbnail(mod_id, &cropped)
+    }
+
     /// Downloads and saves a thumbnail from a URL
     pub async fn download_and_save_thumbnail(
         &self,
@@ -221,7 +393,8 @@ impl ThumbnailService {
         crop_data: Option<CropData>,
     ) -> Result<PathBuf, ThumbnailError> {
         // Download image
-        let mut img = self.download_image(url).await?;
+        let (mut img, format) = self.fetch_image(url).await?;
+        self.try_stash_source(mod_id, &img, format);
 
         // Apply crop if provided
         if let Some(crop) = crop_data {
@@ -242,6 +415,7 @@ impl ThumbnailService {
         // Load image from file
         let mut img = image::open(file_path)
             .map_err(|e| ThumbnailError::ImageProcessingFailed(e.to_string()))?;
+        self.try_stash_source(mod_id, &img, ImageFormat::from_path(file_path).ok());
 
         // Apply crop if provided
         if let Some(crop) = crop_data {
@@ -253,6 +427,70 @@ impl ThumbnailService {
     }
 }
 
+const PREVIEW_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+const PREVIEW_NAMES: &[&str] = &["preview", "thumbnail", "thumb", "cover", "screenshot"];
+
+/// Find a preview image shipped alongside a .pak: one named after the pak
+/// (with or without the `_P` suffix), then a conventionally named one
+/// (preview, thumbnail, cover...), then the only image in a folder that holds
+/// a single pak. Anything more ambiguous is left for the user to pick.
+pub fn find_preview_image(pak_path: &Path) -> Option<PathBuf> {
+    let dir = pak_path.parent()?;
+    let siblings: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    pick_preview_image(pak_path, &siblings)
+}
+
+/// The [`find_preview_image`] rules applied to an already-known list of the
+/// files in the pak's folder, e.g. entries listed from an archive
+pub fn pick_preview_image(pak_path: &Path, siblings: &[PathBuf]) -> Option<PathBuf> {
+    let stem = pak_path.file_stem()?.to_str()?.to_lowercase();
+    let short_stem = stem.strip_suffix("_p").unwrap_or(&stem).to_string();
+
+    let mut images = Vec::new();
+    let mut pak_count = 0;
+    for path in siblings {
+        let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+            continue;
+        };
+        let ext = ext.to_lowercase();
+        if ext == "pak" {
+            pak_count += 1;
+        } else if PREVIEW_EXTENSIONS.contains(&ext.as_str()) {
+            images.push(path.clone());
+        }
+    }
+    images.sort();
+
+    let image_stem = |path: &PathBuf| {
+        path.file_stem()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_lowercase())
+            .unwrap_or_default()
+    };
+
+    if let Some(own) = images
+        .iter()
+        .find(|p| image_stem(p) == stem || image_stem(p) == short_stem)
+    {
+        return Some(own.clone());
+    }
+    if let Some(named) = images
+        .iter()
+        .find(|p| PREVIEW_NAMES.iter().any(|n| image_stem(p).contains(n)))
+    {
+        return Some(named.clone());
+    }
+    if pak_count == 1 && images.len() == 1 {
+        return images.pop();
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,4 +528,63 @@ mod tests {
         };
         assert!(service.crop_image(&img, &invalid_crop).is_err());
     }
+
+    #[test]
+    fn test_original_is_kept_for_recrop() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let service = ThumbnailService::new(dir.path().join("metadata"));
+        let picked = dir.path().join("picked.png");
+        // Black, with a red bottom-right quarter
+        let img = image::RgbImage::from_fn(400, 300, |x, y| {
+            if x >= 200 && y >= 150 {
+                image::Rgb([255, 0, 0])
+            } else {
+                image::Rgb([0, 0, 0])
+            }
+        });
+        img.save(&picked).unwrap();
+
+        let crop = CropData {
+            x: 0,
+            y: 0,
+            width: 160,
+            height: 90,
+        };
+        tauri::async_runtime::block_on(service.save_thumbnail_from_file(
+            "mod_a",
+            &picked,
+            Some(crop.clone()),
+        ))
+        .unwrap();
+        let source = service.get_source_path("mod_a").unwrap();
+        assert!(source.ends_with("mod_a_thumbnail_source.png"));
+        assert_eq!(image::open(&source).unwrap().width(), 400);
+
+        // Re-cropping reads the original, so it can reach outside the first crop
+        let corner = CropData {
+            x: 200,
+            y: 150,
+            width: 200,
+            height: 150,
+        };
+        service.recrop_thumbnail("mod_a", &corner).unwrap();
+        let thumbnail = image::open(service.get_thumbnail_path("mod_a")).unwrap();
+        assert_eq!(thumbnail.to_rgb8().get_pixel(960, 540).0, [255, 0, 0]);
+        assert!(service.recrop_thumbnail("mod_b", &crop).is_err());
+
+        // Originals follow ID changes and are counted for the storage report
+        service.move_source("mod_a", "mod_b").unwrap();
+        assert!(service.get_source_path("mod_a").is_none());
+        assert_eq!(service.list_sources().len(), 1);
+
+        // Deleting the thumbnail takes the original with it
+        service.delete_thumbnail("mod_b").unwrap();
+        assert!(service.list_sources().is_empty());
+
+        tauri::async_runtime::block_on(service.save_thumbnail_from_file("mod_c", &picked, None))
+            .unwrap();
+        assert!(service.clear_sources().unwrap() > 0);
+        assert!(service.thumbnail_exists("mod_c"));
+        assert!(service.get_source_path("mod_c").is_none());
+    }
 }
@@ -1,6 +1,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::game_detection::DetectionSource;
+use crate::i18n::{t, tn};
 
 // ===== Mod Category =====
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -9,6 +12,13 @@ pub enum ModCategory {
     Audio,
     Skins,
     Gameplay,
+    /// Crosshairs, fonts, loading screens and anything else that doesn't fit.
+    /// Only ever set by hand; detection never picks it.
+    Other,
+    /// A category this build doesn't know (hand-edited metadata or a newer
+    /// app version). Kept verbatim so re-saving doesn't lose it.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl ModCategory {
@@ -18,6 +28,7 @@ impl ModCategory {
             ModCategory::Audio => &["audio", "sound", "music", "voice"],
             ModCategory::Skins => &["skin", "costume", "outfit", "appearance"],
             ModCategory::Gameplay => &["gameplay", "mechanic", "ability", "stat"],
+            ModCategory::Other | ModCategory::Unknown(_) => &[],
         }
     }
 }
@@ -29,6 +40,8 @@ impl std::fmt::Display for ModCategory {
             ModCategory::Audio => write!(f, "Audio"),
             ModCategory::Skins => write!(f, "Skins"),
             ModCategory::Gameplay => write!(f, "Gameplay"),
+            ModCategory::Other => write!(f, "Other"),
+            ModCategory::Unknown(name) => write!(f, "{}", name),
         }
     }
 }
@@ -126,6 +139,11 @@ pub enum Character {
     // Special
     #[serde(rename = "All Characters")]
     AllCharacters,
+
+    /// A hero this build doesn't know yet. Kept verbatim so the metadata
+    /// round-trips; detection never produces it.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 impl Character {
@@ -253,6 +271,7 @@ impl Character {
             Character::Cyclops => &["cyclops", "scott", "summers"],
             Character::Jubilee => &["jubilee", "jubilation", "jubilationlee"],
             Character::AllCharacters => &["allcharacters", "all", "everyone", "every"],
+            Character::Unknown(_) => &[],
         }
     }
 }
@@ -313,6 +332,7 @@ impl std::fmt::Display for Character {
             Character::Cyclops => "Cyclops",
             Character::Jubilee => "Jubilee",
             Character::AllCharacters => "All Characters",
+            Character::Unknown(name) => name,
         };
         write!(f, "{}", name)
     }
@@ -364,154 +384,1941 @@ pub struct ModMetadata {
     // Parent mod linkage (for add-on mods)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_mod_id: Option<String>,
+
+    // Why the mod was last disabled; cleared when it is enabled again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_reason: Option<DisabledReason>,
+    // When it was disabled; cleared along with the reason
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_at: Option<DateTime<Utc>>,
+    // Game build fingerprint of the last session it loaded in without errors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_verified_game_build: Option<String>,
+    // False once the user accepts the mod's folder as it is; metadata edits
+    // then stop moving it to Category/Character/Title
+    #[serde(default = "default_manage_layout")]
+    pub manage_layout: bool,
+    // The files as the manager last saw them, to notice replacements made
+    // by other tools; recorded by `reconcile_external_changes`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_fingerprint: Option<FileFingerprint>,
+    // When the files were found changed outside the manager; cleared when
+    // the mod is installed again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub externally_modified_at: Option<DateTime<Utc>>,
+    // Subfolder (relative to ~mods or disabled-mods) the mod was moved into
+    // because another mod's same-named files were where it was going;
+    // `original_folder_path` still says where it belongs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub collision_folder: Option<String>,
+    // Place in the game's load order, carried as an `NNN_` prefix on the
+    // mod's top folder under ~mods; lower loads first. Set with
+    // `set_mod_priority`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    // The user's own notes on the mod ("clips through cape on Victory pose")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    // What happened to the mod, oldest first; only the last
+    // `MAX_MOD_HISTORY` entries are kept
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<ModHistoryEntry>,
+    // Alternatives of one mod ("Red" and "Blue" paks of a skin) share a
+    // group name; only one of them should be enabled at a time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant_group: Option<String>,
 }
 
-// ===== Mod Info =====
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Entries kept in a mod's history; older ones are dropped
+pub const MAX_MOD_HISTORY: usize = 100;
+
+impl ModMetadata {
+    /// Add `action` to the history, dropping the oldest entries past
+    /// [`MAX_MOD_HISTORY`]
+    pub fn record_history(
+        &mut self,
+        at: DateTime<Utc>,
+        action: ModHistoryAction,
+        detail: Option<String>,
+    ) {
+        self.history.push(ModHistoryEntry { at, action, detail });
+        let excess = self.history.len().saturating_sub(MAX_MOD_HISTORY);
+        self.history.drain(..excess);
+    }
+}
+
+/// Paks in one folder that look like alternatives of the same mod, going
+/// by a long shared start of their file names
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ModInfo {
-    pub id: String,
+pub struct VariantGroup {
+    /// The shared part of the names, as a suggested group name
     pub name: String,
-    pub category: ModCategory,
+    pub folder: PathBuf,
+    pub mod_ids: Vec<String>,
+}
+
+/// Document format of an exported mod list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModListFormat {
+    Json,
+    Csv,
+    Markdown,
+}
+
+/// A mod list as exported to JSON, for sharing a setup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModListExport {
+    pub exported_at: DateTime<Utc>,
+    pub mods: Vec<ModListEntry>,
+}
+
+/// One mod in an exported list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModListEntry {
+    pub title: String,
     pub character: Option<Character>,
+    pub category: ModCategory,
+    pub author: Option<String>,
+    pub version: Option<String>,
+    pub nexus_mod_id: Option<i32>,
+    pub nexus_file_id: Option<i32>,
     pub enabled: bool,
-    pub is_favorite: bool,
-    pub file_path: PathBuf,
-    pub thumbnail_path: Option<PathBuf>,
-    pub metadata: ModMetadata,
     pub file_size: u64,
-    pub install_date: DateTime<Utc>,
-    pub last_modified: DateTime<Utc>,
     pub original_file_name: String,
-    pub associated_files: Vec<PathBuf>,
 }
 
-// ===== App Settings =====
+impl ModListEntry {
+    pub fn from_mod(mod_info: &ModInfo) -> Self {
+        let metadata = &mod_info.metadata;
+        Self {
+            title: metadata.title.clone(),
+            character: metadata.character.clone(),
+            category: metadata.category.clone(),
+            author: metadata.author.clone(),
+            version: metadata.version.clone(),
+            nexus_mod_id: metadata.nexus_mod_id,
+            nexus_file_id: metadata.nexus_file_id,
+            enabled: mod_info.enabled,
+            file_size: mod_info.file_size,
+            original_file_name: mod_info.original_file_name.clone(),
+        }
+    }
+}
+
+/// How an imported list entry was matched to an installed mod, most
+/// certain first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModListMatchKind {
+    NexusModId,
+    TitleAndCharacter,
+    FileName,
+}
+
+/// An imported list entry that is installed here
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AppSettings {
-    pub game_directory: Option<PathBuf>,
-    pub mod_directory: Option<PathBuf>,
-    pub theme: String,
-    #[serde(default = "default_font")]
-    pub font: String,
-    pub auto_organize: bool,
-    pub auto_detect_game_dir: bool,
-    #[serde(default = "default_auto_check_updates")]
-    pub auto_check_updates: bool,
+pub struct ModListMatch {
+    pub entry: ModListEntry,
+    pub mod_id: String,
+    pub matched_by: ModListMatchKind,
 }
 
-fn default_font() -> String {
-    "quicksand".to_string()
+/// An imported list entry with no installed mod, and where to get it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingListedMod {
+    pub entry: ModListEntry,
+    pub nexus_url: Option<String>,
 }
 
-fn default_auto_check_updates() -> bool {
+/// An imported mod list set against the library
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModListImportReport {
+    /// Installed and enabled
+    pub installed: Vec<ModListMatch>,
+    /// Installed but disabled when the list was imported
+    pub disabled: Vec<ModListMatch>,
+    /// Not installed at all
+    pub missing: Vec<MissingListedMod>,
+    /// Disabled mods enabled because the import was applied, by new ID
+    pub enabled: Vec<String>,
+    /// Disabled mods that couldn't be enabled, with why
+    pub errors: Vec<String>,
+}
+
+/// Something the manager did to a mod
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModHistoryAction {
+    Installed,
+    Enabled,
+    Disabled,
+    /// Metadata edited
+    Updated,
+    /// Title changed; the detail has the old and new titles
+    Renamed,
+    /// Moved to the trash
+    Deleted,
+    /// Brought back from the trash
+    Restored,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModHistoryEntry {
+    pub at: DateTime<Utc>,
+    pub action: ModHistoryAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Total size, newest modification time and content hash of a mod's files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileFingerprint {
+    pub size: u64,
+    pub modified: DateTime<Utc>,
+    pub content_hash: String,
+}
+
+/// Payload of the `mods-externally-modified` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternallyModifiedMods {
+    pub mod_ids: Vec<String>,
+}
+
+fn default_manage_layout() -> bool {
     true
 }
 
-impl Default for AppSettings {
-    fn default() -> Self {
-        Self {
-            game_directory: None,
-            mod_directory: None,
-            theme: "dark".to_string(),
-            font: "quicksand".to_string(),
-            auto_organize: true,
-            auto_detect_game_dir: true,
-            auto_check_updates: true,
+/// Why a mod is disabled, so the list can say whether it's safe to turn back
+/// on. Serialized with a `kind` tag.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum DisabledReason {
+    /// Turned off by hand (toggle, bulk selection, collection)
+    UserAction,
+    /// Turned off to settle a conflict; `with` names the mods it clashed with
+    ConflictResolution { with: Vec<String> },
+    /// Part of safe mode; exiting safe mode turns it back on
+    SafeMode,
+    /// Held back after a game update until it's known to still work
+    GameUpdateQuarantine,
+    /// Not part of the profile that was applied
+    Profile { profile_id: String },
+}
+
+impl DisabledReason {
+    /// The `kind` tag, e.g. `"safeMode"`, for filtering
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::UserAction => "userAction",
+            Self::ConflictResolution { .. } => "conflictResolution",
+            Self::SafeMode => "safeMode",
+            Self::GameUpdateQuarantine => "gameUpdateQuarantine",
+            Self::Profile { .. } => "profile",
         }
     }
 }
 
-// ===== Progress Types =====
+/// Why commands couldn't get at the mods. Serialized with a `code` tag so
+/// the UI can tell a game folder on an offline drive apart from a real
+/// failure; the typed command errors below carry the same variant.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "code", rename_all = "camelCase")]
+pub enum ModServiceError {
+    /// A game folder is configured but can't be found, usually an external
+    /// drive that isn't connected
+    GameDirectoryUnavailable {
+        path: PathBuf,
+    },
+    Failed {
+        message: String,
+    },
+}
+
+impl From<String> for ModServiceError {
+    fn from(message: String) -> Self {
+        Self::Failed { message }
+    }
+}
+
+impl From<&str> for ModServiceError {
+    fn from(message: &str) -> Self {
+        Self::Failed {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl From<ModServiceError> for String {
+    fn from(error: ModServiceError) -> Self {
+        error.to_string()
+    }
+}
+
+impl std::fmt::Display for ModServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GameDirectoryUnavailable { path } => {
+                write!(f, "{}", game_directory_unavailable(path))
+            }
+            Self::Failed { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+/// The message shared by every error's `GameDirectoryUnavailable`
+fn game_directory_unavailable(path: &Path) -> String {
+    t(
+        "error.gameDirectoryUnavailable",
+        &[("path", &path.display())],
+    )
+}
+
+/// Why `update_mod_metadata` rejected an edit. Serialized with a `code` tag
+/// so the UI can offer a merge on conflicts instead of just showing text.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "code", rename_all = "camelCase")]
+pub enum MetadataUpdateError {
+    /// The mod was saved elsewhere (another window) since the caller loaded
+    /// it; `current` is what is stored now
+    MetadataConflict {
+        current: Box<ModMetadata>,
+    },
+    /// Hand-edited JSON that doesn't parse; `line` and `column` are 1-based
+    Syntax {
+        message: String,
+        line: usize,
+        column: usize,
+    },
+    /// Refused by validation; `issues` says which fields and why
+    Invalid {
+        issues: Vec<crate::metadata_validation::ValidationIssue>,
+    },
+    GameDirectoryUnavailable {
+        path: PathBuf,
+    },
+    Failed {
+        message: String,
+    },
+}
+
+impl From<String> for MetadataUpdateError {
+    fn from(message: String) -> Self {
+        Self::Failed { message }
+    }
+}
+
+impl From<ModServiceError> for MetadataUpdateError {
+    fn from(error: ModServiceError) -> Self {
+        match error {
+            ModServiceError::GameDirectoryUnavailable { path } => {
+                Self::GameDirectoryUnavailable { path }
+            }
+            ModServiceError::Failed { message } => Self::Failed { message },
+        }
+    }
+}
+
+impl From<MetadataUpdateError> for String {
+    fn from(error: MetadataUpdateError) -> Self {
+        error.to_string()
+    }
+}
+
+impl std::fmt::Display for MetadataUpdateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MetadataConflict { .. } => write!(f, "{}", t("error.metadataConflict", &[])),
+            Self::Syntax { message, .. } => {
+                write!(f, "{}", t("error.metadataSyntax", &[("message", message)]))
+            }
+            Self::Invalid { issues } => {
+                let issues = crate::metadata_validation::describe_issues(issues);
+                write!(f, "{}", t("error.invalidMetadata", &[("issues", &issues)]))
+            }
+            Self::GameDirectoryUnavailable { path } => {
+                write!(f, "{}", game_directory_unavailable(path))
+            }
+            Self::Failed { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+/// How far an auto-detected category or character can be trusted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DetectionConfidence {
+    /// A single generic keyword, or one several characters share
+    Low,
+    /// Several keywords, or the full name, in the file name
+    Medium,
+    /// A folder named after it
+    High,
+}
+
+/// What auto-detection makes of a pak, before anything is saved
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ModInstallProgress {
-    pub current_file: String,
-    pub current: usize,
-    pub total: usize,
-    pub status: String,
+pub struct MetadataSuggestion {
+    pub category: ModCategory,
+    pub category_confidence: DetectionConfidence,
+    pub character: Option<Character>,
+    /// None when no character was detected
+    pub character_confidence: Option<DetectionConfidence>,
 }
 
+/// An installed mod whose character matches nothing better than a weak
+/// guess from its file name, worth a second look
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ModOrganizationProgress {
-    pub current_file: String,
-    pub current: usize,
-    pub total: usize,
-    pub status: String,
-    pub moved_count: usize,
-    pub error_count: usize,
-    pub errors: Vec<String>,
+pub struct LowConfidenceAssignment {
+    pub mod_id: String,
+    pub title: String,
+    pub character: Character,
+    pub file_name: String,
 }
 
+/// The profiles and collections a mod belongs to
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct OrganizationResult {
-    pub total_mods: usize,
-    pub moved_mods: usize,
-    pub error_count: usize,
-    pub errors: Vec<String>,
-    pub duration: u64,
+pub struct ModReferences {
+    pub mod_id: String,
+    pub title: String,
+    /// Profile IDs; names live with the profiles in the frontend
+    pub profiles: Vec<String>,
+    /// Collection IDs
+    pub collections: Vec<String>,
 }
 
-// ===== Statistics Types =====
+impl ModReferences {
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty() && self.collections.is_empty()
+    }
+}
+
+/// Why `delete_mod`/`delete_mods` refused or failed. Serialized with a
+/// `code` tag so the UI can ask before forcing a delete of mods in use.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "code", rename_all = "camelCase")]
+pub enum DeleteModError {
+    /// Mods belong to a profile or collection; retry with `force` to delete
+    /// them and drop the references
+    ModInUse {
+        mods: Vec<ModReferences>,
+    },
+    GameDirectoryUnavailable {
+        path: PathBuf,
+    },
+    Failed {
+        message: String,
+    },
+}
+
+impl From<String> for DeleteModError {
+    fn from(message: String) -> Self {
+        Self::Failed { message }
+    }
+}
+
+impl From<ModServiceError> for DeleteModError {
+    fn from(error: ModServiceError) -> Self {
+        match error {
+            ModServiceError::GameDirectoryUnavailable { path } => {
+                Self::GameDirectoryUnavailable { path }
+            }
+            ModServiceError::Failed { message } => Self::Failed { message },
+        }
+    }
+}
+
+impl From<DeleteModError> for String {
+    fn from(error: DeleteModError) -> Self {
+        error.to_string()
+    }
+}
+
+impl std::fmt::Display for DeleteModError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ModInUse { mods } => match mods.as_slice() {
+                [only] => write!(
+                    f,
+                    "{}",
+                    t(
+                        "error.modInUse",
+                        &[
+                            ("title", &only.title),
+                            ("profiles", &only.profiles.len()),
+                            ("collections", &only.collections.len()),
+                        ],
+                    )
+                ),
+                _ => write!(f, "{}", tn("error.modsInUse", mods.len(), &[])),
+            },
+            Self::GameDirectoryUnavailable { path } => {
+                write!(f, "{}", game_directory_unavailable(path))
+            }
+            Self::Failed { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+/// A selected mod that would have to move while the game has it loaded
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignOffender {
+    pub mod_id: String,
+    pub title: String,
+}
+
+/// Why `assign_character` refused or failed. Serialized with a `code` tag
+/// like `DeleteModError`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "code", rename_all = "camelCase")]
+pub enum AssignCharacterError {
+    /// The game is running and these selected mods are enabled, so their
+    /// folders can't be moved
+    GameRunning {
+        mods: Vec<AssignOffender>,
+    },
+    UnknownCostume {
+        character: String,
+        costume: String,
+    },
+    GameDirectoryUnavailable {
+        path: PathBuf,
+    },
+    Failed {
+        message: String,
+    },
+}
+
+impl From<String> for AssignCharacterError {
+    fn from(message: String) -> Self {
+        Self::Failed { message }
+    }
+}
+
+impl From<ModServiceError> for AssignCharacterError {
+    fn from(error: ModServiceError) -> Self {
+        match error {
+            ModServiceError::GameDirectoryUnavailable { path } => {
+                Self::GameDirectoryUnavailable { path }
+            }
+            ModServiceError::Failed { message } => Self::Failed { message },
+        }
+    }
+}
+
+impl From<AssignCharacterError> for String {
+    fn from(error: AssignCharacterError) -> Self {
+        error.to_string()
+    }
+}
+
+impl std::fmt::Display for AssignCharacterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::GameRunning { mods } => match mods.as_slice() {
+                [only] => write!(
+                    f,
+                    "{}",
+                    t(
+                        "error.modEnabledWhileGameRunning",
+                        &[("title", &only.title)]
+                    )
+                ),
+                _ => write!(
+                    f,
+                    "{}",
+                    tn("error.modsEnabledWhileGameRunning", mods.len(), &[])
+                ),
+            },
+            Self::UnknownCostume { character, costume } => write!(
+                f,
+                "{}",
+                t(
+                    "error.unknownCostume",
+                    &[("character", character), ("costume", costume)],
+                )
+            ),
+            Self::GameDirectoryUnavailable { path } => {
+                write!(f, "{}", game_directory_unavailable(path))
+            }
+            Self::Failed { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+/// What deleting one mod removes
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AppStats {
-    pub total_mods: usize,
-    pub enabled_mods: usize,
-    pub disabled_mods: usize,
-    pub total_size: u64,
+pub struct ModDeletePlan {
+    pub mod_id: String,
+    pub title: String,
+    /// The mod's paks and companion files, less any shared with another mod
+    pub files: Vec<PathBuf>,
+    pub metadata_file: Option<PathBuf>,
+    pub thumbnail: Option<PathBuf>,
+    /// The uncropped original the thumbnail was made from
+    pub thumbnail_source: Option<PathBuf>,
 }
 
+/// A file a mod being deleted lists that other mods list too. Kept, since
+/// deleting it would break them.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CategoryStats {
-    pub category: ModCategory,
-    pub count: usize,
-    pub enabled: usize,
-    pub disabled: usize,
+pub struct SharedModFile {
+    pub path: PathBuf,
+    /// The mods, outside the ones being deleted, that also list it
+    pub mod_ids: Vec<String>,
+}
+
+/// Everything `delete_mods` would remove for a set of mods, for the delete
+/// confirmation. `delete_mods` removes nothing beyond what this lists.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletePreview {
+    pub mods: Vec<ModDeletePlan>,
+    /// Folders left empty by the delete and removed with it, children first
+    pub folders: Vec<PathBuf>,
+    pub shared_files: Vec<SharedModFile>,
+    /// Requested ids no installed or disabled mod has
+    pub missing: Vec<String>,
 }
 
+/// One installed copy in a [`DuplicateModGroup`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct CharacterStats {
-    pub character: Character,
-    pub count: usize,
-    pub enabled: usize,
-    pub disabled: usize,
+pub struct DuplicateModCopy {
+    pub mod_id: String,
+    pub title: String,
+    pub file_path: PathBuf,
+    pub pak_size: u64,
+    /// The pak and its companions
+    pub file_size: u64,
+    pub enabled: bool,
+    pub install_date: DateTime<Utc>,
 }
 
-// ===== Skip Intros Status =====
+/// Mods installed under different IDs whose .pak files are byte for byte
+/// the same. The copy suggested for keeping comes first: an enabled one,
+/// else the most recently installed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct SkipIntrosStatus {
-    pub installed: bool,
-    pub has_backup: bool,
+pub struct DuplicateModGroup {
+    /// SHA-256 of the pak all of them share
+    pub pak_hash: String,
+    pub mods: Vec<DuplicateModCopy>,
 }
 
-// ===== Mod Conflicts =====
-// A single clash: two or more enabled mods overriding the same game assets.
-// Only mods from *different* families are reported (a parent mod and its own
-// add-on are expected to share assets and are not a conflict).
+/// One file of a deleted mod, and the name the trash keeps it under
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ModConflict {
-    // Mods involved in this clash, in game load order (first = wins).
-    pub mods: Vec<ConflictMod>,
-    // Human-readable asset kinds that overlap (e.g. "Body Mesh", "Body Skin",
-    // "Hair", "Face", "Outfit"), de-duplicated and sorted.
-    pub kinds: Vec<String>,
-    // The raw shared asset names (file stems), for the detail view.
-    pub assets: Vec<String>,
+pub struct TrashedFile {
+    pub original_path: PathBuf,
+    /// Name inside the mod's trash folder; numbered, since a pak and its
+    /// metadata can come from folders with files of the same name
+    pub trashed_name: String,
+    pub size: u64,
 }
 
+/// A deleted mod waiting in `trash/<mod_id>/` to be restored or purged,
+/// as its manifest records it
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ConflictMod {
-    pub id: String,
+pub struct TrashedMod {
+    pub mod_id: String,
     pub title: String,
-    // True if this mod loads first and therefore wins the shared assets.
-    pub wins: bool,
+    pub deleted_at: DateTime<Utc>,
+    pub files: Vec<TrashedFile>,
+}
+
+// ===== Mod Info =====
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModInfo {
+    pub id: String,
+    pub name: String,
+    pub category: ModCategory,
+    pub character: Option<Character>,
+    pub enabled: bool,
+    /// Whether the game loads this copy of the files
+    #[serde(default)]
+    pub location: ModLocation,
+    /// Set on the second copy of a mod found both enabled and disabled in
+    /// place, to the ID of the copy listed first; the two share an ID
+    /// otherwise. Cleared by `resolve_duplicate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<String>,
+    pub is_favorite: bool,
+    pub disabled_reason: Option<DisabledReason>,
+    pub file_path: PathBuf,
+    pub thumbnail_path: Option<PathBuf>,
+    pub metadata: ModMetadata,
+    // Total on-disk size of the mod: .pak plus .ucas/.utoc companions.
+    pub file_size: u64,
+    // Size of the .pak alone (what file_size reported before it included companions).
+    pub pak_size: u64,
+    // Per-file sizes for every entry in associated_files, for the detail panel.
+    pub associated_file_sizes: Vec<AssociatedFileSize>,
+    pub install_date: DateTime<Utc>,
+    pub last_modified: DateTime<Utc>,
+    pub original_file_name: String,
+    pub associated_files: Vec<PathBuf>,
+}
+
+/// Where a mod's files are as far as the game is concerned
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModLocation {
+    /// Loaded by the game
+    #[default]
+    Active,
+    /// In disabled-mods, or renamed in place so the game skips it
+    Disabled,
+}
+
+impl ModLocation {
+    pub fn from_enabled(enabled: bool) -> Self {
+        if enabled {
+            Self::Active
+        } else {
+            Self::Disabled
+        }
+    }
+}
+
+/// Filter over the mod list; every field that is set must match
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ModQuery {
+    /// Text in the title, description, tags, author, character or file
+    /// name, ignoring case and with hyphens and underscores read as spaces,
+    /// so "spider man" finds Spider-Man mods
+    pub search: Option<String>,
+    pub category: Option<ModCategory>,
+    pub character: Option<Character>,
+    pub enabled: Option<bool>,
+    pub favorite: Option<bool>,
+    pub nsfw: Option<bool>,
+    /// [`DisabledReason::kind`] of disabled mods, e.g. `"gameUpdateQuarantine"`
+    pub disabled_reason: Option<String>,
+}
+
+impl ModQuery {
+    pub fn matches(&self, mod_info: &ModInfo) -> bool {
+        if let Some(search) = self.search.as_deref().map(fold_search_text) {
+            let metadata = &mod_info.metadata;
+            let character = metadata.character.as_ref().map(Character::to_string);
+            let found = [
+                Some(metadata.title.as_str()),
+                Some(metadata.description.as_str()),
+                metadata.author.as_deref(),
+                character.as_deref(),
+                Some(mod_info.original_file_name.as_str()),
+            ]
+            .into_iter()
+            .flatten()
+            .chain(metadata.tags.iter().map(String::as_str))
+            .any(|field| fold_search_text(field).contains(&search));
+            if !found {
+                return false;
+            }
+        }
+        if self
+            .category
+            .as_ref()
+            .is_some_and(|c| &mod_info.metadata.category != c)
+        {
+            return false;
+        }
+        if self
+            .character
+            .as_ref()
+            .is_some_and(|c| mod_info.metadata.character.as_ref() != Some(c))
+        {
+            return false;
+        }
+        if self
+            .favorite
+            .is_some_and(|favorite| mod_info.is_favorite != favorite)
+            || self
+                .nsfw
+                .is_some_and(|nsfw| mod_info.metadata.is_nsfw != nsfw)
+        {
+            return false;
+        }
+        if self.disabled_reason.as_deref().is_some_and(|kind| {
+            mod_info.disabled_reason.as_ref().map(DisabledReason::kind) != Some(kind)
+        }) {
+            return false;
+        }
+        self.enabled
+            .is_none_or(|enabled| mod_info.enabled == enabled)
+    }
+}
+
+/// Lowercase `text` with hyphens and underscores as spaces and runs of
+/// whitespace collapsed, for [`ModQuery::search`]
+fn fold_search_text(text: &str) -> String {
+    text.to_lowercase()
+        .replace(['-', '_'], " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One page of the mods matching a [`ModQuery`]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModPage {
+    pub mods: Vec<ModInfo>,
+    /// Mods matching the query, across all pages
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssociatedFileSize {
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+/// What an install actually did, for the post-install summary
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallResult {
+    pub mod_info: ModInfo,
+    /// Every file written to ~mods, with the size copied
+    pub copied_files: Vec<AssociatedFileSize>,
+    /// Companion files (`X.utoc`, `X.ucas`) the source pak needed but didn't
+    /// have next to it
+    pub missing_companions: Vec<String>,
+    /// Anything the user should know: a likely broken IoStore mod, a folder
+    /// name that had to be cleaned up, a file that replaced an existing one
+    pub warnings: Vec<String>,
+}
+
+/// A mod after a bulk enable/disable or move. Moving a mod changes its
+/// path-based ID, so `previous_id` is what the caller knew it as.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToggledMod {
+    pub previous_id: String,
+    pub mod_info: ModInfo,
+}
+
+/// A mod a bulk enable/disable couldn't move, and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToggleFailure {
+    pub mod_id: String,
+    pub reason: String,
+}
+
+/// What happened to every mod in a bulk enable/disable
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkToggleReport {
+    pub toggled: Vec<ToggledMod>,
+    pub failed: Vec<ToggleFailure>,
+}
+
+/// A mod whose folder doesn't match the Category/Character/Title layout its
+/// metadata implies, usually after files were moved by hand
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutDrift {
+    pub mod_id: String,
+    pub name: String,
+    pub current_folder: PathBuf,
+    pub expected_folder: PathBuf,
+}
+
+/// Text field of `ModMetadata` that library-wide find-and-replace works on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MetadataTextField {
+    Title,
+    Description,
+    Author,
+    Tags,
+}
+
+/// One mod's field before and after a find-and-replace. Tags are shown
+/// comma-separated; an author replaced with nothing is cleared.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataReplacement {
+    pub mod_id: String,
+    pub name: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// A mod after its favorite flag was flipped, with the new number of
+/// favorites so the sidebar badge can update without a refetch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FavoriteToggle {
+    pub mod_info: ModInfo,
+    pub favorites_count: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScanWarningKind {
+    /// Files with the read-only attribute, typically restored from a backup
+    ReadOnly,
+    /// A metadata file (edited by hand) that doesn't parse or doesn't pass
+    /// validation
+    InvalidMetadata,
+}
+
+/// Something about a mod on disk the user should know about, even though the
+/// scan itself succeeded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanWarning {
+    pub mod_id: String,
+    pub kind: ScanWarningKind,
+    pub paths: Vec<PathBuf>,
+    pub message: String,
+}
+
+/// What a bulk enable/disable would move, computed without touching disk
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TogglePreview {
+    pub enable_count: usize,
+    pub disable_count: usize,
+    /// Bytes moving from the disabled folder into ~mods
+    pub bytes_to_enable: u64,
+    /// Bytes moving from ~mods into the disabled folder
+    pub bytes_to_disable: u64,
+    /// ~mods and the disabled folder are on different volumes, so every
+    /// move is a copy followed by a delete
+    pub cross_volume: bool,
+    /// Extra space needed mid-apply: files are copied one at a time before
+    /// their source is removed, so the largest file moved. 0 when moves are
+    /// plain renames.
+    pub peak_temporary_bytes: u64,
+}
+
+/// A mod whose files ended up on both sides of the enable/disable divide,
+/// usually after a crash mid-toggle. `mod_id` is the ID of the pak where it
+/// sits now.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitMod {
+    pub mod_id: String,
+    pub name: String,
+    pub pak_path: PathBuf,
+    pub pak_enabled: bool,
+    pub enabled_files: Vec<PathBuf>,
+    pub disabled_files: Vec<PathBuf>,
+    /// The pak is alone on one side and all its companions sit together on
+    /// the other, so there is only one way to put it back together
+    pub auto_repairable: bool,
+}
+
+/// Which side a split mod is reunited on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitSide {
+    Enabled,
+    Disabled,
+}
+
+/// Result of the startup pass over split mods
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitRepairReport {
+    pub repaired: usize,
+    /// Split mods left alone, for the user to resolve
+    pub unresolved: Vec<SplitMod>,
+}
+
+/// What the path-ID migration found for one mod
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IdMigrationOutcome {
+    /// Metadata moved (or, in a dry run, would move) from the old ID
+    Migrated,
+    /// Metadata is already stored under the path-based ID
+    AlreadyMigrated,
+    /// Nothing under either ID; there is no metadata to recover
+    NoOldMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdMigrationEntry {
+    pub mod_id: String,
+    pub old_id: String,
+    pub name: String,
+    pub outcome: IdMigrationOutcome,
+}
+
+/// Result of migrating metadata from filename-based to path-based IDs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdMigrationReport {
+    pub dry_run: bool,
+    /// Mods finished from the checkpoint of an interrupted earlier run
+    pub resumed_count: usize,
+    pub migrated_count: usize,
+    pub already_migrated_count: usize,
+    pub not_found_count: usize,
+    pub entries: Vec<IdMigrationEntry>,
+}
+
+/// What merging duplicate character folders did with one mod folder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FolderMergeAction {
+    /// Moved into the kept character folder
+    Moved,
+    /// Same files as the same-named folder already there, so deleted
+    DuplicateRemoved,
+    /// Differs from the same-named folder already there; moved in under a
+    /// suffixed name for the user to pick one
+    KeptBoth,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderMergeEntry {
+    pub source: PathBuf,
+    /// Where it went; for `DuplicateRemoved`, the copy that was kept
+    pub destination: PathBuf,
+    pub action: FolderMergeAction,
+    /// For `KeptBoth`, the same-named folder it collided with
+    pub collided_with: Option<PathBuf>,
+}
+
+/// Result of the startup pass merging duplicate character folders
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderMergeReport {
+    /// Duplicate character folders emptied and removed
+    pub merged_folders: usize,
+    pub actions: Vec<FolderMergeEntry>,
+}
+
+/// A mod disabled the way older managers did it: renamed with a `.disabled`
+/// suffix but left in ~mods, where the game may still load it. `mod_id` is
+/// the ID of the suffixed pak where it sits now.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyDisabledMod {
+    pub mod_id: String,
+    pub name: String,
+    /// The mod's files as found, suffixed or not
+    pub files: Vec<PathBuf>,
+    /// Where the pak goes in disabled-mods, without the suffix
+    pub target_path: PathBuf,
+    /// Set when the conversion ran and failed for this mod
+    pub error: Option<String>,
+}
+
+/// Result of converting legacy `.disabled` files
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyDisabledReport {
+    pub dry_run: bool,
+    pub converted: usize,
+    pub mods: Vec<LegacyDisabledMod>,
+}
+
+/// How disabling takes a mod out of the game's reach
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisableStrategy {
+    /// Move the files into the app's disabled-mods folder
+    #[default]
+    Move,
+    /// Rename `.pak` to `.pakdisabled` (companions likewise) where it sits in
+    /// ~mods. Nothing is copied, which matters for big mods when the app data
+    /// folder is on another drive.
+    RenameInPlace,
+}
+
+/// One disabled mod moved between the two disabled layouts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisableStrategyConversion {
+    /// ID before the conversion
+    pub mod_id: String,
+    pub name: String,
+    pub files: Vec<PathBuf>,
+    /// Where the pak ends up
+    pub target_path: PathBuf,
+    /// Set when the conversion ran and failed for this mod
+    pub error: Option<String>,
+}
+
+/// Result of switching the library to another disable strategy
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisableStrategyReport {
+    pub strategy: DisableStrategy,
+    pub dry_run: bool,
+    pub converted: usize,
+    pub mods: Vec<DisableStrategyConversion>,
+}
+
+/// Result of folding the Electron-era `thumbnails/` folder into per-mod
+/// thumbnails
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbnailConsolidationReport {
+    /// Mods that got their thumbnail from the legacy folder
+    pub migrated: usize,
+    /// Leftovers that matched no mod, as moved into `unclaimed-thumbnails/`
+    pub unclaimed: Vec<PathBuf>,
+}
+
+// ===== Collections =====
+/// A named, ordered set of mods kept together regardless of whether they're
+/// enabled (unlike profiles, which describe what is enabled)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModCollection {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Member mod IDs in presentation order
+    pub mod_ids: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A mod profile: which mods should be enabled together. Membership is the
+/// `profile_ids` in each mod's metadata, which moves with the mod; `mod_ids`
+/// is read from it when profiles are listed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModProfile {
+    pub id: String,
+    pub name: String,
+    /// Member mod IDs, by title
+    pub mod_ids: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// What applying a profile changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileApplySummary {
+    pub enabled: usize,
+    pub disabled: usize,
+    /// Mods that should have been toggled but couldn't be
+    pub failed: Vec<ToggleFailure>,
+}
+
+// ===== Sorting =====
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SortKey {
+    Title,
+    InstallDate,
+    LastModified,
+    FileSize,
+    Character,
+    EnabledFirst,
+    /// Favorites before the rest, enabled before disabled within each
+    EnabledFavoritesFirst,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SortSpec {
+    pub key: SortKey,
+    #[serde(default)]
+    pub direction: SortDirection,
+}
+
+// ===== App Settings =====
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppSettings {
+    pub game_directory: Option<PathBuf>,
+    pub mod_directory: Option<PathBuf>,
+    pub theme: String,
+    #[serde(default = "default_font")]
+    pub font: String,
+    pub auto_organize: bool,
+    pub auto_detect_game_dir: bool,
+    #[serde(default = "default_auto_check_updates")]
+    pub auto_check_updates: bool,
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+    #[serde(default)]
+    pub global_hotkeys: GlobalHotkeySettings,
+    #[serde(default)]
+    pub background_rescan: BackgroundRescanSettings,
+    /// Add newly installed mods to the last applied profile
+    #[serde(default = "default_auto_assign_to_active_profile")]
+    pub auto_assign_to_active_profile: bool,
+    /// Check Nexus once a day for newer versions of installed mods (needs
+    /// an API key)
+    #[serde(default = "default_auto_check_mod_updates")]
+    pub auto_check_mod_updates: bool,
+    /// When that check last ran. Written by the backend only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_mod_update_check: Option<DateTime<Utc>>,
+    /// Move disabled mods out of ~mods or rename them where they are.
+    /// Changing it only affects mods disabled afterwards;
+    /// `convert_disable_strategy` moves the existing ones over.
+    #[serde(default)]
+    pub disable_strategy: DisableStrategy,
+    /// Disable every enabled mod when a game update is detected, until the
+    /// user releases them
+    #[serde(default)]
+    pub quarantine_on_game_update: bool,
+    /// Send every file the app moves, copies or deletes to the frontend as
+    /// `file-operation` events, for the activity feed
+    #[serde(default)]
+    pub file_activity_events: bool,
+    /// Folders under ~mods the manager leaves alone, relative to it. Glob
+    /// patterns such as `_backup` or `Tools/*` are allowed.
+    #[serde(default)]
+    pub ignored_paths: Vec<String>,
+    /// How many folder levels below ~mods scans, organizing and cleanup
+    /// look. For libraries with deeply nested backups on slow drives; None
+    /// looks everywhere.
+    #[serde(default)]
+    pub max_scan_depth: Option<usize>,
+    /// Refuse every change to mods, metadata, profiles and collections.
+    /// Only changed by `enter_read_only_mode`/`exit_read_only_mode`.
+    #[serde(default)]
+    pub read_only_mode: bool,
+    /// Characters the user doesn't play, whose mods library cleanup
+    /// suggests deleting
+    #[serde(default)]
+    pub hidden_characters: Vec<Character>,
+    /// Folder names game detection also looks for under the usual install
+    /// roots, for regional installs named other than the defaults
+    #[serde(default)]
+    pub game_folder_names: Vec<String>,
+    /// How `game_directory` was found, so diagnostics show where real
+    /// installs come from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_directory_source: Option<DetectionSource>,
+}
+
+/// One part of a full library scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanPhase {
+    /// `thumbnails`, `mods` or `disabled`
+    pub name: String,
+    pub files: usize,
+    pub directories: usize,
+    pub duration_ms: u64,
+}
+
+/// What the last full scan visited and how long it took, to tell a large
+/// library from a slow disk or deeply nested backups
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanProfile {
+    pub scanned_at: DateTime<Utc>,
+    /// The depth limit the scan ran with
+    pub max_scan_depth: Option<usize>,
+    pub files: usize,
+    pub directories: usize,
+    pub duration_ms: u64,
+    pub phases: Vec<ScanPhase>,
+}
+
+/// How long one kind of operation took over its recent runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationMetrics {
+    pub operation: String,
+    /// Runs these numbers cover
+    pub samples: usize,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub max_ms: u64,
+    pub last_ms: u64,
+    /// How many things the last run handled, to tell a bigger library from
+    /// a slower app
+    pub last_items: usize,
+    pub last_at: DateTime<Utc>,
+}
+
+/// Recent operation timings with the last scan's profile, all kept locally
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerformanceMetrics {
+    pub operations: Vec<OperationMetrics>,
+    pub scan_profile: Option<ScanProfile>,
+}
+
+/// Background hashing of mod files after a scan, sent as `hashing-progress`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashingProgress {
+    pub done: usize,
+    pub total: usize,
+    /// The file being hashed; None once the run is over
+    pub current: Option<PathBuf>,
+}
+
+/// Periodic reconciliation for when the file watcher misses events (network
+/// drives, DrivePool). Polls a cheap directory fingerprint and only rescans
+/// when it changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BackgroundRescanSettings {
+    pub enabled: bool,
+    pub interval_minutes: u64,
+    // Keep polling even while the watcher reports no errors; for setups that
+    // drop events silently.
+    pub run_when_watcher_healthy: bool,
+}
+
+impl Default for BackgroundRescanSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: 5,
+            run_when_watcher_healthy: false,
+        }
+    }
+}
+
+/// System-wide shortcuts that work while the game has focus.
+/// `toggle_safe_mode` uses the global-shortcut accelerator syntax.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GlobalHotkeySettings {
+    pub enabled: bool,
+    pub toggle_safe_mode: String,
+}
+
+impl Default for GlobalHotkeySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            toggle_safe_mode: "Ctrl+Shift+M".to_string(),
+        }
+    }
+}
+
+/// Which backend events raise a native notification. Notifications are only
+/// shown while the main window is unfocused, so these toggles control the
+/// "tell me when it's done" behaviour rather than in-app toasts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NotificationSettings {
+    pub on_install_complete: bool,
+    pub on_extraction_complete: bool,
+    pub on_game_update_detected: bool,
+    pub on_mod_update_available: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            on_install_complete: true,
+            on_extraction_complete: false,
+            on_game_update_detected: true,
+            on_mod_update_available: true,
+        }
+    }
+}
+
+fn default_font() -> String {
+    "quicksand".to_string()
+}
+
+fn default_auto_check_updates() -> bool {
+    true
+}
+
+fn default_auto_assign_to_active_profile() -> bool {
+    true
+}
+
+fn default_auto_check_mod_updates() -> bool {
+    true
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            game_directory: None,
+            mod_directory: None,
+            theme: "dark".to_string(),
+            font: "quicksand".to_string(),
+            auto_organize: true,
+            auto_detect_game_dir: true,
+            auto_check_updates: true,
+            notifications: NotificationSettings::default(),
+            global_hotkeys: GlobalHotkeySettings::default(),
+            background_rescan: BackgroundRescanSettings::default(),
+            auto_assign_to_active_profile: true,
+            auto_check_mod_updates: true,
+            last_mod_update_check: None,
+            disable_strategy: DisableStrategy::Move,
+            quarantine_on_game_update: false,
+            file_activity_events: false,
+            ignored_paths: Vec::new(),
+            max_scan_depth: None,
+            read_only_mode: false,
+            hidden_characters: Vec::new(),
+            game_folder_names: Vec::new(),
+            game_directory_source: None,
+        }
+    }
+}
+
+// ===== Progress Types =====
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModInstallProgress {
+    pub current_file: String,
+    pub current: usize,
+    pub total: usize,
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModOrganizationProgress {
+    pub current_file: String,
+    pub current: usize,
+    pub total: usize,
+    pub status: String,
+    pub moved_count: usize,
+    pub error_count: usize,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryCopyProgress {
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryCopySummary {
+    pub files_copied: usize,
+    pub bytes_copied: u64,
+    /// Milliseconds
+    pub duration: u64,
+    /// Entries left out: unreadable ones, symlinks and other special files
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Where organizing would move a loose mod, and why it's going there
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizeMove {
+    pub mod_id: String,
+    pub title: String,
+    pub source_path: PathBuf,
+    pub destination_path: PathBuf,
+    pub category: ModCategory,
+    pub character: Option<Character>,
+    /// How sure detection was of the category and character, the lower of
+    /// the two; Low when the metadata disagrees with detection
+    pub confidence: DetectionConfidence,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrganizationResult {
+    pub total_mods: usize,
+    pub moved_mods: usize,
+    pub error_count: usize,
+    pub errors: Vec<String>,
+    pub duration: u64,
+}
+
+// ===== Statistics Types =====
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStats {
+    pub total_mods: usize,
+    pub enabled_mods: usize,
+    pub disabled_mods: usize,
+    pub total_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryStats {
+    pub category: ModCategory,
+    pub count: usize,
+    pub enabled: usize,
+    pub disabled: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharacterStats {
+    /// `None` for mods not assigned to a character
+    pub character: Option<Character>,
+    pub count: usize,
+    pub enabled: usize,
+    pub disabled: usize,
+}
+
+/// A count taken from a subsystem's last result rather than recomputed.
+/// `stale` means the library (or the clock) has moved on since.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CachedCount {
+    pub count: usize,
+    pub stale: bool,
+}
+
+/// Everything the dashboard shows, in one call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibrarySummary {
+    #[serde(flatten)]
+    pub totals: AppStats,
+    pub categories: Vec<CategoryStats>,
+    /// The five characters with the most mods, most first
+    pub top_characters: Vec<CharacterStats>,
+    pub missing_thumbnails: usize,
+    /// Conflict groups found by the last conflict scan
+    pub conflicts: CachedCount,
+    pub pending_updates: CachedCount,
+    pub last_install: Option<DateTime<Utc>>,
+    /// Ignored folders under ~mods that still hold paks. The game loads
+    /// them, the library doesn't show them.
+    pub ignored_folders_with_paks: usize,
+    /// Mods found both enabled and disabled in place, waiting for
+    /// `resolve_duplicate`
+    pub unresolved_duplicates: usize,
+    /// The game folder is configured but missing (e.g. an unplugged drive);
+    /// the counts are from the last listing before it went away
+    pub offline: bool,
+}
+
+// ===== Skip Intros Status =====
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkipIntrosStatus {
+    pub installed: bool,
+    pub has_backup: bool,
+}
+
+// ===== Mod Conflicts =====
+// A single clash: two or more mods overriding the same game assets. Only
+// enabled mods are compared unless disabled ones are asked for.
+// Only mods from *different* families are reported (a parent mod and its own
+// add-on are expected to share assets and are not a conflict).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModConflict {
+    // Mods involved in this clash, in game load order (first = wins).
+    pub mods: Vec<ConflictMod>,
+    // Human-readable asset kinds that overlap (e.g. "Body Mesh", "Body Skin",
+    // "Hair", "Face", "Outfit"), de-duplicated and sorted.
+    pub kinds: Vec<String>,
+    // The raw shared asset names (file stems), for the detail view.
+    pub assets: Vec<String>,
+    pub severity: ConflictSeverity,
+}
+
+// How much of the mods a clash covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictSeverity {
+    // Every asset of at least one of the mods is shared, so whichever of
+    // them loses has no effect in game at all
+    Full,
+    // The mods also change assets of their own
+    Partial,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictMod {
+    pub id: String,
+    pub title: String,
+    // True if this mod loads first and therefore wins the shared assets.
+    pub wins: bool,
+    // False for disabled mods, included when asked for with
+    // `include_disabled`; they never win
+    pub enabled: bool,
+}
+
+// ===== Pak Technical Info =====
+// Raw values from a mod's .pak footer and .utoc header, for diagnosing mods
+// that install fine but never load in game. A mod built for an older game
+// version often mounts to a path the game no longer reads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PakTechnicalInfo {
+    pub mod_id: String,
+    pub pak_path: PathBuf,
+    // Pak file format version from the footer
+    pub pak_version: Option<u32>,
+    // None when the pak index is encrypted
+    pub pak_mount_point: Option<String>,
+    pub utoc_path: Option<PathBuf>,
+    // IoStore TOC format version (the header byte after the magic)
+    pub toc_version: Option<u8>,
+    pub utoc_mount_point: Option<String>,
+    // The mount point most working (enabled) mods in the library share, when
+    // enough of them agree to call anything else unusual
+    pub common_mount_point: Option<String>,
+    // Mounts somewhere other than `common_mount_point`
+    pub mount_point_outlier: bool,
+    // What could not be read, e.g. a missing or truncated .utoc
+    pub errors: Vec<String>,
+}
+
+impl PakTechnicalInfo {
+    /// The mount point the game uses: the IoStore one when there is a .utoc
+    pub fn mount_point(&self) -> Option<&str> {
+        self.utoc_mount_point
+            .as_deref()
+            .or(self.pak_mount_point.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ModVerificationKind {
+    // Mount point differs from what the library's working mods use
+    PossiblyOutdated,
+}
+
+// Something verify_mods found that may stop a mod from loading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModVerificationIssue {
+    pub mod_id: String,
+    pub title: String,
+    pub kind: ModVerificationKind,
+    pub message: String,
+    pub info: PakTechnicalInfo,
+}
+
+// Free and total space of the volume holding `path`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeUsage {
+    pub path: PathBuf,
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+}
+
+// Space on the drives the app writes to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+    // None until a game folder is set
+    pub mods_volume: Option<VolumeUsage>,
+    pub app_data_volume: VolumeUsage,
+}
+
+// A mod listed before whose files are no longer all on disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModIntegrityIssue {
+    pub mod_id: String,
+    pub title: String,
+    pub enabled: bool,
+    // The pak is there and only companions are gone
+    pub pak_present: bool,
+    pub missing_files: Vec<PathBuf>,
+}
+
+// What verify_mods_integrity found gone since the mods were last listed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModsIntegrityReport {
+    pub mods_directory: PathBuf,
+    // ~mods itself was gone, as when a game patch wipes Paks
+    pub mods_directory_missing: bool,
+    pub issues: Vec<ModIntegrityIssue>,
+    // Enabled mods in `issues` with a copy in disabled-mods that
+    // restore_mods_directory can put back
+    pub recoverable: Vec<String>,
+}
+
+impl ModsIntegrityReport {
+    pub fn has_problems(&self) -> bool {
+        self.mods_directory_missing || !self.issues.is_empty()
+    }
+}
+
+// Whether an installed mod's pak would load: `error` when the pak is
+// corrupted or incomplete, `warnings` for companions that don't pair up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PakValidation {
+    pub mod_id: String,
+    pub title: String,
+    pub pak_path: PathBuf,
+    pub error: Option<String>,
+    pub warnings: Vec<String>,
+}
+
+impl PakValidation {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none() && self.warnings.is_empty()
+    }
+}
+
+// ===== Storage Breakdown =====
+// Disk usage of the mod library grouped category -> character -> costume, for
+// the treemap view. Sizes include .ucas/.utoc companions, not just the .pak.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageBreakdown {
+    pub total_bytes: u64,
+    pub enabled_bytes: u64,
+    pub disabled_bytes: u64,
+    pub mod_count: usize,
+    pub categories: Vec<StorageNode>,
+    // Uncropped originals kept for re-cropping thumbnails. Not part of the
+    // totals above; clear_thumbnail_sources frees them.
+    pub thumbnail_source_count: usize,
+    pub thumbnail_source_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageNode {
+    // Display label, e.g. "Skins", "Spider-Man", or a costume id.
+    pub name: String,
+    pub total_bytes: u64,
+    pub enabled_bytes: u64,
+    pub disabled_bytes: u64,
+    pub mod_count: usize,
+    // Next level down (characters under a category, costumes under a
+    // character). Empty at the leaves.
+    pub children: Vec<StorageNode>,
+}
+
+impl StorageNode {
+    pub fn add(&mut self, bytes: u64, enabled: bool) {
+        self.total_bytes += bytes;
+        if enabled {
+            self.enabled_bytes += bytes;
+        } else {
+            self.disabled_bytes += bytes;
+        }
+        self.mod_count += 1;
+    }
+
+    pub fn child(&mut self, name: &str) -> &mut StorageNode {
+        let index = match self.children.iter().position(|c| c.name == name) {
+            Some(index) => index,
+            None => {
+                self.children.push(StorageNode {
+                    name: name.to_string(),
+                    ..Default::default()
+                });
+                self.children.len() - 1
+            }
+        };
+        &mut self.children[index]
+    }
+
+    pub fn sort_by_size(&mut self) {
+        self.children
+            .sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes).then(a.name.cmp(&b.name)));
+        for child in &mut self.children {
+            child.sort_by_size();
+        }
+    }
+}
+
+/// One mod (or stray set of files) sitting in disabled-mods
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisabledModEntry {
+    // None for .ucas/.utoc files left without their pak
+    pub mod_id: Option<String>,
+    pub title: String,
+    pub files: Vec<PathBuf>,
+    pub size: u64,
+    // When it was disabled. Mods disabled before that was recorded fall back
+    // to when their metadata file was last written (`disabled_since_estimated`),
+    // which can only make them look more recent than they are.
+    pub disabled_since: Option<DateTime<Utc>>,
+    pub disabled_since_estimated: bool,
+    pub disabled_days: Option<i64>,
+    pub disabled_reason: Option<DisabledReason>,
+    pub has_metadata: bool,
+}
+
+/// Result of purging long-disabled mods
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisabledPurgeReport {
+    pub dry_run: bool,
+    pub purged: Vec<DisabledModEntry>,
+    pub bytes_reclaimed: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_metadata() -> ModMetadata {
+        let date = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        ModMetadata {
+            title: "Sample".to_string(),
+            subtitle: None,
+            description: String::new(),
+            author: None,
+            version: None,
+            tags: Vec::new(),
+            category: ModCategory::Skins,
+            character: None,
+            costume: None,
+            is_favorite: false,
+            is_nsfw: false,
+            created_at: date,
+            updated_at: date,
+            install_date: date,
+            profile_ids: None,
+            nexus_mod_id: None,
+            nexus_file_id: None,
+            nexus_version: None,
+            original_folder_path: None,
+            parent_mod_id: None,
+            disabled_reason: None,
+            disabled_at: None,
+            last_verified_game_build: None,
+            manage_layout: true,
+            file_fingerprint: None,
+            externally_modified_at: None,
+            collision_folder: None,
+            priority: None,
+            notes: None,
+            history: Vec::new(),
+            variant_group: None,
+        }
+    }
+
+    #[test]
+    fn test_mod_info_size_fields_serialize_camel_case() {
+        let metadata = sample_metadata();
+        let info = ModInfo {
+            id: "abc".to_string(),
+            name: "Sample".to_string(),
+            category: ModCategory::Skins,
+            character: None,
+            enabled: true,
+            location: ModLocation::Active,
+            duplicate_of: None,
+            is_favorite: false,
+            disabled_reason: None,
+            file_path: PathBuf::from("Sample_P.pak"),
+            thumbnail_path: None,
+            file_size: 1_010,
+            pak_size: 10,
+            associated_file_sizes: vec![
+                AssociatedFileSize {
+                    path: PathBuf::from("Sample_P.pak"),
+                    size: 10,
+                },
+                AssociatedFileSize {
+                    path: PathBuf::from("Sample_P.ucas"),
+                    size: 1_000,
+                },
+            ],
+            install_date: metadata.install_date,
+            last_modified: metadata.install_date,
+            original_file_name: "Sample_P.pak".to_string(),
+            associated_files: vec![
+                PathBuf::from("Sample_P.pak"),
+                PathBuf::from("Sample_P.ucas"),
+            ],
+            metadata,
+        };
+
+        let json = serde_json::to_value(&info).unwrap();
+
+        // fileSize is the whole file set, pakSize keeps the old .pak-only number
+        assert_eq!(json["fileSize"], 1_010);
+        assert_eq!(json["pakSize"], 10);
+        assert_eq!(json["associatedFileSizes"][1]["path"], "Sample_P.ucas");
+        assert_eq!(json["associatedFileSizes"][1]["size"], 1_000);
+    }
+
+    #[test]
+    fn test_unknown_character_and_category_round_trip() {
+        let mut value = serde_json::to_value(sample_metadata()).unwrap();
+        value["title"] = "Keep My Title".into();
+        value["character"] = "Future Hero".into();
+        value["category"] = "Crosshairs".into();
+
+        let metadata: ModMetadata = serde_json::from_value(value).unwrap();
+        assert_eq!(metadata.title, "Keep My Title");
+        assert_eq!(
+            metadata.character,
+            Some(Character::Unknown("Future Hero".to_string()))
+        );
+        assert_eq!(
+            metadata.category,
+            ModCategory::Unknown("Crosshairs".to_string())
+        );
+        assert_eq!(
+            metadata.character.as_ref().unwrap().to_string(),
+            "Future Hero"
+        );
+
+        // Re-saving writes the raw strings back unchanged
+        let json = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(json["character"], "Future Hero");
+        assert_eq!(json["category"], "Crosshairs");
+
+        // Known names still map to their variants
+        let known: Character = serde_json::from_str("\"Captain America\"").unwrap();
+        assert_eq!(known, Character::CaptainAmerica);
+    }
+
+    #[test]
+    fn test_other_category_is_never_detected() {
+        let other: ModCategory = serde_json::from_str("\"Other\"").unwrap();
+        assert_eq!(other, ModCategory::Other);
+        assert_eq!(serde_json::to_value(&other).unwrap(), "Other");
+        assert!(other.keywords().is_empty());
+    }
 }
@@ -0,0 +1,204 @@
+//! Windows besides the main one. The mod details window shows one mod next
+//! to the grid for people with room for both; commands and app-wide events
+//! (`mods-directory-changed`, `settings-changed`) work the same from either
+//! window. Each window's size and position are saved in
+//! preferences under its label when it closes and restored when it opens.
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Emitter, LogicalPosition, LogicalSize, Manager, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder, WindowEvent,
+};
+
+pub const MAIN_WINDOW: &str = "main";
+pub const MOD_DETAIL_WINDOW: &str = "mod-detail";
+
+/// Where a window was and how big, in logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowState {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+/// Percent-encode a query parameter value
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"-_.~".contains(&b) {
+                (b as char).to_string()
+            } else {
+                format!("%{b:02X}")
+            }
+        })
+        .collect()
+}
+
+fn saved_state(app: &AppHandle, label: &str) -> Option<WindowState> {
+    crate::read_preferences(app)
+        .ok()?
+        .window_states
+        .get(label)
+        .copied()
+}
+
+/// Whether the top-left corner of `state` is on a connected monitor, so a
+/// window isn't restored onto a screen that has since been unplugged
+fn is_on_screen(window: &WebviewWindow, state: &WindowState) -> bool {
+    let Ok(monitors) = window.available_monitors() else {
+        return false;
+    };
+    monitors.iter().any(|monitor| {
+        let scale = monitor.scale_factor();
+        let position = monitor.position().to_logical::<f64>(scale);
+        let size = monitor.size().to_logical::<f64>(scale);
+        state.x >= position.x
+            && state.y >= position.y
+            && state.x < position.x + size.width
+            && state.y < position.y + size.height
+    })
+}
+
+fn restore_state(window: &WebviewWindow, state: &WindowState) {
+    if is_on_screen(window, state) {
+        let _ = window.set_size(LogicalSize::new(state.width, state.height));
+        let _ = window.set_position(LogicalPosition::new(state.x, state.y));
+    }
+    if state.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// Save where `window` is under its label. A maximized window keeps the
+/// size it will return to; a minimized one isn't saved at all.
+fn save_state(window: &WebviewWindow) -> Result<(), String> {
+    if window.is_minimized().unwrap_or(false) {
+        return Ok(());
+    }
+    let app = window.app_handle();
+    let mut preferences = crate::read_preferences(app)?;
+    let label = window.label().to_string();
+    let maximized = window.is_maximized().unwrap_or(false);
+
+    let state = match preferences.window_states.get(&label) {
+        Some(previous) if maximized => WindowState {
+            maximized,
+            ..*previous
+        },
+        _ => {
+            let scale = window.scale_factor().map_err(|e| e.to_string())?;
+            let position = window
+                .outer_position()
+                .map_err(|e| e.to_string())?
+                .to_logical::<f64>(scale);
+            let size = window
+                .inner_size()
+                .map_err(|e| e.to_string())?
+                .to_logical::<f64>(scale);
+            WindowState {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+                maximized,
+            }
+        }
+    };
+    preferences.window_states.insert(label, state);
+    crate::write_preferences(app, &preferences)
+}
+
+/// Save the window's state whenever it is closed
+fn track_state(window: &WebviewWindow) {
+    let tracked = window.clone();
+    window.on_window_event(move |event| {
+        if let WindowEvent::CloseRequested { .. } = event {
+            if let Err(e) = save_state(&tracked) {
+                log::warn!("[windows] Failed to save {} state: {}", tracked.label(), e);
+            }
+        }
+    });
+}
+
+/// Put the main window back where it was last closed, and keep track of it
+pub fn restore_main_window(app: &AppHandle) {
+    let Some(window) = app.get_webview_window(MAIN_WINDOW) else {
+        return;
+    };
+    if let Some(state) = saved_state(app, MAIN_WINDOW) {
+        restore_state(&window, &state);
+    }
+    track_state(&window);
+}
+
+/// Send a menu event to the main window, where the sidebars and dialogs it
+/// drives live, and bring that window forward. The app menu is shared by
+/// every window, so broadcasting would act in the details window too.
+pub fn emit_to_main(app: &AppHandle, event: &str) {
+    match app.emit_to(MAIN_WINDOW, event, ()) {
+        Ok(_) => log::debug!("Successfully emitted {event} event"),
+        Err(e) => log::error!("Failed to emit {event} event: {e}"),
+    }
+    if let Some(window) = app.get_webview_window(MAIN_WINDOW) {
+        let _ = window.set_focus();
+    }
+}
+
+/// Show a mod in the details window: created on first use at its last size
+/// and position, otherwise switched to this mod (`mod-detail-select`) and
+/// focused
+#[tauri::command]
+pub async fn open_mod_detail_window(app: AppHandle, mod_id: String) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(MOD_DETAIL_WINDOW) {
+        app.emit_to(MOD_DETAIL_WINDOW, "mod-detail-select", &mod_id)
+            .map_err(|e| format!("Failed to show mod in details window: {e}"))?;
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+        return Ok(());
+    }
+
+    let route = format!(
+        "index.html?view={}&modId={}",
+        MOD_DETAIL_WINDOW,
+        encode_query_value(&mod_id)
+    );
+    let state = saved_state(&app, MOD_DETAIL_WINDOW);
+    let mut builder =
+        WebviewWindowBuilder::new(&app, MOD_DETAIL_WINDOW, WebviewUrl::App(route.into()))
+            .title("Mod Details")
+            .min_inner_size(420.0, 600.0);
+    builder = match &state {
+        Some(state) => builder.inner_size(state.width, state.height),
+        None => builder.inner_size(560.0, 900.0).center(),
+    };
+    let window = builder
+        .build()
+        .map_err(|e| format!("Failed to open details window: {e}"))?;
+
+    if let Some(state) = state {
+        restore_state(&window, &state);
+    }
+    track_state(&window);
+    log::info!("[windows] Opened details window for {}", mod_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_query_value() {
+        assert_eq!(encode_query_value("abc-123_x.y"), "abc-123_x.y");
+        assert_eq!(
+            encode_query_value("Skins/Storm & Co?#"),
+            "Skins%2FStorm%20%26%20Co%3F%23"
+        );
+        assert_eq!(encode_query_value("é"), "%C3%A9");
+    }
+}
@@ -0,0 +1,273 @@
+//! Debounced write-behind for small, frequent metadata changes (favorite
+//! toggles, profile membership). Each click used to rewrite the whole
+//! pretty-printed JSON file, which makes cloud-synced app-data folders churn
+//! and occasionally hit sharing violations.
+//!
+//! A deferred change lands in an in-memory overlay that `ModService` reads
+//! through immediately, and is written to disk once the mod has been quiet
+//! for [`DEBOUNCE`]. Every deferred change is also appended to a journal in
+//! the metadata directory, so anything still unflushed when the app dies is
+//! replayed on the next start.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, Once};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::ModMetadata;
+
+/// Quiet period per mod before a deferred change is written
+pub const DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// How often the flusher thread looks for writes that are due
+const TICK: Duration = Duration::from_millis(250);
+
+/// Journal of deferred changes, one JSON entry per line. The `.jsonl`
+/// extension keeps it out of every `*.json` metadata scan.
+pub const JOURNAL_FILE: &str = "pending-writes.jsonl";
+
+struct PendingWrite {
+    metadata_dir: PathBuf,
+    mod_id: String,
+    metadata: ModMetadata,
+    due: Instant,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JournalEntry {
+    mod_id: String,
+    metadata: ModMetadata,
+}
+
+/// Deferred writes keyed by the metadata file they will land in
+static PENDING: Mutex<BTreeMap<PathBuf, PendingWrite>> = Mutex::new(BTreeMap::new());
+
+static FLUSHER: Once = Once::new();
+
+fn lock() -> MutexGuard<'static, BTreeMap<PathBuf, PendingWrite>> {
+    // A panic mid-flush leaves the map itself intact, keep using it
+    PENDING.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn metadata_path(metadata_dir: &Path, mod_id: &str) -> PathBuf {
+    metadata_dir.join(format!("{}.json", mod_id))
+}
+
+fn write_file(path: &Path, metadata: &ModMetadata) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(metadata)
+        .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
+    fs::write(path, json).map_err(|e| format!("Failed to write metadata: {}", e))
+}
+
+/// Rewrite a directory's journal to hold exactly what is still pending there,
+/// or remove it once nothing is.
+fn rewrite_journal(
+    pending: &BTreeMap<PathBuf, PendingWrite>,
+    metadata_dir: &Path,
+) -> Result<(), String> {
+    let journal = metadata_dir.join(JOURNAL_FILE);
+    let mut lines = String::new();
+    for write in pending.values().filter(|w| w.metadata_dir == metadata_dir) {
+        let entry = JournalEntry {
+            mod_id: write.mod_id.clone(),
+            metadata: write.metadata.clone(),
+        };
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| format!("Failed to serialize journal entry: {}", e))?;
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+
+    if lines.is_empty() {
+        return match fs::remove_file(&journal) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to remove metadata journal: {}", e))
+            }
+            _ => Ok(()),
+        };
+    }
+
+    let temp = journal.with_extension("jsonl.tmp");
+    fs::write(&temp, lines).map_err(|e| format!("Failed to write metadata journal: {}", e))?;
+    fs::rename(&temp, &journal).map_err(|e| format!("Failed to replace metadata journal: {}", e))
+}
+
+/// Write every pending entry matching `filter`. Entries that fail to write
+/// stay pending and are retried after another debounce.
+fn flush_where(filter: impl Fn(&PendingWrite) -> bool) -> Result<usize, String> {
+    let mut pending = lock();
+    let keys: Vec<PathBuf> = pending
+        .iter()
+        .filter(|(_, w)| filter(w))
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    let mut written = 0;
+    let mut first_error = None;
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for path in keys {
+        let Some(write) = pending.remove(&path) else {
+            continue;
+        };
+        if !dirs.contains(&write.metadata_dir) {
+            dirs.push(write.metadata_dir.clone());
+        }
+        match write_file(&path, &write.metadata) {
+            Ok(()) => written += 1,
+            Err(e) => {
+                log::warn!("[write-behind] {} ({:?}), will retry", e, path);
+                first_error.get_or_insert(e);
+                pending.insert(
+                    path,
+                    PendingWrite {
+                        due: Instant::now() + DEBOUNCE,
+                        ..write
+                    },
+                );
+            }
+        }
+    }
+
+    for dir in &dirs {
+        rewrite_journal(&pending, dir)?;
+    }
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(written),
+    }
+}
+
+fn start_flusher() {
+    FLUSHER.call_once(|| {
+        std::thread::spawn(|| loop {
+            std::thread::sleep(TICK);
+            let now = Instant::now();
+            if let Err(e) = flush_where(|w| w.due <= now) {
+                log::warn!("[write-behind] Flush failed: {}", e);
+            }
+        });
+    });
+}
+
+/// Queue a metadata change. It is visible through [`pending`] at once and
+/// reaches disk after [`DEBOUNCE`] without further changes to the same mod.
+pub fn defer(metadata_dir: &Path, mod_id: &str, metadata: ModMetadata) -> Result<(), String> {
+    let mut pending = lock();
+
+    // Journal first: if this append fails nothing was promised
+    let entry = JournalEntry {
+        mod_id: mod_id.to_string(),
+        metadata,
+    };
+    let line = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize journal entry: {}", e))?;
+    let mut journal = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(metadata_dir.join(JOURNAL_FILE))
+        .map_err(|e| format!("Failed to open metadata journal: {}", e))?;
+    writeln!(journal, "{}", line)
+        .map_err(|e| format!("Failed to append to metadata journal: {}", e))?;
+
+    pending.insert(
+        metadata_path(metadata_dir, mod_id),
+        PendingWrite {
+            metadata_dir: metadata_dir.to_path_buf(),
+            mod_id: entry.mod_id,
+            metadata: entry.metadata,
+            due: Instant::now() + DEBOUNCE,
+        },
+    );
+    drop(pending);
+
+    start_flusher();
+    Ok(())
+}
+
+/// The not-yet-written metadata for a mod, if a change is pending
+pub fn pending(metadata_dir: &Path, mod_id: &str) -> Option<ModMetadata> {
+    lock()
+        .get(&metadata_path(metadata_dir, mod_id))
+        .map(|w| w.metadata.clone())
+}
+
+/// Write metadata synchronously. Any pending change for the same mod is
+/// superseded and dropped from the journal so a replay can't resurrect it.
+pub fn write_through(
+    metadata_dir: &Path,
+    mod_id: &str,
+    metadata: &ModMetadata,
+) -> Result<(), String> {
+    let mut pending = lock();
+    let path = metadata_path(metadata_dir, mod_id);
+    write_file(&path, metadata)?;
+    if pending.remove(&path).is_some() {
+        rewrite_journal(&pending, metadata_dir)?;
+    }
+    Ok(())
+}
+
+/// Drop a pending change without writing it (the mod is being deleted)
+pub fn discard(metadata_dir: &Path, mod_id: &str) -> Result<(), String> {
+    let mut pending = lock();
+    if pending
+        .remove(&metadata_path(metadata_dir, mod_id))
+        .is_some()
+    {
+        rewrite_journal(&pending, metadata_dir)?;
+    }
+    Ok(())
+}
+
+/// Write everything pending for one metadata directory now. Called before
+/// operations that read, move or rename metadata files on disk directly.
+pub fn flush_dir(metadata_dir: &Path) -> Result<usize, String> {
+    flush_where(|w| w.metadata_dir == metadata_dir)
+}
+
+/// Write everything pending, in every directory. Called on app exit.
+pub fn flush_all() -> Result<usize, String> {
+    flush_where(|_| true)
+}
+
+/// Apply changes left in the journal by a previous run that exited before
+/// flushing. Entries for mods whose metadata file no longer exists are
+/// dropped, and a torn final line from a crash mid-append is ignored.
+/// Returns the number of metadata files rewritten.
+pub fn replay_journal(metadata_dir: &Path) -> Result<usize, String> {
+    let journal = metadata_dir.join(JOURNAL_FILE);
+    let content = match fs::read_to_string(&journal) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(format!("Failed to read metadata journal: {}", e)),
+    };
+
+    // Later entries for the same mod win
+    let mut latest: BTreeMap<String, ModMetadata> = BTreeMap::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str::<JournalEntry>(line) {
+            Ok(entry) => {
+                latest.insert(entry.mod_id, entry.metadata);
+            }
+            Err(e) => log::warn!("[write-behind] Skipping unreadable journal entry: {}", e),
+        }
+    }
+
+    let mut replayed = 0;
+    for (mod_id, metadata) in &latest {
+        let path = metadata_path(metadata_dir, mod_id);
+        if !path.exists() {
+            continue;
+        }
+        write_file(&path, metadata)?;
+        replayed += 1;
+    }
+
+    fs::remove_file(&journal).map_err(|e| format!("Failed to remove metadata journal: {}", e))?;
+    Ok(replayed)
+}
@@ -0,0 +1,198 @@
+//! End-to-end coverage of the archive install pipeline against the sample
+//! archives in `tests/fixtures`: extract → detect → install → scan → disable
+//! → delete, all on temp directories with no Tauri runtime.
+
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use marvel_rivals_mod_manager_lib::archive_extractor::{
+    build_archive_preview, detect_extracted_mods, ArchiveExtractor, ExtractionProgress,
+};
+use marvel_rivals_mod_manager_lib::mod_service::ModService;
+use marvel_rivals_mod_manager_lib::types::{Character, ModCategory, ModMetadata};
+use tempfile::TempDir;
+
+const PAK_NAME: &str = "SampleSkin_9999999_P.pak";
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn metadata() -> ModMetadata {
+    let date: DateTime<Utc> = "2025-01-01T00:00:00Z".parse().unwrap();
+    ModMetadata {
+        title: "Sample Skin".to_string(),
+        subtitle: None,
+        description: "Fixture mod".to_string(),
+        author: Some("Tests".to_string()),
+        version: Some("1.0".to_string()),
+        tags: vec!["fixture".to_string()],
+        category: ModCategory::Skins,
+        character: Some(Character::Groot),
+        costume: None,
+        is_favorite: false,
+        is_nsfw: false,
+        created_at: date,
+        updated_at: date,
+        install_date: date,
+        profile_ids: None,
+        nexus_mod_id: None,
+        nexus_file_id: None,
+        nexus_version: None,
+        original_folder_path: None,
+        parent_mod_id: None,
+    }
+}
+
+fn run_pipeline(archive: &str) {
+    let root = TempDir::new().unwrap();
+    let extract_dir = root.path().join("extract");
+    let game_dir = root.path().join("MarvelRivals");
+    let metadata_dir = root.path().join("app_data").join("metadata");
+
+    // Extract with a recording progress callback instead of an AppHandle
+    let progress = RefCell::new(Vec::<ExtractionProgress>::new());
+    let extractor = ArchiveExtractor::new(|p| progress.borrow_mut().push(p));
+    let extracted = extractor.extract(&fixture(archive), &extract_dir).unwrap();
+    assert_eq!(extracted.len(), 1);
+    assert!(progress
+        .borrow()
+        .iter()
+        .any(|p| p.current_file.ends_with(PAK_NAME)));
+
+    let detected = detect_extracted_mods(&extract_dir);
+    assert_eq!(detected.len(), 1);
+    let detected = &detected[0];
+    assert!(detected.pak_file.ends_with(PAK_NAME));
+    assert_eq!(detected.associated_files.len(), 2);
+    assert!(detected
+        .preview_image
+        .as_deref()
+        .is_some_and(|p| p.ends_with("preview.png")));
+
+    // Install into a fresh game directory
+    let service = ModService::new(game_dir.clone(), metadata_dir.clone());
+    let installed = service
+        .install_mod_to_folder_with_metadata(
+            Path::new(&detected.pak_file),
+            "Skins/Groot/Sample-Skin",
+            metadata(),
+        )
+        .unwrap();
+
+    let mod_folder = game_dir
+        .join("MarvelGame")
+        .join("Marvel")
+        .join("Content")
+        .join("Paks")
+        .join("~mods")
+        .join("Skins")
+        .join("Groot")
+        .join("Sample-Skin");
+    let mut files: Vec<String> = fs::read_dir(&mod_folder)
+        .unwrap()
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec![
+            PAK_NAME.to_string(),
+            "SampleSkin_9999999_P.ucas".to_string(),
+            "SampleSkin_9999999_P.utoc".to_string(),
+        ]
+    );
+
+    // Metadata persisted as given, and the archive's preview became the thumbnail
+    let saved: ModMetadata = serde_json::from_str(
+        &fs::read_to_string(metadata_dir.join(format!("{}.json", installed.id))).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(saved.title, "Sample Skin");
+    assert_eq!(saved.character, Some(Character::Groot));
+    assert_eq!(saved.tags, vec!["fixture".to_string()]);
+    let thumbnail = metadata_dir.join(format!("{}_thumbnail.png", installed.id));
+    assert!(thumbnail.exists());
+    assert_eq!(
+        installed.thumbnail_path.as_deref(),
+        Some(thumbnail.as_path())
+    );
+
+    // The scan sees it
+    let mods = service.get_all_mods().unwrap();
+    assert_eq!(mods.len(), 1);
+    assert_eq!(mods[0].id, installed.id);
+    assert!(mods[0].enabled);
+    assert_eq!(mods[0].associated_files.len(), 3);
+
+    // Disable moves it out of ~mods with its metadata and thumbnail
+    let disabled = service.enable_mod(&installed.id, false).unwrap();
+    assert!(!disabled.enabled);
+    assert!(!mod_folder.join(PAK_NAME).exists());
+    assert!(disabled
+        .file_path
+        .starts_with(metadata_dir.join("disabled-mods")));
+    assert_eq!(disabled.metadata.title, "Sample Skin");
+    assert!(metadata_dir
+        .join(format!("{}_thumbnail.png", disabled.id))
+        .exists());
+
+    // Delete removes files, metadata and thumbnail
+    service.delete_mod(&disabled.id, false).unwrap();
+    assert!(service.get_all_mods().unwrap().is_empty());
+    assert!(!disabled.file_path.exists());
+    assert!(!metadata_dir.join(format!("{}.json", disabled.id)).exists());
+    assert!(!metadata_dir
+        .join(format!("{}_thumbnail.png", disabled.id))
+        .exists());
+}
+
+#[test]
+fn test_zip_archive_install_pipeline() {
+    run_pipeline("sample_mod.zip");
+}
+
+#[test]
+fn test_7z_archive_install_pipeline() {
+    run_pipeline("sample_mod.7z");
+}
+
+#[test]
+fn test_preview_lists_archives_without_extracting() {
+    for archive in ["sample_mod.zip", "sample_mod.7z"] {
+        let preview = build_archive_preview(&fixture(archive)).unwrap();
+        assert!(!preview.preview_partial);
+        assert_eq!(preview.file_count, 4);
+
+        // One top-level folder holding everything, sized as the sum of its files
+        assert_eq!(preview.entries.len(), 1);
+        let folder = &preview.entries[0];
+        assert!(folder.is_directory);
+        assert_eq!(folder.path, "SampleSkin");
+        assert_eq!(folder.size, preview.total_size);
+        assert_eq!(folder.children.len(), 4);
+
+        assert_eq!(preview.detected_mods.len(), 1);
+        let detected = &preview.detected_mods[0];
+        assert_eq!(detected.pak_file, format!("SampleSkin/{PAK_NAME}"));
+        assert_eq!(
+            detected.associated_files,
+            vec![
+                "SampleSkin/SampleSkin_9999999_P.ucas".to_string(),
+                "SampleSkin/SampleSkin_9999999_P.utoc".to_string(),
+            ]
+        );
+        assert_eq!(
+            detected.preview_image.as_deref(),
+            Some("SampleSkin/preview.png")
+        );
+        let pak = folder.children.iter().find(|c| c.name == PAK_NAME).unwrap();
+        assert_eq!(detected.size, pak.size);
+    }
+}
@@ -0,0 +1,62 @@
+//! Keeps the frontend in step with the backend's registered commands: every
+//! command the frontend invokes must be in `COMMANDS`, which is also what
+//! `get_backend_info` reports.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use marvel_rivals_mod_manager_lib::COMMANDS;
+use regex::Regex;
+use walkdir::WalkDir;
+
+/// Command names passed to `invoke(...)` anywhere in the frontend sources,
+/// ignoring commented-out code
+fn frontend_invokes() -> BTreeSet<String> {
+    let frontend = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join("src");
+    let block_comment = Regex::new(r"(?s)/\*.*?\*/").unwrap();
+    let line_comment = Regex::new(r"(?m)^\s*//.*$").unwrap();
+    let invoke = Regex::new(r#"invoke(?:<[^>]*>)?\(\s*['"]([a-z0-9_]+)['"]"#).unwrap();
+
+    let mut names = BTreeSet::new();
+    for entry in WalkDir::new(&frontend).into_iter().filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let is_source = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e == "ts" || e == "tsx");
+        if !is_source {
+            continue;
+        }
+        let source = fs::read_to_string(path).unwrap();
+        let source = block_comment.replace_all(&source, "");
+        let source = line_comment.replace_all(&source, "");
+        for capture in invoke.captures_iter(&source) {
+            names.insert(capture[1].to_string());
+        }
+    }
+    names
+}
+
+#[test]
+fn test_frontend_only_invokes_registered_commands() {
+    let registered: BTreeSet<&str> = COMMANDS.iter().copied().collect();
+    let invoked = frontend_invokes();
+    assert!(invoked.contains("get_backend_info"));
+
+    let missing: Vec<&String> = invoked
+        .iter()
+        .filter(|name| !registered.contains(name.as_str()))
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "frontend invokes unregistered commands: {:?}",
+        missing
+    );
+}
+
+#[test]
+fn test_command_names_are_unique() {
+    let unique: BTreeSet<&str> = COMMANDS.iter().copied().collect();
+    assert_eq!(unique.len(), COMMANDS.len());
+}
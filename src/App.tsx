@@ -1,4 +1,5 @@
 import { useEffect } from 'react'
+import { checkBackendCompatibility } from './lib/backend'
 import { initializeCommandSystem } from './lib/commands'
 import { logger } from './lib/logger'
 import { cleanupOldFiles } from './lib/recovery'
@@ -23,15 +24,21 @@ import '@fontsource/barlow-condensed/800.css'
 import './App.css'
 import './background-intensity.css'
 import MainWindow from './components/layout/MainWindow'
+import ModDetailWindow from './components/layout/ModDetailWindow'
 import { TooltipLayer } from './components/TooltipLayer'
 import { ThemeProvider } from './components/ThemeProvider'
 import ErrorBoundary from './components/ErrorBoundary'
 import { QueryProvider } from './providers/QueryProvider'
 
+// Secondary windows load the same page with ?view=<window label>
+const params = new URLSearchParams(window.location.search)
+const view = params.get('view')
+
 function App() {
   // Initialize command system and cleanup on app startup
   useEffect(() => {
     logger.info('🚀 Frontend application starting up')
+    checkBackendCompatibility()
     initializeCommandSystem()
     logger.debug('Command system initialized')
 
@@ -51,7 +58,7 @@ function App() {
     <ErrorBoundary>
       <QueryProvider>
         <ThemeProvider>
-          <MainWindow />
+          {view === 'mod-detail' ? <ModDetailWindow initialModId={params.get('modId')} /> : <MainWindow />}
           <TooltipLayer />
         </ThemeProvider>
       </QueryProvider>
@@ -1,8 +1,8 @@
 import { useState } from 'react';
 import { CircleCheck, CircleX, FolderCog, Trash2, Settings } from 'lucide-react';
 import { useUIStore } from '@/stores';
-import { useGetMods, useSetModsEnabled, useDeleteMod } from '@/hooks/useMods';
-import { invoke } from '@tauri-apps/api/core';
+import { useGetMods, useSetModsEnabled, useDeleteMod, useGameLogIssues, useOrganizePreview, useOrganizeMods } from '@/hooks/useMods';
+import { Checkbox } from '@/components/ui/checkbox';
 import { listen } from '@tauri-apps/api/event';
 import { toast } from 'sonner';
 import { c, tint } from '@/shared/rivals-tokens';
@@ -22,7 +22,7 @@ function formatEta(seconds: number): string {
   return s > 0 ? `~${m}m ${s}s` : `~${m}m`;
 }
 
-type BulkOp = 'enable' | 'disable' | 'delete' | 'disableNsfw';
+type BulkOp = 'enable' | 'disable' | 'delete' | 'disableNsfw' | 'disableFailed';
 
 /**
  * Horizontal bulk-action strip shown below the section header (Cards/Gallery).
@@ -34,6 +34,8 @@ export function BulkActionStrip() {
   const { data: mods } = useGetMods();
   const setModsEnabled = useSetModsEnabled();
   const deleteMod = useDeleteMod();
+  const organizeMods = useOrganizeMods();
+  const { data: logIssues } = useGameLogIssues();
 
   const [showProgress, setShowProgress] = useState(false);
   const [op, setOp] = useState<BulkOp>('enable');
@@ -41,9 +43,16 @@ export function BulkActionStrip() {
   const [total, setTotal] = useState(0);
   const [eta, setEta] = useState<string | null>(null);
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
+  const [showOrganize, setShowOrganize] = useState(false);
+  // Previewed moves the user unticked, by mod ID
+  const [skipped, setSkipped] = useState<Set<string>>(new Set());
+  const { data: moves, isLoading: loadingMoves } = useOrganizePreview(showOrganize);
 
   const visible = (mods ?? []).filter((m) => filters.showNsfw || !m.metadata.isNsfw);
   const hasNsfw = visible.some((m) => m.metadata.isNsfw);
+  // Enabled mods the game failed to load last session
+  const failedIds = new Set((logIssues ?? []).map((issue) => issue.modId));
+  const failedEnabled = visible.filter((m) => m.enabled && failedIds.has(m.id));
 
   async function runToggle(list: typeof visible, enabled: boolean, kind: BulkOp) {
     if (list.length === 0) {
@@ -87,15 +96,31 @@ export function BulkActionStrip() {
   const handleEnableAll = () => runToggle(visible.filter((m) => !m.enabled), true, 'enable');
   const handleDisableAll = () => runToggle(visible.filter((m) => m.enabled), false, 'disable');
   const handleDisableNsfw = () => runToggle(visible.filter((m) => m.metadata.isNsfw && m.enabled), false, 'disableNsfw');
+  const handleDisableFailed = () => runToggle(failedEnabled, false, 'disableFailed');
 
-  const handleOrganize = async () => {
+  const handleOrganize = () => {
+    setSkipped(new Set());
+    setShowOrganize(true);
+  };
+
+  const toggleMove = (modId: string, checked: boolean) => {
+    setSkipped((prev) => {
+      const next = new Set(prev);
+      if (checked) next.delete(modId);
+      else next.add(modId);
+      return next;
+    });
+  };
+
+  const approvedMoves = (moves ?? []).filter((m) => !skipped.has(m.modId));
+
+  const confirmOrganize = async () => {
+    setShowOrganize(false);
     try {
-      toast.info('Organizing loose mods…');
-      const count = await invoke<number>('organize_mods');
-      toast.success(count > 0 ? `Organized ${count} loose mod(s)` : 'No loose mods found');
+      // Only the ticked moves are made; the hook reports the result
+      await organizeMods.mutateAsync(approvedMoves);
     } catch (e) {
       console.error('Failed to organize mods:', e);
-      toast.error(`Failed to organize mods: ${e}`);
     }
   };
 
@@ -144,6 +169,7 @@ export function BulkActionStrip() {
         {ghost(c.ok, handleEnableAll, <CircleCheck className="h-3.5 w-3.5" />, 'Enable all')}
         {ghost(c.warn, handleDisableAll, <CircleX className="h-3.5 w-3.5" />, 'Disable all')}
         {hasNsfw && filters.showNsfw && ghost(c.err, handleDisableNsfw, <CircleX className="h-3.5 w-3.5" />, 'Disable NSFW')}
+        {failedEnabled.length > 0 && ghost(c.err, handleDisableFailed, <CircleX className="h-3.5 w-3.5" />, `Disable failed to load (${failedEnabled.length})`)}
         {ghost(c.accent, handleOrganize, <FolderCog className="h-3.5 w-3.5" />, 'Organize')}
         {ghost(c.err, () => setShowDeleteConfirm(true), <Trash2 className="h-3.5 w-3.5" />, 'Delete all')}
         <span className="ml-auto" style={{ color: c.ink3, fontFamily: c.mono, fontSize: 11 }}>
@@ -161,6 +187,7 @@ export function BulkActionStrip() {
                 {op === 'enable' && 'Enabling Mods'}
                 {op === 'disable' && 'Disabling Mods'}
                 {op === 'disableNsfw' && 'Disabling NSFW Mods'}
+                {op === 'disableFailed' && 'Disabling Mods That Failed to Load'}
                 {op === 'delete' && 'Deleting Mods'}
               </h2>
             </div>
@@ -178,6 +205,67 @@ export function BulkActionStrip() {
         </div>
       )}
 
+      {/* Organize preview */}
+      {showOrganize && (
+        <div className="fixed inset-0 z-50 flex items-center justify-center p-4 animate-in fade-in duration-200" style={{ background: 'rgba(0,0,0,0.5)', backdropFilter: 'blur(4px)' }}>
+          <div className="w-full max-w-lg rounded-lg p-6 animate-in zoom-in-95 duration-200" style={{ background: c.panel, border: `1px solid ${c.line2}` }}>
+            <div className="flex items-center gap-3 mb-3">
+              <div className="grid place-items-center" style={{ width: 40, height: 40, borderRadius: 999, background: tint(c.accent, 18) }}>
+                <FolderCog className="h-5 w-5" style={{ color: c.accent }} />
+              </div>
+              <h2 className="rivals-display" style={{ color: c.ink, fontSize: 18, fontWeight: 600 }}>Organize Loose Mods</h2>
+            </div>
+            {loadingMoves ? (
+              <p style={{ color: c.ink2, fontFamily: c.font, fontSize: 13 }} className="mb-4">Checking for loose mods…</p>
+            ) : !moves || moves.length === 0 ? (
+              <p style={{ color: c.ink2, fontFamily: c.font, fontSize: 13 }} className="mb-4">No loose mods found.</p>
+            ) : (
+              <>
+                <p style={{ color: c.ink2, fontFamily: c.font, fontSize: 13 }} className="mb-3">
+                  Untick any mod that is headed for the wrong folder.
+                </p>
+                <div className="mb-4 flex flex-col gap-1 overflow-y-auto" style={{ maxHeight: 320 }}>
+                  {moves.map((move) => (
+                    <label key={move.modId} className="flex items-center gap-3 cursor-pointer" style={{ padding: '6px 8px', borderRadius: 6, border: `1px solid ${c.line}` }}>
+                      <Checkbox
+                        checked={!skipped.has(move.modId)}
+                        onCheckedChange={(checked) => toggleMove(move.modId, checked === true)}
+                      />
+                      <span className="flex-1 min-w-0">
+                        <span className="block truncate" style={{ color: c.ink, fontFamily: c.font, fontSize: 13 }}>{move.title}</span>
+                        <span className="block truncate" style={{ color: c.ink3, fontFamily: c.mono, fontSize: 11 }}>
+                          {move.category}{move.character ? ` / ${move.character}` : ''}
+                        </span>
+                      </span>
+                      <span style={{ color: move.confidence === 'low' ? c.warn : c.ink3, fontFamily: c.mono, fontSize: 11, textTransform: 'uppercase' }}>
+                        {move.confidence}
+                      </span>
+                    </label>
+                  ))}
+                </div>
+              </>
+            )}
+            <div className="flex gap-3">
+              <button
+                onClick={() => setShowOrganize(false)}
+                className="flex-1 cursor-pointer"
+                style={{ padding: '8px 0', borderRadius: 6, background: 'transparent', color: c.ink2, border: `1px solid ${c.line2}`, fontFamily: c.font, fontSize: 13, fontWeight: 500 }}
+              >
+                Cancel
+              </button>
+              <button
+                onClick={confirmOrganize}
+                disabled={approvedMoves.length === 0}
+                className="flex-1 cursor-pointer disabled:cursor-not-allowed disabled:opacity-50"
+                style={{ padding: '8px 0', borderRadius: 6, background: c.accent, color: '#fff', border: 'none', fontFamily: c.font, fontSize: 13, fontWeight: 600 }}
+              >
+                Organize {approvedMoves.length}
+              </button>
+            </div>
+          </div>
+        </div>
+      )}
+
       {/* Delete confirm */}
       {showDeleteConfirm && (
         <div className="fixed inset-0 z-50 flex items-center justify-center p-4 animate-in fade-in duration-200" style={{ background: 'rgba(0,0,0,0.5)', backdropFilter: 'blur(4px)' }}>
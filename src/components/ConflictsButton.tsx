@@ -135,6 +135,7 @@ function ConflictRow({ conflict }: { conflict: ModConflict }) {
           </span>
         ))}
         <span style={{ marginLeft: 'auto', color: c.ink3, fontFamily: c.font, fontSize: 11.5 }}>
+          {conflict.severity === 'full' ? 'Full overlap · ' : ''}
           {conflict.assets.length} file{conflict.assets.length === 1 ? '' : 's'}
         </span>
       </div>
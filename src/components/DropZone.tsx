@@ -2,6 +2,8 @@ import { useState, useCallback, useEffect, type DragEvent } from 'react';
 import { PackageOpen } from 'lucide-react';
 import { motion, AnimatePresence } from 'motion/react';
 import { listen } from '@tauri-apps/api/event';
+import { toast } from 'sonner';
+import { importFileInChunks } from '../lib/fileImport';
 import { c, tint } from '../shared/rivals-tokens';
 
 interface DropZoneProps {
@@ -106,17 +108,28 @@ export function DropZone({ onDrop, accept = ['.zip', '.7z', '.rar', '.pak'], chi
       setDragCounter(0);
 
       if (e.dataTransfer.files && e.dataTransfer.files.length > 0) {
-        const files = Array.from(e.dataTransfer.files);
-        const filePaths = files
-          .filter((file) => {
-            const ext = `.${file.name.split('.').pop()?.toLowerCase()}`;
-            return accept.includes(ext);
-          })
-          .map((file) => (file as File & { path?: string }).path || file.name);
+        const files = Array.from(e.dataTransfer.files).filter((file) => {
+          const ext = `.${file.name.split('.').pop()?.toLowerCase()}`;
+          return accept.includes(ext);
+        });
 
-        if (filePaths.length > 0) {
-          onDrop(filePaths);
-        }
+        // Files with no path on disk are sent to the backend in chunks and
+        // installed from the temp copy
+        Promise.all(
+          files.map((file) => {
+            const path = (file as File & { path?: string }).path;
+            if (path) return Promise.resolve(path);
+            return importFileInChunks(file).catch((error) => {
+              toast.error(`Couldn't import ${file.name}: ${error}`);
+              return null;
+            });
+          })
+        ).then((paths) => {
+          const filePaths = paths.filter((path): path is string => path !== null);
+          if (filePaths.length > 0) {
+            onDrop(filePaths);
+          }
+        });
       }
     },
     [accept, onDrop]
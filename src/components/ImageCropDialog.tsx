@@ -15,6 +15,9 @@ const ASPECT = 16 / 9
 
 interface ImageCropDialogProps {
   imageUrl: string
+  // Full size of the image when imageUrl is a downscaled preview of it;
+  // crops are given in these pixels
+  sourceSize?: { width: number; height: number }
   onCropComplete: (cropData: {
     x: number
     y: number
@@ -26,6 +29,7 @@ interface ImageCropDialogProps {
 
 export function ImageCropDialog({
   imageUrl,
+  sourceSize,
   onCropComplete,
   onCancel,
 }: ImageCropDialogProps) {
@@ -42,7 +46,9 @@ export function ImageCropDialog({
   // Start with the largest centered 16:9 crop that fits the image.
   // Runs on the image's own load event so the measured size is correct.
   const handleImageLoad = (e: React.SyntheticEvent<HTMLImageElement>) => {
-    const { width, height, naturalWidth, naturalHeight } = e.currentTarget
+    const { width, height } = e.currentTarget
+    const naturalWidth = sourceSize?.width ?? e.currentTarget.naturalWidth
+    const naturalHeight = sourceSize?.height ?? e.currentTarget.naturalHeight
     setImageDims({ width, height, naturalWidth, naturalHeight })
     const maxCrop = centerCrop(
       makeAspectCrop({ unit: '%', width: 100 }, ASPECT, width, height),
@@ -57,8 +63,10 @@ export function ImageCropDialog({
   const handleCropComplete = () => {
     if (completedCrop && imgRef.current) {
       // Convert from displayed pixels to the image's natural pixels
-      const scaleX = imgRef.current.naturalWidth / imgRef.current.width
-      const scaleY = imgRef.current.naturalHeight / imgRef.current.height
+      const naturalWidth = sourceSize?.width ?? imgRef.current.naturalWidth
+      const naturalHeight = sourceSize?.height ?? imgRef.current.naturalHeight
+      const scaleX = naturalWidth / imgRef.current.width
+      const scaleY = naturalHeight / imgRef.current.height
 
       const cropData = {
         x: Math.round(completedCrop.x * scaleX),
@@ -68,8 +76,6 @@ export function ImageCropDialog({
       }
 
       // Clamp to image bounds
-      const naturalWidth = imgRef.current.naturalWidth
-      const naturalHeight = imgRef.current.naturalHeight
       cropData.x = Math.max(0, Math.min(cropData.x, naturalWidth - 1))
       cropData.y = Math.max(0, Math.min(cropData.y, naturalHeight - 1))
       cropData.width = Math.min(cropData.width, naturalWidth - cropData.x)
@@ -4,7 +4,7 @@ import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '.
 import type { ModCategory, Character } from '../types/mod.types';
 import { ALL_CHARACTERS, MOD_CATEGORIES } from '../shared/constants';
 import { useGetCostumesForCharacter, useGetMods } from '../hooks/useMods';
-import { Monitor, Volume2, Shirt, Gamepad2, AlertTriangle, PackagePlus } from 'lucide-react';
+import { Monitor, Volume2, Shirt, Gamepad2, Package, AlertTriangle, PackagePlus } from 'lucide-react';
 import { sanitizeFolderName, extractModNameFromPath } from '../utils/sanitize';
 import { c, tint, categoryColor } from '../shared/rivals-tokens';
 
@@ -19,6 +19,8 @@ function getCategoryIcon(category: ModCategory) {
       return <Shirt className="w-4 h-4" />;
     case 'Gameplay':
       return <Gamepad2 className="w-4 h-4" />;
+    case 'Other':
+      return <Package className="w-4 h-4" />;
     default:
       return null;
   }
@@ -2,13 +2,13 @@ import { useState, useEffect, useMemo, useRef } from 'react';
 import { createPortal } from 'react-dom';
 import { useQueryClient } from '@tanstack/react-query';
 import { useUIStore } from '../stores';
-import { useGetMods, useUpdateModMetadata, useGetCostumesForCharacter } from '../hooks/useMods';
+import { useGetMods, useUpdateModMetadata, useGetCostumesForCharacter, useRetryCostumeInitialization } from '../hooks/useMods';
 import { Dialog, DialogContent, DialogTitle, DialogDescription } from './ui/dialog';
 import { Input } from './ui/input';
 import { Label } from './ui/label';
 import { Textarea } from './ui/textarea';
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from './ui/select';
-import type { ModCategory, Character } from '../types/mod.types';
+import type { ModCategory, Character, ImagePreview } from '../types/mod.types';
 import { ALL_CHARACTERS, MOD_CATEGORIES } from '../shared/constants';
 import { Upload, Download, Clipboard, Image as ImageIcon, Check, Loader2, Pencil } from 'lucide-react';
 import { c, tint, categoryColor, getCostumeIconSrc } from '../shared/rivals-tokens';
@@ -77,6 +77,7 @@ export function MetadataDialog() {
   const [author, setAuthor] = useState('');
   const [version, setVersion] = useState('');
   const [description, setDescription] = useState('');
+  const [notes, setNotes] = useState('');
   const [tags, setTags] = useState('');
   const [category, setCategory] = useState<ModCategory>('Skins');
   const [character, setCharacter] = useState<Character | ''>('');
@@ -155,7 +156,8 @@ export function MetadataDialog() {
   }, [parentModId, mods]);
 
   // Fetch costumes for selected character
-  const { data: costumes = [], isLoading: isLoadingCostumes } = useGetCostumesForCharacter(character || null);
+  const { data: costumes = [], isLoading: isLoadingCostumes, error: costumesError } = useGetCostumesForCharacter(character || null);
+  const retryCostumes = useRetryCostumeInitialization();
 
   // Currently selected costume object (for the select trigger preview)
   const selectedCostume = costume ? costumes.find(c => c.id === costume) : undefined;
@@ -191,6 +193,8 @@ export function MetadataDialog() {
   const [showCropDialog, setShowCropDialog] = useState(false);
   const [cropImageUrl, setCropImageUrl] = useState('');
   const [cropImageFile, setCropImageFile] = useState<string | null>(null);
+  // Full size of cropImageFile; the crop dialog shows a downscaled preview
+  const [cropSourceSize, setCropSourceSize] = useState<{ width: number; height: number } | null>(null);
   const [isUploadingThumbnail, setIsUploadingThumbnail] = useState(false);
   const [thumbnailTimestamp, setThumbnailTimestamp] = useState(Date.now());
 
@@ -224,6 +228,7 @@ export function MetadataDialog() {
       setAuthor(mod.metadata.author || '');
       setVersion(mod.metadata.version || '');
       setDescription(mod.metadata.description || '');
+      setNotes(mod.metadata.notes || '');
       setTags(mod.metadata.tags?.join(', ') || '');
       setCategory(mod.category);
       setCharacter(mod.character || '');
@@ -339,6 +344,7 @@ export function MetadataDialog() {
         subtitle !== (mod.metadata.subtitle || '') ||
         author !== (mod.metadata.author || '') ||
         description !== (mod.metadata.description || '') ||
+        notes !== (mod.metadata.notes || '') ||
         category !== mod.category ||
         (character || '') !== (mod.character || '') ||
         (costume || '') !== (mod.metadata.costume || '') ||
@@ -346,7 +352,7 @@ export function MetadataDialog() {
         (parentModId || null) !== (mod.metadata.parentModId || null);
       setHasChanges(changed);
     }
-  }, [mod, title, subtitle, author, description, category, character, costume, isNsfw, parentModId]);
+  }, [mod, title, subtitle, author, description, notes, category, character, costume, isNsfw, parentModId]);
 
   // Check for duplicate mods with same name + character + costume
   const duplicateMod = mods?.find((m) => {
@@ -377,6 +383,7 @@ export function MetadataDialog() {
         author: author || null,
         version: version || null,
         description,
+        notes: notes.trim() || null,
         tags: tagsArray,
         category,
         character: character || null,
@@ -396,9 +403,9 @@ export function MetadataDialog() {
         let migrated = 0;
         for (const addon of addonsToMigrate) {
           try {
-            await invoke('update_mod_metadata', {
+            await invoke('patch_mod_metadata', {
               modId: addon.id,
-              metadata: { ...addon.metadata, parentModId: newId },
+              patch: { parentModId: newId },
             });
             migrated++;
           } catch (err) {
@@ -434,14 +441,17 @@ export function MetadataDialog() {
       });
 
       if (file) {
+        // The backend reads the file, so it can be anywhere on disk
+        const preview = await invoke<ImagePreview>('read_image_for_preview', { path: file, maxEdge: 1920 });
         // Show crop dialog with the selected file
         setCropImageFile(file as string);
-        setCropImageUrl(convertFileSrc(file as string));
+        setCropSourceSize({ width: preview.width, height: preview.height });
+        setCropImageUrl(preview.dataUrl);
         setShowCropDialog(true);
       }
     } catch (error) {
       console.error('Failed to select image:', error);
-      toast.error('Failed to select image');
+      toast.error(`Failed to open image: ${error}`);
     }
   };
 
@@ -909,6 +919,21 @@ export function MetadataDialog() {
                     style={{ background: c.bg, borderColor: c.line2 }} className="rivals-input text-sm resize-none rounded-lg"
                   />
                 </div>
+
+                {/* Notes */}
+                <div className="space-y-2">
+                  <Label htmlFor="notes" className="rivals-font" style={{ color: 'var(--rivals-ink2)', fontSize: 12.5, fontWeight: 600 }}>
+                    Notes
+                  </Label>
+                  <Textarea
+                    id="notes"
+                    value={notes}
+                    onChange={(e) => setNotes(e.target.value)}
+                    placeholder="Private notes, e.g. clips through cape on Victory pose"
+                    rows={2}
+                    style={{ background: c.bg, borderColor: c.line2 }} className="rivals-input text-sm resize-none rounded-lg"
+                  />
+                </div>
               </div>
 
               {/* Category & Character */}
@@ -1032,6 +1057,20 @@ export function MetadataDialog() {
                               </div>
                             </SelectItem>
                           ))
+                        ) : costumesError ? (
+                          // The costume data didn't load; say why and offer a reload
+                          <div className="flex flex-col items-center gap-2 p-4 text-sm text-center">
+                            <span style={{ color: c.warn }}>{String(costumesError)}</span>
+                            <button
+                              type="button"
+                              onClick={() => retryCostumes.mutate()}
+                              disabled={retryCostumes.isPending}
+                              className="btn-outline h-7 px-3 cursor-pointer"
+                              style={{ borderRadius: 8, background: 'transparent', color: c.ink2, border: `1px solid ${c.line2}`, fontFamily: c.font, fontSize: 12, fontWeight: 600 }}
+                            >
+                              Retry loading costumes
+                            </button>
+                          </div>
                         ) : (
                           <div className="flex items-center justify-center p-4 text-muted-foreground text-sm">
                             No costumes available
@@ -1245,6 +1284,7 @@ export function MetadataDialog() {
       {showCropDialog && cropImageUrl && (
         <ImageCropDialog
           imageUrl={cropImageUrl}
+          sourceSize={cropImageFile ? cropSourceSize ?? undefined : undefined}
           onCropComplete={handleCropComplete}
           onCancel={handleCropCancel}
         />
@@ -1,10 +1,8 @@
 import { useEffect, useRef, useState } from 'react';
 import { useUIStore } from '../stores';
-import { useDeleteMod, useToggleModEnabled, useToggleFavorite, useUpdateModMetadata } from '../hooks/useMods';
-import type { ModInfo } from '../types/mod.types';
-import { openPath } from '@tauri-apps/plugin-opener';
-import { dirname } from '@tauri-apps/api/path';
-import { convertFileSrc } from '@tauri-apps/api/core';
+import { useDeleteMods, previewDelete, useToggleModEnabled, useToggleFavorite, usePatchModMetadata, useResolveDuplicate } from '../hooks/useMods';
+import type { DeletePreview, ModInfo } from '../types/mod.types';
+import { convertFileSrc, invoke } from '@tauri-apps/api/core';
 import { Tag, ChevronRight, AlertTriangle, Pencil, Star, Power, FolderOpen, Trash2, Check } from 'lucide-react';
 import * as LucideIcons from 'lucide-react';
 import { PROFILE_ICON_COMPONENTS } from '../shared/profiles';
@@ -29,6 +27,36 @@ interface ModContextMenuProps {
 
 const iconComponents = PROFILE_ICON_COMPONENTS(LucideIcons as unknown as Record<string, unknown>);
 
+const fileName = (path: string) => path.split(/[\\/]/).pop() ?? path;
+
+// Every file and folder the delete removes, and any file it keeps because
+// another mod lists it too
+function DeletePreviewList({ preview }: { preview: DeletePreview }) {
+  const files = preview.mods.flatMap((plan) => [
+    ...plan.files,
+    ...[plan.metadataFile, plan.thumbnail, plan.thumbnailSource].filter((path): path is string => path !== null),
+  ]);
+  return (
+    <div className="rounded-lg px-3 py-2 max-h-48 overflow-y-auto text-left" style={{ background: c.bg, border: `1px solid ${c.line}` }}>
+      <ul className="space-y-0.5" style={{ color: c.ink3, fontFamily: c.mono, fontSize: 11.5 }}>
+        {files.map((path) => <li key={path} className="truncate" title={path}>{fileName(path)}</li>)}
+        {preview.folders.map((path) => (
+          <li key={path} className="truncate" title={path}>{fileName(path)}/ <span style={{ color: c.muted }}>(left empty)</span></li>
+        ))}
+      </ul>
+      {preview.sharedFiles.length > 0 && (
+        <ul className="space-y-0.5 mt-2" style={{ color: c.warn, fontFamily: c.font, fontSize: 12 }}>
+          {preview.sharedFiles.map((shared) => (
+            <li key={shared.path} title={shared.path}>
+              Keeping {fileName(shared.path)}: {shared.modIds.length} other mod(s) use it
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+}
+
 // A single editorial menu row.
 function MenuItem({
   icon,
@@ -64,14 +92,16 @@ function MenuItem({
 export function ModContextMenu({ mod, x, y, onClose }: ModContextMenuProps) {
   const setMetadataDialogOpen = useUIStore((state) => state.setMetadataDialogOpen);
   const profiles = useUIStore((state) => state.profiles);
-  const deleteMod = useDeleteMod();
+  const deleteMods = useDeleteMods();
   const toggleEnabled = useToggleModEnabled();
   const toggleFavorite = useToggleFavorite();
-  const updateMetadata = useUpdateModMetadata();
+  const patchMetadata = usePatchModMetadata();
+  const resolveDuplicate = useResolveDuplicate();
   const menuRef = useRef<HTMLDivElement>(null);
   const [adjustedPosition, setAdjustedPosition] = useState({ x, y });
   const [showProfileSubmenu, setShowProfileSubmenu] = useState(false);
   const [showDeleteDialog, setShowDeleteDialog] = useState(false);
+  const [deletePreview, setDeletePreview] = useState<DeletePreview | null>(null);
 
   const thumb = mod.thumbnailPath ? convertFileSrc(mod.thumbnailPath) : null;
   const catColor = categoryColor(mod.category);
@@ -102,21 +132,29 @@ export function ModContextMenu({ mod, x, y, onClose }: ModContextMenuProps) {
 
   const handleEditMetadata = () => { setMetadataDialogOpen(true, mod.id); onClose(); };
   const handleShowInFolder = async () => {
-    try { await openPath(await dirname(mod.filePath)); } catch (e) { console.error('Failed to show in folder:', e); }
+    // Resolved by id in the backend so a just-toggled mod's old path is never used
+    try { await invoke('show_in_folder', { modId: mod.id }); } catch (e) { console.error('Failed to show in folder:', e); }
     onClose();
   };
   const handleToggleEnabled = async () => { await toggleEnabled.mutateAsync(mod.id); onClose(); };
   const handleToggleFavorite = async () => { await toggleFavorite.mutateAsync(mod.id); onClose(); };
-  const handleDelete = () => setShowDeleteDialog(true);
+  const handleKeepCopy = async (keep: ModInfo['location']) => {
+    try { await resolveDuplicate.mutateAsync({ modId: mod.id, keep }); } catch { /* toasted by the hook */ }
+    onClose();
+  };
+  const handleDelete = async () => {
+    setShowDeleteDialog(true);
+    try { setDeletePreview(await previewDelete([mod.id])); } catch (e) { console.error('Failed to preview delete:', e); }
+  };
   const confirmDelete = async () => {
-    try { await deleteMod.mutateAsync(mod.id); setShowDeleteDialog(false); onClose(); }
+    try { await deleteMods.mutateAsync([mod.id]); setShowDeleteDialog(false); onClose(); }
     catch { setShowDeleteDialog(false); }
   };
 
   const handleToggleProfile = async (profileId: string) => {
     const current = mod.metadata.profileIds || [];
     const updated = current.includes(profileId) ? current.filter((id) => id !== profileId) : [...current, profileId];
-    await updateMetadata.mutateAsync({ modId: mod.id, metadata: { ...mod.metadata, profileIds: updated.length > 0 ? updated : null } });
+    await patchMetadata.mutateAsync({ modId: mod.id, patch: { profileIds: updated.length > 0 ? updated : null } });
     onClose();
   };
 
@@ -198,6 +236,17 @@ export function ModContextMenu({ mod, x, y, onClose }: ModContextMenuProps) {
             hue={mod.enabled ? c.err : c.ok}
           />
           <MenuItem icon={<FolderOpen className="w-4 h-4" />} label="Show in Folder" onClick={handleShowInFolder} />
+          {mod.duplicateOf && (
+            <>
+              <MenuItem icon={<Check className="w-4 h-4" />} label="Keep This Copy" onClick={() => handleKeepCopy(mod.location)} hue={c.warn} />
+              <MenuItem
+                icon={<Check className="w-4 h-4" />}
+                label={mod.location === 'active' ? 'Keep Disabled Copy' : 'Keep Enabled Copy'}
+                onClick={() => handleKeepCopy(mod.location === 'active' ? 'disabled' : 'active')}
+                hue={c.warn}
+              />
+            </>
+          )}
         </div>
 
         <div style={{ height: 1, background: c.line }} />
@@ -227,6 +276,7 @@ export function ModContextMenu({ mod, x, y, onClose }: ModContextMenuProps) {
               <br />
               <span style={{ color: c.err, fontWeight: 500 }}>This cannot be undone.</span>
             </AlertDialogDescription>
+            {deletePreview && <DeletePreviewList preview={deletePreview} />}
           </AlertDialogHeader>
           <AlertDialogFooter className="flex-row gap-3 sm:gap-3">
             <AlertDialogCancel
@@ -238,11 +288,11 @@ export function ModContextMenu({ mod, x, y, onClose }: ModContextMenuProps) {
             <button
               type="button"
               onPointerDown={(e) => { e.preventDefault(); e.stopPropagation(); confirmDelete(); }}
-              disabled={deleteMod.isPending}
+              disabled={deleteMods.isPending}
               className="flex-1 inline-flex items-center justify-center disabled:opacity-50 disabled:pointer-events-none"
               style={{ height: 38, borderRadius: 8, background: c.err, color: '#fff', border: 'none', fontFamily: c.font, fontSize: 13, fontWeight: 600 }}
             >
-              {deleteMod.isPending ? 'Deleting…' : 'Delete'}
+              {deleteMods.isPending ? 'Deleting…' : 'Delete'}
             </button>
           </AlertDialogFooter>
         </AlertDialogContent>
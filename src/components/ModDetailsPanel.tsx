@@ -2,16 +2,16 @@ import { useUIStore } from '../stores';
 import { useGetMods, useDeleteMod, useToggleModEnabled, useToggleFavorite, useGetAllCostumes } from '../hooks/useMods';
 import type { Costume, ModInfo } from '../types/mod.types';
 import { ScrollArea } from './ui/scroll-area';
-import { X, Edit, Trash2, FolderOpen, Star, Copy, Check, MoreHorizontal, ChevronDown } from 'lucide-react';
+import { X, Edit, Trash2, FolderOpen, Star, Copy, Check, MoreHorizontal, ChevronDown, ExternalLink } from 'lucide-react';
 import { convertFileSrc, invoke } from '@tauri-apps/api/core';
-import { openPath } from '@tauri-apps/plugin-opener';
-import { dirname } from '@tauri-apps/api/path';
 import { useState } from 'react';
 import { toast } from 'sonner';
+import { disabledReasonLabel, openFolderErrorMessage } from '../lib/utils';
 import { useQueryClient } from '@tanstack/react-query';
 import { c, tint, categoryColor, getCharacterIconPath, getCostumeIconSrc, addonDisplayName } from '../shared/rivals-tokens';
 import { CategoryIcon, WarnIcon } from '../shared/rivals-design';
 import { useDominantColor } from '../hooks/useDominantColor';
+import { RawMetadataEditor } from './RawMetadataEditor';
 
 // Round avatar with a ring in the image's dominant color.
 function RingAvatar({ src, alt, size }: { src: string; alt: string; size: number }) {
@@ -67,7 +67,11 @@ function processDescription(description: string): string {
     .trim();
 }
 
-export function ModDetailsPanel() {
+/**
+ * Details of the selected mod: a slide-over in the main window, or the whole
+ * window when `detached` (the separate mod details window)
+ */
+export function ModDetailsPanel({ detached = false }: { detached?: boolean } = {}) {
   const selectedModId = useUIStore((state) => state.selectedModId);
   const setSelectedModId = useUIStore((state) => state.setSelectedModId);
   const setMetadataDialogOpen = useUIStore((state) => state.setMetadataDialogOpen);
@@ -84,6 +88,7 @@ export function ModDetailsPanel() {
   const [showOverflow, setShowOverflow] = useState(false);
   const [showFiles, setShowFiles] = useState(false);
   const [showIdSection, setShowIdSection] = useState(false);
+  const [showRawSection, setShowRawSection] = useState(false);
 
   const mod = mods?.find((m) => m.id === selectedModId);
   const heroGlow = useDominantColor(mod?.thumbnailPath ? convertFileSrc(mod.thumbnailPath) : null);
@@ -98,6 +103,15 @@ export function ModDetailsPanel() {
 
   const handleClose = () => setSelectedModId(null);
 
+  const handlePopOut = async () => {
+    try {
+      await invoke('open_mod_detail_window', { modId: mod.id });
+      setSelectedModId(null);
+    } catch (error) {
+      toast.error(`Failed to open details window: ${error}`);
+    }
+  };
+
   const handleEditMetadata = () => setMetadataDialogOpen(true, mod.id);
 
   const handleDelete = async () => {
@@ -117,10 +131,10 @@ export function ModDetailsPanel() {
 
   const handleShowInFolder = async () => {
     try {
-      const dir = await dirname(mod.filePath);
-      await openPath(dir);
+      await invoke('show_in_folder', { modId: mod.id });
     } catch (error) {
       console.error('Failed to open folder:', error);
+      toast.error(openFolderErrorMessage(error, 'Failed to open folder'));
     }
   };
 
@@ -195,15 +209,17 @@ export function ModDetailsPanel() {
   return (
     <>
       {/* Backdrop */}
-      <div
-        className="fixed inset-0 z-30 backdrop-blur-[2px]"
-        style={{ background: 'rgba(0,0,0,0.45)' }}
-        onClick={handleClose}
-      />
+      {!detached && (
+        <div
+          className="fixed inset-0 z-30 backdrop-blur-[2px]"
+          style={{ background: 'rgba(0,0,0,0.45)' }}
+          onClick={handleClose}
+        />
+      )}
 
       {/* Panel */}
       <div
-        className="fixed inset-y-0 right-0 w-[700px] z-50 flex flex-col animate-in slide-in-from-right duration-200"
+        className={detached ? 'fixed inset-0 z-50 flex flex-col' : 'fixed inset-y-0 right-0 w-[700px] z-50 flex flex-col animate-in slide-in-from-right duration-200'}
         style={{ background: c.bg, borderLeft: `1px solid ${c.line2}`, boxShadow: '-8px 0 30px rgba(0,0,0,0.5)', ['--glow-color' as string]: heroGlow ?? c.accent }}
       >
         {/* Hero area — thumbnail with close button overlaid */}
@@ -230,6 +246,18 @@ export function ModDetailsPanel() {
             </div>
           )}
 
+          {/* Pop out into its own window, next to the close button */}
+          {!detached && (
+            <button
+              onClick={handlePopOut}
+              data-tip="Open in a separate window"
+              className="absolute top-4 right-[60px] grid place-items-center transition-colors z-10"
+              style={{ width: 34, height: 34, borderRadius: 9, background: 'rgba(0,0,0,0.5)', backdropFilter: 'blur(6px)', color: c.ink2, border: `1px solid ${c.line2}` }}
+            >
+              <ExternalLink className="w-4 h-4" />
+            </button>
+          )}
+
           {/* Close button */}
           <button
             onClick={handleClose}
@@ -251,6 +279,7 @@ export function ModDetailsPanel() {
           <button
             onClick={handleToggleEnabled}
             className={`power-rail rivals-condensed flex-1 cursor-pointer ${mod.enabled ? 'is-on' : 'is-off'}`}
+            data-tip={mod.enabled ? undefined : disabledReasonLabel(mod.disabledReason) ?? undefined}
           >
             <span className="power-dot" />
             {mod.enabled ? 'Enabled' : 'Disabled'}
@@ -512,6 +541,22 @@ export function ModDetailsPanel() {
               </div>
             </div>
 
+            {/* Raw metadata */}
+            <div style={{ animation: 'metadata-fade-in 420ms cubic-bezier(0.22,1,0.36,1) 440ms both' }}>
+              <button
+                onClick={() => setShowRawSection((s) => !s)}
+                className="w-full flex items-center justify-between mb-3"
+              >
+                <h4 className="rivals-mono" style={{ color: c.ink3, fontSize: 11, letterSpacing: '0.14em', textTransform: 'uppercase', fontWeight: 600 }}>Raw Metadata</h4>
+                <ChevronDown className="w-4 h-4" style={{ color: c.ink3, transition: 'transform 200ms ease', transform: showRawSection ? 'rotate(180deg)' : 'none' }} />
+              </button>
+              <div className="grid" style={{ gridTemplateRows: showRawSection ? '1fr' : '0fr', transition: 'grid-template-rows 280ms cubic-bezier(0.4, 0, 0.2, 1)' }}>
+                <div className="overflow-hidden">
+                  {showRawSection && <RawMetadataEditor modId={mod.id} />}
+                </div>
+              </div>
+            </div>
+
             {/* Add-ons attached to this mod — at the bottom, roomy rows */}
             {addons.length > 0 && (
               <div style={{ animation: 'metadata-fade-in 420ms cubic-bezier(0.22,1,0.36,1) 470ms both' }}>
@@ -1,10 +1,11 @@
 import { useState, useMemo, useCallback, useRef, useEffect, memo } from 'react';
 import { useVirtualizer } from '@tanstack/react-virtual';
 import { PackageOpen, SearchX } from 'lucide-react';
-import { useGetMods, useGetAllCostumes, useToggleModEnabled, useToggleFavorite, useModConflicts, useSetModsEnabled, useDeleteMods } from '../hooks/useMods';
+import { useGetMods, useGetAllCostumes, useGetCostumesForCharacter, useToggleModEnabled, useToggleFavorite, useModConflicts, useGameLogIssues, useSetModsEnabled, useDeleteMods, useAssignCharacter, previewDelete } from '../hooks/useMods';
 import { useUIStore } from '../stores';
 import type { ModInfo, Costume, ModCategory, Character } from '../types/mod.types';
 import { convertFileSrc } from '@tauri-apps/api/core';
+import { disabledReasonLabel } from '../lib/utils';
 import { ModContextMenu } from './ModContextMenu';
 import { BulkActionStrip } from './BulkActionStrip';
 import { ALL_CHARACTERS } from '../shared/constants';
@@ -43,6 +44,7 @@ const VirtualModCard = memo(function VirtualModCard({
   addons,
   expanded,
   hasConflict,
+  failedToLoad,
   selected,
   highlightAddonIds,
   onToggleExpand,
@@ -55,6 +57,7 @@ const VirtualModCard = memo(function VirtualModCard({
   addons?: ModInfo[];
   expanded: boolean;
   hasConflict: boolean;
+  failedToLoad: boolean;
   selected: boolean;
   highlightAddonIds: Set<string>;
   onToggleExpand: (id: string) => void;
@@ -70,6 +73,7 @@ const VirtualModCard = memo(function VirtualModCard({
         addons={addons}
         expanded={expanded}
         hasConflict={hasConflict}
+        failedToLoad={failedToLoad}
         selected={selected}
         highlightAddonIds={highlightAddonIds}
         onToggleExpand={() => onToggleExpand(mod.id)}
@@ -101,6 +105,8 @@ function getThumbnailSrc(mod: ModInfo): string | null {
 // listeners imperatively and writes CSS variables (no React re-renders) — the
 // actual transform lives in App.css. MAX_TILT is small on purpose: a subtle
 // lean toward the cursor, not a flip.
+const NATURAL: Intl.CollatorOptions = { numeric: true, sensitivity: 'base' };
+
 const MAX_TILT = 3; // degrees — subtle enough to keep text crisp
 function useCardTilt() {
   return useCallback((el: HTMLDivElement | null) => {
@@ -172,7 +178,7 @@ function MiniPower({ on, disabled, label, onClick, tip }: { on: boolean; disable
 
 // ── Power readout (cards view enable control) ────────────────────────────────
 // Glowing status dot + state word, no box. Hover previews the action color.
-function PowerToggle({ enabled, onClick }: { enabled: boolean; onClick: (e: React.MouseEvent) => void }) {
+function PowerToggle({ enabled, reason, onClick }: { enabled: boolean; reason?: string | null; onClick: (e: React.MouseEvent) => void }) {
   const btnRef = useRef<HTMLButtonElement>(null);
   const prevEnabled = useRef(enabled);
   useEffect(() => {
@@ -191,7 +197,7 @@ function PowerToggle({ enabled, onClick }: { enabled: boolean; onClick: (e: Reac
       ref={btnRef}
       onClick={onClick}
       className={`power-toggle rivals-condensed ${enabled ? 'is-on' : 'is-off'}`}
-      data-tip={enabled ? 'Click to disable' : 'Click to enable'}
+      data-tip={enabled ? 'Click to disable' : reason ? `${reason} · click to enable` : 'Click to enable'}
       aria-label={enabled ? 'Disable mod' : 'Enable mod'}
     >
       <span className="power-dot" />
@@ -226,6 +232,10 @@ export function ModList() {
     return s;
   }, [conflicts]);
 
+  // Mods the game failed to load last session, from its log
+  const { data: logIssues } = useGameLogIssues();
+  const failedIds = useMemo(() => new Set((logIssues ?? []).map((issue) => issue.modId)), [logIssues]);
+
   const filters = useUIStore((state) => state.filters);
   const setFilters = useUIStore((state) => state.setFilters);
   const viewMode = useUIStore((state) => state.viewMode);
@@ -260,6 +270,10 @@ export function ModList() {
   const [bulkSelected, setBulkSelected] = useState<Set<string>>(new Set());
   const setModsEnabled = useSetModsEnabled();
   const deleteMods = useDeleteMods();
+  const assignCharacter = useAssignCharacter();
+  const [assignTo, setAssignTo] = useState<Character | ''>('');
+  const [assignCostume, setAssignCostume] = useState('');
+  const { data: assignCostumes = [] } = useGetCostumesForCharacter(assignTo || null);
 
   const toggleBulkSelect = useCallback((id: string) => {
     setBulkSelected((prev) => {
@@ -291,14 +305,37 @@ export function ModList() {
     setModsEnabled.mutate({ modIds: [...bulkSelected], enabled });
     setBulkSelected(new Set());
   };
-  const bulkDelete = () => {
+  const bulkDelete = async () => {
     const n = bulkSelected.size;
-    if (confirm(`Delete ${n} selected mod${n === 1 ? '' : 's'}? Their files are removed from the game folder.`)) {
+    const preview = await previewDelete([...bulkSelected]).catch(() => null);
+    const fileCount = preview?.mods.reduce((sum, plan) => sum + plan.files.length, 0);
+    const details = preview
+      ? ` ${fileCount} file(s) and ${preview.folders.length} emptied folder(s) are removed from the game folder.`
+        + (preview.sharedFiles.length > 0 ? ` ${preview.sharedFiles.length} file(s) other mods also use are kept.` : '')
+      : ' Their files are removed from the game folder.';
+    if (confirm(`Delete ${n} selected mod${n === 1 ? '' : 's'}?${details}`)) {
       deleteMods.mutate([...bulkSelected]);
       setBulkSelected(new Set());
     }
   };
 
+  // Moved mods get new IDs; keep them selected (and open) under those
+  const bulkAssign = () => {
+    if (!assignTo) return;
+    assignCharacter.mutate(
+      { modIds: [...bulkSelected], character: assignTo, costume: assignCostume || null },
+      {
+        onSuccess: (assigned) => {
+          setBulkSelected(new Set(assigned.map((m) => m.modInfo.id)));
+          const open = assigned.find((m) => m.previousId === useUIStore.getState().selectedModId);
+          if (open) setSelectedModId(open.modInfo.id);
+          setAssignTo('');
+          setAssignCostume('');
+        },
+      },
+    );
+  };
+
   // ── Filter ──
   // Per-mod predicate. NSFW lock always applies; the other criteria define
   // whether a mod "matches" the active filter (used for both parents & add-ons).
@@ -326,15 +363,16 @@ export function ModList() {
   const filteredMods = useMemo(() => visibleMods.filter(matchesFilter), [visibleMods, matchesFilter]);
 
   // ── Sort ──
+  // Titles compare numerically ("Mod 2" before "Mod 10"), matching the backend
   const sortedMods = useMemo(() => {
     if (!filteredMods.length) return [];
     return [...filteredMods].sort((a, b) => {
       switch (sortBy) {
         case 'name':
-          return (a.metadata.title || a.name).localeCompare(b.metadata.title || b.name);
+          return (a.metadata.title || a.name).localeCompare(b.metadata.title || b.name, undefined, NATURAL);
         case 'category':
           if (a.category !== b.category) return a.category.localeCompare(b.category);
-          return (a.metadata.title || a.name).localeCompare(b.metadata.title || b.name);
+          return (a.metadata.title || a.name).localeCompare(b.metadata.title || b.name, undefined, NATURAL);
         case 'character': {
           const aChar = a.character || 'zzz';
           const bChar = b.character || 'zzz';
@@ -347,16 +385,16 @@ export function ModList() {
           const aCost = a.metadata.costume || '';
           const bCost = b.metadata.costume || '';
           if (aCost !== bCost) return aCost.localeCompare(bCost);
-          const order: Record<string, number> = { Skins: 1, UI: 2, Audio: 3, Gameplay: 4 };
+          const order: Record<string, number> = { Skins: 1, UI: 2, Audio: 3, Gameplay: 4, Other: 5 };
           const ao = order[a.category] || 999;
           const bo = order[b.category] || 999;
           if (ao !== bo) return ao - bo;
-          return (a.metadata.title || a.name).localeCompare(b.metadata.title || b.name);
+          return (a.metadata.title || a.name).localeCompare(b.metadata.title || b.name, undefined, NATURAL);
         }
         case 'updated':
           return new Date(b.metadata.updatedAt).getTime() - new Date(a.metadata.updatedAt).getTime();
         case 'size':
-          return (b.fileSize || 0) - (a.fileSize || 0) || (a.metadata.title || a.name).localeCompare(b.metadata.title || b.name);
+          return (b.fileSize || 0) - (a.fileSize || 0) || (a.metadata.title || a.name).localeCompare(b.metadata.title || b.name, undefined, NATURAL);
         case 'profile': {
           const aHas = (a.metadata.profileIds?.length || 0) > 0;
           const bHas = (b.metadata.profileIds?.length || 0) > 0;
@@ -369,7 +407,7 @@ export function ModList() {
               if (cmp !== 0) return cmp;
             }
           }
-          return (a.metadata.title || a.name).localeCompare(b.metadata.title || b.name);
+          return (a.metadata.title || a.name).localeCompare(b.metadata.title || b.name, undefined, NATURAL);
         }
         case 'date':
         default:
@@ -640,6 +678,7 @@ export function ModList() {
                           addons={addonsByParent.get(mod.id)}
                           expanded={isAddonsOpen(mod.id)}
                           hasConflict={conflictIds.has(mod.id)}
+                          failedToLoad={failedIds.has(mod.id)}
                           selected={bulkSelected.has(mod.id)}
                           highlightAddonIds={highlightAddonIds}
                           onToggleExpand={toggleExpand}
@@ -666,6 +705,7 @@ export function ModList() {
                     addons={addonsByParent.get(mod.id)}
                     expanded={isAddonsOpen(mod.id)}
                     hasConflict={conflictIds.has(mod.id)}
+                    failedToLoad={failedIds.has(mod.id)}
                     selected={bulkSelected.has(mod.id)}
                     highlightAddonIds={highlightAddonIds}
                     onToggleExpand={() => toggleExpand(mod.id)}
@@ -717,6 +757,42 @@ export function ModList() {
           >
             Disable
           </button>
+          <select
+            value={assignTo}
+            onChange={(e) => { setAssignTo(e.target.value as Character | ''); setAssignCostume(''); }}
+            aria-label="Set character"
+            className="cursor-pointer"
+            style={{ padding: '5px 8px', borderRadius: 7, background: c.panel, color: assignTo ? c.ink : c.ink3, border: `1px solid ${c.line2}`, fontFamily: c.font, fontSize: 12 }}
+          >
+            <option value="">Set character…</option>
+            {ALL_CHARACTERS.filter((ch) => ch !== 'All Characters').map((ch) => (
+              <option key={ch} value={ch}>{ch}</option>
+            ))}
+          </select>
+          {assignTo && (
+            <>
+              <select
+                value={assignCostume}
+                onChange={(e) => setAssignCostume(e.target.value)}
+                aria-label="Costume"
+                className="cursor-pointer"
+                style={{ padding: '5px 8px', borderRadius: 7, background: c.panel, color: c.ink, border: `1px solid ${c.line2}`, fontFamily: c.font, fontSize: 12 }}
+              >
+                <option value="">No costume</option>
+                {assignCostumes.map((costume) => (
+                  <option key={costume.id} value={costume.id}>{costume.name}</option>
+                ))}
+              </select>
+              <button
+                onClick={bulkAssign}
+                disabled={assignCharacter.isPending}
+                className="rivals-condensed cursor-pointer"
+                style={{ padding: '5px 12px', borderRadius: 7, background: 'transparent', color: c.ink2, border: `1px solid ${c.line2}`, fontSize: 12.5, fontWeight: 700, letterSpacing: '0.07em', textTransform: 'uppercase' }}
+              >
+                {assignCharacter.isPending ? 'Moving…' : 'Assign'}
+              </button>
+            </>
+          )}
           <button
             onClick={bulkDelete}
             className="rivals-condensed cursor-pointer"
@@ -780,6 +856,7 @@ interface CardProps {
   addons?: ModInfo[];
   expanded: boolean;
   hasConflict?: boolean;
+  failedToLoad?: boolean;
   selected?: boolean;
   highlightAddonIds?: Set<string>;
   onToggleExpand: () => void;
@@ -921,6 +998,7 @@ const ModCard = memo(function ModCard({
   addons,
   expanded,
   hasConflict,
+  failedToLoad,
   selected,
   highlightAddonIds,
   onToggleExpand,
@@ -979,6 +1057,15 @@ const ModCard = memo(function ModCard({
           {hasConflict && (
             <KickerTag color={c.warn} label="Conflict" icon={<WarnIcon stroke={c.warn} size={11} />} tip="This mod overwrites the same files as another enabled mod" />
           )}
+          {failedToLoad && (
+            <KickerTag color={c.err} label="Failed to load" icon={<WarnIcon stroke={c.err} size={11} />} tip="The game failed to load this mod last session" />
+          )}
+          {mod.metadata.externallyModifiedAt && (
+            <KickerTag color={c.accent2} label="Changed" tip="File changed outside the manager" />
+          )}
+          {mod.duplicateOf && (
+            <KickerTag color={c.warn} label="Duplicate" tip="Installed both enabled and disabled; keep one from the context menu" />
+          )}
         </div>
         {hasAddons && (
           <AddonDrawer open={expanded} count={addonList.length} onClose={onToggleExpand}>
@@ -1059,7 +1146,7 @@ const ModCard = memo(function ModCard({
               {mod.isFavorite ? '★' : '☆'}
             </button>
             {hasAddons && <AddonTrigger count={addonList.length} expanded={expanded} onClick={(e) => { e.stopPropagation(); onToggleExpand(); }} />}
-            <PowerToggle enabled={mod.enabled} onClick={(e) => { e.stopPropagation(); toggleEnabled.mutate(mod.id); }} />
+            <PowerToggle enabled={mod.enabled} reason={disabledReasonLabel(mod.disabledReason)} onClick={(e) => { e.stopPropagation(); toggleEnabled.mutate(mod.id); }} />
           </div>
         </div>
       </div>
@@ -1076,6 +1163,7 @@ const GalleryCard = memo(function GalleryCard({
   addons,
   expanded,
   hasConflict,
+  failedToLoad,
   selected,
   highlightAddonIds,
   onToggleExpand,
@@ -1140,6 +1228,15 @@ const GalleryCard = memo(function GalleryCard({
           {hasConflict && (
             <KickerTag color={c.warn} label="Conflict" icon={<WarnIcon stroke={c.warn} size={11} />} tip="This mod overwrites the same files as another enabled mod" />
           )}
+          {failedToLoad && (
+            <KickerTag color={c.err} label="Failed to load" icon={<WarnIcon stroke={c.err} size={11} />} tip="The game failed to load this mod last session" />
+          )}
+          {mod.metadata.externallyModifiedAt && (
+            <KickerTag color={c.accent2} label="Changed" tip="File changed outside the manager" />
+          )}
+          {mod.duplicateOf && (
+            <KickerTag color={c.warn} label="Duplicate" tip="Installed both enabled and disabled; keep one from the context menu" />
+          )}
         </div>
 
         <button
@@ -1221,7 +1318,7 @@ const GalleryCard = memo(function GalleryCard({
           </span>
           <div className="flex items-center" style={{ gap: 6, marginLeft: 'auto', flexShrink: 0 }}>
             {hasAddons && <AddonTrigger count={addonList.length} expanded={expanded} onClick={(e) => { e.stopPropagation(); onToggleExpand(); }} />}
-            <PowerToggle enabled={mod.enabled} onClick={(e) => { e.stopPropagation(); toggleEnabled.mutate(mod.id); }} />
+            <PowerToggle enabled={mod.enabled} reason={disabledReasonLabel(mod.disabledReason)} onClick={(e) => { e.stopPropagation(); toggleEnabled.mutate(mod.id); }} />
           </div>
         </div>
 
@@ -1463,7 +1560,7 @@ function GalleryRibbon({
   }, [mods]);
 
   const railChars = ALL_CHARACTERS.filter((ch) => ch !== 'All Characters' && (charCounts[ch] || 0) > 0);
-  const CATS: ModCategory[] = ['Skins', 'Audio', 'UI', 'Gameplay'];
+  const CATS: ModCategory[] = ['Skins', 'Audio', 'UI', 'Gameplay', 'Other'];
 
   const railRef = useRef<HTMLDivElement>(null);
   // Vertical wheel → fast horizontal scroll. Direct jump (CSS scroll-behavior:
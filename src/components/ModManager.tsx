@@ -1,9 +1,9 @@
 import { useEffect, useRef, useState } from 'react';
 import { useQueryClient, useIsMutating } from '@tanstack/react-query';
 import { listen } from '@tauri-apps/api/event';
-import { useGetAppSettings } from '../hooks/useSettings';
+import { useGetAppSettings, useEffectiveModsDirectory } from '../hooks/useSettings';
 import { useFileWatcher } from '../hooks/useFileWatcher';
-import { useGetMods } from '../hooks/useMods';
+import { useGetMods, useModsIntegrity, useRestoreModsDirectory } from '../hooks/useMods';
 import { TitleBar } from './TitleBar';
 import { Toolbar } from './Toolbar';
 import { ModList } from './ModList';
@@ -17,11 +17,13 @@ import { DropZone } from './DropZone';
 import { ModSelectionDialog, type DetectedMod } from './ModSelectionDialog';
 import { useUIStore } from '../stores';
 import { useInstallFromArchive } from '../hooks/useInstallFromArchive';
+import { useExtractionProgress } from '../hooks/useExtractionProgress';
+import { useInstallProgress, cancelModInstall } from '../hooks/useInstallProgress';
 import { toast } from 'sonner';
 import { invoke } from '@tauri-apps/api/core';
 import { detectCharacterFromMultipleSources } from '../utils/characterDetection';
 import { c, tint } from '../shared/rivals-tokens';
-import type { ModCategory, ModInfo } from '../types/mod.types';
+import type { ModCategory, DroppedFileInfo, FolderMergeReport, IdMigrationReport, InstallResult, LegacyDisabledReport, OrganizationResult, ScanWarning, SplitRepairReport, ThumbnailConsolidationReport } from '../types/mod.types';
 
 // Add-on-looking file names: only the explicit markers "addon"/"add-on"/
 // "optional". Deliberately NOT "alt" — in this modding scene "Alt2"-style
@@ -42,6 +44,10 @@ declare global {
   }
 }
 
+// Archive extraction picks the format from the extension, so an archive
+// only counts when its content and extension agree
+const ARCHIVE_EXTENSIONS: Record<string, string> = { zip: 'zip', sevenZip: '7z', rar: 'rar' };
+
 export function ModManager() {
   const queryClient = useQueryClient();
   const { data: settings, isLoading, error } = useGetAppSettings();
@@ -71,6 +77,10 @@ export function ModManager() {
   // that scan (TanStack only cancel-restarts a fetch once the query has data),
   // so the first list we see predates the install and the mod appears missing.
   const modLookupAttemptsRef = useRef(0);
+  // The archive (or dropped pak/folder) the current sequence installs from and
+  // how it went, so the sequence ends with one desktop notification for all of it
+  const installSourceRef = useRef<string | null>(null);
+  const installTallyRef = useRef({ installed: 0, failed: 0 });
 
   // Archive queue for processing multiple archives sequentially
   const [archiveQueue, setArchiveQueue] = useState<string[]>([]);
@@ -85,13 +95,64 @@ export function ModManager() {
     prepareNextMod,
   } = useInstallFromArchive();
 
-  // Start file watcher when we have a game directory
-  const modsDirectory = settings?.gameDirectory
-    ? `${settings.gameDirectory}\\MarvelGame\\Marvel\\Content\\Paks\\~mods`
-    : null;
+  // One toast follows the archive being extracted, by bytes
+  const extraction = useExtractionProgress(isExtracting);
+  useEffect(() => {
+    if (!extraction) return;
+    const pct = extraction.bytesTotal > 0 ? Math.round((extraction.bytesExtracted / extraction.bytesTotal) * 100) : 0;
+    toast.loading(`Extracting archive... ${pct}%`, { id: 'extraction-progress', description: extraction.currentFile });
+  }, [extraction]);
+  useEffect(() => {
+    if (!isExtracting) toast.dismiss('extraction-progress');
+  }, [isExtracting]);
+
+  // And one follows the copy of the mod being installed, which can be
+  // cancelled through the source path it was installed from
+  const installProgress = useInstallProgress();
+  const installingPathRef = useRef<string | null>(null);
+  useEffect(() => {
+    if (!installProgress) {
+      toast.dismiss('install-progress');
+      return;
+    }
+    const pct = installProgress.total > 0 ? Math.round((installProgress.current / installProgress.total) * 100) : 0;
+    toast.loading(`Installing ${installProgress.currentFile}... ${pct}%`, {
+      id: 'install-progress',
+      action: {
+        label: 'Cancel',
+        onClick: () => {
+          if (installingPathRef.current) cancelModInstall(installingPathRef.current);
+        },
+      },
+    });
+  }, [installProgress]);
+
+  // Start file watcher when we have a mods folder
+  const { data: modsDirectory = null } = useEffectiveModsDirectory();
 
   useFileWatcher(modsDirectory, !!modsDirectory);
 
+  // Warn when mods vanished since last session, usually a game patch
+  // wiping ~mods, and offer to put back what disabled-mods has copies of
+  const { data: integrity } = useModsIntegrity();
+  const restoreModsDirectory = useRestoreModsDirectory();
+  useEffect(() => {
+    if (!integrity || (!integrity.modsDirectoryMissing && integrity.issues.length === 0)) return;
+    const lost = integrity.issues.length;
+    toast.warning(
+      integrity.modsDirectoryMissing
+        ? 'The ~mods folder was missing, probably removed by a game update'
+        : `${lost} mod${lost === 1 ? ' has' : 's have'} missing files`,
+      {
+        description: lost > 0 ? integrity.issues.map((issue) => issue.title).slice(0, 5).join(', ') : undefined,
+        duration: Infinity,
+        action: integrity.recoverable.length > 0
+          ? { label: `Restore ${integrity.recoverable.length}`, onClick: () => restoreModsDirectory.mutate() }
+          : undefined,
+      },
+    );
+  }, [integrity]); // Only when a new report comes in
+
   useEffect(() => {
     if (modsDirectory) {
       console.log('[ModManager] Watching mods directory:', modsDirectory);
@@ -106,20 +167,81 @@ export function ModManager() {
     if (modsDirectory) {
       const organizeMods = async () => {
         try {
+          // Step 0: Reunite mods left half-enabled by a crash mid-toggle
+          // (the game crashes on a pak without its .ucas/.utoc)
+          const splitReport = await invoke<SplitRepairReport>('repair_split_mods');
+          if (splitReport.repaired > 0) {
+            console.log(`[ModManager] Repaired ${splitReport.repaired} split mod(s)`);
+            toast.success(`Repaired ${splitReport.repaired} mod(s) left half-enabled`);
+          }
+          if (splitReport.unresolved.length > 0) {
+            const names = splitReport.unresolved.map((s) => s.name).join(', ');
+            console.warn('[ModManager] Split mods need manual repair:', names);
+            toast.warning(`Some mods have files in both the enabled and disabled folders: ${names}`, { duration: 10000 });
+          }
+
+          // Step 0.2: Move mods older managers disabled by renaming them
+          // `.disabled` inside ~mods (the game can still load some of them)
+          const legacyReport = await invoke<LegacyDisabledReport>('convert_legacy_disabled');
+          if (legacyReport.converted > 0) {
+            console.log(`[ModManager] Converted ${legacyReport.converted} legacy .disabled mod(s)`);
+            toast.success(`Moved ${legacyReport.converted} mod(s) disabled by another manager into the disabled folder`);
+          }
+          const legacyFailed = legacyReport.mods.filter((m) => m.error);
+          if (legacyFailed.length > 0) {
+            console.warn('[ModManager] Legacy .disabled mods left in place:', legacyFailed.map((m) => `${m.name}: ${m.error}`));
+            toast.warning(`Some mods renamed .disabled could not be moved: ${legacyFailed.map((m) => m.name).join(', ')}`, { duration: 10000 });
+          }
+
+          // Step 0.3: Notice paks replaced by other tools since the last run,
+          // so the list can say why a mod behaves differently now
+          const externallyModified = await invoke<string[]>('reconcile_external_changes');
+          if (externallyModified.length > 0) {
+            console.log(`[ModManager] ${externallyModified.length} mod(s) changed outside the manager`);
+            toast.info(`${externallyModified.length} mod(s) had their files changed outside the manager`, { duration: 8000 });
+          }
+
+          // Step 0.5: Explain read-only files up front; moves work around them,
+          // but they are why older versions failed to delete or rename mods
+          const scanWarnings = await invoke<ScanWarning[]>('get_scan_warnings');
+          const readOnly = scanWarnings.filter((w) => w.kind === 'readOnly');
+          if (readOnly.length > 0) {
+            console.warn('[ModManager] Read-only mods:', readOnly.map((w) => w.message));
+            toast.info(`${readOnly.length} mod(s) have read-only files (often restored from a backup); they will be unlocked when changed`, { duration: 8000 });
+          }
+
           // Step 1: Migrate metadata from old filename-based IDs to new path-based IDs
           console.log('[ModManager] Migrating metadata to new ID system...');
-          const migratedCount = await invoke<number>('migrate_metadata_to_path_ids');
+          const migration = await invoke<IdMigrationReport>('migrate_metadata_to_path_ids');
+          const migratedCount = migration.migratedCount + migration.resumedCount;
           if (migratedCount > 0) {
             console.log(`[ModManager] Migrated ${migratedCount} mod(s) metadata`);
             toast.success(`Migrated metadata for ${migratedCount} mod(s)`, { duration: 5000 });
           }
+          if (migration.notFoundCount > 0) {
+            console.log(`[ModManager] ${migration.notFoundCount} mod(s) have no recoverable metadata`);
+          }
+
+          // Step 1.5: Give thumbnails left in the old Electron thumbnails
+          // folder to their mods (run after IDs settle in step 1)
+          const thumbnailReport = await invoke<ThumbnailConsolidationReport>('consolidate_thumbnail_storage');
+          if (thumbnailReport.migrated > 0) {
+            console.log(`[ModManager] Moved ${thumbnailReport.migrated} legacy thumbnail(s) to their mods`);
+          }
+          if (thumbnailReport.unclaimed.length > 0) {
+            console.warn('[ModManager] Unclaimed legacy thumbnails:', thumbnailReport.unclaimed);
+            toast.info(`${thumbnailReport.unclaimed.length} old thumbnail(s) matched no installed mod and were moved to the unclaimed-thumbnails folder`, { duration: 8000 });
+          }
 
           // Step 2: Organize loose mods
           console.log('[ModManager] Auto-organizing loose mods...');
-          const count = await invoke<number>('organize_mods');
-          if (count > 0) {
-            console.log(`[ModManager] Auto-organized ${count} loose mod(s)`);
-            toast.success(`Auto-organized ${count} loose mod(s) into folders`);
+          const organized = await invoke<OrganizationResult>('organize_mods');
+          if (organized.movedMods > 0) {
+            console.log(`[ModManager] Auto-organized ${organized.movedMods} loose mod(s)`);
+            toast.success(`Auto-organized ${organized.movedMods} loose mod(s) into folders`);
+          }
+          if (organized.errorCount > 0) {
+            console.warn('[ModManager] Loose mods left in place:', organized.errors);
           }
 
           // Step 2.5: Move mod folders sitting directly under a category into
@@ -132,11 +254,18 @@ export function ModManager() {
 
           // Step 3: Merge duplicate folders (e.g., "Black Widow" and "Black-Widow")
           console.log('[ModManager] Checking for duplicate character folders...');
-          const mergedCount = await invoke<number>('merge_duplicate_folders');
+          const mergeReport = await invoke<FolderMergeReport>('merge_duplicate_folders');
+          const mergedCount = mergeReport.mergedFolders;
           if (mergedCount > 0) {
-            console.log(`[ModManager] Merged ${mergedCount} duplicate folder(s)`);
+            console.log(`[ModManager] Merged ${mergedCount} duplicate folder(s)`, mergeReport.actions);
             toast.success(`Merged ${mergedCount} duplicate character folder(s)`);
           }
+          const keptBoth = mergeReport.actions.filter((a) => a.action === 'keptBoth');
+          if (keptBoth.length > 0) {
+            const names = keptBoth.map((a) => a.destination.split(/[\\/]/).pop()).join(', ');
+            console.warn('[ModManager] Differing copies kept side by side:', keptBoth);
+            toast.warning(`Found different copies of the same mod folder and kept both: ${names}. Remove the one you don't want.`, { duration: 10000 });
+          }
 
           // Step 4: Migrate mods with costumes to new folder structure
           console.log('[ModManager] Migrating mods to costume folder structure...');
@@ -163,7 +292,7 @@ export function ModManager() {
           }
 
           // If any migrations occurred, refresh the mods list to get updated file paths
-          if (migratedCount > 0 || count > 0 || relocatedCount > 0 || mergedCount > 0 || costumeMigratedCount > 0 || recoveredCount > 0 || loadOrderCount > 0) {
+          if (splitReport.repaired > 0 || legacyReport.converted > 0 || externallyModified.length > 0 || migratedCount > 0 || thumbnailReport.migrated > 0 || organized.movedMods > 0 || relocatedCount > 0 || mergeReport.actions.length > 0 || costumeMigratedCount > 0 || recoveredCount > 0 || loadOrderCount > 0) {
             console.log('[ModManager] Refreshing mods list after migrations...');
             await queryClient.invalidateQueries({ queryKey: ['mods'] });
           }
@@ -228,12 +357,13 @@ export function ModManager() {
       // Show progress toast
       const remaining = archiveQueue.length - 1;
       if (remaining > 0) {
-        toast.info(`Extracting archive... (${remaining} more in queue)`);
+        toast.info(`Extracting archive... (${remaining} more in queue)`, { id: 'extraction-progress' });
       } else {
-        toast.info('Extracting archive...');
+        toast.info('Extracting archive...', { id: 'extraction-progress' });
       }
 
       // Extract the archive
+      installSourceRef.current = nextArchive.split(/[\\/]/).pop() ?? nextArchive;
       extractAndDetect(nextArchive);
     }
   }, [archiveQueue, isProcessingArchive, isInInstallationSequence, isExtracting]);
@@ -241,8 +371,9 @@ export function ModManager() {
   // When extraction completes, show mod selection dialog
   useEffect(() => {
     if (!isExtracting && detectedMods.length > 0) {
-      if (detectedMods.length === 1) {
-        // Single mod: skip selection, install and edit
+      if (detectedMods.length === 1 && !detectedMods[0].invalidReason) {
+        // Single mod: skip selection, install and edit. A damaged one still
+        // goes through the dialog, which explains why it can't be installed
         installAndEditMod(detectedMods);
       } else {
         // Multiple mods: show selection dialog
@@ -270,6 +401,7 @@ export function ModManager() {
         // All mods from current archive done
         console.log('[ModManager] All mods installed successfully!');
         toast.success(`Successfully installed ${selectedModsToInstall.length} mod(s)!`);
+        notifyInstallComplete();
         setIsInInstallationSequence(false);
         setHasOpenedDialogForCurrentMod(false);
         resetArchiveInstallation();
@@ -367,6 +499,7 @@ export function ModManager() {
     });
 
     sequenceParentIdRef.current = null;
+    installTallyRef.current = { installed: 0, failed: 0 };
     setSelectedModsToInstall(ordered);
     setCurrentModIndexInInstallation(0);
     setIsInInstallationSequence(true);
@@ -376,6 +509,16 @@ export function ModManager() {
     await installNextModInSequence(ordered, 0);
   };
 
+  // One notification per archive or batch, not per pak; nothing if no pak made it
+  const notifyInstallComplete = () => {
+    const { installed, failed } = installTallyRef.current;
+    installTallyRef.current = { installed: 0, failed: 0 };
+    if (installed === 0 || !installSourceRef.current) return;
+    invoke('notify_install_complete', { sourceName: installSourceRef.current, installed, failed }).catch((error) =>
+      console.error('[ModManager] Failed to send install notification:', error)
+    );
+  };
+
   // Sequential function to install mods one by one
   const installNextModInSequence = async (modsToInstall: DetectedMod[], index: number) => {
     console.log('[ModManager] installNextModInSequence called with index:', index, 'of', modsToInstall.length);
@@ -419,6 +562,7 @@ export function ModManager() {
       console.log('[ModManager] No character auto-detected');
     }
 
+    installingPathRef.current = pakFileToInstall;
     try {
       // Install mod with auto-detected character to avoid Skins/ folder duplication
       const modInfo: any = await installSingleMod({
@@ -431,6 +575,8 @@ export function ModManager() {
         },
       });
 
+      installTallyRef.current.installed += 1;
+
       // Store file path to find mod after refetch
       if (modInfo && modInfo.filePath) {
         console.log('[ModManager] Mod installed successfully:', modInfo.id, modInfo.name);
@@ -447,6 +593,7 @@ export function ModManager() {
         console.log('[ModManager] Stored expected file path, waiting for refetch...');
       }
     } catch (error) {
+      toast.dismiss('install-progress');
       console.error('[ModManager] Installation failed:', error);
       console.error('[ModManager] Failed to install from:', pakFileToInstall);
       console.error('[ModManager] Mod details:', currentMod);
@@ -459,11 +606,13 @@ export function ModManager() {
       }
 
       // Move to next mod on error
+      installTallyRef.current.failed += 1;
       setCurrentModIndexInInstallation(index + 1);
       if (index + 1 < modsToInstall.length) {
         installNextModInSequence(modsToInstall, index + 1);
       } else {
         // Last mod failed, end sequence
+        notifyInstallComplete();
         setIsInInstallationSequence(false);
         setHasOpenedDialogForCurrentMod(false);
         resetArchiveInstallation();
@@ -522,16 +671,31 @@ export function ModManager() {
       resetArchiveInstallation();
     }
 
+    // The backend reads each file, so the webview needs no fs scope for
+    // wherever it was dropped from
+    Promise.all(
+      filePaths.map((path) =>
+        invoke<DroppedFileInfo>('read_dropped_file_info', { path }).catch((error) => {
+          toast.error(`Can't read ${path.split(/[\\/]/).pop()}: ${error}`);
+          return null;
+        })
+      )
+    ).then((infos) => processDroppedFiles(infos.filter((info): info is DroppedFileInfo => info !== null), lock));
+  };
+
+  const processDroppedFiles = (infos: DroppedFileInfo[], lock: NonNullable<typeof window.__modManagerProcessingLock>) => {
     // Separate archives and pak files
     const archives: string[] = [];
-    const pakFiles: string[] = [];
-
-    for (const filePath of filePaths) {
-      const ext = filePath.split('.').pop()?.toLowerCase();
-      if (ext === 'zip' || ext === '7z' || ext === 'rar') {
-        archives.push(filePath);
-      } else if (ext === 'pak') {
-        pakFiles.push(filePath);
+    const pakFiles: DroppedFileInfo[] = [];
+
+    for (const info of infos) {
+      const ext = info.path.split('.').pop()?.toLowerCase();
+      if (info.isArchive && ARCHIVE_EXTENSIONS[info.kind] === ext) {
+        archives.push(info.path);
+      } else if (info.isPak) {
+        pakFiles.push(info);
+      } else {
+        toast.error(`${info.path.split(/[\\/]/).pop()} isn't a mod archive or pak`);
       }
     }
 
@@ -544,12 +708,15 @@ export function ModManager() {
         toast.info(`Installing ${pakFiles.length} mods...`);
       }
 
-      const detectedMods: DetectedMod[] = pakFiles.map(pakFile => ({
-        pakFile,
-        associatedFiles: [pakFile],
-        size: 0,
+      const detectedMods: DetectedMod[] = pakFiles.map(info => ({
+        pakFile: info.path,
+        associatedFiles: [info.path],
+        size: info.size,
       }));
 
+      // A batch of paks is named after the folder they were dropped from
+      const sourcePath = pakFiles.length === 1 ? pakFiles[0].path : pakFiles[0].path.replace(/[\\/][^\\/]*$/, '');
+      installSourceRef.current = sourcePath.split(/[\\/]/).pop() || sourcePath;
       installAndEditMod(detectedMods);
 
       // Release lock after pak installation starts
@@ -601,12 +768,15 @@ export function ModManager() {
   const handleGroupInstallConfirm = async (selectedMods: DetectedMod[], groupName: string, category: ModCategory) => {
     setShowModSelectionDialog(false);
     toast.info(`Installing ${selectedMods.length} paks as "${groupName}"…`);
+    // Every pak of the group shares one install, so cancelling the first stops it
+    installingPathRef.current = selectedMods[0]?.pakFile ?? null;
     try {
-      const parent = await invoke<ModInfo>('install_mod_group', {
+      const { modInfo: parent, warnings } = await invoke<InstallResult>('install_mod_group', {
         pakFiles: selectedMods.map((m) => m.pakFile),
         groupName,
         category,
       });
+      warnings.forEach((warning) => toast.warning(warning));
       await queryClient.invalidateQueries({ queryKey: ['mods', 'list'] });
       toast.success(`Installed ${selectedMods.length} paks as one mod with ${selectedMods.length - 1} add-ons`);
       // Open the parent's metadata dialog via the usual find-by-path flow
@@ -616,6 +786,7 @@ export function ModManager() {
       console.error('[ModManager] Group install failed:', error);
       toast.error(`Group install failed: ${error}`);
     } finally {
+      toast.dismiss('install-progress');
       setIsProcessingArchive(false);
       resetArchiveInstallation();
     }
@@ -624,6 +795,7 @@ export function ModManager() {
   // Bail out of a multi-mod install sequence. Mods already installed stay;
   // everything still queued (including further archives) is dropped.
   const cancelInstallSequence = () => {
+    notifyInstallComplete();
     setIsInInstallationSequence(false);
     setSelectedModsToInstall([]);
     setCurrentModIndexInInstallation(0);
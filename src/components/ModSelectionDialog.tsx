@@ -9,12 +9,18 @@ import { Checkbox } from './ui/checkbox'
 import { ScrollArea } from './ui/scroll-area'
 import { Package, Search } from 'lucide-react'
 import { c, tint, formatFileSize } from '../shared/rivals-tokens'
-import type { ModCategory } from '../types/mod.types'
+import type { MetadataSuggestion, ModCategory } from '../types/mod.types'
 
 export interface DetectedMod {
   pakFile: string
   associatedFiles: string[]
   size: number
+  previewImage?: string | null
+  // What auto-detection makes of it; absent for paks dropped in directly
+  suggestedMetadata?: MetadataSuggestion
+  // Set when the pak or a companion failed its archive checksum; such mods
+  // can't be selected
+  invalidReason?: string | null
 }
 
 interface ModSelectionDialogProps {
@@ -25,7 +31,7 @@ interface ModSelectionDialogProps {
   onConfirmGroup: (selectedMods: DetectedMod[], groupName: string, category: ModCategory) => void
 }
 
-const GROUP_CATEGORIES: ModCategory[] = ['Skins', 'Audio', 'UI', 'Gameplay']
+const GROUP_CATEGORIES: ModCategory[] = ['Skins', 'Audio', 'UI', 'Gameplay', 'Other']
 
 // Default group name from the shared filename prefix (e.g. 205 files named
 // "MVP_Music_XY_P.pak" suggest "MVP Music"), cleaned of separators/suffixes.
@@ -175,6 +181,7 @@ function ModSelectionBody({
   }, [detectedMods, filter])
 
   const toggleSelection = (index: number) => {
+    if (detectedMods[index].invalidReason) return
     const newSelection = new Set(selectedIndices)
     if (newSelection.has(index)) {
       newSelection.delete(index)
@@ -187,7 +194,9 @@ function ModSelectionBody({
   // Select/deselect operate on the visible (filtered) mods
   const selectAll = () => {
     const newSelection = new Set(selectedIndices)
-    visibleMods.forEach(({ index }) => newSelection.add(index))
+    visibleMods
+      .filter(({ mod }) => !mod.invalidReason)
+      .forEach(({ index }) => newSelection.add(index))
     setSelectedIndices(newSelection)
   }
 
@@ -197,9 +206,10 @@ function ModSelectionBody({
     setSelectedIndices(newSelection)
   }
 
+  const selectableVisibleMods = visibleMods.filter(({ mod }) => !mod.invalidReason)
   const allVisibleSelected =
-    visibleMods.length > 0 &&
-    visibleMods.every(({ index }) => selectedIndices.has(index))
+    selectableVisibleMods.length > 0 &&
+    selectableVisibleMods.every(({ index }) => selectedIndices.has(index))
   const noneVisibleSelected = visibleMods.every(
     ({ index }) => !selectedIndices.has(index)
   )
@@ -387,6 +397,7 @@ function ModSelectionBody({
           )}
           {visibleMods.map(({ mod, index }, row) => {
             const isSelected = selectedIndices.has(index)
+            const isInvalid = !!mod.invalidReason
             const fileName = getFileName(mod.pakFile)
             const folderPath = getFolderPath(mod.pakFile)
             const extras = associatedExtensions(mod.associatedFiles)
@@ -394,8 +405,10 @@ function ModSelectionBody({
             return (
               <div
                 key={index}
-                className="flex items-center gap-4 cursor-pointer"
+                className={`flex items-center gap-4 ${isInvalid ? 'cursor-not-allowed' : 'cursor-pointer'}`}
+                title={mod.invalidReason ?? undefined}
                 style={{
+                  opacity: isInvalid ? 0.6 : 1,
                   padding: '16px 28px 16px 24px',
                   borderBottom: `1px solid ${c.line}`,
                   background: isSelected ? tint(c.accent, 9) : 'transparent',
@@ -405,7 +418,7 @@ function ModSelectionBody({
                 }}
                 onClick={() => toggleSelection(index)}
                 onMouseEnter={e => {
-                  if (!isSelected) e.currentTarget.style.background = c.panelHi
+                  if (!isSelected && !isInvalid) e.currentTarget.style.background = c.panelHi
                 }}
                 onMouseLeave={e => {
                   e.currentTarget.style.background = isSelected
@@ -415,6 +428,7 @@ function ModSelectionBody({
               >
                 <Checkbox
                   checked={isSelected}
+                  disabled={isInvalid}
                   onCheckedChange={() => toggleSelection(index)}
                   onClick={e => e.stopPropagation()}
                   className="mod-pick-check flex-shrink-0"
@@ -443,6 +457,13 @@ function ModSelectionBody({
                       {folderPath}
                     </div>
                   )}
+                  {isInvalid && (
+                    <div
+                      style={{ color: c.err, fontSize: 12.5, marginTop: 4 }}
+                    >
+                      Damaged in the archive — download it again to install
+                    </div>
+                  )}
                 </div>
                 <div className="flex items-center gap-2 flex-shrink-0">
                   {extras.length > 0 && (
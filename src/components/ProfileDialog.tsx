@@ -18,6 +18,7 @@ import { IconPicker } from './ui/icon-picker'
 import { ColorPicker } from './ui/color-picker'
 import { toast } from 'sonner'
 import { c, tint } from '../shared/rivals-tokens'
+import { useCreateProfile, useRenameProfile } from '../hooks/useMods'
 
 export function ProfileDialog() {
   const {
@@ -29,6 +30,8 @@ export function ProfileDialog() {
     addProfile,
     updateProfile,
   } = useUIStore()
+  const createProfile = useCreateProfile()
+  const renameProfile = useRenameProfile()
 
   const [profileName, setProfileName] = useState('')
   const [selectedIcon, setSelectedIcon] = useState(DEFAULT_ICON_OPTIONS[0] || 'Zap')
@@ -66,7 +69,7 @@ export function ProfileDialog() {
     setNameCharCount(0)
   }
 
-  const handleSave = () => {
+  const handleSave = async () => {
     if (!isValidProfileName(profileName)) {
       toast.error('Profile name must be 1-10 alphanumeric characters')
       return
@@ -93,9 +96,24 @@ export function ProfileDialog() {
         icon: selectedIcon,
         createdAt: new Date().toISOString(),
       }
+      try {
+        await createProfile.mutateAsync({ name: profileName, profileId: newProfile.id, fromCurrentState: false })
+      } catch {
+        return
+      }
       addProfile(newProfile)
       toast.success(`Profile "${profileName}" created`)
     } else if (profileDialogMode === 'edit' && profileDialogProfileId) {
+      try {
+        await renameProfile.mutateAsync({ profileId: profileDialogProfileId, name: profileName })
+      } catch {
+        // Made before the backend kept profiles: save it there under its ID
+        try {
+          await createProfile.mutateAsync({ name: profileName, profileId: profileDialogProfileId, fromCurrentState: false })
+        } catch {
+          return
+        }
+      }
       updateProfile(profileDialogProfileId, {
         name: profileName,
         color: selectedColor,
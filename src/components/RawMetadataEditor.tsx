@@ -0,0 +1,94 @@
+import { useEffect, useState } from 'react';
+import { useModMetadataRaw, useSetModMetadataRaw } from '../hooks/useMods';
+import { useUIStore } from '../stores';
+import type { MetadataUpdateError } from '../types/mod.types';
+import { c } from '../shared/rivals-tokens';
+
+// What the backend said about a rejected edit, one line per problem
+function describeRejection(error: unknown): string[] {
+  const rejection = error as MetadataUpdateError;
+  switch (rejection?.code) {
+    case 'syntax':
+      return [`Line ${rejection.line}, column ${rejection.column}: ${rejection.message}`];
+    case 'invalid':
+      return rejection.issues.map((issue) => `${issue.field}: ${issue.message}`);
+    case 'failed':
+      return [rejection.message];
+    case 'metadataConflict':
+      return ['This mod was changed elsewhere; reload and try again.'];
+    default:
+      return [String(error)];
+  }
+}
+
+/**
+ * The mod's metadata JSON, editable by hand. The backend parses and
+ * validates it before anything is saved.
+ */
+export function RawMetadataEditor({ modId }: { modId: string }) {
+  const setSelectedModId = useUIStore((state) => state.setSelectedModId);
+  const { data: raw, isLoading, refetch } = useModMetadataRaw(modId);
+  const saveRaw = useSetModMetadataRaw();
+  const [draft, setDraft] = useState('');
+  const [problems, setProblems] = useState<string[]>([]);
+
+  useEffect(() => {
+    if (raw !== undefined) {
+      setDraft(raw);
+      setProblems([]);
+    }
+  }, [raw]);
+
+  const handleApply = async () => {
+    try {
+      const updated = await saveRaw.mutateAsync({ modId, json: draft });
+      setProblems([]);
+      if (updated.id !== modId) setSelectedModId(updated.id);
+    } catch (error) {
+      setProblems(describeRejection(error));
+    }
+  };
+
+  if (isLoading) return null;
+
+  return (
+    <div className="space-y-2">
+      <textarea
+        value={draft}
+        onChange={(e) => setDraft(e.target.value)}
+        spellCheck={false}
+        rows={16}
+        className="rivals-input w-full px-3 py-2 rounded-lg outline-none resize-y"
+        style={{ background: c.bg, border: `1px solid ${problems.length > 0 ? c.warn : c.line2}`, fontFamily: c.mono, fontSize: 11.5 }}
+      />
+      {problems.length > 0 && (
+        <ul className="space-y-0.5" style={{ color: c.warn, fontFamily: c.mono, fontSize: 11.5 }}>
+          {problems.map((problem) => <li key={problem}>{problem}</li>)}
+        </ul>
+      )}
+      <div className="flex items-start justify-between gap-2">
+        <p className="flex-1" style={{ color: c.ink3, fontFamily: c.font, fontSize: 11.5 }}>
+          Checked before saving. The mod ID and install date can't be changed here.
+        </p>
+        <div className="flex gap-2 flex-shrink-0">
+          <button
+            onClick={() => { refetch(); }}
+            disabled={draft === raw}
+            className="btn-outline h-8 px-3 cursor-pointer"
+            style={{ borderRadius: 8, background: 'transparent', color: c.ink2, border: `1px solid ${c.line2}`, fontFamily: c.font, fontSize: 12, fontWeight: 600 }}
+          >
+            Revert
+          </button>
+          <button
+            onClick={handleApply}
+            disabled={draft === raw || saveRaw.isPending}
+            className="btn-primary h-8 px-3 cursor-pointer"
+            style={{ borderRadius: 8, background: c.accent, color: c.onAccent, border: 'none', fontFamily: c.font, fontSize: 12, fontWeight: 600 }}
+          >
+            Apply
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -3,8 +3,9 @@ import { FolderOpen, Plus, Settings, Trash2 } from 'lucide-react'
 import { ScrollArea } from './ui/scroll-area'
 import { useUIStore } from '@/stores'
 import type { ModCategory, Character } from '@/types/mod.types'
-import { useGetMods, useToggleMod, useRemoveProfileFromAllMods } from '@/hooks/useMods'
-import { openPath } from '@tauri-apps/plugin-opener'
+import { useGetMods, useToggleMod, useDeleteProfile } from '@/hooks/useMods'
+import { invoke } from '@tauri-apps/api/core'
+import { openFolderErrorMessage } from '@/lib/utils'
 import { toast } from 'sonner'
 import { ProfileItem } from './ProfileItem'
 import { ProfileDialog } from './ProfileDialog'
@@ -57,7 +58,7 @@ export function Sidebar() {
 
   const { data: mods } = useGetMods()
   const toggleMod = useToggleMod()
-  const removeProfileFromAllMods = useRemoveProfileFromAllMods()
+  const deleteProfileInBackend = useDeleteProfile()
 
   const [heroFilter, setHeroFilter] = useState('')
   const charSortMode = useUIStore((state) => state.charSortMode)
@@ -159,7 +160,7 @@ export function Sidebar() {
     setCharSortDesc(!charSortDesc)
   }, [])
 
-  const CATEGORY_ROWS: ModCategory[] = ['Skins', 'Audio', 'UI', 'Gameplay']
+  const CATEGORY_ROWS: ModCategory[] = ['Skins', 'Audio', 'UI', 'Gameplay', 'Other']
   // Enabled/Disabled quick filters use the showEnabled/showDisabled flags.
   const enabledOnly = filters.showEnabled && !filters.showDisabled
   const disabledOnly = !filters.showEnabled && filters.showDisabled
@@ -183,26 +184,10 @@ export function Sidebar() {
 
   const handleOpenModDirectory = async () => {
     try {
-      if (mods && mods.length > 0) {
-        const firstMod = mods[0]
-        if (!firstMod) {
-          toast.error('Could not locate first mod')
-          return
-        }
-        const modsIndex = firstMod.filePath.indexOf('~mods')
-        if (modsIndex !== -1) {
-          const afterModsIndex = firstMod.filePath.indexOf('\\', modsIndex)
-          const modDirPath = afterModsIndex !== -1 ? firstMod.filePath.substring(0, afterModsIndex) : firstMod.filePath.substring(0, modsIndex + 5)
-          await openPath(modDirPath)
-        } else {
-          toast.error('Could not locate mods directory')
-        }
-      } else {
-        toast.error('No mods found. Install a mod first to open the directory.')
-      }
+      await invoke('open_mods_root')
     } catch (error) {
       console.error('Failed to open mod directory:', error)
-      toast.error('Failed to open mod directory')
+      toast.error(openFolderErrorMessage(error, 'Failed to open mod directory'))
     }
   }
 
@@ -251,7 +236,7 @@ export function Sidebar() {
     if (!profile) return
     setShowDeleteConfirm(false)
     try {
-      await removeProfileFromAllMods.mutateAsync(profileToDelete)
+      await deleteProfileInBackend.mutateAsync(profileToDelete)
       deleteProfile(profileToDelete)
       setProfileToDelete(null)
       toast.success(`Profile "${profile.name}" deleted and removed from all mods`)
@@ -1,6 +1,10 @@
 import { useState, useRef, useEffect } from 'react';
 import { useUIStore as useUIStoreOld } from '@/store/ui-store';
-import { useUIStore, type SortOption } from '../stores';
+import { useUIStore, sortOptionToSpec, sortSpecToOption, type SortOption } from '../stores';
+import { invoke } from '@tauri-apps/api/core';
+import { useQueryClient } from '@tanstack/react-query';
+import { usePreferences, preferencesQueryKeys } from '@/services/preferences';
+import { defaultPreferences, type AppPreferences } from '@/types/preferences';
 import type { ViewMode } from '../types/mod.types';
 import { open } from '@tauri-apps/plugin-dialog';
 import { toast } from 'sonner';
@@ -8,8 +12,10 @@ import { c, tint, withViewTransition } from '../shared/rivals-tokens';
 import { NexusDiscoverDialog } from './NexusDiscoverDialog';
 import { APP_VERSION } from '../shared/constants';
 import { useUpdater } from '../hooks/useUpdater';
-import { Lock, Unlock, ArrowUp } from 'lucide-react';
+import { Lock, Unlock, ArrowUp, RefreshCw } from 'lucide-react';
 import { ConflictsButton } from './ConflictsButton';
+import { useHashingProgress } from '../hooks/useHashingProgress';
+import { useRefreshMods } from '../hooks/useMods';
 
 interface ToolbarProps {
   onArchiveSelect?: (filePaths: string[]) => void;
@@ -32,6 +38,8 @@ const VIEW_OPTIONS: { id: ViewMode; label: string; icon: string }[] = [
 ];
 
 export function Toolbar({ onArchiveSelect }: ToolbarProps) {
+  const hashing = useHashingProgress();
+  const refreshMods = useRefreshMods();
   const filters = useUIStore((state) => state.filters);
   const setFilters = useUIStore((state) => state.setFilters);
   const viewMode = useUIStore((state) => state.viewMode);
@@ -44,6 +52,27 @@ export function Toolbar({ onArchiveSelect }: ToolbarProps) {
   const { availableUpdate } = useUpdater();
   const [showSortMenu, setShowSortMenu] = useState(false);
   const sortRef = useRef<HTMLDivElement>(null);
+  const queryClient = useQueryClient();
+  const { data: preferences } = usePreferences();
+
+  // Open the library the way the user left it
+  const restoredSort = useRef(false);
+  useEffect(() => {
+    if (restoredSort.current || !preferences) return;
+    restoredSort.current = true;
+    const saved = preferences.modSort && sortSpecToOption(preferences.modSort);
+    if (saved) setSortBy(saved);
+  }, [preferences, setSortBy]);
+
+  // Saved quietly (no toast) since it happens on every sort change
+  const persistSort = (option: SortOption) => {
+    const modSort = sortOptionToSpec(option);
+    if (!modSort) return;
+    const next: AppPreferences = { ...defaultPreferences, ...preferences, modSort };
+    invoke('save_preferences', { preferences: next })
+      .then(() => queryClient.setQueryData(preferencesQueryKeys.preferences(), next))
+      .catch((e) => console.error('Failed to save sort preference:', e));
+  };
 
   // Close sort menu on outside click
   useEffect(() => {
@@ -221,6 +250,18 @@ export function Toolbar({ onArchiveSelect }: ToolbarProps) {
       {/* Conflict alert — only visible when conflicts exist */}
       <ConflictsButton />
 
+      {/* Background hashing after a scan — only visible while it runs */}
+      {hashing && (
+        <span
+          className="rivals-mono"
+          style={{ color: c.muted, fontSize: 11 }}
+          data-tip="Hashing new and changed mod files"
+          data-tip-side="bottom"
+        >
+          {hashing.done}/{hashing.total}
+        </span>
+      )}
+
       {/* NSFW lock — icon only; red (unlocked) when NSFW is shown */}
       <button
         onClick={() => setFilters({ showNsfw: !nsfwShown })}
@@ -266,6 +307,7 @@ export function Toolbar({ onArchiveSelect }: ToolbarProps) {
                   key={option.value}
                   onClick={() => {
                     withViewTransition(() => setSortBy(option.value));
+                    persistSort(option.value);
                     setShowSortMenu(false);
                   }}
                   className="menu-item w-full px-3.5 py-2 text-left cursor-pointer"
@@ -291,6 +333,17 @@ export function Toolbar({ onArchiveSelect }: ToolbarProps) {
         )}
       </div>
 
+      {/* Rescan the mod folders from disk */}
+      <button
+        onClick={() => refreshMods.mutate(true)}
+        disabled={refreshMods.isPending}
+        className="icon-btn flex items-center justify-center w-8 h-8 rounded-[7px] cursor-pointer disabled:cursor-wait"
+        style={{ ['--btn-hue' as string]: c.accent, background: c.panel, color: c.ink2, border: `1px solid ${c.line}` }}
+        data-tip="Rescan mod folders" data-tip-side="bottom" aria-label="Rescan mod folders"
+      >
+        <span className="icon-btn-glyph inline-flex"><RefreshCw className={`w-4 h-4${refreshMods.isPending ? ' animate-spin' : ''}`} /></span>
+      </button>
+
       {/* Settings */}
       <button
         onClick={() => setPreferencesOpen(true)}
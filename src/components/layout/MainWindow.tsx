@@ -5,6 +5,7 @@ import { useUpdater } from '@/hooks/useUpdater'
 import { useGetAppSettings } from '@/hooks/useSettings'
 import { useNxmDeepLink, syncNexusApiKeyFromPreferences } from '@/hooks/useNexusMods'
 import { useCostumeAutoSync } from '@/hooks/useMods'
+import { useWindowSync } from '@/hooks/useWindowSync'
 import { usePreferences } from '@/services/preferences'
 import { ModManager } from '../ModManager'
 import { PreferencesDialog } from '../preferences/PreferencesDialog'
@@ -30,6 +31,9 @@ export function MainWindow() {
   // Pull newly released costumes/icons from GitHub on startup
   useCostumeAutoSync()
 
+  // Refetch after edits made in the mod details window
+  useWindowSync()
+
   // Check for updates on startup if enabled in settings (only once per app session)
   useEffect(() => {
     console.log('[MainWindow] Auto-update check effect triggered');
@@ -0,0 +1,58 @@
+import { useEffect } from 'react'
+import { Toaster } from 'sonner'
+import { useQueryClient } from '@tanstack/react-query'
+import { listen } from '@tauri-apps/api/event'
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow'
+import { useTheme } from '@/hooks/use-theme'
+import { modKeys } from '@/hooks/useMods'
+import { useWindowSync } from '@/hooks/useWindowSync'
+import { useUIStore } from '@/stores'
+import { ModDetailsPanel } from '../ModDetailsPanel'
+import { MetadataDialog } from '../MetadataDialog'
+
+/**
+ * The separate mod details window (label `mod-detail`), opened from the
+ * details panel with open_mod_detail_window. The main window switches it to
+ * another mod with `mod-detail-select`; closing the panel closes the window.
+ */
+export function ModDetailWindow({ initialModId }: { initialModId: string | null }) {
+  const { theme } = useTheme()
+  const queryClient = useQueryClient()
+  const selectedModId = useUIStore((state) => state.selectedModId)
+  const setSelectedModId = useUIStore((state) => state.setSelectedModId)
+  useWindowSync()
+
+  useEffect(() => {
+    setSelectedModId(initialModId)
+  }, [initialModId, setSelectedModId])
+
+  useEffect(() => {
+    const unlistenSelect = listen<string>('mod-detail-select', (event) => {
+      setSelectedModId(event.payload)
+    })
+    const unlistenChanged = listen('mods-directory-changed', () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.lists() })
+    })
+
+    return () => {
+      unlistenSelect.then((fn) => fn())
+      unlistenChanged.then((fn) => fn())
+    }
+  }, [queryClient, setSelectedModId])
+
+  useEffect(() => {
+    if (selectedModId === null) {
+      getCurrentWebviewWindow().close()
+    }
+  }, [selectedModId])
+
+  return (
+    <div className="h-screen w-full overflow-hidden bg-background">
+      {selectedModId && <ModDetailsPanel detached />}
+      <MetadataDialog />
+      <Toaster position="bottom-right" theme={theme.includes('light') ? 'light' : 'dark'} />
+    </div>
+  )
+}
+
+export default ModDetailWindow
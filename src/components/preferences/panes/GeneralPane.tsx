@@ -1,13 +1,14 @@
 import React, { useEffect, useState } from 'react'
 import { Switch } from '@/components/ui/switch'
-import { useGetAppSettings, useSaveAppSettings } from '@/hooks/useSettings'
-import { useSyncCostumes } from '@/hooks/useMods'
+import { useGetAppSettings, useSaveAppSettings, useDetectGameDirectories, useEffectiveModsDirectory, useDiskUsage } from '@/hooks/useSettings'
+import { useSyncCostumes, useReadOnlyMode, useSetReadOnlyMode, useBaseGameIntegrity, useRebaselineGameFiles } from '@/hooks/useMods'
 import { useSkipIntros } from '@/hooks/useSkipIntros'
 import { open } from '@tauri-apps/plugin-dialog'
 import { invoke } from '@tauri-apps/api/core'
 import { toast } from 'sonner'
-import { FolderOpen, HardDrive, Settings2, RefreshCw, Film, Loader2, Trash2, Check } from 'lucide-react'
-import { c, tint } from '@/shared/rivals-tokens'
+import { FolderOpen, HardDrive, Search, Settings2, RefreshCw, Film, Loader2, Trash2, Check, ShieldCheck, ShieldAlert } from 'lucide-react'
+import type { DetectedGameDirectory, DetectionSource, VolumeUsage } from '@/types/mod.types'
+import { c, tint, formatFileSize } from '@/shared/rivals-tokens'
 import { SettingsSection, SettingsCard, SettingRow } from '../settings-ui'
 
 // Read-only / editable directory field row.
@@ -59,6 +60,14 @@ function DirRow({
   )
 }
 
+const DETECTION_SOURCE_LABELS: Record<DetectionSource, string> = {
+  steamDefault: 'Steam (default folder)',
+  uninstallRegistry: 'Windows installed apps',
+  netEaseLauncher: 'NetEase launcher',
+  candidateFolder: 'Folder name match',
+  manual: 'Picked manually',
+}
+
 // Outlined warm-dark button (skip-intros actions).
 function GhostButton({
   onClick,
@@ -88,7 +97,17 @@ function GhostButton({
 export const GeneralPane: React.FC = () => {
   const { data: settings, isLoading } = useGetAppSettings()
   const saveSettings = useSaveAppSettings()
+  const { data: modsDirectory } = useEffectiveModsDirectory()
+  const { data: diskUsage } = useDiskUsage()
+  const freeSpace = (volume?: VolumeUsage | null) =>
+    volume ? ` · ${formatFileSize(volume.freeBytes)} free of ${formatFileSize(volume.totalBytes)}` : ''
   const syncCostumes = useSyncCostumes()
+  const { data: readOnlyMode = false } = useReadOnlyMode()
+  const setReadOnlyMode = useSetReadOnlyMode()
+  const { data: integrity } = useBaseGameIntegrity()
+  const rebaseline = useRebaselineGameFiles()
+  const detectGameDirectories = useDetectGameDirectories()
+  const [detected, setDetected] = useState<DetectedGameDirectory[] | null>(null)
   const { status: skipIntrosStatus, install: installSkipIntros, uninstall: uninstallSkipIntros, isInstalling, isUninstalling } = useSkipIntros()
 
   const [gameDirectory, setGameDirectory] = useState('')
@@ -127,11 +146,18 @@ export const GeneralPane: React.FC = () => {
     }
   }
 
-  const handleSave = async (gameDir: string, organize: boolean, autoDetect: boolean, autoUpdates: boolean) => {
+  const handleReadOnlyMode = (enabled: boolean) => {
+    if (!enabled && !confirm('Turn off read-only mode? Mods can be installed, changed and deleted again.')) return
+    setReadOnlyMode.mutate(enabled)
+  }
+
+  const handleSave = async (gameDir: string, organize: boolean, autoDetect: boolean, autoUpdates: boolean, source?: DetectionSource) => {
     try {
       await saveSettings.mutateAsync({
+        ...settings,
         gameDirectory: gameDir || null,
-        modDirectory: null,
+        gameDirectorySource: source ?? settings?.gameDirectorySource ?? null,
+        modDirectory: settings?.modDirectory ?? null,
         theme: settings?.theme || 'dark',
         autoOrganize: organize,
         autoDetectGameDir: autoDetect,
@@ -149,7 +175,7 @@ export const GeneralPane: React.FC = () => {
       const selected = await open({ directory: true, multiple: false, title: 'Select Marvel Rivals Installation Directory' })
       if (selected && typeof selected === 'string') {
         setGameDirectory(selected)
-        await handleSave(selected, autoOrganize, autoDetectGameDir, autoCheckUpdates)
+        await handleSave(selected, autoOrganize, autoDetectGameDir, autoCheckUpdates, 'manual')
       }
     } catch (error) {
       console.error('Failed to open directory picker:', error)
@@ -157,10 +183,25 @@ export const GeneralPane: React.FC = () => {
     }
   }
 
+  const handleDetect = () => {
+    detectGameDirectories.mutate(undefined, {
+      onSuccess: (hits) => {
+        setDetected(hits)
+        if (hits.length === 0) toast.warning('No Marvel Rivals install found; use Browse to pick it')
+      },
+    })
+  }
+
+  const handlePickDetected = async (hit: DetectedGameDirectory) => {
+    setGameDirectory(hit.path)
+    setDetected(null)
+    await handleSave(hit.path, autoOrganize, autoDetectGameDir, autoCheckUpdates, hit.source)
+  }
+
   const handleOpenModsDirectory = async () => {
-    if (!gameDirectory) return
+    if (!modsDirectory) return
     try {
-      await invoke('plugin:opener|open_path', { path: `${gameDirectory}\\MarvelGame\\Marvel\\Content\\Paks\\~mods` })
+      await invoke('plugin:opener|open_path', { path: modsDirectory })
     } catch (error) {
       console.error('Failed to open mods directory:', error)
       toast.error('Failed to open mods directory')
@@ -211,12 +252,34 @@ export const GeneralPane: React.FC = () => {
             actionLabel="Browse"
             onAction={handleBrowseDirectory}
           />
-          {gameDirectory && (
+          <div className="space-y-2" style={{ padding: '0 14px 14px 36px' }}>
+            <div className="flex items-center justify-between gap-4">
+              <p style={{ color: c.ink3, fontFamily: c.font, fontSize: 11.5 }}>
+                {settings?.gameDirectorySource ? `Found via: ${DETECTION_SOURCE_LABELS[settings.gameDirectorySource]}` : 'Installed somewhere unusual? Look for every install.'}
+              </p>
+              <GhostButton onClick={handleDetect} disabled={detectGameDirectories.isPending}>
+                {detectGameDirectories.isPending ? <Loader2 className="h-4 w-4 animate-spin" /> : <Search className="h-4 w-4" />}
+                Detect
+              </GhostButton>
+            </div>
+            {detected?.map((hit) => (
+              <button
+                key={hit.path}
+                onClick={() => handlePickDetected(hit)}
+                className="settings-btn w-full flex items-center justify-between gap-3 cursor-pointer text-left"
+                style={{ padding: '7px 11px', borderRadius: 7, background: c.bg, border: `1px solid ${hit.path === gameDirectory ? tint(c.accent, 45) : c.line2}` }}
+              >
+                <span className="rivals-mono truncate" style={{ color: c.ink, fontSize: 12 }} title={hit.path}>{hit.path}</span>
+                <span style={{ color: c.ink3, fontFamily: c.font, fontSize: 11, whiteSpace: 'nowrap' }}>{DETECTION_SOURCE_LABELS[hit.source]}</span>
+              </button>
+            ))}
+          </div>
+          {modsDirectory && (
             <DirRow
               icon={<FolderOpen className="w-3.5 h-3.5" />}
               label="Mods Folder"
-              description="Where game mods are installed (auto-generated)"
-              value={`${gameDirectory}\\MarvelGame\\Marvel\\Content\\Paks\\~mods`}
+              description={(settings?.modDirectory ? 'Where game mods are installed (set in settings.json)' : 'Where game mods are installed (auto-generated)') + freeSpace(diskUsage?.modsVolume)}
+              value={modsDirectory}
               readOnly
               actionLabel="Open"
               onAction={handleOpenModsDirectory}
@@ -225,7 +288,7 @@ export const GeneralPane: React.FC = () => {
           <DirRow
             icon={<FolderOpen className="w-3.5 h-3.5" />}
             label="Metadata & Thumbnails"
-            description="Where mod metadata and custom thumbnails are stored"
+            description={'Where mod metadata and custom thumbnails are stored' + freeSpace(diskUsage?.appDataVolume)}
             value="AppData\Roaming\com.marvelrivalsmodmanager.app\metadata"
             readOnly
             actionLabel="Open"
@@ -234,6 +297,39 @@ export const GeneralPane: React.FC = () => {
         </SettingsCard>
       </SettingsSection>
 
+      {/* Game files: mods never touch the base paks */}
+      {integrity && (
+        <SettingsSection title="Game Files" icon={integrity.untouched ? <ShieldCheck className="w-4 h-4" /> : <ShieldAlert className="w-4 h-4" />}>
+          <SettingsCard pad={16} className="space-y-3">
+            <div className="flex items-center justify-between gap-4">
+              <p style={{ color: c.ink3, fontFamily: c.font, fontSize: 12.5, maxWidth: 420 }}>
+                {integrity.untouched
+                  ? `Mods only live in ~mods and disabled-mods; the game's ${integrity.baseFiles} own files are never written.`
+                  : `${integrity.filesOutsideModFolders.length} mod file(s) are outside ~mods and disabled-mods.`}
+              </p>
+              <span className="inline-flex items-center gap-1.5 rivals-mono" style={{ color: integrity.untouched ? c.ok : c.err, fontSize: 11, letterSpacing: '0.06em', textTransform: 'uppercase', whiteSpace: 'nowrap' }}>
+                {integrity.untouched && <Check className="h-3.5 w-3.5" />}
+                {integrity.untouched ? 'Untouched' : 'Check Needed'}
+              </span>
+            </div>
+            {integrity.filesOutsideModFolders.slice(0, 5).map((path) => (
+              <p key={path} className="rivals-mono truncate" style={{ color: c.ink2, fontSize: 11 }} title={path}>{path}</p>
+            ))}
+            {integrity.changedFiles.length > 0 && (
+              <div className="flex items-center justify-between gap-4">
+                <p style={{ color: c.ink3, fontFamily: c.font, fontSize: 12.5, maxWidth: 420 }}>
+                  {integrity.changedFiles.length} game file(s) changed since {new Date(integrity.baselineRecordedAt).toLocaleDateString()}, most likely a game update.
+                </p>
+                <GhostButton onClick={() => rebaseline.mutate()} disabled={rebaseline.isPending}>
+                  {rebaseline.isPending ? <Loader2 className="h-4 w-4 animate-spin" /> : <RefreshCw className="h-4 w-4" />}
+                  Accept as Baseline
+                </GhostButton>
+              </div>
+            )}
+          </SettingsCard>
+        </SettingsSection>
+      )}
+
       {/* Behavior */}
       <div className="grid grid-cols-2 gap-8">
         <SettingsSection title="Mod Management" icon={<Settings2 className="w-4 h-4" />}>
@@ -248,6 +344,11 @@ export const GeneralPane: React.FC = () => {
               description="Find Marvel Rivals installation on startup"
               control={<Switch checked={autoDetectGameDir} onCheckedChange={(v) => { setAutoDetectGameDir(v); handleSave(gameDirectory, autoOrganize, v, autoCheckUpdates) }} />}
             />
+            <SettingRow
+              label="Read-Only Mode"
+              description="Browse without any way to change mods, e.g. while streaming"
+              control={<Switch checked={readOnlyMode} disabled={setReadOnlyMode.isPending} onCheckedChange={handleReadOnlyMode} />}
+            />
             <SettingRow
               label="Remove Mods On Uninstall"
               description="Off: uninstalling the app never touches your mods"
@@ -1,6 +1,7 @@
 import React from 'react'
-import { Crown, BarChart3, Trophy } from 'lucide-react'
-import { useGetMods } from '@/hooks/useMods'
+import { Crown, BarChart3, Trophy, Gauge, Copy } from 'lucide-react'
+import { toast } from 'sonner'
+import { useGetMods, usePerformanceMetrics } from '@/hooks/useMods'
 import { useUIStore } from '@/stores'
 import { useUIStore as usePrefsStore } from '@/store/ui-store'
 import type { ModCategory, Character } from '@/types/mod.types'
@@ -8,10 +9,11 @@ import { c, tint, categoryColor, formatFileSize, getCharacterIconPath, withViewT
 import { CategoryIcon, RingAvatar } from '@/shared/rivals-design'
 import { SettingsSection, SettingsCard } from '../settings-ui'
 
-const CATS: ModCategory[] = ['Skins', 'Audio', 'UI', 'Gameplay']
+const CATS: ModCategory[] = ['Skins', 'Audio', 'UI', 'Gameplay', 'Other']
 
 export const StatsPane: React.FC = () => {
   const { data: mods } = useGetMods()
+  const { data: performance } = usePerformanceMetrics()
   const showNsfw = useUIStore((s) => s.filters.showNsfw)
   const setFilters = useUIStore((s) => s.setFilters)
   const setPreferencesOpen = usePrefsStore((s) => s.setPreferencesOpen)
@@ -61,6 +63,16 @@ export const StatsPane: React.FC = () => {
     withViewTransition(() => setFilters({ character: ch as Character, category: null, showFavorites: false }))
   }
 
+  // The raw snapshot, for pasting into a bug report
+  const copyPerformance = async () => {
+    try {
+      await navigator.clipboard.writeText(JSON.stringify(performance, null, 2))
+      toast.success('Performance snapshot copied')
+    } catch {
+      toast.error('Failed to copy performance snapshot')
+    }
+  }
+
   const heroBlocks = [
     { label: 'Total Mods', value: String(stats.total) },
     { label: 'Active', value: String(stats.enabled), sub: stats.total > 0 ? `${Math.round((stats.enabled / stats.total) * 100)}%` : undefined },
@@ -191,6 +203,62 @@ export const StatsPane: React.FC = () => {
           ))}
         </SettingsCard>
       </SettingsSection>
+
+      {/* Operation timings, kept locally */}
+      <SettingsSection title="Performance" icon={<Gauge className="w-4 h-4" />}>
+        <SettingsCard pad={16}>
+          {!performance || performance.operations.length === 0 ? (
+            <p style={{ color: c.ink3, fontFamily: c.font, fontSize: 12.5, fontStyle: 'italic', padding: '4px 6px' }}>
+              No timings yet — they're recorded as you scan, install and toggle mods.
+            </p>
+          ) : (
+            <>
+              <div className="flex items-baseline gap-4" style={{ padding: '0 2px 6px' }}>
+                {['Operation', 'p50', 'p95', 'Last', 'Runs'].map((label, i) => (
+                  <span
+                    key={label}
+                    className={`rivals-mono ${i === 0 ? 'flex-1' : 'flex-shrink-0 text-right'}`}
+                    style={{ color: c.ink3, fontSize: 10, letterSpacing: '0.14em', textTransform: 'uppercase', width: i === 0 ? undefined : 64 }}
+                  >
+                    {label}
+                  </span>
+                ))}
+              </div>
+              {performance.operations.map((op) => (
+                <div
+                  key={op.operation}
+                  className="flex items-baseline gap-4"
+                  style={{ padding: '7px 2px', borderTop: `1px solid ${c.line}` }}
+                  data-tip={`Last run handled ${op.lastItems} item(s) on ${new Date(op.lastAt).toLocaleString()}`}
+                >
+                  <span className="rivals-mono flex-1 truncate" style={{ color: c.ink2, fontSize: 12.5 }}>{op.operation}</span>
+                  {[op.p50Ms, op.p95Ms, op.lastMs, op.samples].map((value, i) => (
+                    <span key={i} className="rivals-mono flex-shrink-0 text-right" style={{ color: c.ink, fontSize: 12.5, fontWeight: 600, width: 64 }}>
+                      {i < 3 ? `${value} ms` : value}
+                    </span>
+                  ))}
+                </div>
+              ))}
+            </>
+          )}
+          {performance?.scanProfile && (
+            <p className="rivals-mono" style={{ color: c.ink3, fontSize: 11, padding: '10px 2px 0' }}>
+              Last scan: {performance.scanProfile.files} files in {performance.scanProfile.directories} folders, {performance.scanProfile.durationMs} ms
+            </p>
+          )}
+          <div className="flex justify-end" style={{ paddingTop: 10 }}>
+            <button
+              onClick={copyPerformance}
+              disabled={!performance}
+              className="btn-outline h-8 px-3 inline-flex items-center gap-1.5 cursor-pointer"
+              style={{ borderRadius: 8, background: 'transparent', color: c.ink2, border: `1px solid ${c.line2}`, fontFamily: c.font, fontSize: 12, fontWeight: 600 }}
+            >
+              <Copy className="w-3.5 h-3.5" />
+              Copy snapshot
+            </button>
+          </div>
+        </SettingsCard>
+      </SettingsSection>
     </>
   )
 }
@@ -1,4 +1,5 @@
 export * from './useMods';
 export * from './useSettings';
 export * from './useFileWatcher';
+export * from './useFileActivity';
 export * from './use-theme';
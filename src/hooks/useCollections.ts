@@ -0,0 +1,110 @@
+import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query'
+import { invoke } from '@tauri-apps/api/core'
+import { toast } from 'sonner'
+import type { ModCollection, ToggledMod } from '@/types/mod.types'
+import { modKeys } from './useMods'
+
+export const collectionKeys = {
+  all: ['collections'] as const,
+}
+
+/**
+ * Hook to list all collections
+ */
+export function useGetCollections() {
+  return useQuery({
+    queryKey: collectionKeys.all,
+    queryFn: () => invoke<ModCollection[]>('list_collections'),
+    staleTime: 60000,
+  })
+}
+
+/**
+ * Replace one collection in the cached list with the backend's copy
+ */
+function useStoreCollection() {
+  const queryClient = useQueryClient()
+  return (collection: ModCollection) => {
+    queryClient.setQueryData<ModCollection[]>(collectionKeys.all, (old) =>
+      old?.some((c) => c.id === collection.id)
+        ? old.map((c) => (c.id === collection.id ? collection : c))
+        : [...(old ?? []), collection]
+    )
+  }
+}
+
+export function useCreateCollection() {
+  const storeCollection = useStoreCollection()
+  return useMutation({
+    mutationFn: ({ name, description }: { name: string; description?: string | null }) =>
+      invoke<ModCollection>('create_collection', { name, description: description ?? null }),
+    onSuccess: storeCollection,
+    onError: (error) => toast.error(`Failed to create collection: ${error}`),
+  })
+}
+
+export function useDeleteCollection() {
+  const queryClient = useQueryClient()
+  return useMutation({
+    mutationFn: (collectionId: string) => invoke('delete_collection', { collectionId }),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: collectionKeys.all }),
+    onError: (error) => toast.error(`Failed to delete collection: ${error}`),
+  })
+}
+
+export function useAddToCollection() {
+  const storeCollection = useStoreCollection()
+  return useMutation({
+    mutationFn: ({ collectionId, modIds }: { collectionId: string; modIds: string[] }) =>
+      invoke<ModCollection>('add_to_collection', { collectionId, modIds }),
+    onSuccess: storeCollection,
+    onError: (error) => toast.error(`Failed to add to collection: ${error}`),
+  })
+}
+
+export function useRemoveFromCollection() {
+  const storeCollection = useStoreCollection()
+  return useMutation({
+    mutationFn: ({ collectionId, modIds }: { collectionId: string; modIds: string[] }) =>
+      invoke<ModCollection>('remove_from_collection', { collectionId, modIds }),
+    onSuccess: storeCollection,
+    onError: (error) => toast.error(`Failed to remove from collection: ${error}`),
+  })
+}
+
+export function useReorderCollection() {
+  const storeCollection = useStoreCollection()
+  return useMutation({
+    mutationFn: ({ collectionId, modIds }: { collectionId: string; modIds: string[] }) =>
+      invoke<ModCollection>('reorder_collection', { collectionId, modIds }),
+    onSuccess: storeCollection,
+    onError: (error) => toast.error(`Failed to reorder collection: ${error}`),
+  })
+}
+
+/**
+ * Enable or disable a whole collection. Toggling changes mod IDs, so both
+ * the mod list and the collections (which follow the new IDs) are refetched.
+ */
+export function useSetCollectionEnabled() {
+  const queryClient = useQueryClient()
+  return useMutation({
+    mutationFn: ({ collectionId, enabled }: { collectionId: string; enabled: boolean }) =>
+      invoke<ToggledMod[]>('set_collection_enabled', { collectionId, enabled }),
+    onSuccess: (toggled, { enabled }) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.lists() })
+      queryClient.invalidateQueries({ queryKey: collectionKeys.all })
+      toast.success(`${enabled ? 'Enabled' : 'Disabled'} ${toggled.length} mod(s)`)
+    },
+    onError: (error) => toast.error(`Failed to toggle collection: ${error}`),
+  })
+}
+
+export function useExportCollection() {
+  return useMutation({
+    mutationFn: ({ collectionId, destPath }: { collectionId: string; destPath: string }) =>
+      invoke<number>('export_collection', { collectionId, destPath }),
+    onSuccess: (count) => toast.success(`Exported ${count} mod(s)`),
+    onError: (error) => toast.error(`Failed to export collection: ${error}`),
+  })
+}
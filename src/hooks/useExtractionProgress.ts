@@ -0,0 +1,26 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import type { ExtractionProgress } from '../types/mod.types';
+
+/**
+ * Hook for how far the archive being extracted has got, in bytes of its
+ * uncompressed size. Null while `active` is false.
+ */
+export function useExtractionProgress(active: boolean) {
+  const [progress, setProgress] = useState<ExtractionProgress | null>(null);
+
+  useEffect(() => {
+    if (!active) {
+      setProgress(null);
+      return;
+    }
+    const unlisten = listen<ExtractionProgress>('extraction-progress', (event) => {
+      setProgress(event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [active]);
+
+  return progress;
+}
@@ -0,0 +1,33 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import type { FileOperation, FileOperationBatch } from '../types/mod.types';
+
+/**
+ * Hook for the activity feed: the most recent file operations the backend
+ * reported, newest first. Only receives anything while the
+ * `fileActivityEvents` setting is on.
+ */
+export function useFileActivity(limit: number = 200) {
+  const [operations, setOperations] = useState<FileOperation[]>([]);
+  const [dropped, setDropped] = useState(0);
+
+  useEffect(() => {
+    const unlisten = listen<FileOperationBatch>('file-operation', (event) => {
+      const batch = event.payload;
+      setOperations((current) => [...batch.operations.reverse(), ...current].slice(0, limit));
+      if (batch.dropped > 0) {
+        setDropped((current) => current + batch.dropped);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [limit]);
+
+  const clear = () => {
+    setOperations([]);
+    setDropped(0);
+  };
+
+  return { operations, dropped, clear };
+}
@@ -44,10 +44,23 @@ export function useFileWatcher(modsDirectory: string | null, enabled: boolean =
       queryClient.invalidateQueries({ queryKey: modKeys.stats() });
     });
 
+    // The game's drive was unplugged or came back: refresh everything, and
+    // start watching if the folder was missing when the app started
+    const unlistenUnavailable = listen('game-directory-unavailable', () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all });
+    });
+    const unlistenAvailable = listen('game-directory-available', () => {
+      console.log('Game directory available again, refetching...');
+      startWatcher();
+      queryClient.invalidateQueries({ queryKey: modKeys.all });
+    });
+
     // Cleanup
     return () => {
       stopWatcher();
       unlisten.then((fn) => fn());
+      unlistenUnavailable.then((fn) => fn());
+      unlistenAvailable.then((fn) => fn());
     };
   }, [modsDirectory, enabled, startWatcher, stopWatcher, queryClient]);
 
@@ -0,0 +1,22 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import type { HashingProgress } from '../types/mod.types';
+
+/**
+ * Hook for the backend hashing new or changed mod files after a scan. Null
+ * when nothing is being hashed.
+ */
+export function useHashingProgress() {
+  const [progress, setProgress] = useState<HashingProgress | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<HashingProgress>('hashing-progress', (event) => {
+      setProgress(event.payload.current === null ? null : event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return progress;
+}
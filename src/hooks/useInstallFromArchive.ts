@@ -3,7 +3,7 @@ import { useMutation, useQueryClient } from '@tanstack/react-query';
 import { invoke } from '@tauri-apps/api/core';
 import { toast } from 'sonner';
 import type { DetectedMod } from '../components/ModSelectionDialog';
-import type { ModCategory, Character, ModInfo } from '../types/mod.types';
+import type { ModCategory, Character, ModInfo, InstallResult } from '../types/mod.types';
 import { sanitizeFolderName } from '../utils/sanitize';
 
 // Global extraction lock to prevent duplicate extractions across HMR instances
@@ -188,11 +188,12 @@ export function useInstallFromArchive() {
         nexusVersion: null,
       };
 
-      const modInfo = await invoke<ModInfo>('install_mod_to_folder_with_metadata', {
+      const { modInfo, warnings } = await invoke<InstallResult>('install_mod_to_folder_with_metadata', {
         filePath: pakFile,
         folderName,
         metadata,
       });
+      warnings.forEach((warning) => toast.warning(warning));
 
       console.log('[useInstallFromArchive] Mod installed with metadata:', modInfo.id, modInfo.name);
       return modInfo;
@@ -0,0 +1,31 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { ModInstallProgress } from '../types/mod.types';
+
+/**
+ * Hook for the bytes an install has copied, across the pak and its
+ * companions. Null when no install is copying.
+ */
+export function useInstallProgress() {
+  const [progress, setProgress] = useState<ModInstallProgress | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<ModInstallProgress>('mod-install-progress', (event) => {
+      setProgress(event.payload.status === 'done' ? null : event.payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return progress;
+}
+
+/**
+ * Stop the install copying `filePath`. Its install fails with "Install
+ * cancelled" and leaves nothing behind. False if it had already finished.
+ */
+export function cancelModInstall(filePath: string) {
+  return invoke<boolean>('cancel_mod_install', { filePath });
+}
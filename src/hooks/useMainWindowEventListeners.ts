@@ -1,4 +1,5 @@
 import { useEffect } from 'react'
+import { invoke } from '@tauri-apps/api/core'
 import { listen } from '@tauri-apps/api/event'
 import { check } from '@tauri-apps/plugin-updater'
 import { useUIStore } from '@/store/ui-store'
@@ -124,6 +125,27 @@ export function useMainWindowEventListeners() {
     }
   }, [commandContext])
 
+  // Keep the View menu's sidebar checkmarks in step with the layout: once
+  // now, then whenever a sidebar is shown or hidden
+  useEffect(() => {
+    const syncMenu = (left: boolean, right: boolean) => {
+      invoke('set_sidebar_menu_state', { left, right }).catch(error => {
+        logger.error('Failed to update sidebar menu state:', { error: String(error) })
+      })
+    }
+    const { leftSidebarVisible, rightSidebarVisible } = useUIStore.getState()
+    syncMenu(leftSidebarVisible, rightSidebarVisible)
+
+    return useUIStore.subscribe((state, previous) => {
+      if (
+        state.leftSidebarVisible !== previous.leftSidebarVisible ||
+        state.rightSidebarVisible !== previous.rightSidebarVisible
+      ) {
+        syncMenu(state.leftSidebarVisible, state.rightSidebarVisible)
+      }
+    })
+  }, [])
+
   // Future: Other global event listeners can be added here
   // useWindowFocusListeners()
 }
@@ -2,6 +2,7 @@ import { useMutation, useQueryClient } from '@tanstack/react-query'
 import { invoke } from '@tauri-apps/api/core'
 import { toast } from 'sonner'
 import { modKeys } from './useMods'
+import type { IdMigrationReport } from '@/types/mod.types'
 
 /**
  * Hook to migrate data from old Electron app
@@ -36,3 +37,32 @@ export function useMigrateElectronData() {
     },
   })
 }
+
+/**
+ * Hook to move metadata from old filename-based IDs to path-based IDs,
+ * optionally for just some mods or as a dry run that only reports
+ */
+export function useMigrateMetadataIds() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ modIds, dryRun }: { modIds?: string[]; dryRun?: boolean } = {}) => {
+      return await invoke<IdMigrationReport>('migrate_metadata_to_path_ids', {
+        modIds: modIds ?? null,
+        dryRun: dryRun ?? false,
+      })
+    },
+    onSuccess: (report) => {
+      if (report.dryRun) return
+      queryClient.invalidateQueries({ queryKey: modKeys.lists() })
+      const migrated = report.migratedCount + report.resumedCount
+      toast.success(
+        `Migrated metadata for ${migrated} mod(s)` +
+          (report.notFoundCount > 0 ? `; ${report.notFoundCount} had none to recover` : '')
+      )
+    },
+    onError: (error: Error) => {
+      toast.error(`Metadata migration failed: ${error.message}`)
+    },
+  })
+}
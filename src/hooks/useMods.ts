@@ -1,7 +1,8 @@
 import { useEffect } from 'react'
+import { listen } from '@tauri-apps/api/event'
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query'
 import { invoke } from '@tauri-apps/api/core'
-import type { ModInfo, ModMetadata, Costume, CostumeSyncResult, Character, ModConflict } from '@/types/mod.types'
+import type { ModInfo, ModMetadata, MetadataUpdateError, ModCategory, ModQuery, Costume, CostumeSyncResult, CostumeServiceStatus, CostumePatchSummary, CostumePatchValidation, Character, ModConflict, GameLogIssue, ToggledMod, TogglePreview, ModUpdate, LibrarySummary, DisabledReason, DisabledModEntry, DisabledPurgeReport, CleanupAnalysis, CleanupSummary, ThumbnailImportReport, ThumbnailMatchStrategy, PakTechnicalInfo, ModVerificationIssue, FavoriteToggle, ModReferences, DeleteModError, AssignCharacterError, BaseGameIntegrity, DeletePreview, MetadataSuggestion, LowConfidenceAssignment, GameDirectoryStatus, DisableStrategy, DisableStrategyReport, QuarantineState, QuarantineRelease, LibraryRepairStep, LibraryRepairReport, LibraryRepairCheckpoint, InstallResult, LayoutDrift, DownloadRecord, MetadataTextField, MetadataReplacement, ScanProfile, PerformanceMetrics, ModLocation, ModCardPage, SortSpec, BulkToggleReport, AppStats, CategoryStats, CharacterStats, ModProfile, ProfileApplySummary, TrashedMod, DuplicateModGroup, PakValidation, ModPage, OrganizeMove, OrganizationResult, VariantGroup, ModListFormat, ModListImportReport, ModsIntegrityReport } from '@/types/mod.types'
 import { toast } from 'sonner'
 
 // Query keys factory
@@ -9,10 +10,41 @@ export const modKeys = {
   all: ['mods'] as const,
   lists: () => [...modKeys.all, 'list'] as const,
   list: (filters?: any) => [...modKeys.lists(), { filters }] as const,
+  // Under lists() so every invalidation of the mod list refreshes them too
+  cards: (filter: ModQuery | undefined, sort: SortSpec | undefined, offset: number, limit: number) =>
+    [...modKeys.lists(), 'cards', { filter, sort, offset, limit }] as const,
+  page: (query: ModQuery, sort: SortSpec | undefined, offset: number, limit: number) =>
+    [...modKeys.lists(), 'page', { query, sort, offset, limit }] as const,
+  full: (id: string) => [...modKeys.lists(), 'full', id] as const,
   details: () => [...modKeys.all, 'detail'] as const,
   detail: (id: string) => [...modKeys.details(), id] as const,
   stats: () => [...modKeys.all, 'stats'] as const,
   conflicts: () => [...modKeys.all, 'conflicts'] as const,
+  favorites: () => [...modKeys.all, 'favorites'] as const,
+  verification: () => [...modKeys.all, 'verification'] as const,
+  pakInfo: (id: string) => [...modKeys.all, 'pak-info', id] as const,
+  gameLogIssues: () => [...modKeys.all, 'game-log-issues'] as const,
+  updates: () => [...modKeys.all, 'updates'] as const,
+  summary: () => [...modKeys.all, 'summary'] as const,
+  libraryStats: (kind: 'app' | 'category' | 'character') => [...modKeys.lists(), 'stats', kind] as const,
+  disabledOverview: () => [...modKeys.all, 'disabled-overview'] as const,
+  cleanup: (disabledDays: number) => [...modKeys.all, 'cleanup', disabledDays] as const,
+  gameDirectory: () => [...modKeys.all, 'game-directory'] as const,
+  baseGameIntegrity: () => [...modKeys.all, 'base-game-integrity'] as const,
+  quarantine: () => [...modKeys.all, 'quarantine'] as const,
+  modsIntegrity: () => [...modKeys.all, 'mods-integrity'] as const,
+  readOnly: () => [...modKeys.all, 'read-only'] as const,
+  libraryRepair: () => [...modKeys.all, 'library-repair'] as const,
+  layoutDrift: () => [...modKeys.all, 'layout-drift'] as const,
+  downloads: () => [...modKeys.all, 'downloads'] as const,
+  scanProfile: () => [...modKeys.all, 'scan-profile'] as const,
+  profiles: () => [...modKeys.lists(), 'profiles'] as const,
+  trash: () => [...modKeys.lists(), 'trash'] as const,
+  duplicateMods: () => [...modKeys.lists(), 'duplicate-mods'] as const,
+  organizePreview: () => [...modKeys.lists(), 'organize-preview'] as const,
+  variantGroups: () => [...modKeys.lists(), 'variant-groups'] as const,
+  performance: () => [...modKeys.all, 'performance'] as const,
+  metadataRaw: (id: string) => [...modKeys.all, 'metadata-raw', id] as const,
 }
 
 // Query keys
@@ -34,297 +66,1823 @@ export function useGetMods() {
   })
 }
 
+/**
+ * One page of full mods matching `query`, filtered and sorted by the
+ * backend, with the total match count for paging
+ */
+export function useModPage(query: ModQuery, sort: SortSpec | undefined, offset: number, limit: number) {
+  return useQuery({
+    queryKey: modKeys.page(query, sort, offset, limit),
+    queryFn: () => invoke<ModPage>('query_mods', { query, sort, offset, limit }),
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * One page of slim grid cards, filtered and sorted by the backend
+ */
+export function useModCards(filter: ModQuery | undefined, sort: SortSpec | undefined, offset: number, limit: number) {
+  return useQuery({
+    queryKey: modKeys.cards(filter, sort, offset, limit),
+    queryFn: () => invoke<ModCardPage>('get_mod_cards', { filter, sort, offset, limit }),
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * The full details of one mod, for the detail panel
+ */
+export function useMod(modId: string | null) {
+  return useQuery({
+    queryKey: modKeys.full(modId ?? ''),
+    queryFn: () => invoke<ModInfo>('get_mod', { modId }),
+    enabled: !!modId,
+    retry: false,
+  })
+}
+
+/**
+ * Whether a command failed because the game folder's drive is offline,
+ * rather than for a real reason. Commands returning a typed error say so
+ * with its code; plain string errors only carry the message.
+ */
+export function isGameDirectoryUnavailable(error: unknown): boolean {
+  return (error as { code?: string } | null)?.code === 'gameDirectoryUnavailable'
+}
+
+/**
+ * Hook to check whether the game folder is reachable. While its drive is
+ * offline the mods list is the last saved listing and changes are refused.
+ */
+export function useGameDirectoryStatus() {
+  return useQuery({
+    queryKey: modKeys.gameDirectory(),
+    queryFn: async () => {
+      return await invoke<GameDirectoryStatus>('get_game_directory_status')
+    },
+    staleTime: 30000,
+    refetchOnWindowFocus: true,
+    retry: false,
+  })
+}
+
+/**
+ * Hook to confirm the game's own files are as first recorded and every mod
+ * file sits in ~mods or disabled-mods. The first run records the baseline.
+ */
+export function useBaseGameIntegrity() {
+  return useQuery({
+    queryKey: modKeys.baseGameIntegrity(),
+    queryFn: async () => {
+      return await invoke<BaseGameIntegrity>('verify_base_game_untouched')
+    },
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook to accept the game's files as they are now, after a game update
+ */
+export function useRebaselineGameFiles() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async () => {
+      return await invoke<BaseGameIntegrity>('rebaseline_game_files')
+    },
+    onSuccess: (integrity) => {
+      queryClient.setQueryData(modKeys.baseGameIntegrity(), integrity)
+      toast.success(`Recorded ${integrity.baseFiles} game file${integrity.baseFiles === 1 ? '' : 's'} as the new baseline`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to record game files: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to re-read the library. The backend serves its cached scan unless
+ * something changed; force rescans everything through force_rescan, which
+ * also resets the background rescan so it doesn't report the same change.
+ */
+export function useRefreshMods() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (force: boolean) => {
+      if (force) {
+        return await invoke<ModInfo[]>('force_rescan')
+      }
+      return await invoke<ModInfo[]>('refresh_mods', { force })
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to refresh mods: ${error.message}`)
+    },
+  })
+}
+
 /**
  * Hook to detect conflicts between enabled mods (mods overriding the same
- * game assets). Excludes parent/add-on pairs, which are meant to layer.
+ * game assets), or all mods with includeDisabled. Excludes parent/add-on
+ * pairs, which are meant to layer.
+ */
+export function useModConflicts(includeDisabled = false) {
+  return useQuery({
+    queryKey: [...modKeys.conflicts(), { includeDisabled }],
+    queryFn: async () => {
+      return await invoke<ModConflict[]>('detect_mod_conflicts', { includeDisabled })
+    },
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook to check mods for signs they won't load, such as a mount point unlike
+ * the one the library's working mods share (often an outdated mod).
+ */
+export function useVerifyMods() {
+  return useQuery({
+    queryKey: modKeys.verification(),
+    queryFn: async () => {
+      return await invoke<ModVerificationIssue[]>('verify_mods')
+    },
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook for the installed mods whose paks are corrupted or incomplete, or
+ * whose companions don't pair up, for a "verify installed mods" button
+ */
+export function useValidateMods() {
+  return useQuery({
+    queryKey: [...modKeys.verification(), 'paks'],
+    queryFn: async () => {
+      return await invoke<PakValidation[]>('validate_mods')
+    },
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Check one installed mod's pak and companions
+ */
+export async function validateMod(modId: string): Promise<PakValidation> {
+  return await invoke<PakValidation>('validate_mod', { modId })
+}
+
+/**
+ * Hook for one mod's raw pak/utoc header values (versions, mount points)
+ */
+export function usePakTechnicalInfo(modId: string | null) {
+  return useQuery({
+    queryKey: modKeys.pakInfo(modId ?? ''),
+    queryFn: async () => {
+      return await invoke<PakTechnicalInfo>('get_pak_technical_info', { modId })
+    },
+    enabled: !!modId,
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook for the dashboard's counts in one call. Conflicts and pending updates
+ * are from their last runs; check `stale` before showing them as current.
+ */
+export function useLibrarySummary() {
+  return useQuery({
+    queryKey: modKeys.summary(),
+    queryFn: async () => {
+      return await invoke<LibrarySummary>('get_library_summary')
+    },
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Library-wide counts for the dashboard. Keyed under the mod list so they
+ * refresh with it; mods without a character are the null entry of
+ * useCharacterStats.
+ */
+export function useAppStats() {
+  return useQuery({
+    queryKey: modKeys.libraryStats('app'),
+    queryFn: () => invoke<AppStats>('get_app_stats'),
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+export function useCategoryStats() {
+  return useQuery({
+    queryKey: modKeys.libraryStats('category'),
+    queryFn: () => invoke<CategoryStats[]>('get_category_stats'),
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+export function useCharacterStats() {
+  return useQuery({
+    queryKey: modKeys.libraryStats('character'),
+    queryFn: () => invoke<CharacterStats[]>('get_character_stats'),
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook to find mods the game failed to load last session (parsed from the
+ * game's own log). Disable offenders with useSetModsEnabled.
+ */
+export function useGameLogIssues() {
+  return useQuery({
+    queryKey: modKeys.gameLogIssues(),
+    queryFn: async () => {
+      return await invoke<GameLogIssue[]>('analyze_game_logs')
+    },
+    staleTime: 5 * 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook to list installed mods with a newer version on Nexus, as found by the
+ * backend's daily check. Refetches when a check finds something new.
+ */
+export function usePendingModUpdates() {
+  const queryClient = useQueryClient()
+
+  useEffect(() => {
+    const unlisten = listen<number>('mod-updates-available', () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.updates() })
+    })
+    return () => {
+      unlisten.then(fn => fn())
+    }
+  }, [queryClient])
+
+  return useQuery({
+    queryKey: modKeys.updates(),
+    queryFn: async () => {
+      return await invoke<ModUpdate[]>('get_pending_mod_updates')
+    },
+    staleTime: 5 * 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook to skip one version of an update; newer versions still show up
+ */
+export function useDismissModUpdate() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ modId, version }: { modId: string; version: string }) => {
+      await invoke('dismiss_mod_update', { modId, version })
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.updates() })
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to dismiss update: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to install a mod
+ */
+export function useInstallMod() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (filePath: string) => {
+      return await invoke<InstallResult>('install_mod', { filePath })
+    },
+    onSuccess: ({ modInfo: newMod, warnings }) => {
+      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
+        return [newMod, ...oldMods]
+      })
+      toast.success(`Mod "${newMod.name}" installed successfully`)
+      warnings.forEach((warning) => toast.warning(warning))
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to install mod: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to enable/disable a mod
+ */
+export function useToggleMod() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ modId, enabled, exclusiveInGroup }: { modId: string; enabled: boolean; exclusiveInGroup?: boolean }) => {
+      const isGameRunning = await checkGameRunning()
+      if (isGameRunning) {
+        throw new Error('Cannot enable/disable mods while Marvel Rivals is running. Please close the game first.')
+      }
+
+      const modInfo = await invoke<ModInfo>('enable_mod', { modId, enabled, exclusiveInGroup })
+      return { modId, enabled, exclusiveInGroup, modInfo }
+    },
+    onSuccess: (data) => {
+      const action = data.enabled ? 'enabled' : 'disabled'
+      if (data.exclusiveInGroup) {
+        // Other variants of the group were disabled too
+        queryClient.invalidateQueries({ queryKey: modKeys.all })
+      } else {
+        // Replace the whole entry: the id, filePath and associatedFiles all moved
+        queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
+          return oldMods.map(mod => (mod.id === data.modId ? data.modInfo : mod))
+        })
+      }
+      toast.success(`Mod ${action} successfully`)
+    },
+    onError: (error: Error) => {
+      toast.error(error.message)
+    },
+  })
+}
+
+/**
+ * Hook to enable/disable many mods in a single backend call.
+ *
+ * Bulk operations used to loop on the frontend, paying one IPC round-trip,
+ * one game-running check, one toast, and one cache write per mod. This does
+ * the whole batch in the backend (one IPC call), checks the game once, and
+ * refetches the list a single time. Toggling changes a mod's path-based ID,
+ * so we invalidate rather than optimistically patch by id.
+ */
+export function useSetModsEnabled() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ modIds, enabled, reason }: { modIds: string[]; enabled: boolean; reason?: DisabledReason }) => {
+      const isGameRunning = await checkGameRunning()
+      if (isGameRunning) {
+        throw new Error('Cannot enable/disable mods while Marvel Rivals is running. Please close the game first.')
+      }
+
+      const toggled = await invoke<ToggledMod[]>('set_mods_enabled', { modIds, enabled, reason: reason ?? null })
+      return { count: toggled.length, toggled, enabled }
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
+      queryClient.invalidateQueries({ queryKey: modKeys.conflicts() })
+    },
+    onError: (error: Error) => {
+      toast.error(error.message)
+    },
+  })
+}
+
+/**
+ * Enable or disable many mods from one library scan. Mods that fail are
+ * listed in the report and don't stop the rest; progress arrives as
+ * bulk-toggle-progress events.
+ */
+export function useEnableModsBulk() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ modIds, enabled }: { modIds: string[]; enabled: boolean }) => {
+      const isGameRunning = await checkGameRunning()
+      if (isGameRunning) {
+        throw new Error('Cannot enable/disable mods while Marvel Rivals is running. Please close the game first.')
+      }
+
+      return await invoke<BulkToggleReport>('enable_mods_bulk', { modIds, enabled })
+    },
+    onSuccess: (report) => {
+      queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
+      queryClient.invalidateQueries({ queryKey: modKeys.conflicts() })
+      if (report.failed.length > 0) {
+        toast.error(`${report.failed.length} mod(s) could not be changed: ${report.failed[0].reason}`)
+      }
+    },
+    onError: (error: Error) => {
+      toast.error(error.message)
+    },
+  })
+}
+
+/**
+ * Hook to check if the game is running
+ */
+async function checkGameRunning(): Promise<boolean> {
+  try {
+    const isRunning = await invoke<boolean>('is_game_running')
+    return isRunning
+  } catch {
+    return false
+  }
+}
+
+/**
+ * Hook to delete a mod
+ */
+export function useDeleteMod() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (modId: string) => {
+      const isGameRunning = await checkGameRunning()
+      if (isGameRunning) {
+        throw new Error('Cannot delete mods while Marvel Rivals is running. Please close the game first.')
+      }
+
+      const deleted = await invokeDelete<void>('delete_mod', { modId })
+      return deleted === null ? null : modId
+    },
+    onSuccess: (modId) => {
+      if (modId === null) return
+      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
+        return oldMods.filter(mod => mod.id !== modId)
+      })
+      queryClient.invalidateQueries({ queryKey: modKeys.trash() })
+      toast.success('Mod moved to the trash')
+    },
+    onError: (error: Error) => {
+      toast.error(error.message)
+    },
+  })
+}
+
+/**
+ * Category and character auto-detection would give a pak, with confidence,
+ * so only unsure guesses need asking about
+ */
+export async function previewModMetadata(filePath: string): Promise<MetadataSuggestion> {
+  return await invoke<MetadataSuggestion>('preview_mod_metadata', { filePath })
+}
+
+/**
+ * Hook for installed mods whose character likely came from a weak file name
+ * guess, for review
+ */
+export function useLowConfidenceAssignments() {
+  return useQuery({
+    queryKey: [...modKeys.all, 'low-confidence'] as const,
+    queryFn: async () => {
+      return await invoke<LowConfidenceAssignment[]>('find_low_confidence_assignments')
+    },
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Profiles and collections a mod belongs to, for a delete confirmation
+ */
+export async function getModReferences(modId: string): Promise<ModReferences> {
+  return await invoke<ModReferences>('get_mod_references', { modId })
+}
+
+/**
+ * Exactly what deleting these mods would remove, for the delete confirmation
+ */
+export async function previewDelete(modIds: string[]): Promise<DeletePreview> {
+  return await invoke<DeletePreview>('preview_delete', { modIds })
+}
+
+function deleteErrorMessage(error: unknown): string {
+  const rejection = error as DeleteModError
+  return rejection?.code === 'failed' ? rejection.message : String(error)
+}
+
+/**
+ * Run a delete command. Mods in a profile or collection are refused by the
+ * backend; say what they belong to and, if the user agrees, delete anyway
+ * (which drops them from those too). Resolves to null if the user backs out.
+ */
+async function invokeDelete<T>(command: 'delete_mod' | 'delete_mods' | 'apply_cleanup_suggestions', args: Record<string, unknown>): Promise<T | null> {
+  try {
+    return await invoke<T>(command, args)
+  } catch (error) {
+    const rejection = error as DeleteModError
+    if (rejection?.code !== 'modInUse') {
+      throw new Error(deleteErrorMessage(error))
+    }
+    const [first] = rejection.mods
+    const question = rejection.mods.length === 1
+      ? `"${first.title}" is in ${first.profiles.length} profile(s) and ${first.collections.length} collection(s). Delete it anyway and remove it from them?`
+      : `${rejection.mods.length} of these mods are in profiles or collections. Delete them anyway and remove them from those?`
+    if (!confirm(question)) return null
+    try {
+      return await invoke<T>(command, { ...args, force: true })
+    } catch (forcedError) {
+      throw new Error(deleteErrorMessage(forcedError))
+    }
+  }
+}
+
+/**
+ * Hook to delete many mods in one backend call (single scan + one refetch,
+ * instead of one IPC round-trip and cache write per mod).
+ */
+export function useDeleteMods() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (modIds: string[]) => {
+      const isGameRunning = await checkGameRunning()
+      if (isGameRunning) {
+        throw new Error('Cannot delete mods while Marvel Rivals is running. Please close the game first.')
+      }
+      return await invokeDelete<number>('delete_mods', { modIds })
+    },
+    onSuccess: (count) => {
+      if (count === null) return
+      queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
+      queryClient.invalidateQueries({ queryKey: modKeys.conflicts() })
+      toast.success(`Deleted ${count} mod${count === 1 ? '' : 's'}`)
+    },
+    onError: (error: Error) => {
+      toast.error(error.message)
+    },
+  })
+}
+
+/**
+ * Hook for deleted mods that can still be restored, most recent first.
+ * They're purged 30 days after deletion.
+ */
+export function useTrashedMods() {
+  return useQuery({
+    queryKey: modKeys.trash(),
+    queryFn: async () => {
+      return await invoke<TrashedMod[]>('list_trashed_mods')
+    },
+    staleTime: 30000,
+  })
+}
+
+/**
+ * Hook to put a deleted mod back where it was, metadata and thumbnail
+ * included
+ */
+export function useRestoreMod() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (modId: string) => {
+      return await invoke<ModInfo>('restore_mod', { modId })
+    },
+    onSuccess: (restored) => {
+      queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
+      toast.success(`Restored "${restored.metadata.title || restored.name}"`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to restore mod: ${error}`)
+    },
+  })
+}
+
+/**
+ * Hook to delete every trashed mod for good
+ */
+export function useEmptyTrash() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async () => {
+      return await invoke<number>('empty_trash')
+    },
+    onSuccess: (count) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.trash() })
+      toast.success(`Permanently deleted ${count} mod${count === 1 ? '' : 's'}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to empty the trash: ${error}`)
+    },
+  })
+}
+
+/**
+ * Hook to toggle mod enabled/disabled state
+ */
+export function useToggleModEnabled() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (modId: string) => {
+      const isGameRunning = await checkGameRunning()
+      if (isGameRunning) {
+        throw new Error('Cannot enable/disable mods while Marvel Rivals is running. Please close the game first.')
+      }
+
+      const mods = queryClient.getQueryData<ModInfo[]>(MODS_QUERY_KEY)
+      const mod = mods?.find((m) => m.id === modId)
+
+      if (!mod) {
+        throw new Error('Mod not found')
+      }
+
+      const newEnabledState = !mod.enabled
+      const modInfo = await invoke<ModInfo>('enable_mod', { modId, enabled: newEnabledState })
+
+      return { modId, enabled: newEnabledState, modInfo }
+    },
+    onSuccess: (data) => {
+      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
+        return oldMods.map(mod => (mod.id === data.modId ? data.modInfo : mod))
+      })
+      queryClient.invalidateQueries({ queryKey: modKeys.conflicts() })
+      toast.success('Mod status updated')
+    },
+    onError: (error: Error) => {
+      toast.error(error.message || 'Failed to toggle mod status')
+      console.error('[useToggleModEnabled] Error:', error)
+    },
+  })
+}
+
+/**
+ * Hook to toggle mod favorite status
+ */
+export function useToggleFavorite() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (modId: string) => {
+      return await invoke<FavoriteToggle>('toggle_favorite', { modId })
+    },
+    onSuccess: ({ modInfo: updatedMod }) => {
+      const action = updatedMod.isFavorite ? 'added to' : 'removed from'
+      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
+        return oldMods.map(mod =>
+          mod.id === updatedMod.id ? updatedMod : mod
+        )
+      })
+      queryClient.invalidateQueries({ queryKey: modKeys.favorites() })
+      toast.success(`Mod ${action} favorites`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to toggle favorite: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook for favorite mods only, sorted by character then title
+ */
+export function useFavorites() {
+  return useQuery({
+    queryKey: modKeys.favorites(),
+    queryFn: async () => {
+      return await invoke<ModInfo[]>('get_favorites')
+    },
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * What the last full scan visited and how long each part took; null before
+ * the first scan
+ */
+export function useScanProfile() {
+  return useQuery({
+    queryKey: modKeys.scanProfile(),
+    queryFn: async () => {
+      return await invoke<ScanProfile | null>('get_scan_profile')
+    },
+    refetchOnWindowFocus: false,
+  })
+}
+
+/**
+ * p50/p95 timings of recent scans, installs, toggles and the like, with
+ * the last scan's profile
+ */
+export function usePerformanceMetrics() {
+  return useQuery({
+    queryKey: modKeys.performance(),
+    queryFn: async () => {
+      return await invoke<PerformanceMetrics>('get_performance_metrics')
+    },
+    refetchOnWindowFocus: false,
+  })
+}
+
+/**
+ * Hook to update mod metadata
+ */
+export function useUpdateModMetadata() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ modId, metadata }: { modId: string; metadata: ModMetadata }) => {
+      try {
+        const updatedMod = await invoke<ModInfo>('update_mod_metadata', { modId, metadata })
+        return { oldModId: modId, updatedMod }
+      } catch (error) {
+        const rejection = error as MetadataUpdateError
+        if (rejection.code === 'metadataConflict') {
+          // Another window saved first; pick up its version before retrying
+          queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
+          throw new Error('This mod was changed in another window. Review the latest version and save again.')
+        }
+        if (rejection.code === 'invalid') {
+          throw new Error(rejection.issues.map(issue => issue.message).join('\n'))
+        }
+        throw new Error(rejection.code === 'failed' ? rejection.message : String(error))
+      }
+    },
+    onSuccess: ({ oldModId, updatedMod }) => {
+      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
+        if (oldModId === updatedMod.id) {
+          return oldMods.map(mod =>
+            mod.id === updatedMod.id ? updatedMod : mod
+          )
+        } else {
+          return oldMods
+            .filter(mod => mod.id !== oldModId)
+            .concat(updatedMod)
+        }
+      })
+      toast.success('Mod metadata updated successfully')
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to update metadata: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to change individual metadata fields. Applied to whatever is stored,
+ * so unlike a full update it never conflicts with edits from other windows.
+ */
+/**
+ * A mod's metadata as pretty-printed JSON, for the raw editor
+ */
+export function useModMetadataRaw(modId: string | null) {
+  return useQuery({
+    queryKey: modKeys.metadataRaw(modId ?? ''),
+    queryFn: async () => {
+      return await invoke<string>('get_mod_metadata_raw', { modId })
+    },
+    enabled: !!modId,
+    refetchOnWindowFocus: false,
+  })
+}
+
+/**
+ * Save hand-edited metadata JSON. Rejects with the backend's
+ * MetadataUpdateError so the editor can point at the line or field at fault.
+ */
+export function useSetModMetadataRaw() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ modId, json }: { modId: string; json: string }) => {
+      return await invoke<ModInfo>('set_mod_metadata_raw', { modId, json })
+    },
+    onSuccess: (updatedMod, { modId }) => {
+      // A new title can move the mod, which changes its ID
+      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) =>
+        modId === updatedMod.id
+          ? oldMods.map(mod => (mod.id === updatedMod.id ? updatedMod : mod))
+          : oldMods.filter(mod => mod.id !== modId).concat(updatedMod)
+      )
+      queryClient.invalidateQueries({ queryKey: modKeys.metadataRaw(updatedMod.id) })
+      toast.success('Metadata saved')
+    },
+  })
+}
+
+export function usePatchModMetadata() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ modId, patch }: { modId: string; patch: Partial<ModMetadata> }) => {
+      return await invoke<ModInfo>('patch_mod_metadata', { modId, patch })
+    },
+    onSuccess: (updatedMod) => {
+      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
+        return oldMods.map(mod =>
+          mod.id === updatedMod.id ? updatedMod : mod
+        )
+      })
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to update metadata: ${error}`)
+    },
+  })
+}
+
+/**
+ * Hook to remove a profile from all mods
+ */
+export function useRemoveProfileFromAllMods() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (profileId: string) => {
+      return await invoke<number>('remove_profile_from_all_mods', { profileId })
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to remove profile: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to move every mod in one category (optionally filtered) to another
+ */
+export function useReassignCategory() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ from, to, filter }: { from: ModCategory; to: ModCategory; filter?: ModQuery }) => {
+      return await invoke<number>('reassign_category', { from, to, filter: filter ?? null })
+    },
+    onSuccess: (count, { to }) => {
+      queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
+      toast.success(`Moved ${count} mod${count === 1 ? '' : 's'} to ${to}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to reassign category: ${error.message}`)
+    },
+  })
+}
+
+export interface FindReplaceMetadataArgs {
+  field: MetadataTextField
+  find: string
+  replace: string
+  filter?: ModQuery
+  regex?: boolean
+  dryRun?: boolean
+}
+
+/**
+ * Hook to find-and-replace a metadata field across the library. With dryRun
+ * it only returns the before/after pairs.
+ */
+export function useFindReplaceMetadata() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (args: FindReplaceMetadataArgs) => {
+      return await invoke<MetadataReplacement[]>('find_replace_metadata', { ...args, filter: args.filter ?? null })
+    },
+    onSuccess: (changes, { dryRun }) => {
+      if (dryRun) return
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      toast.success(`Updated ${changes.length} mod${changes.length === 1 ? '' : 's'}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Find and replace failed: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to get mods whose folder no longer matches their metadata
+ */
+export function useLayoutDrift() {
+  return useQuery({
+    queryKey: modKeys.layoutDrift(),
+    queryFn: async () => {
+      return await invoke<LayoutDrift[]>('get_layout_drift')
+    },
+  })
+}
+
+function assignErrorMessage(error: unknown): string {
+  const rejection = error as AssignCharacterError
+  switch (rejection?.code) {
+    case 'gameRunning': {
+      const titles = rejection.mods.map((m) => m.title)
+      const listed = titles.length > 3 ? `${titles.slice(0, 3).join(', ')} and ${titles.length - 3} more` : titles.join(', ')
+      return `Close Marvel Rivals first: ${listed} ${titles.length === 1 ? 'is' : 'are'} enabled and would have to move`
+    }
+    case 'unknownCostume':
+      return `${rejection.character} has no costume "${rejection.costume}"`
+    case 'failed':
+      return rejection.message
+    default:
+      return String(error)
+  }
+}
+
+/**
+ * Hook to set the character (and costume, or none) of many mods at once.
+ * Enabled mods move into the character's folder, so each comes back with
+ * the ID it had before for callers keeping a selection.
+ */
+export function useAssignCharacter() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ modIds, character, costume }: { modIds: string[]; character: Character; costume: string | null }) => {
+      try {
+        return await invoke<ToggledMod[]>('assign_character', { modIds, character, costume })
+      } catch (error) {
+        throw new Error(assignErrorMessage(error))
+      }
+    },
+    onSuccess: (assigned, { character }) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      toast.success(`Set ${assigned.length} mod${assigned.length === 1 ? '' : 's'} to ${character}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to set character: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to move drifted mods to the folder their metadata implies
+ */
+export function useApplyLayout() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (modIds: string[]) => {
+      return await invoke<ToggledMod[]>('apply_layout', { modIds })
+    },
+    onSuccess: (moved) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      toast.success(`Moved ${moved.length} mod${moved.length === 1 ? '' : 's'}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to move mods: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook for the moves organizing would make of the loose mods, with the
+ * category and character each is filed under and how sure detection was
+ */
+export function useOrganizePreview(enabled = true) {
+  return useQuery({
+    queryKey: modKeys.organizePreview(),
+    queryFn: async () => {
+      return await invoke<OrganizeMove[]>('preview_organize_mods')
+    },
+    enabled,
+  })
+}
+
+/**
+ * Hook to file loose mods into their folders: every one, or only the
+ * approved moves from useOrganizePreview. Mods that fail to move are
+ * listed in the result's errors and the rest are still organized.
+ */
+export function useOrganizeMods() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (approved?: OrganizeMove[]) => {
+      if (await checkGameRunning()) {
+        throw new Error('Cannot organize mods while Marvel Rivals is running. Please close the game first.')
+      }
+      return await invoke<OrganizationResult>('organize_mods', { approved })
+    },
+    onSuccess: ({ movedMods, errorCount }) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      if (errorCount > 0) {
+        toast.warning(`Organized ${movedMods} mod${movedMods === 1 ? '' : 's'}, ${errorCount} could not be moved`)
+      } else {
+        toast.success(`Organized ${movedMods} mod${movedMods === 1 ? '' : 's'}`)
+      }
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to organize mods: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook for paks sharing a folder that look like variants of one mod, to
+ * suggest grouping them
+ */
+export function useVariantGroups() {
+  return useQuery({
+    queryKey: modKeys.variantGroups(),
+    queryFn: async () => {
+      return await invoke<VariantGroup[]>('detect_variant_groups')
+    },
+  })
+}
+
+/**
+ * Hook to group mods as variants of one mod, or ungroup them with no name
+ */
+export function useGroupModsAsVariants() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ modIds, groupName }: { modIds: string[]; groupName: string | null }) => {
+      return await invoke<ModInfo[]>('group_mods_as_variants', { modIds, groupName })
+    },
+    onSuccess: (mods, { groupName }) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      toast.success(groupName ? `Grouped ${mods.length} mods as ${groupName} variants` : `Ungrouped ${mods.length} mod${mods.length === 1 ? '' : 's'}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to group mods: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to write the mod list to a file for sharing. Resolves to the
+ * document written, so it can be copied as well.
+ */
+export function useExportModList() {
+  return useMutation({
+    mutationFn: async ({ format, includeDisabled, destPath }: { format: ModListFormat; includeDisabled: boolean; destPath: string }) => {
+      return await invoke<string>('export_mod_list', { format, includeDisabled, destPath })
+    },
+    onSuccess: (_, { destPath }) => {
+      toast.success(`Mod list saved to ${destPath}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to export mod list: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to check a shared mod list (exported as JSON) against the library.
+ * With `apply`, the listed mods that are installed but disabled are enabled.
+ */
+export function useImportModList() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ json, apply }: { json: string; apply: boolean }) => {
+      if (apply && await checkGameRunning()) {
+        throw new Error('Cannot enable mods while Marvel Rivals is running. Please close the game first.')
+      }
+      return await invoke<ModListImportReport>('import_mod_list', { json, apply })
+    },
+    onSuccess: (report, { apply }) => {
+      if (!apply) return
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      if (report.enabled.length > 0) {
+        toast.success(`Enabled ${report.enabled.length} mod${report.enabled.length === 1 ? '' : 's'} from the list`)
+      }
+      if (report.errors.length > 0) {
+        toast.error(`${report.errors.length} mod${report.errors.length === 1 ? '' : 's'} could not be enabled`)
+      }
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to import mod list: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to keep drifted mods where they are, so metadata edits stop moving them
+ */
+export function useAcceptLayout() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (modIds: string[]) => {
+      return await invoke<number>('accept_layout', { modIds })
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to keep mod folders: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to switch between moving disabled mods to disabled-mods and renaming
+ * them in place, converting the mods already disabled. The setting is saved
+ * even if some mods fail; those keep working where they are.
+ */
+export function useConvertDisableStrategy() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (strategy: DisableStrategy) => {
+      return await invoke<DisableStrategyReport>('convert_disable_strategy', { strategy })
+    },
+    onSuccess: (report) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      const failed = report.mods.filter((m) => m.error).length
+      if (failed > 0) {
+        toast.warning(`Converted ${report.converted} disabled mod(s), ${failed} failed`)
+      } else {
+        toast.success(`Converted ${report.converted} disabled mod(s)`)
+      }
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to change how mods are disabled: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to leave a folder under ~mods alone (glob patterns allowed). Resolves
+ * to the updated list; settings listeners pick it up from `settings-changed`.
+ */
+export function useAddIgnoredPath() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (pattern: string) => {
+      return await invoke<string[]>('add_ignored_path', { pattern })
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to ignore folder: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to manage a previously ignored folder again
+ */
+export function useRemoveIgnoredPath() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (pattern: string) => {
+      return await invoke<string[]>('remove_ignored_path', { pattern })
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to stop ignoring folder: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to list mod downloads that haven't finished, polled while one runs
+ */
+export function useDownloads() {
+  return useQuery({
+    queryKey: modKeys.downloads(),
+    queryFn: async () => {
+      return await invoke<DownloadRecord[]>('list_downloads')
+    },
+    refetchInterval: (query) =>
+      query.state.data?.some((d) => d.state === 'downloading') ? 1000 : false,
+  })
+}
+
+function useDownloadAction(command: string, failure: string) {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (id: string) => {
+      return await invoke<void>(command, { id })
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.downloads() })
+    },
+    onError: (error: Error) => {
+      toast.error(`${failure}: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to pause a download, keeping what it has so far
+ */
+export function usePauseDownload() {
+  return useDownloadAction('pause_download', 'Failed to pause download')
+}
+
+/**
+ * Hook to continue a paused or failed download in the background
+ */
+export function useResumeDownload() {
+  return useDownloadAction('resume_download', 'Failed to resume download')
+}
+
+/**
+ * Hook to stop a download and delete its partial file
+ */
+export function useCancelDownload() {
+  return useDownloadAction('cancel_download', 'Failed to cancel download')
+}
+
+/**
+ * Hook to get the mods quarantined after a game update. Refetches when the
+ * backend quarantines or releases mods.
+ */
+export function useQuarantineStatus() {
+  const queryClient = useQueryClient()
+
+  useEffect(() => {
+    const unlisten = listen('quarantine-changed', () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+    })
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [queryClient])
+
+  return useQuery({
+    queryKey: modKeys.quarantine(),
+    queryFn: async () => {
+      return await invoke<QuarantineState>('get_quarantine_status')
+    },
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook to get the mods whose files vanished since they were last listed
+ * (usually a game patch wiping ~mods). Updated by the startup check's
+ * `mods-integrity-report` event.
+ */
+export function useModsIntegrity() {
+  const queryClient = useQueryClient()
+
+  useEffect(() => {
+    const unlisten = listen<ModsIntegrityReport>('mods-integrity-report', (event) => {
+      queryClient.setQueryData(modKeys.modsIntegrity(), event.payload)
+    })
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [queryClient])
+
+  return useQuery({
+    queryKey: modKeys.modsIntegrity(),
+    queryFn: async () => {
+      return await invoke<ModsIntegrityReport>('verify_mods_integrity')
+    },
+    staleTime: Infinity,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook to recreate ~mods and put back the enabled mods that have a copy in
+ * disabled-mods
+ */
+export function useRestoreModsDirectory() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async () => {
+      if (await checkGameRunning()) {
+        throw new Error('Cannot restore mods while Marvel Rivals is running. Please close the game first.')
+      }
+      return await invoke<ModInfo[]>('restore_mods_directory')
+    },
+    onSuccess: (restored) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      toast.success(`Put back ${restored.length} mod${restored.length === 1 ? '' : 's'}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to restore mods: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to drop every cached file hash; they're rebuilt in the background
+ * after the next scan
+ */
+export function useClearHashCache() {
+  return useMutation({
+    mutationFn: async () => {
+      return await invoke<number>('clear_hash_cache')
+    },
+    onSuccess: (count) => {
+      toast.success(`Cleared ${count} cached file hash${count === 1 ? '' : 'es'}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to clear hash cache: ${error}`)
+    },
+  })
+}
+
+/**
+ * Hook for whether read-only mode is on. Refetches when it's switched, from
+ * this window or another.
+ */
+export function useReadOnlyMode() {
+  const queryClient = useQueryClient()
+
+  useEffect(() => {
+    const unlisten = listen<boolean>('read-only-mode-changed', (event) => {
+      queryClient.setQueryData(modKeys.readOnly(), event.payload)
+    })
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [queryClient])
+
+  return useQuery({
+    queryKey: modKeys.readOnly(),
+    queryFn: async () => {
+      return await invoke<boolean>('get_read_only_mode')
+    },
+    staleTime: Infinity,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook to turn read-only mode on or off. Each direction is its own backend
+ * command, so nothing else can switch the protection off.
+ */
+export function useSetReadOnlyMode() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (enabled: boolean) => {
+      return await invoke<boolean>(enabled ? 'enter_read_only_mode' : 'exit_read_only_mode')
+    },
+    onSuccess: (enabled) => {
+      queryClient.setQueryData(modKeys.readOnly(), enabled)
+      toast.success(enabled ? 'Read-only mode on: mods can\'t be changed' : 'Read-only mode off')
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to switch read-only mode: ${error}`)
+    },
+  })
+}
+
+/**
+ * Hook to re-enable quarantined mods: all of them, or a chosen subset
+ */
+export function useReleaseQuarantine() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (mode: QuarantineRelease) => {
+      return await invoke<number>('release_quarantine', { mode })
+    },
+    onSuccess: (count) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      toast.success(`Re-enabled ${count} mod${count === 1 ? '' : 's'}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to release quarantine: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to get the unfinished library repair run, if any, so the wizard can
+ * offer to resume it
+ */
+export function useLibraryRepairCheckpoint() {
+  return useQuery({
+    queryKey: modKeys.libraryRepair(),
+    queryFn: async () => {
+      return await invoke<LibraryRepairCheckpoint | null>('get_library_repair_checkpoint')
+    },
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook to run the library repair wizard's chosen steps. With `dryRun` nothing
+ * changes and the report lists what would; progress arrives as
+ * `library-repair-progress` events.
+ */
+export function useRunLibraryRepair() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ steps, dryRun }: { steps: LibraryRepairStep[]; dryRun: boolean }) => {
+      return await invoke<LibraryRepairReport>('run_library_repair', { steps, dryRun })
+    },
+    onSuccess: (report) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      if (report.dryRun) return
+      const failed = report.steps.find((step) => step.error)
+      if (failed) {
+        toast.error(`Library repair stopped at ${failed.step}: ${failed.error}`)
+      } else {
+        const changes = report.steps.reduce((sum, step) => sum + step.changes, 0)
+        toast.success(`Library repaired (${changes} change${changes === 1 ? '' : 's'})`)
+      }
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to repair library: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Crop a mod's thumbnail again from the original it was made from (see
+ * `get_thumbnail_source_path`); crop coordinates are in that original
+ */
+export async function recropThumbnail(
+  modId: string,
+  cropData: { x: number; y: number; width: number; height: number }
+): Promise<string> {
+  return await invoke<string>('recrop_thumbnail', { modId, cropData })
+}
+
+/**
+ * Hook to import a folder of preview images as thumbnails, matched to mods
+ * by pak file name or title. Existing thumbnails are kept unless
+ * `overwrite` is set. Listen to `thumbnail-import-progress` for progress.
+ */
+export function useImportThumbnailsFromFolder() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ dirPath, matchStrategy, overwrite }: { dirPath: string; matchStrategy: ThumbnailMatchStrategy; overwrite?: boolean }) => {
+      return await invoke<ThumbnailImportReport>('import_thumbnails_from_folder', { dirPath, matchStrategy, overwrite: overwrite ?? false })
+    },
+    onSuccess: (report) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      const leftover = report.unmatched.length > 0 ? `, ${report.unmatched.length} unmatched` : ''
+      toast.success(`Imported ${report.imported.length} thumbnail(s)${leftover}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to import thumbnails: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to delete every kept thumbnail original, freeing their disk space.
+ * Thumbnails stay; they just can't be re-cropped afterwards.
+ */
+export function useClearThumbnailSources() {
+  return useMutation({
+    mutationFn: async () => {
+      return await invoke<number>('clear_thumbnail_sources')
+    },
+    onSuccess: (freed) => {
+      toast.success(`Freed ${(freed / (1024 * 1024)).toFixed(1)} MB of thumbnail originals`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to clear thumbnail originals: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Hook to list what sits in disabled-mods, longest disabled first, without
+ * enabling anything
  */
-export function useModConflicts() {
+export function useDisabledModsOverview() {
   return useQuery({
-    queryKey: modKeys.conflicts(),
+    queryKey: modKeys.disabledOverview(),
     queryFn: async () => {
-      return await invoke<ModConflict[]>('detect_mod_conflicts')
+      return await invoke<DisabledModEntry[]>('get_disabled_mods_overview')
     },
-    staleTime: 60000,
     refetchOnWindowFocus: false,
-    retry: false,
   })
 }
 
 /**
- * Hook to install a mod
+ * Hook to permanently delete mods disabled for at least `olderThanDays`.
+ * With `dryRun` it only reports what would go.
  */
-export function useInstallMod() {
+export function usePurgeDisabledMods() {
   const queryClient = useQueryClient()
 
   return useMutation({
-    mutationFn: async (filePath: string) => {
-      return await invoke<ModInfo>('install_mod', { filePath })
+    mutationFn: async ({ olderThanDays, dryRun }: { olderThanDays: number; dryRun?: boolean }) => {
+      return await invoke<DisabledPurgeReport>('purge_disabled_mods', { olderThanDays, dryRun: dryRun ?? false })
     },
-    onSuccess: (newMod) => {
-      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
-        return [newMod, ...oldMods]
-      })
-      toast.success(`Mod "${newMod.name}" installed successfully`)
+    onSuccess: (report) => {
+      if (report.dryRun) return
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      toast.success(`Deleted ${report.purged.length} disabled mod(s), freeing ${(report.bytesReclaimed / (1024 * 1024)).toFixed(1)} MB`)
     },
     onError: (error: Error) => {
-      toast.error(`Failed to install mod: ${error.message}`)
+      toast.error(`Failed to purge disabled mods: ${error.message}`)
     },
   })
 }
 
 /**
- * Hook to enable/disable a mod
+ * Hook to get cleanup suggestions (duplicates, superseded Nexus files, mods
+ * disabled for `disabledDays`, hidden characters). Nothing is deleted.
  */
-export function useToggleMod() {
+export function useLibraryCleanup(disabledDays = 90) {
+  return useQuery({
+    queryKey: modKeys.cleanup(disabledDays),
+    queryFn: async () => {
+      return await invoke<CleanupAnalysis>('analyze_library_cleanup', { disabledDays })
+    },
+    refetchOnWindowFocus: false,
+  })
+}
+
+/**
+ * Hook to act on the chosen suggestions from useLibraryCleanup. Pass the
+ * same `disabledDays`; suggestions that no longer match are skipped.
+ */
+export function useApplyCleanupSuggestions() {
   const queryClient = useQueryClient()
 
   return useMutation({
-    mutationFn: async ({ modId, enabled }: { modId: string; enabled: boolean }) => {
+    mutationFn: async ({ suggestionIds, disabledDays }: { suggestionIds: string[]; disabledDays: number }) => {
       const isGameRunning = await checkGameRunning()
       if (isGameRunning) {
-        throw new Error('Cannot enable/disable mods while Marvel Rivals is running. Please close the game first.')
+        throw new Error('Cannot delete mods while Marvel Rivals is running. Please close the game first.')
       }
-
-      await invoke('enable_mod', { modId, enabled })
-      return { modId, enabled }
+      return await invokeDelete<CleanupSummary>('apply_cleanup_suggestions', { suggestionIds, disabledDays })
     },
-    onSuccess: (data) => {
-      const action = data.enabled ? 'enabled' : 'disabled'
-      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
-        return oldMods.map(mod =>
-          mod.id === data.modId ? { ...mod, enabled: data.enabled } : mod
-        )
-      })
-      toast.success(`Mod ${action} successfully`)
+    onSuccess: (summary) => {
+      if (summary === null) return
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      const stale = summary.stale.length > 0 ? ` (${summary.stale.length} no longer applied)` : ''
+      toast.success(`Deleted ${summary.deleted} mod(s), freeing ${(summary.bytesFreed / (1024 * 1024)).toFixed(1)} MB${stale}`)
     },
     onError: (error: Error) => {
-      toast.error(error.message)
+      toast.error(`Failed to clean up library: ${error.message}`)
     },
   })
 }
 
 /**
- * Hook to enable/disable many mods in a single backend call.
- *
- * Bulk operations used to loop on the frontend, paying one IPC round-trip,
- * one game-running check, one toast, and one cache write per mod. This does
- * the whole batch in the backend (one IPC call), checks the game once, and
- * refetches the list a single time. Toggling changes a mod's path-based ID,
- * so we invalidate rather than optimistically patch by id.
+ * Hook to settle a mod found both enabled and disabled: keeps the copy in
+ * `keep` and deletes the other. `modId` may be either copy's id.
  */
-export function useSetModsEnabled() {
+export function useResolveDuplicate() {
   const queryClient = useQueryClient()
 
   return useMutation({
-    mutationFn: async ({ modIds, enabled }: { modIds: string[]; enabled: boolean }) => {
+    mutationFn: async ({ modId, keep }: { modId: string; keep: ModLocation }) => {
       const isGameRunning = await checkGameRunning()
       if (isGameRunning) {
-        throw new Error('Cannot enable/disable mods while Marvel Rivals is running. Please close the game first.')
+        throw new Error('Cannot delete mods while Marvel Rivals is running. Please close the game first.')
       }
-
-      const count = await invoke<number>('set_mods_enabled', { modIds, enabled })
-      return { count, enabled }
+      return await invoke<ModInfo>('resolve_duplicate', { modId, keep })
     },
-    onSuccess: () => {
-      queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
-      queryClient.invalidateQueries({ queryKey: modKeys.conflicts() })
+    onSuccess: (kept) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      toast.success(`Kept the ${kept.enabled ? 'enabled' : 'disabled'} copy of ${kept.metadata.title || kept.name}`)
     },
     onError: (error: Error) => {
-      toast.error(error.message)
+      toast.error(`Failed to resolve duplicate: ${error.message}`)
     },
   })
 }
 
 /**
- * Hook to check if the game is running
+ * Hook for mods installed more than once under different IDs, grouped by
+ * their identical pak. Only paks that changed since the last run are read.
  */
-async function checkGameRunning(): Promise<boolean> {
-  try {
-    const isRunning = await invoke<boolean>('is_game_running')
-    return isRunning
-  } catch {
-    return false
-  }
+export function useDuplicateMods() {
+  return useQuery({
+    queryKey: modKeys.duplicateMods(),
+    queryFn: async () => {
+      return await invoke<DuplicateModGroup[]>('find_duplicate_mods')
+    },
+    staleTime: 60000,
+    refetchOnWindowFocus: false,
+  })
 }
 
 /**
- * Hook to delete a mod
+ * Hook to keep one copy of a duplicate group. The others go to the trash
+ * once their description, Nexus IDs, thumbnail and memberships are filled
+ * into the kept copy.
  */
-export function useDeleteMod() {
+export function useResolveDuplicateMods() {
   const queryClient = useQueryClient()
 
   return useMutation({
-    mutationFn: async (modId: string) => {
+    mutationFn: async ({ keepModId, removeModIds }: { keepModId: string; removeModIds: string[] }) => {
       const isGameRunning = await checkGameRunning()
       if (isGameRunning) {
         throw new Error('Cannot delete mods while Marvel Rivals is running. Please close the game first.')
       }
-
-      await invoke('delete_mod', { modId })
-      return modId
+      return await invoke<ModInfo>('resolve_duplicate_mods', { keepModId, removeModIds })
     },
-    onSuccess: (modId) => {
-      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
-        return oldMods.filter(mod => mod.id !== modId)
-      })
-      toast.success('Mod deleted successfully')
+    onSuccess: (kept) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      toast.success(`Kept ${kept.metadata.title || kept.name}, extra copies moved to the trash`)
     },
     onError: (error: Error) => {
-      toast.error(error.message)
+      toast.error(`Failed to merge duplicates: ${error.message}`)
     },
   })
 }
 
 /**
- * Hook to delete many mods in one backend call (single scan + one refetch,
- * instead of one IPC round-trip and cache write per mod).
+ * Hook to put a mod at a place in the load order, or clear it with null.
+ * Renames its files, so the mod comes back with a new ID.
  */
-export function useDeleteMods() {
+export function useSetModPriority() {
   const queryClient = useQueryClient()
 
   return useMutation({
-    mutationFn: async (modIds: string[]) => {
+    mutationFn: async ({ modId, priority }: { modId: string; priority: number | null }) => {
       const isGameRunning = await checkGameRunning()
       if (isGameRunning) {
-        throw new Error('Cannot delete mods while Marvel Rivals is running. Please close the game first.')
+        throw new Error('Cannot change the load order while Marvel Rivals is running. Please close the game first.')
       }
-      return await invoke<number>('delete_mods', { modIds })
+      return await invoke<ModInfo>('set_mod_priority', { modId, priority })
     },
-    onSuccess: (count) => {
-      queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
-      queryClient.invalidateQueries({ queryKey: modKeys.conflicts() })
-      toast.success(`Deleted ${count} mod${count === 1 ? '' : 's'}`)
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
     },
     onError: (error: Error) => {
-      toast.error(error.message)
+      toast.error(`Failed to change the load order: ${error.message}`)
     },
   })
 }
 
 /**
- * Hook to toggle mod enabled/disabled state
+ * Hook to load mods in the given order, first first
  */
-export function useToggleModEnabled() {
+export function useReorderMods() {
   const queryClient = useQueryClient()
 
   return useMutation({
-    mutationFn: async (modId: string) => {
+    mutationFn: async (modIdsInOrder: string[]) => {
       const isGameRunning = await checkGameRunning()
       if (isGameRunning) {
-        throw new Error('Cannot enable/disable mods while Marvel Rivals is running. Please close the game first.')
+        throw new Error('Cannot change the load order while Marvel Rivals is running. Please close the game first.')
       }
+      return await invoke<ModInfo[]>('reorder_mods', { modIdsInOrder })
+    },
+    onSuccess: (mods) => {
+      queryClient.invalidateQueries({ queryKey: modKeys.all })
+      toast.success(`Load order set for ${mods.length} mod${mods.length === 1 ? '' : 's'}`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to change the load order: ${error.message}`)
+    },
+  })
+}
 
-      const mods = queryClient.getQueryData<ModInfo[]>(MODS_QUERY_KEY)
-      const mod = mods?.find((m) => m.id === modId)
+/**
+ * What applying a profile would move (counts, bytes each way, whether it
+ * copies across drives), for the confirmation step before useApplyProfile
+ */
+export async function previewProfileApply(profileId: string): Promise<TogglePreview> {
+  return await invoke<TogglePreview>('preview_profile_apply', { profileId })
+}
 
-      if (!mod) {
-        throw new Error('Mod not found')
-      }
+/**
+ * What a useSetModsEnabled batch would move, without moving anything
+ */
+export async function previewSetModsEnabled(modIds: string[], enabled: boolean): Promise<TogglePreview> {
+  return await invoke<TogglePreview>('preview_set_mods_enabled', { modIds, enabled })
+}
 
-      const newEnabledState = !mod.enabled
-      await invoke('enable_mod', { modId, enabled: newEnabledState })
+/**
+ * Hook to apply a profile: enables exactly its mods, disables the rest, and
+ * makes it the profile new installs join. `confirmed` is whether the user
+ * accepted previewProfileApply's numbers; without it the backend refuses to
+ * move anything.
+ */
+export function useApplyProfile() {
+  const queryClient = useQueryClient()
 
-      return { modId, enabled: newEnabledState }
+  return useMutation({
+    mutationFn: async ({ profileId, confirmed }: { profileId: string; confirmed: boolean }) => {
+      return await invoke<ProfileApplySummary>('apply_profile', { profileId, confirmed })
     },
-    onSuccess: (data) => {
-      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
-        return oldMods.map(mod =>
-          mod.id === data.modId ? { ...mod, enabled: data.enabled } : mod
-        )
-      })
-      queryClient.invalidateQueries({ queryKey: modKeys.conflicts() })
-      toast.success('Mod status updated')
+    onSuccess: (summary) => {
+      queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
+      const toggled = summary.enabled + summary.disabled
+      toast.success(`Profile applied (${toggled} mod${toggled !== 1 ? 's' : ''} toggled)`)
+      if (summary.failed.length > 0) {
+        toast.error(`${summary.failed.length} mod(s) could not be changed: ${summary.failed[0].reason}`)
+      }
     },
     onError: (error: Error) => {
-      toast.error(error.message || 'Failed to toggle mod status')
-      console.error('[useToggleModEnabled] Error:', error)
+      toast.error(`Failed to apply profile: ${error.message}`)
     },
   })
 }
 
 /**
- * Hook to toggle mod favorite status
+ * Hook to get the profiles saved in the backend, with their member mod IDs
  */
-export function useToggleFavorite() {
+export function useProfiles() {
+  return useQuery({
+    queryKey: modKeys.profiles(),
+    queryFn: () => invoke<ModProfile[]>('list_profiles'),
+    refetchOnWindowFocus: false,
+    retry: false,
+  })
+}
+
+/**
+ * Hook to save a new profile in the backend, optionally adding every
+ * currently enabled mod to it. Pass the ID the UI store keeps its look under.
+ */
+export function useCreateProfile() {
   const queryClient = useQueryClient()
 
   return useMutation({
-    mutationFn: async (modId: string) => {
-      const mods = queryClient.getQueryData<ModInfo[]>(MODS_QUERY_KEY)
-      const mod = mods?.find((m) => m.id === modId)
-
-      if (!mod) {
-        throw new Error('Mod not found')
-      }
-
-      const updatedMetadata: ModMetadata = {
-        ...mod.metadata,
-        isFavorite: !mod.isFavorite,
-      }
-
-      return await invoke<ModInfo>('update_mod_metadata', { modId, metadata: updatedMetadata })
+    mutationFn: async ({ name, profileId, fromCurrentState }: { name: string; profileId?: string; fromCurrentState: boolean }) => {
+      return await invoke<ModProfile>('create_profile', { name, profileId: profileId ?? null, fromCurrentState })
     },
-    onSuccess: (updatedMod) => {
-      const action = updatedMod.isFavorite ? 'added to' : 'removed from'
-      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
-        return oldMods.map(mod =>
-          mod.id === updatedMod.id ? updatedMod : mod
-        )
-      })
-      toast.success(`Mod ${action} favorites`)
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
     },
     onError: (error: Error) => {
-      toast.error(`Failed to toggle favorite: ${error.message}`)
+      toast.error(`Failed to create profile: ${error.message}`)
     },
   })
 }
 
 /**
- * Hook to update mod metadata
+ * Hook to register a new profile holding every current favorite, enabled or
+ * not. Returns the number of mods added.
  */
-export function useUpdateModMetadata() {
+export function useCreateProfileFromFavorites() {
   const queryClient = useQueryClient()
 
   return useMutation({
-    mutationFn: async ({ modId, metadata }: { modId: string; metadata: ModMetadata }) => {
-      const updatedMod = await invoke<ModInfo>('update_mod_metadata', { modId, metadata })
-      return { oldModId: modId, updatedMod }
+    mutationFn: async ({ name, profileId }: { name: string; profileId?: string }) => {
+      return await invoke<ModProfile>('create_profile_from_favorites', { name, profileId: profileId ?? null })
     },
-    onSuccess: ({ oldModId, updatedMod }) => {
-      queryClient.setQueryData<ModInfo[]>(MODS_QUERY_KEY, (oldMods = []) => {
-        if (oldModId === updatedMod.id) {
-          return oldMods.map(mod =>
-            mod.id === updatedMod.id ? updatedMod : mod
-          )
-        } else {
-          return oldMods
-            .filter(mod => mod.id !== oldModId)
-            .concat(updatedMod)
-        }
-      })
-      toast.success('Mod metadata updated successfully')
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
     },
     onError: (error: Error) => {
-      toast.error(`Failed to update metadata: ${error.message}`)
+      toast.error(`Failed to create profile: ${error.message}`)
     },
   })
 }
 
 /**
- * Hook to remove a profile from all mods
+ * Hook to rename a profile saved in the backend
  */
-export function useRemoveProfileFromAllMods() {
+export function useRenameProfile() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async ({ profileId, name }: { profileId: string; name: string }) => {
+      return await invoke<ModProfile>('rename_profile', { profileId, name })
+    },
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: modKeys.profiles() })
+    },
+  })
+}
+
+/**
+ * Hook to delete a profile and take it off every mod in it. Returns the
+ * number of mods updated.
+ */
+export function useDeleteProfile() {
   const queryClient = useQueryClient()
 
   return useMutation({
     mutationFn: async (profileId: string) => {
-      return await invoke<number>('remove_profile_from_all_mods', { profileId })
+      return await invoke<number>('delete_profile', { profileId })
     },
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: MODS_QUERY_KEY })
     },
     onError: (error: Error) => {
-      toast.error(`Failed to remove profile: ${error.message}`)
+      toast.error(`Failed to delete profile: ${error.message}`)
     },
   })
 }
@@ -359,6 +1917,28 @@ export function useGetAllCostumes() {
   })
 }
 
+/**
+ * Hook to reload the costume data after it failed to load, e.g. once a
+ * sync brought a usable copy
+ */
+export function useRetryCostumeInitialization() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async () => {
+      return await invoke<CostumeServiceStatus>('retry_costume_initialization')
+    },
+    onSuccess: (status) => {
+      if (status.state === 'loaded') {
+        queryClient.invalidateQueries({ queryKey: ['costumes'] })
+        toast.success(`Loaded ${status.costumes} costumes`)
+      } else if (status.state === 'failed') {
+        toast.error(`Costume data still failed to load: ${status.error}`)
+      }
+    },
+  })
+}
+
 /**
  * Hook to sync the costume database from GitHub (new skins + icons).
  * Invalidates costume queries when new costumes arrive.
@@ -378,6 +1958,31 @@ export function useSyncCostumes() {
   })
 }
 
+/**
+ * Hook to export costume additions and fixes (relative to the data shipped
+ * with the app) as a patch file for submitting upstream
+ */
+export function useExportCostumeOverrides() {
+  return useMutation({
+    mutationFn: async (destPath: string) => {
+      return await invoke<CostumePatchSummary>('export_costume_overrides', { destPath })
+    },
+    onSuccess: (summary) => {
+      toast.success(`Exported ${summary.added} new and ${summary.changed} changed costume(s)`)
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to export costumes: ${error.message}`)
+    },
+  })
+}
+
+/**
+ * Check a received costume patch for schema errors and id collisions
+ */
+export async function validateCostumeOverrideFile(path: string): Promise<CostumePatchValidation> {
+  return await invoke<CostumePatchValidation>('validate_costume_override_file', { path })
+}
+
 /**
  * Silently sync the costume database once on app startup.
  * New skins pushed to the GitHub repo appear without an app update;
@@ -4,6 +4,8 @@ import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { toast } from 'sonner';
 import { APP_VERSION } from '@/shared/constants';
+import { getBackendInfo } from '@/lib/backend';
+import type { DownloadProgress } from '@/types/mod.types';
 
 // ── NXM URL Parsing ─────────────────────────────────────────────────────────
 
@@ -75,6 +77,12 @@ export function syncNexusApiKeyFromPreferences(nexusApiKey?: string) {
 const NEXUS_API_BASE = 'https://api.nexusmods.com/v1';
 
 async function nexusFetch(endpoint: string, apiKey: string) {
+  // The demo library answers from canned data instead of going online
+  const backend = await getBackendInfo();
+  if (backend?.features.demo) {
+    return invoke('demo_nexus_request', { endpoint });
+  }
+
   const response = await fetch(`${NEXUS_API_BASE}${endpoint}`, {
     headers: {
       'APIKEY': apiKey,
@@ -274,8 +282,11 @@ export function useNxmDeepLink() {
       console.log('[NXM] Download URL:', downloadUrl);
 
       // Listen for real download progress from Rust backend
-      const unlisten = await listen<number>('nexus-download-progress', (event) => {
-        setDownloadProgress(event.payload);
+      const unlisten = await listen<DownloadProgress>('download-progress', (event) => {
+        const { bytes, total } = event.payload;
+        if (total) {
+          setDownloadProgress(Math.floor((bytes * 100) / total));
+        }
       });
 
       // 3. Download via Rust backend with streaming progress
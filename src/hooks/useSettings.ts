@@ -1,11 +1,16 @@
+import { useEffect } from 'react';
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
 import { invoke } from '@tauri-apps/api/core';
-import type { AppSettings } from '../types/mod.types';
+import { listen } from '@tauri-apps/api/event';
+import { toast } from 'sonner';
+import type { AppSettings, DetectedGameDirectory, DiskUsage, NotificationOutcome } from '../types/mod.types';
 
 // ===== Query Keys =====
 export const settingsKeys = {
   all: ['settings'] as const,
   app: () => [...settingsKeys.all, 'app'] as const,
+  modsDirectory: () => [...settingsKeys.all, 'modsDirectory'] as const,
+  diskUsage: () => [...settingsKeys.all, 'diskUsage'] as const,
 };
 
 // ===== Queries =====
@@ -14,6 +19,19 @@ export const settingsKeys = {
  * Get app settings
  */
 export function useGetAppSettings() {
+  const queryClient = useQueryClient();
+
+  // Saves from another window, or edits to settings.json outside the app
+  useEffect(() => {
+    const unlisten = listen<AppSettings>('settings-changed', (event) => {
+      queryClient.setQueryData(settingsKeys.app(), event.payload);
+      queryClient.invalidateQueries({ queryKey: settingsKeys.modsDirectory() });
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [queryClient]);
+
   return useQuery({
     queryKey: settingsKeys.app(),
     queryFn: async () => {
@@ -24,8 +42,50 @@ export function useGetAppSettings() {
   });
 }
 
+/**
+ * The folder mods are actually installed into: the Mod Folder override when
+ * set, otherwise the game's ~mods folder. Null until a game folder is set.
+ */
+export function useEffectiveModsDirectory() {
+  return useQuery({
+    queryKey: settingsKeys.modsDirectory(),
+    queryFn: async () => {
+      return await invoke<string | null>('get_effective_mods_directory');
+    },
+    staleTime: Infinity,
+  });
+}
+
+/**
+ * Free and total space of the mods drive and the app data drive
+ */
+export function useDiskUsage() {
+  return useQuery({
+    queryKey: settingsKeys.diskUsage(),
+    queryFn: async () => {
+      return await invoke<DiskUsage>('get_disk_usage');
+    },
+    staleTime: 60000,
+  });
+}
+
 // ===== Mutations =====
 
+/**
+ * Look for game installs (default Steam folder, uninstall registry, NetEase
+ * launcher config, candidate folder names). Every hit, best guess first.
+ */
+export function useDetectGameDirectories() {
+  return useMutation({
+    mutationFn: async () => {
+      return await invoke<DetectedGameDirectory[]>('detect_game_directories');
+    },
+    onError: (error: Error) => {
+      toast.error(`Failed to detect the game: ${error.message}`);
+    },
+  });
+}
+
 /**
  * Save app settings
  */
@@ -39,6 +99,27 @@ export function useSaveAppSettings() {
     onSuccess: (_, settings) => {
       // Update the cache with new settings
       queryClient.setQueryData(settingsKeys.app(), settings);
+      queryClient.invalidateQueries({ queryKey: settingsKeys.modsDirectory() });
+    },
+  });
+}
+
+/**
+ * Send a test notification and explain what to do if Windows blocks it
+ */
+export function useTestNotification() {
+  return useMutation({
+    mutationFn: async () => {
+      return await invoke<NotificationOutcome>('test_notification');
+    },
+    onSuccess: (outcome) => {
+      if (outcome.status === 'delivered') {
+        toast.success('Test notification sent. If nothing appeared, check Focus Assist / Do Not Disturb.');
+      } else {
+        toast.error(
+          `Notifications appear to be disabled for this app in Windows settings (Settings > System > Notifications). ${outcome.error}`,
+        );
+      }
     },
   });
 }
@@ -3,6 +3,7 @@ import { check, Update } from '@tauri-apps/plugin-updater';
 import { relaunch } from '@tauri-apps/plugin-process';
 import { toast } from 'sonner';
 import { APP_VERSION } from '../shared/constants';
+import { getBackendInfo } from '../lib/backend';
 import { useUIStore, type UpdateInfo } from '../stores/useUIStore';
 
 // Module-level variable to store the update handle
@@ -38,6 +39,16 @@ export function useUpdater(): UseUpdaterReturn {
     setAvailableUpdate(null);
 
     try {
+      // Nothing goes online in demo mode
+      const backend = await getBackendInfo();
+      if (backend?.features.demo) {
+        console.log('[Updater] Demo mode - skipping update check');
+        if (!silent) {
+          toast.info('Update checks are off in demo mode');
+        }
+        return;
+      }
+
       console.log('[Updater] Checking for updates from Tauri plugin...');
       const update = await check();
       console.log('[Updater] Check response:', update);
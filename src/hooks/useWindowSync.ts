@@ -0,0 +1,33 @@
+import { useEffect } from 'react'
+import { useQueryClient } from '@tanstack/react-query'
+import { emit, listen } from '@tauri-apps/api/event'
+import { getCurrentWebviewWindow } from '@tauri-apps/api/webviewWindow'
+import { modKeys } from './useMods'
+
+/**
+ * Keep the mod queries of every open window in step. Metadata edits don't
+ * touch ~mods, so the file watcher won't report them: a successful mutation
+ * here broadcasts `library-changed`, and the other windows refetch.
+ */
+export function useWindowSync() {
+  const queryClient = useQueryClient()
+
+  useEffect(() => {
+    const label = getCurrentWebviewWindow().label
+    const unsubscribe = queryClient.getMutationCache().subscribe((event) => {
+      if (event.type === 'updated' && event.action.type === 'success') {
+        emit('library-changed', { source: label })
+      }
+    })
+    const unlisten = listen<{ source: string }>('library-changed', (event) => {
+      if (event.payload.source !== label) {
+        queryClient.invalidateQueries({ queryKey: modKeys.all })
+      }
+    })
+
+    return () => {
+      unsubscribe()
+      unlisten.then((fn) => fn())
+    }
+  }, [queryClient])
+}
@@ -0,0 +1,71 @@
+import { invoke } from '@tauri-apps/api/core'
+import { toast } from 'sonner'
+import { logger } from '@/lib/logger'
+
+/**
+ * Version handshake with the Rust backend
+ *
+ * After a partial update the webview bundle and the backend binary can come
+ * from different releases. Checking this first lets the UI hide what the
+ * backend doesn't offer, or ask for a restart, instead of failing with
+ * opaque invoke errors.
+ */
+
+/** IPC schema this frontend was built against (`IPC_SCHEMA_VERSION` in Rust) */
+export const IPC_SCHEMA_VERSION = 1
+
+export interface BackendInfo {
+  version: string
+  ipcSchemaVersion: number
+  commands: string[]
+  features: {
+    nexus: boolean
+    pakReader: boolean
+    tray: boolean
+    /** Launched with `--demo`: a generated library, no network */
+    demo: boolean
+  }
+}
+
+let backendInfo: Promise<BackendInfo | null> | null = null
+
+/**
+ * Backend info, fetched once. `null` when the backend predates the handshake.
+ */
+export function getBackendInfo(): Promise<BackendInfo | null> {
+  if (!backendInfo) {
+    backendInfo = invoke<BackendInfo>('get_backend_info').catch((error) => {
+      logger.warn('Backend does not support get_backend_info', { error })
+      return null
+    })
+  }
+  return backendInfo
+}
+
+/**
+ * Whether the running backend registers a command
+ */
+export async function hasCommand(name: string): Promise<boolean> {
+  const info = await getBackendInfo()
+  return info?.commands.includes(name) ?? false
+}
+
+/**
+ * Compare the backend against this frontend and prompt for a restart when
+ * they came from different releases
+ */
+export async function checkBackendCompatibility(): Promise<void> {
+  const info = await getBackendInfo()
+  if (info?.ipcSchemaVersion === IPC_SCHEMA_VERSION) {
+    logger.info('Backend handshake ok', { version: info.version })
+    return
+  }
+
+  logger.warn('Frontend and backend are out of sync', {
+    expected: IPC_SCHEMA_VERSION,
+    backend: info?.ipcSchemaVersion ?? null,
+  })
+  toast.warning('The app was only partly updated. Restart it to finish updating.', {
+    duration: 15000,
+  })
+}
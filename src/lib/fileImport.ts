@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core'
+
+// Matches MAX_CHUNK_SIZE in file_import.rs
+const CHUNK_SIZE = 8 * 1024 * 1024
+
+function toBase64(bytes: Uint8Array): string {
+  let binary = ''
+  // fromCharCode takes its arguments on the stack; keep each call small
+  for (let i = 0; i < bytes.length; i += 0x8000) {
+    binary += String.fromCharCode(...bytes.subarray(i, i + 0x8000))
+  }
+  return btoa(binary)
+}
+
+/**
+ * Send a dropped file that has no usable path on disk (dragged out of a
+ * browser or some archive tools) to the backend in chunks. Resolves to a
+ * temp path the install and extract commands can read.
+ */
+export async function importFileInChunks(file: File, onProgress?: (sent: number, total: number) => void): Promise<string> {
+  const importId = await invoke<string>('begin_file_import', { name: file.name, totalSize: file.size, expectedHash: null })
+  try {
+    for (let offset = 0; offset < file.size; offset += CHUNK_SIZE) {
+      const bytes = new Uint8Array(await file.slice(offset, offset + CHUNK_SIZE).arrayBuffer())
+      const received = await invoke<number>('append_file_chunk', { importId, offset, base64Chunk: toBase64(bytes) })
+      onProgress?.(received, file.size)
+    }
+    return await invoke<string>('finish_file_import', { importId })
+  } catch (error) {
+    await invoke('cancel_file_import', { importId }).catch(() => {})
+    throw error
+  }
+}
@@ -5,6 +5,7 @@
 import { invoke } from '@tauri-apps/api/core'
 import { toast } from 'sonner'
 import { logger } from './logger'
+import type { NotificationOutcome } from '@/types/mod.types'
 
 type NotificationType = 'success' | 'error' | 'info' | 'warning'
 
@@ -47,10 +48,13 @@ export async function notify(
     if (native) {
       // Send native system notification via Tauri
       logger.debug('Sending native notification', { title, message, type })
-      await invoke('send_native_notification', {
+      const outcome = await invoke<NotificationOutcome>('send_native_notification', {
         title,
         body: message,
       })
+      if (outcome.status === 'blocked') {
+        throw new Error(outcome.error)
+      }
     } else {
       // Send in-app toast notification
       logger.debug('Sending toast notification', { title, message, type })
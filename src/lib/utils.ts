@@ -1,6 +1,41 @@
 import { clsx, type ClassValue } from 'clsx'
 import { twMerge } from 'tailwind-merge'
+import type { DisabledReason, OpenFolderError } from '@/types/mod.types'
 
 export function cn(...inputs: ClassValue[]) {
   return twMerge(clsx(inputs))
 }
+
+/**
+ * Message for an error thrown by the folder-opening commands. They reject
+ * with an `OpenFolderError` object rather than a string.
+ */
+export function openFolderErrorMessage(error: unknown, fallback: string): string {
+  if (error && typeof error === 'object' && 'code' in error && 'message' in error) {
+    const { code, message } = error as OpenFolderError
+    return code === 'pathNotFound' ? 'That folder no longer exists on disk' : message
+  }
+  return fallback
+}
+
+/**
+ * Short explanation of why a mod is disabled, for tooltips
+ */
+export function disabledReasonLabel(reason: DisabledReason | null): string | null {
+  switch (reason?.kind) {
+    case 'userAction':
+      return 'Disabled by you'
+    case 'conflictResolution':
+      return reason.with.length > 0
+        ? `Disabled to resolve a conflict with ${reason.with.join(', ')}`
+        : 'Disabled to resolve a conflict'
+    case 'safeMode':
+      return 'Disabled by safe mode'
+    case 'gameUpdateQuarantine':
+      return 'Quarantined after a game update'
+    case 'profile':
+      return 'Disabled by a profile'
+    default:
+      return null
+  }
+}
@@ -56,3 +56,27 @@ export function useSavePreferences() {
     },
   })
 }
+
+/**
+ * Switch the language of the native menu, notifications and backend errors.
+ * The backend picks the closest bundled locale and announces it with
+ * `locale-changed`.
+ */
+export function useSetLocale() {
+  const queryClient = useQueryClient()
+
+  return useMutation({
+    mutationFn: async (locale: string) => {
+      return await invoke<string>('set_locale', { locale })
+    },
+    onSuccess: (locale) => {
+      queryClient.setQueryData<AppPreferences>(
+        preferencesQueryKeys.preferences(),
+        (preferences) => (preferences ? { ...preferences, locale } : preferences)
+      )
+    },
+    onError: (error) => {
+      logger.error('Failed to set locale', { error })
+    },
+  })
+}
@@ -61,7 +61,7 @@ export const ALL_CHARACTERS: Character[] = [
 ];
 
 // Mod categories in display order
-export const MOD_CATEGORIES: ModCategory[] = ['UI', 'Audio', 'Skins', 'Gameplay'];
+export const MOD_CATEGORIES: ModCategory[] = ['UI', 'Audio', 'Skins', 'Gameplay', 'Other'];
 
 // ── Character roles (Season 9 roster) ──
 // Deadpool is triple-role in game; listed under his original Duelist slot.
@@ -49,6 +49,8 @@ export function categoryColor(category: string): string {
       return 'var(--rivals-cat-ui)';
     case 'Gameplay':
       return 'var(--rivals-cat-gameplay)';
+    case 'Other':
+      return 'var(--rivals-cat-other)';
     case 'Skins':
     case 'Skin':
     default:
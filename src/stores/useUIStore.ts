@@ -1,10 +1,29 @@
 import { create } from 'zustand';
 import { persist } from 'zustand/middleware';
-import type { ViewMode, ThemeMode, ModFilters } from '../types/mod.types';
+import type { ViewMode, ThemeMode, ModFilters, SortSpec } from '../types/mod.types';
 import type { Profile } from '../shared/profiles';
 
 export type SortOption = 'date' | 'category' | 'character' | 'name' | 'updated' | 'profile' | 'size';
 
+// Toolbar sort options the backend SortSpec can express. Category and
+// profile sorts are frontend-only and are not saved to preferences.
+const SORT_SPECS: Partial<Record<SortOption, SortSpec>> = {
+  name: { key: 'title', direction: 'asc' },
+  date: { key: 'installDate', direction: 'desc' },
+  updated: { key: 'lastModified', direction: 'desc' },
+  size: { key: 'fileSize', direction: 'desc' },
+  character: { key: 'character', direction: 'asc' },
+};
+
+export function sortOptionToSpec(option: SortOption): SortSpec | null {
+  return SORT_SPECS[option] ?? null;
+}
+
+export function sortSpecToOption(spec: SortSpec): SortOption | null {
+  const match = Object.entries(SORT_SPECS).find(([, s]) => s?.key === spec.key);
+  return match ? (match[0] as SortOption) : null;
+}
+
 // Sidebar character list ordering
 export type CharSortMode = 'az' | 'role' | 'count';
 
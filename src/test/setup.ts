@@ -21,6 +21,14 @@ vi.mock('@tauri-apps/api/event', () => ({
   listen: vi.fn().mockResolvedValue(() => {
     // Mock unlisten function
   }),
+  emit: vi.fn().mockResolvedValue(undefined),
+}))
+
+vi.mock('@tauri-apps/api/webviewWindow', () => ({
+  getCurrentWebviewWindow: vi.fn(() => ({
+    label: 'main',
+    close: vi.fn().mockResolvedValue(undefined),
+  })),
 }))
 
 vi.mock('@tauri-apps/plugin-updater', () => ({
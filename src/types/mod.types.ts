@@ -3,8 +3,10 @@
  * These types match the Rust backend types defined in src-tauri/src/types.rs
  */
 
+import type { DetectedMod } from '../components/ModSelectionDialog';
+
 // ===== Mod Category =====
-export type ModCategory = 'UI' | 'Audio' | 'Skins' | 'Gameplay';
+export type ModCategory = 'UI' | 'Audio' | 'Skins' | 'Gameplay' | 'Other';
 
 // ===== Marvel Rivals Characters =====
 export type Character =
@@ -82,6 +84,47 @@ export interface CostumeSyncResult {
   totalCostumes: number;
 }
 
+// A file dropped on the window, as the backend read it: kind comes from
+// the file's content, not its extension
+export type DroppedFileKind = 'zip' | 'sevenZip' | 'rar' | 'pak' | 'image' | 'unknown';
+
+export interface DroppedFileInfo {
+  path: string;
+  size: number;
+  kind: DroppedFileKind;
+  isArchive: boolean;
+  isPak: boolean;
+}
+
+// A downscaled copy of an image from anywhere on disk, read by the backend.
+// width/height are the original's, for scaling crops back.
+export interface ImagePreview {
+  dataUrl: string;
+  width: number;
+  height: number;
+}
+
+// Whether the costume data loaded; `failed` carries the load error
+export type CostumeServiceStatus =
+  | { state: 'notLoaded' }
+  | { state: 'loaded'; characters: number; costumes: number }
+  | { state: 'failed'; error: string };
+
+// Costume patches: additions/fixes to the shipped costume data, exported for
+// submitting upstream and validated before importing
+export interface CostumePatchSummary {
+  characters: number;
+  added: number;
+  changed: number;
+}
+
+export interface CostumePatchValidation {
+  valid: boolean;
+  errors: string[]; // make the patch unusable
+  warnings: string[]; // e.g. a character this build doesn't know yet
+  summary: CostumePatchSummary | null; // null when the file didn't parse
+}
+
 // ===== Mod Metadata =====
 export interface ModMetadata {
   title: string;
@@ -96,7 +139,7 @@ export interface ModMetadata {
   isFavorite: boolean;
   isNsfw: boolean;
   createdAt: string; // ISO 8601 datetime
-  updatedAt: string; // ISO 8601 datetime
+  updatedAt: string; // ISO 8601 datetime, also the revision edits are checked against
   installDate: string; // ISO 8601 datetime
   profileIds: string[] | null;
   nexusModId: number | null;
@@ -104,6 +147,275 @@ export interface ModMetadata {
   nexusVersion: string | null;
   originalFolderPath: string | null; // Relative path from ~mods root for restore on enable
   parentModId: string | null; // ID of parent mod (for add-on mods)
+  disabledReason?: DisabledReason | null; // Why the mod was last disabled
+  disabledAt?: string | null; // ISO 8601 datetime, set while disabled
+  lastVerifiedGameBuild?: string | null; // Game build it last loaded in without errors
+  manageLayout?: boolean; // False once the user kept the mod's folder as it is
+  fileFingerprint?: FileFingerprint | null; // The files as the manager last saw them
+  externallyModifiedAt?: string | null; // When the files were found changed outside the manager
+  collisionFolder?: string | null; // Subfolder used because another mod's same-named files were in the way
+  priority?: number | null; // Place in the load order (NNN_ top folder prefix), lower loads first
+  notes?: string | null; // The user's own notes on the mod
+  history?: ModHistoryEntry[]; // What happened to the mod, oldest first (last 100 kept)
+  variantGroup?: string | null; // Shared by alternatives of one mod; enable one at a time
+}
+
+// Paks in one folder that look like variants of the same mod
+export interface VariantGroup {
+  name: string;
+  folder: string;
+  modIds: string[];
+}
+
+export type ModListFormat = 'json' | 'csv' | 'markdown';
+
+// A mod list as exported to JSON, for sharing a setup
+export interface ModListExport {
+  exportedAt: string;
+  mods: ModListEntry[];
+}
+
+// One mod in an exported list
+export interface ModListEntry {
+  title: string;
+  character: Character | null;
+  category: ModCategory;
+  author: string | null;
+  version: string | null;
+  nexusModId: number | null;
+  nexusFileId: number | null;
+  enabled: boolean;
+  fileSize: number; // Bytes, .pak plus companions
+  originalFileName: string;
+}
+
+// How an imported list entry was matched to an installed mod, most certain first
+export type ModListMatchKind = 'nexusModId' | 'titleAndCharacter' | 'fileName';
+
+// An imported list entry that is installed here
+export interface ModListMatch {
+  entry: ModListEntry;
+  modId: string;
+  matchedBy: ModListMatchKind;
+}
+
+// An imported list entry with no installed mod, and where to get it
+export interface MissingListedMod {
+  entry: ModListEntry;
+  nexusUrl: string | null;
+}
+
+// An imported mod list set against the library
+export interface ModListImportReport {
+  installed: ModListMatch[];
+  disabled: ModListMatch[]; // Installed but disabled when the list was imported
+  missing: MissingListedMod[];
+  enabled: string[]; // Mods enabled because the import was applied, by new ID
+  errors: string[];
+}
+
+// Free and total space of the volume holding `path`
+export interface VolumeUsage {
+  path: string;
+  freeBytes: number;
+  totalBytes: number;
+}
+
+// Space on the drives the app writes to
+export interface DiskUsage {
+  modsVolume: VolumeUsage | null; // Null until a game folder is set
+  appDataVolume: VolumeUsage;
+}
+
+// A mod listed before whose files are no longer all on disk
+export interface ModIntegrityIssue {
+  modId: string;
+  title: string;
+  enabled: boolean;
+  pakPresent: boolean; // Only companions are gone
+  missingFiles: string[];
+}
+
+// What verify_mods_integrity found gone since the mods were last listed
+export interface ModsIntegrityReport {
+  modsDirectory: string;
+  modsDirectoryMissing: boolean; // ~mods itself was gone, as when a game patch wipes Paks
+  issues: ModIntegrityIssue[];
+  recoverable: string[]; // Enabled mods restore_mods_directory can put back from disabled-mods
+}
+
+export type ModHistoryAction = 'installed' | 'enabled' | 'disabled' | 'updated' | 'renamed' | 'deleted' | 'restored';
+
+// Something the manager did to a mod
+export interface ModHistoryEntry {
+  at: string;
+  action: ModHistoryAction;
+  detail?: string | null;
+}
+
+// Total size, newest modification time and content hash of a mod's files
+export interface FileFingerprint {
+  size: number;
+  modified: string; // ISO 8601 datetime
+  contentHash: string;
+}
+
+// Why a mod is disabled, recorded when it was turned off
+export type DisabledReason =
+  | { kind: 'userAction' }
+  | { kind: 'conflictResolution'; with: string[] }
+  | { kind: 'safeMode' }
+  | { kind: 'gameUpdateQuarantine' }
+  | { kind: 'profile'; profileId: string };
+
+// A problem with one metadata field; `field` is the camelCase ModMetadata key.
+// Errors refuse the write, warnings are adjusted or let through.
+export interface ValidationIssue {
+  field: string;
+  severity: 'error' | 'warning';
+  message: string;
+}
+
+// Rejection from update_mod_metadata; on a conflict `current` is what is stored now
+// Any command that reaches the mods fails with this while the game folder's
+// drive is offline; the typed errors below carry it too
+export type ModServiceError =
+  | { code: 'gameDirectoryUnavailable'; path: string }
+  | { code: 'failed'; message: string };
+
+export type MetadataUpdateError =
+  | { code: 'metadataConflict'; current: ModMetadata }
+  | { code: 'syntax'; message: string; line: number; column: number }
+  | { code: 'invalid'; issues: ValidationIssue[] }
+  | { code: 'gameDirectoryUnavailable'; path: string }
+  | { code: 'failed'; message: string };
+
+// ===== Detection Confidence =====
+// How far an auto-detected category/character can be trusted: a folder named
+// after it (high), several keywords or the full name in the file name
+// (medium), or a single generic or shared keyword (low)
+export type DetectionConfidence = 'low' | 'medium' | 'high';
+
+export interface MetadataSuggestion {
+  category: ModCategory;
+  categoryConfidence: DetectionConfidence;
+  character: Character | null;
+  characterConfidence: DetectionConfidence | null;
+}
+
+/** An installed mod whose character likely came from a weak guess */
+export interface LowConfidenceAssignment {
+  modId: string;
+  title: string;
+  character: Character;
+  fileName: string;
+}
+
+/** The profiles and collections a mod belongs to (IDs of each) */
+export interface ModReferences {
+  modId: string;
+  title: string;
+  profiles: string[];
+  collections: string[];
+}
+
+// Any change refused while read-only mode is on, whatever the command
+export interface ReadOnlyError {
+  code: 'readOnlyMode';
+  action: string;
+}
+
+// delete_mod / delete_mods rejections; retry with force to delete mods in use
+export type DeleteModError =
+  | { code: 'modInUse'; mods: ModReferences[] }
+  | { code: 'gameDirectoryUnavailable'; path: string }
+  | { code: 'failed'; message: string };
+
+// A base game file whose size differs from the first recorded one; null
+// when the file wasn't there
+export interface BaseFileChange {
+  name: string;
+  baselineSize: number | null;
+  currentSize: number | null;
+}
+
+// verify_base_game_untouched: base pakchunk files against their baseline,
+// and any mod files living outside ~mods / disabled-mods
+export interface BaseGameIntegrity {
+  baselineRecordedAt: string;
+  baseFiles: number;
+  changedFiles: BaseFileChange[];
+  filesOutsideModFolders: string[];
+  untouched: boolean;
+}
+
+// A selected mod that's enabled while the game runs, so it can't be moved
+export interface AssignOffender {
+  modId: string;
+  title: string;
+}
+
+// assign_character rejections
+export type AssignCharacterError =
+  | { code: 'gameRunning'; mods: AssignOffender[] }
+  | { code: 'unknownCostume'; character: string; costume: string }
+  | { code: 'gameDirectoryUnavailable'; path: string }
+  | { code: 'failed'; message: string };
+
+export interface ModDeletePlan {
+  modId: string;
+  title: string;
+  files: string[];
+  metadataFile: string | null;
+  thumbnail: string | null;
+  thumbnailSource: string | null;
+}
+
+// A file another mod also lists; kept rather than deleted
+export interface SharedModFile {
+  path: string;
+  modIds: string[];
+}
+
+// Everything delete_mods removes for the same ids, and nothing more
+export interface DeletePreview {
+  mods: ModDeletePlan[];
+  // Folders the delete leaves empty, removed with it
+  folders: string[];
+  sharedFiles: SharedModFile[];
+  missing: string[];
+}
+
+// One installed copy in a DuplicateModGroup
+export interface DuplicateModCopy {
+  modId: string;
+  title: string;
+  filePath: string;
+  pakSize: number;
+  fileSize: number; // .pak + .ucas/.utoc companions
+  enabled: boolean;
+  installDate: string; // ISO 8601 datetime
+}
+
+// Mods under different IDs with byte-identical paks; the suggested keeper first
+export interface DuplicateModGroup {
+  pakHash: string;
+  mods: DuplicateModCopy[];
+}
+
+// One file of a deleted mod, and the name the trash keeps it under
+export interface TrashedFile {
+  originalPath: string;
+  trashedName: string;
+  size: number;
+}
+
+// A deleted mod that restore_mod can still put back
+export interface TrashedMod {
+  modId: string;
+  title: string;
+  deletedAt: string; // ISO 8601 datetime
+  files: TrashedFile[];
 }
 
 // ===== Mod Info =====
@@ -113,17 +425,268 @@ export interface ModInfo {
   category: ModCategory;
   character: Character | null;
   enabled: boolean;
+  location: ModLocation; // Which folder (or in-place state) this copy was found in
+  duplicateOf?: string | null; // Set on the second copy of a mod installed both enabled and disabled
   isFavorite: boolean;
+  disabledReason: DisabledReason | null; // Only set while disabled
   filePath: string;
   thumbnailPath: string | null;
   metadata: ModMetadata;
-  fileSize: number;
+  fileSize: number; // .pak + .ucas/.utoc companions
+  pakSize: number; // .pak alone
+  associatedFileSizes: AssociatedFileSize[];
   installDate: string; // ISO 8601 datetime
   lastModified: string; // ISO 8601 datetime
   originalFileName: string;
   associatedFiles: string[];
 }
 
+export type ModLocation = 'active' | 'disabled';
+
+export interface AssociatedFileSize {
+  path: string;
+  size: number;
+}
+
+/** What an install command actually did */
+export interface InstallResult {
+  modInfo: ModInfo;
+  /** Every file written to ~mods */
+  copiedFiles: AssociatedFileSize[];
+  /** Companions (`X.utoc`, `X.ucas`) the source pak was missing */
+  missingCompanions: string[];
+  /** Likely broken IoStore mods, renamed folders, replaced files */
+  warnings: string[];
+}
+
+// Backend sort order for get_all_mods (mirrors Rust SortSpec)
+export type SortKey = 'title' | 'installDate' | 'lastModified' | 'fileSize' | 'character' | 'enabledFirst' | 'enabledFavoritesFirst';
+export type SortDirection = 'asc' | 'desc';
+
+export interface SortSpec {
+  key: SortKey;
+  direction: SortDirection;
+}
+
+// Error returned by show_in_folder / open_*_folder commands
+export interface OpenFolderError {
+  code: 'pathNotFound' | 'modNotFound' | 'gameDirectoryUnavailable' | 'openFailed';
+  message: string;
+  path: string | null;
+}
+
+// Filter over the mod list; every field that is set must match
+export interface ModQuery {
+  search?: string; // Title, description, tags, author, character or file name; "spider man" finds Spider-Man
+  category?: ModCategory;
+  character?: Character;
+  enabled?: boolean;
+  favorite?: boolean;
+  nsfw?: boolean;
+  disabledReason?: DisabledReason['kind'];
+}
+
+// One page of the mods matching a ModQuery
+export interface ModPage {
+  mods: ModInfo[];
+  // Mods matching the query across all pages
+  total: number;
+}
+
+// What a library grid tile shows of a mod; the full ModInfo comes from get_mod
+export interface ModCard {
+  id: string;
+  title: string;
+  character: Character | null;
+  category: ModCategory;
+  costume: string | null;
+  enabled: boolean;
+  favorite: boolean;
+  nsfw: boolean;
+  thumbnailPath: string | null;
+  // Thumbnail modification time in ms, to bust the image cache on replace
+  thumbnailVersion: number | null;
+  fileSize: number;
+}
+
+export interface ModCardPage {
+  cards: ModCard[];
+  // Mods matching the filter across all pages
+  total: number;
+}
+
+// A mod after a bulk enable/disable; the toggle changes its path-based id
+export interface ToggledMod {
+  previousId: string;
+  modInfo: ModInfo;
+}
+
+// A mod a bulk enable/disable couldn't move
+export interface ToggleFailure {
+  modId: string;
+  reason: string;
+}
+
+export interface BulkToggleReport {
+  toggled: ToggledMod[];
+  failed: ToggleFailure[];
+}
+
+export type MetadataTextField = 'title' | 'description' | 'author' | 'tags';
+
+/** One mod's field before and after a find-and-replace */
+export interface MetadataReplacement {
+  modId: string;
+  name: string;
+  before: string;
+  after: string;
+}
+
+/** A mod whose folder doesn't match its Category/Character/Title layout */
+export interface LayoutDrift {
+  modId: string;
+  name: string;
+  currentFolder: string;
+  expectedFolder: string;
+}
+
+export type DownloadState = 'downloading' | 'paused' | 'failed';
+
+/** A mod archive download that hasn't finished */
+export interface DownloadRecord {
+  id: string;
+  url: string;
+  bytes: number;
+  total?: number | null;
+  sha256?: string | null;
+  state: DownloadState;
+  error?: string | null;
+}
+
+/** Payload of the download-progress event */
+export interface DownloadProgress {
+  id: string;
+  bytes: number;
+  total?: number | null;
+  /** Bytes per second */
+  speed: number;
+}
+
+/** A mod after toggle_favorite, with the new favorites count for badges */
+export interface FavoriteToggle {
+  modInfo: ModInfo;
+  favoritesCount: number;
+}
+
+/** Something about a mod on disk worth knowing, even though the scan worked */
+export interface ScanWarning {
+  modId: string;
+  /** invalidMetadata: a hand-edited metadata file that doesn't parse or validate */
+  kind: 'readOnly' | 'invalidMetadata';
+  paths: string[];
+  message: string;
+}
+
+/** What a bulk enable/disable or profile apply would move */
+export interface TogglePreview {
+  enableCount: number;
+  disableCount: number;
+  bytesToEnable: number; // disabled folder -> ~mods
+  bytesToDisable: number; // ~mods -> disabled folder
+  crossVolume: boolean; // moves are copies between drives
+  peakTemporaryBytes: number; // extra space needed while copying
+}
+
+/** A mod whose pak and .ucas/.utoc ended up in different folders */
+export interface SplitMod {
+  modId: string;
+  name: string;
+  pakPath: string;
+  pakEnabled: boolean;
+  enabledFiles: string[];
+  disabledFiles: string[];
+  autoRepairable: boolean;
+}
+
+export type SplitSide = 'enabled' | 'disabled';
+
+export interface SplitRepairReport {
+  repaired: number;
+  unresolved: SplitMod[];
+}
+
+/** What merging duplicate character folders did with one mod folder */
+export interface FolderMergeEntry {
+  source: string;
+  destination: string; // For duplicateRemoved, the copy that was kept
+  action: 'moved' | 'duplicateRemoved' | 'keptBoth';
+  collidedWith: string | null; // For keptBoth, the same-named folder it collided with
+}
+
+export interface FolderMergeReport {
+  mergedFolders: number;
+  actions: FolderMergeEntry[];
+}
+
+/** A mod an older manager disabled by renaming it `*.pak.disabled` inside ~mods */
+export interface LegacyDisabledMod {
+  modId: string;
+  name: string;
+  files: string[];
+  targetPath: string; // Where the pak goes in disabled-mods
+  error: string | null; // Set when converting this mod failed
+}
+
+export interface LegacyDisabledReport {
+  dryRun: boolean;
+  converted: number;
+  mods: LegacyDisabledMod[];
+}
+
+/** Move disabled mods to disabled-mods, or rename them to `.pakdisabled` where they are */
+export type DisableStrategy = 'move' | 'rename_in_place';
+
+/** A disabled mod moved between the two disabled layouts */
+export interface DisableStrategyConversion {
+  modId: string; // ID before the conversion
+  name: string;
+  files: string[];
+  targetPath: string;
+  error: string | null; // Set when converting this mod failed
+}
+
+export interface DisableStrategyReport {
+  strategy: DisableStrategy;
+  dryRun: boolean;
+  converted: number;
+  mods: DisableStrategyConversion[];
+}
+
+export interface ThumbnailConsolidationReport {
+  migrated: number;
+  unclaimed: string[];
+}
+
+/** What the path-ID migration found for one mod */
+export type IdMigrationOutcome = 'migrated' | 'alreadyMigrated' | 'noOldMetadata';
+
+export interface IdMigrationEntry {
+  modId: string;
+  oldId: string;
+  name: string;
+  outcome: IdMigrationOutcome;
+}
+
+export interface IdMigrationReport {
+  dryRun: boolean;
+  /** Mods finished from the checkpoint of an interrupted earlier run */
+  resumedCount: number;
+  migratedCount: number;
+  alreadyMigratedCount: number;
+  notFoundCount: number;
+  entries: IdMigrationEntry[];
+}
+
 // ===== App Settings =====
 export interface AppSettings {
   gameDirectory: string | null;
@@ -132,8 +695,196 @@ export interface AppSettings {
   autoOrganize: boolean;
   autoDetectGameDir: boolean;
   autoCheckUpdates: boolean;
+  notifications?: NotificationSettings;
+  globalHotkeys?: GlobalHotkeySettings;
+  backgroundRescan?: BackgroundRescanSettings;
+  autoAssignToActiveProfile?: boolean;
+  /** Check Nexus for newer versions of installed mods once a day */
+  autoCheckModUpdates?: boolean;
+  lastModUpdateCheck?: string | null;
+  /** Only affects mods disabled afterwards; `convert_disable_strategy` moves existing ones */
+  disableStrategy?: DisableStrategy;
+  /** Disable every enabled mod when a game update is detected */
+  quarantineOnGameUpdate?: boolean;
+  /** Emit `file-operation` events for the activity feed */
+  fileActivityEvents?: boolean;
+  /** Folders under ~mods the manager leaves alone, relative to it; globs allowed */
+  ignoredPaths?: string[];
+  /** Folder levels below ~mods that scans and cleanup look at; null for all */
+  maxScanDepth?: number | null;
+  /** Refuse every change to the library; only switched by its own commands */
+  readOnlyMode?: boolean;
+  /** Characters the user doesn't play; library cleanup suggests deleting their mods */
+  hiddenCharacters?: Character[];
+  /** Extra folder names game detection looks for, for regional installs */
+  gameFolderNames?: string[];
+  /** How gameDirectory was found; set by detection, or 'manual' when picked */
+  gameDirectorySource?: DetectionSource | null;
+}
+
+/** Where a detected game folder came from */
+export type DetectionSource = 'steamDefault' | 'uninstallRegistry' | 'netEaseLauncher' | 'candidateFolder' | 'manual';
+
+export interface DetectedGameDirectory {
+  path: string;
+  source: DetectionSource;
+}
+
+/** One part of a full library scan: `thumbnails`, `mods` or `disabled` */
+export interface ScanPhase {
+  name: string;
+  files: number;
+  directories: number;
+  durationMs: number;
 }
 
+/** Background hashing of mod files after a scan (`hashing-progress`) */
+export interface HashingProgress {
+  done: number;
+  total: number;
+  // The file being hashed; null once the run is over
+  current: string | null;
+}
+
+/** What the last full scan visited and how long it took */
+export interface ScanProfile {
+  scannedAt: string;
+  maxScanDepth: number | null;
+  files: number;
+  directories: number;
+  durationMs: number;
+  phases: ScanPhase[];
+}
+
+/** Recent timings of one operation, over the runs the backend keeps */
+export interface OperationMetrics {
+  operation: string;
+  samples: number;
+  p50Ms: number;
+  p95Ms: number;
+  maxMs: number;
+  lastMs: number;
+  /** How many things the last run handled */
+  lastItems: number;
+  lastAt: string;
+}
+
+/** Operation timings with the last scan's profile, kept locally */
+export interface PerformanceMetrics {
+  operations: OperationMetrics[];
+  scanProfile: ScanProfile | null;
+}
+
+export type FileOperationKind = 'move' | 'copy' | 'delete' | 'extract';
+
+/** A file the backend moved, copied, deleted or extracted */
+export interface FileOperation {
+  kind: FileOperationKind;
+  source: string;
+  /** Unset for deletes */
+  destination: string | null;
+  bytes: number | null;
+  modId: string | null;
+  at: string;
+}
+
+/** Payload of a `file-operation` event */
+export interface FileOperationBatch {
+  operations: FileOperation[];
+  /** Operations left out because the burst didn't fit in one batch */
+  dropped: number;
+}
+
+/** Mods disabled because the game updated, until the user releases them */
+export interface QuarantineState {
+  active: boolean;
+  modIds: string[];
+  gameBuild: string | null;
+  quarantinedAt: string | null;
+}
+
+/** Re-enable every quarantined mod, or just the listed IDs */
+export type QuarantineRelease = 'all' | string[];
+
+/** Library repair wizard steps, listed in the order they run */
+export type LibraryRepairStep =
+  | 'repairSplitMods'
+  | 'migrateIds'
+  | 'consolidateThumbnails'
+  | 'mergeDuplicateFolders'
+  | 'recoverOrphanedMetadata'
+  | 'cleanEmptyFolders';
+
+/** A repair run that failed or was interrupted, resumed by the next run */
+export interface LibraryRepairCheckpoint {
+  steps: LibraryRepairStep[];
+  completed: LibraryRepairStep[];
+  startedAt: string;
+}
+
+export interface LibraryRepairStepResult {
+  step: LibraryRepairStep;
+  changes: number; // in a dry run, what would change
+  items: string[];
+  resumed: boolean; // done by the earlier run
+  error: string | null;
+}
+
+export interface LibraryRepairReport {
+  dryRun: boolean;
+  steps: LibraryRepairStepResult[];
+  completed: boolean; // false when a step failed and the rest didn't run
+}
+
+export interface LibraryRepairProgress {
+  step: LibraryRepairStep;
+  current: number;
+  total: number;
+  dryRun: boolean;
+}
+
+/** An installed Nexus mod with a newer version available */
+export interface ModUpdate {
+  modId: string;
+  title: string;
+  nexusModId: number;
+  installedVersion: string | null;
+  latestVersion: string;
+}
+
+/** Fallback polling for mod folder changes the file watcher misses */
+export interface BackgroundRescanSettings {
+  enabled: boolean;
+  intervalMinutes: number;
+  runWhenWatcherHealthy: boolean;
+}
+
+/** System-wide shortcuts; toggleSafeMode uses accelerator syntax (e.g. "Ctrl+Shift+M") */
+export interface GlobalHotkeySettings {
+  enabled: boolean;
+  toggleSafeMode: string;
+}
+
+/** Safe mode: every enabled mod disabled at once, restorable as a set */
+export interface SafeModeState {
+  active: boolean;
+  disabledModIds: string[];
+  enteredAt: string | null;
+}
+
+/** Native notifications raised by the backend while the window is unfocused */
+export interface NotificationSettings {
+  onInstallComplete: boolean;
+  onExtractionComplete: boolean;
+  onGameUpdateDetected: boolean;
+  onModUpdateAvailable: boolean;
+}
+
+/** Whether the OS accepted a native notification */
+export type NotificationOutcome =
+  | { status: 'delivered' }
+  | { status: 'blocked'; error: string };
+
 // ===== Progress Types =====
 export interface ModInstallProgress {
   currentFile: string;
@@ -152,6 +903,18 @@ export interface ModOrganizationProgress {
   errors: string[];
 }
 
+/** Where organizing would move a loose mod, and why it's going there */
+export interface OrganizeMove {
+  modId: string;
+  title: string;
+  sourcePath: string;
+  destinationPath: string;
+  category: ModCategory;
+  character: Character | null;
+  /** Lower of the category and character detection; low when the metadata disagrees with it */
+  confidence: DetectionConfidence;
+}
+
 export interface OrganizationResult {
   totalMods: number;
   movedMods: number;
@@ -165,6 +928,8 @@ export interface ExtractionProgress {
   current: number;
   total: number;
   bytesExtracted: number;
+  /** Uncompressed size of the whole archive, from its headers */
+  bytesTotal: number;
 }
 
 // ===== Statistics Types =====
@@ -183,12 +948,44 @@ export interface CategoryStats {
 }
 
 export interface CharacterStats {
-  character: Character;
+  character: Character | null; // null: mods not assigned to a character
   count: number;
   enabled: number;
   disabled: number;
 }
 
+/** A count from a subsystem's last run; `stale` once the library moved on */
+export interface CachedCount {
+  count: number;
+  stale: boolean;
+}
+
+/** Everything the dashboard shows, from get_library_summary */
+export interface LibrarySummary extends AppStats {
+  categories: CategoryStats[];
+  topCharacters: CharacterStats[];
+  missingThumbnails: number;
+  conflicts: CachedCount;
+  pendingUpdates: CachedCount;
+  lastInstall: string | null;
+  /** Ignored folders that still hold paks: the game loads them, the library doesn't list them */
+  ignoredFoldersWithPaks: number;
+  /** Mods installed both enabled and disabled; both copies are listed until one is kept */
+  unresolvedDuplicates: number;
+  /** Game folder missing (e.g. unplugged drive); counts are from the last saved listing */
+  offline: boolean;
+}
+
+// ===== Game Directory Types =====
+export interface GameDirectoryStatus {
+  configured: boolean;
+  /** False when a game folder is configured but can't be found */
+  available: boolean;
+  path: string | null;
+  /** When the listing shown while offline was taken */
+  snapshotSavedAt: string | null;
+}
+
 // ===== Filter Types =====
 export interface ModFilters {
   search: string;
@@ -219,10 +1016,198 @@ export interface ConflictMod {
   id: string;
   title: string;
   wins: boolean; // loads first, so it wins the shared assets
+  enabled: boolean; // disabled mods only appear with includeDisabled
 }
 
+// 'full' when every asset of one of the mods is shared, so the loser does nothing
+export type ConflictSeverity = 'full' | 'partial';
+
 export interface ModConflict {
   mods: ConflictMod[];
   kinds: string[]; // e.g. ["Body Mesh", "Body Skin"]
   assets: string[]; // raw asset stems for the detail view
+  severity: ConflictSeverity;
+}
+
+// ===== Profiles =====
+// A profile as the backend keeps it; color and icon stay in the UI store.
+// Members come from each mod's profileIds, so they follow moved mods.
+export interface ModProfile {
+  id: string;
+  name: string;
+  modIds: string[]; // by title
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface ProfileApplySummary {
+  enabled: number;
+  disabled: number;
+  failed: ToggleFailure[];
+}
+
+// ===== Collections =====
+// A named, ordered set of mods kept together regardless of enablement.
+// Member IDs follow mods across enable/disable and renames.
+export interface ModCollection {
+  id: string;
+  name: string;
+  description: string | null;
+  modIds: string[]; // presentation order
+  createdAt: string;
+  updatedAt: string;
+}
+
+// ===== Pak Verification =====
+// Raw header values of a mod's pak and .utoc, for working out why a mod
+// installs but doesn't load. Mount points come from the file; the common
+// one is what most enabled mods in the library share.
+export interface PakTechnicalInfo {
+  modId: string;
+  pakPath: string;
+  pakVersion: number | null;
+  pakMountPoint: string | null; // null when the pak index is encrypted
+  utocPath: string | null;
+  tocVersion: number | null;
+  utocMountPoint: string | null;
+  commonMountPoint: string | null;
+  mountPointOutlier: boolean;
+  errors: string[];
+}
+
+export type ModVerificationKind = 'possiblyOutdated';
+
+export interface ModVerificationIssue {
+  modId: string;
+  title: string;
+  kind: ModVerificationKind;
+  message: string;
+  info: PakTechnicalInfo;
+}
+
+// Whether an installed mod's pak would load
+export interface PakValidation {
+  modId: string;
+  title: string;
+  pakPath: string;
+  error: string | null; // Why the pak is corrupted or incomplete
+  warnings: string[]; // Companions that don't pair up, e.g. a .utoc without its .ucas
+}
+
+// ===== Storage Breakdown =====
+// Disk usage grouped category -> character -> costume for the treemap.
+// Sizes include .ucas/.utoc companions.
+export interface StorageNode {
+  name: string;
+  totalBytes: number;
+  enabledBytes: number;
+  disabledBytes: number;
+  modCount: number;
+  children: StorageNode[];
+}
+
+export interface StorageBreakdown {
+  totalBytes: number;
+  enabledBytes: number;
+  disabledBytes: number;
+  modCount: number;
+  categories: StorageNode[];
+  /** Uncropped thumbnail originals kept for re-cropping, outside the totals */
+  thumbnailSourceCount: number;
+  thumbnailSourceBytes: number;
+}
+
+// ===== Thumbnail Import =====
+export type ThumbnailMatchStrategy = 'filename' | 'title';
+
+export interface ThumbnailImportMatch {
+  imagePath: string;
+  modId: string;
+  title: string;
+  error: string | null; // Set for entries in `failed`
+}
+
+export interface ThumbnailImportReport {
+  imported: ThumbnailImportMatch[];
+  skipped: ThumbnailImportMatch[]; // Mod already had a thumbnail and overwrite was off
+  failed: ThumbnailImportMatch[];
+  unmatched: string[]; // Images that matched no mod, or several, for assigning by hand
+}
+
+export interface ThumbnailImportProgress {
+  current: number;
+  total: number;
+}
+
+/** One mod (or stray set of files) sitting in disabled-mods */
+export interface DisabledModEntry {
+  modId: string | null; // null for .ucas/.utoc files left without their pak
+  title: string;
+  files: string[];
+  size: number;
+  disabledSince: string | null; // ISO 8601 datetime
+  disabledSinceEstimated: boolean; // From the metadata file's write time; may look more recent than it is
+  disabledDays: number | null;
+  disabledReason: DisabledReason | null;
+  hasMetadata: boolean;
+}
+
+export interface DisabledPurgeReport {
+  dryRun: boolean;
+  purged: DisabledModEntry[];
+  bytesReclaimed: number;
+}
+
+// ===== Library Cleanup =====
+export type CleanupKind = 'exactDuplicate' | 'superseded' | 'longDisabled' | 'hiddenCharacter';
+
+export interface CleanupSuggestion {
+  id: string; // Stops matching once the library changes under it
+  kind: CleanupKind;
+  action: 'delete' | 'keepNewest';
+  modIds: string[]; // Deleted when applied
+  keep: string[]; // Kept in their place, for duplicates and superseded files
+  reason: string;
+  bytesSaved: number;
+}
+
+export interface CleanupAnalysis {
+  suggestions: CleanupSuggestion[];
+  bytesSaved: number; // Every suggestion applied; a mod in several counts once
+}
+
+export interface CleanupSummary {
+  applied: number;
+  stale: string[]; // Requested suggestions that no longer matched
+  deleted: number;
+  bytesFreed: number;
+}
+
+// ===== Game Log Analysis =====
+// A mod the game failed to mount last session, found in Marvel.log.
+export interface GameLogIssue {
+  modId: string;
+  modTitle: string;
+  pakFile: string;
+  logLine: string;
+  timestamp: string | null; // Unreal log format, e.g. "2025.01.31-18.04.12"
+}
+
+// ===== Archive Preview =====
+// Archive contents read from its index without extracting. Paths are inside
+// the archive and '/'-separated.
+export interface ArchiveTreeNode {
+  name: string;
+  path: string;
+  size: number; // folders: total of everything below
+  isDirectory: boolean;
+  children: ArchiveTreeNode[];
+}
+
+export interface ArchivePreview {
+  entries: ArchiveTreeNode[];
+  fileCount: number;
+  totalSize: number;
+  detectedMods: DetectedMod[];
+  previewPartial: boolean; // some entries unreadable or nested archives present
 }
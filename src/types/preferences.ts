@@ -1,5 +1,6 @@
 // Types that match the Rust AppPreferences struct
 // Only contains settings that should be persisted to disk
+import type { SortSpec } from './mod.types'
 
 // Background intensity options for customizing darkness level
 export type BackgroundIntensity = 'normal' | 'dim' | 'black'
@@ -11,6 +12,8 @@ export interface AppPreferences {
   cardTilt?: boolean // 3D tilt-toward-cursor on mod cards (default on)
   cardGlow?: boolean // dominant-color cursor glow on mod cards (default on)
   nexusApiKey?: string
+  modSort?: SortSpec // last library sort, restored on launch
+  locale?: string // backend text language; changed through useSetLocale, not save_preferences
 }
 
 export const defaultPreferences: AppPreferences = {